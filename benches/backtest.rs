@@ -107,7 +107,7 @@ fn bench_btc_daily(c: &mut Criterion) {
     group.bench_function("sma_20_50_crossover", |b| {
         b.iter(|| {
             let mut strat = SmaCrossover::new(20, 50);
-            black_box(bt.run(&candles, &mut strat));
+            black_box(bt.run(&candles, &mut strat).unwrap());
         })
     });
     group.finish();
@@ -122,7 +122,7 @@ fn bench_synthetic_1m(c: &mut Criterion) {
     group.bench_function("sma_20_50_crossover", |b| {
         b.iter(|| {
             let mut strat = SmaCrossover::new(20, 50);
-            black_box(bt.run(&candles, &mut strat));
+            black_box(bt.run(&candles, &mut strat).unwrap());
         })
     });
     group.finish();
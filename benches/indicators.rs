@@ -7,6 +7,7 @@
 //! ```
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rsta::indicators::fixed::FixedSma;
 use rsta::indicators::momentum::{Cci, Rsi, StochasticOscillator};
 use rsta::indicators::trend::{Adx, Ema, Macd, Sma};
 use rsta::indicators::volatility::{Atr, BollingerBands};
@@ -53,6 +54,12 @@ fn close_indicators(c: &mut Criterion) {
             black_box(<Sma as Indicator<f64, f64>>::calculate(&mut sma, &closes).unwrap())
         })
     });
+    group.bench_function("fixed_sma_20", |b| {
+        b.iter(|| {
+            let mut sma = FixedSma::<20>::new();
+            black_box(<FixedSma<20> as Indicator<f64, f64>>::calculate(&mut sma, &closes).unwrap())
+        })
+    });
     group.bench_function("ema_20", |b| {
         b.iter(|| {
             let mut ema = Ema::new(20).unwrap();
@@ -137,12 +137,14 @@ fn main() {
             }
         }
     }
-    let bh = bt.run(&candles, &mut BuyAndHold { entered: false });
+    let bh = bt
+        .run(&candles, &mut BuyAndHold { entered: false })
+        .unwrap();
     print_metrics("buy & hold", &bh.metrics);
     println!();
 
     // Strategy under test.
     let mut strat = SmaCrossover::new(20, 50);
-    let result = bt.run(&candles, &mut strat);
+    let result = bt.run(&candles, &mut strat).unwrap();
     print_metrics("SMA(20) / SMA(50) crossover", &result.metrics);
 }
@@ -17,8 +17,10 @@ Usage::
     pip install pandas pandas-ta
     python scripts/gen_golden.py
 
-The script overwrites every ``golden_btc_*.csv`` it knows about. Commit the
-resulting CSVs alongside the corresponding test additions.
+The script overwrites every ``golden_btc_*.csv`` it knows about, then
+refreshes the slices embedded by the ``reference`` module
+(``src/reference/data/``). Commit the resulting CSVs alongside the
+corresponding test additions.
 
 Pin a specific ``pandas-ta`` version in the PR description if you upgrade —
 some indicators have multiple implementation conventions and the reference
@@ -106,9 +108,55 @@ def gen_btc() -> None:
     write_golden("btc_macd_12_26_9_hist", macd["hist"])
 
 
+# Rows of the BTC dataset embedded in the crate by `rsta::reference`.
+EMBEDDED_BTC_ROWS = 400
+EMBED_DIR = ROOT / "src" / "reference" / "data"
+
+
+def embed_reference(dataset: pd.DataFrame, stem: str, goldens: dict) -> None:
+    """Write `<stem>.csv` (OHLCV) and `<stem>_reference.csv` (one column per
+    golden, empty during warmup) for the `reference` module."""
+    dataset.to_csv(EMBED_DIR / f"{stem}.csv", index=False)
+    wide = pd.DataFrame(index=range(len(dataset)))
+    for column, name in goldens.items():
+        golden = pd.read_csv(DATA_DIR / f"golden_{name}.csv", index_col="index")
+        wide[column] = golden.iloc[:, 0].reindex(wide.index)
+    wide.index.name = "index"
+    wide.to_csv(EMBED_DIR / f"{stem}_reference.csv", float_format="%.17g")
+    print(f"wrote {EMBED_DIR / stem}*.csv ({len(dataset)} rows)")
+
+
+def gen_embedded() -> None:
+    embed_reference(
+        pd.read_csv(SAMPLE),
+        "sample_ohlcv",
+        {"sma_5": "sma_5", "ema_5": "ema_5", "rsi_14": "rsi_14"},
+    )
+    if not BTC.exists():
+        return
+    btc = pd.read_csv(BTC, names=KRAKEN_COLS).drop(columns="trade_count")
+    embed_reference(
+        btc.head(EMBEDDED_BTC_ROWS),
+        "btc_usd_daily",
+        {
+            name.removeprefix("btc_"): name
+            for name in [
+                "btc_sma_20",
+                "btc_ema_20",
+                "btc_rsi_14",
+                "btc_atr_14",
+                "btc_macd_12_26_9_line",
+                "btc_macd_12_26_9_signal",
+                "btc_macd_12_26_9_hist",
+            ]
+        },
+    )
+
+
 def main() -> None:
     gen_synthetic()
     gen_btc()
+    gen_embedded()
 
 
 if __name__ == "__main__":
@@ -0,0 +1,319 @@
+//! # Corporate action adjustment
+//!
+//! A 2-for-1 split halves the price overnight; a dividend drops it by the
+//! amount paid on the ex-date. Neither is a market move, but an unadjusted
+//! history shows both as gaps: moving averages cross, ATR spikes and
+//! breakout rules fire on a day nothing happened. [`adjust`] applies the
+//! splits and dividends of a [`CorporateAction`] list to a candle history
+//! so the series is continuous across them.
+//!
+//! - [`AdjustMode::Backward`] keeps the latest prices as traded and scales
+//!   the history before each action (the usual "adjusted close").
+//! - [`AdjustMode::Forward`] keeps the earliest prices as traded and scales
+//!   everything after each action, so past values never change when new
+//!   actions occur.
+//!
+//! Splits of ratio `r` scale prices by `1 / r` and volume by `r`, so the
+//! traded value is unchanged. A cash dividend `D` scales prices by
+//! `1 - D / close`, with `close` the last close before the ex-date, and
+//! leaves volume alone. Actions take effect at their timestamp: bars at
+//! or after it are post-action.
+//!
+//! ```
+//! use rsta::adjust::{adjust, AdjustMode, CorporateAction};
+//! use rsta::indicators::Candle;
+//!
+//! let bar = |timestamp, close| Candle {
+//!     timestamp, open: close, high: close, low: close, close, volume: 100.0,
+//! };
+//! // A 2-for-1 split effective at t = 3.
+//! let candles = [bar(1, 100.0), bar(2, 102.0), bar(3, 51.5), bar(4, 52.0)];
+//! let split = [CorporateAction::split(3, 2.0)];
+//!
+//! let back = adjust(&candles, &split, AdjustMode::Backward).unwrap();
+//! let closes: Vec<f64> = back.iter().map(|c| c.close).collect();
+//! assert_eq!(closes, vec![50.0, 51.0, 51.5, 52.0]);
+//! assert_eq!(back[0].volume, 200.0);
+//!
+//! let forward = adjust(&candles, &split, AdjustMode::Forward).unwrap();
+//! assert_eq!(forward[3].close, 104.0);
+//! ```
+//!
+//! To find actions missing from a list, [`gaps`] reports the bars that
+//! open far from the previous close.
+
+use crate::indicators::{Candle, IndicatorError};
+
+/// A split or cash dividend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CorporateAction {
+    /// Share split effective at `timestamp`.
+    Split {
+        /// First timestamp traded on the new share basis.
+        timestamp: u64,
+        /// New shares per old share: `2.0` for a 2-for-1 split, `0.1` for
+        /// a 1-for-10 reverse split.
+        ratio: f64,
+    },
+    /// Cash dividend going ex at `timestamp`.
+    Dividend {
+        /// Ex-dividend timestamp: the first bar trading without it.
+        timestamp: u64,
+        /// Cash paid per share, in price units.
+        amount: f64,
+    },
+}
+
+impl CorporateAction {
+    /// A split of `ratio` new shares per old share at `timestamp`.
+    pub fn split(timestamp: u64, ratio: f64) -> Self {
+        CorporateAction::Split { timestamp, ratio }
+    }
+
+    /// A dividend of `amount` per share going ex at `timestamp`.
+    pub fn dividend(timestamp: u64, amount: f64) -> Self {
+        CorporateAction::Dividend { timestamp, amount }
+    }
+
+    /// When the action takes effect.
+    pub fn timestamp(&self) -> u64 {
+        match *self {
+            CorporateAction::Split { timestamp, .. } => timestamp,
+            CorporateAction::Dividend { timestamp, .. } => timestamp,
+        }
+    }
+
+    fn validate(&self) -> Result<(), IndicatorError> {
+        match *self {
+            CorporateAction::Split { ratio, .. } if ratio <= 0.0 || !ratio.is_finite() => Err(
+                IndicatorError::invalid_parameter("ratio", ratio, "must be positive and finite"),
+            ),
+            CorporateAction::Dividend { amount, .. } if amount < 0.0 || !amount.is_finite() => {
+                Err(IndicatorError::invalid_parameter(
+                    "amount",
+                    amount,
+                    "must be non-negative and finite",
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Which end of the history keeps its traded prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdjustMode {
+    /// Latest bars unchanged, earlier ones scaled.
+    #[default]
+    Backward,
+    /// Earliest bars unchanged, later ones scaled.
+    Forward,
+}
+
+/// Price and volume multipliers of one bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Factors {
+    /// Multiplier of the open, high, low and close.
+    pub price: f64,
+    /// Multiplier of the volume.
+    pub volume: f64,
+}
+
+/// Cumulative adjustment factors, one per candle, for `mode`.
+///
+/// # Arguments
+/// * `candles` - History in ascending timestamp order
+/// * `actions` - Splits and dividends, in any order
+/// * `mode` - Which end of the history keeps its prices
+///
+/// # Returns
+/// * `Result<Vec<Factors>, IndicatorError>` - The factors of each candle or an error
+pub fn factors(
+    candles: &[Candle],
+    actions: &[CorporateAction],
+    mode: AdjustMode,
+) -> Result<Vec<Factors>, IndicatorError> {
+    let mut actions = actions.to_vec();
+    actions.sort_by_key(CorporateAction::timestamp);
+    // (index of the first post-action candle, price factor, volume factor)
+    let mut steps = Vec::with_capacity(actions.len());
+    for action in &actions {
+        action.validate()?;
+        let at = candles.partition_point(|c| c.timestamp < action.timestamp());
+        if at == 0 {
+            // The whole history already trades on the post-action basis.
+            continue;
+        }
+        let step = match *action {
+            CorporateAction::Split { ratio, .. } => (1.0 / ratio, ratio),
+            CorporateAction::Dividend { amount, .. } => {
+                let close = candles[at - 1].close;
+                if amount >= close {
+                    return Err(IndicatorError::InvalidInput {
+                        index: at - 1,
+                        reason: format!("dividend {amount} not below the prior close {close}"),
+                    });
+                }
+                (1.0 - amount / close, 1.0)
+            }
+        };
+        steps.push((at, step.0, step.1));
+    }
+
+    // Backward, a bar takes the factors of the actions after it: start
+    // from all of them and divide each out once the action is passed.
+    // Forward starts from none, so the same division applies the inverse
+    // of the actions at or before the bar.
+    let (mut price, mut volume) = match mode {
+        AdjustMode::Backward => steps
+            .iter()
+            .fold((1.0, 1.0), |(p, v), s| (p * s.1, v * s.2)),
+        AdjustMode::Forward => (1.0, 1.0),
+    };
+    let mut pending = steps.iter().peekable();
+    let mut out = Vec::with_capacity(candles.len());
+    for i in 0..candles.len() {
+        while let Some(&(_, p, v)) = pending.next_if(|s| s.0 <= i) {
+            price /= p;
+            volume /= v;
+        }
+        out.push(Factors { price, volume });
+    }
+    Ok(out)
+}
+
+/// `candles` adjusted for `actions`. See the [module docs](self).
+///
+/// # Arguments
+/// * `candles` - History in ascending timestamp order
+/// * `actions` - Splits and dividends, in any order; a split or dividend
+///   after the last candle still adjusts the history in backward mode
+/// * `mode` - Which end of the history keeps its prices
+///
+/// # Returns
+/// * `Result<Vec<Candle>, IndicatorError>` - The adjusted candles or an error
+pub fn adjust(
+    candles: &[Candle],
+    actions: &[CorporateAction],
+    mode: AdjustMode,
+) -> Result<Vec<Candle>, IndicatorError> {
+    let factors = factors(candles, actions, mode)?;
+    Ok(candles
+        .iter()
+        .zip(factors)
+        .map(|(c, f)| Candle {
+            timestamp: c.timestamp,
+            open: c.open * f.price,
+            high: c.high * f.price,
+            low: c.low * f.price,
+            close: c.close * f.price,
+            volume: c.volume * f.volume,
+        })
+        .collect())
+}
+
+/// A bar opening far from the previous close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gap {
+    /// Index of the gapping candle.
+    pub index: usize,
+    /// Its timestamp.
+    pub timestamp: u64,
+    /// Open over the previous close: `0.5` for a halving.
+    pub ratio: f64,
+}
+
+/// Bars whose open differs from the previous close by more than
+/// `threshold` (a fraction: `0.3` flags moves beyond ±30%). A ratio near
+/// `1 / r` for a whole `r` usually means an unlisted split.
+///
+/// # Arguments
+/// * `candles` - History in ascending timestamp order
+/// * `threshold` - Smallest relative gap to report (must be positive)
+///
+/// # Returns
+/// * `Result<Vec<Gap>, IndicatorError>` - The gaps in order or an error
+pub fn gaps(candles: &[Candle], threshold: f64) -> Result<Vec<Gap>, IndicatorError> {
+    if threshold <= 0.0 || !threshold.is_finite() {
+        return Err(IndicatorError::invalid_parameter(
+            "threshold",
+            threshold,
+            "must be positive and finite",
+        ));
+    }
+    Ok(candles
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0].close > 0.0)
+        .map(|(i, pair)| Gap {
+            index: i + 1,
+            timestamp: pair[1].timestamp,
+            ratio: pair[1].open / pair[0].close,
+        })
+        .filter(|gap| (gap.ratio - 1.0).abs() > threshold)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(timestamp: u64, close: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_split_and_dividend_adjustment() {
+        // Dividend of 2 ex at t = 2 (prior close 100), 1-for-4 reverse split at t = 4.
+        let candles = [bar(1, 100.0), bar(2, 98.0), bar(3, 99.0), bar(4, 396.0)];
+        let actions = [
+            CorporateAction::split(4, 0.25),
+            CorporateAction::dividend(2, 2.0),
+        ];
+
+        let back = adjust(&candles, &actions, AdjustMode::Backward).unwrap();
+        assert_eq!(back[3], candles[3]);
+        assert!((back[2].close - 396.0).abs() < 1e-9);
+        assert!((back[0].close - 100.0 * 0.98 * 4.0).abs() < 1e-9);
+        assert!((back[0].volume - 2.5).abs() < 1e-12);
+        // The split no longer shows as a gap.
+        assert_eq!(gaps(&candles, 0.3).unwrap()[0].index, 3);
+        assert!(gaps(&back, 0.3).unwrap().is_empty());
+
+        let forward = adjust(&candles, &actions, AdjustMode::Forward).unwrap();
+        assert_eq!(forward[0], candles[0]);
+        assert!((forward[1].close - 98.0 / 0.98).abs() < 1e-9);
+        assert!((forward[3].close - 396.0 / 4.0 / 0.98).abs() < 1e-9);
+        assert!((forward[3].volume - 40.0).abs() < 1e-12);
+        // Either way, returns across the actions agree.
+        for i in 1..4 {
+            let b = back[i].close / back[i - 1].close;
+            let f = forward[i].close / forward[i - 1].close;
+            assert!((b - f).abs() < 1e-12);
+        }
+
+        assert!(adjust(
+            &candles,
+            &[CorporateAction::split(2, 0.0)],
+            AdjustMode::Backward
+        )
+        .is_err());
+        assert!(matches!(
+            adjust(
+                &candles,
+                &[CorporateAction::dividend(2, 100.0)],
+                AdjustMode::Forward
+            ),
+            Err(IndicatorError::InvalidInput { index: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,437 @@
+//! # Alerts
+//!
+//! Conditions on indicator streams that notify user code when they fire.
+//!
+//! An [`AlertRule`] watches a value against a level — fixed (RSI vs 30)
+//! or moving (close vs the upper Bollinger band) — and decides, bar by
+//! bar, whether to fire:
+//!
+//! - [`Condition::Above`] / [`Condition::Below`] fire as soon as the value
+//!   is beyond the level, including on the first bar.
+//! - [`Condition::CrossesAbove`] / [`Condition::CrossesBelow`] fire only
+//!   on the bar the value goes through the level (previous bar at or on
+//!   the other side), using the same rule as
+//!   [`signals::cross`](crate::signals::cross).
+//!
+//! Once fired, a rule is **disarmed** until the value returns to the
+//! other side of the level by at least the hysteresis band, so noise
+//! around the level does not produce a burst of alerts. A **cooldown**
+//! additionally suppresses triggers for a number of bars after each
+//! alert; a suppressed trigger does not disarm the rule.
+//!
+//! [`Alerts`] registers named rules, each with a source closure that
+//! extracts `(value, level)` from the stream item — a [`Candle`], the
+//! output map of an [`IndicatorSet`], or anything else — and invokes the
+//! registered handlers for every alert.
+//!
+//! [`Candle`]: crate::indicators::Candle
+//! [`IndicatorSet`]: crate::indicators::IndicatorSet
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//!
+//! use rsta::alerts::{AlertRule, Alerts, Condition};
+//! use rsta::indicators::momentum::Rsi;
+//! use rsta::indicators::{Candle, Indicator};
+//!
+//! let mut alerts = Alerts::<Candle>::new();
+//! let mut rsi = Rsi::new(5).unwrap();
+//! alerts.add_threshold(
+//!     "rsi_oversold",
+//!     AlertRule::new(Condition::CrossesBelow).with_hysteresis(5.0),
+//!     30.0,
+//!     move |c: &Candle| Indicator::<f64, f64>::next(&mut rsi, c.close).ok().flatten(),
+//! );
+//!
+//! let fired = Arc::new(Mutex::new(Vec::new()));
+//! let sink = Arc::clone(&fired);
+//! alerts.on_alert(move |event| sink.lock().unwrap().push(event.name.clone()));
+//!
+//! let closes = [50.0, 51.0, 52.0, 53.0, 54.0, 55.0, 50.0, 45.0, 40.0, 35.0];
+//! for (i, &close) in closes.iter().enumerate() {
+//!     let candle = Candle {
+//!         timestamp: i as u64, open: close, high: close, low: close, close, volume: 1.0,
+//!     };
+//!     alerts.update(&candle);
+//! }
+//! assert_eq!(*fired.lock().unwrap(), vec!["rsi_oversold".to_string()]);
+//! ```
+
+use std::sync::Arc;
+
+use crate::monitor::{MetricsSink, ALERTS_FIRED};
+
+/// How a rule compares the watched value with its level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    /// Value strictly above the level.
+    Above,
+    /// Value strictly below the level.
+    Below,
+    /// Value moves from at-or-below the level to above it.
+    CrossesAbove,
+    /// Value moves from at-or-above the level to below it.
+    CrossesBelow,
+}
+
+impl Condition {
+    fn is_upward(self) -> bool {
+        matches!(self, Condition::Above | Condition::CrossesAbove)
+    }
+}
+
+/// A fired alert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    /// Name the rule was registered under.
+    pub name: String,
+    /// The rule's condition.
+    pub condition: Condition,
+    /// Watched value on the firing bar.
+    pub value: f64,
+    /// Level on the firing bar.
+    pub level: f64,
+    /// Zero-based index of the firing bar (counted by [`Alerts::update`]).
+    pub bar: usize,
+}
+
+/// Stateful trigger logic for one condition.
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    condition: Condition,
+    hysteresis: f64,
+    cooldown: usize,
+    prev: Option<(f64, f64)>,
+    armed: bool,
+    bars_since_fire: Option<usize>,
+}
+
+impl AlertRule {
+    /// Rule with no hysteresis and no cooldown.
+    pub fn new(condition: Condition) -> Self {
+        Self {
+            condition,
+            hysteresis: 0.0,
+            cooldown: 0,
+            prev: None,
+            armed: true,
+            bars_since_fire: None,
+        }
+    }
+
+    /// After firing, re-arm only once the value is back on the other side
+    /// of the level by at least `band` (in the value's units). Negative or
+    /// non-finite bands are treated as `0`.
+    pub fn with_hysteresis(mut self, band: f64) -> Self {
+        self.hysteresis = if band.is_finite() { band.max(0.0) } else { 0.0 };
+        self
+    }
+
+    /// Suppress triggers for `bars` bars after each alert.
+    pub fn with_cooldown(mut self, bars: usize) -> Self {
+        self.cooldown = bars;
+        self
+    }
+
+    /// The rule's condition.
+    pub fn condition(&self) -> Condition {
+        self.condition
+    }
+
+    /// Feed the next `(value, level)` pair; `true` if the alert fires.
+    /// Non-finite inputs are skipped without touching the state.
+    pub fn check(&mut self, value: f64, level: f64) -> bool {
+        if !value.is_finite() || !level.is_finite() {
+            return false;
+        }
+        if let Some(n) = self.bars_since_fire.as_mut() {
+            *n += 1;
+        }
+
+        let upward = self.condition.is_upward();
+        if !self.armed {
+            let rearm = if upward {
+                value <= level - self.hysteresis
+            } else {
+                value >= level + self.hysteresis
+            };
+            if rearm {
+                self.armed = true;
+            }
+        }
+
+        let triggered = match (self.condition, self.prev) {
+            (Condition::Above, _) => value > level,
+            (Condition::Below, _) => value < level,
+            (Condition::CrossesAbove, Some((pv, pl))) => pv <= pl && value > level,
+            (Condition::CrossesBelow, Some((pv, pl))) => pv >= pl && value < level,
+            (_, None) => false,
+        };
+        self.prev = Some((value, level));
+
+        let cooling = self.bars_since_fire.is_some_and(|n| n < self.cooldown);
+        if triggered && self.armed && !cooling {
+            self.armed = false;
+            self.bars_since_fire = Some(0);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.prev = None;
+        self.armed = true;
+        self.bars_since_fire = None;
+    }
+}
+
+type Source<T> = Box<dyn FnMut(&T) -> Option<(f64, f64)> + Send>;
+type Handler = Box<dyn FnMut(&AlertEvent) + Send>;
+
+struct Registered<T> {
+    name: String,
+    rule: AlertRule,
+    source: Source<T>,
+}
+
+/// Named alert rules evaluated on a stream of `T`, with callbacks.
+pub struct Alerts<T> {
+    rules: Vec<Registered<T>>,
+    handlers: Vec<Handler>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+    bar: usize,
+}
+
+impl<T> Default for Alerts<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Alerts<T> {
+    /// Empty registry.
+    pub fn new() -> Self {
+        Self {
+            rules: Vec::new(),
+            handlers: Vec::new(),
+            metrics: None,
+            bar: 0,
+        }
+    }
+
+    /// Register `rule` under `name`. `source` extracts `(value, level)`
+    /// from each item, or `None` while its inputs are warming up.
+    pub fn add<F>(&mut self, name: &str, rule: AlertRule, source: F) -> &mut Self
+    where
+        F: FnMut(&T) -> Option<(f64, f64)> + Send + 'static,
+    {
+        self.rules.push(Registered {
+            name: name.to_string(),
+            rule,
+            source: Box::new(source),
+        });
+        self
+    }
+
+    /// Register `rule` against the fixed `level`; `source` extracts only
+    /// the value.
+    pub fn add_threshold<F>(
+        &mut self,
+        name: &str,
+        rule: AlertRule,
+        level: f64,
+        mut source: F,
+    ) -> &mut Self
+    where
+        F: FnMut(&T) -> Option<f64> + Send + 'static,
+    {
+        self.add(name, rule, move |item| source(item).map(|v| (v, level)))
+    }
+
+    /// Call `handler` for every alert, in registration order.
+    pub fn on_alert<H>(&mut self, handler: H) -> &mut Self
+    where
+        H: FnMut(&AlertEvent) + Send + 'static,
+    {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Count every alert in `sink`, as [`ALERTS_FIRED`] labeled with the
+    /// rule name.
+    pub fn set_metrics(&mut self, sink: Arc<dyn MetricsSink>) -> &mut Self {
+        self.metrics = Some(sink);
+        self
+    }
+
+    /// Number of registered rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// `true` if no rules are registered.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Evaluate every rule on `item`, invoke the handlers for each alert
+    /// and return the alerts.
+    ///
+    /// Every source is called on every item, so indicators owned by the
+    /// source closures stay in sync with the stream.
+    pub fn update(&mut self, item: &T) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        for registered in &mut self.rules {
+            let Some((value, level)) = (registered.source)(item) else {
+                continue;
+            };
+            if registered.rule.check(value, level) {
+                events.push(AlertEvent {
+                    name: registered.name.clone(),
+                    condition: registered.rule.condition(),
+                    value,
+                    level,
+                    bar: self.bar,
+                });
+            }
+        }
+        for event in &events {
+            if let Some(sink) = &self.metrics {
+                sink.increment(ALERTS_FIRED, &[("alert", &event.name)], 1);
+            }
+            for handler in &mut self.handlers {
+                handler(event);
+            }
+        }
+        self.bar += 1;
+        events
+    }
+
+    /// Reset the rules and the bar counter. Source closures keep their
+    /// own state (e.g. the indicators they own).
+    pub fn reset(&mut self) {
+        for registered in &mut self.rules {
+            registered.rule.reset_state();
+        }
+        self.bar = 0;
+    }
+}
+
+#[cfg(all(test, feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::volatility::BollingerBands;
+    use crate::indicators::{Candle, Indicator};
+    use std::sync::{Arc, Mutex};
+
+    fn fires(rule: &mut AlertRule, values: &[f64], level: f64) -> Vec<usize> {
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &v)| rule.check(v, level).then_some(i))
+            .collect()
+    }
+
+    #[test]
+    fn above_fires_on_entry_and_rearms() {
+        let mut rule = AlertRule::new(Condition::Above);
+        // Already above on the first bar → fires immediately.
+        assert_eq!(
+            fires(&mut rule, &[11.0, 12.0, 9.0, 11.0, 12.0], 10.0),
+            [0, 3]
+        );
+    }
+
+    #[test]
+    fn crosses_need_a_previous_bar() {
+        let mut rule = AlertRule::new(Condition::CrossesAbove);
+        assert_eq!(fires(&mut rule, &[11.0, 12.0, 9.0, 10.0, 11.0], 10.0), [4]);
+        let mut rule = AlertRule::new(Condition::CrossesBelow);
+        assert_eq!(fires(&mut rule, &[35.0, 29.0, 31.0, 28.0], 30.0), [1, 3]);
+    }
+
+    #[test]
+    fn hysteresis_suppresses_chatter() {
+        let values = [29.0, 31.0, 29.5, 30.5, 29.0, 24.0, 31.0];
+        let mut plain = AlertRule::new(Condition::Below);
+        assert_eq!(fires(&mut plain, &values, 30.0), [0, 2, 4]);
+        // Needs to get back above 35 before re-arming: fires once only.
+        let mut rule = AlertRule::new(Condition::Below).with_hysteresis(5.0);
+        assert_eq!(fires(&mut rule, &values, 30.0), [0]);
+        assert!(!rule.check(36.0, 30.0));
+        assert!(rule.check(29.0, 30.0));
+    }
+
+    #[test]
+    fn cooldown_drops_crosses_but_keeps_level_armed() {
+        let values = [9.0, 11.0, 9.0, 11.0, 11.0, 11.0, 9.0, 11.0];
+        let mut cross = AlertRule::new(Condition::CrossesAbove).with_cooldown(3);
+        assert_eq!(fires(&mut cross, &values, 10.0), [1, 7]);
+
+        let mut level = AlertRule::new(Condition::Above).with_cooldown(3);
+        // Bar 3 is suppressed but the rule stays armed, so it fires at
+        // bar 4, once the cooldown has elapsed.
+        assert_eq!(fires(&mut level, &values, 10.0), [1, 4, 7]);
+    }
+
+    #[test]
+    fn non_finite_inputs_are_skipped() {
+        let mut rule = AlertRule::new(Condition::CrossesAbove);
+        assert!(!rule.check(9.0, 10.0));
+        assert!(!rule.check(f64::NAN, 10.0));
+        assert!(rule.check(11.0, 10.0));
+    }
+
+    #[test]
+    fn registry_invokes_handlers_with_moving_level() {
+        let mut alerts = Alerts::<Candle>::new();
+        let mut bb = BollingerBands::new(5, 1.0).unwrap();
+        alerts.add(
+            "bb_breakout",
+            AlertRule::new(Condition::CrossesAbove),
+            move |c: &Candle| {
+                Indicator::<Candle, _>::next(&mut bb, *c)
+                    .ok()
+                    .flatten()
+                    .map(|b| (c.close, b.upper))
+            },
+        );
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        alerts.on_alert(move |e| sink.lock().unwrap().push(e.bar));
+        assert_eq!(alerts.len(), 1);
+
+        let closes = [10.0, 10.2, 9.9, 10.1, 10.0, 10.05, 9.95, 12.0, 12.1];
+        let mut returned = Vec::new();
+        for (i, &close) in closes.iter().enumerate() {
+            let candle = Candle {
+                timestamp: i as u64,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            };
+            returned.extend(alerts.update(&candle));
+        }
+        assert_eq!(returned.len(), 1);
+        assert_eq!(returned[0].bar, 7);
+        assert_eq!(returned[0].name, "bb_breakout");
+        assert!(returned[0].value > returned[0].level);
+        assert_eq!(*seen.lock().unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn reset_rearms_rules() {
+        let mut alerts = Alerts::<f64>::new();
+        alerts.add_threshold("hi", AlertRule::new(Condition::Above), 1.0, |v| Some(*v));
+        assert_eq!(alerts.update(&2.0).len(), 1);
+        assert!(alerts.update(&3.0).is_empty());
+        alerts.reset();
+        let again = alerts.update(&3.0);
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].bar, 0);
+    }
+}
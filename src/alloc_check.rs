@@ -0,0 +1,309 @@
+//! # Allocation checks
+//!
+//! Built-in indicators keep a bounded state: a window buffer sized by
+//! their period, reserved up front, plus a few scalars. From the first
+//! value on, `next()` does not allocate, so it can run on the hot path of a
+//! latency-critical service. The pattern detectors and [`RollingVwap`]
+//! hold collections that fill up to a cap instead. The indicators module
+//! docs list the exceptions. The crate's test suite checks both properties
+//! with the counting allocator below; services can do the same for their
+//! own pipelines.
+//!
+//! Install [`CountingAllocator`] as the global allocator of a test or
+//! debug build, then wrap the code that must not allocate in
+//! [`assert_allocation_free`] (or count with [`count_allocations`]), or
+//! compare [`live_bytes`] before and after a long run to check memory
+//! stays flat. Counts are per thread, so tests running in parallel do not
+//! interfere.
+//!
+//! [`RollingVwap`]: crate::indicators::volume::RollingVwap
+//!
+//! ```
+//! use rsta::alloc_check::{assert_allocation_free, CountingAllocator};
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::Indicator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//!
+//! let mut sma = Sma::new(20).unwrap();
+//! for i in 0..19 {
+//!     sma.next(i as f64).unwrap();
+//! }
+//! let value = assert_allocation_free("Sma::next", || sma.next(21.0).unwrap());
+//! assert!(value.is_some());
+//! ```
+//!
+//! Enable the `alloc-check` feature to use this module outside the crate.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+}
+
+/// Global allocator that forwards to [`System`] and counts the
+/// allocations (including reallocations) and live bytes of each thread.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count(bytes: i64) {
+        // `try_with` because the allocator can run while the thread's
+        // locals are being torn down.
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        Self::track(bytes);
+    }
+
+    fn track(bytes: i64) {
+        let _ = LIVE_BYTES.try_with(|n| n.set(n.get() + bytes));
+    }
+}
+
+// SAFETY: every call forwards to the system allocator unchanged.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size() as i64);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size() as i64);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count(new_size as i64 - layout.size() as i64);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::track(-(layout.size() as i64));
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations made by the current thread so far. Always `0` unless
+/// [`CountingAllocator`] is the global allocator.
+pub fn allocations() -> u64 {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Bytes allocated and not yet freed by the current thread (negative if
+/// it freed memory another thread allocated). Always `0` unless
+/// [`CountingAllocator`] is the global allocator.
+pub fn live_bytes() -> i64 {
+    LIVE_BYTES.with(Cell::get)
+}
+
+/// Run `f` and return its result with the number of allocations it made
+/// on the current thread.
+pub fn count_allocations<R>(f: impl FnOnce() -> R) -> (R, u64) {
+    let before = allocations();
+    let result = f();
+    (result, allocations() - before)
+}
+
+/// Run `f` and panic if it allocated, naming `what` in the message.
+///
+/// # Panics
+/// If `f` made any allocation on the current thread.
+pub fn assert_allocation_free<R>(what: &str, f: impl FnOnce() -> R) -> R {
+    let (result, count) = count_allocations(f);
+    assert!(count == 0, "{what} allocated {count} time(s)");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_counts_allocations_of_the_current_thread() {
+        let before = live_bytes();
+        let (v, count) = count_allocations(|| vec![1u8; 16]);
+        assert_eq!((v.len(), count), (16, 1));
+        assert_eq!(live_bytes() - before, 16);
+        assert_eq!(count_allocations(|| v.iter().sum::<u8>()).1, 0);
+        drop(v);
+        assert_eq!(live_bytes(), before);
+        let panic = std::panic::catch_unwind(|| assert_allocation_free("boxing", || Box::new(1)));
+        assert!(panic.is_err());
+    }
+
+    #[cfg(all(
+        feature = "trend",
+        feature = "momentum",
+        feature = "volume",
+        feature = "volatility"
+    ))]
+    #[test]
+    fn test_warm_indicators_do_not_allocate() {
+        use crate::indicators::momentum::*;
+        use crate::indicators::performance::*;
+        use crate::indicators::statistics::*;
+        use crate::indicators::transform::*;
+        use crate::indicators::trend::*;
+        use crate::indicators::volatility::*;
+        use crate::indicators::volume::*;
+        use crate::indicators::{Candle, FixedEma, FixedSma, FixedWma, Indicator, Tick, TradeSide};
+
+        let price = |i: usize| 100.0 + 10.0 * (i as f64 * 0.3).sin() + (i % 7) as f64;
+        let candle = |i: usize| {
+            let close = price(i);
+            Candle {
+                timestamp: i as u64,
+                open: price(i + 1),
+                high: close + 2.0 + (i % 3) as f64,
+                low: close - 2.0 - (i % 5) as f64,
+                close,
+                volume: 1000.0 + 100.0 * (i % 11) as f64,
+            }
+        };
+        let tick = |i: usize| Tick {
+            timestamp: i as u64,
+            price: price(i),
+            size: 1.0 + (i % 4) as f64,
+            side: if i % 3 == 0 {
+                TradeSide::Sell
+            } else {
+                TradeSide::Buy
+            },
+        };
+        let pair = |i: usize| (price(i) / 1000.0 - 0.1, price(i + 5) / 1000.0 - 0.1);
+
+        // From the end of warm-up on, updates must stay off the allocator.
+        fn check<T, O>(mut indicator: impl Indicator<T, O>, input: impl Fn(usize) -> T) {
+            let warm_up = indicator.min_periods();
+            for i in 0..warm_up {
+                indicator.next(input(i)).unwrap();
+            }
+            let name = format!("{}::next", indicator.name());
+            assert_allocation_free(&name, || {
+                for i in warm_up..1000 {
+                    indicator.next(input(i)).unwrap();
+                }
+            });
+        }
+        macro_rules! check {
+            ($input:expr; $($indicator:expr),* $(,)?) => { $(check($indicator, $input);)* };
+        }
+
+        check!(price;
+            AdaptiveRsi::new(), AdaptiveStochastic::new(), Rsi::new(14).unwrap(),
+            RollingSharpe::new(20).unwrap(), RollingSortino::new(20).unwrap(),
+            PermutationEntropy::new(20, 3).unwrap(), Kurtosis::new(20).unwrap(),
+            Skewness::new(20).unwrap(), LogReturns::new(1).unwrap(),
+            MinMaxScaler::new(20).unwrap(), ZScoreNormalizer::new(20).unwrap(),
+            Dema::new(10).unwrap(), DominantCycle::new(),
+            ElderImpulse::new(13, 12, 26, 9).unwrap(), Ema::new(10).unwrap(), Gmma::new(),
+            Hma::new(16).unwrap(), MaDistance::new(20, MaType::Wma).unwrap(),
+            Macd::new(12, 26, 9).unwrap(), Sma::new(20).unwrap(),
+            SuperSmoother::new(10).unwrap(), Tema::new(10).unwrap(), Wma::new(10).unwrap(),
+            BollingerBands::new(20, 2.0).unwrap(),
+            Std::new(20).unwrap(), FixedEma::<10>::new(), FixedSma::<10>::new(),
+            FixedWma::<10>::new(), crate::anomaly::ZScoreSpike::new(20, 3.0).unwrap(),
+            crate::anomaly::MadOutlier::new(20, 3.5).unwrap(),
+            crate::risk::RollingVar::new(50, 0.95, 1, crate::risk::VarMethod::Historical).unwrap(),
+            crate::risk::RollingVar::new(50, 0.95, 1, crate::risk::VarMethod::Parametric).unwrap(),
+        );
+        #[cfg(feature = "patterns")]
+        check!(candle; crate::patterns::fibonacci::FibonacciTracker::new(3).unwrap());
+        check!(candle;
+            Cci::new(20).unwrap(), StochasticOscillator::new(14, 3).unwrap(),
+            WilliamsR::new(14).unwrap(), Adx::new(14).unwrap(), Ichimoku::new(9, 26, 52).unwrap(),
+            Sar::new(0.02, 0.02, 0.2).unwrap(), Atr::new(14).unwrap(), TrueRange::new(),
+            Choppiness::new(14).unwrap(), Donchian::new(20).unwrap(),
+            KeltnerChannels::new(20, 10, 2.0).unwrap(), VolatilityRank::new(14, 100).unwrap(),
+            AdOscillator::new(3, 10).unwrap(), Adl::new(), Cmf::new(20).unwrap(),
+            CmfSignal::new(20, 9).unwrap(), Mfi::new(14).unwrap(), Obv::new(),
+            VolumeOscillator::new(5, 10).unwrap(), Vroc::new(14).unwrap(),
+            VwMacd::new(12, 26, 9).unwrap(),
+            CandleReturns::new(ReturnKind::Log), Vwap::new(),
+            crate::regime::RegimeDetector::new(14, 50).unwrap(),
+        );
+        check!(tick;
+            CumulativeDelta::new(), RollingVwap::new(u64::MAX).unwrap().with_max_trades(64).unwrap(), TradeImbalance::new(20).unwrap(),
+        );
+        check!(pair;
+            InformationRatio::new(20).unwrap(), RollingAlpha::new(20).unwrap(),
+            RelativeStrengthLine::new(10).unwrap(), crate::pairs::RollingOls::new(20).unwrap(),
+        );
+    }
+
+    #[cfg(all(feature = "patterns", feature = "volume"))]
+    #[test]
+    fn test_pattern_state_is_bounded() {
+        use crate::indicators::volume::RollingVwap;
+        use crate::indicators::{Candle, Indicator, Tick, TradeSide};
+        use crate::patterns::candlestick::CandlestickPatterns;
+        use crate::patterns::gaps::{GapDetector, GapThreshold};
+        use crate::patterns::levels::SupportResistance;
+        use crate::patterns::trendlines::Trendlines;
+
+        // A zigzag uptrend that gaps up every fourth bar: new price areas,
+        // swings and unfilled gaps keep coming.
+        let close =
+            |i: usize| 100.0 * 1.002f64.powi(i as i32) * (1.0 + 0.01 * (i as f64 * 0.9).sin());
+        let candle = |i: usize| {
+            let c = close(i);
+            let prev = if i == 0 { c } else { close(i - 1) };
+            let open = if i % 4 == 0 { prev * 1.006 } else { prev };
+            Candle {
+                timestamp: i as u64,
+                open,
+                high: open.max(c) * 1.002,
+                low: open.min(c) * 0.998,
+                close: c,
+                volume: 1000.0 + 100.0 * (i % 11) as f64,
+            }
+        };
+        let tick = |i: usize| Tick {
+            timestamp: i as u64,
+            price: close(i % 1000),
+            size: 1.0,
+            side: TradeSide::Unknown,
+        };
+
+        // Memory must stay flat over a long run once the caps are reached;
+        // the slack covers the latest output each detector keeps.
+        fn check<T, O>(
+            mut indicator: impl Indicator<T, O>,
+            input: impl Fn(usize) -> T,
+            from: usize,
+        ) {
+            for i in 0..from {
+                indicator.next(input(i)).unwrap();
+            }
+            let before = live_bytes();
+            for i in from..from + 20_000 {
+                indicator.next(input(i)).unwrap();
+            }
+            let growth = live_bytes() - before;
+            assert!(
+                growth < 64 * 1024,
+                "{} grew by {growth} bytes",
+                indicator.name()
+            );
+        }
+
+        check(CandlestickPatterns::new(), candle, 100);
+        check(
+            GapDetector::new(GapThreshold::Percent(0.003)).unwrap(),
+            candle,
+            2_000,
+        );
+        check(SupportResistance::new(), candle, 2_000);
+        check(Trendlines::new(), candle, 2_000);
+        check(
+            RollingVwap::new(u64::MAX).unwrap(),
+            tick,
+            RollingVwap::DEFAULT_MAX_TRADES,
+        );
+    }
+}
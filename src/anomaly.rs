@@ -0,0 +1,613 @@
+//! # Anomaly detection
+//!
+//! Flags bars whose price move or volume is far outside its recent
+//! distribution. Each value is scored against the **previous** `period`
+//! values, so a spike does not dilute its own baseline:
+//!
+//! - [`ZScoreSpike`]: standard deviations from the rolling mean. Fast and
+//!   familiar, but a few large outliers in the window inflate the
+//!   deviation and mask the next ones.
+//! - [`MadOutlier`]: the robust z-score, distance from the rolling median
+//!   in units of the scaled median absolute deviation (MAD). Insensitive
+//!   to the outliers already in the window.
+//!
+//! A window with no spread (all values equal) scores a repeat of that
+//! value `0` and any other value `±∞`, which always counts as an anomaly.
+//!
+//! [`AnomalyDetector`] runs detectors over candles — price spikes are
+//! scored on close-to-close returns, so a steady trend does not register,
+//! volume spikes on the raw volume — and returns an [`AnomalyEvent`] per
+//! flagged series. [`AnomalyEvent::to_alert`] turns one into an
+//! [`AlertEvent`] for the handlers of the [alerting](crate::alerts)
+//! subsystem.
+//!
+//! ```
+//! use rsta::anomaly::{AnomalyDetector, AnomalyMethod, Field};
+//! use rsta::indicators::Candle;
+//!
+//! let mut detector = AnomalyDetector::new()
+//!     .with_price(AnomalyMethod::ZScore, 20, 4.0)
+//!     .unwrap()
+//!     .with_volume(AnomalyMethod::Mad, 20, 5.0)
+//!     .unwrap();
+//!
+//! let mut events = Vec::new();
+//! for i in 0..40u64 {
+//!     let close = 100.0 + (i as f64 * 0.7).sin();
+//!     // Bar 30 trades ten times the usual volume.
+//!     let volume = if i == 30 { 10_000.0 } else { 1_000.0 + (i % 3) as f64 * 50.0 };
+//!     let candle = Candle { timestamp: i, open: close, high: close, low: close, close, volume };
+//!     events.extend(detector.update(&candle));
+//! }
+//! assert_eq!(events.len(), 1);
+//! assert_eq!((events[0].field, events[0].timestamp), (Field::Volume, 30));
+//! assert_eq!(events[0].to_alert().name, "volume_mad");
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::alerts::{AlertEvent, Condition};
+use crate::indicators::utils::{validate_data_length, validate_period, RollingStats};
+use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::returns;
+
+/// Scale turning a MAD into a standard deviation estimate for normally
+/// distributed data, `1 / Φ⁻¹(3/4)`.
+const MAD_SCALE: f64 = 1.4826;
+
+/// How values are scored against their window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnomalyMethod {
+    /// Rolling z-score, see [`ZScoreSpike`].
+    #[default]
+    ZScore,
+    /// Median absolute deviation, see [`MadOutlier`].
+    Mad,
+}
+
+/// Series of a candle an [`AnomalyEvent`] was raised on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Field {
+    /// Close-to-close return.
+    Price,
+    /// Traded volume.
+    Volume,
+}
+
+/// An anomalous bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnomalyEvent {
+    /// Series that spiked.
+    pub field: Field,
+    /// Scoring method that flagged it.
+    pub method: AnomalyMethod,
+    /// Timestamp of the candle.
+    pub timestamp: u64,
+    /// Zero-based index of the candle (counted by
+    /// [`AnomalyDetector::update`]).
+    pub bar: usize,
+    /// The candle's close or volume.
+    pub value: f64,
+    /// Signed score: positive for spikes up, negative for spikes down.
+    pub score: f64,
+    /// Absolute score the detector flags from.
+    pub threshold: f64,
+}
+
+impl AnomalyEvent {
+    /// Rule name of the event, e.g. `"price_zscore"` or `"volume_mad"`.
+    pub fn name(&self) -> &'static str {
+        match (self.field, self.method) {
+            (Field::Price, AnomalyMethod::ZScore) => "price_zscore",
+            (Field::Price, AnomalyMethod::Mad) => "price_mad",
+            (Field::Volume, AnomalyMethod::ZScore) => "volume_zscore",
+            (Field::Volume, AnomalyMethod::Mad) => "volume_mad",
+        }
+    }
+
+    /// The event as an [`AlertEvent`]: the score against the threshold,
+    /// [`Condition::Above`] for spikes up and [`Condition::Below`] for
+    /// spikes down.
+    pub fn to_alert(&self) -> AlertEvent {
+        let (condition, level) = if self.score > 0.0 {
+            (Condition::Above, self.threshold)
+        } else {
+            (Condition::Below, -self.threshold)
+        };
+        AlertEvent {
+            name: self.name().to_string(),
+            condition,
+            value: self.score,
+            level,
+            bar: self.bar,
+        }
+    }
+}
+
+fn validate_threshold(threshold: f64) -> Result<(), IndicatorError> {
+    if threshold.is_finite() && threshold > 0.0 {
+        Ok(())
+    } else {
+        Err(IndicatorError::invalid_parameter(
+            "threshold",
+            threshold,
+            "must be positive and finite",
+        ))
+    }
+}
+
+/// `deviation / spread`, with a zero spread scoring `0` for no deviation
+/// and `±∞` otherwise.
+fn score(deviation: f64, spread: f64) -> f64 {
+    if spread > 0.0 {
+        deviation / spread
+    } else if deviation == 0.0 {
+        0.0
+    } else {
+        f64::INFINITY.copysign(deviation)
+    }
+}
+
+/// Rolling z-score spike detector
+///
+/// Scores each value by its distance from the mean of the previous
+/// `period` values, in population standard deviations, and flags scores
+/// of at least `threshold` in absolute value.
+///
+/// # Example
+///
+/// ```
+/// use rsta::anomaly::ZScoreSpike;
+/// use rsta::indicators::Indicator;
+///
+/// let mut spike = ZScoreSpike::new(4, 3.0).unwrap();
+/// let scores = spike.calculate(&[1.0, 2.0, 1.0, 2.0, 9.0]).unwrap();
+/// // Mean 1.5, deviation 0.5 over the first four values.
+/// assert_eq!(scores, vec![15.0]);
+/// assert!(spike.is_anomaly(scores[0]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZScoreSpike {
+    period: usize,
+    threshold: f64,
+    stats: RollingStats,
+    last: Option<f64>,
+}
+
+impl ZScoreSpike {
+    /// Create a new z-score spike detector
+    ///
+    /// # Arguments
+    /// * `period` - Number of previous values in the baseline (must be at least 2)
+    /// * `threshold` - Absolute score flagged as an anomaly (must be positive)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new detector or an error
+    pub fn new(period: usize, threshold: f64) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        validate_threshold(threshold)?;
+        Ok(Self {
+            period,
+            threshold,
+            stats: RollingStats::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Absolute score flagged as an anomaly.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Whether `score` reaches the threshold.
+    pub fn is_anomaly(&self, score: f64) -> bool {
+        score.abs() >= self.threshold
+    }
+
+    /// Reset the detector state
+    pub fn reset_state(&mut self) {
+        self.stats.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let baseline = self
+            .stats
+            .is_full()
+            .then(|| Some(score(value - self.stats.mean()?, self.stats.std_dev()?)))
+            .flatten();
+        self.stats.push(value);
+        if baseline.is_some() {
+            self.last = baseline;
+        }
+        baseline
+    }
+}
+
+impl Indicator<f64, f64> for ZScoreSpike {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period + 1)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "ZScoreSpike"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+}
+
+/// Median-absolute-deviation outlier detector
+///
+/// Scores each value by its distance from the median of the previous
+/// `period` values, in units of `1.4826 * MAD` (a standard deviation for
+/// normal data), and flags scores of at least `threshold` in absolute
+/// value.
+///
+/// # Formula
+///
+/// ```text
+/// MAD   = median(|x[i] - median(x)|)   over the previous period values
+/// score = (x - median(x)) / (1.4826 * MAD)
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use rsta::anomaly::MadOutlier;
+/// use rsta::indicators::Indicator;
+///
+/// // The 50 in the window does not hide the 40 that follows.
+/// let mut outlier = MadOutlier::new(5, 3.5).unwrap();
+/// let scores = outlier.calculate(&[10.0, 11.0, 50.0, 9.0, 10.0, 40.0]).unwrap();
+/// // Median 10, MAD 1.
+/// assert!((scores[0] - 30.0 / 1.4826).abs() < 1e-12);
+/// assert!(outlier.is_anomaly(scores[0]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MadOutlier {
+    period: usize,
+    threshold: f64,
+    window: VecDeque<f64>,
+    /// Sorting buffer reused by every update.
+    scratch: Vec<f64>,
+    last: Option<f64>,
+}
+
+impl MadOutlier {
+    /// Create a new MAD outlier detector
+    ///
+    /// # Arguments
+    /// * `period` - Number of previous values in the baseline (must be at least 2)
+    /// * `threshold` - Absolute score flagged as an anomaly (must be positive)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new detector or an error
+    pub fn new(period: usize, threshold: f64) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        validate_threshold(threshold)?;
+        Ok(Self {
+            period,
+            threshold,
+            window: VecDeque::with_capacity(period),
+            scratch: Vec::with_capacity(period),
+            last: None,
+        })
+    }
+
+    /// Absolute score flagged as an anomaly.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Whether `score` reaches the threshold.
+    pub fn is_anomaly(&self, score: f64) -> bool {
+        score.abs() >= self.threshold
+    }
+
+    /// Reset the detector state
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let baseline = (self.window.len() == self.period).then(|| {
+            let sorted = &mut self.scratch;
+            sorted.clear();
+            sorted.extend(self.window.iter().copied());
+            let median = median(sorted);
+            for x in sorted.iter_mut() {
+                *x = (*x - median).abs();
+            }
+            score(value - median, MAD_SCALE * self::median(sorted))
+        });
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if baseline.is_some() {
+            self.last = baseline;
+        }
+        baseline
+    }
+}
+
+/// Median of a non-empty slice, which it sorts.
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_unstable_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+impl Indicator<f64, f64> for MadOutlier {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period + 1)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "MadOutlier"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+}
+
+/// One configured detector of an [`AnomalyDetector`].
+#[derive(Debug, Clone)]
+enum Scorer {
+    ZScore(ZScoreSpike),
+    Mad(MadOutlier),
+}
+
+impl Scorer {
+    fn new(method: AnomalyMethod, period: usize, threshold: f64) -> Result<Self, IndicatorError> {
+        Ok(match method {
+            AnomalyMethod::ZScore => Scorer::ZScore(ZScoreSpike::new(period, threshold)?),
+            AnomalyMethod::Mad => Scorer::Mad(MadOutlier::new(period, threshold)?),
+        })
+    }
+
+    fn method(&self) -> AnomalyMethod {
+        match self {
+            Scorer::ZScore(_) => AnomalyMethod::ZScore,
+            Scorer::Mad(_) => AnomalyMethod::Mad,
+        }
+    }
+
+    /// Score `value`; `Some((score, threshold))` when it is an anomaly.
+    fn check(&mut self, value: f64) -> Option<(f64, f64)> {
+        let (score, threshold) = match self {
+            Scorer::ZScore(spike) => (spike.step(value)?, spike.threshold),
+            Scorer::Mad(outlier) => (outlier.step(value)?, outlier.threshold),
+        };
+        (score.abs() >= threshold).then_some((score, threshold))
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Scorer::ZScore(spike) => spike.reset_state(),
+            Scorer::Mad(outlier) => outlier.reset_state(),
+        }
+    }
+}
+
+/// Price and volume anomaly detection over candles. See the
+/// [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyDetector {
+    price: Option<Scorer>,
+    volume: Option<Scorer>,
+    prev_close: Option<f64>,
+    bar: usize,
+}
+
+impl AnomalyDetector {
+    /// Detector with no series watched; add them with
+    /// [`with_price`](Self::with_price) and [`with_volume`](Self::with_volume).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watch close-to-close returns over the previous `period` returns.
+    pub fn with_price(
+        mut self,
+        method: AnomalyMethod,
+        period: usize,
+        threshold: f64,
+    ) -> Result<Self, IndicatorError> {
+        self.price = Some(Scorer::new(method, period, threshold)?);
+        Ok(self)
+    }
+
+    /// Watch volume over the previous `period` bars.
+    pub fn with_volume(
+        mut self,
+        method: AnomalyMethod,
+        period: usize,
+        threshold: f64,
+    ) -> Result<Self, IndicatorError> {
+        self.volume = Some(Scorer::new(method, period, threshold)?);
+        Ok(self)
+    }
+
+    /// Score `candle` and return its anomalies, price first.
+    pub fn update(&mut self, candle: &Candle) -> Vec<AnomalyEvent> {
+        let mut events = Vec::new();
+        let ret = self
+            .prev_close
+            .and_then(|prev| returns::simple_return(prev, candle.close));
+        self.prev_close = Some(candle.close);
+        let inputs = [
+            (Field::Price, self.price.as_mut(), ret, candle.close),
+            (
+                Field::Volume,
+                self.volume.as_mut(),
+                Some(candle.volume),
+                candle.volume,
+            ),
+        ];
+        for (field, scorer, input, value) in inputs {
+            let (Some(scorer), Some(input)) = (scorer, input) else {
+                continue;
+            };
+            if let Some((score, threshold)) = scorer.check(input) {
+                events.push(AnomalyEvent {
+                    field,
+                    method: scorer.method(),
+                    timestamp: candle.timestamp,
+                    bar: self.bar,
+                    value,
+                    score,
+                    threshold,
+                });
+            }
+        }
+        self.bar += 1;
+        events
+    }
+
+    /// Reset the detectors and the bar counter.
+    pub fn reset(&mut self) {
+        for scorer in [self.price.as_mut(), self.volume.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            scorer.reset();
+        }
+        self.prev_close = None;
+        self.bar = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: u64, close: f64, volume: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+        }
+    }
+
+    #[test]
+    fn scores_exclude_the_current_value() {
+        let data: Vec<f64> = (0..30).map(|i| (i as f64 * 1.3).sin()).collect();
+        let mut spike = ZScoreSpike::new(8, 3.0).unwrap();
+        let mut outlier = MadOutlier::new(8, 3.0).unwrap();
+        let z = spike.calculate(&data).unwrap();
+        let m = outlier.calculate(&data).unwrap();
+        assert_eq!((z.len(), m.len()), (22, 22));
+        for i in 8..data.len() {
+            let window = &data[i - 8..i];
+            let mean = window.iter().sum::<f64>() / 8.0;
+            let std = (window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 8.0).sqrt();
+            assert!((z[i - 8] - (data[i] - mean) / std).abs() < 1e-9);
+
+            let mut sorted = window.to_vec();
+            let med = median(&mut sorted);
+            let mut dev: Vec<f64> = window.iter().map(|x| (x - med).abs()).collect();
+            let mad = median(&mut dev);
+            assert!((m[i - 8] - (data[i] - med) / (MAD_SCALE * mad)).abs() < 1e-9);
+        }
+        assert_eq!(spike.current(), z.last());
+    }
+
+    #[test]
+    fn flat_windows_and_invalid_parameters() {
+        let mut spike = ZScoreSpike::new(3, 2.0).unwrap();
+        assert_eq!(
+            spike.calculate(&[5.0, 5.0, 5.0, 5.0, 4.0]).unwrap(),
+            vec![0.0, f64::NEG_INFINITY]
+        );
+        let mut outlier = MadOutlier::new(3, 2.0).unwrap();
+        assert_eq!(
+            outlier.calculate(&[5.0, 5.0, 5.0, 6.0]).unwrap(),
+            vec![f64::INFINITY]
+        );
+        assert!(ZScoreSpike::new(1, 2.0).is_err());
+        assert!(MadOutlier::new(5, 0.0).is_err());
+        assert!(MadOutlier::new(5, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn detector_flags_price_crash_as_alert() {
+        let mut detector = AnomalyDetector::new()
+            .with_price(AnomalyMethod::Mad, 10, 4.0)
+            .unwrap();
+        let mut events = Vec::new();
+        for i in 0..30u64 {
+            let close = if i >= 25 {
+                80.0
+            } else {
+                100.0 + (i as f64 * 0.9).sin()
+            };
+            events.extend(detector.update(&candle(i, close, 1.0)));
+        }
+        // Only the drop itself; the flat closes after it are returns of 0.
+        assert_eq!(events.len(), 1);
+        let event = events[0];
+        assert_eq!(
+            (event.field, event.bar, event.value),
+            (Field::Price, 25, 80.0)
+        );
+        assert!(event.score < -4.0);
+
+        let alert = event.to_alert();
+        assert_eq!(alert.name, "price_mad");
+        assert_eq!(alert.condition, Condition::Below);
+        assert_eq!((alert.level, alert.bar), (-4.0, 25));
+
+        detector.reset();
+        assert!(detector.update(&candle(0, 1.0, 1.0)).is_empty());
+    }
+}
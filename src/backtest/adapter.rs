@@ -0,0 +1,469 @@
+//! # Execution adapters
+//!
+//! [`ExecutionAdapter`] is the seam between a live strategy and the venue
+//! that executes its orders. Implement it over an exchange or broker
+//! client — place, cancel, flatten and position queries — and the rest of
+//! the signal→order flow stays the same: [`apply`](ExecutionAdapter::apply)
+//! turns a strategy [`Action`] into orders and
+//! [`on_signal`](ExecutionAdapter::on_signal) does the same for a
+//! [`SignalEvent`], with the backtester's rules: an entry on the side
+//! already held is ignored and an entry on the opposite side closes the
+//! position first.
+//!
+//! Two adapters ship with the crate:
+//!
+//! - [`NoopAdapter`] records orders without executing anything, for dry
+//!   runs and tests of the order flow.
+//! - [`PaperAdapter`] fills orders against the candles it is fed, with the
+//!   fill and cost models of a [`BacktestConfig`] and a multi-symbol
+//!   [`portfolio::Portfolio`](crate::portfolio::Portfolio) for the
+//!   account.
+//!
+//! ```
+//! use rsta::backtest::adapter::{ExecutionAdapter, PaperAdapter};
+//! use rsta::backtest::{BacktestConfig, Quantity, Side};
+//! use rsta::indicators::Candle;
+//! use rsta::signals::SignalEvent;
+//!
+//! let mut venue = PaperAdapter::new(BacktestConfig::default());
+//! let candle = |t: u64, close: f64| Candle { timestamp: t, open: close, high: close, low: close, close, volume: 1.0 };
+//!
+//! venue.on_candle("BTC", &candle(1, 100.0));
+//! venue.on_signal("BTC", Some(SignalEvent::Long), Quantity::Fixed(2.0)).unwrap();
+//! venue.on_candle("BTC", &candle(2, 110.0));
+//!
+//! let position = venue.position("BTC").unwrap().unwrap();
+//! assert_eq!((position.side, position.quantity), (Side::Long, 2.0));
+//! assert_eq!(venue.portfolio().unrealized_pnl(), 20.0);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::indicators::Candle;
+use crate::portfolio;
+use crate::signals::SignalEvent;
+
+use super::orders::{Order, OrderType};
+use super::{entry_fill, resolve_quantity, Action, BacktestConfig, Position, Quantity, Side};
+
+/// Identifier an adapter assigns to a placed order.
+pub type OrderId = u64;
+
+/// Errors returned by an [`ExecutionAdapter`].
+#[derive(Debug, thiserror::Error)]
+pub enum AdapterError {
+    /// No working order has this id.
+    #[error("Unknown order {0}")]
+    UnknownOrder(OrderId),
+
+    /// The venue refused the order.
+    #[error("Order rejected: {0}")]
+    Rejected(String),
+
+    /// The adapter does not implement the operation.
+    #[error("Not supported: {0}")]
+    Unsupported(&'static str),
+
+    /// Transport or client error from the venue.
+    #[error("Venue error: {0}")]
+    Venue(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// Order placement and account queries against one venue.
+///
+/// `place` has exchange semantics: a buy adds to a long or reduces a
+/// short. The provided [`apply`](Self::apply) and
+/// [`on_signal`](Self::on_signal) layer the backtester's entry rules on
+/// top.
+pub trait ExecutionAdapter {
+    /// Place `order` for `symbol` and return its id. A market order fills
+    /// right away; others work until filled or cancelled.
+    fn place(&mut self, symbol: &str, order: Order) -> Result<OrderId, AdapterError>;
+
+    /// Cancel the working order `id`.
+    fn cancel(&mut self, id: OrderId) -> Result<(), AdapterError>;
+
+    /// Flatten the position in `symbol` with a market order. Returns the
+    /// order's id, `None` if there was no position.
+    fn close(&mut self, symbol: &str) -> Result<Option<OrderId>, AdapterError>;
+
+    /// The open position in `symbol`, if any.
+    fn position(&self, symbol: &str) -> Result<Option<Position>, AdapterError>;
+
+    /// Ids of the orders still working for `symbol`.
+    fn open_orders(&self, symbol: &str) -> Result<Vec<OrderId>, AdapterError>;
+
+    /// Replace the stop-loss and take-profit protecting the position in
+    /// `symbol`. Unsupported by default.
+    fn set_exits(
+        &mut self,
+        symbol: &str,
+        stop_loss: Option<f64>,
+        take_profit: Option<f64>,
+    ) -> Result<(), AdapterError> {
+        let _ = (symbol, stop_loss, take_profit);
+        Err(AdapterError::Unsupported("set_exits"))
+    }
+
+    /// Carry out a strategy `action` on `symbol`. Returns the id of the
+    /// entry or exit order placed, if any.
+    fn apply(&mut self, symbol: &str, action: Action) -> Result<Option<OrderId>, AdapterError> {
+        match action {
+            Action::Hold => Ok(None),
+            Action::EnterLong(quantity) => enter(self, symbol, Order::long(quantity)),
+            Action::EnterShort(quantity) => enter(self, symbol, Order::short(quantity)),
+            Action::Submit(order) if order.kind == OrderType::Market => enter(self, symbol, order),
+            Action::Submit(order) => self.place(symbol, order).map(Some),
+            Action::Exit => self.close(symbol),
+            Action::SetExits {
+                stop_loss,
+                take_profit,
+            } => self.set_exits(symbol, stop_loss, take_profit).map(|_| None),
+            Action::Cancel => {
+                for id in self.open_orders(symbol)? {
+                    self.cancel(id)?;
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    /// Act on a signal `event` for `symbol`: `Long` and `Short` enter with
+    /// `quantity`, `Exit` flattens, anything else does nothing.
+    fn on_signal(
+        &mut self,
+        symbol: &str,
+        event: Option<SignalEvent>,
+        quantity: Quantity,
+    ) -> Result<Option<OrderId>, AdapterError> {
+        let action = match event {
+            Some(SignalEvent::Long) => Action::EnterLong(quantity),
+            Some(SignalEvent::Short) => Action::EnterShort(quantity),
+            Some(SignalEvent::Exit) => Action::Exit,
+            _ => Action::Hold,
+        };
+        self.apply(symbol, action)
+    }
+}
+
+/// Market entry following the backtester's rules: nothing if already on
+/// `order.side`, a close first if on the other side.
+fn enter<A: ExecutionAdapter + ?Sized>(
+    adapter: &mut A,
+    symbol: &str,
+    order: Order,
+) -> Result<Option<OrderId>, AdapterError> {
+    match adapter.position(symbol)? {
+        Some(position) if position.side == order.side => return Ok(None),
+        Some(_) => {
+            adapter.close(symbol)?;
+        }
+        None => {}
+    }
+    adapter.place(symbol, order).map(Some)
+}
+
+// ---------------------------------------------------------------------------
+// No-op adapter
+// ---------------------------------------------------------------------------
+
+/// Adapter that accepts every order and executes none of them.
+///
+/// Placed orders stay in [`orders`](Self::orders) until cancelled, and no
+/// position ever opens — useful to dry-run the order flow of a strategy.
+#[derive(Debug, Clone, Default)]
+pub struct NoopAdapter {
+    next_id: OrderId,
+    orders: Vec<(OrderId, String, Order)>,
+}
+
+impl NoopAdapter {
+    /// Adapter with no orders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Orders placed and not cancelled, in placement order.
+    pub fn orders(&self) -> &[(OrderId, String, Order)] {
+        &self.orders
+    }
+}
+
+impl ExecutionAdapter for NoopAdapter {
+    fn place(&mut self, symbol: &str, order: Order) -> Result<OrderId, AdapterError> {
+        self.next_id += 1;
+        self.orders.push((self.next_id, symbol.to_string(), order));
+        Ok(self.next_id)
+    }
+
+    fn cancel(&mut self, id: OrderId) -> Result<(), AdapterError> {
+        let index = self
+            .orders
+            .iter()
+            .position(|(order_id, ..)| *order_id == id)
+            .ok_or(AdapterError::UnknownOrder(id))?;
+        self.orders.remove(index);
+        Ok(())
+    }
+
+    fn close(&mut self, _symbol: &str) -> Result<Option<OrderId>, AdapterError> {
+        Ok(None)
+    }
+
+    fn position(&self, _symbol: &str) -> Result<Option<Position>, AdapterError> {
+        Ok(None)
+    }
+
+    fn open_orders(&self, symbol: &str) -> Result<Vec<OrderId>, AdapterError> {
+        Ok(self
+            .orders
+            .iter()
+            .filter(|(_, s, _)| s == symbol)
+            .map(|(id, ..)| *id)
+            .collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Paper adapter
+// ---------------------------------------------------------------------------
+
+/// Adapter filling orders against the candles it is fed.
+///
+/// Market orders fill at the last candle of their symbol through the
+/// configured [`FillModel`](super::execution::FillModel); other orders
+/// work against the following candles as in the backtester. Commissions
+/// come from the [`CostModel`](super::execution::CostModel) and fills are
+/// booked in a [`portfolio::Portfolio`]. Bracket exits are not simulated:
+/// orders carrying a stop-loss or take-profit are rejected as
+/// unsupported.
+#[derive(Debug, Clone)]
+pub struct PaperAdapter {
+    config: BacktestConfig,
+    book: portfolio::Portfolio,
+    candles: BTreeMap<String, Candle>,
+    working: BTreeMap<OrderId, (String, Order)>,
+    next_id: OrderId,
+}
+
+impl PaperAdapter {
+    /// Flat account with the cash and execution models of `config`.
+    pub fn new(config: BacktestConfig) -> Self {
+        Self {
+            book: portfolio::Portfolio::new(config.initial_cash),
+            config,
+            candles: BTreeMap::new(),
+            working: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Feed a closed candle of `symbol`: work its resting orders against
+    /// it, then mark the account at its close. Returns the ids of the
+    /// orders that filled.
+    pub fn on_candle(&mut self, symbol: &str, candle: &Candle) -> Vec<OrderId> {
+        let execution = self.config.execution();
+        let mut filled = Vec::new();
+        let ids: Vec<OrderId> = self
+            .working
+            .iter()
+            .filter(|(_, (s, _))| s == symbol)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in ids {
+            let Some((_, order)) = self.working.get_mut(&id) else {
+                continue;
+            };
+            if let Some(price) = entry_fill(order, candle, &execution) {
+                let order = self.working.remove(&id).map(|(_, order)| order);
+                // An order the account cannot afford any more is dropped.
+                if let Some(order) = order {
+                    let _ = self.fill(symbol, &order, price, candle.timestamp);
+                }
+                filled.push(id);
+            }
+        }
+        self.candles.insert(symbol.to_string(), *candle);
+        self.book.mark(symbol, candle.close);
+        filled
+    }
+
+    /// The simulated account.
+    pub fn portfolio(&self) -> &portfolio::Portfolio {
+        &self.book
+    }
+
+    fn fill(
+        &mut self,
+        symbol: &str,
+        order: &Order,
+        price: f64,
+        timestamp: u64,
+    ) -> Result<(), AdapterError> {
+        let execution = self.config.execution();
+        let quantity = resolve_quantity(
+            order.quantity,
+            self.book.cash(),
+            price,
+            execution.cost.as_ref(),
+        )
+        .ok_or_else(|| AdapterError::Rejected(format!("no quantity to trade in {symbol}")))?;
+        let fee = execution.cost.commission(quantity, price);
+        let filled = match order.side {
+            Side::Long => self.book.buy(symbol, quantity, price, fee, timestamp),
+            Side::Short => self.book.sell(symbol, quantity, price, fee, timestamp),
+        };
+        filled
+            .map(|_| ())
+            .map_err(|err| AdapterError::Rejected(err.to_string()))
+    }
+}
+
+impl ExecutionAdapter for PaperAdapter {
+    fn place(&mut self, symbol: &str, order: Order) -> Result<OrderId, AdapterError> {
+        if order.stop_loss.is_some() || order.take_profit.is_some() {
+            return Err(AdapterError::Unsupported("bracket orders"));
+        }
+        if order.kind == OrderType::Market {
+            let candle = *self
+                .candles
+                .get(symbol)
+                .ok_or_else(|| AdapterError::Rejected(format!("no price for {symbol}")))?;
+            let execution = self.config.execution();
+            let price = super::market_price(&execution, order.side, true, &candle);
+            self.fill(symbol, &order, price, candle.timestamp)?;
+        }
+        self.next_id += 1;
+        if order.kind != OrderType::Market {
+            self.working
+                .insert(self.next_id, (symbol.to_string(), order));
+        }
+        Ok(self.next_id)
+    }
+
+    fn cancel(&mut self, id: OrderId) -> Result<(), AdapterError> {
+        self.working
+            .remove(&id)
+            .map(|_| ())
+            .ok_or(AdapterError::UnknownOrder(id))
+    }
+
+    fn close(&mut self, symbol: &str) -> Result<Option<OrderId>, AdapterError> {
+        let Some(position) = self.book.position(symbol) else {
+            return Ok(None);
+        };
+        let quantity = Quantity::Fixed(position.quantity);
+        let order = match position.side {
+            Side::Long => Order::short(quantity),
+            Side::Short => Order::long(quantity),
+        };
+        self.place(symbol, order).map(Some)
+    }
+
+    fn position(&self, symbol: &str) -> Result<Option<Position>, AdapterError> {
+        Ok(self.book.position(symbol).copied())
+    }
+
+    fn open_orders(&self, symbol: &str) -> Result<Vec<OrderId>, AdapterError> {
+        Ok(self
+            .working
+            .iter()
+            .filter(|(_, (s, _))| s == symbol)
+            .map(|(id, _)| *id)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: u64, close: f64, high: f64, low: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_paper_adapter_order_flow() {
+        let config = BacktestConfig {
+            fee_rate: 0.001,
+            ..BacktestConfig::default()
+        };
+        let mut venue = PaperAdapter::new(config);
+        assert!(matches!(
+            venue.apply("X", Action::EnterLong(Quantity::Fixed(1.0))),
+            Err(AdapterError::Rejected(_))
+        ));
+
+        venue.on_candle("X", &candle(1, 100.0, 101.0, 99.0));
+        let id = venue
+            .apply("X", Action::EnterLong(Quantity::Fixed(10.0)))
+            .unwrap();
+        assert!(id.is_some());
+        // Already long: ignored.
+        let again = venue.apply("X", Action::EnterLong(Quantity::Fixed(10.0)));
+        assert_eq!(again.unwrap(), None);
+
+        // Reversal closes the long at 110, then opens the short.
+        venue.on_candle("X", &candle(2, 110.0, 111.0, 109.0));
+        venue
+            .on_signal("X", Some(SignalEvent::Short), Quantity::Fixed(5.0))
+            .unwrap();
+        let (_, trade) = &venue.portfolio().trades()[0];
+        assert_eq!(trade.quantity, 10.0);
+        assert!((trade.pnl - (100.0 - 1.0 - 1.1)).abs() < 1e-9);
+        let position = venue.position("X").unwrap().unwrap();
+        assert_eq!((position.side, position.quantity), (Side::Short, 5.0));
+
+        // A resting limit fills on a later candle that reaches it.
+        let limit = venue
+            .place("Y", Order::long(Quantity::Fixed(1.0)).limit(50.0))
+            .unwrap();
+        assert_eq!(venue.open_orders("Y").unwrap(), vec![limit]);
+        assert!(venue
+            .on_candle("Y", &candle(3, 52.0, 53.0, 51.0))
+            .is_empty());
+        assert_eq!(
+            venue.on_candle("Y", &candle(4, 50.0, 51.0, 49.0)),
+            vec![limit]
+        );
+        assert!(venue.position("Y").unwrap().is_some());
+
+        assert!(matches!(
+            venue.cancel(limit),
+            Err(AdapterError::UnknownOrder(_))
+        ));
+        let bracket = Order::long(Quantity::Fixed(1.0)).with_stop_loss(40.0);
+        assert!(matches!(
+            venue.place("Y", bracket),
+            Err(AdapterError::Unsupported(_))
+        ));
+        venue.apply("X", Action::Exit).unwrap();
+        assert!(venue.position("X").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_noop_adapter_records_orders() {
+        let mut venue = NoopAdapter::new();
+        venue
+            .apply(
+                "X",
+                Action::Submit(Order::short(Quantity::AllCash).stop(9.0)),
+            )
+            .unwrap();
+        venue
+            .on_signal("X", Some(SignalEvent::Long), Quantity::AllCash)
+            .unwrap();
+        assert_eq!(venue.orders().len(), 2);
+        assert_eq!(venue.apply("X", Action::Exit).unwrap(), None);
+        venue.apply("X", Action::Cancel).unwrap();
+        assert!(venue.orders().is_empty());
+        assert!(venue.set_exits("X", Some(1.0), None).is_err());
+    }
+}
@@ -0,0 +1,606 @@
+//! # Event bus
+//!
+//! An event-driven way to assemble a trading loop from independent
+//! components. Each stage of the flow is an [`Event`]:
+//!
+//! | Event | Meaning |
+//! |---|---|
+//! | [`CandleEvent`] | a closed candle of a symbol |
+//! | [`IndicatorEvent`] | an indicator value computed from that candle |
+//! | [`SignalFired`] | a [`SignalEvent`] raised by a signal |
+//! | [`OrderEvent`] | an [`Action`] to execute for a symbol |
+//! | [`FillEvent`] | an execution reported by the venue |
+//! | [`RiskViolation`] | an order refused by a pre-trade risk check (see [`risk_checks`](super::risk_checks)) |
+//!
+//! [`EventBus::publish`] passes an event through the registered
+//! [`Handler`]s in order. A handler may publish follow-up events, which
+//! are processed after the current one, first in first out, until the
+//! queue is empty. It may also return [`Propagation::Stop`] to keep the
+//! event from the handlers after it — a risk check placed before the
+//! execution stage vetoes an order that way.
+//!
+//! Built-in stages cover the ends of the flow: [`IndicatorStage`] turns
+//! candles into indicator events, [`PaperStage`] executes orders on a
+//! [`PaperTrader`] and reports fills, and [`MetricsStage`] counts the
+//! flow for monitoring. Anything in between — signals, sizing, logging,
+//! risk — is a handler of your own; closures
+//! `FnMut(&Event, &mut Vec<Event>) -> Propagation` are handlers.
+//!
+//! ```
+//! use rsta::backtest::events::{
+//!     Event, EventBus, IndicatorStage, OrderEvent, PaperStage, Propagation,
+//! };
+//! use rsta::backtest::paper::PaperTrader;
+//! use rsta::backtest::{Action, BacktestConfig, Quantity};
+//! use rsta::indicators::factory::IndicatorSpec;
+//! use rsta::indicators::Candle;
+//!
+//! let mut bus = EventBus::new();
+//! bus.add(IndicatorStage::new(vec![("sma", IndicatorSpec::new("sma").param("period", 3.0))]).unwrap());
+//! // Strategy: buy when the close is above its 3-bar average.
+//! bus.add(|event: &Event, out: &mut Vec<Event>| {
+//!     if let Event::Indicator(ind) = event {
+//!         if ind.close > ind.value.primary() {
+//!             out.push(Event::Order(OrderEvent {
+//!                 symbol: ind.symbol.clone(),
+//!                 timestamp: ind.timestamp,
+//!                 action: Action::EnterLong(Quantity::Fixed(1.0)),
+//!             }));
+//!         }
+//!     }
+//!     Propagation::Continue
+//! });
+//! bus.add(PaperStage::new("BTC", PaperTrader::new(BacktestConfig::default())));
+//!
+//! let mut fills = 0;
+//! for (t, close) in [10.0, 10.0, 10.0, 12.0, 13.0].into_iter().enumerate() {
+//!     let candle = Candle { timestamp: t as u64, open: close, high: close, low: close, close, volume: 1.0 };
+//!     let events = bus.publish(Event::candle("BTC", candle));
+//!     fills += events.iter().filter(|e| matches!(e, Event::Fill(_))).count();
+//! }
+//! assert_eq!(fills, 1); // the second entry is ignored: already long
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+use crate::indicators::any_indicator::IndicatorValue;
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::{Candle, IndicatorError};
+use crate::instrument;
+use crate::monitor::{self, MetricsSink};
+use crate::signals::SignalEvent;
+
+use super::execution::OrderSide;
+use super::paper::PaperTrader;
+use super::risk_checks::RiskViolation;
+use super::strategy_indicators::StrategyIndicators;
+use super::{Action, Side};
+
+/// A closed candle of `symbol`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleEvent {
+    /// Instrument identifier.
+    pub symbol: String,
+    /// The candle.
+    pub candle: Candle,
+}
+
+/// A value of the indicator registered as `key`, computed from a candle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorEvent {
+    /// Instrument identifier.
+    pub symbol: String,
+    /// Timestamp of the candle the value was computed from.
+    pub timestamp: u64,
+    /// Close of that candle.
+    pub close: f64,
+    /// Key the indicator was declared under.
+    pub key: &'static str,
+    /// The value.
+    pub value: IndicatorValue,
+}
+
+/// A signal raised for `symbol`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalFired {
+    /// Instrument identifier.
+    pub symbol: String,
+    /// Timestamp of the candle the signal was raised on.
+    pub timestamp: u64,
+    /// The signal's event.
+    pub event: SignalEvent,
+}
+
+/// An action to execute for `symbol`.
+#[derive(Debug, Clone)]
+pub struct OrderEvent {
+    /// Instrument identifier.
+    pub symbol: String,
+    /// Timestamp of the candle the decision was taken on.
+    pub timestamp: u64,
+    /// What to do.
+    pub action: Action,
+}
+
+/// An execution of `quantity` units of `symbol` at `price`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillEvent {
+    /// Instrument identifier.
+    pub symbol: String,
+    /// Timestamp of the fill.
+    pub timestamp: u64,
+    /// Buy or sell.
+    pub side: OrderSide,
+    /// Units filled.
+    pub quantity: f64,
+    /// Fill price.
+    pub price: f64,
+}
+
+/// Anything that flows through an [`EventBus`].
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// A closed candle.
+    Candle(CandleEvent),
+    /// An indicator value.
+    Indicator(IndicatorEvent),
+    /// A signal.
+    Signal(SignalFired),
+    /// An order to execute.
+    Order(OrderEvent),
+    /// An execution.
+    Fill(FillEvent),
+    /// An order refused by a risk check.
+    Risk(RiskViolation),
+}
+
+impl Event {
+    /// Candle event for `symbol`.
+    pub fn candle(symbol: &str, candle: Candle) -> Self {
+        Event::Candle(CandleEvent {
+            symbol: symbol.to_string(),
+            candle,
+        })
+    }
+
+    /// Symbol the event is about.
+    pub fn symbol(&self) -> &str {
+        match self {
+            Event::Candle(e) => &e.symbol,
+            Event::Indicator(e) => &e.symbol,
+            Event::Signal(e) => &e.symbol,
+            Event::Order(e) => &e.symbol,
+            Event::Fill(e) => &e.symbol,
+            Event::Risk(e) => &e.symbol,
+        }
+    }
+
+    /// Timestamp of the candle or fill behind the event.
+    pub fn timestamp(&self) -> u64 {
+        match self {
+            Event::Candle(e) => e.candle.timestamp,
+            Event::Indicator(e) => e.timestamp,
+            Event::Signal(e) => e.timestamp,
+            Event::Order(e) => e.timestamp,
+            Event::Fill(e) => e.timestamp,
+            Event::Risk(e) => e.timestamp,
+        }
+    }
+}
+
+/// Whether an event goes on to the next handlers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    /// Pass the event to the next handler.
+    Continue,
+    /// Drop the event here.
+    Stop,
+}
+
+/// A component on the bus.
+pub trait Handler {
+    /// Handle `event`, pushing any follow-up events onto `out`.
+    fn handle(&mut self, event: &Event, out: &mut Vec<Event>) -> Propagation;
+}
+
+impl<F> Handler for F
+where
+    F: FnMut(&Event, &mut Vec<Event>) -> Propagation,
+{
+    fn handle(&mut self, event: &Event, out: &mut Vec<Event>) -> Propagation {
+        self(event, out)
+    }
+}
+
+/// Handlers that events pass through, in registration order.
+#[derive(Default)]
+pub struct EventBus {
+    handlers: Vec<Box<dyn Handler>>,
+}
+
+impl std::fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("handlers", &self.handlers.len())
+            .finish()
+    }
+}
+
+impl EventBus {
+    /// Bus without handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `handler`; it sees events after the handlers already added.
+    pub fn add(&mut self, handler: impl Handler + 'static) -> &mut Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    /// Insert `handler` at `index`, before the handler currently there,
+    /// e.g. a risk check in front of the execution stage.
+    ///
+    /// # Panics
+    /// If `index > self.len()`.
+    pub fn insert(&mut self, index: usize, handler: impl Handler + 'static) -> &mut Self {
+        self.handlers.insert(index, Box::new(handler));
+        self
+    }
+
+    /// Number of handlers.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// `true` if no handler is registered.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Process `event` and every follow-up it causes. Returns all of them
+    /// in processing order, `event` first, including those a handler
+    /// stopped.
+    ///
+    /// Handlers that keep answering each other's events never return;
+    /// avoid publishing an event kind from a handler of that same kind.
+    pub fn publish(&mut self, event: Event) -> Vec<Event> {
+        let mut queue = VecDeque::from([event]);
+        let mut processed = Vec::new();
+        let mut out = Vec::new();
+        while let Some(event) = queue.pop_front() {
+            instrument::trace!(event = ?event, "dispatching");
+            for handler in &mut self.handlers {
+                let flow = handler.handle(&event, &mut out);
+                queue.extend(out.drain(..));
+                if flow == Propagation::Stop {
+                    instrument::debug!(event = ?event, "propagation stopped");
+                    break;
+                }
+            }
+            processed.push(event);
+        }
+        processed
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Built-in stages
+// ---------------------------------------------------------------------------
+
+/// Computes declared indicators from candle events, per symbol, and
+/// publishes an [`IndicatorEvent`] for each value.
+pub struct IndicatorStage {
+    specs: Vec<(&'static str, IndicatorSpec)>,
+    symbols: BTreeMap<String, StrategyIndicators>,
+}
+
+impl std::fmt::Debug for IndicatorStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndicatorStage")
+            .field("specs", &self.specs)
+            .field("symbols", &self.symbols)
+            .finish()
+    }
+}
+
+impl IndicatorStage {
+    /// Stage computing `specs`, as `(key, spec)` pairs like
+    /// [`Strategy::indicators`](super::Strategy::indicators)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The stage, or the error of a spec that does not build
+    pub fn new(specs: Vec<(&'static str, IndicatorSpec)>) -> Result<Self, IndicatorError> {
+        StrategyIndicators::build(specs.clone())?;
+        Ok(Self {
+            specs,
+            symbols: BTreeMap::new(),
+        })
+    }
+
+    /// Indicators of `symbol`, once it has had a candle.
+    pub fn indicators(&self, symbol: &str) -> Option<&StrategyIndicators> {
+        self.symbols.get(symbol)
+    }
+}
+
+impl Handler for IndicatorStage {
+    fn handle(&mut self, event: &Event, out: &mut Vec<Event>) -> Propagation {
+        let Event::Candle(CandleEvent { symbol, candle }) = event else {
+            return Propagation::Continue;
+        };
+        if !self.symbols.contains_key(symbol) {
+            let Ok(built) = StrategyIndicators::build(self.specs.clone()) else {
+                return Propagation::Continue;
+            };
+            self.symbols.insert(symbol.clone(), built);
+        }
+        let indicators = self.symbols.get_mut(symbol).expect("inserted above");
+        // A candle the indicators reject produces no values.
+        if indicators.update(candle).is_err() {
+            return Propagation::Continue;
+        }
+        for (key, _) in &self.specs {
+            if let Some(value) = indicators.get(key) {
+                out.push(Event::Indicator(IndicatorEvent {
+                    symbol: symbol.clone(),
+                    timestamp: candle.timestamp,
+                    close: candle.close,
+                    key,
+                    value,
+                }));
+            }
+        }
+        Propagation::Continue
+    }
+}
+
+/// Executes the order events of one symbol on a [`PaperTrader`] and
+/// publishes a [`FillEvent`] for every resulting execution.
+///
+/// Candle events of the symbol are fed to the trader as they arrive, so
+/// resting orders work against them; order events then apply at that
+/// candle's close.
+#[derive(Debug, Clone)]
+pub struct PaperStage {
+    symbol: String,
+    trader: PaperTrader,
+}
+
+impl PaperStage {
+    /// Stage trading `symbol` on `trader`.
+    pub fn new(symbol: &str, trader: PaperTrader) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            trader,
+        }
+    }
+
+    /// The simulated account.
+    pub fn trader(&self) -> &PaperTrader {
+        &self.trader
+    }
+
+    /// Fills implied by the change from `before` to the current state.
+    fn fills(&self, before: (usize, Option<super::Position>), timestamp: u64) -> Vec<Event> {
+        let portfolio = self.trader.portfolio();
+        let fill = |side: Side, opening: bool, quantity: f64, price: f64| {
+            let side = match (side, opening) {
+                (Side::Long, true) | (Side::Short, false) => OrderSide::Buy,
+                (Side::Long, false) | (Side::Short, true) => OrderSide::Sell,
+            };
+            Event::Fill(FillEvent {
+                symbol: self.symbol.clone(),
+                timestamp,
+                side,
+                quantity,
+                price,
+            })
+        };
+        let mut fills: Vec<Event> = portfolio.trades[before.0..]
+            .iter()
+            .map(|t| fill(t.side, false, t.quantity, t.exit_price))
+            .collect();
+        if let Some(pos) = portfolio.position {
+            let opened = before.1.is_none_or(|old| {
+                old.entry_timestamp != pos.entry_timestamp || old.side != pos.side
+            });
+            if opened {
+                fills.push(fill(pos.side, true, pos.quantity, pos.entry_price));
+            }
+        }
+        fills
+    }
+}
+
+impl Handler for PaperStage {
+    fn handle(&mut self, event: &Event, out: &mut Vec<Event>) -> Propagation {
+        if event.symbol() != self.symbol {
+            return Propagation::Continue;
+        }
+        let before = (self.trader.portfolio().trades.len(), self.trader.position());
+        match event {
+            Event::Candle(e) => {
+                self.trader.act(&e.candle, Action::Hold);
+            }
+            Event::Order(e) => {
+                self.trader.submit(e.action);
+            }
+            _ => return Propagation::Continue,
+        }
+        out.extend(self.fills(before, event.timestamp()));
+        Propagation::Continue
+    }
+}
+
+/// Counts the events that reach it in a [`MetricsSink`]: candles, signals
+/// other than `Hold`, orders, fills and refused orders per symbol, plus
+/// the timestamp of each symbol's last candle (see [`monitor`]). Add it
+/// first to see every event, including orders a later stage stops.
+#[derive(Clone)]
+pub struct MetricsStage {
+    sink: Arc<dyn MetricsSink>,
+}
+
+impl std::fmt::Debug for MetricsStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsStage").finish_non_exhaustive()
+    }
+}
+
+impl MetricsStage {
+    /// Stage reporting to `sink`.
+    pub fn new(sink: Arc<dyn MetricsSink>) -> Self {
+        Self { sink }
+    }
+}
+
+impl Handler for MetricsStage {
+    fn handle(&mut self, event: &Event, _out: &mut Vec<Event>) -> Propagation {
+        let symbol = [("symbol", event.symbol())];
+        match event {
+            Event::Candle(e) => {
+                self.sink.increment(monitor::CANDLES_PROCESSED, &symbol, 1);
+                self.sink.gauge(
+                    monitor::LAST_CANDLE_TIMESTAMP,
+                    &symbol,
+                    e.candle.timestamp as f64,
+                );
+            }
+            Event::Signal(e) if e.event != SignalEvent::Hold => {
+                self.sink.increment(monitor::SIGNALS_FIRED, &symbol, 1);
+            }
+            Event::Order(_) => self.sink.increment(monitor::ORDERS, &symbol, 1),
+            Event::Fill(_) => self.sink.increment(monitor::FILLS, &symbol, 1),
+            Event::Risk(v) => {
+                let rule = format!("{:?}", v.rule);
+                let labels = [("symbol", event.symbol()), ("rule", rule.as_str())];
+                self.sink.increment(monitor::ORDERS_REFUSED, &labels, 1);
+            }
+            _ => {}
+        }
+        Propagation::Continue
+    }
+}
+
+#[cfg(all(test, feature = "trend"))]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, Quantity};
+    use crate::monitor::InMemoryMetrics;
+
+    fn candle(timestamp: u64, close: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_stages_run_in_order_and_risk_checks_veto_orders() {
+        let mut bus = EventBus::new();
+        let specs = vec![("sma", IndicatorSpec::new("sma").param("period", 2.0))];
+        bus.add(IndicatorStage::new(specs).unwrap());
+        // Signal: close above its average.
+        bus.add(|event: &Event, out: &mut Vec<Event>| {
+            if let Event::Indicator(e) = event {
+                let signal = if e.close > e.value.primary() {
+                    SignalEvent::Long
+                } else {
+                    SignalEvent::Exit
+                };
+                out.push(Event::Signal(SignalFired {
+                    symbol: e.symbol.clone(),
+                    timestamp: e.timestamp,
+                    event: signal,
+                }));
+            }
+            Propagation::Continue
+        });
+        // Orders from signals.
+        bus.add(|event: &Event, out: &mut Vec<Event>| {
+            if let Event::Signal(e) = event {
+                let action = match e.event {
+                    SignalEvent::Long => Action::EnterLong(Quantity::Fixed(2.0)),
+                    _ => Action::Exit,
+                };
+                out.push(Event::Order(OrderEvent {
+                    symbol: e.symbol.clone(),
+                    timestamp: e.timestamp,
+                    action,
+                }));
+            }
+            Propagation::Continue
+        });
+        let execution = bus.len();
+        bus.add(PaperStage::new(
+            "X",
+            PaperTrader::new(BacktestConfig::default()),
+        ));
+        // Risk check in front of execution: no new entries from bar 3 on.
+        bus.insert(execution, |event: &Event, _: &mut Vec<Event>| match event {
+            Event::Order(OrderEvent {
+                action: Action::EnterLong(_),
+                ..
+            }) if event.timestamp() >= 3 => Propagation::Stop,
+            _ => Propagation::Continue,
+        });
+        let metrics = Arc::new(InMemoryMetrics::new());
+        bus.insert(0, MetricsStage::new(metrics.clone()));
+
+        let first = bus.publish(Event::candle("X", candle(0, 100.0)));
+        assert_eq!(first.len(), 1);
+
+        let events = bus.publish(Event::candle("X", candle(1, 104.0)));
+        let kinds: Vec<&str> = events
+            .iter()
+            .map(|e| match e {
+                Event::Candle(_) => "candle",
+                Event::Indicator(_) => "indicator",
+                Event::Signal(_) => "signal",
+                Event::Order(_) => "order",
+                Event::Fill(_) => "fill",
+                Event::Risk(_) => "risk",
+            })
+            .collect();
+        assert_eq!(kinds, ["candle", "indicator", "signal", "order", "fill"]);
+        let Event::Fill(fill) = &events[4] else {
+            unreachable!()
+        };
+        assert_eq!(
+            (fill.side, fill.quantity, fill.price),
+            (OrderSide::Buy, 2.0, 104.0)
+        );
+
+        // Falling close: exit fill.
+        let events = bus.publish(Event::candle("X", candle(2, 102.0)));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            Event::Fill(FillEvent {
+                side: OrderSide::Sell,
+                ..
+            })
+        )));
+        // Vetoed entry: no fill.
+        let events = bus.publish(Event::candle("X", candle(3, 110.0)));
+        assert!(!events.iter().any(|e| matches!(e, Event::Fill(_))));
+        // Other symbols do not reach the trader.
+        let events = bus.publish(Event::candle("Y", candle(0, 1.0)));
+        assert_eq!(events.len(), 1);
+
+        let x = [("symbol", "X")];
+        assert_eq!(metrics.counter_value(monitor::CANDLES_PROCESSED, &x), 4);
+        assert_eq!(metrics.counter_value(monitor::SIGNALS_FIRED, &x), 3);
+        assert_eq!(metrics.counter_value(monitor::ORDERS, &x), 3);
+        assert_eq!(metrics.counter_value(monitor::FILLS, &x), 2);
+        assert_eq!(
+            metrics.gauge_value(monitor::LAST_CANDLE_TIMESTAMP, &x),
+            Some(3.0)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! # Maximum adverse / favorable excursion
+//!
+//! How far price moved against a position (maximum adverse excursion,
+//! MAE) and in its favor (maximum favorable excursion, MFE) while it was
+//! open. Across many trades, the MAE of the winners shows how tight a
+//! stop can be without cutting them, and the MFE of all trades how much
+//! profit a target leaves on the table.
+//!
+//! The [`Backtester`](super::Backtester) tracks both on every open
+//! [`Position`](super::Position) and records them on each closed
+//! [`Trade`](super::Trade). [`ExcursionTracker`] does the same for a live
+//! position: create it at the fill and feed it every bar (or tick) until
+//! the exit.
+//!
+//! ```
+//! use rsta::backtest::excursion::ExcursionTracker;
+//! use rsta::backtest::Side;
+//! use rsta::indicators::Candle;
+//!
+//! let mut tracker = ExcursionTracker::new(Side::Long, 100.0);
+//! for (high, low) in [(103.0, 98.0), (108.0, 101.0), (105.0, 99.5)] {
+//!     let candle = Candle { timestamp: 0, open: low, high, low, close: high, volume: 1.0 };
+//!     tracker.update(&candle);
+//! }
+//! assert_eq!(tracker.mae(), 2.0);
+//! assert_eq!(tracker.mfe(), 8.0);
+//! assert!((tracker.mfe_pct() - 0.08).abs() < 1e-12);
+//! ```
+
+use crate::backtest::Side;
+use crate::indicators::Candle;
+
+/// Running MAE / MFE of one position.
+///
+/// Excursions are price distances per unit from the entry price, `0` or
+/// more: a long's MAE is how far the lowest low fell below the entry, its
+/// MFE how far the highest high rose above it, and the reverse for a
+/// short.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExcursionTracker {
+    side: Side,
+    entry_price: f64,
+    mae: f64,
+    mfe: f64,
+}
+
+impl ExcursionTracker {
+    /// Start tracking a position filled at `entry_price`.
+    pub fn new(side: Side, entry_price: f64) -> Self {
+        Self {
+            side,
+            entry_price,
+            mae: 0.0,
+            mfe: 0.0,
+        }
+    }
+
+    /// Account for a bar traded while the position was open.
+    pub fn update(&mut self, candle: &Candle) {
+        self.update_price(candle.high);
+        self.update_price(candle.low);
+    }
+
+    /// Account for a single traded price, e.g. a tick or a fill.
+    pub fn update_price(&mut self, price: f64) {
+        let gain = match self.side {
+            Side::Long => price - self.entry_price,
+            Side::Short => self.entry_price - price,
+        };
+        self.mfe = self.mfe.max(gain);
+        self.mae = self.mae.max(-gain);
+    }
+
+    /// Direction of the tracked position.
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Entry price the excursions are measured from.
+    pub fn entry_price(&self) -> f64 {
+        self.entry_price
+    }
+
+    /// Maximum adverse excursion, in price units.
+    pub fn mae(&self) -> f64 {
+        self.mae
+    }
+
+    /// Maximum favorable excursion, in price units.
+    pub fn mfe(&self) -> f64 {
+        self.mfe
+    }
+
+    /// MAE as a fraction of the entry price (`0.02` = 2%).
+    pub fn mae_pct(&self) -> f64 {
+        fraction(self.mae, self.entry_price)
+    }
+
+    /// MFE as a fraction of the entry price.
+    pub fn mfe_pct(&self) -> f64 {
+        fraction(self.mfe, self.entry_price)
+    }
+}
+
+/// `distance / entry_price`, `0` for a zero entry price.
+pub(crate) fn fraction(distance: f64, entry_price: f64) -> f64 {
+    if entry_price != 0.0 {
+        distance / entry_price
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_excursions() {
+        let mut tracker = ExcursionTracker::new(Side::Short, 50.0);
+        assert_eq!((tracker.mae(), tracker.mfe()), (0.0, 0.0));
+        for price in [51.0, 47.5, 52.5, 49.0] {
+            tracker.update_price(price);
+        }
+        assert_eq!(tracker.mae(), 2.5);
+        assert_eq!(tracker.mfe(), 2.5);
+        assert!((tracker.mae_pct() - 0.05).abs() < 1e-12);
+        assert_eq!(tracker.side(), Side::Short);
+        assert_eq!(ExcursionTracker::new(Side::Long, 0.0).mfe_pct(), 0.0);
+    }
+}
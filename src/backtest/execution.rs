@@ -0,0 +1,318 @@
+//! # Execution models
+//!
+//! How the [`Backtester`](super::Backtester) turns an order into a fill
+//! is split in two pluggable parts, so execution assumptions can change
+//! without touching the engine:
+//!
+//! - a [`FillModel`] decides the **price**: where a market order fills
+//!   and whether (and where) a limit or stop price is reached within a
+//!   bar;
+//! - a [`CostModel`] decides the **commission** charged on a fill.
+//!
+//! Set them with [`BacktestConfig::with_fill_model`] and
+//! [`BacktestConfig::with_cost_model`]. Without them the engine uses
+//! [`CloseFill`] with [`BacktestConfig::slippage`] and
+//! [`PercentCommission`] with [`BacktestConfig::fee_rate`].
+//!
+//! Built-in models:
+//!
+//! | Model | Behaviour |
+//! |---|---|
+//! | [`CloseFill`] | Market orders at the close, moved against the order by a proportional slippage |
+//! | [`SpreadFill`] | Market orders at the close plus or minus half a quoted spread |
+//! | [`PercentCommission`] | A fraction of the traded notional |
+//! | [`PerUnitCommission`] | A fixed amount per share / contract, with an optional minimum per order |
+//!
+//! ```
+//! use rsta::backtest::execution::{PerUnitCommission, SpreadFill};
+//! use rsta::backtest::BacktestConfig;
+//!
+//! // US equities: 1 cent spread, $0.005 per share with a $1 minimum.
+//! let config = BacktestConfig::default()
+//!     .with_fill_model(SpreadFill::new(0.01).unwrap())
+//!     .with_cost_model(PerUnitCommission::new(0.005).unwrap().with_minimum(1.0));
+//! # let _ = config;
+//! ```
+//!
+//! [`BacktestConfig::with_fill_model`]: super::BacktestConfig::with_fill_model
+//! [`BacktestConfig::with_cost_model`]: super::BacktestConfig::with_cost_model
+//! [`BacktestConfig::slippage`]: super::BacktestConfig::slippage
+//! [`BacktestConfig::fee_rate`]: super::BacktestConfig::fee_rate
+
+use std::fmt::Debug;
+
+use crate::indicators::{Candle, IndicatorError};
+
+/// Direction of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrderSide {
+    /// Opens a long or covers a short.
+    Buy,
+    /// Closes a long or opens a short.
+    Sell,
+}
+
+/// Decides fill prices.
+pub trait FillModel: Debug + Send + Sync {
+    /// Price at which a market order placed on `candle` fills. The engine
+    /// executes market orders at the bar's close.
+    fn market_fill(&self, side: OrderSide, candle: &Candle) -> f64;
+
+    /// Price at which a limit order resting through `candle` fills, or
+    /// `None` if the bar never reached `limit`.
+    ///
+    /// By default a buy fills when the low trades at or below the limit,
+    /// at the limit or at the open if the bar gapped through it; sells
+    /// mirror this on the high.
+    fn limit_fill(&self, side: OrderSide, limit: f64, candle: &Candle) -> Option<f64> {
+        match side {
+            OrderSide::Buy if candle.low <= limit => Some(limit.min(candle.open)),
+            OrderSide::Sell if candle.high >= limit => Some(limit.max(candle.open)),
+            _ => None,
+        }
+    }
+
+    /// Price at which a stop order resting through `candle` fills, or
+    /// `None` if the bar never traded at `stop`.
+    ///
+    /// By default a buy stop triggers when the high reaches the stop and
+    /// fills at the stop, or at the open if the bar gapped past it; sell
+    /// stops mirror this on the low.
+    fn stop_fill(&self, side: OrderSide, stop: f64, candle: &Candle) -> Option<f64> {
+        match side {
+            OrderSide::Buy if candle.high >= stop => Some(stop.max(candle.open)),
+            OrderSide::Sell if candle.low <= stop => Some(stop.min(candle.open)),
+            _ => None,
+        }
+    }
+}
+
+/// Decides commissions.
+pub trait CostModel: Debug + Send + Sync {
+    /// Commission, in account currency, on a fill of `quantity` units at
+    /// `price`.
+    fn commission(&self, quantity: f64, price: f64) -> f64;
+
+    /// Largest quantity whose cost at `price`, commission included, fits in
+    /// `cash`. Sizes the cash-based [`Quantity`](super::Quantity) rules.
+    ///
+    /// The default charges the commission of the all-cash quantity, which
+    /// errs on the small side for any commission that grows with size.
+    fn max_quantity(&self, cash: f64, price: f64) -> f64 {
+        let gross = cash / price;
+        ((cash - self.commission(gross, price)) / price).max(0.0)
+    }
+}
+
+/// Market orders fill at the close, moved against the order by a fraction
+/// of the price: buys at `close * (1 + slippage)`, sells at
+/// `close * (1 - slippage)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CloseFill {
+    pub(crate) slippage: f64,
+}
+
+impl CloseFill {
+    /// Create a close fill model
+    ///
+    /// # Arguments
+    /// * `slippage` - Proportional slippage, e.g. `0.0005` for 5 bps (must be at least 0)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new model or an error
+    pub fn new(slippage: f64) -> Result<Self, IndicatorError> {
+        validate_non_negative("slippage", slippage)?;
+        Ok(Self { slippage })
+    }
+}
+
+impl FillModel for CloseFill {
+    fn market_fill(&self, side: OrderSide, candle: &Candle) -> f64 {
+        match side {
+            OrderSide::Buy => candle.close * (1.0 + self.slippage),
+            OrderSide::Sell => candle.close * (1.0 - self.slippage),
+        }
+    }
+}
+
+/// Market orders cross a bid/ask spread centred on the close: buys fill
+/// at `close + spread / 2`, sells at `close - spread / 2`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadFill {
+    spread: f64,
+}
+
+impl SpreadFill {
+    /// Create a spread fill model
+    ///
+    /// # Arguments
+    /// * `spread` - Quoted spread in price units (must be at least 0)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new model or an error
+    pub fn new(spread: f64) -> Result<Self, IndicatorError> {
+        validate_non_negative("spread", spread)?;
+        Ok(Self { spread })
+    }
+}
+
+impl FillModel for SpreadFill {
+    fn market_fill(&self, side: OrderSide, candle: &Candle) -> f64 {
+        match side {
+            OrderSide::Buy => candle.close + self.spread / 2.0,
+            OrderSide::Sell => candle.close - self.spread / 2.0,
+        }
+    }
+}
+
+/// Commission as a fraction of the traded notional.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentCommission {
+    pub(crate) rate: f64,
+}
+
+impl PercentCommission {
+    /// Create a percent commission
+    ///
+    /// # Arguments
+    /// * `rate` - Fraction of the notional, e.g. `0.001` for 10 bps (must be at least 0)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new model or an error
+    pub fn new(rate: f64) -> Result<Self, IndicatorError> {
+        validate_non_negative("rate", rate)?;
+        Ok(Self { rate })
+    }
+}
+
+impl CostModel for PercentCommission {
+    fn commission(&self, quantity: f64, price: f64) -> f64 {
+        quantity * price * self.rate
+    }
+
+    fn max_quantity(&self, cash: f64, price: f64) -> f64 {
+        (cash / (price * (1.0 + self.rate))).max(0.0)
+    }
+}
+
+/// Fixed commission per unit traded, with an optional minimum per order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerUnitCommission {
+    per_unit: f64,
+    minimum: f64,
+}
+
+impl PerUnitCommission {
+    /// Create a per-unit commission
+    ///
+    /// # Arguments
+    /// * `per_unit` - Amount charged per share or contract (must be at least 0)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new model or an error
+    pub fn new(per_unit: f64) -> Result<Self, IndicatorError> {
+        validate_non_negative("per_unit", per_unit)?;
+        Ok(Self {
+            per_unit,
+            minimum: 0.0,
+        })
+    }
+
+    /// Charge at least `minimum` per order (0 by default).
+    pub fn with_minimum(mut self, minimum: f64) -> Self {
+        self.minimum = minimum.max(0.0);
+        self
+    }
+}
+
+impl CostModel for PerUnitCommission {
+    fn commission(&self, quantity: f64, _price: f64) -> f64 {
+        (quantity * self.per_unit).max(self.minimum)
+    }
+
+    fn max_quantity(&self, cash: f64, price: f64) -> f64 {
+        let quantity = cash / (price + self.per_unit);
+        if quantity * self.per_unit >= self.minimum {
+            quantity
+        } else {
+            ((cash - self.minimum) / price).max(0.0)
+        }
+    }
+}
+
+fn validate_non_negative(name: &str, value: f64) -> Result<(), IndicatorError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(IndicatorError::invalid_parameter(
+            name,
+            value,
+            "must be a finite value of at least 0",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_fill_models() {
+        let candle = bar(100.0, 104.0, 97.0, 102.0);
+        let close = CloseFill::new(0.01).unwrap();
+        assert!((close.market_fill(OrderSide::Buy, &candle) - 103.02).abs() < 1e-9);
+        assert!((close.market_fill(OrderSide::Sell, &candle) - 100.98).abs() < 1e-9);
+        let spread = SpreadFill::new(0.5).unwrap();
+        assert_eq!(spread.market_fill(OrderSide::Buy, &candle), 102.25);
+        assert_eq!(spread.market_fill(OrderSide::Sell, &candle), 101.75);
+
+        // Limits: reached, missed, and gapped through at the open
+        assert_eq!(spread.limit_fill(OrderSide::Buy, 98.0, &candle), Some(98.0));
+        assert_eq!(spread.limit_fill(OrderSide::Buy, 96.0, &candle), None);
+        assert_eq!(
+            spread.limit_fill(OrderSide::Buy, 101.0, &candle),
+            Some(100.0)
+        );
+        assert_eq!(
+            spread.limit_fill(OrderSide::Sell, 103.0, &candle),
+            Some(103.0)
+        );
+        assert_eq!(
+            spread.limit_fill(OrderSide::Sell, 99.0, &candle),
+            Some(100.0)
+        );
+
+        assert!(CloseFill::new(-0.1).is_err());
+        assert!(SpreadFill::new(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_cost_models_and_sizing() {
+        let percent = PercentCommission::new(0.01).unwrap();
+        assert_eq!(percent.commission(10.0, 50.0), 5.0);
+        let quantity = percent.max_quantity(1_010.0, 10.0);
+        assert!((quantity - 100.0).abs() < 1e-9);
+
+        let per_unit = PerUnitCommission::new(0.01).unwrap().with_minimum(1.0);
+        assert_eq!(per_unit.commission(10.0, 50.0), 1.0);
+        assert_eq!(per_unit.commission(500.0, 50.0), 5.0);
+        // Below the minimum, the whole minimum comes off the cash
+        assert!((per_unit.max_quantity(101.0, 10.0) - 10.0).abs() < 1e-9);
+        let quantity = per_unit.max_quantity(10_010.0, 1.0);
+        let cost = quantity * 1.0 + per_unit.commission(quantity, 1.0);
+        assert!((cost - 10_010.0).abs() < 1e-6);
+        assert!(PercentCommission::new(-1.0).is_err());
+    }
+}
@@ -1,20 +1,21 @@
 //! # Backtesting Engine
 //!
-//! Single-asset, single-position backtester. The strategy receives one
+//! Single-asset, single-position backtester (see [`universe`] for several
+//! symbols sharing one account). The strategy receives one
 //! [`Candle`] at a time plus a read-only [`Context`] and returns an
 //! [`Action`] describing what to do this bar (enter long, enter short,
 //! exit, or hold).
 //!
-//! Execution is at the bar's close, with configurable proportional fees
-//! and slippage. Sufficient for validating signal/strategy ideas; not a
-//! production live-trading engine.
+//! Market actions execute at the bar's close; limit, stop, stop-limit and
+//! bracket orders (see [`orders`]) fill within later bars. Fill prices and
+//! commissions come from pluggable [`FillModel`](execution::FillModel) /
+//! [`CostModel`](execution::CostModel) implementations, proportional
+//! slippage and fees by default (see [`execution`]). Sufficient for
+//! validating signal/strategy ideas; not a production live-trading engine.
 //!
 //! ## Out of scope
 //!
-//! - Multiple assets / portfolios
 //! - Pyramiding (adding to an existing position)
-//! - Limit / stop / take-profit orders inside the engine — strategies can
-//!   implement them by reading bar OHLC and emitting `Exit` themselves
 //! - Margin, borrow rates, dividends, corporate actions
 //!
 //! ## Example
@@ -46,13 +47,37 @@
 //!
 //! let bt = Backtester::new(BacktestConfig::default());
 //! let mut strat = BuyAndHold { entered: false };
-//! let result = bt.run(&candles, &mut strat);
+//! let result = bt.run(&candles, &mut strat).unwrap();
 //! assert!(result.metrics.final_equity > 10_000.0); // bought low, held to high
 //! ```
 
+pub mod adapter;
+pub mod events;
+pub mod excursion;
+pub mod execution;
+pub mod monte_carlo;
+pub mod optimize;
+pub mod orders;
+pub mod paper;
+pub mod r_multiple;
+pub mod report;
+pub mod risk_checks;
 pub mod sizing;
+pub mod stops;
+pub mod strategy_indicators;
+pub mod universe;
 
-use crate::indicators::Candle;
+use std::sync::Arc;
+
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::{Candle, IndicatorError, Tick};
+use crate::instrument;
+use crate::returns;
+
+use self::excursion::ExcursionTracker;
+use self::execution::{CloseFill, CostModel, FillModel, OrderSide, PercentCommission};
+use self::orders::{price_legs, IntrabarPolicy, Order, OrderType};
+use self::strategy_indicators::StrategyIndicators;
 
 // ---------------------------------------------------------------------------
 // Position / trade types
@@ -78,6 +103,37 @@ pub struct Position {
     pub entry_price: f64,
     /// Bar timestamp of the entry.
     pub entry_timestamp: u64,
+    /// Adverse / favorable excursion since the entry, updated with the
+    /// high and low of every later bar.
+    pub excursion: ExcursionTracker,
+    /// Stop-loss working on the position, if any.
+    pub stop_loss: Option<f64>,
+    /// Take-profit working on the position, if any. It forms a
+    /// one-cancels-other pair with `stop_loss`.
+    pub take_profit: Option<f64>,
+    /// Distance per unit from the entry to the first stop-loss set on the
+    /// losing side of it (see [`r_multiple`]).
+    pub initial_risk: Option<f64>,
+}
+
+impl Position {
+    /// Quantity with sign: positive long, negative short.
+    pub fn signed_quantity(&self) -> f64 {
+        match self.side {
+            Side::Long => self.quantity,
+            Side::Short => -self.quantity,
+        }
+    }
+
+    /// Signed market value at `price` (negative for shorts).
+    pub fn market_value(&self, price: f64) -> f64 {
+        self.signed_quantity() * price
+    }
+
+    /// PnL if the position were closed at `price`, before fees.
+    pub fn unrealized_pnl(&self, price: f64) -> f64 {
+        self.signed_quantity() * (price - self.entry_price)
+    }
 }
 
 /// A closed trade — produced when an open [`Position`] is exited.
@@ -99,6 +155,45 @@ pub struct Trade {
     pub pnl: f64,
     /// Total fees paid on entry + exit legs.
     pub fees_paid: f64,
+    /// Maximum adverse excursion per unit, from the entry fill to the exit
+    /// fill (see [`excursion`]).
+    pub mae: f64,
+    /// Maximum favorable excursion per unit.
+    pub mfe: f64,
+    /// Initial risk per unit of the position (see [`r_multiple`]).
+    pub initial_risk: Option<f64>,
+}
+
+impl Trade {
+    /// `true` if the trade made money after fees.
+    pub fn is_win(&self) -> bool {
+        self.pnl > 0.0
+    }
+
+    /// Net PnL as a fraction of the entry notional.
+    pub fn return_pct(&self) -> f64 {
+        let notional = self.quantity * self.entry_price;
+        if notional == 0.0 {
+            0.0
+        } else {
+            self.pnl / notional
+        }
+    }
+
+    /// MAE as a fraction of the entry price (`0.02` = 2%).
+    pub fn mae_pct(&self) -> f64 {
+        excursion::fraction(self.mae, self.entry_price)
+    }
+
+    /// MFE as a fraction of the entry price.
+    pub fn mfe_pct(&self) -> f64 {
+        excursion::fraction(self.mfe, self.entry_price)
+    }
+
+    /// Net PnL in multiples of the initial risk, `None` without one.
+    pub fn r_multiple(&self) -> Option<f64> {
+        r_multiple::r_multiple(self.pnl, self.quantity, self.initial_risk?)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -106,7 +201,7 @@ pub struct Trade {
 // ---------------------------------------------------------------------------
 
 /// How to size a new position.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Quantity {
     /// Open with an exact unit size.
     Fixed(f64),
@@ -127,6 +222,20 @@ pub enum Action {
     Exit,
     /// Do nothing.
     Hold,
+    /// Submit an entry [`Order`], replacing any pending one. Market orders
+    /// fill at this bar's close like `EnterLong` / `EnterShort`; the
+    /// others start working on the next bar (see [`orders`]).
+    Submit(Order),
+    /// Replace the stop-loss and take-profit of the open position (`None`
+    /// removes one).
+    SetExits {
+        /// New stop-loss price.
+        stop_loss: Option<f64>,
+        /// New take-profit price.
+        take_profit: Option<f64>,
+    },
+    /// Cancel the pending entry order.
+    Cancel,
 }
 
 /// Read-only view of engine state passed to the strategy each bar.
@@ -137,6 +246,9 @@ pub struct Context<'a> {
     pub candle_index: usize,
     /// Close of the current bar — the price at which any orders this bar will fill.
     pub current_price: f64,
+    /// Indicators declared in [`Strategy::indicators`], updated with the
+    /// current bar.
+    pub indicators: &'a StrategyIndicators,
 }
 
 /// Trading strategy contract.
@@ -145,6 +257,14 @@ pub trait Strategy {
     /// applied to the portfolio at the bar's close.
     fn on_candle(&mut self, candle: &Candle, ctx: &Context) -> Action;
 
+    /// Indicators the engine builds and feeds for this strategy, as
+    /// `(key, spec)` pairs read back through [`Context::indicators`]. When
+    /// any are declared, `on_candle` is first called once all of them have
+    /// warmed up. See [`strategy_indicators`].
+    fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+        Vec::new()
+    }
+
     /// Called once before the first bar.
     fn on_start(&mut self) {}
 
@@ -163,6 +283,8 @@ pub struct Portfolio {
     pub cash: f64,
     /// Currently open position, if any.
     pub position: Option<Position>,
+    /// Entry order waiting to fill, if any.
+    pub pending_order: Option<Order>,
     /// `(timestamp, equity)` pairs sampled at each bar's close.
     pub equity_curve: Vec<(u64, f64)>,
     /// History of all closed trades.
@@ -175,6 +297,7 @@ impl Portfolio {
         Self {
             cash: initial_cash,
             position: None,
+            pending_order: None,
             equity_curve: Vec::new(),
             trades: Vec::new(),
         }
@@ -209,13 +332,23 @@ pub struct BacktestConfig {
     /// Starting cash. Defaults to `10_000.0`.
     pub initial_cash: f64,
     /// Proportional fee per trade leg (e.g. `0.001` = 10 bps). Default: 0.
+    /// Ignored when a `cost_model` is set.
     pub fee_rate: f64,
     /// Proportional unfavorable slippage on fills (buys filled higher, sells lower).
-    /// Default: 0.
+    /// Default: 0. Ignored when a `fill_model` is set.
     pub slippage: f64,
     /// Periods per year used to annualize the Sharpe ratio. Default: 252
     /// (daily bars). Use 252*6.5 for hourly equities, 365 for crypto, etc.
     pub periods_per_year: f64,
+    /// Fill price model. Default: `None`, a [`CloseFill`] with `slippage`.
+    pub fill_model: Option<Arc<dyn FillModel>>,
+    /// Commission model. Default: `None`, a [`PercentCommission`] with
+    /// `fee_rate`.
+    pub cost_model: Option<Arc<dyn CostModel>>,
+    /// Assumed price path inside a bar, deciding which exit fills when a
+    /// bar reaches both the stop-loss and the take-profit. Default:
+    /// [`IntrabarPolicy::WorstCase`].
+    pub intrabar: IntrabarPolicy,
 }
 
 impl Default for BacktestConfig {
@@ -225,10 +358,57 @@ impl Default for BacktestConfig {
             fee_rate: 0.0,
             slippage: 0.0,
             periods_per_year: 252.0,
+            fill_model: None,
+            cost_model: None,
+            intrabar: IntrabarPolicy::default(),
         }
     }
 }
 
+impl BacktestConfig {
+    /// Price fills with `model` instead of the proportional `slippage`.
+    pub fn with_fill_model(mut self, model: impl FillModel + 'static) -> Self {
+        self.fill_model = Some(Arc::new(model));
+        self
+    }
+
+    /// Charge commissions with `model` instead of the proportional
+    /// `fee_rate`.
+    pub fn with_cost_model(mut self, model: impl CostModel + 'static) -> Self {
+        self.cost_model = Some(Arc::new(model));
+        self
+    }
+
+    /// Resolve stop-loss / take-profit conflicts within a bar with `policy`.
+    pub fn with_intrabar(mut self, policy: IntrabarPolicy) -> Self {
+        self.intrabar = policy;
+        self
+    }
+
+    fn execution(&self) -> Execution {
+        Execution {
+            fill: self.fill_model.clone().unwrap_or_else(|| {
+                Arc::new(CloseFill {
+                    slippage: self.slippage,
+                })
+            }),
+            cost: self.cost_model.clone().unwrap_or_else(|| {
+                Arc::new(PercentCommission {
+                    rate: self.fee_rate,
+                })
+            }),
+            intrabar: self.intrabar,
+        }
+    }
+}
+
+/// Fill and cost models of one run.
+struct Execution {
+    fill: Arc<dyn FillModel>,
+    cost: Arc<dyn CostModel>,
+    intrabar: IntrabarPolicy,
+}
+
 /// Aggregate metrics derived from the equity curve and trade log.
 #[derive(Debug, Clone, Copy)]
 pub struct Metrics {
@@ -276,24 +456,76 @@ impl Backtester {
     ///
     /// The strategy's `on_start` is called before the first bar, `on_finish`
     /// after the last bar (and after the final equity sample is recorded).
-    pub fn run<S: Strategy>(&self, candles: &[Candle], strategy: &mut S) -> BacktestResult {
+    ///
+    /// # Errors
+    /// Returns the error of an indicator declared by the strategy that
+    /// cannot be built or fails on a candle.
+    pub fn run<S: Strategy>(
+        &self,
+        candles: &[Candle],
+        strategy: &mut S,
+    ) -> Result<BacktestResult, IndicatorError> {
+        self.run_with_ticks(candles, &[], strategy)
+    }
+
+    /// Like [`run`](Self::run), replaying `ticks` to fill entry orders,
+    /// stop-losses and take-profits in the order they traded.
+    ///
+    /// `ticks` are the trade prints of the same feed the order-flow
+    /// indicators take, sorted by timestamp; only their timestamp and
+    /// price are used. A tick belongs to the last candle whose timestamp
+    /// is at or before its own. Bars without ticks fall back to
+    /// [`BacktestConfig::intrabar`].
+    ///
+    /// # Errors
+    /// As [`run`](Self::run).
+    pub fn run_with_ticks<S: Strategy>(
+        &self,
+        candles: &[Candle],
+        ticks: &[Tick],
+        strategy: &mut S,
+    ) -> Result<BacktestResult, IndicatorError> {
+        let _span =
+            instrument::info_span!("backtest", candles = candles.len(), ticks = ticks.len());
+        let mut indicators = StrategyIndicators::build(strategy.indicators())?;
         let mut portfolio = Portfolio::new(self.config.initial_cash);
+        let execution = self.config.execution();
         strategy.on_start();
 
+        let mut next_tick = ticks.partition_point(|t| {
+            candles
+                .first()
+                .is_some_and(|first| t.timestamp < first.timestamp)
+        });
         for (i, candle) in candles.iter().enumerate() {
             let price = candle.close;
-            // Build a read-only context around the current portfolio state.
-            // SAFETY: we hold a `&mut Portfolio` only after the strategy
-            // returns, so the borrow does not escape this block.
-            let action = {
-                let ctx = Context {
-                    portfolio: &portfolio,
-                    candle_index: i,
-                    current_price: price,
+            let start = next_tick;
+            if let Some(next) = candles.get(i + 1) {
+                next_tick += ticks[start..].partition_point(|t| t.timestamp < next.timestamp);
+            } else {
+                next_tick = ticks.len();
+            }
+            work_orders(&mut portfolio, candle, &ticks[start..next_tick], &execution);
+            indicators.update(candle)?;
+            // Declared indicators still warming up: the strategy waits.
+            if indicators.is_ready() {
+                // Build a read-only context around the current portfolio state.
+                // SAFETY: we hold a `&mut Portfolio` only after the strategy
+                // returns, so the borrow does not escape this block.
+                let action = {
+                    let ctx = Context {
+                        portfolio: &portfolio,
+                        candle_index: i,
+                        current_price: price,
+                        indicators: &indicators,
+                    };
+                    strategy.on_candle(candle, &ctx)
                 };
-                strategy.on_candle(candle, &ctx)
-            };
-            apply_action(&mut portfolio, action, candle, &self.config);
+                if !matches!(action, Action::Hold) {
+                    instrument::debug!(timestamp = candle.timestamp, action = ?action, "strategy action");
+                }
+                apply_action(&mut portfolio, action, candle, &execution);
+            }
 
             // Sample equity after applying any action.
             let equity = portfolio.equity(price);
@@ -302,7 +534,12 @@ impl Backtester {
 
         strategy.on_finish();
         let metrics = compute_metrics(&portfolio, &self.config);
-        BacktestResult { portfolio, metrics }
+        instrument::debug!(
+            trades = metrics.trade_count,
+            final_equity = metrics.final_equity,
+            "backtest finished"
+        );
+        Ok(BacktestResult { portfolio, metrics })
     }
 }
 
@@ -310,40 +547,36 @@ impl Backtester {
 // Action application
 // ---------------------------------------------------------------------------
 
-fn fill_buy_price(close: f64, slippage: f64) -> f64 {
-    close * (1.0 + slippage)
-}
-
-fn fill_sell_price(close: f64, slippage: f64) -> f64 {
-    close * (1.0 - slippage)
-}
-
 /// Resolve a [`Quantity`] sizing rule against the available cash and fill price.
 ///
-/// `fee_rate` is folded into AllCash / PercentCash sizing so the resulting
-/// units fit within the cash budget *including* the entry fee. Fixed sizing
-/// is left untouched — the caller asked for an exact size.
+/// Commissions are folded into AllCash / PercentCash sizing so the
+/// resulting units fit within the cash budget *including* the entry fee.
+/// Fixed sizing is left untouched — the caller asked for an exact size.
 ///
 /// Returns `None` if there is no cash to allocate or the price is non-positive.
-fn resolve_quantity(qty: Quantity, cash: f64, fill_price: f64, fee_rate: f64) -> Option<f64> {
+fn resolve_quantity(
+    qty: Quantity,
+    cash: f64,
+    fill_price: f64,
+    cost: &dyn CostModel,
+) -> Option<f64> {
     if fill_price <= 0.0 {
         return None;
     }
-    let effective_unit_cost = fill_price * (1.0 + fee_rate);
     let units = match qty {
         Quantity::Fixed(q) => q,
         Quantity::AllCash => {
             if cash <= 0.0 {
                 return None;
             }
-            cash / effective_unit_cost
+            cost.max_quantity(cash, fill_price)
         }
         Quantity::PercentCash(pct) => {
             if cash <= 0.0 {
                 return None;
             }
             let pct = pct.clamp(0.0, 1.0);
-            (cash * pct) / effective_unit_cost
+            cost.max_quantity(cash * pct, fill_price)
         }
     };
     if units > 0.0 {
@@ -353,17 +586,29 @@ fn resolve_quantity(qty: Quantity, cash: f64, fill_price: f64, fee_rate: f64) ->
     }
 }
 
-fn close_position(portfolio: &mut Portfolio, candle: &Candle, cfg: &BacktestConfig) {
-    let Some(pos) = portfolio.position.take() else {
-        return;
+/// Fill price of a market order on `candle` closing / opening `side`.
+fn market_price(exec: &Execution, side: Side, opening: bool, candle: &Candle) -> f64 {
+    let order_side = match (side, opening) {
+        (Side::Long, true) | (Side::Short, false) => OrderSide::Buy,
+        (Side::Long, false) | (Side::Short, true) => OrderSide::Sell,
     };
-    let exit_price = match pos.side {
-        // Closing a long = selling — buyer takes the spread.
-        Side::Long => fill_sell_price(candle.close, cfg.slippage),
-        // Closing a short = buying back — same disadvantage.
-        Side::Short => fill_buy_price(candle.close, cfg.slippage),
+    exec.fill.market_fill(order_side, candle)
+}
+
+fn close_position(portfolio: &mut Portfolio, candle: &Candle, exec: &Execution) {
+    if let Some(pos) = portfolio.position {
+        let exit_price = market_price(exec, pos.side, false, candle);
+        close_position_at(portfolio, exit_price, candle.timestamp, exec);
+    }
+}
+
+fn close_position_at(portfolio: &mut Portfolio, exit_price: f64, timestamp: u64, exec: &Execution) {
+    let Some(mut pos) = portfolio.position.take() else {
+        return;
     };
-    let exit_fee = exit_price * pos.quantity * cfg.fee_rate;
+    // Slippage can fill the exit outside the bar's range.
+    pos.excursion.update_price(exit_price);
+    let exit_fee = exec.cost.commission(pos.quantity, exit_price);
 
     match pos.side {
         Side::Long => {
@@ -376,45 +621,62 @@ fn close_position(portfolio: &mut Portfolio, candle: &Candle, cfg: &BacktestConf
         }
     }
 
-    let entry_fee = pos.entry_price * pos.quantity * cfg.fee_rate;
+    let entry_fee = exec.cost.commission(pos.quantity, pos.entry_price);
     let gross_pnl = match pos.side {
         Side::Long => pos.quantity * (exit_price - pos.entry_price),
         Side::Short => pos.quantity * (pos.entry_price - exit_price),
     };
     let total_fees = entry_fee + exit_fee;
+    instrument::debug!(
+        side = ?pos.side,
+        quantity = pos.quantity,
+        price = exit_price,
+        timestamp,
+        pnl = gross_pnl - total_fees,
+        "position closed"
+    );
     portfolio.trades.push(Trade {
         side: pos.side,
         quantity: pos.quantity,
         entry_price: pos.entry_price,
         exit_price,
         entry_timestamp: pos.entry_timestamp,
-        exit_timestamp: candle.timestamp,
+        exit_timestamp: timestamp,
         pnl: gross_pnl - total_fees,
         fees_paid: total_fees,
+        mae: pos.excursion.mae(),
+        mfe: pos.excursion.mfe(),
+        initial_risk: pos.initial_risk,
     });
 }
 
-fn open_position(
+fn open_position_at(
     portfolio: &mut Portfolio,
-    side: Side,
-    qty: Quantity,
-    candle: &Candle,
-    cfg: &BacktestConfig,
+    order: &Order,
+    fill_price: f64,
+    timestamp: u64,
+    exec: &Execution,
 ) {
-    let fill_price = match side {
-        Side::Long => fill_buy_price(candle.close, cfg.slippage),
-        Side::Short => fill_sell_price(candle.close, cfg.slippage),
-    };
-    let Some(units) = resolve_quantity(qty, portfolio.cash, fill_price, cfg.fee_rate) else {
-        return; // not enough cash, skip silently
+    let side = order.side;
+    let Some(units) = resolve_quantity(
+        order.quantity,
+        portfolio.cash,
+        fill_price,
+        exec.cost.as_ref(),
+    ) else {
+        // Not enough cash: skip the entry.
+        instrument::debug!(side = ?side, price = fill_price, timestamp, cash = portfolio.cash, "entry skipped: no cash to size it");
+        return;
     };
-    let entry_fee = fill_price * units * cfg.fee_rate;
+    let entry_fee = exec.cost.commission(units, fill_price);
     match side {
         Side::Long => {
             // Pay cash for the units + fee.
             let cost = units * fill_price + entry_fee;
             if cost > portfolio.cash {
-                return; // can't afford with fees included
+                // Can't afford with fees included.
+                instrument::debug!(side = ?side, price = fill_price, timestamp, cost, cash = portfolio.cash, "entry skipped: cost exceeds cash");
+                return;
             }
             portfolio.cash -= cost;
         }
@@ -423,36 +685,218 @@ fn open_position(
             portfolio.cash += units * fill_price - entry_fee;
         }
     }
+    instrument::debug!(side = ?side, quantity = units, price = fill_price, timestamp, "position opened");
     portfolio.position = Some(Position {
         side,
         quantity: units,
         entry_price: fill_price,
-        entry_timestamp: candle.timestamp,
+        entry_timestamp: timestamp,
+        excursion: ExcursionTracker::new(side, fill_price),
+        stop_loss: order.stop_loss,
+        take_profit: order.take_profit,
+        initial_risk: order
+            .stop_loss
+            .and_then(|stop| r_multiple::initial_risk(side, fill_price, stop)),
     });
 }
 
-fn apply_action(portfolio: &mut Portfolio, action: Action, candle: &Candle, cfg: &BacktestConfig) {
+/// Enter `order` at `price`, closing an opposite position first at the
+/// same price. An order on the side already held is dropped — no
+/// pyramiding.
+fn enter_at(
+    portfolio: &mut Portfolio,
+    order: &Order,
+    price: impl Fn(Side, bool) -> f64,
+    timestamp: u64,
+    exec: &Execution,
+) {
+    if let Some(pos) = portfolio.position {
+        if pos.side == order.side {
+            return;
+        }
+        close_position_at(portfolio, price(pos.side, false), timestamp, exec);
+    }
+    open_position_at(portfolio, order, price(order.side, true), timestamp, exec);
+}
+
+/// Fill price of the position's stop-loss or take-profit along `legs`,
+/// walking them in order. Legs traded before the exit count towards the
+/// position's excursions.
+fn exit_fill(pos: &mut Position, legs: &[Candle], path: bool, exec: &Execution) -> Option<f64> {
+    let side = match pos.side {
+        Side::Long => OrderSide::Sell,
+        Side::Short => OrderSide::Buy,
+    };
+    // Legs of a path move one way, so of two exits hit on one leg the
+    // first is the one nearest its open.
+    for leg in legs {
+        let stop = pos
+            .stop_loss
+            .and_then(|price| exec.fill.stop_fill(side, price, leg));
+        let target = pos
+            .take_profit
+            .and_then(|price| exec.fill.limit_fill(side, price, leg));
+        let fill = match (stop, target) {
+            (Some(stop), Some(target)) if path => {
+                if (stop - leg.open).abs() <= (target - leg.open).abs() {
+                    Some(stop)
+                } else {
+                    Some(target)
+                }
+            }
+            (Some(stop), Some(target)) => match exec.intrabar {
+                IntrabarPolicy::BestCase => Some(target),
+                _ => Some(stop),
+            },
+            (stop, target) => stop.or(target),
+        };
+        if fill.is_some() {
+            return fill;
+        }
+        pos.excursion.update(leg);
+    }
+    None
+}
+
+/// Close the open position if its exits fill along `legs`.
+fn work_exits(
+    portfolio: &mut Portfolio,
+    legs: &[Candle],
+    path: bool,
+    timestamp: u64,
+    exec: &Execution,
+) {
+    if let Some(pos) = portfolio.position.as_mut() {
+        if let Some(price) = exit_fill(pos, legs, path, exec) {
+            close_position_at(portfolio, price, timestamp, exec);
+        }
+    }
+}
+
+/// The part of `leg` left after `order` triggered on it. A path leg runs
+/// from the trigger to its close; a whole bar keeps its range, opening at
+/// the trigger.
+fn rest_of_leg(order: &Order, leg: &Candle, path: bool) -> Candle {
+    let trigger = match (order.kind, order.side) {
+        (OrderType::Market, _) => leg.close,
+        (OrderType::Limit(limit), Side::Long) => limit.min(leg.open),
+        (OrderType::Limit(limit), Side::Short) => limit.max(leg.open),
+        (OrderType::Stop(stop) | OrderType::StopLimit { stop, .. }, Side::Long) => {
+            stop.max(leg.open)
+        }
+        (OrderType::Stop(stop) | OrderType::StopLimit { stop, .. }, Side::Short) => {
+            stop.min(leg.open)
+        }
+    };
+    if path {
+        Candle {
+            open: trigger,
+            high: trigger.max(leg.close),
+            low: trigger.min(leg.close),
+            ..*leg
+        }
+    } else {
+        Candle {
+            open: trigger,
+            ..*leg
+        }
+    }
+}
+
+/// Fill price of a pending entry order within `candle`. A stop-limit
+/// whose stop trades beyond its limit turns into a resting limit order.
+fn entry_fill(order: &mut Order, candle: &Candle, exec: &Execution) -> Option<f64> {
+    let side = match order.side {
+        Side::Long => OrderSide::Buy,
+        Side::Short => OrderSide::Sell,
+    };
+    match order.kind {
+        OrderType::Market => Some(exec.fill.market_fill(side, candle)),
+        OrderType::Limit(limit) => exec.fill.limit_fill(side, limit, candle),
+        OrderType::Stop(stop) => exec.fill.stop_fill(side, stop, candle),
+        OrderType::StopLimit { stop, limit } => {
+            let triggered = exec.fill.stop_fill(side, stop, candle)?;
+            let within_limit = match side {
+                OrderSide::Buy => triggered <= limit,
+                OrderSide::Sell => triggered >= limit,
+            };
+            if within_limit {
+                Some(triggered)
+            } else {
+                order.kind = OrderType::Limit(limit);
+                None
+            }
+        }
+    }
+}
+
+/// Work the open position's exits and the pending entry order against
+/// `candle` and its `ticks`, before the strategy sees it. A position the
+/// entry opens has its exits worked on the rest of the bar.
+fn work_orders(portfolio: &mut Portfolio, candle: &Candle, ticks: &[Tick], exec: &Execution) {
+    let path = !ticks.is_empty() || exec.intrabar == IntrabarPolicy::OhlcPath;
+    let legs = price_legs(candle, exec.intrabar, ticks);
+    work_exits(portfolio, &legs, path, candle.timestamp, exec);
+    let Some(mut order) = portfolio.pending_order.take() else {
+        return;
+    };
+    for (i, leg) in legs.iter().enumerate() {
+        let stop_limit = matches!(order.kind, OrderType::StopLimit { .. });
+        let Some(price) = entry_fill(&mut order, leg, exec) else {
+            if stop_limit && !matches!(order.kind, OrderType::StopLimit { .. }) {
+                // Gapped past its limit: rests as a limit from the next bar.
+                break;
+            }
+            continue;
+        };
+        // An order on the side already held is dropped by `enter_at`.
+        let held = portfolio.position.is_some_and(|pos| pos.side == order.side);
+        enter_at(portfolio, &order, |_, _| price, candle.timestamp, exec);
+        if !held {
+            let mut rest = vec![rest_of_leg(&order, leg, path)];
+            rest.extend_from_slice(&legs[i + 1..]);
+            work_exits(portfolio, &rest, path, candle.timestamp, exec);
+        }
+        return;
+    }
+    portfolio.pending_order = Some(order);
+}
+
+fn apply_action(portfolio: &mut Portfolio, action: Action, candle: &Candle, exec: &Execution) {
+    let market = |side, opening| market_price(exec, side, opening, candle);
     match action {
         Action::Hold => {}
-        Action::Exit => close_position(portfolio, candle, cfg),
+        Action::Exit => close_position(portfolio, candle, exec),
         Action::EnterLong(qty) => {
-            // Close any opposite position first.
-            if matches!(portfolio.position, Some(p) if p.side == Side::Short) {
-                close_position(portfolio, candle, cfg);
-            }
-            // If we're already long, do nothing — no pyramiding in v1.
-            if portfolio.position.is_none() {
-                open_position(portfolio, Side::Long, qty, candle, cfg);
-            }
+            enter_at(portfolio, &Order::long(qty), market, candle.timestamp, exec);
         }
         Action::EnterShort(qty) => {
-            if matches!(portfolio.position, Some(p) if p.side == Side::Long) {
-                close_position(portfolio, candle, cfg);
-            }
-            if portfolio.position.is_none() {
-                open_position(portfolio, Side::Short, qty, candle, cfg);
+            enter_at(
+                portfolio,
+                &Order::short(qty),
+                market,
+                candle.timestamp,
+                exec,
+            );
+        }
+        Action::Submit(order) => match order.kind {
+            OrderType::Market => enter_at(portfolio, &order, market, candle.timestamp, exec),
+            _ => portfolio.pending_order = Some(order),
+        },
+        Action::SetExits {
+            stop_loss,
+            take_profit,
+        } => {
+            if let Some(pos) = portfolio.position.as_mut() {
+                if pos.initial_risk.is_none() {
+                    pos.initial_risk = stop_loss
+                        .and_then(|stop| r_multiple::initial_risk(pos.side, pos.entry_price, stop));
+                }
+                pos.stop_loss = stop_loss;
+                pos.take_profit = take_profit;
             }
         }
+        Action::Cancel => portfolio.pending_order = None,
     }
 }
 
@@ -461,21 +905,22 @@ fn apply_action(portfolio: &mut Portfolio, action: Action, candle: &Candle, cfg:
 // ---------------------------------------------------------------------------
 
 fn compute_metrics(portfolio: &Portfolio, cfg: &BacktestConfig) -> Metrics {
-    let final_equity = portfolio
-        .equity_curve
+    let pnls: Vec<f64> = portfolio.trades.iter().map(|t| t.pnl).collect();
+    metrics_from(&portfolio.equity_curve, &pnls, cfg)
+}
+
+/// Metrics of an equity curve and the net PnLs of its closed trades.
+fn metrics_from(equity_curve: &[(u64, f64)], pnls: &[f64], cfg: &BacktestConfig) -> Metrics {
+    let final_equity = equity_curve
         .last()
         .map(|&(_, e)| e)
         .unwrap_or(cfg.initial_cash);
-    let total_return = if cfg.initial_cash > 0.0 {
-        (final_equity - cfg.initial_cash) / cfg.initial_cash
-    } else {
-        0.0
-    };
+    let total_return = returns::simple_return(cfg.initial_cash, final_equity).unwrap_or(0.0);
 
     // Max drawdown over the equity curve.
     let mut peak = cfg.initial_cash;
     let mut max_dd = 0.0_f64;
-    for &(_, eq) in &portfolio.equity_curve {
+    for &(_, eq) in equity_curve {
         if eq > peak {
             peak = eq;
         }
@@ -488,19 +933,17 @@ fn compute_metrics(portfolio: &Portfolio, cfg: &BacktestConfig) -> Metrics {
     }
 
     // Sharpe from per-bar returns of the equity curve.
-    let sharpe = sharpe_from_equity_curve(&portfolio.equity_curve, cfg.periods_per_year);
+    let sharpe = sharpe_from_equity_curve(equity_curve, cfg.periods_per_year);
 
     // Trade-level stats.
-    let trade_count = portfolio.trades.len();
+    let trade_count = pnls.len();
     let (wins, gross_profit, gross_loss) =
-        portfolio
-            .trades
-            .iter()
-            .fold((0usize, 0.0_f64, 0.0_f64), |(w, gp, gl), t| {
-                if t.pnl > 0.0 {
-                    (w + 1, gp + t.pnl, gl)
+        pnls.iter()
+            .fold((0usize, 0.0_f64, 0.0_f64), |(w, gp, gl), &pnl| {
+                if pnl > 0.0 {
+                    (w + 1, gp + pnl, gl)
                 } else {
-                    (w, gp, gl + t.pnl)
+                    (w, gp, gl + pnl)
                 }
             });
     let win_rate = if trade_count == 0 {
@@ -531,17 +974,10 @@ fn sharpe_from_equity_curve(curve: &[(u64, f64)], periods_per_year: f64) -> f64
     if curve.len() < 2 {
         return 0.0;
     }
+    // Bars after the account is wiped out have no defined return.
     let returns: Vec<f64> = curve
         .windows(2)
-        .filter_map(|w| {
-            let prev = w[0].1;
-            let cur = w[1].1;
-            if prev > 0.0 {
-                Some((cur - prev) / prev)
-            } else {
-                None
-            }
-        })
+        .filter_map(|w| returns::simple_return(w[0].1, w[1].1))
         .collect();
     if returns.len() < 2 {
         return 0.0;
@@ -558,6 +994,7 @@ fn sharpe_from_equity_curve(curve: &[(u64, f64)], periods_per_year: f64) -> f64
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::TradeSide;
 
     fn ramp(n: usize) -> Vec<Candle> {
         (1..=n)
@@ -607,7 +1044,7 @@ mod tests {
         let bt = Backtester::new(BacktestConfig::default());
         let candles = ramp(10);
         let mut s = BuyAndHold { entered: false };
-        let res = bt.run(&candles, &mut s);
+        let res = bt.run(&candles, &mut s).unwrap();
         // Bought at ~1.0, ramps to 10.0 → ~10x equity.
         assert!(res.metrics.final_equity > res.metrics.final_equity * 0.0); // sanity
         assert!(res.metrics.total_return > 5.0);
@@ -620,7 +1057,7 @@ mod tests {
         let bt = Backtester::new(BacktestConfig::default());
         let candles = ramp(10);
         let mut s = OneTrade { bar: 0 };
-        let res = bt.run(&candles, &mut s);
+        let res = bt.run(&candles, &mut s).unwrap();
         assert_eq!(res.portfolio.trades.len(), 1);
         let t = res.portfolio.trades[0];
         assert_eq!(t.side, Side::Long);
@@ -643,7 +1080,7 @@ mod tests {
         };
         let bt = Backtester::new(cfg);
         let mut s = OneTrade { bar: 0 };
-        let res = bt.run(&ramp(10), &mut s);
+        let res = bt.run(&ramp(10), &mut s).unwrap();
         let t = res.portfolio.trades[0];
         // Two legs of fee at 1% each compound below the no-fee baseline.
         assert!(t.pnl < 40_000.0);
@@ -658,7 +1095,7 @@ mod tests {
         };
         let bt = Backtester::new(cfg);
         let mut s = OneTrade { bar: 0 };
-        let res = bt.run(&ramp(10), &mut s);
+        let res = bt.run(&ramp(10), &mut s).unwrap();
         let t = res.portfolio.trades[0];
         assert!(t.entry_price > 1.0); // bought higher than close
         assert!(t.exit_price < 5.0); // sold lower than close
@@ -679,7 +1116,7 @@ mod tests {
     #[test]
     fn short_in_uptrend_loses_money() {
         let bt = Backtester::new(BacktestConfig::default());
-        let res = bt.run(&ramp(10), &mut ShortStrategy);
+        let res = bt.run(&ramp(10), &mut ShortStrategy).unwrap();
         assert_eq!(res.portfolio.trades.len(), 1);
         let t = res.portfolio.trades[0];
         assert_eq!(t.side, Side::Short);
@@ -693,7 +1130,7 @@ mod tests {
         let bt = Backtester::new(BacktestConfig::default());
         let candles = ramp(7);
         let mut s = BuyAndHold { entered: false };
-        let res = bt.run(&candles, &mut s);
+        let res = bt.run(&candles, &mut s).unwrap();
         assert_eq!(res.portfolio.equity_curve.len(), candles.len());
     }
 
@@ -710,7 +1147,7 @@ mod tests {
             }
         }
         let bt = Backtester::new(BacktestConfig::default());
-        let res = bt.run(&ramp(10), &mut Flip);
+        let res = bt.run(&ramp(10), &mut Flip).unwrap();
         assert_eq!(
             res.portfolio.trades.len(),
             1,
@@ -722,10 +1159,322 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn trades_record_excursions() {
+        // Long from bar 0 (close 1) to bar 4 (close 5): lows never go below
+        // 1.5, the highest high before the exit is 5.5.
+        let bt = Backtester::new(BacktestConfig::default());
+        let res = bt.run(&ramp(10), &mut OneTrade { bar: 0 }).unwrap();
+        let t = res.portfolio.trades[0];
+        assert_eq!(t.mae, 0.0);
+        assert_eq!(t.mfe, 4.5);
+        assert_eq!(t.mfe_pct(), 4.5);
+
+        // Short from 1 to 5: bar 1's low of 1.5 is the best it gets.
+        let res = bt.run(&ramp(10), &mut ShortStrategy).unwrap();
+        let t = res.portfolio.trades[0];
+        assert_eq!(t.mae, 4.5);
+        assert_eq!(t.mfe, 0.0);
+    }
+
+    #[test]
+    fn execution_models_replace_fee_rate_and_slippage() {
+        use super::execution::{PerUnitCommission, SpreadFill};
+
+        let cfg = BacktestConfig {
+            fee_rate: 0.5,
+            slippage: 0.5,
+            ..Default::default()
+        }
+        .with_fill_model(SpreadFill::new(0.2).unwrap())
+        .with_cost_model(PerUnitCommission::new(0.01).unwrap().with_minimum(2.0));
+        let res = Backtester::new(cfg)
+            .run(&ramp(10), &mut ShortStrategy)
+            .unwrap();
+        let t = res.portfolio.trades[0];
+        // Short 100 sold at 1 - 0.1, bought back at 5 + 0.1
+        assert!((t.entry_price - 0.9).abs() < 1e-12);
+        assert!((t.exit_price - 5.1).abs() < 1e-12);
+        // 100 units at 1 cent is below the 2.0 minimum on both legs
+        assert!((t.fees_paid - 4.0).abs() < 1e-12);
+        assert!((t.pnl - (100.0 * (0.9 - 5.1) - 4.0)).abs() < 1e-9);
+
+        // All-cash sizing leaves room for the per-unit commission
+        let cfg = BacktestConfig::default().with_cost_model(PerUnitCommission::new(0.5).unwrap());
+        let res = Backtester::new(cfg)
+            .run(&ramp(10), &mut OneTrade { bar: 0 })
+            .unwrap();
+        let t = res.portfolio.trades[0];
+        assert!((t.quantity * 1.5 - 10_000.0).abs() < 1e-6);
+    }
+
+    /// Plays one action per bar, then holds.
+    struct Script(Vec<Action>);
+    impl Strategy for Script {
+        fn on_candle(&mut self, _c: &Candle, ctx: &Context) -> Action {
+            self.0
+                .get(ctx.candle_index)
+                .copied()
+                .unwrap_or(Action::Hold)
+        }
+    }
+
+    fn bars(ohlc: &[(f64, f64, f64, f64)]) -> Vec<Candle> {
+        ohlc.iter()
+            .enumerate()
+            .map(|(i, &(open, high, low, close))| Candle {
+                timestamp: i as u64,
+                open,
+                high,
+                low,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stop_entry_with_bracket_exits_at_target() {
+        let order = Order::long(Quantity::Fixed(10.0))
+            .stop(3.2)
+            .with_stop_loss(2.0)
+            .with_take_profit(6.2);
+        let res = Backtester::new(BacktestConfig::default())
+            .run(&ramp(10), &mut Script(vec![Action::Submit(order)]))
+            .unwrap();
+        let t = res.portfolio.trades[0];
+        assert_eq!((t.entry_timestamp, t.entry_price), (3, 3.2));
+        assert_eq!((t.exit_timestamp, t.exit_price), (6, 6.2));
+        assert!(res.portfolio.position.is_none());
+        assert!(res.portfolio.pending_order.is_none());
+    }
+
+    #[test]
+    fn intrabar_policy_decides_when_both_exits_are_hit() {
+        let candles = bars(&[(100.0, 100.0, 100.0, 100.0), (100.0, 106.0, 94.0, 100.0)]);
+        let order = Order::long(Quantity::Fixed(1.0))
+            .with_stop_loss(95.0)
+            .with_take_profit(105.0);
+        let exit = |policy| {
+            let cfg = BacktestConfig::default().with_intrabar(policy);
+            let res = Backtester::new(cfg)
+                .run(&candles, &mut Script(vec![Action::Submit(order)]))
+                .unwrap();
+            res.portfolio.trades[0]
+        };
+        let worst = exit(IntrabarPolicy::WorstCase);
+        assert_eq!((worst.exit_price, worst.mae), (95.0, 5.0));
+        // Exited at the target, the rest of the bar was never traded
+        let best = exit(IntrabarPolicy::BestCase);
+        assert_eq!((best.exit_price, best.mfe, best.mae), (105.0, 5.0, 0.0));
+    }
+
+    #[test]
+    fn bracket_exits_are_worked_on_the_fill_bar() {
+        let order = Order::long(Quantity::Fixed(1.0))
+            .limit(98.0)
+            .with_stop_loss(96.0)
+            .with_take_profit(104.0);
+        let run = |policy, bar| {
+            let candles = bars(&[(100.0, 100.0, 100.0, 100.0), bar]);
+            let cfg = BacktestConfig::default().with_intrabar(policy);
+            let res = Backtester::new(cfg)
+                .run(&candles, &mut Script(vec![Action::Submit(order)]))
+                .unwrap();
+            res.portfolio
+        };
+        // The bar trades through the entry down to the stop
+        for policy in [
+            IntrabarPolicy::WorstCase,
+            IntrabarPolicy::BestCase,
+            IntrabarPolicy::OhlcPath,
+        ] {
+            let t = run(policy, (100.0, 101.0, 95.0, 100.0)).trades[0];
+            assert_eq!(
+                (
+                    t.entry_timestamp,
+                    t.entry_price,
+                    t.exit_timestamp,
+                    t.exit_price
+                ),
+                (1, 98.0, 1, 96.0)
+            );
+        }
+        // Up bar: the low comes first, the target fills after the entry
+        let t = run(IntrabarPolicy::OhlcPath, (100.0, 105.0, 97.0, 104.5)).trades[0];
+        assert_eq!((t.exit_timestamp, t.exit_price, t.mae), (1, 104.0, 1.0));
+        // Down bar: the high came before the entry and does not count
+        let p = run(IntrabarPolicy::OhlcPath, (100.0, 105.0, 97.0, 97.5));
+        assert!(p.trades.is_empty());
+        let pos = p.position.unwrap();
+        assert_eq!(
+            (pos.entry_price, pos.excursion.mfe(), pos.excursion.mae()),
+            (98.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn ticks_after_the_entry_work_its_exits() {
+        let order = Order::long(Quantity::Fixed(1.0))
+            .stop(101.0)
+            .with_stop_loss(99.0)
+            .with_take_profit(104.0);
+        let candles = bars(&[(100.0, 100.0, 100.0, 100.0), (100.0, 102.0, 98.0, 101.5)]);
+        let run = |ticks: &[Tick]| {
+            let cfg = BacktestConfig::default().with_intrabar(IntrabarPolicy::OhlcPath);
+            Backtester::new(cfg)
+                .run_with_ticks(&candles, ticks, &mut Script(vec![Action::Submit(order)]))
+                .unwrap()
+        };
+        // Along the assumed path the low comes before the entry
+        let res = run(&[]);
+        assert!(res.portfolio.trades.is_empty());
+        assert_eq!(res.portfolio.position.unwrap().entry_price, 101.0);
+
+        // The ticks fill the entry, then trade down through the stop
+        let ticks =
+            [(1, 100.5), (1, 101.5), (1, 98.5), (1, 101.5)].map(|(timestamp, price)| Tick {
+                timestamp,
+                price,
+                size: 1.0,
+                side: TradeSide::Unknown,
+            });
+        let t = run(&ticks).portfolio.trades[0];
+        assert_eq!(
+            (
+                t.entry_timestamp,
+                t.entry_price,
+                t.exit_timestamp,
+                t.exit_price
+            ),
+            (1, 101.0, 1, 99.0)
+        );
+        assert_eq!((t.mfe, t.mae), (0.5, 2.0));
+    }
+
+    #[test]
+    fn ohlc_path_and_ticks_order_the_exits() {
+        let order = Order::long(Quantity::Fixed(1.0))
+            .with_stop_loss(95.0)
+            .with_take_profit(105.0);
+        let run = |close: f64, ticks: &[Tick]| {
+            let candles = bars(&[(100.0, 100.0, 100.0, 100.0), (100.0, 106.0, 94.0, close)]);
+            let cfg = BacktestConfig::default().with_intrabar(IntrabarPolicy::OhlcPath);
+            let res = Backtester::new(cfg)
+                .run_with_ticks(&candles, ticks, &mut Script(vec![Action::Submit(order)]))
+                .unwrap();
+            res.portfolio.trades[0]
+        };
+        // Up bar: open → low → high, the stop trades first
+        let t = run(105.5, &[]);
+        assert_eq!((t.exit_price, t.mae, t.mfe), (95.0, 5.0, 0.0));
+        // Down bar: open → high → low, the target trades first
+        let t = run(94.5, &[]);
+        assert_eq!((t.exit_price, t.mfe), (105.0, 5.0));
+
+        // Replayed ticks replace the assumed path
+        let ticks =
+            [(0, 50.0), (1, 101.0), (1, 99.0), (1, 105.5), (1, 94.0)].map(|(timestamp, price)| {
+                Tick {
+                    timestamp,
+                    price,
+                    size: 1.0,
+                    side: TradeSide::Unknown,
+                }
+            });
+        let t = run(105.5, &ticks);
+        assert_eq!((t.exit_price, t.mae), (105.0, 1.0));
+    }
+
+    #[test]
+    fn stop_limit_gapped_past_its_limit_rests_as_limit() {
+        let candles = bars(&[
+            (100.0, 100.0, 100.0, 100.0),
+            (104.0, 105.0, 103.5, 104.0),
+            (103.0, 103.0, 102.0, 102.5),
+        ]);
+        let order = Order::long(Quantity::Fixed(1.0)).stop_limit(102.0, 102.5);
+        let mut script = Script(vec![Action::Submit(order), Action::Hold]);
+        let res = Backtester::new(BacktestConfig::default())
+            .run(&candles[..2], &mut script)
+            .unwrap();
+        assert_eq!(
+            res.portfolio.pending_order.map(|o| o.kind),
+            Some(OrderType::Limit(102.5))
+        );
+        let res = Backtester::new(BacktestConfig::default())
+            .run(&candles, &mut script)
+            .unwrap();
+        let pos = res.portfolio.position.unwrap();
+        assert_eq!((pos.entry_timestamp, pos.entry_price), (2, 102.5));
+
+        // Cancelled before it can fill
+        let mut script = Script(vec![Action::Submit(order), Action::Cancel]);
+        let res = Backtester::new(BacktestConfig::default())
+            .run(&candles, &mut script)
+            .unwrap();
+        assert!(res.portfolio.position.is_none());
+        assert!(res.portfolio.pending_order.is_none());
+    }
+
+    #[test]
+    fn set_exits_protects_a_market_position() {
+        let mut script = Script(vec![
+            Action::EnterShort(Quantity::Fixed(1.0)),
+            Action::SetExits {
+                stop_loss: Some(4.2),
+                take_profit: None,
+            },
+        ]);
+        let res = Backtester::new(BacktestConfig::default())
+            .run(&ramp(10), &mut script)
+            .unwrap();
+        let t = res.portfolio.trades[0];
+        assert_eq!(t.side, Side::Short);
+        assert_eq!((t.exit_timestamp, t.exit_price), (4, 4.2));
+    }
+
+    #[cfg(feature = "trend")]
+    #[test]
+    fn declared_indicators_are_warmed_up_and_fed() {
+        /// Records the first bar it sees and the SMA there.
+        struct Declared {
+            spec: IndicatorSpec,
+            first: Option<(usize, Option<f64>)>,
+        }
+        impl Strategy for Declared {
+            fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+                vec![("sma", self.spec.clone())]
+            }
+            fn on_candle(&mut self, _c: &Candle, ctx: &Context) -> Action {
+                self.first
+                    .get_or_insert((ctx.candle_index, ctx.indicators.value("sma")));
+                Action::Hold
+            }
+        }
+
+        let mut strategy = Declared {
+            spec: IndicatorSpec::new("sma").param("period", 3.0),
+            first: None,
+        };
+        let res = Backtester::new(BacktestConfig::default())
+            .run(&ramp(10), &mut strategy)
+            .unwrap();
+        assert_eq!(strategy.first, Some((2, Some(2.0))));
+        assert_eq!(res.portfolio.equity_curve.len(), 10);
+
+        let mut broken = Declared {
+            spec: IndicatorSpec::new("sma"),
+            first: None,
+        };
+        let bt = Backtester::new(BacktestConfig::default());
+        assert!(bt.run(&ramp(10), &mut broken).is_err());
+    }
+
     #[test]
     fn metrics_are_zero_for_empty_input() {
         let bt = Backtester::new(BacktestConfig::default());
-        let res = bt.run(&[], &mut BuyAndHold { entered: false });
+        let res = bt.run(&[], &mut BuyAndHold { entered: false }).unwrap();
         assert_eq!(res.metrics.final_equity, 10_000.0);
         assert_eq!(res.metrics.trade_count, 0);
         assert_eq!(res.metrics.total_return, 0.0);
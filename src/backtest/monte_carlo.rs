@@ -0,0 +1,332 @@
+//! Monte Carlo robustness analysis of backtest results.
+//!
+//! A single backtest is one path through history. Resampling its trades
+//! (or per-bar returns) many times shows how much of the result is down
+//! to the order outcomes happened to arrive in:
+//!
+//! - [`Resampling::Shuffle`] permutes the sequence. Final equity is
+//!   unchanged for additive trade PnLs, but the drawdown distribution
+//!   shows how bad an unlucky ordering could have been.
+//! - [`Resampling::Bootstrap`] draws with replacement, so both final
+//!   equity and drawdown vary — a rough confidence interval on the
+//!   strategy's edge.
+//!
+//! Every run is reproducible from the `seed`.
+//!
+//! ```
+//! use rsta::backtest::monte_carlo::{MonteCarlo, Resampling};
+//!
+//! let pnls = [120.0, -80.0, 200.0, -150.0, 90.0, -60.0, 40.0];
+//! let mc = MonteCarlo::new(1_000, Resampling::Shuffle, 7).unwrap();
+//! let summary = mc.trades(&pnls, 10_000.0).unwrap();
+//!
+//! // Shuffling never changes the sum of the PnLs...
+//! assert!((summary.final_equity.mean() - 10_160.0).abs() < 1e-6);
+//! // ...but the drawdown depends on the order.
+//! let (lo, hi) = summary.max_drawdown.interval(0.9);
+//! assert!(lo < hi);
+//! ```
+
+use super::BacktestResult;
+use crate::indicators::IndicatorError;
+use crate::rng::Rng;
+
+/// How each simulated sequence is drawn from the original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resampling {
+    /// Random permutation of the original sequence.
+    Shuffle,
+    /// Same length, drawn with replacement.
+    Bootstrap,
+}
+
+/// Sorted sample of one statistic across Monte Carlo runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distribution {
+    sorted: Vec<f64>,
+}
+
+impl Distribution {
+    fn new(mut samples: Vec<f64>) -> Self {
+        samples.sort_by(f64::total_cmp);
+        Self { sorted: samples }
+    }
+
+    /// The simulated values in ascending order.
+    pub fn samples(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    /// Arithmetic mean.
+    pub fn mean(&self) -> f64 {
+        self.sorted.iter().sum::<f64>() / self.sorted.len() as f64
+    }
+
+    /// Smallest value.
+    pub fn min(&self) -> f64 {
+        self.sorted[0]
+    }
+
+    /// Largest value.
+    pub fn max(&self) -> f64 {
+        self.sorted[self.sorted.len() - 1]
+    }
+
+    /// Value at quantile `q` in `[0, 1]` (clamped), linearly interpolated.
+    pub fn percentile(&self, q: f64) -> f64 {
+        let pos = q.clamp(0.0, 1.0) * (self.sorted.len() - 1) as f64;
+        let lo = pos.floor() as usize;
+        let hi = pos.ceil() as usize;
+        self.sorted[lo] + (self.sorted[hi] - self.sorted[lo]) * (pos - lo as f64)
+    }
+
+    /// Median (50th percentile).
+    pub fn median(&self) -> f64 {
+        self.percentile(0.5)
+    }
+
+    /// Central interval holding `confidence` of the runs, e.g. `0.95` for
+    /// the 2.5th–97.5th percentiles.
+    pub fn interval(&self, confidence: f64) -> (f64, f64) {
+        let tail = (1.0 - confidence.clamp(0.0, 1.0)) / 2.0;
+        (self.percentile(tail), self.percentile(1.0 - tail))
+    }
+}
+
+/// Distributions produced by a Monte Carlo analysis.
+#[derive(Debug, Clone)]
+pub struct MonteCarloSummary {
+    /// Ending equity of each run.
+    pub final_equity: Distribution,
+    /// Max peak-to-trough relative drawdown of each run, in `[0, 1]`.
+    pub max_drawdown: Distribution,
+}
+
+impl MonteCarloSummary {
+    /// Fraction of runs that ended below `initial_equity`.
+    pub fn probability_of_loss(&self, initial_equity: f64) -> f64 {
+        let losers = self
+            .final_equity
+            .samples()
+            .iter()
+            .filter(|&&e| e < initial_equity)
+            .count();
+        losers as f64 / self.final_equity.samples().len() as f64
+    }
+}
+
+/// Monte Carlo resampler.
+#[derive(Debug, Clone)]
+pub struct MonteCarlo {
+    runs: usize,
+    method: Resampling,
+    seed: u64,
+}
+
+impl MonteCarlo {
+    /// Create a resampler performing `runs` simulations.
+    pub fn new(runs: usize, method: Resampling, seed: u64) -> Result<Self, IndicatorError> {
+        if runs == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "runs",
+                runs,
+                "must be at least 1",
+            ));
+        }
+        Ok(Self { runs, method, seed })
+    }
+
+    /// Resample per-trade PnLs (absolute amounts, added to equity).
+    pub fn trades(
+        &self,
+        pnls: &[f64],
+        initial_equity: f64,
+    ) -> Result<MonteCarloSummary, IndicatorError> {
+        self.simulate(pnls, initial_equity, |equity, pnl| equity + pnl)
+    }
+
+    /// Resample per-period returns (fractions, compounded into equity).
+    pub fn returns(
+        &self,
+        returns: &[f64],
+        initial_equity: f64,
+    ) -> Result<MonteCarloSummary, IndicatorError> {
+        self.simulate(returns, initial_equity, |equity, r| equity * (1.0 + r))
+    }
+
+    /// Resample the closed trades of a backtest, starting from
+    /// `initial_cash` (normally `BacktestConfig::initial_cash`).
+    pub fn backtest(
+        &self,
+        result: &BacktestResult,
+        initial_cash: f64,
+    ) -> Result<MonteCarloSummary, IndicatorError> {
+        let pnls: Vec<f64> = result.portfolio.trades.iter().map(|t| t.pnl).collect();
+        self.trades(&pnls, initial_cash)
+    }
+
+    fn simulate(
+        &self,
+        steps: &[f64],
+        initial_equity: f64,
+        apply: impl Fn(f64, f64) -> f64,
+    ) -> Result<MonteCarloSummary, IndicatorError> {
+        if steps.is_empty() {
+            return Err(IndicatorError::insufficient_data(1, 0));
+        }
+        if !initial_equity.is_finite() || initial_equity <= 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "initial_equity",
+                initial_equity,
+                "must be positive and finite",
+            ));
+        }
+
+        let mut rng = Rng::new(self.seed);
+        let mut path = steps.to_vec();
+        let mut finals = Vec::with_capacity(self.runs);
+        let mut drawdowns = Vec::with_capacity(self.runs);
+        for _ in 0..self.runs {
+            match self.method {
+                Resampling::Shuffle => rng.shuffle(&mut path),
+                Resampling::Bootstrap => {
+                    for slot in path.iter_mut() {
+                        *slot = steps[rng.below(steps.len())];
+                    }
+                }
+            }
+
+            let mut equity = initial_equity;
+            let mut peak = initial_equity;
+            let mut max_dd = 0.0_f64;
+            for &step in &path {
+                equity = apply(equity, step);
+                peak = peak.max(equity);
+                if peak > 0.0 {
+                    max_dd = max_dd.max((peak - equity) / peak);
+                }
+            }
+            finals.push(equity);
+            drawdowns.push(max_dd);
+        }
+
+        Ok(MonteCarloSummary {
+            final_equity: Distribution::new(finals),
+            max_drawdown: Distribution::new(drawdowns),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{Action, BacktestConfig, Backtester, Context, Quantity, Strategy};
+    use crate::indicators::Candle;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn distribution_percentiles() {
+        let d = Distribution::new(vec![5.0, 1.0, 4.0, 2.0, 3.0]);
+        assert_eq!(d.samples(), [1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(d.median(), 3.0);
+        assert!(approx(d.percentile(0.1), 1.4));
+        assert_eq!(d.interval(1.0), (1.0, 5.0));
+        assert_eq!((d.min(), d.max(), d.mean()), (1.0, 5.0, 3.0));
+    }
+
+    #[test]
+    fn shuffle_preserves_final_equity() {
+        let pnls = [100.0, -50.0, 30.0, -20.0];
+        let mc = MonteCarlo::new(200, Resampling::Shuffle, 1).unwrap();
+        let s = mc.trades(&pnls, 1_000.0).unwrap();
+        assert!(approx(s.final_equity.min(), 1_060.0));
+        assert!(approx(s.final_equity.max(), 1_060.0));
+        // Worst ordering: both losses straight away → 70 / 1000.
+        assert!(approx(s.max_drawdown.max(), 0.07));
+        assert!(s.max_drawdown.min() >= 0.0);
+        assert_eq!(s.probability_of_loss(1_000.0), 0.0);
+    }
+
+    #[test]
+    fn bootstrap_varies_final_equity() {
+        let pnls = [100.0, -100.0, 50.0, -40.0, 10.0];
+        let mc = MonteCarlo::new(500, Resampling::Bootstrap, 9).unwrap();
+        let s = mc.trades(&pnls, 1_000.0).unwrap();
+        assert!(s.final_equity.min() < s.final_equity.max());
+        // Every run draws 5 trades from [-100, 100].
+        assert!(s.final_equity.min() >= 500.0 && s.final_equity.max() <= 1_500.0);
+        let p = s.probability_of_loss(1_000.0);
+        assert!(p > 0.0 && p < 1.0);
+    }
+
+    #[test]
+    fn same_seed_same_result() {
+        let returns = [0.01, -0.02, 0.015, 0.005, -0.01];
+        let a = MonteCarlo::new(50, Resampling::Bootstrap, 3).unwrap();
+        let b = MonteCarlo::new(50, Resampling::Bootstrap, 3).unwrap();
+        assert_eq!(
+            a.returns(&returns, 100.0).unwrap().final_equity,
+            b.returns(&returns, 100.0).unwrap().final_equity
+        );
+    }
+
+    #[test]
+    fn returns_compound() {
+        let mc = MonteCarlo::new(10, Resampling::Shuffle, 0).unwrap();
+        let s = mc.returns(&[0.1, -0.1], 100.0).unwrap();
+        assert!(approx(s.final_equity.median(), 99.0));
+        assert!(approx(s.max_drawdown.max(), 0.1));
+    }
+
+    #[test]
+    fn from_backtest_trades() {
+        struct Flip;
+        impl Strategy for Flip {
+            fn on_candle(&mut self, _c: &Candle, ctx: &Context) -> Action {
+                if ctx.candle_index % 2 == 0 {
+                    Action::EnterLong(Quantity::Fixed(1.0))
+                } else {
+                    Action::Exit
+                }
+            }
+        }
+        let candles: Vec<Candle> = (0..10)
+            .map(|i| {
+                let p = 100.0 + if i % 4 < 2 { i as f64 } else { -(i as f64) };
+                Candle {
+                    timestamp: i,
+                    open: p,
+                    high: p,
+                    low: p,
+                    close: p,
+                    volume: 1.0,
+                }
+            })
+            .collect();
+        let cfg = BacktestConfig::default();
+        let result = Backtester::new(cfg.clone())
+            .run(&candles, &mut Flip)
+            .unwrap();
+        let mc = MonteCarlo::new(20, Resampling::Shuffle, 5).unwrap();
+        let s = mc.backtest(&result, cfg.initial_cash).unwrap();
+        let total: f64 = result.portfolio.trades.iter().map(|t| t.pnl).sum();
+        assert!(approx(s.final_equity.mean(), cfg.initial_cash + total));
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert!(MonteCarlo::new(0, Resampling::Shuffle, 0).is_err());
+        let mc = MonteCarlo::new(1, Resampling::Shuffle, 0).unwrap();
+        assert!(matches!(
+            mc.trades(&[], 100.0),
+            Err(IndicatorError::InsufficientData { .. })
+        ));
+        assert!(matches!(
+            mc.trades(&[1.0], 0.0),
+            Err(IndicatorError::InvalidParameter { .. })
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+//! Parameter search over the backtester.
+//!
+//! An [`Optimizer`] enumerates parameter sets from a [`ParamGrid`] —
+//! exhaustively, or a seeded random sample of it — builds a strategy for
+//! each with a user closure, runs it through a [`Backtester`], scores the
+//! result with a user objective and returns the sets ranked best first.
+//!
+//! Parameter sets are [`IndicatorParams`] maps, so they can be handed
+//! straight to [`IndicatorFactory`](crate::indicators::IndicatorFactory)
+//! when the strategy builds its indicators from names.
+//!
+//! With the `parallel` feature the backtests run on the rayon thread
+//! pool; results are identical either way.
+//!
+//! ```
+//! use rsta::backtest::optimize::{Optimizer, ParamGrid};
+//! use rsta::backtest::{Action, BacktestConfig, Context, Quantity, Strategy};
+//! use rsta::indicators::Candle;
+//!
+//! /// Buy at bar `entry`, sell at bar `exit`.
+//! struct Window { entry: usize, exit: usize }
+//! impl Strategy for Window {
+//!     fn on_candle(&mut self, _c: &Candle, ctx: &Context) -> Action {
+//!         match ctx.candle_index {
+//!             i if i == self.entry => Action::EnterLong(Quantity::AllCash),
+//!             i if i == self.exit => Action::Exit,
+//!             _ => Action::Hold,
+//!         }
+//!     }
+//! }
+//!
+//! let candles: Vec<Candle> = (1..=50)
+//!     .map(|i| Candle {
+//!         timestamp: i, open: i as f64, high: i as f64, low: i as f64,
+//!         close: i as f64, volume: 1.0,
+//!     })
+//!     .collect();
+//!
+//! let grid = ParamGrid::new()
+//!     .values("entry", [0.0, 10.0, 20.0])
+//!     .range("exit", 30.0, 45.0, 5.0)
+//!     .unwrap();
+//! let ranked = Optimizer::new(grid, BacktestConfig::default()).run(
+//!     &candles,
+//!     |p| Some(Window { entry: p["entry"] as usize, exit: p["exit"] as usize }),
+//!     |result| result.metrics.total_return,
+//! );
+//!
+//! assert_eq!(ranked.len(), 12);
+//! assert_eq!(ranked[0].params["entry"], 0.0);
+//! assert_eq!(ranked[0].params["exit"], 45.0);
+//! ```
+
+use std::collections::HashSet;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::{BacktestConfig, BacktestResult, Backtester, Strategy};
+use crate::indicators::{Candle, IndicatorError, IndicatorParams};
+use crate::rng::Rng;
+
+/// Named parameter axes; the search space is their Cartesian product.
+#[derive(Debug, Clone, Default)]
+pub struct ParamGrid {
+    axes: Vec<(String, Vec<f64>)>,
+}
+
+impl ParamGrid {
+    /// Empty grid (a single, empty parameter set).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an axis with explicit values, replacing any axis of that name.
+    pub fn values(mut self, name: &str, values: impl IntoIterator<Item = f64>) -> Self {
+        let values: Vec<f64> = values.into_iter().collect();
+        match self.axes.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = values,
+            None => self.axes.push((name.to_string(), values)),
+        }
+        self
+    }
+
+    /// Add an axis `start, start + step, …` up to and including `end`.
+    pub fn range(
+        self,
+        name: &str,
+        start: f64,
+        end: f64,
+        step: f64,
+    ) -> Result<Self, IndicatorError> {
+        if !(start.is_finite() && end.is_finite() && step.is_finite()) || step <= 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                name,
+                format!("{start}..={end} step {step}"),
+                "needs finite bounds and a positive step",
+            ));
+        }
+        if end < start {
+            return Err(IndicatorError::invalid_parameter(
+                name,
+                format!("{start}..={end}"),
+                "range ends before it starts",
+            ));
+        }
+        // Half a step of slack so `end` survives floating-point drift.
+        let count = ((end - start) / step + 0.5).floor() as usize + 1;
+        let values = (0..count).map(|i| start + step * i as f64);
+        Ok(self.values(name, values))
+    }
+
+    /// Axis names in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.axes.iter().map(|(n, _)| n.as_str())
+    }
+
+    /// Number of parameter sets in the grid.
+    pub fn len(&self) -> usize {
+        self.axes
+            .iter()
+            .fold(1usize, |acc, (_, v)| acc.saturating_mul(v.len()))
+    }
+
+    /// `true` if some axis has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `index`-th parameter set; the last axis varies fastest.
+    fn get(&self, mut index: usize) -> IndicatorParams {
+        let mut params = IndicatorParams::new();
+        for (name, values) in self.axes.iter().rev() {
+            params.insert(name.clone(), values[index % values.len()]);
+            index /= values.len();
+        }
+        params
+    }
+
+    /// Every parameter set, in grid order.
+    pub fn iter(&self) -> impl Iterator<Item = IndicatorParams> + '_ {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+}
+
+/// How an [`Optimizer`] walks the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMethod {
+    /// Every combination.
+    Grid,
+    /// `samples` distinct combinations drawn uniformly with a fixed seed.
+    Random {
+        /// Number of parameter sets to evaluate.
+        samples: usize,
+        /// RNG seed; the same seed gives the same sample.
+        seed: u64,
+    },
+}
+
+/// One evaluated parameter set.
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    /// The parameters the strategy was built with.
+    pub params: IndicatorParams,
+    /// Objective value (higher is better).
+    pub score: f64,
+    /// Full backtest output.
+    pub result: BacktestResult,
+}
+
+/// Runs a strategy over many parameter sets and ranks them.
+#[derive(Debug, Clone)]
+pub struct Optimizer {
+    grid: ParamGrid,
+    method: SearchMethod,
+    config: BacktestConfig,
+}
+
+impl Optimizer {
+    /// Exhaustive search over `grid`, backtesting with `config`.
+    pub fn new(grid: ParamGrid, config: BacktestConfig) -> Self {
+        Self {
+            grid,
+            method: SearchMethod::Grid,
+            config,
+        }
+    }
+
+    /// Switch to random search.
+    pub fn method(mut self, method: SearchMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// The parameter sets that [`run`](Self::run) will evaluate.
+    ///
+    /// Random search caps `samples` at the grid size, so asking for more
+    /// samples than combinations degenerates to a grid search.
+    pub fn candidates(&self) -> Vec<IndicatorParams> {
+        match self.method {
+            SearchMethod::Grid => self.grid.iter().collect(),
+            SearchMethod::Random { samples, seed } => {
+                let total = self.grid.len();
+                if samples >= total {
+                    return self.grid.iter().collect();
+                }
+                let mut rng = Rng::new(seed);
+                let mut seen = HashSet::with_capacity(samples);
+                let mut picked = Vec::with_capacity(samples);
+                while picked.len() < samples {
+                    let index = rng.below(total);
+                    if seen.insert(index) {
+                        picked.push(self.grid.get(index));
+                    }
+                }
+                picked
+            }
+        }
+    }
+
+    /// Evaluate every candidate and return them sorted by descending score.
+    ///
+    /// `build` returns `None` to skip a combination (e.g. a fast period not
+    /// below the slow one); combinations whose declared indicators fail are
+    /// skipped too. `NaN` scores rank last; ties keep candidate
+    /// order.
+    pub fn run<S, B, O>(&self, candles: &[Candle], build: B, objective: O) -> Vec<Evaluation>
+    where
+        S: Strategy,
+        B: Fn(&IndicatorParams) -> Option<S> + Sync,
+        O: Fn(&BacktestResult) -> f64 + Sync,
+    {
+        let backtester = Backtester::new(self.config.clone());
+        let evaluate = |params: IndicatorParams| {
+            let mut strategy = build(&params)?;
+            let result = backtester.run(candles, &mut strategy).ok()?;
+            Some(Evaluation {
+                score: objective(&result),
+                params,
+                result,
+            })
+        };
+
+        #[cfg(feature = "parallel")]
+        let mut evaluations: Vec<Evaluation> = self
+            .candidates()
+            .into_par_iter()
+            .filter_map(evaluate)
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let mut evaluations: Vec<Evaluation> =
+            self.candidates().into_iter().filter_map(evaluate).collect();
+
+        evaluations.sort_by(|a, b| match (a.score.is_nan(), b.score.is_nan()) {
+            (false, false) => b.score.total_cmp(&a.score),
+            (nan_a, nan_b) => nan_a.cmp(&nan_b),
+        });
+        evaluations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{Action, Context, Quantity};
+    use crate::indicators::Candle;
+
+    struct Window {
+        entry: usize,
+        exit: usize,
+    }
+
+    impl Strategy for Window {
+        fn on_candle(&mut self, _c: &Candle, ctx: &Context) -> Action {
+            match ctx.candle_index {
+                i if i == self.entry => Action::EnterLong(Quantity::AllCash),
+                i if i == self.exit => Action::Exit,
+                _ => Action::Hold,
+            }
+        }
+    }
+
+    fn ramp(n: u64) -> Vec<Candle> {
+        (1..=n)
+            .map(|i| Candle {
+                timestamp: i,
+                open: i as f64,
+                high: i as f64,
+                low: i as f64,
+                close: i as f64,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    fn build(p: &IndicatorParams) -> Option<Window> {
+        let (entry, exit) = (p["entry"] as usize, p["exit"] as usize);
+        (entry < exit).then_some(Window { entry, exit })
+    }
+
+    #[test]
+    fn grid_order_and_len() {
+        let grid = ParamGrid::new()
+            .values("a", [1.0, 2.0])
+            .values("b", [10.0, 20.0, 30.0]);
+        assert_eq!(grid.len(), 6);
+        let sets: Vec<(f64, f64)> = grid.iter().map(|p| (p["a"], p["b"])).collect();
+        assert_eq!(
+            sets,
+            [
+                (1.0, 10.0),
+                (1.0, 20.0),
+                (1.0, 30.0),
+                (2.0, 10.0),
+                (2.0, 20.0),
+                (2.0, 30.0)
+            ]
+        );
+        assert_eq!(grid.names().collect::<Vec<_>>(), ["a", "b"]);
+    }
+
+    #[test]
+    fn range_includes_end_and_validates() {
+        let grid = ParamGrid::new().range("x", 0.1, 0.5, 0.1).unwrap();
+        assert_eq!(grid.len(), 5);
+        assert!(ParamGrid::new().range("x", 0.0, 1.0, 0.0).is_err());
+        assert!(ParamGrid::new().range("x", 2.0, 1.0, 0.5).is_err());
+        assert!(ParamGrid::new().values("x", []).is_empty());
+    }
+
+    #[test]
+    fn grid_search_ranks_best_first() {
+        let grid = ParamGrid::new()
+            .range("entry", 0.0, 20.0, 5.0)
+            .unwrap()
+            .range("exit", 10.0, 40.0, 10.0)
+            .unwrap();
+        let ranked = Optimizer::new(grid, BacktestConfig::default())
+            .run(&ramp(50), build, |r| r.metrics.total_return);
+        // Combinations with entry >= exit are skipped.
+        assert_eq!(ranked.len(), 20 - 4);
+        assert_eq!(ranked[0].params["entry"], 0.0);
+        assert_eq!(ranked[0].params["exit"], 40.0);
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(ranked[0].result.metrics.trade_count, 1);
+    }
+
+    #[test]
+    fn random_search_is_seeded_distinct_and_capped() {
+        let grid = ParamGrid::new()
+            .range("entry", 0.0, 9.0, 1.0)
+            .unwrap()
+            .range("exit", 10.0, 19.0, 1.0)
+            .unwrap();
+        let opt =
+            Optimizer::new(grid.clone(), BacktestConfig::default()).method(SearchMethod::Random {
+                samples: 15,
+                seed: 42,
+            });
+        let a = opt.candidates();
+        assert_eq!(a.len(), 15);
+        assert_eq!(a, opt.candidates());
+        for (i, p) in a.iter().enumerate() {
+            assert!(!a[i + 1..].contains(p));
+        }
+
+        let all = Optimizer::new(grid, BacktestConfig::default())
+            .method(SearchMethod::Random {
+                samples: 1_000,
+                seed: 1,
+            })
+            .candidates();
+        assert_eq!(all.len(), 100);
+    }
+
+    #[test]
+    fn nan_scores_rank_last() {
+        let grid = ParamGrid::new()
+            .values("entry", [0.0, 5.0, 10.0])
+            .values("exit", [20.0]);
+        let ranked = Optimizer::new(grid, BacktestConfig::default()).run(&ramp(30), build, |r| {
+            if r.portfolio.trades[0].entry_price == 6.0 {
+                f64::NAN
+            } else {
+                r.metrics.total_return
+            }
+        });
+        assert_eq!(ranked.len(), 3);
+        assert!(ranked[2].score.is_nan());
+        assert!(!ranked[0].score.is_nan() && !ranked[1].score.is_nan());
+    }
+}
@@ -0,0 +1,253 @@
+//! # Orders
+//!
+//! Besides the market actions executed at the close
+//! ([`Action::EnterLong`](super::Action::EnterLong) and friends), a
+//! strategy can submit an [`Order`] with
+//! [`Action::Submit`](super::Action::Submit):
+//!
+//! - **limit**: buy at or below / sell at or above a price;
+//! - **stop**: buy once price trades up to / sell once it trades down to
+//!   a price, then fill like a market order;
+//! - **stop-limit**: once the stop trades, rest as a limit order;
+//! - **bracket**: any entry with a stop-loss and / or take-profit attached.
+//!   Once the entry fills, the two exits form a one-cancels-other (OCO)
+//!   pair: whichever fills first closes the position and cancels the
+//!   other.
+//!
+//! Orders submitted on a bar start working on the next one and are
+//! checked against its high and low, with fill prices from the
+//! configured [`FillModel`](super::execution::FillModel): a bar that gaps
+//! through a price fills at the open. One entry order is pending at a
+//! time; submitting another replaces it, and
+//! [`Action::Cancel`](super::Action::Cancel) drops it. Exits attached to
+//! an entry are worked from the fill on, on the rest of the fill bar
+//! under the [`IntrabarPolicy`]; change them with
+//! [`Action::SetExits`](super::Action::SetExits).
+//!
+//! When a bar reaches both the stop-loss and the take-profit, its candle
+//! cannot tell which came first. [`IntrabarPolicy`] decides, either
+//! outright (worst or best case) or by assuming a price path through the
+//! bar's open, high, low and close. With tick data
+//! ([`Backtester::run_with_ticks`](super::Backtester::run_with_ticks)),
+//! exits are replayed tick by tick instead and the policy only applies to
+//! bars without ticks.
+//!
+//! ```
+//! use rsta::backtest::orders::Order;
+//! use rsta::backtest::{Action, BacktestConfig, Backtester, Context, Quantity, Strategy};
+//! use rsta::indicators::Candle;
+//!
+//! // Buy a pullback to 98 with a 95 stop and a 105 target.
+//! struct Pullback;
+//! impl Strategy for Pullback {
+//!     fn on_candle(&mut self, _candle: &Candle, ctx: &Context) -> Action {
+//!         if ctx.candle_index == 0 {
+//!             let order = Order::long(Quantity::Fixed(10.0))
+//!                 .limit(98.0)
+//!                 .with_stop_loss(95.0)
+//!                 .with_take_profit(105.0);
+//!             Action::Submit(order)
+//!         } else {
+//!             Action::Hold
+//!         }
+//!     }
+//! }
+//!
+//! let bars = [(100.0, 101.0, 99.0), (99.0, 100.0, 97.0), (101.0, 106.0, 100.0)];
+//! let candles: Vec<Candle> = bars
+//!     .iter()
+//!     .enumerate()
+//!     .map(|(i, &(close, high, low))| Candle {
+//!         timestamp: i as u64, open: close, high, low, close, volume: 1.0,
+//!     })
+//!     .collect();
+//!
+//! let result = Backtester::new(BacktestConfig::default()).run(&candles, &mut Pullback).unwrap();
+//! let trade = result.portfolio.trades[0];
+//! assert_eq!((trade.entry_price, trade.exit_price), (98.0, 105.0));
+//! ```
+
+use crate::backtest::{Quantity, Side};
+use crate::indicators::{Candle, Tick};
+
+/// How an order is triggered and priced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderType {
+    /// Fill at the close of the bar the order is submitted on.
+    Market,
+    /// Fill at this price or better.
+    Limit(f64),
+    /// Once price trades at this level, fill like a market order.
+    Stop(f64),
+    /// Once price trades at `stop`, rest as a limit order at `limit`.
+    StopLimit {
+        /// Trigger price.
+        stop: f64,
+        /// Limit price once triggered.
+        limit: f64,
+    },
+}
+
+/// An entry order, optionally with bracket exits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Order {
+    /// Direction of the position to open.
+    pub side: Side,
+    /// Position size, resolved against cash at fill time.
+    pub quantity: Quantity,
+    /// Trigger and pricing.
+    pub kind: OrderType,
+    /// Stop-loss attached to the position once filled.
+    pub stop_loss: Option<f64>,
+    /// Take-profit attached to the position once filled.
+    pub take_profit: Option<f64>,
+}
+
+impl Order {
+    /// Market order opening a long.
+    pub fn long(quantity: Quantity) -> Self {
+        Self::new(Side::Long, quantity)
+    }
+
+    /// Market order opening a short.
+    pub fn short(quantity: Quantity) -> Self {
+        Self::new(Side::Short, quantity)
+    }
+
+    fn new(side: Side, quantity: Quantity) -> Self {
+        Self {
+            side,
+            quantity,
+            kind: OrderType::Market,
+            stop_loss: None,
+            take_profit: None,
+        }
+    }
+
+    /// Make this a limit order at `price`.
+    pub fn limit(mut self, price: f64) -> Self {
+        self.kind = OrderType::Limit(price);
+        self
+    }
+
+    /// Make this a stop order triggered at `price`.
+    pub fn stop(mut self, price: f64) -> Self {
+        self.kind = OrderType::Stop(price);
+        self
+    }
+
+    /// Make this a stop-limit order.
+    pub fn stop_limit(mut self, stop: f64, limit: f64) -> Self {
+        self.kind = OrderType::StopLimit { stop, limit };
+        self
+    }
+
+    /// Attach a stop-loss at `price`.
+    pub fn with_stop_loss(mut self, price: f64) -> Self {
+        self.stop_loss = Some(price);
+        self
+    }
+
+    /// Attach a take-profit at `price`.
+    pub fn with_take_profit(mut self, price: f64) -> Self {
+        self.take_profit = Some(price);
+        self
+    }
+}
+
+/// Which exit fills when one bar reaches both the stop-loss and the
+/// take-profit of a position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntrabarPolicy {
+    /// Assume the stop-loss traded first: conservative.
+    #[default]
+    WorstCase,
+    /// Assume the take-profit traded first: optimistic, an upper bound.
+    BestCase,
+    /// Assume the bar traded open → low → high → close when it closed at or
+    /// above its open, and open → high → low → close otherwise, and fill
+    /// whichever exit that path reaches first. Excursions also stop at the
+    /// exit instead of counting the whole bar.
+    OhlcPath,
+}
+
+/// Split `candle` into the legs price is assumed to have travelled, in
+/// order, each as a candle opening where the leg starts.
+///
+/// `ticks` (the bar's trades, in order) take precedence over `policy`:
+/// the first tick is a leg of its own, then each leg runs from one tick
+/// to the next. Without ticks, [`IntrabarPolicy::OhlcPath`] gives three
+/// legs and the other policies the whole bar.
+pub(crate) fn price_legs(candle: &Candle, policy: IntrabarPolicy, ticks: &[Tick]) -> Vec<Candle> {
+    let leg = |from: f64, to: f64| Candle {
+        timestamp: candle.timestamp,
+        open: from,
+        high: from.max(to),
+        low: from.min(to),
+        close: to,
+        volume: 0.0,
+    };
+    if let Some(first) = ticks.first() {
+        let mut legs = vec![leg(first.price, first.price)];
+        legs.extend(ticks.windows(2).map(|w| leg(w[0].price, w[1].price)));
+        return legs;
+    }
+    match policy {
+        IntrabarPolicy::WorstCase | IntrabarPolicy::BestCase => vec![*candle],
+        IntrabarPolicy::OhlcPath => {
+            let (first, second) = if candle.close >= candle.open {
+                (candle.low, candle.high)
+            } else {
+                (candle.high, candle.low)
+            };
+            vec![
+                leg(candle.open, first),
+                leg(first, second),
+                leg(second, candle.close),
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::TradeSide;
+
+    #[test]
+    fn test_price_legs() {
+        let up = Candle {
+            timestamp: 7,
+            open: 10.0,
+            high: 14.0,
+            low: 9.0,
+            close: 13.0,
+            volume: 1.0,
+        };
+        assert_eq!(price_legs(&up, IntrabarPolicy::WorstCase, &[]), vec![up]);
+
+        let path: Vec<(f64, f64)> = price_legs(&up, IntrabarPolicy::OhlcPath, &[])
+            .iter()
+            .map(|leg| (leg.open, leg.close))
+            .collect();
+        assert_eq!(path, vec![(10.0, 9.0), (9.0, 14.0), (14.0, 13.0)]);
+        let down = Candle { close: 9.5, ..up };
+        let legs = price_legs(&down, IntrabarPolicy::OhlcPath, &[]);
+        assert_eq!((legs[0].high, legs[1].low), (14.0, 9.0));
+
+        let ticks = [11.0, 12.5, 10.0].map(|price| Tick {
+            timestamp: 7,
+            price,
+            size: 1.0,
+            side: TradeSide::Unknown,
+        });
+        let legs = price_legs(&up, IntrabarPolicy::BestCase, &ticks);
+        assert_eq!(legs.len(), 3);
+        assert_eq!((legs[0].low, legs[0].high), (11.0, 11.0));
+        assert_eq!(
+            (legs[2].open, legs[2].low, legs[2].high),
+            (12.5, 10.0, 12.5)
+        );
+    }
+}
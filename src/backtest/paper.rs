@@ -0,0 +1,318 @@
+//! # Paper trading
+//!
+//! [`PaperTrader`] runs a signal on live data against a simulated account,
+//! with the engine of the [`Backtester`](super::Backtester): the same
+//! [`BacktestConfig`] fill and cost models, the same [`Portfolio`]
+//! book-keeping and the same order handling. Feed it each closed candle
+//! with the [`SignalEvent`] your pipeline produced for it — or let
+//! [`step`](PaperTrader::step) drive the [`Signal`] — and read positions,
+//! PnL and metrics at any time. Between candles, [`mark`](PaperTrader::mark)
+//! re-prices the open position on the latest trade.
+//!
+//! Events map to actions as in a strategy: `Long` enters long (reversing
+//! a short), `Short` enters short when shorts are enabled and otherwise
+//! only closes a long, `Exit` flattens, `Hold` and `None` do nothing.
+//! Market actions fill at the candle's close; [`act`](PaperTrader::act)
+//! takes any other [`Action`], including resting orders that work against
+//! later candles.
+//!
+//! ```
+//! use rsta::backtest::paper::PaperTrader;
+//! use rsta::backtest::{BacktestConfig, Quantity};
+//! use rsta::indicators::Candle;
+//! use rsta::signals::ThresholdAbove;
+//!
+//! let mut trader = PaperTrader::new(BacktestConfig::default()).with_quantity(Quantity::Fixed(10.0));
+//! let mut breakout = ThresholdAbove::new(100.0);
+//!
+//! for (t, close) in [98.0, 99.0, 101.0, 104.0].into_iter().enumerate() {
+//!     let candle = Candle { timestamp: t as u64, open: close, high: close, low: close, close, volume: 1.0 };
+//!     trader.step(&mut breakout, close, &candle);
+//! }
+//! assert_eq!(trader.position().unwrap().entry_price, 101.0);
+//! assert_eq!(trader.unrealized_pnl(), 30.0);
+//!
+//! trader.mark(103.0); // a trade between candles
+//! assert_eq!(trader.pnl(), 20.0);
+//! ```
+
+use crate::indicators::Candle;
+use crate::signals::{Signal, SignalEvent};
+
+use super::{
+    apply_action, compute_metrics, work_orders, Action, BacktestConfig, Metrics, Portfolio,
+    Position, Quantity, Side, Trade,
+};
+
+/// Simulated account driven by live signals.
+#[derive(Debug, Clone)]
+pub struct PaperTrader {
+    config: BacktestConfig,
+    portfolio: Portfolio,
+    quantity: Quantity,
+    shorts: bool,
+    candle: Option<Candle>,
+    last_price: Option<f64>,
+}
+
+impl PaperTrader {
+    /// Flat account with the cash and execution models of `config`.
+    pub fn new(config: BacktestConfig) -> Self {
+        Self {
+            portfolio: Portfolio::new(config.initial_cash),
+            config,
+            quantity: Quantity::AllCash,
+            shorts: false,
+            candle: None,
+            last_price: None,
+        }
+    }
+
+    /// Size entries with `quantity` (default: all cash).
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Open shorts on `Short` events instead of only closing longs.
+    pub fn with_shorts(mut self) -> Self {
+        self.shorts = true;
+        self
+    }
+
+    /// Process a closed `candle` and the signal `event` for it. Returns the
+    /// trades the candle closed.
+    pub fn on_candle(&mut self, candle: &Candle, event: Option<SignalEvent>) -> &[Trade] {
+        let action = self.action_for(event);
+        self.act(candle, action)
+    }
+
+    /// Feed `input` to `signal` and process `candle` with its event.
+    pub fn step<S: Signal>(
+        &mut self,
+        signal: &mut S,
+        input: S::Input,
+        candle: &Candle,
+    ) -> &[Trade] {
+        let event = signal.next(input);
+        self.on_candle(candle, event)
+    }
+
+    /// Process a closed `candle`, then apply `action` at its close as the
+    /// backtester would. Returns the trades the candle closed.
+    pub fn act(&mut self, candle: &Candle, action: Action) -> &[Trade] {
+        let closed = self.portfolio.trades.len();
+        work_orders(&mut self.portfolio, candle, &[], &self.config.execution());
+        self.candle = Some(*candle);
+        self.last_price = Some(candle.close);
+        let equity = self.portfolio.equity(candle.close);
+        self.portfolio.equity_curve.push((candle.timestamp, equity));
+        self.submit(action);
+        &self.portfolio.trades[closed..]
+    }
+
+    /// Apply `action` at the close of the last processed candle, for
+    /// decisions taken after the candle was fed. Does nothing before the
+    /// first candle. Returns the trades the action closed.
+    pub fn submit(&mut self, action: Action) -> &[Trade] {
+        let closed = self.portfolio.trades.len();
+        if let Some(candle) = self.candle {
+            apply_action(
+                &mut self.portfolio,
+                action,
+                &candle,
+                &self.config.execution(),
+            );
+            let equity = self.portfolio.equity(candle.close);
+            if let Some(last) = self.portfolio.equity_curve.last_mut() {
+                last.1 = equity;
+            }
+        }
+        &self.portfolio.trades[closed..]
+    }
+
+    /// Re-price the account at `price` without trading, e.g. on a trade
+    /// print inside the current candle.
+    pub fn mark(&mut self, price: f64) {
+        self.last_price = Some(price);
+    }
+
+    /// The open position, if any.
+    pub fn position(&self) -> Option<Position> {
+        self.portfolio.position
+    }
+
+    /// The simulated account: cash, position, pending order, closed trades
+    /// and the equity sampled at every candle.
+    pub fn portfolio(&self) -> &Portfolio {
+        &self.portfolio
+    }
+
+    /// Price of the last candle close or [`mark`](Self::mark).
+    pub fn last_price(&self) -> Option<f64> {
+        self.last_price
+    }
+
+    /// Equity marked at the last price; the cash before any price is seen.
+    pub fn equity(&self) -> f64 {
+        match self.last_price {
+            Some(price) => self.portfolio.equity(price),
+            None => self.portfolio.cash,
+        }
+    }
+
+    /// Net PnL of the closed trades.
+    pub fn realized_pnl(&self) -> f64 {
+        self.portfolio.trades.iter().map(|t| t.pnl).sum()
+    }
+
+    /// Gross PnL of the open position at the last price.
+    pub fn unrealized_pnl(&self) -> f64 {
+        match (self.portfolio.position, self.last_price) {
+            (Some(pos), Some(price)) => match pos.side {
+                Side::Long => pos.quantity * (price - pos.entry_price),
+                Side::Short => pos.quantity * (pos.entry_price - price),
+            },
+            _ => 0.0,
+        }
+    }
+
+    /// Change in equity since the start, all fees included.
+    pub fn pnl(&self) -> f64 {
+        self.equity() - self.config.initial_cash
+    }
+
+    /// Backtest metrics over the candles processed so far.
+    pub fn metrics(&self) -> Metrics {
+        compute_metrics(&self.portfolio, &self.config)
+    }
+
+    /// Back to a flat account with the initial cash.
+    pub fn reset(&mut self) {
+        self.portfolio = Portfolio::new(self.config.initial_cash);
+        self.candle = None;
+        self.last_price = None;
+    }
+
+    fn action_for(&self, event: Option<SignalEvent>) -> Action {
+        let held = self.portfolio.position.map(|p| p.side);
+        match event {
+            Some(SignalEvent::Long) => Action::EnterLong(self.quantity),
+            Some(SignalEvent::Short) if self.shorts => Action::EnterShort(self.quantity),
+            Some(SignalEvent::Short) if held == Some(Side::Long) => Action::Exit,
+            Some(SignalEvent::Exit) => Action::Exit,
+            _ => Action::Hold,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{Backtester, Context, Strategy};
+    use crate::signals::CrossUp;
+
+    /// Long while the close crosses above 100, flat when it falls back.
+    struct Crossing(CrossUp, CrossUp);
+
+    impl Crossing {
+        fn event(&mut self, close: f64) -> Option<SignalEvent> {
+            let up = self.0.next((close, 100.0));
+            let down = self.1.next((100.0, close));
+            match (up, down) {
+                (Some(SignalEvent::Long), _) => Some(SignalEvent::Long),
+                (_, Some(SignalEvent::Long)) => Some(SignalEvent::Exit),
+                _ => None,
+            }
+        }
+    }
+
+    impl Strategy for Crossing {
+        fn on_candle(&mut self, candle: &Candle, _ctx: &Context) -> Action {
+            match self.event(candle.close) {
+                Some(SignalEvent::Long) => Action::EnterLong(Quantity::PercentCash(0.5)),
+                Some(SignalEvent::Exit) => Action::Exit,
+                _ => Action::Hold,
+            }
+        }
+    }
+
+    fn crossing() -> Crossing {
+        Crossing(CrossUp::new(), CrossUp::new())
+    }
+
+    #[test]
+    fn test_matches_the_backtester() {
+        let candles: Vec<Candle> = [98.0, 101.0, 105.0, 99.0, 97.0, 102.0, 108.0, 104.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                timestamp: i as u64,
+                open: close - 1.0,
+                high: close + 2.0,
+                low: close - 2.0,
+                close,
+                volume: 1.0,
+            })
+            .collect();
+        let config = BacktestConfig {
+            fee_rate: 0.001,
+            slippage: 0.002,
+            ..BacktestConfig::default()
+        };
+        let backtest = Backtester::new(config.clone())
+            .run(&candles, &mut crossing())
+            .unwrap();
+
+        let mut trader = PaperTrader::new(config).with_quantity(Quantity::PercentCash(0.5));
+        let mut signal = crossing();
+        let mut closed = 0;
+        for candle in &candles {
+            let event = signal.event(candle.close);
+            closed += trader.on_candle(candle, event).len();
+        }
+        assert_eq!(closed, 1);
+        assert_eq!(trader.portfolio().trades.len(), 1);
+        assert_eq!(trader.equity(), backtest.metrics.final_equity);
+        assert_eq!(
+            trader.portfolio().equity_curve,
+            backtest.portfolio.equity_curve
+        );
+        assert_eq!(trader.metrics().trade_count, backtest.metrics.trade_count);
+        assert!(trader.position().is_some());
+        let expected = trader.realized_pnl() + trader.unrealized_pnl()
+            - trader
+                .position()
+                .map_or(0.0, |p| p.quantity * p.entry_price * 0.001);
+        assert!((trader.pnl() - expected).abs() < 1e-9);
+
+        trader.reset();
+        assert_eq!(trader.equity(), 10_000.0);
+        assert!(trader.position().is_none());
+    }
+
+    #[test]
+    fn test_short_events_only_close_longs_without_shorts() {
+        let candle = |close: f64| Candle {
+            timestamp: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        };
+        let mut trader = PaperTrader::new(BacktestConfig::default());
+        trader.on_candle(&candle(10.0), Some(SignalEvent::Short));
+        assert!(trader.position().is_none());
+        trader.on_candle(&candle(10.0), Some(SignalEvent::Long));
+        let closed = trader.on_candle(&candle(12.0), Some(SignalEvent::Short));
+        assert_eq!(closed.len(), 1);
+        assert!(trader.position().is_none());
+
+        let mut trader = PaperTrader::new(BacktestConfig::default()).with_shorts();
+        trader.on_candle(&candle(10.0), Some(SignalEvent::Short));
+        trader.mark(8.0);
+        assert_eq!(trader.position().map(|p| p.side), Some(Side::Short));
+        assert_eq!(trader.unrealized_pnl(), 2_000.0);
+    }
+}
@@ -0,0 +1,289 @@
+//! # R-multiples
+//!
+//! Trade results in units of the risk taken. The initial risk (R) of a
+//! position is the distance from its entry to its first protective stop;
+//! a trade's R-multiple is its net PnL divided by that risk times its
+//! size, so a `2R` winner made twice what the stop put on the line and a
+//! full stop-out is about `-1R`. Normalized that way, trades of different
+//! sizes and volatilities compare directly.
+//!
+//! The [`Backtester`](super::Backtester) records the initial risk on every
+//! [`Trade`] whose position had a stop-loss below a long's entry (above a
+//! short's), from a bracket [`Order`](super::orders::Order) or the first
+//! [`Action::SetExits`](super::Action::SetExits). Trades without one have
+//! no R-multiple and are left out of the aggregates below.
+//!
+//! ```
+//! use rsta::backtest::orders::Order;
+//! use rsta::backtest::r_multiple::RStats;
+//! use rsta::backtest::{Action, BacktestConfig, Backtester, Context, Quantity, Strategy};
+//! use rsta::indicators::Candle;
+//!
+//! /// Buys the first bar with a stop 2 below the close, sells the fourth.
+//! struct Bracket;
+//! impl Strategy for Bracket {
+//!     fn on_candle(&mut self, candle: &Candle, ctx: &Context) -> Action {
+//!         match ctx.candle_index {
+//!             0 => Action::Submit(Order::long(Quantity::Fixed(10.0)).with_stop_loss(candle.close - 2.0)),
+//!             3 => Action::Exit,
+//!             _ => Action::Hold,
+//!         }
+//!     }
+//! }
+//!
+//! let candles: Vec<Candle> = [100.0, 101.0, 103.0, 105.0]
+//!     .into_iter()
+//!     .map(|close| Candle { timestamp: 0, open: close, high: close, low: close, close, volume: 1.0 })
+//!     .collect();
+//! let result = Backtester::new(BacktestConfig::default()).run(&candles, &mut Bracket).unwrap();
+//!
+//! assert_eq!(result.portfolio.trades[0].r_multiple(), Some(2.5));
+//! let stats = RStats::from_trades(&result.portfolio.trades).unwrap();
+//! assert_eq!(stats.expectancy, 2.5);
+//! ```
+
+use crate::backtest::{Side, Trade};
+use crate::indicators::IndicatorError;
+
+/// Risk per unit of a `side` position entered at `entry_price` with a
+/// stop at `stop`, `None` when the stop is not on the losing side.
+pub fn initial_risk(side: Side, entry_price: f64, stop: f64) -> Option<f64> {
+    let risk = match side {
+        Side::Long => entry_price - stop,
+        Side::Short => stop - entry_price,
+    };
+    (risk > 0.0).then_some(risk)
+}
+
+/// `pnl` of `quantity` units in multiples of `risk` per unit, `None` for
+/// a position without risk.
+pub fn r_multiple(pnl: f64, quantity: f64, risk: f64) -> Option<f64> {
+    let at_risk = quantity * risk;
+    (at_risk > 0.0).then(|| pnl / at_risk)
+}
+
+/// R-multiples of the trades that have one, in trade order.
+pub fn r_multiples(trades: &[Trade]) -> Vec<f64> {
+    trades.iter().filter_map(Trade::r_multiple).collect()
+}
+
+/// Aggregate R statistics of a set of trades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RStats {
+    /// Number of trades with an R-multiple.
+    pub trades: usize,
+    /// Mean R-multiple: the expected result per trade in units of risk.
+    pub expectancy: f64,
+    /// Sum of the R-multiples.
+    pub total: f64,
+    /// Mean R-multiple of the winners, `0` without any.
+    pub average_win: f64,
+    /// Mean R-multiple of the losers (negative), `0` without any.
+    pub average_loss: f64,
+    /// Largest R-multiple.
+    pub best: f64,
+    /// Smallest R-multiple.
+    pub worst: f64,
+    /// Sample standard deviation of the R-multiples, `0` for one trade.
+    pub std_dev: f64,
+    /// System quality number: `sqrt(trades) * expectancy / std_dev`, `0`
+    /// when the R-multiples do not vary.
+    pub sqn: f64,
+}
+
+impl RStats {
+    /// Statistics of the trades with an R-multiple, `None` if there are
+    /// none.
+    pub fn from_trades(trades: &[Trade]) -> Option<Self> {
+        Self::from_r_multiples(&r_multiples(trades))
+    }
+
+    /// Statistics of R-multiples computed elsewhere, `None` if empty.
+    pub fn from_r_multiples(rs: &[f64]) -> Option<Self> {
+        if rs.is_empty() {
+            return None;
+        }
+        let n = rs.len() as f64;
+        let total: f64 = rs.iter().sum();
+        let expectancy = total / n;
+        let mean_of = |keep: fn(f64) -> bool| {
+            let (sum, count) = rs
+                .iter()
+                .filter(|&&r| keep(r))
+                .fold((0.0, 0usize), |(sum, count), &r| (sum + r, count + 1));
+            if count > 0 {
+                sum / count as f64
+            } else {
+                0.0
+            }
+        };
+        let std_dev = if rs.len() > 1 {
+            let var = rs.iter().map(|r| (r - expectancy).powi(2)).sum::<f64>() / (n - 1.0);
+            var.sqrt()
+        } else {
+            0.0
+        };
+        Some(Self {
+            trades: rs.len(),
+            expectancy,
+            total,
+            average_win: mean_of(|r| r > 0.0),
+            average_loss: mean_of(|r| r < 0.0),
+            best: rs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            worst: rs.iter().copied().fold(f64::INFINITY, f64::min),
+            std_dev,
+            sqn: if std_dev > 0.0 {
+                n.sqrt() * expectancy / std_dev
+            } else {
+                0.0
+            },
+        })
+    }
+}
+
+/// One bar of an R-multiple histogram: the trades with `lower <= R < upper`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RBin {
+    /// Inclusive lower edge.
+    pub lower: f64,
+    /// Exclusive upper edge.
+    pub upper: f64,
+    /// Number of trades in the bin.
+    pub count: usize,
+}
+
+/// Distribution of the trades' R-multiples in bins of `bin_width` R
+/// aligned on multiples of it (`[-1, 0)`, `[0, 1)`, ... for a width of
+/// 1), from the bin of the worst trade to that of the best. Empty bins in
+/// between are kept, so the result plots as is.
+///
+/// # Arguments
+/// * `trades` - Closed trades; those without an R-multiple are skipped
+/// * `bin_width` - Width of a bin in R (must be positive and finite)
+///
+/// # Returns
+/// * `Result<Vec<RBin>, IndicatorError>` - The bins in ascending order or an error
+pub fn histogram(trades: &[Trade], bin_width: f64) -> Result<Vec<RBin>, IndicatorError> {
+    if bin_width <= 0.0 || !bin_width.is_finite() {
+        return Err(IndicatorError::invalid_parameter(
+            "bin_width",
+            bin_width,
+            "must be positive and finite",
+        ));
+    }
+    let rs = r_multiples(trades);
+    let index = |r: f64| (r / bin_width).floor() as i64;
+    let (Some(first), Some(last)) = (
+        rs.iter().map(|&r| index(r)).min(),
+        rs.iter().map(|&r| index(r)).max(),
+    ) else {
+        return Ok(Vec::new());
+    };
+    let mut bins: Vec<RBin> = (first..=last)
+        .map(|i| RBin {
+            lower: i as f64 * bin_width,
+            upper: (i + 1) as f64 * bin_width,
+            count: 0,
+        })
+        .collect();
+    for r in rs {
+        bins[(index(r) - first) as usize].count += 1;
+    }
+    Ok(bins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{Action, BacktestConfig, Backtester, Context, Quantity, Strategy};
+    use crate::indicators::Candle;
+
+    fn trade(pnl: f64, initial_risk: Option<f64>) -> Trade {
+        Trade {
+            side: Side::Long,
+            quantity: 2.0,
+            entry_price: 100.0,
+            exit_price: 100.0,
+            entry_timestamp: 0,
+            exit_timestamp: 1,
+            pnl,
+            fees_paid: 0.0,
+            mae: 0.0,
+            mfe: 0.0,
+            initial_risk,
+        }
+    }
+
+    #[test]
+    fn test_stats_and_histogram() {
+        // Risking 2 × 5 = 10 per trade: +3R, -1R, +0.5R, -1R; one unsized.
+        let trades = [
+            trade(30.0, Some(5.0)),
+            trade(-10.0, Some(5.0)),
+            trade(5.0, Some(5.0)),
+            trade(-10.0, Some(5.0)),
+            trade(50.0, None),
+        ];
+        assert_eq!(r_multiples(&trades), vec![3.0, -1.0, 0.5, -1.0]);
+
+        let stats = RStats::from_trades(&trades).unwrap();
+        assert_eq!(stats.trades, 4);
+        assert_eq!(stats.total, 1.5);
+        assert_eq!(stats.expectancy, 0.375);
+        assert_eq!((stats.average_win, stats.average_loss), (1.75, -1.0));
+        assert_eq!((stats.best, stats.worst), (3.0, -1.0));
+        assert!((stats.sqn - 2.0 * 0.375 / stats.std_dev).abs() < 1e-12);
+        assert!(RStats::from_trades(&trades[4..]).is_none());
+
+        let bins = histogram(&trades, 1.0).unwrap();
+        let counts: Vec<(f64, usize)> = bins.iter().map(|b| (b.lower, b.count)).collect();
+        assert_eq!(
+            counts,
+            vec![(-1.0, 2), (0.0, 1), (1.0, 0), (2.0, 0), (3.0, 1)]
+        );
+        assert!(histogram(&trades, 0.0).is_err());
+        assert!(histogram(&[], 1.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_backtester_records_the_first_losing_side_stop() {
+        /// Shorts bar 0, protects it on bar 1 then trails the stop to a
+        /// profit on bar 2, covers on bar 3.
+        struct Script;
+        impl Strategy for Script {
+            fn on_candle(&mut self, _c: &Candle, ctx: &Context) -> Action {
+                let exits = |stop| Action::SetExits {
+                    stop_loss: Some(stop),
+                    take_profit: None,
+                };
+                match ctx.candle_index {
+                    0 => Action::EnterShort(Quantity::Fixed(1.0)),
+                    1 => exits(104.0),
+                    2 => exits(99.0),
+                    3 => Action::Exit,
+                    _ => Action::Hold,
+                }
+            }
+        }
+        let candles: Vec<Candle> = [100.0, 99.0, 97.0, 96.0]
+            .into_iter()
+            .map(|close| Candle {
+                timestamp: 0,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            })
+            .collect();
+        let result = Backtester::new(BacktestConfig::default())
+            .run(&candles, &mut Script)
+            .unwrap();
+        let t = result.portfolio.trades[0];
+        assert_eq!(t.initial_risk, Some(4.0));
+        assert_eq!(t.r_multiple(), Some(1.0));
+
+        // A stop on the winning side is no risk.
+        assert_eq!(initial_risk(Side::Long, 100.0, 101.0), None);
+    }
+}
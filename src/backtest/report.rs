@@ -0,0 +1,675 @@
+//! # Backtest reports
+//!
+//! A [`Report`] gathers what is usually shared about a backtest — summary
+//! statistics, monthly returns, the worst drawdowns and the trade list —
+//! from a single-asset [`BacktestResult`] or a multi-asset
+//! [`UniverseResult`], and renders it as:
+//!
+//! - [`to_json`](Report::to_json): one JSON object, for dashboards and
+//!   storage;
+//! - [`to_text`](Report::to_text): aligned plain-text tables, for a
+//!   terminal or a log;
+//! - [`to_html`](Report::to_html): a self-contained HTML page with the same
+//!   tables.
+//!
+//! Months are calendar months of the bar timestamps (Unix seconds), in UTC
+//! unless [`with_timezone`](Report::with_timezone) says otherwise.
+//!
+//! ```
+//! use rsta::backtest::report::Report;
+//! use rsta::backtest::{Action, BacktestConfig, Backtester, Context, Quantity, Strategy};
+//! use rsta::indicators::Candle;
+//!
+//! struct OneTrade;
+//! impl Strategy for OneTrade {
+//!     fn on_candle(&mut self, _candle: &Candle, ctx: &Context) -> Action {
+//!         match ctx.candle_index {
+//!             0 => Action::EnterLong(Quantity::AllCash),
+//!             40 => Action::Exit,
+//!             _ => Action::Hold,
+//!         }
+//!     }
+//! }
+//!
+//! // Seventy daily bars from 2024-01-01.
+//! let candles: Vec<Candle> = (0..70)
+//!     .map(|i| {
+//!         let close = 100.0 + (i as f64 * 0.2).sin() * 5.0 + i as f64 * 0.1;
+//!         Candle {
+//!             timestamp: 1_704_067_200 + i * 86_400,
+//!             open: close, high: close + 1.0, low: close - 1.0, close, volume: 1.0,
+//!         }
+//!     })
+//!     .collect();
+//! let config = BacktestConfig::default();
+//! let result = Backtester::new(config.clone()).run(&candles, &mut OneTrade).unwrap();
+//!
+//! let report = Report::from_backtest("XYZ", &result, &config);
+//! assert_eq!(report.monthly_returns.len(), 3); // January to March
+//! assert_eq!(report.trades.len(), 1);
+//! assert!(report.to_text().contains("Monthly returns"));
+//! assert!(report.to_json().starts_with('{'));
+//! assert!(report.to_html().contains("<table>"));
+//! ```
+//!
+//! [`BacktestResult`]: super::BacktestResult
+//! [`UniverseResult`]: super::universe::UniverseResult
+
+use std::fmt::Write as _;
+
+use crate::backtest::universe::{UniverseConfig, UniverseResult};
+use crate::backtest::{BacktestConfig, BacktestResult, Metrics, Side, Trade};
+use crate::calendar::TimeZone;
+
+/// Drawdowns listed in the text and HTML renderings.
+const DRAWDOWN_ROWS: usize = 5;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Return of one calendar month.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonthlyReturn {
+    /// Calendar year.
+    pub year: i32,
+    /// Month, 1 to 12.
+    pub month: u8,
+    /// Equity at the month's last bar over equity at the previous month's
+    /// last bar (the initial cash for the first month), minus one.
+    pub return_pct: f64,
+}
+
+/// One fall from an equity peak and the climb back.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    /// Timestamp of the peak the drawdown is measured from.
+    pub start: u64,
+    /// Timestamp of the lowest equity.
+    pub trough: u64,
+    /// Timestamp equity regained the peak, `None` if it never did.
+    pub recovery: Option<u64>,
+    /// Depth as a fraction of the peak, in `[0, 1]` while equity stays
+    /// positive.
+    pub depth: f64,
+}
+
+/// Shareable summary of a backtest. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// Starting cash.
+    pub initial_cash: f64,
+    /// Aggregate metrics of the run.
+    pub metrics: Metrics,
+    /// Fees paid on every fill.
+    pub total_fees: f64,
+    /// `(timestamp, equity)` at each bar's close.
+    pub equity_curve: Vec<(u64, f64)>,
+    /// Returns per calendar month, oldest first.
+    pub monthly_returns: Vec<MonthlyReturn>,
+    /// Drawdowns, deepest first.
+    pub drawdowns: Vec<Drawdown>,
+    /// Closed trades with their symbols, in the order they happened.
+    pub trades: Vec<(String, Trade)>,
+    timezone: TimeZone,
+}
+
+impl Report {
+    /// Report on a single-asset run, labelling its trades with `symbol`.
+    pub fn from_backtest(symbol: &str, result: &BacktestResult, config: &BacktestConfig) -> Self {
+        let trades: Vec<(String, Trade)> = result
+            .portfolio
+            .trades
+            .iter()
+            .map(|t| (symbol.to_string(), *t))
+            .collect();
+        // Fees of a still-open position are not part of any trade yet.
+        let total_fees = trades.iter().map(|(_, t)| t.fees_paid).sum();
+        Self::new(
+            config.initial_cash,
+            result.metrics,
+            total_fees,
+            result.portfolio.equity_curve.clone(),
+            trades,
+        )
+    }
+
+    /// Report on a multi-asset run.
+    pub fn from_universe(result: &UniverseResult, config: &UniverseConfig) -> Self {
+        Self::new(
+            config.backtest.initial_cash,
+            result.metrics,
+            result.portfolio.fees_paid(),
+            result.equity_curve.clone(),
+            result.portfolio.trades().to_vec(),
+        )
+    }
+
+    fn new(
+        initial_cash: f64,
+        metrics: Metrics,
+        total_fees: f64,
+        equity_curve: Vec<(u64, f64)>,
+        trades: Vec<(String, Trade)>,
+    ) -> Self {
+        let timezone = TimeZone::Utc;
+        Self {
+            initial_cash,
+            metrics,
+            total_fees,
+            monthly_returns: monthly_returns(&equity_curve, initial_cash, timezone),
+            drawdowns: drawdowns(&equity_curve, initial_cash),
+            equity_curve,
+            trades,
+            timezone,
+        }
+    }
+
+    /// Bucket months and print dates in `timezone` instead of UTC.
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = timezone;
+        self.monthly_returns = monthly_returns(&self.equity_curve, self.initial_cash, timezone);
+        self
+    }
+
+    /// The report as one JSON object with the fields of [`Report`].
+    /// Non-finite numbers (an infinite profit factor) are written as `null`.
+    pub fn to_json(&self) -> String {
+        let m = &self.metrics;
+        let mut out = String::from("{");
+        let _ = write!(
+            out,
+            "\"initial_cash\":{},\"total_fees\":{},\"metrics\":{{\
+             \"final_equity\":{},\"total_return\":{},\"max_drawdown\":{},\"sharpe\":{},\
+             \"win_rate\":{},\"trade_count\":{},\"profit_factor\":{}}}",
+            json_number(self.initial_cash),
+            json_number(self.total_fees),
+            json_number(m.final_equity),
+            json_number(m.total_return),
+            json_number(m.max_drawdown),
+            json_number(m.sharpe),
+            json_number(m.win_rate),
+            m.trade_count,
+            json_number(m.profit_factor),
+        );
+
+        let months: Vec<String> = self
+            .monthly_returns
+            .iter()
+            .map(|r| {
+                format!(
+                    "{{\"year\":{},\"month\":{},\"return_pct\":{}}}",
+                    r.year,
+                    r.month,
+                    json_number(r.return_pct)
+                )
+            })
+            .collect();
+        let _ = write!(out, ",\"monthly_returns\":[{}]", months.join(","));
+
+        let drawdowns: Vec<String> = self
+            .drawdowns
+            .iter()
+            .map(|d| {
+                let recovery = d.recovery.map_or("null".to_string(), |ts| ts.to_string());
+                format!(
+                    "{{\"start\":{},\"trough\":{},\"recovery\":{},\"depth\":{}}}",
+                    d.start,
+                    d.trough,
+                    recovery,
+                    json_number(d.depth)
+                )
+            })
+            .collect();
+        let _ = write!(out, ",\"drawdowns\":[{}]", drawdowns.join(","));
+
+        let trades: Vec<String> = self
+            .trades
+            .iter()
+            .map(|(symbol, t)| {
+                format!(
+                    "{{\"symbol\":{},\"side\":\"{}\",\"quantity\":{},\"entry_price\":{},\
+                     \"exit_price\":{},\"entry_timestamp\":{},\"exit_timestamp\":{},\
+                     \"pnl\":{},\"fees_paid\":{}}}",
+                    json_string(symbol),
+                    side_name(t.side),
+                    json_number(t.quantity),
+                    json_number(t.entry_price),
+                    json_number(t.exit_price),
+                    t.entry_timestamp,
+                    t.exit_timestamp,
+                    json_number(t.pnl),
+                    json_number(t.fees_paid)
+                )
+            })
+            .collect();
+        let _ = write!(out, ",\"trades\":[{}]", trades.join(","));
+
+        let curve: Vec<String> = self
+            .equity_curve
+            .iter()
+            .map(|&(ts, equity)| format!("[{ts},{}]", json_number(equity)))
+            .collect();
+        let _ = write!(out, ",\"equity_curve\":[{}]}}", curve.join(","));
+        out
+    }
+
+    /// The report as plain-text tables: statistics, monthly returns, the
+    /// five deepest drawdowns and the trade list.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (i, table) in self.tables().iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(&table.to_text());
+        }
+        out
+    }
+
+    /// The report as a self-contained HTML page with the tables of
+    /// [`to_text`](Self::to_text).
+    pub fn to_html(&self) -> String {
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Backtest report</title>\n<style>\n\
+             body { font-family: sans-serif; margin: 2em; }\n\
+             table { border-collapse: collapse; margin-bottom: 2em; }\n\
+             th, td { border: 1px solid #ccc; padding: 0.25em 0.75em; }\n\
+             td { text-align: right; font-variant-numeric: tabular-nums; }\n\
+             td:first-child { text-align: left; }\n\
+             </style>\n</head>\n<body>\n<h1>Backtest report</h1>\n",
+        );
+        for table in self.tables() {
+            out.push_str(&table.to_html());
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+
+    fn tables(&self) -> Vec<Table> {
+        vec![
+            self.stats_table(),
+            self.monthly_table(),
+            self.drawdown_table(),
+            self.trade_table(),
+        ]
+    }
+
+    fn stats_table(&self) -> Table {
+        let m = &self.metrics;
+        let period = match (self.equity_curve.first(), self.equity_curve.last()) {
+            (Some(&(first, _)), Some(&(last, _))) => {
+                format!("{} to {}", self.date(first), self.date(last))
+            }
+            _ => "-".to_string(),
+        };
+        let rows = [
+            ("Period", period),
+            ("Initial cash", format!("{:.2}", self.initial_cash)),
+            ("Final equity", format!("{:.2}", m.final_equity)),
+            ("Total return", percent(m.total_return)),
+            ("Max drawdown", percent(m.max_drawdown)),
+            ("Sharpe", format!("{:.2}", m.sharpe)),
+            ("Trades", m.trade_count.to_string()),
+            ("Win rate", percent(m.win_rate)),
+            ("Profit factor", format!("{:.2}", m.profit_factor)),
+            ("Fees paid", format!("{:.2}", self.total_fees)),
+        ];
+        Table {
+            title: "Statistics",
+            header: vec!["Metric".to_string(), "Value".to_string()],
+            rows: rows
+                .into_iter()
+                .map(|(name, value)| vec![name.to_string(), value])
+                .collect(),
+        }
+    }
+
+    fn monthly_table(&self) -> Table {
+        let mut header = vec!["Year".to_string()];
+        header.extend(MONTHS.iter().map(|m| m.to_string()));
+        header.push("Total".to_string());
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut year_growth = 1.0;
+        for (i, r) in self.monthly_returns.iter().enumerate() {
+            if i == 0 || self.monthly_returns[i - 1].year != r.year {
+                let mut row = vec![String::new(); 14];
+                row[0] = r.year.to_string();
+                rows.push(row);
+                year_growth = 1.0;
+            }
+            year_growth *= 1.0 + r.return_pct;
+            if let Some(row) = rows.last_mut() {
+                row[usize::from(r.month)] = format!("{:.2}", r.return_pct * 100.0);
+                row[13] = format!("{:.2}", (year_growth - 1.0) * 100.0);
+            }
+        }
+        Table {
+            title: "Monthly returns (%)",
+            header,
+            rows,
+        }
+    }
+
+    fn drawdown_table(&self) -> Table {
+        Table {
+            title: "Worst drawdowns",
+            header: ["Depth", "Start", "Trough", "Recovery"]
+                .map(String::from)
+                .to_vec(),
+            rows: self
+                .drawdowns
+                .iter()
+                .take(DRAWDOWN_ROWS)
+                .map(|d| {
+                    vec![
+                        percent(d.depth),
+                        self.date(d.start),
+                        self.date(d.trough),
+                        d.recovery.map_or("-".to_string(), |ts| self.date(ts)),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    fn trade_table(&self) -> Table {
+        Table {
+            title: "Trades",
+            header: [
+                "Symbol", "Side", "Quantity", "Entry", "Exit", "Opened", "Closed", "PnL", "Return",
+            ]
+            .map(String::from)
+            .to_vec(),
+            rows: self
+                .trades
+                .iter()
+                .map(|(symbol, t)| {
+                    vec![
+                        symbol.clone(),
+                        side_name(t.side).to_string(),
+                        format!("{:.4}", t.quantity),
+                        format!("{:.4}", t.entry_price),
+                        format!("{:.4}", t.exit_price),
+                        self.date(t.entry_timestamp),
+                        self.date(t.exit_timestamp),
+                        format!("{:.2}", t.pnl),
+                        percent(t.return_pct()),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    fn date(&self, timestamp: u64) -> String {
+        let date = self.timezone.date(timestamp);
+        format!("{:04}-{:02}-{:02}", date.year(), date.month(), date.day())
+    }
+}
+
+/// Returns per calendar month of an equity curve.
+fn monthly_returns(curve: &[(u64, f64)], initial: f64, timezone: TimeZone) -> Vec<MonthlyReturn> {
+    let mut months: Vec<(i32, u8, f64)> = Vec::new();
+    for &(ts, equity) in curve {
+        let date = timezone.date(ts);
+        match months.last_mut() {
+            Some(last) if (last.0, last.1) == (date.year(), date.month()) => last.2 = equity,
+            _ => months.push((date.year(), date.month(), equity)),
+        }
+    }
+    let mut previous = initial;
+    months
+        .into_iter()
+        .map(|(year, month, equity)| {
+            let return_pct = if previous != 0.0 {
+                equity / previous - 1.0
+            } else {
+                0.0
+            };
+            previous = equity;
+            MonthlyReturn {
+                year,
+                month,
+                return_pct,
+            }
+        })
+        .collect()
+}
+
+/// Every drawdown of an equity curve, deepest first.
+fn drawdowns(curve: &[(u64, f64)], initial: f64) -> Vec<Drawdown> {
+    let Some(&(first, _)) = curve.first() else {
+        return Vec::new();
+    };
+    let mut peak = (first, initial);
+    let mut current: Option<Drawdown> = None;
+    let mut all = Vec::new();
+    for &(ts, equity) in curve {
+        if equity >= peak.1 {
+            if let Some(mut drawdown) = current.take() {
+                drawdown.recovery = Some(ts);
+                all.push(drawdown);
+            }
+            peak = (ts, equity);
+        } else if peak.1 > 0.0 {
+            let depth = (peak.1 - equity) / peak.1;
+            let drawdown = current.get_or_insert(Drawdown {
+                start: peak.0,
+                trough: ts,
+                recovery: None,
+                depth,
+            });
+            if depth > drawdown.depth {
+                drawdown.depth = depth;
+                drawdown.trough = ts;
+            }
+        }
+    }
+    all.extend(current);
+    all.sort_by(|a, b| b.depth.total_cmp(&a.depth));
+    all
+}
+
+/// A titled table, rendered as text or HTML.
+struct Table {
+    title: &'static str,
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn to_text(&self) -> String {
+        let mut widths: Vec<usize> = self.header.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (cell, &width))| {
+                    if i == 0 {
+                        format!("{cell:<width$}")
+                    } else {
+                        format!("{cell:>width$}")
+                    }
+                })
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+
+        let mut out = format!("{}\n{}\n", self.title, "=".repeat(self.title.len()));
+        out.push_str(&line(&self.header));
+        out.push('\n');
+        if self.rows.is_empty() {
+            out.push_str("(none)\n");
+        }
+        for row in &self.rows {
+            out.push_str(&line(row));
+            out.push('\n');
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let mut out = format!("<h2>{}</h2>\n<table>\n<tr>", html_escape(self.title));
+        for cell in &self.header {
+            let _ = write!(out, "<th>{}</th>", html_escape(cell));
+        }
+        out.push_str("</tr>\n");
+        for row in &self.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                let _ = write!(out, "<td>{}</td>", html_escape(cell));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+        out
+    }
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Long => "long",
+        Side::Short => "short",
+    }
+}
+
+fn percent(fraction: f64) -> String {
+    format!("{:.2}%", fraction * 100.0)
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAN_1_2024: u64 = 1_704_067_200;
+    const DAY: u64 = 86_400;
+
+    #[test]
+    fn test_monthly_returns_and_drawdowns() {
+        // 2024-01-01, 2024-01-31, 2024-02-01, 2024-03-01
+        let curve = [
+            (JAN_1_2024, 100.0),
+            (JAN_1_2024 + 30 * DAY, 110.0),
+            (JAN_1_2024 + 31 * DAY, 99.0),
+            (JAN_1_2024 + 60 * DAY, 121.0),
+        ];
+        let months = monthly_returns(&curve, 100.0, TimeZone::Utc);
+        let summary: Vec<(u8, f64)> = months.iter().map(|m| (m.month, m.return_pct)).collect();
+        assert_eq!(summary.len(), 3);
+        assert!((summary[0].1 - 0.1).abs() < 1e-12);
+        assert!((summary[1].1 + 0.1).abs() < 1e-12);
+        assert_eq!(summary[2].0, 3);
+
+        let dd = drawdowns(
+            &[
+                (1, 100.0),
+                (2, 90.0),
+                (3, 95.0),
+                (4, 80.0),
+                (5, 120.0),
+                (6, 114.0),
+            ],
+            100.0,
+        );
+        assert_eq!(dd.len(), 2);
+        assert_eq!((dd[0].start, dd[0].trough, dd[0].recovery), (1, 4, Some(5)));
+        assert!((dd[0].depth - 0.2).abs() < 1e-12);
+        assert_eq!((dd[1].start, dd[1].recovery), (5, None));
+    }
+
+    #[test]
+    fn test_renderings() {
+        let metrics = Metrics {
+            final_equity: 1_050.0,
+            total_return: 0.05,
+            max_drawdown: 0.0,
+            sharpe: 1.5,
+            win_rate: 1.0,
+            trade_count: 1,
+            profit_factor: f64::INFINITY,
+        };
+        let trade = Trade {
+            side: Side::Short,
+            quantity: 10.0,
+            entry_price: 10.0,
+            exit_price: 5.0,
+            entry_timestamp: JAN_1_2024,
+            exit_timestamp: JAN_1_2024 + DAY,
+            pnl: 50.0,
+            fees_paid: 0.0,
+            mae: 0.0,
+            mfe: 5.0,
+            initial_risk: None,
+        };
+        let curve = vec![(JAN_1_2024, 1_000.0), (JAN_1_2024 + DAY, 1_050.0)];
+        let trades = vec![("A\"<B>".to_string(), trade)];
+        let report = Report::new(1_000.0, metrics, 0.0, curve, trades);
+
+        let json = report.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["metrics"]["profit_factor"].is_null());
+        assert_eq!(value["trades"][0]["symbol"], "A\"<B>");
+        assert_eq!(value["trades"][0]["side"], "short");
+        assert_eq!(value["monthly_returns"][0]["month"], 1);
+        assert_eq!(value["equity_curve"][1][1], 1_050.0);
+
+        let text = report.to_text();
+        assert!(text.contains("Period         2024-01-01 to 2024-01-02"));
+        assert!(text
+            .contains("Worst drawdowns\n===============\nDepth  Start  Trough  Recovery\n(none)"));
+        assert!(text.contains("2024  5.00"));
+
+        let html = report.to_html();
+        assert!(html.contains("<td>A&quot;&lt;B&gt;</td>"));
+        assert!(html.ends_with("</html>\n"));
+
+        // Dates move with the time zone
+        let shifted = report.with_timezone(TimeZone::Fixed(-3_600));
+        assert!(shifted.to_text().contains("2023-12-31"));
+        assert_eq!(shifted.monthly_returns.len(), 2);
+    }
+}
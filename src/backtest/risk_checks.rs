@@ -0,0 +1,441 @@
+//! # Pre-trade risk checks
+//!
+//! [`RiskLimits`] bounds what a strategy may do; a [`RiskManager`] checks
+//! every order against them before it reaches the venue and answers with
+//! a structured [`RiskViolation`] when one is breached:
+//!
+//! | Rule | Breached when |
+//! |---|---|
+//! | [`RiskRule::MaxPositionSize`] | an entry would hold more units than the limit |
+//! | [`RiskRule::MaxExposure`] | an entry's notional in one symbol exceeds the limit |
+//! | [`RiskRule::MaxDailyLoss`] | equity has fallen by the limit or more since the day began |
+//! | [`RiskRule::OrderRate`] | the limit of orders within the time window is used up |
+//!
+//! Only entries are ever refused: exits, cancellations and exit updates
+//! reduce risk and always pass, though exits count towards the order rate.
+//! Cash-based sizes are valued at the last price against current equity,
+//! before fees.
+//!
+//! The manager keeps its own view of the account from the candles and
+//! fills it is shown. On an [`EventBus`](super::events::EventBus), a
+//! [`RiskStage`] placed in front of the execution stage does that
+//! wiring: it stops refused [`OrderEvent`]s and publishes the violation as
+//! an [`Event::Risk`].
+//!
+//! ```
+//! use rsta::backtest::risk_checks::{RiskLimits, RiskManager, RiskRule};
+//! use rsta::backtest::{Action, Quantity};
+//! use rsta::indicators::Candle;
+//!
+//! let limits = RiskLimits::new().with_max_position_size(100.0).unwrap();
+//! let mut risk = RiskManager::new(limits, 10_000.0);
+//! risk.on_candle("AAPL", &Candle { timestamp: 0, open: 50.0, high: 50.0, low: 50.0, close: 50.0, volume: 1.0 });
+//!
+//! assert!(risk.check("AAPL", &Action::EnterLong(Quantity::Fixed(80.0)), 0).is_ok());
+//! // All cash buys 200 shares at 50.
+//! let violation = risk.check("AAPL", &Action::EnterLong(Quantity::AllCash), 0).unwrap_err();
+//! assert_eq!(violation.rule, RiskRule::MaxPositionSize);
+//! assert_eq!(violation.value, 200.0);
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::calendar::{Date, TimeZone};
+use crate::indicators::{Candle, IndicatorError};
+use crate::instrument;
+use crate::portfolio;
+
+use super::events::{Event, Handler, OrderEvent, Propagation};
+use super::execution::OrderSide;
+use super::{Action, Quantity};
+
+/// Limits enforced by a [`RiskManager`]. All are off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RiskLimits {
+    /// Largest position in one symbol, in units.
+    pub max_position_size: Option<f64>,
+    /// Largest notional held in one symbol.
+    pub max_exposure: Option<f64>,
+    /// Largest loss in one day, in account currency.
+    pub max_daily_loss: Option<f64>,
+    /// At most `.0` orders within any `.1` seconds.
+    pub max_order_rate: Option<(usize, u64)>,
+    /// Time zone in which days start, for the daily loss. Default: UTC.
+    pub timezone: TimeZone,
+}
+
+impl RiskLimits {
+    /// No limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cap the position in any symbol at `units` (must be positive).
+    pub fn with_max_position_size(mut self, units: f64) -> Result<Self, IndicatorError> {
+        self.max_position_size = Some(positive("max_position_size", units)?);
+        Ok(self)
+    }
+
+    /// Cap the notional in any symbol at `notional` (must be positive).
+    pub fn with_max_exposure(mut self, notional: f64) -> Result<Self, IndicatorError> {
+        self.max_exposure = Some(positive("max_exposure", notional)?);
+        Ok(self)
+    }
+
+    /// Refuse entries for the rest of the day once equity has fallen by
+    /// `loss` (must be positive) since the day began.
+    pub fn with_max_daily_loss(mut self, loss: f64) -> Result<Self, IndicatorError> {
+        self.max_daily_loss = Some(positive("max_daily_loss", loss)?);
+        Ok(self)
+    }
+
+    /// Allow at most `orders` (at least 1) within any `window` seconds
+    /// (at least 1).
+    pub fn with_max_order_rate(
+        mut self,
+        orders: usize,
+        window: u64,
+    ) -> Result<Self, IndicatorError> {
+        if orders == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "orders",
+                orders,
+                "must be at least 1",
+            ));
+        }
+        if window == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "window",
+                window,
+                "must be at least 1",
+            ));
+        }
+        self.max_order_rate = Some((orders, window));
+        Ok(self)
+    }
+
+    /// Start days for the daily loss in `timezone`.
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+}
+
+fn positive(name: &str, value: f64) -> Result<f64, IndicatorError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(IndicatorError::invalid_parameter(
+            name,
+            value,
+            "must be positive and finite",
+        ))
+    }
+}
+
+/// Which limit an order breached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiskRule {
+    /// [`RiskLimits::max_position_size`].
+    MaxPositionSize,
+    /// [`RiskLimits::max_exposure`].
+    MaxExposure,
+    /// [`RiskLimits::max_daily_loss`].
+    MaxDailyLoss,
+    /// [`RiskLimits::max_order_rate`].
+    OrderRate,
+}
+
+/// An order refused by a [`RiskManager`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskViolation {
+    /// Symbol of the order.
+    pub symbol: String,
+    /// Time of the check.
+    pub timestamp: u64,
+    /// The rule breached.
+    pub rule: RiskRule,
+    /// The rule's limit.
+    pub limit: f64,
+    /// The value the order would have reached: units, notional, the day's
+    /// loss or the orders in the window.
+    pub value: f64,
+}
+
+/// Checks orders against [`RiskLimits`] and tracks the state they need.
+#[derive(Debug, Clone)]
+pub struct RiskManager {
+    limits: RiskLimits,
+    book: portfolio::Portfolio,
+    day: Option<Date>,
+    day_start_equity: f64,
+    orders: VecDeque<u64>,
+}
+
+impl RiskManager {
+    /// Manager enforcing `limits` for an account starting with
+    /// `initial_cash`.
+    pub fn new(limits: RiskLimits, initial_cash: f64) -> Self {
+        Self {
+            limits,
+            book: portfolio::Portfolio::new(initial_cash),
+            day: None,
+            day_start_equity: initial_cash,
+            orders: VecDeque::new(),
+        }
+    }
+
+    /// The limits enforced.
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Record a candle of `symbol`: the price entries are valued at, and
+    /// the start of a new day for the daily loss.
+    pub fn on_candle(&mut self, symbol: &str, candle: &Candle) {
+        let date = self.limits.timezone.date(candle.timestamp);
+        if self.day != Some(date) {
+            self.day = Some(date);
+            self.day_start_equity = self.book.equity();
+        }
+        self.book.mark(symbol, candle.close);
+    }
+
+    /// Record a fill of the account.
+    pub fn on_fill(
+        &mut self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        fee: f64,
+        timestamp: u64,
+    ) -> Result<(), IndicatorError> {
+        match side {
+            OrderSide::Buy => self.book.buy(symbol, quantity, price, fee, timestamp)?,
+            OrderSide::Sell => self.book.sell(symbol, quantity, price, fee, timestamp)?,
+        };
+        Ok(())
+    }
+
+    /// Change in equity since the current day began.
+    pub fn daily_pnl(&self) -> f64 {
+        self.book.equity() - self.day_start_equity
+    }
+
+    /// The account as the manager sees it.
+    pub fn portfolio(&self) -> &portfolio::Portfolio {
+        &self.book
+    }
+
+    /// Check `action` for `symbol` at `timestamp`. An accepted order
+    /// counts towards the order rate.
+    pub fn check(
+        &mut self,
+        symbol: &str,
+        action: &Action,
+        timestamp: u64,
+    ) -> Result<(), RiskViolation> {
+        let violation = |rule, limit, value| RiskViolation {
+            symbol: symbol.to_string(),
+            timestamp,
+            rule,
+            limit,
+            value,
+        };
+        let quantity = match *action {
+            Action::EnterLong(quantity) | Action::EnterShort(quantity) => quantity,
+            Action::Submit(order) => order.quantity,
+            Action::Exit => {
+                self.orders.push_back(timestamp);
+                return Ok(());
+            }
+            Action::Hold | Action::SetExits { .. } | Action::Cancel => return Ok(()),
+        };
+
+        if let Some(limit) = self.limits.max_daily_loss {
+            let loss = -self.daily_pnl();
+            if loss >= limit {
+                return Err(violation(RiskRule::MaxDailyLoss, limit, loss));
+            }
+        }
+        if let Some((max, window)) = self.limits.max_order_rate {
+            while self
+                .orders
+                .front()
+                .is_some_and(|&t| t + window <= timestamp)
+            {
+                self.orders.pop_front();
+            }
+            if self.orders.len() >= max {
+                return Err(violation(
+                    RiskRule::OrderRate,
+                    max as f64,
+                    (self.orders.len() + 1) as f64,
+                ));
+            }
+        }
+        let price = self.book.last_price(symbol);
+        let units = match (quantity, price) {
+            (Quantity::Fixed(units), _) => Some(units),
+            (Quantity::AllCash, Some(price)) => Some(self.book.equity() / price),
+            (Quantity::PercentCash(pct), Some(price)) => {
+                Some(self.book.equity() * pct.clamp(0.0, 1.0) / price)
+            }
+            _ => None,
+        };
+        if let (Some(limit), Some(units)) = (self.limits.max_position_size, units) {
+            if units > limit {
+                return Err(violation(RiskRule::MaxPositionSize, limit, units));
+            }
+        }
+        if let (Some(limit), Some(units), Some(price)) = (self.limits.max_exposure, units, price) {
+            if units * price > limit {
+                return Err(violation(RiskRule::MaxExposure, limit, units * price));
+            }
+        }
+        self.orders.push_back(timestamp);
+        Ok(())
+    }
+}
+
+/// [`RiskManager`] on an [`EventBus`](super::events::EventBus).
+///
+/// Feeds the manager the candle and fill events it sees and checks each
+/// [`OrderEvent`]; a refused order stops there and an [`Event::Risk`]
+/// reports why. Place it before the execution stage.
+#[derive(Debug, Clone)]
+pub struct RiskStage {
+    manager: RiskManager,
+}
+
+impl RiskStage {
+    /// Stage checking orders with `manager`.
+    pub fn new(manager: RiskManager) -> Self {
+        Self { manager }
+    }
+
+    /// The underlying manager.
+    pub fn manager(&self) -> &RiskManager {
+        &self.manager
+    }
+}
+
+impl Handler for RiskStage {
+    fn handle(&mut self, event: &Event, out: &mut Vec<Event>) -> Propagation {
+        match event {
+            Event::Candle(e) => self.manager.on_candle(&e.symbol, &e.candle),
+            Event::Fill(e) => {
+                // Fills come from the venue; a malformed one is not ours to refuse.
+                let _ =
+                    self.manager
+                        .on_fill(&e.symbol, e.side, e.quantity, e.price, 0.0, e.timestamp);
+            }
+            Event::Order(OrderEvent {
+                symbol,
+                timestamp,
+                action,
+            }) => {
+                if let Err(violation) = self.manager.check(symbol, action, *timestamp) {
+                    instrument::warning!(
+                        symbol = %violation.symbol,
+                        timestamp = violation.timestamp,
+                        rule = ?violation.rule,
+                        limit = violation.limit,
+                        value = violation.value,
+                        "order refused"
+                    );
+                    out.push(Event::Risk(violation));
+                    return Propagation::Stop;
+                }
+            }
+            _ => {}
+        }
+        Propagation::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::events::{EventBus, PaperStage};
+    use crate::backtest::paper::PaperTrader;
+    use crate::backtest::BacktestConfig;
+
+    fn candle(timestamp: u64, close: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_daily_loss_and_order_rate() {
+        let limits = RiskLimits::new()
+            .with_max_daily_loss(100.0)
+            .unwrap()
+            .with_max_order_rate(2, 60)
+            .unwrap();
+        let mut risk = RiskManager::new(limits, 10_000.0);
+        let buy = Action::EnterLong(Quantity::Fixed(10.0));
+
+        risk.on_candle("X", &candle(0, 100.0));
+        assert!(risk.check("X", &buy, 0).is_ok());
+        risk.on_fill("X", OrderSide::Buy, 10.0, 100.0, 0.0, 0)
+            .unwrap();
+        assert!(risk.check("X", &Action::Exit, 10).is_ok());
+        let rate = risk.check("X", &buy, 20).unwrap_err();
+        assert_eq!((rate.rule, rate.value), (RiskRule::OrderRate, 3.0));
+        // The window has moved past the first order.
+        assert!(risk.check("X", &buy, 61).is_ok());
+
+        risk.on_candle("X", &candle(3_600, 89.0));
+        let loss = risk.check("X", &buy, 7_200).unwrap_err();
+        assert_eq!((loss.rule, loss.value), (RiskRule::MaxDailyLoss, 110.0));
+        // Exits always pass; a new day resets the loss.
+        assert!(risk.check("X", &Action::Exit, 7_200).is_ok());
+        risk.on_candle("X", &candle(86_400, 89.0));
+        assert_eq!(risk.daily_pnl(), 0.0);
+        assert!(risk.check("X", &buy, 86_400).is_ok());
+
+        assert!(RiskLimits::new().with_max_exposure(-1.0).is_err());
+        assert!(RiskLimits::new().with_max_order_rate(0, 60).is_err());
+    }
+
+    #[test]
+    fn test_stage_vetoes_orders_on_the_bus() {
+        let limits = RiskLimits::new().with_max_exposure(1_000.0).unwrap();
+        let mut bus = EventBus::new();
+        bus.add(|event: &Event, out: &mut Vec<Event>| {
+            if let Event::Candle(e) = event {
+                out.push(Event::Order(OrderEvent {
+                    symbol: e.symbol.clone(),
+                    timestamp: e.candle.timestamp,
+                    action: Action::EnterLong(Quantity::Fixed(10.0)),
+                }));
+            }
+            Propagation::Continue
+        });
+        bus.add(RiskStage::new(RiskManager::new(limits, 10_000.0)));
+        bus.add(PaperStage::new(
+            "X",
+            PaperTrader::new(BacktestConfig::default()),
+        ));
+
+        // 10 units at 150 is 1 500 of exposure: refused.
+        let events = bus.publish(Event::candle("X", candle(0, 150.0)));
+        let violation = events.iter().find_map(|e| match e {
+            Event::Risk(v) => Some(v),
+            _ => None,
+        });
+        assert_eq!(violation.map(|v| v.rule), Some(RiskRule::MaxExposure));
+        assert!(!events.iter().any(|e| matches!(e, Event::Fill(_))));
+
+        let events = bus.publish(Event::candle("X", candle(1, 90.0)));
+        assert!(events.iter().any(|e| matches!(e, Event::Fill(_))));
+    }
+}
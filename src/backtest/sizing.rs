@@ -122,6 +122,54 @@ pub fn risk_based(equity: f64, risk_fraction: f64, entry: f64, stop: f64) -> f64
     dollar_risk / stop_distance
 }
 
+/// ATR-stop sizing: [`risk_based`] with the stop placed `atr_multiple`
+/// ATRs away from the entry, the usual "risk 1% with a 2-ATR stop" rule.
+///
+/// `units = (equity × risk_fraction) / (atr × atr_multiple)`
+///
+/// Returns `0.0` on bad inputs (non-finite, non-positive ATR or
+/// multiple, …).
+///
+/// # Example
+/// ```
+/// use rsta::backtest::sizing::atr_risk_based;
+///
+/// // 10 000 USD, risk 1%, ATR 2.5, stop at 2 ATR → 100 / 5 = 20 units.
+/// let units = atr_risk_based(10_000.0, 0.01, 2.5, 2.0);
+/// assert!((units - 20.0).abs() < 1e-9);
+/// ```
+pub fn atr_risk_based(equity: f64, risk_fraction: f64, atr: f64, atr_multiple: f64) -> f64 {
+    if !equity.is_finite()
+        || !risk_fraction.is_finite()
+        || !atr.is_finite()
+        || !atr_multiple.is_finite()
+        || equity <= 0.0
+        || risk_fraction <= 0.0
+        || atr <= 0.0
+        || atr_multiple <= 0.0
+    {
+        return 0.0;
+    }
+    equity * risk_fraction.clamp(0.0, 1.0) / (atr * atr_multiple)
+}
+
+/// Convert a fraction of equity (from [`kelly`], [`fixed_fractional`],
+/// …) into units at `price`, for `Quantity::Fixed`.
+///
+/// Returns `0.0` on non-finite or non-positive inputs.
+pub fn fraction_to_units(equity: f64, fraction: f64, price: f64) -> f64 {
+    if !equity.is_finite()
+        || !fraction.is_finite()
+        || !price.is_finite()
+        || equity <= 0.0
+        || fraction <= 0.0
+        || price <= 0.0
+    {
+        return 0.0;
+    }
+    equity * fraction.clamp(0.0, 1.0) / price
+}
+
 /// Volatility-targeted sizing: scale the position so that the expected
 /// per-bar P&L volatility is `target_volatility × equity`.
 ///
@@ -260,6 +308,34 @@ mod tests {
         assert!(approx(risk_based(10_000.0, 5.0, 100.0, 98.0), 5_000.0));
     }
 
+    // ----- atr_risk_based -----
+
+    #[test]
+    fn atr_risk_based_matches_risk_based() {
+        // A 2-ATR stop with ATR 1.5 is a stop 3.0 below entry.
+        let atr = atr_risk_based(20_000.0, 0.02, 1.5, 2.0);
+        assert!(approx(atr, risk_based(20_000.0, 0.02, 100.0, 97.0)));
+    }
+
+    #[test]
+    fn atr_risk_based_rejects_bad_inputs() {
+        assert_eq!(atr_risk_based(10_000.0, 0.01, 0.0, 2.0), 0.0);
+        assert_eq!(atr_risk_based(10_000.0, 0.01, 2.0, 0.0), 0.0);
+        assert_eq!(atr_risk_based(10_000.0, 0.01, f64::NAN, 2.0), 0.0);
+        assert_eq!(atr_risk_based(-1.0, 0.01, 2.0, 2.0), 0.0);
+    }
+
+    // ----- fraction_to_units -----
+
+    #[test]
+    fn fraction_to_units_converts_kelly() {
+        // Kelly 0.4 of 10k at price 50 → 80 units.
+        let f = kelly(0.6, 2.0, 1.0);
+        assert!(approx(fraction_to_units(10_000.0, f, 50.0), 80.0));
+        assert_eq!(fraction_to_units(10_000.0, 0.5, 0.0), 0.0);
+        assert_eq!(fraction_to_units(10_000.0, 0.0, 50.0), 0.0);
+    }
+
     // ----- volatility_targeted -----
 
     #[test]
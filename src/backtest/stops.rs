@@ -0,0 +1,530 @@
+//! # Stop management
+//!
+//! Stateful exit rules for one open position. Create a manager at the
+//! fill, feed it every later bar with [`StopManager::update`], and it
+//! reports a [`StopExit`] on the bar that reaches the stop; in between,
+//! [`StopManager::stop`] is the level to protect the position with.
+//!
+//! | Manager | Stop |
+//! |---|---|
+//! | [`AtrTrailingStop`] | a multiple of the ATR behind the best price since entry (chandelier exit) |
+//! | [`PercentTrailingStop`] | a fixed fraction behind the best price since entry |
+//! | [`BreakevenStop`] | an initial stop, moved to the entry once price runs a multiple of the initial risk (R) |
+//! | [`TimeStop`] | none: exits at the close after a number of bars |
+//!
+//! A [`StopGroup`] runs several at once and keeps the tightest stop.
+//!
+//! A bar is checked against the stop of the previous bar before its own
+//! prices move the stop, so a stop never triggers on the bar that set it.
+//! Exits fill at the stop, or at the open when the bar gapped through it.
+//! The managers only need candles, so the same code protects positions in
+//! a [`Strategy`](super::Strategy) — return [`Action::Exit`](super::Action::Exit)
+//! or pass `stop()` to [`Action::SetExits`](super::Action::SetExits) —
+//! and in live code.
+//!
+//! ```
+//! use rsta::backtest::stops::{BreakevenStop, StopKind, StopManager};
+//! use rsta::backtest::Side;
+//! use rsta::indicators::Candle;
+//!
+//! // Long at 100 with a 95 stop; move to breakeven once 1R (105) trades.
+//! let mut stop = BreakevenStop::new(Side::Long, 100.0, 95.0, 1.0).unwrap();
+//! let bar = |high: f64, low: f64| Candle { timestamp: 0, open: high, high, low, close: low, volume: 1.0 };
+//!
+//! assert!(stop.update(&bar(104.0, 99.0)).is_none());
+//! assert_eq!(stop.stop(), Some(95.0));
+//! assert!(stop.update(&bar(106.0, 101.0)).is_none());
+//! assert_eq!(stop.stop(), Some(100.0));
+//!
+//! let exit = stop.update(&bar(102.0, 99.0)).unwrap();
+//! assert_eq!((exit.price, exit.kind), (100.0, StopKind::Breakeven));
+//! ```
+
+use std::fmt::Debug;
+
+use crate::backtest::Side;
+#[cfg(feature = "volatility")]
+use crate::indicators::volatility::Atr;
+#[cfg(feature = "volatility")]
+use crate::indicators::Indicator;
+use crate::indicators::{Candle, IndicatorError};
+
+/// Which rule closed the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StopKind {
+    /// A trailing stop.
+    Trailing,
+    /// The initial stop of a [`BreakevenStop`].
+    Initial,
+    /// A stop moved to the entry price.
+    Breakeven,
+    /// A [`TimeStop`].
+    Time,
+}
+
+/// Exit reported by a [`StopManager`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StopExit {
+    /// Fill price of the exit.
+    pub price: f64,
+    /// Rule that triggered it.
+    pub kind: StopKind,
+}
+
+/// Exit rule tracking one open position.
+pub trait StopManager: Debug {
+    /// Account for a bar traded while the position was open. Returns the
+    /// exit if the bar reached the stop, otherwise moves the stop with the
+    /// bar's prices.
+    fn update(&mut self, candle: &Candle) -> Option<StopExit>;
+
+    /// Stop working on the next bar, if any.
+    fn stop(&self) -> Option<f64>;
+}
+
+/// Fill price if `candle` reaches `stop` of a `side` position.
+fn hit(side: Side, stop: f64, candle: &Candle) -> Option<f64> {
+    match side {
+        Side::Long if candle.low <= stop => Some(stop.min(candle.open)),
+        Side::Short if candle.high >= stop => Some(stop.max(candle.open)),
+        _ => None,
+    }
+}
+
+/// The stop closer to the price of the two.
+fn tighter(side: Side, a: f64, b: f64) -> f64 {
+    match side {
+        Side::Long => a.max(b),
+        Side::Short => a.min(b),
+    }
+}
+
+/// Best price of `candle` for a `side` position.
+fn best(side: Side, candle: &Candle) -> f64 {
+    match side {
+        Side::Long => candle.high,
+        Side::Short => candle.low,
+    }
+}
+
+/// `price` moved `distance` against a `side` position.
+fn behind(side: Side, price: f64, distance: f64) -> f64 {
+    match side {
+        Side::Long => price - distance,
+        Side::Short => price + distance,
+    }
+}
+
+fn exit(side: Side, stop: Option<f64>, candle: &Candle, kind: StopKind) -> Option<StopExit> {
+    let price = hit(side, stop?, candle)?;
+    Some(StopExit { price, kind })
+}
+
+// ---------------------------------------------------------------------------
+// Trailing stops
+// ---------------------------------------------------------------------------
+
+/// Trailing stop a fixed fraction behind the best price since entry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentTrailingStop {
+    side: Side,
+    fraction: f64,
+    stop: f64,
+}
+
+impl PercentTrailingStop {
+    /// Create a new percent trailing stop
+    ///
+    /// # Arguments
+    /// * `side` - Direction of the position
+    /// * `entry_price` - Fill price of the entry, where trailing starts
+    /// * `fraction` - Distance behind the best price, as a fraction of it (in `(0, 1)`)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new stop or an error
+    pub fn new(side: Side, entry_price: f64, fraction: f64) -> Result<Self, IndicatorError> {
+        if fraction <= 0.0 || fraction >= 1.0 || fraction.is_nan() {
+            return Err(IndicatorError::invalid_parameter(
+                "fraction",
+                fraction,
+                "must be in (0, 1)",
+            ));
+        }
+        let mut stop = Self {
+            side,
+            fraction,
+            stop: 0.0,
+        };
+        stop.stop = stop.level(entry_price);
+        Ok(stop)
+    }
+
+    fn level(&self, price: f64) -> f64 {
+        behind(self.side, price, price * self.fraction)
+    }
+}
+
+impl StopManager for PercentTrailingStop {
+    fn update(&mut self, candle: &Candle) -> Option<StopExit> {
+        let exit = exit(self.side, Some(self.stop), candle, StopKind::Trailing);
+        if exit.is_none() {
+            let level = self.level(best(self.side, candle));
+            self.stop = tighter(self.side, self.stop, level);
+        }
+        exit
+    }
+
+    fn stop(&self) -> Option<f64> {
+        Some(self.stop)
+    }
+}
+
+/// Trailing stop a multiple of the ATR behind the best price since entry
+/// (a chandelier exit).
+///
+/// The ATR is computed from the bars fed to the stop, plus any history
+/// given with [`with_history`](Self::with_history); there is no stop
+/// until it has warmed up.
+#[cfg(feature = "volatility")]
+#[derive(Debug, Clone)]
+pub struct AtrTrailingStop {
+    side: Side,
+    multiple: f64,
+    atr: Atr,
+    best: f64,
+    stop: Option<f64>,
+}
+
+#[cfg(feature = "volatility")]
+impl AtrTrailingStop {
+    /// Create a new ATR trailing stop
+    ///
+    /// # Arguments
+    /// * `side` - Direction of the position
+    /// * `entry_price` - Fill price of the entry, where trailing starts
+    /// * `period` - ATR period (must be at least 1)
+    /// * `multiple` - Distance behind the best price in ATRs (must be positive)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new stop or an error
+    pub fn new(
+        side: Side,
+        entry_price: f64,
+        period: usize,
+        multiple: f64,
+    ) -> Result<Self, IndicatorError> {
+        if multiple <= 0.0 || !multiple.is_finite() {
+            return Err(IndicatorError::invalid_parameter(
+                "multiple",
+                multiple,
+                "must be positive and finite",
+            ));
+        }
+        Ok(Self {
+            side,
+            multiple,
+            atr: Atr::new(period)?,
+            best: entry_price,
+            stop: None,
+        })
+    }
+
+    /// Warm the ATR up with the bars before the entry, so the stop is set
+    /// from the first bar after it
+    ///
+    /// # Arguments
+    /// * `candles` - Bars up to and including the entry bar
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The stop or the ATR's error
+    pub fn with_history(mut self, candles: &[Candle]) -> Result<Self, IndicatorError> {
+        let mut atr = None;
+        for candle in candles {
+            atr = self.atr.next(*candle)?.or(atr);
+        }
+        if let Some(atr) = atr {
+            self.stop = Some(behind(self.side, self.best, self.multiple * atr));
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "volatility")]
+impl StopManager for AtrTrailingStop {
+    fn update(&mut self, candle: &Candle) -> Option<StopExit> {
+        if let Some(exit) = exit(self.side, self.stop, candle, StopKind::Trailing) {
+            return Some(exit);
+        }
+        self.best = match self.side {
+            Side::Long => self.best.max(candle.high),
+            Side::Short => self.best.min(candle.low),
+        };
+        // A bar the ATR rejects (NaN prices) leaves the stop where it is.
+        if let Ok(Some(atr)) = self.atr.next(*candle) {
+            let level = behind(self.side, self.best, self.multiple * atr);
+            self.stop = Some(match self.stop {
+                Some(stop) => tighter(self.side, stop, level),
+                None => level,
+            });
+        }
+        None
+    }
+
+    fn stop(&self) -> Option<f64> {
+        self.stop
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Breakeven and time stops
+// ---------------------------------------------------------------------------
+
+/// Initial stop moved to the entry price once the position has gained a
+/// multiple of its initial risk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreakevenStop {
+    side: Side,
+    entry_price: f64,
+    trigger: f64,
+    stop: f64,
+    moved: bool,
+}
+
+impl BreakevenStop {
+    /// Create a new breakeven stop
+    ///
+    /// # Arguments
+    /// * `side` - Direction of the position
+    /// * `entry_price` - Fill price of the entry
+    /// * `initial_stop` - Stop until breakeven (below the entry for a long, above for a short)
+    /// * `after_r` - Gain, in multiples of the initial risk, that moves the stop (must be positive)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new stop or an error
+    pub fn new(
+        side: Side,
+        entry_price: f64,
+        initial_stop: f64,
+        after_r: f64,
+    ) -> Result<Self, IndicatorError> {
+        let risk = match side {
+            Side::Long => entry_price - initial_stop,
+            Side::Short => initial_stop - entry_price,
+        };
+        if risk <= 0.0 || risk.is_nan() {
+            return Err(IndicatorError::invalid_parameter(
+                "initial_stop",
+                initial_stop,
+                format!("must be on the losing side of the entry ({entry_price})"),
+            ));
+        }
+        if after_r <= 0.0 || !after_r.is_finite() {
+            return Err(IndicatorError::invalid_parameter(
+                "after_r",
+                after_r,
+                "must be positive and finite",
+            ));
+        }
+        Ok(Self {
+            side,
+            entry_price,
+            trigger: behind(side, entry_price, -after_r * risk),
+            stop: initial_stop,
+            moved: false,
+        })
+    }
+
+    /// `true` once the stop sits at the entry price.
+    pub fn is_breakeven(&self) -> bool {
+        self.moved
+    }
+}
+
+impl StopManager for BreakevenStop {
+    fn update(&mut self, candle: &Candle) -> Option<StopExit> {
+        let kind = if self.moved {
+            StopKind::Breakeven
+        } else {
+            StopKind::Initial
+        };
+        let exit = exit(self.side, Some(self.stop), candle, kind);
+        let reached = match self.side {
+            Side::Long => candle.high >= self.trigger,
+            Side::Short => candle.low <= self.trigger,
+        };
+        if exit.is_none() && reached && !self.moved {
+            self.moved = true;
+            self.stop = self.entry_price;
+        }
+        exit
+    }
+
+    fn stop(&self) -> Option<f64> {
+        Some(self.stop)
+    }
+}
+
+/// Exit at the close after a fixed number of bars.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeStop {
+    max_bars: usize,
+    bars: usize,
+}
+
+impl TimeStop {
+    /// Create a new time stop
+    ///
+    /// # Arguments
+    /// * `max_bars` - Bars after the entry bar to hold for (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new stop or an error
+    pub fn new(max_bars: usize) -> Result<Self, IndicatorError> {
+        if max_bars == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_bars",
+                max_bars,
+                "must be at least 1",
+            ));
+        }
+        Ok(Self { max_bars, bars: 0 })
+    }
+
+    /// Bars seen since the entry.
+    pub fn bars_held(&self) -> usize {
+        self.bars
+    }
+}
+
+impl StopManager for TimeStop {
+    fn update(&mut self, candle: &Candle) -> Option<StopExit> {
+        self.bars += 1;
+        (self.bars >= self.max_bars).then_some(StopExit {
+            price: candle.close,
+            kind: StopKind::Time,
+        })
+    }
+
+    fn stop(&self) -> Option<f64> {
+        None
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Groups
+// ---------------------------------------------------------------------------
+
+/// Several stop managers on one position.
+///
+/// Every member sees every bar. When several exit on the same bar, the
+/// stop reached first wins: the highest for a long, the lowest for a
+/// short, and price stops before a time stop at the close.
+#[derive(Debug)]
+pub struct StopGroup {
+    side: Side,
+    members: Vec<Box<dyn StopManager>>,
+}
+
+impl StopGroup {
+    /// Empty group for a `side` position.
+    pub fn new(side: Side) -> Self {
+        Self {
+            side,
+            members: Vec::new(),
+        }
+    }
+
+    /// Add `manager` to the group.
+    pub fn with(mut self, manager: impl StopManager + 'static) -> Self {
+        self.members.push(Box::new(manager));
+        self
+    }
+}
+
+impl StopManager for StopGroup {
+    fn update(&mut self, candle: &Candle) -> Option<StopExit> {
+        let side = self.side;
+        self.members
+            .iter_mut()
+            .filter_map(|m| m.update(candle))
+            .reduce(|a, b| match (a.kind, b.kind) {
+                (StopKind::Time, _) => b,
+                (_, StopKind::Time) => a,
+                _ if tighter(side, a.price, b.price) == a.price => a,
+                _ => b,
+            })
+    }
+
+    fn stop(&self) -> Option<f64> {
+        self.members
+            .iter()
+            .filter_map(|m| m.stop())
+            .reduce(|a, b| tighter(self.side, a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_percent_trailing_time_and_group() {
+        let mut trail = PercentTrailingStop::new(Side::Short, 100.0, 0.1).unwrap();
+        assert!((trail.stop().unwrap() - 110.0).abs() < 1e-12);
+        assert!(trail.update(&bar(98.0, 99.0, 90.0, 92.0)).is_none());
+        assert!((trail.stop().unwrap() - 99.0).abs() < 1e-12);
+        // Gaps up through the stop: fills at the open.
+        let exit = trail.update(&bar(101.0, 102.0, 100.0, 101.0)).unwrap();
+        assert_eq!((exit.price, exit.kind), (101.0, StopKind::Trailing));
+
+        let mut time = TimeStop::new(2).unwrap();
+        assert!(time.update(&bar(1.0, 1.0, 1.0, 1.0)).is_none());
+        assert_eq!(time.update(&bar(1.0, 2.0, 1.0, 1.5)).unwrap().price, 1.5);
+
+        let mut group = StopGroup::new(Side::Long)
+            .with(PercentTrailingStop::new(Side::Long, 100.0, 0.05).unwrap())
+            .with(BreakevenStop::new(Side::Long, 100.0, 90.0, 0.5).unwrap())
+            .with(TimeStop::new(2).unwrap());
+        assert_eq!(group.stop(), Some(95.0));
+        assert!(group.update(&bar(100.0, 106.0, 99.0, 105.0)).is_none());
+        let trailed = group.stop().unwrap();
+        assert!((trailed - 100.7).abs() < 1e-9);
+        // Both price stops and the time stop trigger: the trailing stop
+        // is reached first.
+        let exit = group.update(&bar(102.0, 102.0, 96.0, 97.0)).unwrap();
+        assert_eq!((exit.price, exit.kind), (trailed, StopKind::Trailing));
+
+        assert!(PercentTrailingStop::new(Side::Long, 100.0, 1.0).is_err());
+        assert!(BreakevenStop::new(Side::Short, 100.0, 95.0, 1.0).is_err());
+        assert!(TimeStop::new(0).is_err());
+    }
+
+    #[cfg(feature = "volatility")]
+    #[test]
+    fn test_atr_trailing_stop() {
+        let history: Vec<Candle> = (0..3).map(|_| bar(100.0, 101.0, 99.0, 100.0)).collect();
+        let mut stop = AtrTrailingStop::new(Side::Long, 100.0, 3, 2.0)
+            .unwrap()
+            .with_history(&history)
+            .unwrap();
+        // ATR 2: stop 4 below the entry.
+        assert_eq!(stop.stop(), Some(96.0));
+        assert!(stop.update(&bar(100.0, 110.0, 108.0, 109.0)).is_none());
+        let trailed = stop.stop().unwrap();
+        assert!(trailed > 96.0 && trailed < 110.0);
+        let exit = stop.update(&bar(105.0, 105.0, 95.0, 96.0)).unwrap();
+        assert_eq!(exit.price, trailed.min(105.0));
+        assert!(AtrTrailingStop::new(Side::Long, 100.0, 3, 0.0).is_err());
+    }
+}
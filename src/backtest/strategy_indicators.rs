@@ -0,0 +1,210 @@
+//! # Indicators declared by a strategy
+//!
+//! Instead of owning and feeding its indicators, a [`Strategy`] can list
+//! them in [`Strategy::indicators`] as `(key, spec)` pairs, where the
+//! [`IndicatorSpec`] names the indicator and its parameters as in the
+//! [factory](crate::indicators::factory). The [`Backtester`] builds them
+//! before the first bar, feeds every candle to them before the strategy
+//! sees it, and holds the strategy until all of them have warmed up.
+//! Their values are in [`Context::indicators`] under the declared keys.
+//!
+//! ```
+//! use rsta::backtest::{Action, BacktestConfig, Backtester, Context, Quantity, Strategy};
+//! use rsta::indicators::factory::IndicatorSpec;
+//! use rsta::indicators::Candle;
+//!
+//! // Long while the 5-bar SMA is above the 20-bar SMA.
+//! struct Crossover;
+//! impl Strategy for Crossover {
+//!     fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+//!         vec![
+//!             ("fast", IndicatorSpec::new("sma").param("period", 5.0)),
+//!             ("slow", IndicatorSpec::new("sma").param("period", 20.0)),
+//!         ]
+//!     }
+//!
+//!     fn on_candle(&mut self, _candle: &Candle, ctx: &Context) -> Action {
+//!         let (Some(fast), Some(slow)) = (ctx.indicators.value("fast"), ctx.indicators.value("slow"))
+//!         else {
+//!             return Action::Hold;
+//!         };
+//!         match (ctx.portfolio.position.is_some(), fast > slow) {
+//!             (false, true) => Action::EnterLong(Quantity::AllCash),
+//!             (true, false) => Action::Exit,
+//!             _ => Action::Hold,
+//!         }
+//!     }
+//! }
+//!
+//! let candles: Vec<Candle> = (0..60)
+//!     .map(|i| {
+//!         let close = 100.0 + (i as f64 * 0.2).sin() * 10.0;
+//!         Candle { timestamp: i, open: close, high: close, low: close, close, volume: 1.0 }
+//!     })
+//!     .collect();
+//! let result = Backtester::new(BacktestConfig::default()).run(&candles, &mut Crossover).unwrap();
+//! assert!(result.metrics.trade_count > 0);
+//! ```
+//!
+//! [`Strategy`]: super::Strategy
+//! [`Strategy::indicators`]: super::Strategy::indicators
+//! [`Backtester`]: super::Backtester
+//! [`Context::indicators`]: super::Context::indicators
+
+use crate::indicators::any_indicator::{AnyIndicator, IndicatorValue};
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::{Candle, IndicatorError};
+
+/// One declared indicator with its last two values.
+struct Entry {
+    key: &'static str,
+    indicator: Box<dyn AnyIndicator>,
+    current: Option<IndicatorValue>,
+    previous: Option<IndicatorValue>,
+}
+
+/// Indicators a strategy declared, updated by the engine on every bar.
+#[derive(Default)]
+pub struct StrategyIndicators {
+    entries: Vec<Entry>,
+}
+
+impl std::fmt::Debug for StrategyIndicators {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|e| (e.key, e.current)))
+            .finish()
+    }
+}
+
+impl StrategyIndicators {
+    /// Build the indicators of `specs`
+    ///
+    /// # Arguments
+    /// * `specs` - `(key, spec)` pairs (keys must be unique)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The indicators or the first build error
+    pub fn build(specs: Vec<(&'static str, IndicatorSpec)>) -> Result<Self, IndicatorError> {
+        let mut entries: Vec<Entry> = Vec::with_capacity(specs.len());
+        for (key, spec) in specs {
+            if entries.iter().any(|e| e.key == key) {
+                return Err(IndicatorError::invalid_parameter(
+                    "key",
+                    key,
+                    "must be unique among the declared indicators",
+                ));
+            }
+            entries.push(Entry {
+                key,
+                indicator: spec.build()?,
+                current: None,
+                previous: None,
+            });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Feed `candle` to every indicator.
+    pub fn update(&mut self, candle: &Candle) -> Result<(), IndicatorError> {
+        for entry in &mut self.entries {
+            if let Some(value) = entry.indicator.next(*candle)? {
+                entry.previous = entry.current.replace(value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Latest value of the indicator declared as `key`, `None` while it
+    /// warms up or if no such key was declared.
+    pub fn get(&self, key: &str) -> Option<IndicatorValue> {
+        self.entry(key)?.current
+    }
+
+    /// Value before the latest one, e.g. to detect crossings.
+    pub fn previous(&self, key: &str) -> Option<IndicatorValue> {
+        self.entry(key)?.previous
+    }
+
+    /// Headline value of `key` (see [`IndicatorValue::primary`]).
+    pub fn value(&self, key: &str) -> Option<f64> {
+        self.get(key).map(|v| v.primary())
+    }
+
+    /// `true` once every indicator has produced a value.
+    pub fn is_ready(&self) -> bool {
+        self.entries.iter().all(|e| e.current.is_some())
+    }
+
+    /// Bars needed before every indicator has produced a value.
+    pub fn warmup(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|e| e.indicator.min_periods())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Declared keys, in declaration order.
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|e| e.key)
+    }
+
+    /// Number of declared indicators.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` if the strategy declared no indicator.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entry(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+}
+
+#[cfg(all(test, feature = "trend"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_update_and_lookup() {
+        let specs = vec![
+            ("fast", IndicatorSpec::new("sma").param("period", 2.0)),
+            ("slow", IndicatorSpec::new("wma").param("period", 3.0)),
+        ];
+        let mut indicators = StrategyIndicators::build(specs).unwrap();
+        assert_eq!(indicators.warmup(), 3);
+        assert_eq!(indicators.keys().collect::<Vec<_>>(), vec!["fast", "slow"]);
+
+        for close in [1.0, 2.0, 3.0] {
+            assert!(!indicators.is_ready());
+            let candle = Candle {
+                timestamp: 0,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            };
+            indicators.update(&candle).unwrap();
+        }
+        assert!(indicators.is_ready());
+        assert_eq!(indicators.value("fast"), Some(2.5));
+        assert_eq!(
+            indicators.previous("fast"),
+            Some(IndicatorValue::Single(1.5))
+        );
+        assert_eq!(indicators.previous("slow"), None);
+        assert_eq!(indicators.get("missing"), None);
+
+        let duplicate = vec![
+            ("a", IndicatorSpec::new("sma").param("period", 2.0)),
+            ("a", IndicatorSpec::new("sma").param("period", 3.0)),
+        ];
+        assert!(StrategyIndicators::build(duplicate).is_err());
+        assert!(StrategyIndicators::build(vec![("x", IndicatorSpec::new("nope"))]).is_err());
+    }
+}
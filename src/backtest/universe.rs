@@ -0,0 +1,520 @@
+//! # Multi-asset backtests
+//!
+//! [`UniverseBacktester`] runs one [`AllocationStrategy`] over a universe
+//! of symbols sharing one account. On every bar the strategy sees the
+//! candles of all symbols; on rebalance bars (see [`Rebalance`]) it returns
+//! target weights — the fraction of equity to hold in each symbol,
+//! negative for shorts — and the engine trades every symbol to its target
+//! at the close, through the configured fill and cost models.
+//!
+//! Targets pass through the position limits of [`UniverseConfig`] first:
+//! a cap per symbol, a maximum number of positions (the largest weights
+//! win) and a cap on gross exposure (weights are scaled down to fit).
+//!
+//! Accounting uses a [`portfolio::Portfolio`](crate::portfolio::Portfolio),
+//! so positions carry an average-cost basis and every reduction closes a
+//! [`Trade`](crate::portfolio::Trade). The result holds aggregate
+//! [`Metrics`] for the whole account and [`SymbolStats`] per symbol.
+//!
+//! Symbols are aligned by timestamp. A symbol without a candle at a
+//! timestamp keeps its last price and is not traded on that bar.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use rsta::backtest::universe::{
+//!     AllocationStrategy, Rebalance, UniverseBacktester, UniverseConfig, UniverseContext,
+//!     Weights,
+//! };
+//! use rsta::indicators::Candle;
+//!
+//! // Equal weight across the universe, rebalanced every 5 bars.
+//! struct EqualWeight;
+//! impl AllocationStrategy for EqualWeight {
+//!     fn target_weights(&mut self, ctx: &UniverseContext) -> Weights {
+//!         let n = ctx.symbols.len() as f64;
+//!         ctx.symbols.iter().map(|s| (s.clone(), 1.0 / n)).collect()
+//!     }
+//! }
+//!
+//! let series = |drift: f64| -> Vec<Candle> {
+//!     (0..20)
+//!         .map(|i| {
+//!             let close = 100.0 + drift * i as f64;
+//!             Candle { timestamp: i, open: close, high: close, low: close, close, volume: 1.0 }
+//!         })
+//!         .collect()
+//! };
+//! let mut universe = BTreeMap::new();
+//! universe.insert("AAA".to_string(), series(1.0));
+//! universe.insert("BBB".to_string(), series(-1.0));
+//!
+//! let config = UniverseConfig::default().with_rebalance(Rebalance::Every(5));
+//! let result = UniverseBacktester::new(config).run(&universe, &mut EqualWeight);
+//! assert_eq!(result.rebalances, 4);
+//! assert!(result.symbols["AAA"].realized_pnl > 0.0);
+//! assert!(result.symbols["BBB"].unrealized_pnl < 0.0);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::backtest::execution::OrderSide;
+use crate::backtest::{metrics_from, BacktestConfig, Execution, Metrics};
+use crate::indicators::{Candle, IndicatorError};
+use crate::portfolio::Portfolio;
+
+/// Target fraction of equity per symbol, negative for shorts. Symbols
+/// left out are closed.
+pub type Weights = BTreeMap<String, f64>;
+
+/// When the engine asks for new targets and trades to them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rebalance {
+    /// On every bar.
+    EveryBar,
+    /// On the first bar and every `n` bars after it (`n` of 0 counts as 1).
+    Every(usize),
+    /// On the first bar, then whenever a symbol's weight has drifted more
+    /// than this much (absolute, e.g. `0.05` for 5 points) from its last
+    /// target.
+    Drift(f64),
+}
+
+/// Settings of a [`UniverseBacktester`].
+#[derive(Debug, Clone)]
+pub struct UniverseConfig {
+    /// Cash, fill / cost models and annualization, as for the single-asset
+    /// engine. The intrabar policy is unused: all trades happen at the
+    /// close.
+    pub backtest: BacktestConfig,
+    /// Rebalancing schedule. Default: [`Rebalance::EveryBar`].
+    pub rebalance: Rebalance,
+    /// Largest absolute weight of one symbol. Default: 1.
+    pub max_weight: f64,
+    /// Most symbols held at once. Default: no limit.
+    pub max_positions: Option<usize>,
+    /// Largest sum of absolute weights. Default: 1 (no leverage).
+    pub max_gross_exposure: f64,
+}
+
+impl Default for UniverseConfig {
+    fn default() -> Self {
+        Self {
+            backtest: BacktestConfig::default(),
+            rebalance: Rebalance::EveryBar,
+            max_weight: 1.0,
+            max_positions: None,
+            max_gross_exposure: 1.0,
+        }
+    }
+}
+
+impl UniverseConfig {
+    /// Use `backtest` for cash, fills and costs.
+    pub fn new(backtest: BacktestConfig) -> Self {
+        Self {
+            backtest,
+            ..Self::default()
+        }
+    }
+
+    /// Rebalance on `schedule`.
+    pub fn with_rebalance(mut self, schedule: Rebalance) -> Self {
+        self.rebalance = schedule;
+        self
+    }
+
+    /// Cap the absolute weight of any one symbol
+    ///
+    /// # Arguments
+    /// * `max_weight` - Largest absolute weight (must be positive and finite)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The config or an error
+    pub fn with_max_weight(mut self, max_weight: f64) -> Result<Self, IndicatorError> {
+        self.max_weight = validate_positive("max_weight", max_weight)?;
+        Ok(self)
+    }
+
+    /// Hold at most `max_positions` symbols, keeping the largest weights.
+    pub fn with_max_positions(mut self, max_positions: usize) -> Self {
+        self.max_positions = Some(max_positions);
+        self
+    }
+
+    /// Cap the sum of absolute weights
+    ///
+    /// # Arguments
+    /// * `max_gross` - Largest gross exposure as a multiple of equity (must be positive and finite)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The config or an error
+    pub fn with_max_gross_exposure(mut self, max_gross: f64) -> Result<Self, IndicatorError> {
+        self.max_gross_exposure = validate_positive("max_gross_exposure", max_gross)?;
+        Ok(self)
+    }
+
+    /// Apply the position limits to raw target weights.
+    fn limit(&self, targets: Weights) -> Weights {
+        let mut weights: Vec<(String, f64)> = targets
+            .into_iter()
+            .filter(|(_, w)| w.is_finite() && *w != 0.0)
+            .map(|(symbol, w)| (symbol, w.clamp(-self.max_weight, self.max_weight)))
+            .collect();
+        if let Some(max) = self.max_positions {
+            // Stable sort: equal weights keep symbol order.
+            weights.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+            weights.truncate(max);
+        }
+        let gross: f64 = weights.iter().map(|(_, w)| w.abs()).sum();
+        let scale = if gross > self.max_gross_exposure {
+            self.max_gross_exposure / gross
+        } else {
+            1.0
+        };
+        weights
+            .into_iter()
+            .map(|(symbol, w)| (symbol, w * scale))
+            .collect()
+    }
+}
+
+/// Read-only view of the account passed to the strategy.
+pub struct UniverseContext<'a> {
+    /// Shared account, marked at the latest closes.
+    pub portfolio: &'a Portfolio,
+    /// Every symbol of the universe, in order.
+    pub symbols: &'a [String],
+    /// Candles of the symbols that traded on this bar.
+    pub bars: &'a BTreeMap<String, Candle>,
+    /// Timestamp of the bar.
+    pub timestamp: u64,
+    /// Zero-based index of the bar on the merged timeline.
+    pub bar_index: usize,
+}
+
+/// Allocation strategy over a universe of symbols.
+pub trait AllocationStrategy {
+    /// Called on every bar before any rebalance, e.g. to update indicators.
+    fn on_bar(&mut self, _ctx: &UniverseContext) {}
+
+    /// Target weights, called on rebalance bars. Weights of symbols
+    /// without a candle on this bar are ignored until they trade again.
+    fn target_weights(&mut self, ctx: &UniverseContext) -> Weights;
+
+    /// Called once before the first bar.
+    fn on_start(&mut self) {}
+
+    /// Called once after the last bar.
+    fn on_finish(&mut self) {}
+}
+
+/// Per-symbol statistics of a [`UniverseBacktester`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SymbolStats {
+    /// Trades closed, counting every reduction of a position.
+    pub trade_count: usize,
+    /// Fraction of closed trades with positive PnL, in `[0, 1]`.
+    pub win_rate: f64,
+    /// Net PnL of the closed trades.
+    pub realized_pnl: f64,
+    /// PnL of the position still open at the end, before fees.
+    pub unrealized_pnl: f64,
+    /// Fees paid on every fill.
+    pub fees_paid: f64,
+    /// Notional traded, buys and sells.
+    pub turnover: f64,
+}
+
+/// Output of a [`UniverseBacktester::run`] call.
+#[derive(Debug, Clone)]
+pub struct UniverseResult {
+    /// Final state of the shared account, with every closed trade.
+    pub portfolio: Portfolio,
+    /// `(timestamp, equity)` sampled at each bar's close.
+    pub equity_curve: Vec<(u64, f64)>,
+    /// Aggregate metrics of the account.
+    pub metrics: Metrics,
+    /// Statistics per symbol.
+    pub symbols: BTreeMap<String, SymbolStats>,
+    /// Number of bars on which the book was rebalanced.
+    pub rebalances: usize,
+}
+
+/// Multi-asset simulation engine.
+#[derive(Debug, Clone, Default)]
+pub struct UniverseBacktester {
+    /// Configuration knobs.
+    pub config: UniverseConfig,
+}
+
+impl UniverseBacktester {
+    /// Create a new backtester with the given config.
+    pub fn new(config: UniverseConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run `strategy` over `universe`, one candle series per symbol sorted
+    /// by timestamp.
+    pub fn run<S: AllocationStrategy>(
+        &self,
+        universe: &BTreeMap<String, Vec<Candle>>,
+        strategy: &mut S,
+    ) -> UniverseResult {
+        let cfg = &self.config;
+        let execution = cfg.backtest.execution();
+        let mut book = Portfolio::new(cfg.backtest.initial_cash);
+        let symbols: Vec<String> = universe.keys().cloned().collect();
+        let timestamps: BTreeSet<u64> = universe
+            .values()
+            .flat_map(|candles| candles.iter().map(|c| c.timestamp))
+            .collect();
+        let mut cursors = vec![0usize; symbols.len()];
+        let mut stats: BTreeMap<String, SymbolStats> = symbols
+            .iter()
+            .map(|s| (s.clone(), SymbolStats::default()))
+            .collect();
+        let mut targets: Option<Weights> = None;
+        let mut equity_curve = Vec::with_capacity(timestamps.len());
+        let mut rebalances = 0;
+        strategy.on_start();
+
+        for (bar_index, &timestamp) in timestamps.iter().enumerate() {
+            let mut bars = BTreeMap::new();
+            for (symbol, cursor) in symbols.iter().zip(cursors.iter_mut()) {
+                let candles = &universe[symbol];
+                if let Some(candle) = candles.get(*cursor).filter(|c| c.timestamp == timestamp) {
+                    book.mark(symbol, candle.close);
+                    bars.insert(symbol.clone(), *candle);
+                    *cursor += 1;
+                }
+            }
+
+            let ctx = UniverseContext {
+                portfolio: &book,
+                symbols: &symbols,
+                bars: &bars,
+                timestamp,
+                bar_index,
+            };
+            strategy.on_bar(&ctx);
+            let due = match (cfg.rebalance, &targets) {
+                (_, None) | (Rebalance::EveryBar, _) => true,
+                (Rebalance::Every(n), _) => bar_index % n.max(1) == 0,
+                (Rebalance::Drift(threshold), Some(targets)) => {
+                    max_drift(&book, targets) > threshold
+                }
+            };
+            if due {
+                let weights = cfg.limit(strategy.target_weights(&ctx));
+                rebalance(&mut book, &weights, &bars, &execution, &mut stats);
+                targets = Some(weights);
+                rebalances += 1;
+            }
+            equity_curve.push((timestamp, book.equity()));
+        }
+
+        strategy.on_finish();
+        for (symbol, trade) in book.trades() {
+            let s = stats.entry(symbol.clone()).or_default();
+            s.trade_count += 1;
+            s.realized_pnl += trade.pnl;
+            if trade.is_win() {
+                s.win_rate += 1.0;
+            }
+        }
+        for (symbol, s) in stats.iter_mut() {
+            if s.trade_count > 0 {
+                s.win_rate /= s.trade_count as f64;
+            }
+            if let Some(position) = book.position(symbol) {
+                let price = book.last_price(symbol).unwrap_or(position.entry_price);
+                s.unrealized_pnl = position.unrealized_pnl(price);
+            }
+        }
+        let pnls: Vec<f64> = book.trades().iter().map(|(_, t)| t.pnl).collect();
+        let metrics = metrics_from(&equity_curve, &pnls, &cfg.backtest);
+        UniverseResult {
+            portfolio: book,
+            equity_curve,
+            metrics,
+            symbols: stats,
+            rebalances,
+        }
+    }
+}
+
+/// Largest absolute gap between a symbol's current weight and its target.
+fn max_drift(book: &Portfolio, targets: &Weights) -> f64 {
+    let equity = book.equity();
+    if equity <= 0.0 {
+        return 0.0;
+    }
+    let held = book.positions().map(|(symbol, _)| symbol);
+    let symbols: BTreeSet<&str> = targets.keys().map(String::as_str).chain(held).collect();
+    symbols
+        .into_iter()
+        .map(|symbol| {
+            let target = targets.get(symbol).copied().unwrap_or(0.0);
+            (book.exposure(symbol) / equity - target).abs()
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Trade every symbol with a candle on this bar to its target weight,
+/// selling before buying so sales fund purchases.
+fn rebalance(
+    book: &mut Portfolio,
+    targets: &Weights,
+    bars: &BTreeMap<String, Candle>,
+    exec: &Execution,
+    stats: &mut BTreeMap<String, SymbolStats>,
+) {
+    let equity = book.equity();
+    let mut orders: Vec<(&String, f64, &Candle)> = Vec::new();
+    for (symbol, candle) in bars {
+        let target = targets.get(symbol).copied().unwrap_or(0.0);
+        let current = book.position(symbol).map_or(0.0, |p| p.signed_quantity());
+        let goal = if target == 0.0 || equity <= 0.0 || candle.close <= 0.0 {
+            0.0
+        } else {
+            target * equity / candle.close
+        };
+        let delta = goal - current;
+        // Skip dust left by floating-point sizing.
+        if (delta * candle.close).abs() > equity.abs() * 1e-9 {
+            orders.push((symbol, delta, candle));
+        }
+    }
+    orders.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    for (symbol, delta, candle) in orders {
+        let side = if delta > 0.0 {
+            OrderSide::Buy
+        } else {
+            OrderSide::Sell
+        };
+        let quantity = delta.abs();
+        let price = exec.fill.market_fill(side, candle);
+        let fee = exec.cost.commission(quantity, price);
+        let filled = match side {
+            OrderSide::Buy => book.buy(symbol, quantity, price, fee, candle.timestamp),
+            OrderSide::Sell => book.sell(symbol, quantity, price, fee, candle.timestamp),
+        };
+        if filled.is_ok() {
+            // Mark back at the close: the fill price includes slippage.
+            book.mark(symbol, candle.close);
+            let s = stats.entry(symbol.clone()).or_default();
+            s.fees_paid += fee;
+            s.turnover += quantity * price;
+        }
+    }
+}
+
+fn validate_positive(name: &str, value: f64) -> Result<f64, IndicatorError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(IndicatorError::invalid_parameter(
+            name,
+            value,
+            "must be positive and finite",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(closes: &[f64], start: u64) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                timestamp: start + i as u64,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    /// Fixed targets on every call.
+    struct Fixed(Vec<(&'static str, f64)>);
+    impl AllocationStrategy for Fixed {
+        fn target_weights(&mut self, _ctx: &UniverseContext) -> Weights {
+            self.0.iter().map(|&(s, w)| (s.to_string(), w)).collect()
+        }
+    }
+
+    #[test]
+    fn test_limits_on_targets() {
+        let cfg = UniverseConfig::default()
+            .with_max_weight(0.5)
+            .unwrap()
+            .with_max_positions(2);
+        let raw: Weights = [("A", 0.8), ("B", -0.3), ("C", 0.4), ("D", f64::NAN)]
+            .iter()
+            .map(|&(s, w)| (s.to_string(), w))
+            .collect();
+        let limited = cfg.limit(raw);
+        // A capped at 0.5, C kept over B, gross 0.9 fits
+        assert_eq!(limited.len(), 2);
+        assert_eq!(limited["A"], 0.5);
+        assert_eq!(limited["C"], 0.4);
+
+        let cfg = UniverseConfig::default();
+        let limited = cfg.limit([("A".to_string(), 1.0), ("B".to_string(), -1.0)].into());
+        assert_eq!((limited["A"], limited["B"]), (0.5, -0.5));
+        assert!(UniverseConfig::default().with_max_weight(0.0).is_err());
+        assert!(UniverseConfig::default()
+            .with_max_gross_exposure(f64::INFINITY)
+            .is_err());
+    }
+
+    #[test]
+    fn test_rebalance_shared_capital() {
+        let mut universe = BTreeMap::new();
+        universe.insert("A".to_string(), series(&[10.0, 20.0, 20.0], 0));
+        // B starts one bar late
+        universe.insert("B".to_string(), series(&[50.0, 50.0], 1));
+        let mut strategy = Fixed(vec![("A", 0.5), ("B", 0.5)]);
+        let result = UniverseBacktester::default().run(&universe, &mut strategy);
+
+        // Bar 0: half in A (500 units). Bar 1: A doubled, equity 15_000,
+        // sell down to 375 units and buy 150 B.
+        let a = result.portfolio.position("A").unwrap();
+        let b = result.portfolio.position("B").unwrap();
+        assert!((a.quantity - 375.0).abs() < 1e-9);
+        assert!((b.quantity - 150.0).abs() < 1e-9);
+        assert_eq!(result.rebalances, 3);
+        assert_eq!(result.symbols["A"].trade_count, 1);
+        assert!((result.symbols["A"].realized_pnl - 1_250.0).abs() < 1e-9);
+        assert!((result.metrics.final_equity - 15_000.0).abs() < 1e-9);
+        assert_eq!(result.equity_curve.len(), 3);
+    }
+
+    #[test]
+    fn test_drift_schedule_and_costs() {
+        let mut universe = BTreeMap::new();
+        universe.insert("A".to_string(), series(&[10.0, 10.5, 12.0, 12.0], 0));
+        universe.insert("B".to_string(), series(&[10.0; 4], 0));
+        let config = UniverseConfig::new(BacktestConfig {
+            fee_rate: 0.001,
+            ..Default::default()
+        })
+        .with_rebalance(Rebalance::Drift(0.05));
+        let mut strategy = Fixed(vec![("A", 0.5), ("B", 0.5)]);
+        let result = UniverseBacktester::new(config).run(&universe, &mut strategy);
+
+        // A peaks at 54.6% of the book, inside the 5-point band: only the
+        // first bar trades.
+        assert_eq!(result.rebalances, 1);
+        assert!((result.symbols["A"].fees_paid - 5.0).abs() < 1e-9);
+        assert!((result.symbols["B"].turnover - 5_000.0).abs() < 1e-9);
+        assert!(result.symbols["A"].unrealized_pnl > 0.0);
+    }
+}
@@ -0,0 +1,800 @@
+//! Exchange trading calendars
+//!
+//! A [`TradingCalendar`] knows when a market is open: a time zone, the
+//! trading session of each weekday and the holidays. It answers whether a
+//! bar falls inside a session and which session that is, so intraday data
+//! can be filtered to regular hours, split per session, resampled into one
+//! bar per session, or fed to indicators that restart every session
+//! ([`PerSession`]).
+//!
+//! ```
+//! use rsta::calendar::{Date, TradingCalendar};
+//!
+//! let nyse = TradingCalendar::us_equities();
+//! // 2024-03-28 14:00 UTC is 10:00 in New York (daylight saving time).
+//! assert!(nyse.is_open(1_711_634_400));
+//! // 2024-03-29 is Good Friday.
+//! assert!(nyse.is_holiday(Date::new(2024, 3, 29).unwrap()));
+//! assert!(!nyse.is_open(1_711_634_400 + 86_400));
+//!
+//! // Crypto trades around the clock.
+//! assert!(TradingCalendar::crypto().is_open(1_711_634_400 + 86_400));
+//! ```
+//!
+//! Timestamps are Unix seconds, as in [`Candle::timestamp`], and a bar
+//! belongs to the session its timestamp (its open time) falls in. A
+//! session lies within one local day. Calendars do not model early closes
+//! or one-off closures (national mourning days, storms); add those dates
+//! with [`TradingCalendar::with_holiday`].
+
+use std::collections::BTreeSet;
+
+use crate::indicators::{Candle, Indicator, IndicatorError, IndicatorSeries};
+
+const DAY: i64 = 86_400;
+
+/// A calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// The date `year-month-day`, or `None` if it does not exist.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+        valid.then_some(Self { year, month, day })
+    }
+
+    /// The date `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Self {
+        // Howard Hinnant's `civil_from_days`.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01 (negative before).
+    pub fn days(self) -> i64 {
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Year.
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// Month, `1..=12`.
+    pub fn month(self) -> u8 {
+        self.month
+    }
+
+    /// Day of the month, `1..=31`.
+    pub fn day(self) -> u8 {
+        self.day
+    }
+
+    /// Day of the week.
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        Weekday::ALL[(self.days() + 3).rem_euclid(7) as usize]
+    }
+
+    fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.days() + days)
+    }
+}
+
+fn is_leap(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Weekday {
+    /// Monday.
+    Monday,
+    /// Tuesday.
+    Tuesday,
+    /// Wednesday.
+    Wednesday,
+    /// Thursday.
+    Thursday,
+    /// Friday.
+    Friday,
+    /// Saturday.
+    Saturday,
+    /// Sunday.
+    Sunday,
+}
+
+impl Weekday {
+    /// Every weekday, Monday first.
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Monday to Friday.
+    pub const WORKDAYS: [Weekday; 5] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+    ];
+}
+
+/// Time zone used to turn timestamps into local dates and times.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeZone {
+    /// Coordinated Universal Time.
+    #[default]
+    Utc,
+    /// A constant offset from UTC, in seconds (east positive).
+    Fixed(i32),
+    /// US Eastern time (New York), with daylight saving time from the
+    /// second Sunday of March to the first Sunday of November (first
+    /// Sunday of April to last Sunday of October before 2007).
+    UsEastern,
+}
+
+impl TimeZone {
+    /// Offset from UTC in seconds at the instant `timestamp`.
+    pub fn offset_at(self, timestamp: u64) -> i64 {
+        match self {
+            TimeZone::Utc => 0,
+            TimeZone::Fixed(offset) => i64::from(offset),
+            TimeZone::UsEastern => {
+                const STANDARD: i64 = -5 * 3_600;
+                let ts = timestamp as i64;
+                let year = Date::from_days((ts + STANDARD).div_euclid(DAY)).year();
+                let (start, end) = if year >= 2007 {
+                    (
+                        nth_weekday(year, 3, Weekday::Sunday, 2),
+                        nth_weekday(year, 11, Weekday::Sunday, 1),
+                    )
+                } else {
+                    (
+                        nth_weekday(year, 4, Weekday::Sunday, 1),
+                        last_weekday(year, 10, Weekday::Sunday),
+                    )
+                };
+                // Clocks change at 02:00 local: 07:00 UTC in spring, 06:00 in autumn.
+                let dst_start = start.days() * DAY + 7 * 3_600;
+                let dst_end = end.days() * DAY + 6 * 3_600;
+                if (dst_start..dst_end).contains(&ts) {
+                    STANDARD + 3_600
+                } else {
+                    STANDARD
+                }
+            }
+        }
+    }
+
+    /// Seconds since the Unix epoch on the local wall clock.
+    pub fn local(self, timestamp: u64) -> i64 {
+        timestamp as i64 + self.offset_at(timestamp)
+    }
+
+    /// Local date at `timestamp`.
+    pub fn date(self, timestamp: u64) -> Date {
+        Date::from_days(self.local(timestamp).div_euclid(DAY))
+    }
+}
+
+/// The `n`-th (1-based) `weekday` of a month.
+fn nth_weekday(year: i32, month: u8, weekday: Weekday, n: u8) -> Date {
+    let first = Date {
+        year,
+        month,
+        day: 1,
+    };
+    let shift = (weekday as i64 - first.weekday() as i64).rem_euclid(7);
+    first.add_days(shift + 7 * (i64::from(n) - 1))
+}
+
+/// The last `weekday` of a month.
+fn last_weekday(year: i32, month: u8, weekday: Weekday) -> Date {
+    let last = Date {
+        year,
+        month,
+        day: days_in_month(year, month),
+    };
+    let shift = (last.weekday() as i64 - weekday as i64).rem_euclid(7);
+    last.add_days(-shift)
+}
+
+/// Easter Sunday (Gregorian), by the anonymous algorithm.
+fn easter(year: i32) -> Date {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    Date {
+        year,
+        month: month as u8,
+        day: day as u8,
+    }
+}
+
+/// Move a fixed-date holiday that falls on a weekend to the nearest
+/// workday: Saturday to Friday, Sunday to Monday.
+fn observed(date: Date) -> Date {
+    match date.weekday() {
+        Weekday::Saturday => date.add_days(-1),
+        Weekday::Sunday => date.add_days(1),
+        _ => date,
+    }
+}
+
+/// Full-day NYSE / Nasdaq holidays of `year`, under the rules in force
+/// since 2022 (MLK Day from 1998, Juneteenth from 2022).
+pub fn us_equity_holidays(year: i32) -> Vec<Date> {
+    let date = |month, day| Date { year, month, day };
+    let mut holidays = Vec::with_capacity(10);
+    // New Year's Day on a Saturday is not moved to the previous year.
+    let new_year = date(1, 1);
+    if new_year.weekday() != Weekday::Saturday {
+        holidays.push(observed(new_year));
+    }
+    if year >= 1998 {
+        holidays.push(nth_weekday(year, 1, Weekday::Monday, 3));
+    }
+    holidays.push(nth_weekday(year, 2, Weekday::Monday, 3));
+    holidays.push(easter(year).add_days(-2));
+    holidays.push(last_weekday(year, 5, Weekday::Monday));
+    if year >= 2022 {
+        holidays.push(observed(date(6, 19)));
+    }
+    holidays.push(observed(date(7, 4)));
+    holidays.push(nth_weekday(year, 9, Weekday::Monday, 1));
+    holidays.push(nth_weekday(year, 11, Weekday::Thursday, 4));
+    holidays.push(observed(date(12, 25)));
+    holidays
+}
+
+/// Trading hours within a local day, as seconds since local midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Session {
+    open: u32,
+    close: u32,
+}
+
+impl Session {
+    /// The whole day.
+    pub const FULL_DAY: Session = Session {
+        open: 0,
+        close: DAY as u32,
+    };
+
+    /// A session from `open` (inclusive) to `close` (exclusive), in
+    /// seconds since local midnight
+    ///
+    /// # Arguments
+    /// * `open` - Opening time
+    /// * `close` - Closing time, after `open` and at most 86 400
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The session or an error
+    pub fn new(open: u32, close: u32) -> Result<Self, IndicatorError> {
+        if close > DAY as u32 {
+            return Err(IndicatorError::invalid_parameter(
+                "close",
+                close,
+                "must be at most 86400 (sessions lie within one day)",
+            ));
+        }
+        if open >= close {
+            return Err(IndicatorError::invalid_parameter(
+                "open",
+                open,
+                format!("must be before the close {close}"),
+            ));
+        }
+        Ok(Self { open, close })
+    }
+
+    /// A session from `open_hour:open_minute` to `close_hour:close_minute`.
+    pub fn from_hm(
+        open_hour: u32,
+        open_minute: u32,
+        close_hour: u32,
+        close_minute: u32,
+    ) -> Result<Self, IndicatorError> {
+        Self::new(
+            open_hour * 3_600 + open_minute * 60,
+            close_hour * 3_600 + close_minute * 60,
+        )
+    }
+
+    /// Opening time, in seconds since local midnight.
+    pub fn open(&self) -> u32 {
+        self.open
+    }
+
+    /// Closing time, in seconds since local midnight.
+    pub fn close(&self) -> u32 {
+        self.close
+    }
+
+    fn contains(&self, seconds: u32) -> bool {
+        (self.open..self.close).contains(&seconds)
+    }
+}
+
+/// Market sessions and holidays. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct TradingCalendar {
+    timezone: TimeZone,
+    sessions: [Option<Session>; 7],
+    holidays: BTreeSet<Date>,
+    holiday_rule: Option<fn(i32) -> Vec<Date>>,
+}
+
+impl TradingCalendar {
+    /// A calendar in `timezone` with no sessions; add them with
+    /// [`with_session`](Self::with_session).
+    pub fn new(timezone: TimeZone) -> Self {
+        Self {
+            timezone,
+            sessions: [None; 7],
+            holidays: BTreeSet::new(),
+            holiday_rule: None,
+        }
+    }
+
+    /// Round-the-clock trading every day in UTC, as on crypto exchanges.
+    pub fn crypto() -> Self {
+        Self::new(TimeZone::Utc).with_session(&Weekday::ALL, Session::FULL_DAY)
+    }
+
+    /// US equities regular trading hours: 09:30–16:00 New York time,
+    /// Monday to Friday, closed on NYSE holidays ([`us_equity_holidays`]).
+    pub fn us_equities() -> Self {
+        let rth = Session::from_hm(9, 30, 16, 0).expect("valid session");
+        Self::new(TimeZone::UsEastern)
+            .with_session(&Weekday::WORKDAYS, rth)
+            .with_holiday_rule(us_equity_holidays)
+    }
+
+    /// Trade `session` on each of `days`.
+    pub fn with_session(mut self, days: &[Weekday], session: Session) -> Self {
+        for &day in days {
+            self.sessions[day as usize] = Some(session);
+        }
+        self
+    }
+
+    /// Close the market on `date`.
+    pub fn with_holiday(mut self, date: Date) -> Self {
+        self.holidays.insert(date);
+        self
+    }
+
+    /// Close the market on the dates `rule` returns for each year, in
+    /// addition to explicit holidays.
+    pub fn with_holiday_rule(mut self, rule: fn(i32) -> Vec<Date>) -> Self {
+        self.holiday_rule = Some(rule);
+        self
+    }
+
+    /// Time zone of the sessions.
+    pub fn timezone(&self) -> TimeZone {
+        self.timezone
+    }
+
+    /// Session traded on `day`, if any (ignoring holidays).
+    pub fn session(&self, day: Weekday) -> Option<Session> {
+        self.sessions[day as usize]
+    }
+
+    /// Whether `date` is a holiday.
+    pub fn is_holiday(&self, date: Date) -> bool {
+        self.holidays.contains(&date)
+            || self
+                .holiday_rule
+                .is_some_and(|rule| rule(date.year()).contains(&date))
+    }
+
+    /// Whether the market is open at `timestamp`.
+    pub fn is_open(&self, timestamp: u64) -> bool {
+        self.session_start(timestamp).is_some()
+    }
+
+    /// Start of the session `timestamp` falls in, or `None` outside
+    /// trading hours. Bars with the same session start belong to the same
+    /// session.
+    pub fn session_start(&self, timestamp: u64) -> Option<u64> {
+        let local = self.timezone.local(timestamp);
+        let date = Date::from_days(local.div_euclid(DAY));
+        let seconds = local.rem_euclid(DAY) as u32;
+        let session = self.session(date.weekday())?;
+        if !session.contains(seconds) || self.is_holiday(date) {
+            return None;
+        }
+        Some(timestamp - u64::from(seconds - session.open))
+    }
+
+    /// The candles inside trading hours.
+    pub fn filter(&self, candles: &[Candle]) -> Vec<Candle> {
+        candles
+            .iter()
+            .filter(|c| self.is_open(c.timestamp))
+            .copied()
+            .collect()
+    }
+
+    /// Split `candles` into runs belonging to one session each, dropping
+    /// candles outside trading hours.
+    pub fn split<'a>(&self, candles: &'a [Candle]) -> Vec<&'a [Candle]> {
+        let mut runs = Vec::new();
+        let mut current: Option<(u64, usize)> = None;
+        for (i, candle) in candles.iter().enumerate() {
+            let start = self.session_start(candle.timestamp);
+            match (current, start) {
+                (Some((session, _)), Some(s)) if session == s => continue,
+                _ => {}
+            }
+            if let Some((_, from)) = current.take() {
+                runs.push(&candles[from..i]);
+            }
+            current = start.map(|s| (s, i));
+        }
+        if let Some((_, from)) = current {
+            runs.push(&candles[from..]);
+        }
+        runs
+    }
+
+    /// One bar per session (open of the first candle, max high, min low,
+    /// close of the last, summed volume), stamped with the session start.
+    pub fn resample_sessions(&self, candles: &[Candle]) -> Vec<Candle> {
+        self.split(candles)
+            .into_iter()
+            .map(|run| {
+                let first = run[0];
+                let last = run[run.len() - 1];
+                Candle {
+                    timestamp: self
+                        .session_start(first.timestamp)
+                        .unwrap_or(first.timestamp),
+                    open: first.open,
+                    high: run.iter().map(|c| c.high).fold(f64::MIN, f64::max),
+                    low: run.iter().map(|c| c.low).fold(f64::MAX, f64::min),
+                    close: last.close,
+                    volume: run.iter().map(|c| c.volume).sum(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Runs an indicator separately for every session of a calendar
+///
+/// The inner indicator is reset at each session open, and candles outside
+/// trading hours are skipped (they yield `None`). Wrap `Vwap` to get the
+/// usual session VWAP.
+///
+/// ```
+/// use rsta::calendar::{PerSession, TradingCalendar};
+/// use rsta::indicators::volume::Vwap;
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// let bar = |timestamp, price| Candle { timestamp, open: price, high: price, low: price, close: price, volume: 1.0 };
+/// let mut vwap = PerSession::new(Vwap::new(), TradingCalendar::crypto());
+///
+/// let day = 1_700_006_400; // a UTC midnight
+/// assert_eq!(vwap.next(bar(day - 60, 10.0)).unwrap(), Some(10.0));
+/// // A new session starts at midnight: the average restarts.
+/// assert_eq!(vwap.next(bar(day, 20.0)).unwrap(), Some(20.0));
+/// assert_eq!(vwap.next(bar(day + 60, 30.0)).unwrap(), Some(25.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PerSession<I> {
+    inner: I,
+    calendar: TradingCalendar,
+    session: Option<u64>,
+}
+
+impl<I> PerSession<I> {
+    /// Run `inner` per session of `calendar`.
+    pub fn new(inner: I, calendar: TradingCalendar) -> Self {
+        Self {
+            inner,
+            calendar,
+            session: None,
+        }
+    }
+
+    /// The calendar sessions are taken from.
+    pub fn calendar(&self) -> &TradingCalendar {
+        &self.calendar
+    }
+
+    /// Unwrap, returning the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I, O> Indicator<Candle, O> for PerSession<I>
+where
+    I: Indicator<Candle, O>,
+{
+    /// Outputs of every session, concatenated.
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<O>, IndicatorError> {
+        self.reset();
+        let mut out = Vec::new();
+        for &candle in data {
+            out.extend(self.next(candle)?);
+        }
+        Ok(out)
+    }
+
+    /// Candles outside trading hours, and any the inner indicator skips,
+    /// map to `None` at their position.
+    fn calculate_aligned(&mut self, data: &[Candle]) -> Result<Vec<Option<O>>, IndicatorError> {
+        self.reset();
+        data.iter().map(|&candle| self.next(candle)).collect()
+    }
+
+    fn calculate_series(&mut self, data: &[Candle]) -> Result<IndicatorSeries<O>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<O>, IndicatorError> {
+        let Some(session) = self.calendar.session_start(value.timestamp) else {
+            return Ok(None);
+        };
+        if self.session != Some(session) {
+            self.inner.reset();
+            self.session = Some(session);
+        }
+        self.inner.next(value)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.session = None;
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.inner.current()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    /// Unix timestamp of a UTC wall-clock time.
+    fn utc(year: i32, month: u8, day: u8, hour: u64, minute: u64) -> u64 {
+        date(year, month, day).days() as u64 * 86_400 + hour * 3_600 + minute * 60
+    }
+
+    #[test]
+    fn test_date_round_trip() {
+        assert_eq!(Date::from_days(0), date(1970, 1, 1));
+        assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+        assert_eq!(date(2000, 2, 29).days(), 11_016);
+        assert_eq!(date(2024, 3, 29).weekday(), Weekday::Friday);
+        assert_eq!(Date::from_days(-1), date(1969, 12, 31));
+        for days in (-800_000..800_000).step_by(997) {
+            assert_eq!(Date::from_days(days).days(), days);
+        }
+        assert!(Date::new(2023, 2, 29).is_none());
+        assert!(Date::new(2024, 13, 1).is_none());
+    }
+
+    #[test]
+    fn test_us_eastern_daylight_saving() {
+        let ny = TimeZone::UsEastern;
+        // 2024: DST from 10 March 07:00 UTC to 3 November 06:00 UTC.
+        assert_eq!(ny.offset_at(utc(2024, 3, 10, 6, 59)), -5 * 3_600);
+        assert_eq!(ny.offset_at(utc(2024, 3, 10, 7, 0)), -4 * 3_600);
+        assert_eq!(ny.offset_at(utc(2024, 11, 3, 5, 59)), -4 * 3_600);
+        assert_eq!(ny.offset_at(utc(2024, 11, 3, 6, 0)), -5 * 3_600);
+        // Pre-2007 rules: 2 April to 29 October 2006.
+        assert_eq!(ny.offset_at(utc(2006, 3, 20, 12, 0)), -5 * 3_600);
+        assert_eq!(ny.offset_at(utc(2006, 10, 20, 12, 0)), -4 * 3_600);
+        assert_eq!(ny.date(utc(2024, 1, 2, 3, 0)), date(2024, 1, 1));
+        assert_eq!(TimeZone::Fixed(3_600).local(0), 3_600);
+    }
+
+    #[test]
+    fn test_us_equity_holidays() {
+        let expected_2024 = [
+            date(2024, 1, 1),
+            date(2024, 1, 15),
+            date(2024, 2, 19),
+            date(2024, 3, 29),
+            date(2024, 5, 27),
+            date(2024, 6, 19),
+            date(2024, 7, 4),
+            date(2024, 9, 2),
+            date(2024, 11, 28),
+            date(2024, 12, 25),
+        ];
+        assert_eq!(us_equity_holidays(2024), expected_2024);
+
+        // 2022: New Year on a Saturday is not observed; Juneteenth on a
+        // Sunday moves to Monday.
+        let h2022 = us_equity_holidays(2022);
+        assert!(!h2022.contains(&date(2021, 12, 31)));
+        assert!(h2022.contains(&date(2022, 6, 20)));
+        assert_eq!(h2022.len(), 9);
+        assert_eq!(easter(2025), date(2025, 4, 20));
+    }
+
+    #[test]
+    fn test_sessions() {
+        let nyse = TradingCalendar::us_equities();
+        // 2024-07-03 (Wednesday): 13:30 UTC is 09:30 EDT.
+        assert_eq!(nyse.session_start(utc(2024, 7, 3, 13, 29)), None);
+        let open = utc(2024, 7, 3, 13, 30);
+        assert_eq!(nyse.session_start(open), Some(open));
+        assert_eq!(nyse.session_start(utc(2024, 7, 3, 19, 59)), Some(open));
+        assert!(!nyse.is_open(utc(2024, 7, 3, 20, 0)));
+        // Independence Day and a Saturday.
+        assert!(!nyse.is_open(utc(2024, 7, 4, 15, 0)));
+        assert!(!nyse.is_open(utc(2024, 7, 6, 15, 0)));
+        // Winter: 09:30 EST is 14:30 UTC.
+        assert!(!nyse.is_open(utc(2024, 1, 3, 14, 0)));
+        assert!(nyse.is_open(utc(2024, 1, 3, 14, 30)));
+
+        let custom = TradingCalendar::new(TimeZone::Fixed(3_600))
+            .with_session(&[Weekday::Monday], Session::from_hm(8, 0, 9, 0).unwrap())
+            .with_holiday(date(2024, 1, 8));
+        assert!(custom.is_open(utc(2024, 1, 1, 7, 30)));
+        assert!(!custom.is_open(utc(2024, 1, 8, 7, 30)));
+        assert_eq!(custom.session(Weekday::Tuesday), None);
+        assert!(Session::new(10, 10).is_err());
+        assert!(Session::new(0, 86_401).is_err());
+    }
+
+    #[test]
+    fn test_filter_split_and_resample() {
+        let nyse = TradingCalendar::us_equities();
+        // Hourly bars over two days, 12:30 to 21:30 UTC (08:30 to 17:30 EDT).
+        let candles: Vec<Candle> = [3u8, 5]
+            .iter()
+            .flat_map(|&day| {
+                (0..10).map(move |h| {
+                    let price = f64::from(day) * 10.0 + h as f64;
+                    Candle {
+                        timestamp: utc(2024, 7, day, 12 + h, 30),
+                        open: price,
+                        high: price + 1.0,
+                        low: price - 1.0,
+                        close: price + 0.5,
+                        volume: 1.0,
+                    }
+                })
+            })
+            .collect();
+
+        // 13:30 to 19:30 UTC is inside RTH: 7 bars per day.
+        assert_eq!(nyse.filter(&candles).len(), 14);
+        let runs = nyse.split(&candles);
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|run| run.len() == 7));
+
+        let daily = nyse.resample_sessions(&candles);
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].timestamp, utc(2024, 7, 3, 13, 30));
+        assert_eq!((daily[0].open, daily[0].close), (31.0, 37.5));
+        assert_eq!((daily[1].high, daily[1].low), (58.0, 50.0));
+        assert_eq!(daily[1].volume, 7.0);
+    }
+
+    #[test]
+    fn test_per_session_resets_inner_indicator() {
+        #[derive(Debug, Clone)]
+        struct Count(usize, Option<usize>);
+        impl Indicator<Candle, usize> for Count {
+            fn calculate(&mut self, data: &[Candle]) -> Result<Vec<usize>, IndicatorError> {
+                data.iter()
+                    .map(|&c| self.next(c).map(Option::unwrap))
+                    .collect()
+            }
+            fn next(&mut self, _: Candle) -> Result<Option<usize>, IndicatorError> {
+                self.0 += 1;
+                self.1 = Some(self.0);
+                Ok(self.1)
+            }
+            fn reset(&mut self) {
+                *self = Count(0, None);
+            }
+            fn current(&self) -> Option<&usize> {
+                self.1.as_ref()
+            }
+        }
+        let bar = |timestamp| Candle {
+            timestamp,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+        };
+        let nyse = TradingCalendar::us_equities();
+        let mut counter = PerSession::new(Count(0, None), nyse);
+        let bars = [
+            bar(utc(2024, 7, 3, 13, 30)),
+            bar(utc(2024, 7, 3, 14, 30)),
+            bar(utc(2024, 7, 3, 21, 0)), // after the close
+            bar(utc(2024, 7, 5, 13, 30)),
+        ];
+        assert_eq!(counter.calculate(&bars).unwrap(), vec![1, 2, 1]);
+        assert_eq!(counter.current(), Some(&1));
+        assert_eq!(counter.next(bars[2]).unwrap(), None);
+
+        let aligned = counter.calculate_aligned(&bars).unwrap();
+        assert_eq!(aligned, vec![Some(1), Some(2), None, Some(1)]);
+        let series = counter.calculate_series(&bars).unwrap();
+        assert_eq!(series.aligned(), vec![Some(&1), Some(&2), None, Some(&1)]);
+    }
+}
@@ -213,33 +213,8 @@ impl CsvFormatter {
         self.data.clear();
         self.calculated_values.clear();
 
-        let ci = self.config.column_indices;
         for result in rdr.records() {
-            let record = result?;
-
-            if record.len() <= ci.volume {
-                return Err(CsvError::Parse(
-                    "Record has fewer columns than expected".to_string(),
-                ));
-            }
-
-            let date = record.get(ci.date).unwrap_or("").to_string();
-            let timestamp = parse_timestamp(&date, &self.config.date_format)?;
-            let open = parse_f64(record.get(ci.open), "open")?;
-            let high = parse_f64(record.get(ci.high), "high")?;
-            let low = parse_f64(record.get(ci.low), "low")?;
-            let close = parse_f64(record.get(ci.close), "close")?;
-            let volume = parse_f64(record.get(ci.volume), "volume")?;
-
-            self.data.push(OhlcvData {
-                date,
-                timestamp,
-                open,
-                high,
-                low,
-                close,
-                volume,
-            });
+            self.data.push(parse_record(&result?, &self.config)?);
         }
 
         Ok(())
@@ -263,20 +238,17 @@ impl CsvFormatter {
             return Err(CsvError::MissingData);
         }
 
-        let len = self.data.len();
         let candles: Vec<Candle> = self.data.iter().map(OhlcvData::to_candle).collect();
         let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
 
         for (name, indicator) in self.close_indicators.iter_mut() {
-            let values = indicator.calculate(&closes)?;
-            self.calculated_values
-                .insert(name.clone(), align_to_len(values, len));
+            let values = indicator.calculate_aligned(&closes)?;
+            self.calculated_values.insert(name.clone(), values);
         }
 
         for (name, indicator) in self.candle_indicators.iter_mut() {
-            let values = indicator.calculate(&candles)?;
-            self.calculated_values
-                .insert(name.clone(), align_to_len(values, len));
+            let values = indicator.calculate_aligned(&candles)?;
+            self.calculated_values.insert(name.clone(), values);
         }
 
         Ok(())
@@ -346,6 +318,102 @@ impl CsvFormatter {
     }
 }
 
+/// Reads OHLCV rows from a CSV source in fixed-size batches of candles.
+///
+/// Pairs with [`Indicator::calculate_chunk`] to run indicators over
+/// histories too large to load at once: only one chunk is held in memory.
+///
+/// ```
+/// use rsta::csv::{CandleChunks, CsvConfig};
+/// use rsta::indicators::trend::Sma;
+/// use rsta::indicators::Indicator;
+///
+/// let csv = "Date,Open,High,Low,Close,Volume\n\
+///            2024-01-01,1,1,1,1,10\n\
+///            2024-01-02,2,2,2,2,10\n\
+///            2024-01-03,3,3,3,3,10\n";
+/// let mut sma = Sma::new(2).unwrap();
+/// let mut values = Vec::new();
+/// for chunk in CandleChunks::new(csv.as_bytes(), CsvConfig::default(), 2) {
+///     values.extend(sma.calculate_chunk(&chunk.unwrap()).unwrap());
+/// }
+/// assert_eq!(values, vec![1.5, 2.5]);
+/// ```
+pub struct CandleChunks<R> {
+    records: csv::StringRecordsIntoIter<R>,
+    config: CsvConfig,
+    chunk_size: usize,
+}
+
+impl CandleChunks<File> {
+    /// Read the CSV file at `path` in chunks of `chunk_size` candles.
+    pub fn from_path<P: AsRef<Path>>(
+        path: P,
+        config: CsvConfig,
+        chunk_size: usize,
+    ) -> Result<Self, CsvError> {
+        Ok(Self::new(File::open(path)?, config, chunk_size))
+    }
+}
+
+impl<R: Read> CandleChunks<R> {
+    /// Read `reader` in chunks of `chunk_size` candles (at least one).
+    pub fn new(reader: R, config: CsvConfig, chunk_size: usize) -> Self {
+        let records = ReaderBuilder::new()
+            .has_headers(config.has_header)
+            .delimiter(config.delimiter)
+            .from_reader(reader)
+            .into_records();
+        Self {
+            records,
+            config,
+            chunk_size: chunk_size.max(1),
+        }
+    }
+}
+
+impl<R: Read> Iterator for CandleChunks<R> {
+    type Item = Result<Vec<Candle>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        for result in self.records.by_ref() {
+            let row = result
+                .map_err(CsvError::from)
+                .and_then(|record| parse_record(&record, &self.config));
+            match row {
+                Ok(row) => chunk.push(row.to_candle()),
+                Err(e) => return Some(Err(e)),
+            }
+            if chunk.len() == self.chunk_size {
+                break;
+            }
+        }
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+}
+
+fn parse_record(record: &csv::StringRecord, config: &CsvConfig) -> Result<OhlcvData, CsvError> {
+    let ci = config.column_indices;
+    if record.len() <= ci.volume {
+        return Err(CsvError::Parse(
+            "Record has fewer columns than expected".to_string(),
+        ));
+    }
+
+    let date = record.get(ci.date).unwrap_or("").to_string();
+    let timestamp = parse_timestamp(&date, &config.date_format)?;
+    Ok(OhlcvData {
+        date,
+        timestamp,
+        open: parse_f64(record.get(ci.open), "open")?,
+        high: parse_f64(record.get(ci.high), "high")?,
+        low: parse_f64(record.get(ci.low), "low")?,
+        close: parse_f64(record.get(ci.close), "close")?,
+        volume: parse_f64(record.get(ci.volume), "volume")?,
+    })
+}
+
 fn parse_f64(value: Option<&str>, field: &str) -> Result<f64, CsvError> {
     value
         .unwrap_or("0")
@@ -365,18 +433,7 @@ fn parse_timestamp(date: &str, format: &str) -> Result<u64, CsvError> {
     Ok(dt.and_utc().timestamp() as u64)
 }
 
-/// Right-align indicator output with the source data: indicators that need a
-/// warmup period emit fewer values than the input length, so we left-pad with
-/// `None`.
-fn align_to_len(values: Vec<f64>, len: usize) -> Vec<Option<f64>> {
-    let pad = len.saturating_sub(values.len());
-    let mut out = Vec::with_capacity(len);
-    out.extend(std::iter::repeat_n(None, pad));
-    out.extend(values.into_iter().map(Some));
-    out
-}
-
-#[cfg(test)]
+#[cfg(all(test, feature = "volatility"))]
 mod tests {
     use super::*;
     use crate::indicators::trend::Sma;
@@ -440,4 +497,31 @@ mod tests {
         assert!(exported.contains("SMA3"));
         assert!(exported.contains("2024-01-03"));
     }
+
+    #[test]
+    fn chunked_reading_matches_full_load() {
+        let mut f = CsvFormatter::new();
+        f.load_from_reader(sample_csv().as_bytes()).unwrap();
+        let candles: Vec<Candle> = f.data().iter().map(OhlcvData::to_candle).collect();
+        let full = Atr::new(3).unwrap().calculate(&candles).unwrap();
+
+        let mut atr = Atr::new(3).unwrap();
+        let mut sizes = Vec::new();
+        let mut chunked = Vec::new();
+        for chunk in CandleChunks::new(sample_csv().as_bytes(), CsvConfig::default(), 2) {
+            let chunk = chunk.unwrap();
+            sizes.push(chunk.len());
+            chunked.extend(atr.calculate_chunk(&chunk).unwrap());
+        }
+        assert_eq!(sizes, [2, 2, 1]);
+        assert_eq!(chunked, full);
+    }
+
+    #[test]
+    fn chunked_reading_reports_bad_rows() {
+        let csv = "Date,Open,High,Low,Close,Volume\n2024-01-01,1,1,1,x,1\n";
+        let mut chunks = CandleChunks::new(csv.as_bytes(), CsvConfig::default(), 4);
+        assert!(matches!(chunks.next(), Some(Err(CsvError::Parse(_)))));
+        assert!(chunks.next().is_none());
+    }
 }
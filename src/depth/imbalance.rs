@@ -0,0 +1,124 @@
+use crate::depth::BookSnapshot;
+use crate::indicators::utils::validate_period;
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Order Book Imbalance
+///
+/// Compares the size resting on the bid with the size on the ask over the
+/// best `levels` levels of each side. Ranges from `-1` (only asks) to `1`
+/// (only bids); positive values mean buying interest dominates and often
+/// precede upticks.
+///
+/// # Formula
+///
+/// ```text
+/// imbalance = (bid_depth - ask_depth) / (bid_depth + ask_depth)
+/// ```
+///
+/// An empty book gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::depth::{BookImbalance, BookSnapshot, Level};
+/// use rsta::indicators::Indicator;
+///
+/// let book = BookSnapshot::new(0, vec![Level::new(10.0, 3.0)], vec![Level::new(10.5, 1.0)]);
+/// let mut imbalance = BookImbalance::new(1).unwrap();
+/// assert_eq!(imbalance.next(book).unwrap(), Some(0.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BookImbalance {
+    levels: usize,
+    last: Option<f64>,
+}
+
+impl BookImbalance {
+    /// Create a new order book imbalance indicator
+    ///
+    /// # Arguments
+    /// * `levels` - Number of price levels per side (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(levels: usize) -> Result<Self, IndicatorError> {
+        validate_period(levels, 1)?;
+        Ok(Self { levels, last: None })
+    }
+
+    /// Number of price levels per side.
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.last = None;
+    }
+
+    fn step(&mut self, book: &BookSnapshot) -> f64 {
+        let bid = book.bid_depth(self.levels);
+        let ask = book.ask_depth(self.levels);
+        let total = bid + ask;
+        let imbalance = if total > 0.0 {
+            (bid - ask) / total
+        } else {
+            0.0
+        };
+        self.last = Some(imbalance);
+        imbalance
+    }
+}
+
+impl Indicator<BookSnapshot, f64> for BookImbalance {
+    fn calculate(&mut self, data: &[BookSnapshot]) -> Result<Vec<f64>, IndicatorError> {
+        if data.is_empty() {
+            return Err(IndicatorError::insufficient_data(1, 0));
+        }
+        self.reset_state();
+        Ok(data.iter().map(|book| self.step(book)).collect())
+    }
+
+    fn next(&mut self, value: BookSnapshot) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(&value)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "BookImbalance"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::depth::Level;
+
+    #[test]
+    fn test_imbalance_over_levels() {
+        let book = BookSnapshot::new(
+            0,
+            vec![Level::new(10.0, 1.0), Level::new(9.0, 9.0)],
+            vec![Level::new(11.0, 3.0), Level::new(12.0, 1.0)],
+        );
+        let one = BookImbalance::new(1)
+            .unwrap()
+            .calculate(std::slice::from_ref(&book))
+            .unwrap();
+        assert_eq!(one, vec![-0.5]);
+        let two = BookImbalance::new(2).unwrap().calculate(&[book]).unwrap();
+        assert_eq!(two, vec![6.0 / 14.0]);
+
+        let mut imbalance = BookImbalance::new(3).unwrap();
+        assert_eq!(imbalance.next(BookSnapshot::default()).unwrap(), Some(0.0));
+        assert!(imbalance.calculate(&[]).is_err());
+        assert!(BookImbalance::new(0).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::depth::BookSnapshot;
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Microprice
+///
+/// The best bid and ask prices weighted by the size on the opposite side.
+/// When the bid is much larger than the ask, the next trade is more likely
+/// to lift the ask, and the microprice sits close to it; it is a better
+/// short-term fair value than the plain mid price.
+///
+/// # Formula
+///
+/// ```text
+/// microprice = (bid * ask_size + ask * bid_size) / (bid_size + ask_size)
+/// ```
+///
+/// With no size at the top of the book the mid price is used. A snapshot
+/// with an empty side has no price and is rejected with
+/// [`IndicatorError::InvalidInput`].
+///
+/// # Example
+///
+/// ```
+/// use rsta::depth::{BookSnapshot, Level, Microprice};
+/// use rsta::indicators::Indicator;
+///
+/// let book = BookSnapshot::new(0, vec![Level::new(10.0, 1.0)], vec![Level::new(11.0, 4.0)]);
+/// let mut microprice = Microprice::new();
+/// assert_eq!(microprice.next(book).unwrap(), Some(10.2));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Microprice {
+    seen: usize,
+    last: Option<f64>,
+}
+
+impl Microprice {
+    /// Create a new microprice indicator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.seen = 0;
+        self.last = None;
+    }
+
+    fn step(&mut self, book: &BookSnapshot, index: usize) -> Result<f64, IndicatorError> {
+        let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) else {
+            return Err(IndicatorError::InvalidInput {
+                index,
+                reason: "order book side is empty".to_string(),
+            });
+        };
+        let size = bid.size + ask.size;
+        let price = if size > 0.0 {
+            (bid.price * ask.size + ask.price * bid.size) / size
+        } else {
+            (bid.price + ask.price) / 2.0
+        };
+        self.last = Some(price);
+        Ok(price)
+    }
+}
+
+impl Indicator<BookSnapshot, f64> for Microprice {
+    fn calculate(&mut self, data: &[BookSnapshot]) -> Result<Vec<f64>, IndicatorError> {
+        if data.is_empty() {
+            return Err(IndicatorError::insufficient_data(1, 0));
+        }
+        self.reset_state();
+        let result = data
+            .iter()
+            .enumerate()
+            .map(|(i, book)| self.step(book, i))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.seen = data.len();
+        Ok(result)
+    }
+
+    fn next(&mut self, value: BookSnapshot) -> Result<Option<f64>, IndicatorError> {
+        let price = self.step(&value, self.seen)?;
+        self.seen += 1;
+        Ok(Some(price))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Microprice"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::depth::Level;
+
+    #[test]
+    fn test_microprice_leans_to_thin_side() {
+        let book = |bid_size, ask_size| {
+            BookSnapshot::new(
+                0,
+                vec![Level::new(100.0, bid_size)],
+                vec![Level::new(102.0, ask_size)],
+            )
+        };
+        let mut microprice = Microprice::new();
+        let values = microprice
+            .calculate(&[book(1.0, 1.0), book(3.0, 1.0), book(0.0, 0.0)])
+            .unwrap();
+        assert_eq!(values, vec![101.0, 101.5, 101.0]);
+
+        let one_sided = BookSnapshot::new(0, vec![Level::new(100.0, 1.0)], vec![]);
+        assert!(matches!(
+            microprice.calculate(&[book(1.0, 1.0), one_sided]),
+            Err(IndicatorError::InvalidInput { index: 1, .. })
+        ));
+        assert!(microprice.calculate(&[]).is_err());
+    }
+}
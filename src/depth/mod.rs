@@ -0,0 +1,139 @@
+//! Order book snapshots and book-based indicators
+//!
+//! Candles summarise what traded; the order book shows the liquidity
+//! waiting to trade. A [`BookSnapshot`] holds the top levels of both sides
+//! at one instant, and the indicators here implement
+//! `Indicator<BookSnapshot, f64>` so they stream from a depth feed like any
+//! other indicator:
+//!
+//! - [`BookImbalance`] — bid versus ask size over the top levels
+//! - [`Microprice`] — the mid price weighted towards the thinner side
+//!
+//! ```
+//! use rsta::depth::{BookImbalance, BookSnapshot, Level, Microprice};
+//! use rsta::indicators::Indicator;
+//!
+//! let book = BookSnapshot::new(
+//!     1_700_000_000,
+//!     vec![Level::new(99.0, 3.0), Level::new(100.0, 6.0)],
+//!     vec![Level::new(101.0, 2.0), Level::new(102.0, 5.0)],
+//! );
+//! assert_eq!(book.best_bid(), Some(Level::new(100.0, 6.0)));
+//! assert_eq!(book.mid(), Some(100.5));
+//!
+//! let mut imbalance = BookImbalance::new(2).unwrap();
+//! assert_eq!(imbalance.next(book.clone()).unwrap(), Some(2.0 / 16.0));
+//! // Three times more size on the bid: the fair price leans to the ask.
+//! let mut microprice = Microprice::new();
+//! assert_eq!(microprice.next(book).unwrap(), Some(100.75));
+//! ```
+
+pub mod imbalance;
+pub mod microprice;
+
+pub use self::imbalance::BookImbalance;
+pub use self::microprice::Microprice;
+
+/// One price level of an order book.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Level {
+    /// Price of the level
+    pub price: f64,
+    /// Total size resting at the price
+    pub size: f64,
+}
+
+impl Level {
+    /// A level of `size` at `price`.
+    pub fn new(price: f64, size: f64) -> Self {
+        Self { price, size }
+    }
+}
+
+/// The top levels of an order book at one instant
+///
+/// Bids are ordered best (highest) first and asks best (lowest) first;
+/// [`BookSnapshot::new`] sorts them.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BookSnapshot {
+    /// Snapshot time (typically Unix timestamp in seconds or milliseconds)
+    pub timestamp: u64,
+    /// Bid levels, highest price first
+    pub bids: Vec<Level>,
+    /// Ask levels, lowest price first
+    pub asks: Vec<Level>,
+}
+
+impl BookSnapshot {
+    /// A snapshot of `bids` and `asks`, given in any order.
+    pub fn new(timestamp: u64, mut bids: Vec<Level>, mut asks: Vec<Level>) -> Self {
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        Self {
+            timestamp,
+            bids,
+            asks,
+        }
+    }
+
+    /// Highest bid.
+    pub fn best_bid(&self) -> Option<Level> {
+        self.bids.first().copied()
+    }
+
+    /// Lowest ask.
+    pub fn best_ask(&self) -> Option<Level> {
+        self.asks.first().copied()
+    }
+
+    /// Midpoint of the best bid and ask.
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    /// Best ask minus best bid.
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// Whether the best bid is at or above the best ask.
+    pub fn is_crossed(&self) -> bool {
+        self.spread().is_some_and(|spread| spread <= 0.0)
+    }
+
+    /// Total bid size over the best `levels` levels.
+    pub fn bid_depth(&self, levels: usize) -> f64 {
+        self.bids.iter().take(levels).map(|l| l.size).sum()
+    }
+
+    /// Total ask size over the best `levels` levels.
+    pub fn ask_depth(&self, levels: usize) -> f64 {
+        self.asks.iter().take(levels).map(|l| l.size).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_orders_levels() {
+        let book = BookSnapshot::new(
+            0,
+            vec![
+                Level::new(9.0, 1.0),
+                Level::new(10.0, 2.0),
+                Level::new(8.0, 4.0),
+            ],
+            vec![Level::new(12.0, 1.0), Level::new(11.0, 3.0)],
+        );
+        assert_eq!(book.best_bid().unwrap().price, 10.0);
+        assert_eq!(book.best_ask().unwrap().price, 11.0);
+        assert_eq!(book.spread(), Some(1.0));
+        assert_eq!((book.bid_depth(2), book.ask_depth(5)), (3.0, 4.0));
+        assert!(!book.is_crossed());
+        assert_eq!(BookSnapshot::default().mid(), None);
+    }
+}
@@ -0,0 +1,346 @@
+//! # Equity-curve analytics
+//!
+//! [`EquityCurve`] follows an account bar by bar — from per-bar PnL or
+//! from the portfolio value — and keeps its performance statistics up to
+//! date in constant time per update:
+//!
+//! - high-water mark, current drawdown and maximum drawdown (relative to
+//!   the high-water mark, in `[0, 1]` while equity stays positive)
+//! - annualized Sharpe ratio (zero risk-free rate) and volatility of the
+//!   per-bar returns, over the whole history or a rolling window
+//!
+//! The same accumulator serves a live account and a backtest replay, so
+//! dashboards and reports show identical numbers.
+//!
+//! ```
+//! use rsta::equity::EquityCurve;
+//!
+//! let mut curve = EquityCurve::new(1_000.0).unwrap();
+//! curve.push_pnl(100.0);
+//! curve.push_pnl(-220.0);
+//! let snapshot = curve.push_value(1_050.0);
+//!
+//! assert_eq!(snapshot.high_water_mark, 1_100.0);
+//! assert!((curve.max_drawdown() - 0.2).abs() < 1e-12);
+//! assert!(snapshot.drawdown > 0.0 && snapshot.sharpe.is_some());
+//! ```
+
+use crate::indicators::utils::RollingStats;
+use crate::indicators::IndicatorError;
+use crate::returns;
+
+/// Statistics of an [`EquityCurve`] after an update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquitySnapshot {
+    /// Current equity.
+    pub equity: f64,
+    /// Highest equity so far, the initial equity included.
+    pub high_water_mark: f64,
+    /// Current drop from the high-water mark, as a fraction of it.
+    pub drawdown: f64,
+    /// Largest drawdown so far.
+    pub max_drawdown: f64,
+    /// Annualized Sharpe ratio, once two returns are available.
+    pub sharpe: Option<f64>,
+    /// Annualized volatility of the returns, once two returns are
+    /// available.
+    pub volatility: Option<f64>,
+}
+
+/// Per-bar return statistics: over the whole history or a window.
+#[derive(Debug, Clone)]
+enum ReturnStats {
+    /// Welford's running mean and sum of squared deviations.
+    Full {
+        count: usize,
+        mean: f64,
+        m2: f64,
+    },
+    Rolling(RollingStats),
+}
+
+impl ReturnStats {
+    fn push(&mut self, value: f64) {
+        match self {
+            ReturnStats::Full { count, mean, m2 } => {
+                *count += 1;
+                let delta = value - *mean;
+                *mean += delta / *count as f64;
+                *m2 += delta * (value - *mean);
+            }
+            ReturnStats::Rolling(stats) => stats.push(value),
+        }
+    }
+
+    /// Mean and sample (`n - 1`) standard deviation, from two values on.
+    fn mean_std(&self) -> Option<(f64, f64)> {
+        let (count, mean, variance) = match self {
+            ReturnStats::Full { count, mean, m2 } => (*count, *mean, *m2 / (*count as f64 - 1.0)),
+            ReturnStats::Rolling(stats) => {
+                let n = stats.len();
+                let population = stats.variance()?;
+                (n, stats.mean()?, population * n as f64 / (n as f64 - 1.0))
+            }
+        };
+        (count >= 2).then(|| (mean, variance.max(0.0).sqrt()))
+    }
+
+    fn reset(&mut self) {
+        match self {
+            ReturnStats::Full { count, mean, m2 } => {
+                *count = 0;
+                *mean = 0.0;
+                *m2 = 0.0;
+            }
+            ReturnStats::Rolling(stats) => stats.reset(),
+        }
+    }
+}
+
+/// Streaming drawdown, Sharpe and volatility of an account. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct EquityCurve {
+    initial_equity: f64,
+    equity: f64,
+    high_water_mark: f64,
+    max_drawdown: f64,
+    periods_per_year: f64,
+    stats: ReturnStats,
+    bars: usize,
+}
+
+impl EquityCurve {
+    /// Start an account at `initial_equity`
+    ///
+    /// # Arguments
+    /// * `initial_equity` - Starting value (must be positive and finite)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new curve or an error
+    pub fn new(initial_equity: f64) -> Result<Self, IndicatorError> {
+        if !(initial_equity.is_finite() && initial_equity > 0.0) {
+            return Err(IndicatorError::invalid_parameter(
+                "initial_equity",
+                initial_equity,
+                "must be positive and finite",
+            ));
+        }
+        Ok(Self {
+            initial_equity,
+            equity: initial_equity,
+            high_water_mark: initial_equity,
+            max_drawdown: 0.0,
+            periods_per_year: 252.0,
+            stats: ReturnStats::Full {
+                count: 0,
+                mean: 0.0,
+                m2: 0.0,
+            },
+            bars: 0,
+        })
+    }
+
+    /// Compute Sharpe and volatility over the last `period` returns instead
+    /// of the whole history. `period` must be at least 2.
+    pub fn with_window(mut self, period: usize) -> Result<Self, IndicatorError> {
+        if period < 2 {
+            return Err(IndicatorError::invalid_parameter(
+                "period",
+                period,
+                "must be at least 2",
+            ));
+        }
+        self.stats = ReturnStats::Rolling(RollingStats::new(period)?);
+        Ok(self)
+    }
+
+    /// Bars per year used to annualize (252 by default, as in
+    /// `BacktestConfig`).
+    pub fn with_periods_per_year(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Add one bar of profit or loss.
+    pub fn push_pnl(&mut self, pnl: f64) -> EquitySnapshot {
+        self.push_value(self.equity + pnl)
+    }
+
+    /// Add one bar given the portfolio value at its close.
+    pub fn push_value(&mut self, value: f64) -> EquitySnapshot {
+        // No return is defined once the account is wiped out.
+        if let Some(r) = returns::simple_return(self.equity, value) {
+            self.stats.push(r);
+        }
+        self.equity = value;
+        self.high_water_mark = self.high_water_mark.max(value);
+        self.max_drawdown = self.max_drawdown.max(self.drawdown());
+        self.bars += 1;
+        self.snapshot()
+    }
+
+    /// Current statistics.
+    pub fn snapshot(&self) -> EquitySnapshot {
+        EquitySnapshot {
+            equity: self.equity,
+            high_water_mark: self.high_water_mark,
+            drawdown: self.drawdown(),
+            max_drawdown: self.max_drawdown,
+            sharpe: self.sharpe(),
+            volatility: self.volatility(),
+        }
+    }
+
+    /// Current equity.
+    pub fn equity(&self) -> f64 {
+        self.equity
+    }
+
+    /// Highest equity so far.
+    pub fn high_water_mark(&self) -> f64 {
+        self.high_water_mark
+    }
+
+    /// Current drop from the high-water mark, as a fraction of it.
+    pub fn drawdown(&self) -> f64 {
+        (self.high_water_mark - self.equity) / self.high_water_mark
+    }
+
+    /// Largest drawdown so far.
+    pub fn max_drawdown(&self) -> f64 {
+        self.max_drawdown
+    }
+
+    /// Return since the start.
+    pub fn total_return(&self) -> f64 {
+        returns::simple_return(self.initial_equity, self.equity).unwrap_or(0.0)
+    }
+
+    /// Annualized Sharpe ratio of the per-bar returns; `0` when they do
+    /// not vary, `None` before two returns.
+    pub fn sharpe(&self) -> Option<f64> {
+        let (mean, std) = self.stats.mean_std()?;
+        Some(if std > 0.0 {
+            mean / std * self.periods_per_year.sqrt()
+        } else {
+            0.0
+        })
+    }
+
+    /// Annualized volatility of the per-bar returns, `None` before two
+    /// returns.
+    pub fn volatility(&self) -> Option<f64> {
+        let (_, std) = self.stats.mean_std()?;
+        Some(std * self.periods_per_year.sqrt())
+    }
+
+    /// Number of bars pushed.
+    pub fn len(&self) -> usize {
+        self.bars
+    }
+
+    /// Whether no bar has been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.bars == 0
+    }
+
+    /// Restart from the initial equity.
+    pub fn reset(&mut self) {
+        self.equity = self.initial_equity;
+        self.high_water_mark = self.initial_equity;
+        self.max_drawdown = 0.0;
+        self.stats.reset();
+        self.bars = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    /// Sharpe and volatility recomputed from scratch.
+    fn batch(values: &[f64], periods_per_year: f64) -> (f64, f64) {
+        let r = returns::simple_returns(values).unwrap();
+        let n = r.len() as f64;
+        let mean = r.iter().sum::<f64>() / n;
+        let std = (r.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        (
+            mean / std * periods_per_year.sqrt(),
+            std * periods_per_year.sqrt(),
+        )
+    }
+
+    #[test]
+    fn drawdown_tracks_high_water_mark() {
+        let mut curve = EquityCurve::new(100.0).unwrap();
+        assert!(curve.is_empty() && curve.sharpe().is_none());
+        for value in [110.0, 88.0, 99.0, 120.0, 90.0] {
+            curve.push_value(value);
+        }
+        assert_eq!(curve.high_water_mark(), 120.0);
+        assert!(approx(curve.drawdown(), 0.25));
+        assert!(approx(curve.max_drawdown(), 0.25));
+        assert!(approx(curve.total_return(), -0.1));
+        assert_eq!(curve.len(), 5);
+
+        curve.reset();
+        assert_eq!((curve.equity(), curve.max_drawdown()), (100.0, 0.0));
+        assert!(EquityCurve::new(0.0).is_err());
+        assert!(EquityCurve::new(100.0).unwrap().with_window(1).is_err());
+    }
+
+    #[test]
+    fn streaming_sharpe_matches_batch() {
+        let values: Vec<f64> = (0..60)
+            .map(|i| 1_000.0 + i as f64 * 3.0 + (i as f64 * 0.8).sin() * 25.0)
+            .collect();
+
+        let mut full = EquityCurve::new(values[0])
+            .unwrap()
+            .with_periods_per_year(365.0);
+        let mut rolling = EquityCurve::new(values[0])
+            .unwrap()
+            .with_window(20)
+            .unwrap();
+        let mut pnl = EquityCurve::new(values[0])
+            .unwrap()
+            .with_window(20)
+            .unwrap();
+        for w in values.windows(2) {
+            full.push_value(w[1]);
+            rolling.push_value(w[1]);
+            pnl.push_pnl(w[1] - w[0]);
+        }
+
+        let (sharpe, vol) = batch(&values, 365.0);
+        assert!(approx(full.sharpe().unwrap(), sharpe));
+        assert!(approx(full.volatility().unwrap(), vol));
+
+        let (sharpe, vol) = batch(&values[values.len() - 21..], 252.0);
+        assert!(approx(rolling.sharpe().unwrap(), sharpe));
+        assert!(approx(rolling.volatility().unwrap(), vol));
+        assert!(approx(pnl.sharpe().unwrap(), sharpe));
+    }
+
+    #[test]
+    fn flat_returns_and_wipeout() {
+        let mut curve = EquityCurve::new(100.0).unwrap();
+        curve.push_value(100.0);
+        let snapshot = curve.push_value(100.0);
+        assert_eq!(
+            (snapshot.sharpe, snapshot.volatility),
+            (Some(0.0), Some(0.0))
+        );
+
+        let mut wiped = EquityCurve::new(100.0).unwrap();
+        wiped.push_pnl(-100.0);
+        let snapshot = wiped.push_pnl(10.0);
+        assert_eq!(snapshot.max_drawdown, 1.0);
+        assert_eq!(snapshot.sharpe, None);
+    }
+}
@@ -0,0 +1,327 @@
+//! Feature matrices for machine learning
+//!
+//! A [`FeatureSet`] runs a configured list of indicators over a candle
+//! series and lines their outputs up into a [`FeatureMatrix`]: one row per
+//! candle, one named column per indicator output, ready to hand to a
+//! model as an `ndarray::Array2` or plain `Vec<Vec<f64>>`.
+//!
+//! ```
+//! use rsta::features::FeatureSet;
+//! use rsta::indicators::any_indicator::boxed;
+//! use rsta::indicators::{Candle, IndicatorSpec, Macd, Rsi};
+//!
+//! let candles: Vec<Candle> = (0..60)
+//!     .map(|i| {
+//!         let close = 100.0 + (i as f64 * 0.3).sin() * 5.0;
+//!         Candle { timestamp: i, open: close, high: close + 1.0, low: close - 1.0, close, volume: 1_000.0 }
+//!     })
+//!     .collect();
+//!
+//! let mut features = FeatureSet::new();
+//! features
+//!     .add("rsi", boxed(Rsi::new(14).unwrap()))
+//!     .add("macd", boxed(Macd::new(12, 26, 9).unwrap()))
+//!     .add_spec("atr", &IndicatorSpec::new("atr").param("period", 14.0))
+//!     .unwrap();
+//!
+//! let matrix = features.build(&candles).unwrap();
+//! assert_eq!(matrix.columns(), ["rsi", "macd_macd", "macd_signal", "macd_histogram", "atr"]);
+//! // Leading rows where any indicator was still warming up are dropped.
+//! assert_eq!(matrix.nrows(), 60 - 14);
+//! assert_eq!(matrix.timestamps()[0], 14);
+//!
+//! let x = matrix.to_array();
+//! assert_eq!(x.shape(), &[46, 5]);
+//! ```
+//!
+//! Column names are the registration name for single-valued indicators and
+//! `<name>_<field>` for multi-output ones, with the field names of
+//! [`IndicatorValue::fields`](crate::indicators::IndicatorValue::fields).
+//! Every value in a row is computed from that candle and earlier ones, so
+//! the matrix has no look-ahead; shift targets forward yourself when
+//! labelling.
+
+use ndarray::Array2;
+
+use crate::indicators::{AnyIndicator, Candle, IndicatorError, IndicatorSpec};
+
+/// Which rows [`FeatureSet::build`] keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trim {
+    /// Keep every row; missing values are `NaN`.
+    None,
+    /// Drop the leading rows until every column has a value.
+    #[default]
+    Warmup,
+    /// Drop every row with a missing, `NaN` or infinite value.
+    NonFinite,
+}
+
+/// Indicators evaluated together into a [`FeatureMatrix`]. See the
+/// [module docs](self).
+pub struct FeatureSet {
+    indicators: Vec<(String, Box<dyn AnyIndicator>)>,
+    trim: Trim,
+}
+
+impl Default for FeatureSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureSet {
+    /// Create an empty set that trims warm-up rows.
+    pub fn new() -> Self {
+        Self {
+            indicators: Vec::new(),
+            trim: Trim::default(),
+        }
+    }
+
+    /// Choose which rows to keep.
+    pub fn with_trim(mut self, trim: Trim) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Append an indicator; its columns follow those already added.
+    pub fn add(&mut self, name: &str, indicator: Box<dyn AnyIndicator>) -> &mut Self {
+        self.indicators.push((name.to_string(), indicator));
+        self
+    }
+
+    /// Append the indicator described by `spec`, e.g. one loaded from a
+    /// config file.
+    pub fn add_spec(
+        &mut self,
+        name: &str,
+        spec: &IndicatorSpec,
+    ) -> Result<&mut Self, IndicatorError> {
+        Ok(self.add(name, spec.build()?))
+    }
+
+    /// Number of indicators in the set.
+    pub fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    /// Whether the set has no indicators.
+    pub fn is_empty(&self) -> bool {
+        self.indicators.is_empty()
+    }
+
+    /// Reset every indicator and evaluate the set over `candles`
+    ///
+    /// # Returns
+    /// * `Result<FeatureMatrix, IndicatorError>` - The matrix, or the first
+    ///   indicator error; an empty set is an invalid parameter
+    pub fn build(&mut self, candles: &[Candle]) -> Result<FeatureMatrix, IndicatorError> {
+        if self.indicators.is_empty() {
+            return Err(IndicatorError::invalid_parameter(
+                "features",
+                0,
+                "must contain at least one indicator",
+            ));
+        }
+
+        let mut columns = Vec::new();
+        let mut series: Vec<Vec<f64>> = Vec::new();
+        for (name, indicator) in &mut self.indicators {
+            indicator.reset();
+            let outputs = candles
+                .iter()
+                .map(|&candle| indicator.next(candle))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // The field layout comes from the first value; a series that
+            // never warms up still contributes a single column.
+            let fields: Vec<&str> = outputs
+                .iter()
+                .flatten()
+                .next()
+                .map(|v| v.fields().into_iter().map(|(field, _)| field).collect())
+                .unwrap_or_else(|| vec!["value"]);
+            let single = fields.len() == 1;
+            for (j, field) in fields.iter().enumerate() {
+                columns.push(if single {
+                    name.clone()
+                } else {
+                    format!("{name}_{field}")
+                });
+                series.push(
+                    outputs
+                        .iter()
+                        .map(|v| v.map_or(f64::NAN, |v| v.fields()[j].1))
+                        .collect(),
+                );
+            }
+        }
+
+        let keep: Vec<usize> = match self.trim {
+            Trim::None => (0..candles.len()).collect(),
+            Trim::Warmup => {
+                let start = (0..candles.len())
+                    .find(|&row| series.iter().all(|s| !s[row].is_nan()))
+                    .unwrap_or(candles.len());
+                (start..candles.len()).collect()
+            }
+            Trim::NonFinite => (0..candles.len())
+                .filter(|&row| series.iter().all(|s| s[row].is_finite()))
+                .collect(),
+        };
+
+        let mut data = Vec::with_capacity(keep.len() * columns.len());
+        for &row in &keep {
+            data.extend(series.iter().map(|s| s[row]));
+        }
+        Ok(FeatureMatrix {
+            timestamps: keep.iter().map(|&row| candles[row].timestamp).collect(),
+            columns,
+            data,
+        })
+    }
+}
+
+/// Row-major matrix of features with named columns and one timestamp per
+/// row.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureMatrix {
+    columns: Vec<String>,
+    timestamps: Vec<u64>,
+    data: Vec<f64>,
+}
+
+impl FeatureMatrix {
+    /// Column names, in the order the indicators were added.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Timestamp of the candle behind each row.
+    pub fn timestamps(&self) -> &[u64] {
+        &self.timestamps
+    }
+
+    /// Number of rows.
+    pub fn nrows(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// Number of columns.
+    pub fn ncols(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// The values of row `index`.
+    pub fn row(&self, index: usize) -> Option<&[f64]> {
+        let n = self.ncols();
+        self.data.get(index * n..(index + 1) * n)
+    }
+
+    /// The values of the column called `name`.
+    pub fn column(&self, name: &str) -> Option<Vec<f64>> {
+        let j = self.columns.iter().position(|c| c == name)?;
+        Some(
+            self.data
+                .iter()
+                .skip(j)
+                .step_by(self.ncols())
+                .copied()
+                .collect(),
+        )
+    }
+
+    /// The rows as nested vectors.
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        (0..self.nrows())
+            .filter_map(|i| self.row(i).map(<[f64]>::to_vec))
+            .collect()
+    }
+
+    /// The matrix as an `nrows × ncols` array.
+    pub fn to_array(&self) -> Array2<f64> {
+        Array2::from_shape_vec((self.nrows(), self.ncols()), self.data.clone())
+            .expect("data holds nrows * ncols values")
+    }
+}
+
+#[cfg(all(test, feature = "trend", feature = "momentum"))]
+mod tests {
+    use super::*;
+    use crate::indicators::any_indicator::boxed;
+    use crate::indicators::{Macd, Rsi, Sma};
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                timestamp: 100 + i as u64,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_columns_align_with_indicators() {
+        let data = candles(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let mut features = FeatureSet::new();
+        features
+            .add("sma2", boxed(Sma::new(2).unwrap()))
+            .add("sma4", boxed(Sma::new(4).unwrap()));
+        let matrix = features.build(&data).unwrap();
+
+        assert_eq!(matrix.columns(), ["sma2", "sma4"]);
+        assert_eq!(matrix.timestamps(), [103, 104, 105]);
+        assert_eq!(matrix.row(0), Some(&[3.5, 2.5][..]));
+        assert_eq!(matrix.column("sma4"), Some(vec![2.5, 3.5, 4.5]));
+        assert_eq!(matrix.to_rows()[2], vec![5.5, 4.5]);
+        assert_eq!(matrix.to_array()[[1, 0]], 4.5);
+        assert_eq!(matrix.row(3), None);
+    }
+
+    #[test]
+    fn test_trim_modes() {
+        let data = candles(&[1.0, 2.0, 3.0, 4.0]);
+        let build = |trim| {
+            let mut features = FeatureSet::new().with_trim(trim);
+            features.add("sma", boxed(Sma::new(3).unwrap()));
+            features.build(&data).unwrap()
+        };
+
+        let all = build(Trim::None);
+        assert_eq!(all.nrows(), 4);
+        assert!(all.column("sma").unwrap()[1].is_nan());
+        assert_eq!(build(Trim::Warmup).nrows(), 2);
+        assert_eq!(build(Trim::NonFinite).nrows(), 2);
+    }
+
+    #[test]
+    fn test_multi_output_and_errors() {
+        let data = candles(&(0..40).map(|i| 50.0 + (i as f64).sin()).collect::<Vec<_>>());
+        let mut features = FeatureSet::new();
+        features
+            .add("macd", boxed(Macd::new(3, 6, 2).unwrap()))
+            .add("rsi", boxed(Rsi::new(5).unwrap()));
+        let matrix = features.build(&data).unwrap();
+        assert_eq!(
+            matrix.columns(),
+            ["macd_macd", "macd_signal", "macd_histogram", "rsi"]
+        );
+        assert_eq!(matrix.ncols(), 4);
+        assert!(matrix.to_rows().iter().flatten().all(|v| v.is_finite()));
+
+        assert!(FeatureSet::new().build(&data).is_err());
+        assert!(features
+            .add_spec("bad", &IndicatorSpec::new("sma").param("period", 0.0))
+            .is_err());
+        assert_eq!(features.len(), 2);
+    }
+}
@@ -0,0 +1,536 @@
+//! # WebSocket kline feeds
+//!
+//! Connects to a WebSocket kline (candlestick) feed, turns its JSON
+//! messages into [`Candle`]s and drives an [`IndicatorSet`] with them.
+//!
+//! - [`KlineMapping`] says where each field lives in a message, as JSON
+//!   pointers. Values may be JSON numbers or numeric strings.
+//!   [`KlineMapping::binance`] is a ready-made preset.
+//! - [`connect`] opens the socket (`ws://` or `wss://`) and yields a
+//!   [`Stream`] of [`Kline`]s, skipping control frames and messages that
+//!   are not klines (subscription acks, heartbeats).
+//! - [`KlineDriver`] advances the indicators once per **closed** candle.
+//!   Updates to the still-open candle are reported as
+//!   [`FeedEvent::Partial`] without touching indicator state. Feeds with
+//!   no "closed" flag are handled too: a candle is considered closed when
+//!   the first message of the next one arrives.
+//!
+//! Available with the `websocket` feature.
+//!
+//! ```
+//! use rsta::feed::{FeedEvent, KlineDriver, KlineMapping};
+//! use rsta::indicators::indicator_set::IndicatorSet;
+//! use rsta::indicators::volatility::Atr;
+//! use rsta::indicators::Candle;
+//!
+//! let mut set = IndicatorSet::<Candle, f64>::new();
+//! set.add("atr", Box::new(Atr::new(1).unwrap()));
+//! let mut driver = KlineDriver::new(KlineMapping::binance(), set);
+//!
+//! let msg = r#"{"e":"kline","k":{"t":1700000000000,"o":"100.0","h":"101.5",
+//!     "l":"99.0","c":"101.0","v":"12.5","x":true}}"#;
+//! match &driver.handle_text(msg).unwrap()[..] {
+//!     [FeedEvent::Closed { candle, outputs }] => {
+//!         assert_eq!(candle.close, 101.0);
+//!         assert_eq!(outputs["atr"], Some(2.5));
+//!     }
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use futures_util::StreamExt;
+use serde_json::Value;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use crate::indicators::indicator_set::IndicatorSet;
+use crate::indicators::{Candle, IndicatorError};
+
+/// Errors emitted by the feed module.
+#[derive(Debug, thiserror::Error)]
+pub enum FeedError {
+    /// WebSocket transport error (boxed: the tungstenite error is large).
+    #[error("WebSocket error: {0}")]
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
+
+    /// Message is not valid JSON.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// A kline field is missing or not numeric.
+    #[error("Parse error: {0}")]
+    Parse(String),
+
+    /// Indicator-level error while processing a closed candle.
+    #[error("Indicator error: {0}")]
+    Indicator(#[from] IndicatorError),
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for FeedError {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        FeedError::WebSocket(Box::new(e))
+    }
+}
+
+/// One kline message converted to a candle.
+#[derive(Debug, Clone, Copy)]
+pub struct Kline {
+    /// The candle as of this message.
+    pub candle: Candle,
+    /// `true` if the feed marked the candle as final.
+    pub closed: bool,
+}
+
+/// Where the kline fields live in a JSON message, as JSON pointers
+/// (RFC 6901, e.g. `"/k/o"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KlineMapping {
+    envelope: Option<String>,
+    open_time: String,
+    open: String,
+    high: String,
+    low: String,
+    close: String,
+    volume: String,
+    closed: Option<String>,
+}
+
+impl KlineMapping {
+    /// Mapping from the pointers of each field. Without a
+    /// [closed flag](Self::with_closed_flag), candles close when the next
+    /// one starts.
+    pub fn new(
+        open_time: &str,
+        open: &str,
+        high: &str,
+        low: &str,
+        close: &str,
+        volume: &str,
+    ) -> Self {
+        Self {
+            envelope: None,
+            open_time: open_time.to_string(),
+            open: open.to_string(),
+            high: high.to_string(),
+            low: low.to_string(),
+            close: close.to_string(),
+            volume: volume.to_string(),
+            closed: None,
+        }
+    }
+
+    /// Boolean field marking the candle as final.
+    pub fn with_closed_flag(mut self, pointer: &str) -> Self {
+        self.closed = Some(pointer.to_string());
+        self
+    }
+
+    /// When a message has an object at `pointer`, read the fields from
+    /// there (e.g. `"/data"` for multiplexed streams).
+    pub fn with_envelope(mut self, pointer: &str) -> Self {
+        self.envelope = Some(pointer.to_string());
+        self
+    }
+
+    /// Binance spot/futures kline streams, raw (`/ws/<symbol>@kline_<i>`)
+    /// or combined (`/stream?streams=...`). Timestamps are the candle open
+    /// time in milliseconds.
+    pub fn binance() -> Self {
+        Self::new("/k/t", "/k/o", "/k/h", "/k/l", "/k/c", "/k/v")
+            .with_closed_flag("/k/x")
+            .with_envelope("/data")
+    }
+
+    /// Parse one text message. `Ok(None)` if it is not a kline (the
+    /// open-time field is absent).
+    pub fn parse(&self, text: &str) -> Result<Option<Kline>, FeedError> {
+        let root: Value = serde_json::from_str(text)?;
+        self.parse_value(&root)
+    }
+
+    /// [`parse`](Self::parse) on an already decoded message.
+    pub fn parse_value(&self, root: &Value) -> Result<Option<Kline>, FeedError> {
+        let msg = self
+            .envelope
+            .as_deref()
+            .and_then(|p| root.pointer(p))
+            .filter(|v| v.is_object())
+            .unwrap_or(root);
+        let Some(open_time) = msg.pointer(&self.open_time) else {
+            return Ok(None);
+        };
+        let timestamp = match open_time {
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| FeedError::Parse(format!("invalid open time {open_time}")))?;
+
+        let field = |pointer: &str| -> Result<f64, FeedError> {
+            let value = msg
+                .pointer(pointer)
+                .ok_or_else(|| FeedError::Parse(format!("missing field {pointer}")))?;
+            match value {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            }
+            .ok_or_else(|| FeedError::Parse(format!("invalid number at {pointer}: {value}")))
+        };
+        let candle = Candle {
+            timestamp,
+            open: field(&self.open)?,
+            high: field(&self.high)?,
+            low: field(&self.low)?,
+            close: field(&self.close)?,
+            volume: field(&self.volume)?,
+        };
+        let closed = match &self.closed {
+            Some(pointer) => msg
+                .pointer(pointer)
+                .and_then(Value::as_bool)
+                .ok_or_else(|| FeedError::Parse(format!("missing closed flag {pointer}")))?,
+            None => false,
+        };
+        Ok(Some(Kline { candle, closed }))
+    }
+
+    fn has_closed_flag(&self) -> bool {
+        self.closed.is_some()
+    }
+}
+
+/// URL of a raw Binance spot kline stream, e.g.
+/// `binance_kline_url("BTCUSDT", "1m")`.
+pub fn binance_kline_url(symbol: &str, interval: &str) -> String {
+    format!(
+        "wss://stream.binance.com:9443/ws/{}@kline_{}",
+        symbol.to_lowercase(),
+        interval
+    )
+}
+
+/// Output of [`KlineDriver`].
+#[derive(Debug, Clone)]
+pub enum FeedEvent<O> {
+    /// Update of the candle still in progress; indicators not advanced.
+    Partial(Candle),
+    /// A candle closed and every indicator was fed with it.
+    Closed {
+        /// The final candle.
+        candle: Candle,
+        /// Latest output of each indicator, keyed by registration name.
+        outputs: BTreeMap<String, Option<O>>,
+    },
+}
+
+/// Feeds closed candles from a kline feed into an [`IndicatorSet`].
+pub struct KlineDriver<O> {
+    mapping: KlineMapping,
+    set: IndicatorSet<Candle, O>,
+    pending: Option<Candle>,
+    last_closed: Option<u64>,
+}
+
+impl<O> KlineDriver<O> {
+    /// Driver for messages described by `mapping`.
+    pub fn new(mapping: KlineMapping, set: IndicatorSet<Candle, O>) -> Self {
+        Self {
+            mapping,
+            set,
+            pending: None,
+            last_closed: None,
+        }
+    }
+
+    /// The message mapping.
+    pub fn mapping(&self) -> &KlineMapping {
+        &self.mapping
+    }
+
+    /// The driven indicators.
+    pub fn set(&self) -> &IndicatorSet<Candle, O> {
+        &self.set
+    }
+
+    /// `true` if closes are detected from the feed's flag rather than from
+    /// the next candle starting.
+    pub fn uses_closed_flag(&self) -> bool {
+        self.mapping.has_closed_flag()
+    }
+
+    /// The latest update of the candle in progress, if any.
+    pub fn pending(&self) -> Option<&Candle> {
+        self.pending.as_ref()
+    }
+
+    /// Process one kline. Returns up to two events: the close of the
+    /// previous candle (when the feed moved on without flagging it) and
+    /// the update itself. Updates to an already-closed candle, or to one
+    /// older than the pending candle, are ignored.
+    pub fn handle_kline(&mut self, kline: Kline) -> Result<Vec<FeedEvent<O>>, IndicatorError> {
+        let ts = kline.candle.timestamp;
+        if self.last_closed.is_some_and(|last| ts <= last)
+            || self.pending.is_some_and(|pending| ts < pending.timestamp)
+        {
+            return Ok(Vec::new());
+        }
+
+        let mut events = Vec::new();
+        if let Some(prev) = self.pending.take() {
+            if prev.timestamp < ts {
+                events.push(self.close(prev)?);
+            }
+        }
+        if kline.closed {
+            events.push(self.close(kline.candle)?);
+        } else {
+            self.pending = Some(kline.candle);
+            events.push(FeedEvent::Partial(kline.candle));
+        }
+        Ok(events)
+    }
+
+    /// Parse and process one text message.
+    pub fn handle_text(&mut self, text: &str) -> Result<Vec<FeedEvent<O>>, FeedError> {
+        match self.mapping.parse(text)? {
+            Some(kline) => Ok(self.handle_kline(kline)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Consume `feed` until it ends, calling `on_event` for every event.
+    /// Stops at the first error.
+    pub async fn run<S, F>(&mut self, mut feed: S, mut on_event: F) -> Result<(), FeedError>
+    where
+        S: Stream<Item = Result<Kline, FeedError>> + Unpin,
+        F: FnMut(FeedEvent<O>),
+    {
+        while let Some(kline) = feed.next().await {
+            for event in self.handle_kline(kline?)? {
+                on_event(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget the pending candle and reset every indicator.
+    pub fn reset(&mut self) {
+        self.pending = None;
+        self.last_closed = None;
+        self.set.reset();
+    }
+
+    fn close(&mut self, candle: Candle) -> Result<FeedEvent<O>, IndicatorError> {
+        let outputs = self.set.next(candle)?;
+        self.last_closed = Some(candle.timestamp);
+        Ok(FeedEvent::Closed { candle, outputs })
+    }
+}
+
+/// Stream of klines from a WebSocket connection, returned by [`connect`].
+pub struct KlineStream {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mapping: KlineMapping,
+}
+
+impl KlineStream {
+    /// Send a text frame, e.g. a subscription request.
+    pub async fn send_text(&mut self, text: &str) -> Result<(), FeedError> {
+        use futures_util::SinkExt;
+        self.socket.send(Message::text(text)).await?;
+        Ok(())
+    }
+}
+
+impl Stream for KlineStream {
+    type Item = Result<Kline, FeedError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let message = match std::task::ready!(this.socket.poll_next_unpin(cx)) {
+                None => return Poll::Ready(None),
+                Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                Some(Ok(message)) => message,
+            };
+            let parsed = match message {
+                Message::Text(text) => this.mapping.parse(text.as_str()),
+                Message::Binary(bytes) => match std::str::from_utf8(&bytes) {
+                    Ok(text) => this.mapping.parse(text),
+                    // Not a JSON kline; ignore it like any other non-kline.
+                    Err(_) => Ok(None),
+                },
+                Message::Close(_) => return Poll::Ready(None),
+                // Pings are answered by tungstenite itself.
+                _ => Ok(None),
+            };
+            match parsed {
+                Ok(Some(kline)) => return Poll::Ready(Some(Ok(kline))),
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+/// Open a WebSocket connection to `url` and stream its klines.
+pub async fn connect(url: &str, mapping: KlineMapping) -> Result<KlineStream, FeedError> {
+    let (socket, _) = tokio_tungstenite::connect_async(url).await?;
+    Ok(KlineStream { socket, mapping })
+}
+
+#[cfg(all(test, feature = "trend"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::Sma;
+
+    fn driver(mapping: KlineMapping) -> KlineDriver<f64> {
+        let mut set = IndicatorSet::<Candle, f64>::new();
+        set.add("sma", Box::new(Sma::new(2).unwrap()));
+        KlineDriver::new(mapping, set)
+    }
+
+    fn binance_msg(t: u64, close: f64, closed: bool) -> String {
+        format!(
+            r#"{{"e":"kline","E":1,"s":"BTCUSDT","k":{{"t":{t},"o":"{close}","h":"{close}",
+            "l":"{close}","c":"{close}","v":"1.0","x":{closed}}}}}"#
+        )
+    }
+
+    #[test]
+    fn binance_raw_and_combined_messages() {
+        let m = KlineMapping::binance();
+        let raw = m.parse(&binance_msg(60_000, 10.5, false)).unwrap().unwrap();
+        assert_eq!(raw.candle.timestamp, 60_000);
+        assert_eq!(raw.candle.close, 10.5);
+        assert!(!raw.closed);
+
+        let combined = format!(
+            r#"{{"stream":"btcusdt@kline_1m","data":{}}}"#,
+            binance_msg(120_000, 11.0, true)
+        );
+        let k = m.parse(&combined).unwrap().unwrap();
+        assert_eq!((k.candle.timestamp, k.closed), (120_000, true));
+
+        // Subscription acks are not klines.
+        assert!(m.parse(r#"{"result":null,"id":1}"#).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let m = KlineMapping::binance();
+        assert!(matches!(m.parse("not json"), Err(FeedError::Json(_))));
+        let bad = r#"{"k":{"t":1,"o":"x","h":"1","l":"1","c":"1","v":"1","x":true}}"#;
+        assert!(matches!(m.parse(bad), Err(FeedError::Parse(_))));
+        let no_flag = r#"{"k":{"t":1,"o":1,"h":1,"l":1,"c":1,"v":1}}"#;
+        assert!(matches!(m.parse(no_flag), Err(FeedError::Parse(_))));
+    }
+
+    #[test]
+    fn only_closed_candles_advance_indicators() {
+        let mut d = driver(KlineMapping::binance());
+        assert!(d.uses_closed_flag());
+        let ev = d.handle_text(&binance_msg(0, 10.0, false)).unwrap();
+        assert!(matches!(ev[..], [FeedEvent::Partial(c)] if c.close == 10.0));
+        assert_eq!(d.pending().map(|c| c.close), Some(10.0));
+
+        let ev = d.handle_text(&binance_msg(0, 12.0, true)).unwrap();
+        assert!(matches!(&ev[..], [FeedEvent::Closed { outputs, .. }] if outputs["sma"].is_none()));
+        assert!(d.pending().is_none());
+
+        // A late duplicate of the closed candle is ignored.
+        assert!(d
+            .handle_text(&binance_msg(0, 99.0, true))
+            .unwrap()
+            .is_empty());
+
+        d.handle_text(&binance_msg(60, 13.0, false)).unwrap();
+        let ev = d.handle_text(&binance_msg(60, 14.0, true)).unwrap();
+        match &ev[..] {
+            [FeedEvent::Closed { candle, outputs }] => {
+                assert_eq!(candle.close, 14.0);
+                assert_eq!(outputs["sma"], Some(13.0));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn next_candle_closes_previous_without_flag() {
+        let mapping = KlineMapping::new("/t", "/o", "/h", "/l", "/c", "/v");
+        let mut d = driver(mapping);
+        assert!(!d.uses_closed_flag());
+        let msg = |t: u64, c: f64| format!(r#"{{"t":{t},"o":{c},"h":{c},"l":{c},"c":{c},"v":1}}"#);
+        d.handle_text(&msg(1, 1.0)).unwrap();
+        d.handle_text(&msg(1, 2.0)).unwrap();
+        let ev = d.handle_text(&msg(2, 5.0)).unwrap();
+        assert_eq!(ev.len(), 2);
+        assert!(matches!(ev[0], FeedEvent::Closed { candle, .. } if candle.close == 2.0));
+        assert!(matches!(ev[1], FeedEvent::Partial(c) if c.close == 5.0));
+
+        let ev = d.handle_text(&msg(3, 6.0)).unwrap();
+        assert!(matches!(&ev[0], FeedEvent::Closed { outputs, .. } if outputs["sma"] == Some(3.5)));
+
+        // A kline older than the pending one leaves it in place.
+        d.handle_text(&msg(5, 7.0)).unwrap();
+        assert!(d.handle_text(&msg(4, 9.0)).unwrap().is_empty());
+        assert_eq!(d.pending().map(|c| (c.timestamp, c.close)), Some((5, 7.0)));
+        let ev = d.handle_text(&msg(6, 8.0)).unwrap();
+        assert!(matches!(ev[0], FeedEvent::Closed { candle, .. } if candle.timestamp == 5));
+
+        d.reset();
+        assert!(d.pending().is_none());
+    }
+
+    #[test]
+    fn binance_url() {
+        assert_eq!(
+            binance_kline_url("BTCUSDT", "1m"),
+            "wss://stream.binance.com:9443/ws/btcusdt@kline_1m"
+        );
+    }
+
+    #[tokio::test]
+    async fn drives_indicators_from_a_websocket() {
+        use futures_util::SinkExt;
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.send(Message::text(r#"{"result":null,"id":1}"#))
+                .await
+                .unwrap();
+            for (t, close, closed) in [(0, 1.0, false), (0, 2.0, true), (60, 4.0, true)] {
+                ws.send(Message::text(binance_msg(t, close, closed)))
+                    .await
+                    .unwrap();
+            }
+            ws.close(None).await.unwrap();
+        });
+
+        let feed = connect(&format!("ws://{addr}"), KlineMapping::binance())
+            .await
+            .unwrap();
+        let mut d = driver(KlineMapping::binance());
+        let mut events = Vec::new();
+        d.run(feed, |e| events.push(e)).await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], FeedEvent::Partial(_)));
+        match &events[2] {
+            FeedEvent::Closed { outputs, .. } => assert_eq!(outputs["sma"], Some(3.0)),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,542 @@
+//! Type-erased indicators
+//!
+//! Every indicator has its own output type (`f64`, [`MacdResult`],
+//! [`BollingerBandsResult`], ...), which makes it impossible to keep a mixed
+//! collection in a plain `Vec`. [`AnyIndicator`] erases that difference:
+//! all indicators take [`Candle`] input and produce an [`IndicatorValue`],
+//! so UIs and config-driven code can hold them as `Box<dyn AnyIndicator>`.
+//!
+//! [`boxed`] erases any candle indicator whose output converts into an
+//! `IndicatorValue`, which covers every built-in one. Price-based
+//! indicators use the candle close, exactly as their own
+//! `Indicator<Candle, _>` implementation does.
+//!
+//! ```
+//! use rsta::indicators::any_indicator::{boxed, AnyIndicator, IndicatorValue};
+//! use rsta::indicators::momentum::Rsi;
+//! use rsta::indicators::trend::Macd;
+//! use rsta::indicators::volatility::BollingerBands;
+//! use rsta::indicators::Candle;
+//!
+//! let mut indicators: Vec<Box<dyn AnyIndicator>> = vec![
+//!     boxed(Rsi::new(3).unwrap()),
+//!     boxed(Macd::new(2, 4, 2).unwrap()),
+//!     boxed(BollingerBands::new(3, 2.0).unwrap()),
+//! ];
+//!
+//! let candles: Vec<Candle> = (0..10)
+//!     .map(|i| {
+//!         let c = 10.0 + (i % 3) as f64;
+//!         Candle { timestamp: i, open: c, high: c + 1.0, low: c - 1.0, close: c, volume: 100.0 }
+//!     })
+//!     .collect();
+//!
+//! for indicator in indicators.iter_mut() {
+//!     let values = indicator.calculate(&candles).unwrap();
+//!     match values.last().unwrap() {
+//!         IndicatorValue::Single(v) => println!("{}: {v}", indicator.name()),
+//!         IndicatorValue::Bands { upper, lower, .. } => {
+//!             println!("{}: {lower}..{upper}", indicator.name())
+//!         }
+//!         other => println!("{}: {:?}", indicator.name(), other.fields()),
+//!     }
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+#[cfg(feature = "momentum")]
+use crate::indicators::momentum::StochasticResult;
+#[cfg(feature = "trend")]
+use crate::indicators::trend::{AdxResult, IchimokuResult, MacdResult};
+#[cfg(feature = "volatility")]
+use crate::indicators::volatility::{BollingerBandsResult, DonchianResult, KeltnerChannelsResult};
+use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::instrument;
+
+/// Unified output of an [`AnyIndicator`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorValue {
+    /// Scalar output (moving averages, RSI, ATR, OBV, ...).
+    Single(f64),
+    /// Channel / band output (Bollinger, Keltner, Donchian).
+    Bands {
+        /// Upper band.
+        upper: f64,
+        /// Middle line.
+        middle: f64,
+        /// Lower band.
+        lower: f64,
+    },
+    /// Stochastic oscillator %K / %D.
+    Stochastic {
+        /// %K line.
+        k: f64,
+        /// %D line.
+        d: f64,
+    },
+    /// MACD line, signal line and histogram.
+    Macd {
+        /// MACD line.
+        macd: f64,
+        /// Signal line.
+        signal: f64,
+        /// Histogram (`macd - signal`).
+        histogram: f64,
+    },
+    /// ADX with its directional indicators.
+    Adx {
+        /// Average directional index.
+        adx: f64,
+        /// +DI.
+        plus_di: f64,
+        /// -DI.
+        minus_di: f64,
+    },
+    /// Ichimoku cloud lines.
+    Ichimoku {
+        /// Tenkan-sen (conversion line).
+        tenkan: f64,
+        /// Kijun-sen (base line).
+        kijun: f64,
+        /// Senkou span A.
+        senkou_a: f64,
+        /// Senkou span B.
+        senkou_b: f64,
+        /// Chikou span.
+        chikou: f64,
+    },
+}
+
+impl IndicatorValue {
+    /// The scalar value, if this is [`IndicatorValue::Single`].
+    pub fn as_single(&self) -> Option<f64> {
+        match self {
+            IndicatorValue::Single(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// The headline value of the output: the scalar itself, the middle
+    /// band, %K, the MACD line, ADX, or the Tenkan-sen.
+    pub fn primary(&self) -> f64 {
+        match *self {
+            IndicatorValue::Single(v) => v,
+            IndicatorValue::Bands { middle, .. } => middle,
+            IndicatorValue::Stochastic { k, .. } => k,
+            IndicatorValue::Macd { macd, .. } => macd,
+            IndicatorValue::Adx { adx, .. } => adx,
+            IndicatorValue::Ichimoku { tenkan, .. } => tenkan,
+        }
+    }
+
+    /// Every component as `(name, value)` pairs, in declaration order.
+    /// Useful for rendering or serializing without matching on the variant.
+    pub fn fields(&self) -> Vec<(&'static str, f64)> {
+        match *self {
+            IndicatorValue::Single(v) => vec![("value", v)],
+            IndicatorValue::Bands {
+                upper,
+                middle,
+                lower,
+            } => vec![("upper", upper), ("middle", middle), ("lower", lower)],
+            IndicatorValue::Stochastic { k, d } => vec![("k", k), ("d", d)],
+            IndicatorValue::Macd {
+                macd,
+                signal,
+                histogram,
+            } => vec![("macd", macd), ("signal", signal), ("histogram", histogram)],
+            IndicatorValue::Adx {
+                adx,
+                plus_di,
+                minus_di,
+            } => vec![("adx", adx), ("plus_di", plus_di), ("minus_di", minus_di)],
+            IndicatorValue::Ichimoku {
+                tenkan,
+                kijun,
+                senkou_a,
+                senkou_b,
+                chikou,
+            } => vec![
+                ("tenkan", tenkan),
+                ("kijun", kijun),
+                ("senkou_a", senkou_a),
+                ("senkou_b", senkou_b),
+                ("chikou", chikou),
+            ],
+        }
+    }
+}
+
+impl From<f64> for IndicatorValue {
+    fn from(value: f64) -> Self {
+        IndicatorValue::Single(value)
+    }
+}
+
+#[cfg(feature = "volatility")]
+impl From<BollingerBandsResult> for IndicatorValue {
+    fn from(r: BollingerBandsResult) -> Self {
+        IndicatorValue::Bands {
+            upper: r.upper,
+            middle: r.middle,
+            lower: r.lower,
+        }
+    }
+}
+
+#[cfg(feature = "volatility")]
+impl From<KeltnerChannelsResult> for IndicatorValue {
+    fn from(r: KeltnerChannelsResult) -> Self {
+        IndicatorValue::Bands {
+            upper: r.upper,
+            middle: r.middle,
+            lower: r.lower,
+        }
+    }
+}
+
+#[cfg(feature = "volatility")]
+impl From<DonchianResult> for IndicatorValue {
+    fn from(r: DonchianResult) -> Self {
+        IndicatorValue::Bands {
+            upper: r.upper,
+            middle: r.middle,
+            lower: r.lower,
+        }
+    }
+}
+
+#[cfg(feature = "momentum")]
+impl From<StochasticResult> for IndicatorValue {
+    fn from(r: StochasticResult) -> Self {
+        IndicatorValue::Stochastic { k: r.k, d: r.d }
+    }
+}
+
+#[cfg(feature = "trend")]
+impl From<MacdResult> for IndicatorValue {
+    fn from(r: MacdResult) -> Self {
+        IndicatorValue::Macd {
+            macd: r.macd,
+            signal: r.signal,
+            histogram: r.histogram,
+        }
+    }
+}
+
+#[cfg(feature = "trend")]
+impl From<AdxResult> for IndicatorValue {
+    fn from(r: AdxResult) -> Self {
+        IndicatorValue::Adx {
+            adx: r.adx,
+            plus_di: r.plus_di,
+            minus_di: r.minus_di,
+        }
+    }
+}
+
+#[cfg(feature = "trend")]
+impl From<IchimokuResult> for IndicatorValue {
+    fn from(r: IchimokuResult) -> Self {
+        IndicatorValue::Ichimoku {
+            tenkan: r.tenkan,
+            kijun: r.kijun,
+            senkou_a: r.senkou_a,
+            senkou_b: r.senkou_b,
+            chikou: r.chikou,
+        }
+    }
+}
+
+/// Object-safe, output-erased view of an indicator over [`Candle`] input.
+///
+/// Mirrors [`Indicator`], with every output converted to an
+/// [`IndicatorValue`]. `Box<dyn AnyIndicator>` itself implements
+/// `Indicator<Candle, IndicatorValue>`, so erased indicators plug into
+/// [`IndicatorSet`](crate::indicators::IndicatorSet),
+/// [`Pipeline`](crate::indicators::Pipeline) and
+/// [`NanGuard`](crate::indicators::NanGuard).
+pub trait AnyIndicator: Send {
+    /// See [`Indicator::calculate`].
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<IndicatorValue>, IndicatorError>;
+
+    /// See [`Indicator::next`].
+    fn next(&mut self, value: Candle) -> Result<Option<IndicatorValue>, IndicatorError>;
+
+    /// See [`Indicator::reset`].
+    fn reset(&mut self);
+
+    /// See [`Indicator::current`].
+    fn current(&self) -> Option<&IndicatorValue>;
+
+    /// See [`Indicator::name`].
+    fn name(&self) -> &'static str;
+
+    /// See [`Indicator::period`].
+    fn period(&self) -> Option<usize>;
+
+    /// See [`Indicator::min_periods`].
+    fn min_periods(&self) -> usize;
+
+    /// See [`Indicator::output_offset`].
+    fn output_offset(&self) -> usize;
+}
+
+/// Adapter behind [`boxed`]: forwards to the wrapped indicator and converts
+/// its output.
+struct Erased<I, O> {
+    inner: I,
+    last: Option<IndicatorValue>,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<I, O> AnyIndicator for Erased<I, O>
+where
+    I: Indicator<Candle, O> + Send,
+    O: Into<IndicatorValue>,
+{
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<IndicatorValue>, IndicatorError> {
+        let _span = instrument::debug_span!(
+            "calculate",
+            indicator = self.inner.name(),
+            inputs = data.len()
+        );
+        let values: Vec<IndicatorValue> = match self.inner.calculate(data) {
+            Ok(values) => values.into_iter().map(Into::into).collect(),
+            Err(err) => {
+                instrument::debug!(error = %err, "calculation failed");
+                return Err(err);
+            }
+        };
+        instrument::debug!(outputs = values.len(), "calculated");
+        self.last = values.last().copied();
+        Ok(values)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<IndicatorValue>, IndicatorError> {
+        let value: Option<IndicatorValue> = match self.inner.next(candle) {
+            Ok(value) => value.map(Into::into),
+            Err(err) => {
+                instrument::debug!(indicator = self.inner.name(), timestamp = candle.timestamp, error = %err, "update failed");
+                return Err(err);
+            }
+        };
+        instrument::trace!(indicator = self.inner.name(), timestamp = candle.timestamp, value = ?value, "updated");
+        if value.is_some() {
+            self.last = value;
+        }
+        Ok(value)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&IndicatorValue> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        self.inner.output_offset()
+    }
+}
+
+/// Erase the output type of a candle indicator.
+///
+/// Works for every indicator whose output converts into an
+/// [`IndicatorValue`] — all built-in candle indicators, and any custom
+/// indicator with a matching `From` impl.
+pub fn boxed<I, O>(indicator: I) -> Box<dyn AnyIndicator>
+where
+    I: Indicator<Candle, O> + Send + 'static,
+    O: Into<IndicatorValue> + 'static,
+{
+    Box::new(Erased {
+        inner: indicator,
+        last: None,
+        _output: PhantomData,
+    })
+}
+
+impl Indicator<Candle, IndicatorValue> for Box<dyn AnyIndicator> {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<IndicatorValue>, IndicatorError> {
+        (**self).calculate(data)
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<IndicatorValue>, IndicatorError> {
+        (**self).next(value)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    fn current(&self) -> Option<&IndicatorValue> {
+        (**self).current()
+    }
+
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        (**self).period()
+    }
+
+    fn min_periods(&self) -> usize {
+        (**self).min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        (**self).output_offset()
+    }
+}
+
+#[cfg(all(test, feature = "momentum", feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::momentum::{Rsi, StochasticOscillator};
+    use crate::indicators::trend::{Adx, Ichimoku, Macd, Sma};
+    use crate::indicators::volatility::{BollingerBands, Donchian};
+    use crate::indicators::IndicatorSet;
+
+    fn candles(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 50.0 + (i as f64 * 0.7).sin() * 2.0;
+                Candle {
+                    timestamp: i as u64,
+                    open: c - 0.2,
+                    high: c + 1.0,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 500.0 + i as f64,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_heterogeneous_collection() {
+        let data = candles(40);
+        let mut indicators: Vec<Box<dyn AnyIndicator>> = vec![
+            boxed(Sma::new(5).unwrap()),
+            boxed(BollingerBands::new(5, 2.0).unwrap()),
+            boxed(StochasticOscillator::new(5, 3).unwrap()),
+            boxed(Macd::new(3, 6, 4).unwrap()),
+            boxed(Adx::new(5).unwrap()),
+            boxed(Ichimoku::new(3, 5, 8).unwrap()),
+        ];
+
+        for indicator in indicators.iter_mut() {
+            let values = indicator.calculate(&data).unwrap();
+            assert_eq!(
+                values.len(),
+                data.len() - indicator.output_offset(),
+                "{}",
+                indicator.name()
+            );
+        }
+
+        let last = |i: &mut Box<dyn AnyIndicator>| *i.calculate(&data).unwrap().last().unwrap();
+        assert!(matches!(
+            last(&mut indicators[0]),
+            IndicatorValue::Single(_)
+        ));
+        assert!(matches!(
+            last(&mut indicators[1]),
+            IndicatorValue::Bands { .. }
+        ));
+        assert!(matches!(
+            last(&mut indicators[2]),
+            IndicatorValue::Stochastic { .. }
+        ));
+        assert!(matches!(
+            last(&mut indicators[3]),
+            IndicatorValue::Macd { .. }
+        ));
+        assert!(matches!(
+            last(&mut indicators[4]),
+            IndicatorValue::Adx { .. }
+        ));
+        assert!(matches!(
+            last(&mut indicators[5]),
+            IndicatorValue::Ichimoku { .. }
+        ));
+    }
+
+    #[test]
+    fn test_erased_matches_concrete() {
+        let data = candles(30);
+        let mut concrete = BollingerBands::new(10, 2.0).unwrap();
+        let expected = <BollingerBands as Indicator<Candle, BollingerBandsResult>>::calculate(
+            &mut concrete,
+            &data,
+        )
+        .unwrap();
+
+        let mut erased: Box<dyn AnyIndicator> = boxed(BollingerBands::new(10, 2.0).unwrap());
+        assert_eq!(erased.name(), "BollingerBands");
+        assert_eq!(erased.min_periods(), 10);
+        let values = erased.calculate(&data).unwrap();
+        for (v, r) in values.iter().zip(&expected) {
+            assert_eq!(*v, IndicatorValue::from(*r));
+            assert_eq!(v.primary(), r.middle);
+        }
+    }
+
+    #[test]
+    fn test_streaming_and_reset() {
+        let data = candles(10);
+        let mut erased: Box<dyn AnyIndicator> = boxed(Sma::new(3).unwrap());
+        let streamed: Vec<_> = data
+            .iter()
+            .filter_map(|c| erased.next(*c).unwrap())
+            .collect();
+        assert_eq!(streamed, erased.calculate(&data).unwrap());
+
+        AnyIndicator::reset(erased.as_mut());
+        assert_eq!(erased.next(data[0]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_boxed_any_in_indicator_set() {
+        let data = candles(20);
+        let mut set = IndicatorSet::<Candle, IndicatorValue>::new();
+        set.add("rsi", Box::new(boxed(Rsi::new(5).unwrap())))
+            .add("donchian", Box::new(boxed(Donchian::new(5).unwrap())));
+        let out = set.calculate(&data).unwrap();
+        assert!(out["rsi"][19].unwrap().as_single().is_some());
+        assert_eq!(out["donchian"][19].unwrap().fields().len(), 3);
+    }
+
+    #[test]
+    fn test_value_accessors() {
+        let v = IndicatorValue::Macd {
+            macd: 1.0,
+            signal: 0.5,
+            histogram: 0.5,
+        };
+        assert_eq!(v.as_single(), None);
+        assert_eq!(v.primary(), 1.0);
+        assert_eq!(
+            v.fields(),
+            vec![("macd", 1.0), ("signal", 0.5), ("histogram", 0.5)]
+        );
+        assert_eq!(IndicatorValue::from(2.5).as_single(), Some(2.5));
+    }
+}
@@ -71,7 +71,7 @@ use super::traits::PriceDataAccessor;
 /// // The ATR values can be inspected
 /// println!("ATR value: {}", atr_values[0]); // First ATR value
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Candle {
     /// Timestamp (typically Unix timestamp in seconds)
     pub timestamp: u64,
@@ -0,0 +1,175 @@
+//! Indicator metadata for introspection
+//!
+//! A [`Descriptor`] lists what a configuration form needs to know about an
+//! indicator: its factory name, its parameters with their ranges and
+//! defaults, the names of its output fields, and the input it consumes.
+//! Every indicator known to the [`IndicatorFactory`] implements
+//! [`Describe`], and [`IndicatorFactory::descriptor`] looks one up by name,
+//! so a screener builder can render forms without hard-coding indicators.
+//!
+//! ```
+//! use rsta::indicators::descriptor::{Describe, InputKind, ParamKind};
+//! use rsta::indicators::trend::Macd;
+//! use rsta::indicators::IndicatorFactory;
+//!
+//! let macd = Macd::descriptor();
+//! assert_eq!(macd.name, "macd");
+//! assert_eq!(macd.input, InputKind::Price);
+//! assert_eq!(macd.outputs, &["macd", "signal", "histogram"]);
+//!
+//! let fast = &macd.params[0];
+//! assert_eq!((fast.name, fast.kind, fast.default), ("fast", ParamKind::Period, Some(12.0)));
+//! assert!(fast.accepts(5.0) && !fast.accepts(0.0) && !fast.accepts(2.5));
+//!
+//! // The factory resolves names and aliases to the same metadata.
+//! assert_eq!(IndicatorFactory::descriptor("MACD"), Some(macd));
+//! ```
+//!
+//! Parameter names are the keys accepted by
+//! [`IndicatorFactory::create`], and output names match
+//! [`IndicatorValue::fields`](crate::indicators::IndicatorValue::fields).
+//! Only per-parameter ranges are described; constraints between parameters
+//! (MACD's `fast < slow`, say) are still checked when the indicator is
+//! created.
+//!
+//! [`IndicatorFactory`]: crate::indicators::IndicatorFactory
+//! [`IndicatorFactory::descriptor`]: crate::indicators::IndicatorFactory::descriptor
+//! [`IndicatorFactory::create`]: crate::indicators::IndicatorFactory::create
+
+/// Data an indicator consumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputKind {
+    /// A price series: plain `f64` values, or the close of each
+    /// [`Candle`](crate::indicators::Candle).
+    Price,
+    /// Full OHLCV [`Candle`](crate::indicators::Candle)s.
+    Candle,
+}
+
+/// Value type of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParamKind {
+    /// A whole number of bars.
+    Period,
+    /// A real number.
+    Float,
+}
+
+/// A parameter of an indicator, with its valid range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParamDescriptor {
+    /// Parameter name, as accepted by the factory.
+    pub name: &'static str,
+    /// Value type.
+    pub kind: ParamKind,
+    /// Smallest valid value.
+    pub min: f64,
+    /// Whether `min` itself is invalid (for parameters that must be
+    /// positive).
+    pub min_exclusive: bool,
+    /// Largest valid value, if bounded.
+    pub max: Option<f64>,
+    /// Value used when the parameter is omitted; `None` if it is required.
+    pub default: Option<f64>,
+}
+
+impl ParamDescriptor {
+    /// A required period of at least `min` bars.
+    pub const fn period(name: &'static str, min: usize) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Period,
+            min: min as f64,
+            min_exclusive: false,
+            max: None,
+            default: None,
+        }
+    }
+
+    /// A required real parameter that must be greater than zero.
+    pub const fn positive(name: &'static str) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Float,
+            min: 0.0,
+            min_exclusive: true,
+            max: None,
+            default: None,
+        }
+    }
+
+    /// Make the parameter optional with `default` as its value.
+    pub const fn with_default(mut self, default: f64) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    /// Set the largest valid value.
+    pub const fn with_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Whether `value` is in range (and a whole number, for periods).
+    pub fn accepts(&self, value: f64) -> bool {
+        let above_min = if self.min_exclusive {
+            value > self.min
+        } else {
+            value >= self.min
+        };
+        let below_max = self.max.is_none_or(|max| value <= max);
+        let whole = self.kind == ParamKind::Float || value.fract() == 0.0;
+        value.is_finite() && above_min && below_max && whole
+    }
+}
+
+/// Metadata of an indicator. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Descriptor {
+    /// Canonical factory name (`"rsi"`).
+    pub name: &'static str,
+    /// Human-readable name (`"Relative Strength Index"`).
+    pub title: &'static str,
+    /// Data the indicator consumes.
+    pub input: InputKind,
+    /// Constructor parameters, in the order a form would show them.
+    pub params: &'static [ParamDescriptor],
+    /// Names of the output fields; `["value"]` for scalar outputs.
+    pub outputs: &'static [&'static str],
+}
+
+impl Descriptor {
+    /// Look up a parameter by name.
+    pub fn param(&self, name: &str) -> Option<&'static ParamDescriptor> {
+        self.params.iter().find(|p| p.name == name)
+    }
+}
+
+/// Indicators that can describe themselves.
+pub trait Describe {
+    /// Metadata of this indicator type.
+    fn descriptor() -> &'static Descriptor;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param_accepts() {
+        let period = ParamDescriptor::period("period", 2);
+        assert!(period.accepts(2.0) && period.accepts(200.0));
+        assert!(!period.accepts(1.0) && !period.accepts(2.5) && !period.accepts(f64::NAN));
+
+        let k = ParamDescriptor::positive("k")
+            .with_default(2.0)
+            .with_max(5.0);
+        assert_eq!(k.default, Some(2.0));
+        assert!(k.accepts(0.5) && k.accepts(5.0));
+        assert!(!k.accepts(0.0) && !k.accepts(5.5) && !k.accepts(f64::INFINITY));
+    }
+}
@@ -3,6 +3,8 @@
 //! This module defines the common error types used by all indicator implementations
 //! in the library.
 
+use std::fmt::Display;
+
 use thiserror::Error;
 
 /// Common error types for technical indicators
@@ -11,17 +13,23 @@ use thiserror::Error;
 /// technical indicators. All indicator methods that can fail return a `Result`
 /// with this error type.
 ///
+/// Variants carry structured fields so callers can branch on them instead of
+/// parsing messages. The enum is `#[non_exhaustive]`: match it with a
+/// wildcard arm.
+///
 /// # Examples
 ///
-/// ```rust,no_run
+/// ```rust
 /// use rsta::indicators::Sma;
 /// use rsta::indicators::IndicatorError;
-/// use rsta::indicators::Indicator;
 ///
 /// // Handle a parameter validation error
 /// match Sma::new(0) {
-///     Err(IndicatorError::InvalidParameter(msg)) => println!("Invalid parameter: {}", msg),
-///     _ => println!("Unexpected result"),
+///     Err(IndicatorError::InvalidParameter { name, value, .. }) => {
+///         assert_eq!(name, "period");
+///         assert_eq!(value, "0");
+///     }
+///     _ => panic!("Unexpected result"),
 /// }
 /// ```
 ///
@@ -34,23 +42,104 @@ use thiserror::Error;
 /// let mut sma = Sma::new(14).unwrap();
 /// let prices = vec![1.0, 2.0]; // Not enough data
 /// match sma.calculate(&prices) {
-///     Err(IndicatorError::InsufficientData(msg)) => println!("Not enough data: {}", msg),
-///     _ => println!("Unexpected result"),
+///     Err(IndicatorError::InsufficientData { required, provided }) => {
+///         assert_eq!((required, provided), (14, 2));
+///     }
+///     _ => panic!("Unexpected result"),
 /// }
 /// ```
 #[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum IndicatorError {
-    /// Error for invalid parameters (e.g., negative period, invalid multiplier)
-    #[error("Invalid parameter: {0}")]
-    InvalidParameter(String),
+    /// A parameter is out of range (e.g., zero period, negative multiplier)
+    #[error("Invalid parameter {name} = {value}: {constraint}")]
+    InvalidParameter {
+        /// Parameter name, as spelled in the constructor.
+        name: String,
+        /// The rejected value, formatted for display.
+        value: String,
+        /// The constraint the value broke (e.g. `"must be at least 1"`).
+        constraint: String,
+    },
+
+    /// Too few data points to perform the calculation
+    #[error("Insufficient data: {required} data point(s) required, {provided} provided")]
+    InsufficientData {
+        /// Minimum number of data points.
+        required: usize,
+        /// Number of data points supplied.
+        provided: usize,
+    },
 
-    /// Error for insufficient data to perform calculations
-    #[error("Insufficient data: {0}")]
-    InsufficientData(String),
+    /// A denominator was zero
+    ///
+    /// `index` follows the same convention as
+    /// [`InvalidInput`](IndicatorError::InvalidInput).
+    #[error("Division by zero in {context} at index {index}")]
+    DivisionByZero {
+        /// What was being divided (e.g. `"high - low range"`).
+        context: String,
+        /// Position of the bar that produced the zero.
+        index: usize,
+    },
 
-    /// Error during calculation (e.g., division by zero)
+    /// Any other failure during a calculation
     #[error("Calculation error: {0}")]
     CalculationError(String),
+
+    /// Error for an invalid input value (e.g., NaN or infinite price)
+    ///
+    /// `index` is the position of the offending value — within the slice
+    /// passed to `calculate()`, or the number of values previously fed to
+    /// `next()` when streaming.
+    #[error("Invalid input at index {index}: {reason}")]
+    InvalidInput {
+        /// Position of the offending value.
+        index: usize,
+        /// Why the value was rejected.
+        reason: String,
+    },
+}
+
+impl IndicatorError {
+    /// Build an [`InvalidParameter`](IndicatorError::InvalidParameter) error
+    ///
+    /// # Arguments
+    /// * `name` - Parameter name
+    /// * `value` - The rejected value
+    /// * `constraint` - The rule it broke, e.g. `"must be positive"`
+    pub fn invalid_parameter(
+        name: impl Into<String>,
+        value: impl Display,
+        constraint: impl Into<String>,
+    ) -> Self {
+        Self::InvalidParameter {
+            name: name.into(),
+            value: value.to_string(),
+            constraint: constraint.into(),
+        }
+    }
+
+    /// Build an [`InsufficientData`](IndicatorError::InsufficientData) error
+    ///
+    /// # Arguments
+    /// * `required` - Minimum number of data points
+    /// * `provided` - Number of data points supplied
+    pub fn insufficient_data(required: usize, provided: usize) -> Self {
+        Self::InsufficientData { required, provided }
+    }
+
+    /// Build a [`DivisionByZero`](IndicatorError::DivisionByZero) error
+    ///
+    /// # Arguments
+    /// * `context` - What was being divided
+    /// * `index` - Position of the offending bar
+    pub fn division_by_zero(context: impl Into<String>, index: usize) -> Self {
+        Self::DivisionByZero {
+            context: context.into(),
+            index,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -60,54 +149,91 @@ mod tests {
 
     #[test]
     fn test_invalid_parameter_error() {
-        // Create the error
-        let error = IndicatorError::InvalidParameter("Period must be greater than 0".to_string());
+        let error = IndicatorError::invalid_parameter("period", 0, "must be at least 1");
 
         // Test Debug trait
         assert!(format!("{:?}", error).contains("InvalidParameter"));
-        assert!(format!("{:?}", error).contains("Period must be greater than 0"));
+        assert!(format!("{:?}", error).contains("must be at least 1"));
 
         // Test Display trait (error message)
         assert_eq!(
             format!("{}", error),
-            "Invalid parameter: Period must be greater than 0"
+            "Invalid parameter period = 0: must be at least 1"
+        );
+        assert_eq!(
+            error,
+            IndicatorError::InvalidParameter {
+                name: "period".to_string(),
+                value: "0".to_string(),
+                constraint: "must be at least 1".to_string(),
+            }
         );
     }
 
     #[test]
     fn test_insufficient_data_error() {
-        // Create the error
-        let error = IndicatorError::InsufficientData("Need at least 14 data points".to_string());
+        let error = IndicatorError::insufficient_data(14, 3);
 
         // Test Debug trait
         assert!(format!("{:?}", error).contains("InsufficientData"));
-        assert!(format!("{:?}", error).contains("Need at least 14 data points"));
 
         // Test Display trait (error message)
         assert_eq!(
             format!("{}", error),
-            "Insufficient data: Need at least 14 data points"
+            "Insufficient data: 14 data point(s) required, 3 provided"
+        );
+    }
+
+    #[test]
+    fn test_division_by_zero_error() {
+        let error = IndicatorError::division_by_zero("volume sum", 4);
+
+        assert!(format!("{:?}", error).contains("DivisionByZero"));
+        assert_eq!(
+            format!("{}", error),
+            "Division by zero in volume sum at index 4"
         );
     }
 
     #[test]
     fn test_calculation_error() {
         // Create the error
-        let error = IndicatorError::CalculationError("Division by zero".to_string());
+        let error = IndicatorError::CalculationError("Out of order candle".to_string());
 
         // Test Debug trait
         assert!(format!("{:?}", error).contains("CalculationError"));
-        assert!(format!("{:?}", error).contains("Division by zero"));
+        assert!(format!("{:?}", error).contains("Out of order candle"));
 
         // Test Display trait (error message)
-        assert_eq!(format!("{}", error), "Calculation error: Division by zero");
+        assert_eq!(
+            format!("{}", error),
+            "Calculation error: Out of order candle"
+        );
+    }
+
+    #[test]
+    fn test_invalid_input_error() {
+        let error = IndicatorError::InvalidInput {
+            index: 7,
+            reason: "value is NaN".to_string(),
+        };
+
+        assert!(format!("{:?}", error).contains("InvalidInput"));
+        assert_eq!(
+            format!("{}", error),
+            "Invalid input at index 7: value is NaN"
+        );
     }
 
     #[test]
     fn test_error_conversion() {
         // Test that errors can be used with the ? operator
         fn returns_indicator_error() -> Result<(), IndicatorError> {
-            Err(IndicatorError::InvalidParameter("Test error".to_string()))
+            Err(IndicatorError::invalid_parameter(
+                "k",
+                -1.0,
+                "must be positive",
+            ))
         }
 
         fn propagates_error() -> Result<(), IndicatorError> {
@@ -119,8 +245,9 @@ mod tests {
         assert!(result.is_err());
         if let Err(error) = result {
             match error {
-                IndicatorError::InvalidParameter(msg) => {
-                    assert_eq!(msg, "Test error");
+                IndicatorError::InvalidParameter { name, value, .. } => {
+                    assert_eq!(name, "k");
+                    assert_eq!(value, "-1");
                 }
                 _ => panic!("Wrong error type"),
             }
@@ -0,0 +1,599 @@
+//! Building indicators from a name and parameters
+//!
+//! Screeners and backtest services usually read their indicator list from a
+//! config file or an API request. [`IndicatorFactory::create`] turns a name
+//! such as `"rsi"` plus a parameter map into a ready-to-use
+//! `Box<dyn AnyIndicator>`; [`IndicatorSpec`] bundles the two so a whole
+//! list can be deserialized at once (with the `serde` feature).
+//!
+//! ```
+//! use rsta::indicators::factory::{IndicatorFactory, IndicatorParams};
+//!
+//! let mut params = IndicatorParams::new();
+//! params.insert("period".to_string(), 14.0);
+//! let rsi = IndicatorFactory::create("rsi", &params).unwrap();
+//! assert_eq!(rsi.name(), "Rsi");
+//! assert_eq!(rsi.min_periods(), 15);
+//!
+//! // Multi-parameter indicators fall back to their canonical defaults,
+//! // here MACD(12, 26, 9).
+//! let macd = IndicatorFactory::create("macd", &IndicatorParams::new()).unwrap();
+//! assert_eq!(macd.name(), "Macd");
+//!
+//! assert!(IndicatorFactory::create("nope", &params).is_err());
+//! ```
+//!
+//! ## Supported indicators
+//!
+//! | Name(s) | Parameters (default) |
+//! |---|---|
+//! | `sma`, `ema`, `wma`, `dema`, `tema`, `hma` | `period` |
+//! | `rsi`, `cci`, `williams_r` / `willr` | `period` |
+//! | `atr`, `std`, `donchian`, `choppiness` / `chop` | `period` |
+//! | `adx`, `cmf`, `mfi`, `vroc` | `period` |
+//! | `macd` | `fast` (12), `slow` (26), `signal` (9) |
+//! | `stochastic` / `stoch` | `k_period` (14), `d_period` (3), `k_smoothing` (1) |
+//! | `bollinger` / `bb` | `period` (20), `k` (2.0) |
+//! | `keltner` | `ema_period` (20), `atr_period` (20), `multiplier` (2.0) |
+//! | `ichimoku` | `tenkan` (9), `kijun` (26), `senkou_b` (52) |
+//! | `sar` / `psar` | `af_start` (0.02), `af_step` (0.02), `af_max` (0.20) |
+//! | `adl`, `obv`, `vwap`, `true_range` / `tr` | — |
+//!
+//! Names are case-insensitive. Unknown names, unknown parameter keys,
+//! missing required parameters and non-integer periods are reported as
+//! [`IndicatorError::InvalidParameter`], with `name` set to `"name"` for
+//! an unknown indicator and to `"<indicator>.<key>"` for parameters.
+//! [`IndicatorFactory::descriptor`] returns the parameter ranges, defaults
+//! and output fields of each name, for generating configuration forms.
+//! Only indicators of enabled categories (the `trend`, `momentum`,
+//! `volume` and `volatility` features) can be created.
+
+// With every indicator category disabled `create` can only reject names.
+#![cfg_attr(
+    not(any(
+        feature = "trend",
+        feature = "momentum",
+        feature = "volume",
+        feature = "volatility"
+    )),
+    allow(unused)
+)]
+
+use std::collections::BTreeMap;
+
+use crate::indicators::any_indicator::{boxed, AnyIndicator};
+use crate::indicators::descriptor::{Describe, Descriptor};
+#[cfg(feature = "momentum")]
+use crate::indicators::momentum::{Cci, Rsi, StochasticOscillator, WilliamsR};
+#[cfg(all(feature = "trend", feature = "momentum"))]
+use crate::indicators::trend::MaType;
+#[cfg(feature = "trend")]
+use crate::indicators::trend::{Adx, Dema, Ema, Hma, Ichimoku, Macd, Sar, Sma, Tema, Wma};
+#[cfg(feature = "volatility")]
+use crate::indicators::volatility::{
+    Atr, BollingerBands, Choppiness, Donchian, KeltnerChannels, Std, TrueRange,
+};
+#[cfg(feature = "volume")]
+use crate::indicators::volume::{Adl, Cmf, Mfi, Obv, Vroc, Vwap};
+use crate::indicators::IndicatorError;
+
+/// Named numeric parameters for [`IndicatorFactory::create`]. Periods are
+/// given as whole numbers (`14.0`).
+pub type IndicatorParams = BTreeMap<String, f64>;
+
+/// An indicator name together with its parameters, e.g. parsed from
+/// `{"name": "bollinger", "params": {"period": 20, "k": 2.5}}`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndicatorSpec {
+    /// Indicator name, see the [module docs](self) for the supported list.
+    pub name: String,
+    /// Parameters; omitted ones use the indicator's default, if it has one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub params: IndicatorParams,
+}
+
+impl IndicatorSpec {
+    /// Create a spec with no parameters.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            params: IndicatorParams::new(),
+        }
+    }
+
+    /// Set a parameter, builder-style.
+    pub fn param(mut self, key: &str, value: f64) -> Self {
+        self.params.insert(key.to_string(), value);
+        self
+    }
+
+    /// Instantiate the indicator described by this spec.
+    pub fn build(&self) -> Result<Box<dyn AnyIndicator>, IndicatorError> {
+        IndicatorFactory::create(&self.name, &self.params)
+    }
+}
+
+/// Creates indicators by name. See the [module docs](self).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndicatorFactory;
+
+impl IndicatorFactory {
+    /// Canonical names accepted by [`IndicatorFactory::create`] (aliases
+    /// not included).
+    pub const SUPPORTED: &'static [&'static str] = &[
+        #[cfg(feature = "volume")]
+        "adl",
+        #[cfg(feature = "trend")]
+        "adx",
+        #[cfg(feature = "volatility")]
+        "atr",
+        #[cfg(feature = "volatility")]
+        "bollinger",
+        #[cfg(feature = "momentum")]
+        "cci",
+        #[cfg(feature = "volatility")]
+        "choppiness",
+        #[cfg(feature = "volume")]
+        "cmf",
+        #[cfg(feature = "trend")]
+        "dema",
+        #[cfg(feature = "volatility")]
+        "donchian",
+        #[cfg(feature = "trend")]
+        "ema",
+        #[cfg(feature = "trend")]
+        "hma",
+        #[cfg(feature = "trend")]
+        "ichimoku",
+        #[cfg(feature = "volatility")]
+        "keltner",
+        #[cfg(feature = "trend")]
+        "macd",
+        #[cfg(feature = "volume")]
+        "mfi",
+        #[cfg(feature = "volume")]
+        "obv",
+        #[cfg(feature = "momentum")]
+        "rsi",
+        #[cfg(feature = "trend")]
+        "sar",
+        #[cfg(feature = "trend")]
+        "sma",
+        #[cfg(feature = "volatility")]
+        "std",
+        #[cfg(feature = "momentum")]
+        "stochastic",
+        #[cfg(feature = "trend")]
+        "tema",
+        #[cfg(feature = "volatility")]
+        "true_range",
+        #[cfg(feature = "volume")]
+        "vroc",
+        #[cfg(feature = "volume")]
+        "vwap",
+        #[cfg(feature = "momentum")]
+        "williams_r",
+        #[cfg(feature = "trend")]
+        "wma",
+    ];
+
+    /// Instantiate the indicator called `name` with `params`.
+    pub fn create(
+        name: &str,
+        params: &IndicatorParams,
+    ) -> Result<Box<dyn AnyIndicator>, IndicatorError> {
+        let name = name.to_ascii_lowercase();
+        let mut p = Params::new(&name, params);
+        let indicator = match name.as_str() {
+            #[cfg(feature = "trend")]
+            "sma" => boxed(Sma::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "ema" => boxed(Ema::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "wma" => boxed(Wma::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "dema" => boxed(Dema::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "tema" => boxed(Tema::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "hma" => boxed(Hma::new(p.period("period", None)?)?),
+            #[cfg(feature = "momentum")]
+            "rsi" => boxed(Rsi::new(p.period("period", None)?)?),
+            #[cfg(feature = "momentum")]
+            "cci" => boxed(Cci::new(p.period("period", None)?)?),
+            #[cfg(feature = "momentum")]
+            "williams_r" | "willr" => boxed(WilliamsR::new(p.period("period", None)?)?),
+            #[cfg(feature = "volatility")]
+            "atr" => boxed(Atr::new(p.period("period", None)?)?),
+            #[cfg(feature = "volatility")]
+            "std" => boxed(Std::new(p.period("period", None)?)?),
+            #[cfg(feature = "volatility")]
+            "donchian" => boxed(Donchian::new(p.period("period", None)?)?),
+            #[cfg(feature = "volatility")]
+            "choppiness" | "chop" => boxed(Choppiness::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "adx" => boxed(Adx::new(p.period("period", None)?)?),
+            #[cfg(feature = "volume")]
+            "cmf" => boxed(Cmf::new(p.period("period", None)?)?),
+            #[cfg(feature = "volume")]
+            "mfi" => boxed(Mfi::new(p.period("period", None)?)?),
+            #[cfg(feature = "volume")]
+            "vroc" => boxed(Vroc::new(p.period("period", None)?)?),
+            #[cfg(feature = "trend")]
+            "macd" => boxed(Macd::new(
+                p.period("fast", Some(12))?,
+                p.period("slow", Some(26))?,
+                p.period("signal", Some(9))?,
+            )?),
+            #[cfg(feature = "momentum")]
+            "stochastic" | "stoch" => boxed(StochasticOscillator::with_ma(
+                p.period("k_period", Some(14))?,
+                p.period("k_smoothing", Some(1))?,
+                p.period("d_period", Some(3))?,
+                MaType::Sma,
+            )?),
+            #[cfg(feature = "volatility")]
+            "bollinger" | "bb" => boxed(BollingerBands::new(
+                p.period("period", Some(20))?,
+                p.float("k", 2.0),
+            )?),
+            #[cfg(feature = "volatility")]
+            "keltner" => boxed(KeltnerChannels::new(
+                p.period("ema_period", Some(20))?,
+                p.period("atr_period", Some(20))?,
+                p.float("multiplier", 2.0),
+            )?),
+            #[cfg(feature = "trend")]
+            "ichimoku" => boxed(Ichimoku::new(
+                p.period("tenkan", Some(9))?,
+                p.period("kijun", Some(26))?,
+                p.period("senkou_b", Some(52))?,
+            )?),
+            #[cfg(feature = "trend")]
+            "sar" | "psar" => boxed(Sar::new(
+                p.float("af_start", 0.02),
+                p.float("af_step", 0.02),
+                p.float("af_max", 0.20),
+            )?),
+            #[cfg(feature = "volume")]
+            "adl" => boxed(Adl::new()),
+            #[cfg(feature = "volume")]
+            "obv" => boxed(Obv::new()),
+            #[cfg(feature = "volatility")]
+            "true_range" | "tr" => boxed(TrueRange::new()),
+            #[cfg(feature = "volume")]
+            "vwap" => boxed(Vwap::new()),
+            _ => {
+                return Err(IndicatorError::invalid_parameter(
+                    "name",
+                    name,
+                    "unknown indicator",
+                ))
+            }
+        };
+        p.check_unused()?;
+        Ok(indicator)
+    }
+    /// Metadata of the indicator called `name` (case-insensitive, aliases
+    /// accepted), or `None` if it is unknown.
+    pub fn descriptor(name: &str) -> Option<&'static Descriptor> {
+        let descriptor = match name.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "trend")]
+            "sma" => Sma::descriptor(),
+            #[cfg(feature = "trend")]
+            "ema" => Ema::descriptor(),
+            #[cfg(feature = "trend")]
+            "wma" => Wma::descriptor(),
+            #[cfg(feature = "trend")]
+            "dema" => Dema::descriptor(),
+            #[cfg(feature = "trend")]
+            "tema" => Tema::descriptor(),
+            #[cfg(feature = "trend")]
+            "hma" => Hma::descriptor(),
+            #[cfg(feature = "momentum")]
+            "rsi" => Rsi::descriptor(),
+            #[cfg(feature = "momentum")]
+            "cci" => Cci::descriptor(),
+            #[cfg(feature = "momentum")]
+            "williams_r" | "willr" => WilliamsR::descriptor(),
+            #[cfg(feature = "volatility")]
+            "atr" => Atr::descriptor(),
+            #[cfg(feature = "volatility")]
+            "std" => Std::descriptor(),
+            #[cfg(feature = "volatility")]
+            "donchian" => Donchian::descriptor(),
+            #[cfg(feature = "volatility")]
+            "choppiness" | "chop" => Choppiness::descriptor(),
+            #[cfg(feature = "trend")]
+            "adx" => Adx::descriptor(),
+            #[cfg(feature = "volume")]
+            "cmf" => Cmf::descriptor(),
+            #[cfg(feature = "volume")]
+            "mfi" => Mfi::descriptor(),
+            #[cfg(feature = "volume")]
+            "vroc" => Vroc::descriptor(),
+            #[cfg(feature = "trend")]
+            "macd" => Macd::descriptor(),
+            #[cfg(feature = "momentum")]
+            "stochastic" | "stoch" => StochasticOscillator::descriptor(),
+            #[cfg(feature = "volatility")]
+            "bollinger" | "bb" => BollingerBands::descriptor(),
+            #[cfg(feature = "volatility")]
+            "keltner" => KeltnerChannels::descriptor(),
+            #[cfg(feature = "trend")]
+            "ichimoku" => Ichimoku::descriptor(),
+            #[cfg(feature = "trend")]
+            "sar" | "psar" => Sar::descriptor(),
+            #[cfg(feature = "volume")]
+            "adl" => Adl::descriptor(),
+            #[cfg(feature = "volume")]
+            "obv" => Obv::descriptor(),
+            #[cfg(feature = "volatility")]
+            "true_range" | "tr" => TrueRange::descriptor(),
+            #[cfg(feature = "volume")]
+            "vwap" => Vwap::descriptor(),
+            _ => return None,
+        };
+        Some(descriptor)
+    }
+
+    /// Metadata of every indicator in [`SUPPORTED`](Self::SUPPORTED), for
+    /// listing what a screener can offer.
+    pub fn descriptors() -> Vec<&'static Descriptor> {
+        Self::SUPPORTED
+            .iter()
+            .filter_map(|name| Self::descriptor(name))
+            .collect()
+    }
+}
+
+/// Parameter lookup that remembers which keys were read, so typos in a
+/// config surface as errors instead of silently using a default.
+struct Params<'a> {
+    indicator: &'a str,
+    values: &'a IndicatorParams,
+    used: Vec<&'static str>,
+}
+
+impl<'a> Params<'a> {
+    fn new(indicator: &'a str, values: &'a IndicatorParams) -> Self {
+        Self {
+            indicator,
+            values,
+            used: Vec::new(),
+        }
+    }
+
+    /// `indicator.key`, the parameter name reported in errors.
+    fn qualified(&self, key: &str) -> String {
+        format!("{}.{}", self.indicator, key)
+    }
+
+    #[cfg_attr(not(any(feature = "trend", feature = "volatility")), allow(dead_code))]
+    fn float(&mut self, key: &'static str, default: f64) -> f64 {
+        self.used.push(key);
+        self.values.get(key).copied().unwrap_or(default)
+    }
+
+    fn period(
+        &mut self,
+        key: &'static str,
+        default: Option<usize>,
+    ) -> Result<usize, IndicatorError> {
+        self.used.push(key);
+        match (self.values.get(key), default) {
+            (Some(&v), _) => {
+                if v.is_finite() && v >= 0.0 && v.fract() == 0.0 {
+                    Ok(v as usize)
+                } else {
+                    Err(IndicatorError::invalid_parameter(
+                        self.qualified(key),
+                        v,
+                        "must be a non-negative whole number",
+                    ))
+                }
+            }
+            (None, Some(d)) => Ok(d),
+            (None, None) => Err(IndicatorError::invalid_parameter(
+                self.qualified(key),
+                "<missing>",
+                "required parameter",
+            )),
+        }
+    }
+
+    fn check_unused(&self) -> Result<(), IndicatorError> {
+        match self
+            .values
+            .keys()
+            .find(|k| !self.used.contains(&k.as_str()))
+        {
+            Some(key) => Err(IndicatorError::invalid_parameter(
+                self.qualified(key),
+                self.values[key],
+                "unknown parameter",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "trend",
+    feature = "momentum",
+    feature = "volume",
+    feature = "volatility"
+))]
+mod tests {
+    use super::*;
+    use crate::indicators::any_indicator::IndicatorValue;
+    use crate::indicators::{Candle, Indicator};
+
+    fn params(pairs: &[(&str, f64)]) -> IndicatorParams {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    fn candles(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 20.0 + (i as f64 * 0.4).sin();
+                Candle {
+                    timestamp: i as u64,
+                    open: c,
+                    high: c + 0.5,
+                    low: c - 0.5,
+                    close: c,
+                    volume: 10.0 + i as f64,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_supported_name_builds() {
+        let data = candles(120);
+        for name in IndicatorFactory::SUPPORTED {
+            let p = match *name {
+                "adl" | "obv" | "true_range" | "vwap" | "macd" | "stochastic" | "bollinger"
+                | "keltner" | "ichimoku" | "sar" => IndicatorParams::new(),
+                _ => params(&[("period", 5.0)]),
+            };
+            let mut indicator =
+                IndicatorFactory::create(name, &p).unwrap_or_else(|e| panic!("{name}: {e}"));
+            let values = indicator
+                .calculate(&data)
+                .unwrap_or_else(|e| panic!("{name}: {e}"));
+            assert_eq!(
+                values.len(),
+                data.len() - indicator.output_offset(),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_descriptors_match_factory() {
+        let data = candles(120);
+        let descriptors = IndicatorFactory::descriptors();
+        assert_eq!(descriptors.len(), IndicatorFactory::SUPPORTED.len());
+        for (d, name) in descriptors.iter().zip(IndicatorFactory::SUPPORTED) {
+            assert_eq!(d.name, *name);
+            let valid: IndicatorParams = d
+                .params
+                .iter()
+                .map(|p| (p.name.to_string(), p.default.unwrap_or(5.0)))
+                .collect();
+            let mut indicator =
+                IndicatorFactory::create(name, &valid).unwrap_or_else(|e| panic!("{name}: {e}"));
+            let last = *indicator.calculate(&data).unwrap().last().unwrap();
+            let fields: Vec<&str> = last.fields().iter().map(|(f, _)| *f).collect();
+            assert_eq!(fields, d.outputs, "{name}");
+
+            for p in d.params {
+                let below = if p.min_exclusive { p.min } else { p.min - 1.0 };
+                assert!(!p.accepts(below), "{name}.{}", p.name);
+                let mut invalid = valid.clone();
+                invalid.insert(p.name.to_string(), below);
+                assert!(
+                    IndicatorFactory::create(name, &invalid).is_err(),
+                    "{name}.{}",
+                    p.name
+                );
+                if p.default.is_none() {
+                    invalid.remove(p.name);
+                    assert!(IndicatorFactory::create(name, &invalid).is_err());
+                }
+            }
+        }
+        assert_eq!(
+            IndicatorFactory::descriptor("WillR"),
+            IndicatorFactory::descriptor("williams_r")
+        );
+        assert_eq!(IndicatorFactory::descriptor("nope"), None);
+    }
+
+    #[test]
+    fn test_matches_direct_construction() {
+        let data = candles(40);
+        let mut built =
+            IndicatorFactory::create("BB", &params(&[("period", 10.0), ("k", 1.5)])).unwrap();
+        let mut direct = BollingerBands::new(10, 1.5).unwrap();
+        let expected = direct.calculate(&data).unwrap();
+        let values = built.calculate(&data).unwrap();
+        assert_eq!(values.len(), expected.len());
+        for (v, r) in values.iter().zip(&expected) {
+            assert_eq!(*v, IndicatorValue::from(*r));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |name: &str, p: IndicatorParams| match IndicatorFactory::create(name, &p) {
+            Err(IndicatorError::InvalidParameter {
+                name, constraint, ..
+            }) => (name, constraint),
+            Err(other) => panic!("unexpected error {other:?}"),
+            Ok(_) => panic!("{name} should fail"),
+        };
+        let (name, constraint) = err("foo", IndicatorParams::new());
+        assert_eq!(
+            (name.as_str(), constraint.as_str()),
+            ("name", "unknown indicator")
+        );
+        let (name, constraint) = err("rsi", IndicatorParams::new());
+        assert_eq!(
+            (name.as_str(), constraint.as_str()),
+            ("rsi.period", "required parameter")
+        );
+        assert!(err("rsi", params(&[("period", 14.5)]))
+            .1
+            .contains("whole number"));
+        assert!(err("rsi", params(&[("period", -3.0)]))
+            .1
+            .contains("whole number"));
+        let (name, constraint) = err("rsi", params(&[("period", 14.0), ("perod", 3.0)]));
+        assert_eq!(
+            (name.as_str(), constraint.as_str()),
+            ("rsi.perod", "unknown parameter")
+        );
+        // Validation errors from the indicator itself pass through.
+        assert_eq!(err("macd", params(&[("fast", 30.0)])).0, "slow_period");
+        assert_eq!(err("sma", params(&[("period", 0.0)])).0, "period");
+    }
+
+    #[test]
+    fn test_spec_build() {
+        let spec = IndicatorSpec::new("keltner")
+            .param("ema_period", 10.0)
+            .param("atr_period", 10.0);
+        let indicator = spec.build().unwrap();
+        assert_eq!(indicator.name(), "KeltnerChannels");
+        assert_eq!(indicator.min_periods(), 10);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_spec_deserialize() {
+        let json = r#"[
+            {"name": "rsi", "params": {"period": 14}},
+            {"name": "macd"},
+            {"name": "bollinger", "params": {"period": 20, "k": 2.5}}
+        ]"#;
+        let specs: Vec<IndicatorSpec> = serde_json::from_str(json).unwrap();
+        assert_eq!(specs[1], IndicatorSpec::new("macd"));
+        assert_eq!(
+            specs[2],
+            IndicatorSpec::new("bollinger")
+                .param("period", 20.0)
+                .param("k", 2.5)
+        );
+        let built: Vec<_> = specs.iter().map(|s| s.build().unwrap()).collect();
+        assert_eq!(built[0].min_periods(), 15);
+
+        let round_trip: IndicatorSpec =
+            serde_json::from_str(&serde_json::to_string(&specs[2]).unwrap()).unwrap();
+        assert_eq!(round_trip, specs[2]);
+    }
+}
@@ -0,0 +1,492 @@
+//! Const-generic moving averages
+//!
+//! [`FixedSma`], [`FixedWma`] and [`FixedEma`] take their period as a const
+//! generic (`FixedSma<20>`) instead of a runtime argument. The window lives
+//! in a `[f64; N]` on the stack and every loop over it has a length known
+//! at compile time, so the optimizer can unroll it — useful in
+//! latency-sensitive paths where the period never changes.
+//!
+//! A period of zero is rejected at compile time, so the constructors are
+//! infallible. Outputs match the runtime-period indicators ([`Sma`],
+//! [`Wma`], [`Ema`] streamed through `next()`) up to floating-point
+//! rounding.
+//!
+//! [`Sma`]: crate::indicators::trend::Sma
+//! [`Wma`]: crate::indicators::trend::Wma
+//! [`Ema`]: crate::indicators::trend::Ema
+//!
+//! ```
+//! use rsta::indicators::fixed::FixedSma;
+//! use rsta::indicators::Indicator;
+//!
+//! let mut sma = FixedSma::<3>::new();
+//! let values = sma.calculate(&[2.0, 4.0, 6.0, 8.0]).unwrap();
+//! assert_eq!(values, vec![4.0, 6.0]);
+//! ```
+
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// The last `N` values, oldest first.
+#[derive(Debug, Clone, Copy)]
+struct Window<const N: usize> {
+    values: [f64; N],
+    len: usize,
+}
+
+impl<const N: usize> Window<N> {
+    const fn new() -> Self {
+        const { assert!(N > 0, "period must be at least 1") };
+        Self {
+            values: [0.0; N],
+            len: 0,
+        }
+    }
+
+    /// Append `value`, returning the full window once `N` values are in.
+    fn push(&mut self, value: f64) -> Option<&[f64; N]> {
+        if self.len < N {
+            self.values[self.len] = value;
+            self.len += 1;
+        } else {
+            self.values.copy_within(1.., 0);
+            self.values[N - 1] = value;
+        }
+        (self.len == N).then_some(&self.values)
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+/// Simple Moving Average with a compile-time period.
+///
+/// The window is summed afresh on every update, which keeps the result
+/// free of the drift a running sum accumulates.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedSma<const N: usize> {
+    window: Window<N>,
+    last: Option<f64>,
+}
+
+impl<const N: usize> FixedSma<N> {
+    /// Create a new SMA over the last `N` values.
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+            last: None,
+        }
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+
+    fn mean(values: &[f64; N]) -> f64 {
+        values.iter().sum::<f64>() / N as f64
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let out = self.window.push(value).map(Self::mean)?;
+        self.last = Some(out);
+        self.last
+    }
+}
+
+impl<const N: usize> Default for FixedSma<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Indicator<f64, f64> for FixedSma<N> {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, N)?;
+        self.reset_state();
+        // Streamed through the window so `next()` and `calculate_append`
+        // continue from the last `N` values.
+        Ok(data.iter().filter_map(|&v| self.step(v)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedSma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn min_periods(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Indicator<Candle, f64> for FixedSma<N> {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|c| c.close).collect();
+        Indicator::<f64, f64>::calculate(self, &closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(candle.close))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedSma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn min_periods(&self) -> usize {
+        N
+    }
+}
+
+/// Weighted Moving Average with a compile-time period.
+///
+/// Weights run linearly from 1 (oldest) to `N` (most recent).
+#[derive(Debug, Clone, Copy)]
+pub struct FixedWma<const N: usize> {
+    window: Window<N>,
+    last: Option<f64>,
+}
+
+impl<const N: usize> FixedWma<N> {
+    /// Create a new WMA over the last `N` values.
+    pub const fn new() -> Self {
+        Self {
+            window: Window::new(),
+            last: None,
+        }
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+
+    fn weighted(values: &[f64; N]) -> f64 {
+        let n = N as f64;
+        let numer: f64 = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i as f64 + 1.0) * v)
+            .sum();
+        numer / (n * (n + 1.0) / 2.0)
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let out = self.window.push(value).map(Self::weighted)?;
+        self.last = Some(out);
+        self.last
+    }
+}
+
+impl<const N: usize> Default for FixedWma<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Indicator<f64, f64> for FixedWma<N> {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, N)?;
+        self.reset_state();
+        // Streamed through the window so `next()` and `calculate_append`
+        // continue from the last `N` values.
+        Ok(data.iter().filter_map(|&v| self.step(v)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedWma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn min_periods(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Indicator<Candle, f64> for FixedWma<N> {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|c| c.close).collect();
+        Indicator::<f64, f64>::calculate(self, &closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(candle.close))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedWma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn min_periods(&self) -> usize {
+        N
+    }
+}
+
+/// Exponential Moving Average with a compile-time period.
+///
+/// Seeded with the first value like [`Ema`](crate::indicators::trend::Ema),
+/// with the smoothing factor `2 / (N + 1)` folded into a constant.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedEma<const N: usize> {
+    current: Option<f64>,
+}
+
+impl<const N: usize> FixedEma<N> {
+    const ALPHA: f64 = 2.0 / (N as f64 + 1.0);
+
+    /// Create a new EMA with period `N`.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "period must be at least 1") };
+        Self { current: None }
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.current = None;
+    }
+
+    fn step(&mut self, value: f64) -> f64 {
+        let ema = match self.current {
+            Some(prev) => value * Self::ALPHA + prev * (1.0 - Self::ALPHA),
+            None => value,
+        };
+        self.current = Some(ema);
+        ema
+    }
+}
+
+impl<const N: usize> Default for FixedEma<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Indicator<f64, f64> for FixedEma<N> {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, N)?;
+        self.reset_state();
+        Ok(data.iter().map(|&v| self.step(v)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(value)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.current.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedEma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn min_periods(&self) -> usize {
+        N
+    }
+
+    fn output_offset(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Indicator<Candle, f64> for FixedEma<N> {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, N)?;
+        self.reset_state();
+        Ok(data.iter().map(|c| self.step(c.close)).collect())
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(candle.close)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.current.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FixedEma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn min_periods(&self) -> usize {
+        N
+    }
+
+    fn output_offset(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(all(test, feature = "trend"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::{Ema, Sma, Wma};
+
+    fn closes(n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.05)
+            .collect()
+    }
+
+    fn assert_close(a: &[f64], b: &[f64]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < 1e-9, "{x} != {y}");
+        }
+    }
+
+    #[test]
+    fn test_fixed_sma_matches_sma() {
+        let data = closes(50);
+        let mut fixed = FixedSma::<7>::new();
+        let batch = Indicator::<f64, f64>::calculate(&mut fixed, &data).unwrap();
+        let expected = Indicator::<f64, f64>::calculate(&mut Sma::new(7).unwrap(), &data).unwrap();
+        assert_close(&batch, &expected);
+
+        Indicator::<f64, f64>::reset(&mut fixed);
+        let streamed: Vec<f64> = data
+            .iter()
+            .filter_map(|&v| Indicator::<f64, f64>::next(&mut fixed, v).unwrap())
+            .collect();
+        assert_eq!(streamed, batch);
+        assert!(Indicator::<f64, f64>::calculate(&mut fixed, &data[..6]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_wma_matches_wma() {
+        let data = closes(40);
+        let batch = Indicator::<f64, f64>::calculate(&mut FixedWma::<5>::new(), &data).unwrap();
+        let expected = Indicator::<f64, f64>::calculate(&mut Wma::new(5).unwrap(), &data).unwrap();
+        assert_close(&batch, &expected);
+    }
+
+    #[test]
+    fn test_fixed_ema_matches_streamed_ema() {
+        let data = closes(40);
+        let mut ema = Ema::new(9).unwrap();
+        let expected: Vec<f64> = data
+            .iter()
+            .filter_map(|&v| Indicator::<f64, f64>::next(&mut ema, v).unwrap())
+            .collect();
+        let batch = Indicator::<f64, f64>::calculate(&mut FixedEma::<9>::new(), &data).unwrap();
+        assert_eq!(batch, expected);
+    }
+
+    #[test]
+    fn test_append_matches_full_calculate() {
+        let data = closes(300);
+        let (history, new_bars) = data.split_at(200);
+
+        let mut sma = FixedSma::<10>::new();
+        let mut out = Indicator::<f64, f64>::calculate(&mut sma, history).unwrap();
+        assert_eq!(sma.calculate_append(&mut out, new_bars).unwrap(), 100);
+        assert_eq!(
+            out,
+            Indicator::<f64, f64>::calculate(&mut sma, &data).unwrap()
+        );
+
+        let mut wma = FixedWma::<10>::new();
+        let mut out = Indicator::<f64, f64>::calculate(&mut wma, history).unwrap();
+        assert_eq!(wma.calculate_append(&mut out, new_bars).unwrap(), 100);
+        assert_eq!(
+            out,
+            Indicator::<f64, f64>::calculate(&mut wma, &data).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_candle_input_and_metadata() {
+        let candles: Vec<Candle> = closes(10)
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| Candle {
+                timestamp: i as u64,
+                open: c,
+                high: c,
+                low: c,
+                close: c,
+                volume: 1.0,
+            })
+            .collect();
+        let mut sma = FixedSma::<4>::default();
+        let out = Indicator::<Candle, f64>::calculate(&mut sma, &candles).unwrap();
+        assert_eq!(out.len(), 7);
+        assert_eq!(Indicator::<Candle, f64>::min_periods(&sma), 4);
+        assert_eq!(Indicator::<Candle, f64>::name(&sma), "FixedSma");
+        let ema = FixedEma::<4>::new();
+        assert_eq!(Indicator::<Candle, f64>::output_offset(&ema), 0);
+    }
+}
@@ -0,0 +1,382 @@
+//! Multi-indicator engine
+//!
+//! Strategy code usually tracks many indicators on the same series. An
+//! [`IndicatorSet`] registers them under string keys and drives all of them
+//! together: one pass over a slice for backtests, one call per live value
+//! when streaming. Outputs come back as a `BTreeMap` keyed by the
+//! registration name, so iteration order is deterministic.
+//!
+//! All indicators in a set share the input type `T` and output type `O`.
+//! With the `parallel` feature, batch evaluation instead runs each
+//! indicator over the whole slice (and, via
+//! [`IndicatorSet::calculate_symbols`], each symbol) on rayon's thread
+//! pool; `T` must then be `Sync` and `O` `Send`.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use rsta::indicators::indicator_set::IndicatorSet;
+//! use rsta::indicators::trend::{Ema, Sma};
+//!
+//! let mut set = IndicatorSet::<f64, f64>::new();
+//! set.add("sma_3", Box::new(Sma::new(3).unwrap()))
+//!     .add("ema_3", Box::new(Ema::new(3).unwrap()));
+//!
+//! // Batch: one entry per input value, `None` during warm-up.
+//! let out = set.calculate(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+//! assert_eq!(out["sma_3"], vec![None, None, Some(2.0), Some(3.0)]);
+//! assert_eq!(out["ema_3"].len(), 4);
+//!
+//! // Streaming: one call per new value updates every indicator.
+//! let tick = set.next(5.0).unwrap();
+//! assert_eq!(tick["sma_3"], Some(4.0));
+//!
+//! // Many symbols: a fresh set per history.
+//! let mut series = BTreeMap::new();
+//! series.insert("AAA".to_string(), vec![1.0, 2.0, 3.0]);
+//! series.insert("BBB".to_string(), vec![10.0, 20.0, 30.0]);
+//! let out = IndicatorSet::calculate_symbols(&series, || {
+//!     let mut set = IndicatorSet::<f64, f64>::new();
+//!     set.add("sma", Box::new(Sma::new(2).unwrap()));
+//!     set
+//! })
+//! .unwrap();
+//! assert_eq!(out["BBB"]["sma"], vec![None, Some(15.0), Some(25.0)]);
+//! ```
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Boxed indicator stored in an [`IndicatorSet`].
+pub type BoxedIndicator<T, O> = Box<dyn Indicator<T, O> + Send>;
+
+/// A keyed collection of indicators evaluated together.
+///
+/// `calculate()` feeds the input to every registered indicator through
+/// `next()`, so an indicator behaves in the set exactly as it does when
+/// streamed on its own.
+pub struct IndicatorSet<T, O> {
+    indicators: BTreeMap<String, BoxedIndicator<T, O>>,
+}
+
+impl<T, O> Default for IndicatorSet<T, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, O> IndicatorSet<T, O> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self {
+            indicators: BTreeMap::new(),
+        }
+    }
+
+    /// Register `indicator` under `name`, replacing any indicator already
+    /// registered with that name.
+    pub fn add(&mut self, name: &str, indicator: BoxedIndicator<T, O>) -> &mut Self {
+        self.indicators.insert(name.to_string(), indicator);
+        self
+    }
+
+    /// Remove and return the indicator registered under `name`.
+    pub fn remove(&mut self, name: &str) -> Option<BoxedIndicator<T, O>> {
+        self.indicators.remove(name)
+    }
+
+    /// `true` if an indicator is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.indicators.contains_key(name)
+    }
+
+    /// Registered names, in output order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.indicators.keys().map(String::as_str)
+    }
+
+    /// Number of registered indicators.
+    pub fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    /// `true` if no indicators are registered.
+    pub fn is_empty(&self) -> bool {
+        self.indicators.is_empty()
+    }
+
+    /// Number of input values needed before every indicator in the set has
+    /// produced a value (the largest `min_periods()` of its members).
+    pub fn min_periods(&self) -> usize {
+        self.indicators
+            .values()
+            .map(|ind| ind.min_periods())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Reset every registered indicator.
+    pub fn reset(&mut self) {
+        for indicator in self.indicators.values_mut() {
+            indicator.reset();
+        }
+    }
+}
+
+/// Input-aligned outputs of an [`IndicatorSet`], keyed by registration name.
+pub type SetOutputs<O> = BTreeMap<String, Vec<Option<O>>>;
+
+#[cfg(not(feature = "parallel"))]
+impl<T: Clone, O> IndicatorSet<T, O> {
+    /// Reset the set and evaluate every indicator over `data` in a single
+    /// pass.
+    ///
+    /// Each output vector has one entry per input value (`None` while the
+    /// indicator is warming up), so it zips directly with `data`. The
+    /// indicators keep their state afterwards, so `next()` continues from
+    /// the end of `data`.
+    pub fn calculate(&mut self, data: &[T]) -> Result<SetOutputs<O>, IndicatorError> {
+        self.reset();
+        let mut outputs: SetOutputs<O> = self
+            .indicators
+            .keys()
+            .map(|name| (name.clone(), Vec::with_capacity(data.len())))
+            .collect();
+
+        for value in data {
+            for ((_, indicator), (_, series)) in self.indicators.iter_mut().zip(outputs.iter_mut())
+            {
+                series.push(indicator.next(value.clone())?);
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// Evaluate a fresh set from `make_set` over every symbol's history,
+    /// one symbol after the other. The first error by key is returned.
+    pub fn calculate_symbols<F>(
+        series: &BTreeMap<String, Vec<T>>,
+        make_set: F,
+    ) -> Result<BTreeMap<String, SetOutputs<O>>, IndicatorError>
+    where
+        F: Fn() -> Self,
+    {
+        series
+            .iter()
+            .map(|(symbol, data)| Ok((symbol.clone(), make_set().calculate(data)?)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<T: Clone + Sync, O: Send> IndicatorSet<T, O> {
+    /// Reset the set and evaluate every indicator over `data`.
+    ///
+    /// Each output vector has one entry per input value (`None` while the
+    /// indicator is warming up), so it zips directly with `data`. The
+    /// indicators keep their state afterwards, so `next()` continues from
+    /// the end of `data`.
+    ///
+    /// The indicators are independent, so each one runs over the whole
+    /// slice on the rayon thread pool. If several fail, the error of the
+    /// first by name is returned.
+    pub fn calculate(&mut self, data: &[T]) -> Result<SetOutputs<O>, IndicatorError> {
+        self.indicators
+            .par_iter_mut()
+            .map(|(name, indicator)| {
+                indicator.reset();
+                let series = data
+                    .iter()
+                    .map(|value| indicator.next(value.clone()))
+                    .collect::<Result<Vec<_>, _>>();
+                (name.clone(), series)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|(name, series)| series.map(|s| (name, s)))
+            .collect()
+    }
+
+    /// Evaluate a fresh set from `make_set` over every symbol's history.
+    ///
+    /// Symbols are processed concurrently, so backtests over many
+    /// instruments scale with the number of cores. If several symbols
+    /// fail, the error of the first by key is returned.
+    pub fn calculate_symbols<F>(
+        series: &BTreeMap<String, Vec<T>>,
+        make_set: F,
+    ) -> Result<BTreeMap<String, SetOutputs<O>>, IndicatorError>
+    where
+        T: Send,
+        F: Fn() -> Self + Sync,
+    {
+        series
+            .par_iter()
+            .map(|(symbol, data)| (symbol.clone(), make_set().calculate(data)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|(symbol, outputs)| outputs.map(|o| (symbol, o)))
+            .collect()
+    }
+}
+
+impl<T: Clone, O> IndicatorSet<T, O> {
+    /// Feed one new value to every indicator and return their latest
+    /// outputs.
+    pub fn next(&mut self, value: T) -> Result<BTreeMap<String, Option<O>>, IndicatorError> {
+        let mut outputs = BTreeMap::new();
+        for (name, indicator) in self.indicators.iter_mut() {
+            outputs.insert(name.clone(), indicator.next(value.clone())?);
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(all(test, feature = "momentum", feature = "volume", feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::momentum::Rsi;
+    use crate::indicators::trend::{Ema, Sma};
+    use crate::indicators::volatility::{Atr, Donchian};
+    use crate::indicators::volume::Obv;
+    use crate::indicators::Candle;
+
+    fn candles(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 100.0 + (i as f64 * 0.6).sin() * 3.0;
+                Candle {
+                    timestamp: i as u64,
+                    open: c - 0.5,
+                    high: c + 1.0,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 1_000.0 + i as f64,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_set() {
+        let mut set = IndicatorSet::<f64, f64>::new();
+        assert!(set.is_empty());
+        assert_eq!(set.min_periods(), 0);
+        let out = set.calculate(&[1.0, 2.0]).unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_batch_matches_individual_indicators() {
+        let data = candles(40);
+        let mut set = IndicatorSet::<Candle, f64>::new();
+        set.add("atr", Box::new(Atr::new(5).unwrap()))
+            .add("obv", Box::new(Obv::new()))
+            .add("rsi", Box::new(Rsi::new(7).unwrap()))
+            .add("sma", Box::new(Sma::new(10).unwrap()));
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.min_periods(), 10);
+
+        let out = set.calculate(&data).unwrap();
+        assert_eq!(out.keys().collect::<Vec<_>>(), ["atr", "obv", "rsi", "sma"]);
+
+        let mut atr = Atr::new(5).unwrap();
+        let expected = atr.calculate_aligned(&data).unwrap();
+        assert_eq!(out["atr"].len(), data.len());
+        for (got, want) in out["atr"].iter().zip(&expected) {
+            match (got, want) {
+                (Some(g), Some(w)) => assert!((g - w).abs() < 1e-9),
+                (None, None) => {}
+                other => panic!("alignment mismatch: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_continues_after_batch() {
+        let data = candles(30);
+        let mut set = IndicatorSet::<Candle, f64>::new();
+        set.add("sma", Box::new(Sma::new(4).unwrap()));
+        set.calculate(&data[..29]).unwrap();
+        let tick = set.next(data[29]).unwrap();
+
+        let mut sma = Sma::new(4).unwrap();
+        let full = <Sma as Indicator<Candle, f64>>::calculate(&mut sma, &data).unwrap();
+        assert!((tick["sma"].unwrap() - full[full.len() - 1]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_non_scalar_outputs() {
+        let data = candles(12);
+        let mut set = IndicatorSet::new();
+        set.add("dc_3", Box::new(Donchian::new(3).unwrap()))
+            .add("dc_5", Box::new(Donchian::new(5).unwrap()));
+        let out = set.calculate(&data).unwrap();
+        assert_eq!(out["dc_3"].iter().filter(|v| v.is_some()).count(), 10);
+        assert_eq!(out["dc_5"].iter().filter(|v| v.is_some()).count(), 8);
+    }
+
+    #[test]
+    fn test_add_replaces_and_remove() {
+        let mut set = IndicatorSet::<f64, f64>::new();
+        set.add("ma", Box::new(Sma::new(2).unwrap()))
+            .add("ma", Box::new(Ema::new(2).unwrap()));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.names().collect::<Vec<_>>(), ["ma"]);
+        assert_eq!(set.remove("ma").unwrap().name(), "Ema");
+        assert!(!set.contains("ma"));
+    }
+
+    #[test]
+    fn test_error_propagates() {
+        let mut set = IndicatorSet::<f64, f64>::new();
+        set.add("sma", Box::new(Sma::new(2).unwrap()));
+        set.add(
+            "nan_check",
+            Box::new(crate::indicators::NanGuard::new(
+                Sma::new(2).unwrap(),
+                crate::indicators::NanPolicy::Error,
+            )),
+        );
+        assert!(matches!(
+            set.calculate(&[1.0, f64::NAN]),
+            Err(IndicatorError::InvalidInput { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_calculate_symbols() {
+        let mut series = BTreeMap::new();
+        series.insert("a".to_string(), candles(20));
+        series.insert("b".to_string(), candles(30));
+        let make = || {
+            let mut set = IndicatorSet::<Candle, f64>::new();
+            set.add("atr", Box::new(Atr::new(5).unwrap()))
+                .add("sma", Box::new(Sma::new(3).unwrap()));
+            set
+        };
+        let out = IndicatorSet::calculate_symbols(&series, make).unwrap();
+        assert_eq!(out.keys().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(out["b"]["atr"].len(), 30);
+        assert_eq!(out["a"], make().calculate(&series["a"]).unwrap());
+
+        let mut bad = BTreeMap::new();
+        bad.insert("x".to_string(), vec![1.0, f64::NAN]);
+        let guarded = IndicatorSet::calculate_symbols(&bad, || {
+            let mut set = IndicatorSet::<f64, f64>::new();
+            set.add(
+                "sma",
+                Box::new(crate::indicators::NanGuard::new(
+                    Sma::new(1).unwrap(),
+                    crate::indicators::NanPolicy::Error,
+                )),
+            );
+            set
+        });
+        assert!(guarded.is_err());
+    }
+}
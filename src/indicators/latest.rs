@@ -0,0 +1,310 @@
+//! # Lock-free latest values
+//!
+//! [`LatestValue`] is an atomic publish / subscribe cell for the most
+//! recent output of a streaming indicator. The writer swaps in a new
+//! [`Snapshot`] with one atomic store; readers load it without locks and
+//! never block the writer, so a feed updating thousands of times a
+//! second and a dashboard polling once a second run at their own pace.
+//!
+//! [`Publishing`] wraps an indicator and publishes every output it
+//! produces; [`SharedIndicator`](super::SharedIndicator) readers use the
+//! same cell. A [`Subscription`] remembers what it has seen and returns
+//! only snapshots it has not.
+//!
+//! ```
+//! use rsta::indicators::latest::Publishing;
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::Indicator;
+//!
+//! let mut sma = Publishing::new(Sma::new(2).unwrap());
+//! let mut subscription = sma.latest().subscribe();
+//! assert!(subscription.poll().is_none());
+//!
+//! sma.next(10.0).unwrap();
+//! sma.next(13.0).unwrap();
+//! let snapshot = subscription.poll().unwrap();
+//! assert_eq!((snapshot.value, snapshot.sequence), (11.5, 1));
+//! assert!(subscription.poll().is_none()); // nothing new
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+
+use crate::indicators::{Indicator, IndicatorError, IndicatorSeries, Timestamped};
+
+/// A published output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot<O> {
+    /// The output.
+    pub value: O,
+    /// Publication number, from 1, increasing with every publish.
+    pub sequence: u64,
+}
+
+#[derive(Debug)]
+struct Slot<O> {
+    snapshot: ArcSwapOption<Snapshot<O>>,
+    published: AtomicU64,
+}
+
+/// Lock-free cell holding the latest output of an indicator. Clones
+/// share the cell. Meant for one publisher and any number of readers.
+#[derive(Debug)]
+pub struct LatestValue<O> {
+    slot: Arc<Slot<O>>,
+}
+
+impl<O> Clone for LatestValue<O> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: Arc::clone(&self.slot),
+        }
+    }
+}
+
+impl<O> Default for LatestValue<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> LatestValue<O> {
+    /// Empty cell.
+    pub fn new() -> Self {
+        Self {
+            slot: Arc::new(Slot {
+                snapshot: ArcSwapOption::empty(),
+                published: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Replace the latest value.
+    pub fn publish(&self, value: O) {
+        let sequence = self.slot.published.fetch_add(1, Ordering::AcqRel) + 1;
+        self.slot
+            .snapshot
+            .store(Some(Arc::new(Snapshot { value, sequence })));
+    }
+
+    /// Remove the latest value, e.g. when the indicator is reset.
+    /// Sequence numbers keep increasing.
+    pub fn clear(&self) {
+        self.slot.snapshot.store(None);
+    }
+
+    /// The latest snapshot, if any.
+    pub fn load(&self) -> Option<Arc<Snapshot<O>>> {
+        self.slot.snapshot.load_full()
+    }
+
+    /// Number of values published so far.
+    pub fn sequence(&self) -> u64 {
+        self.slot.published.load(Ordering::Acquire)
+    }
+
+    /// Reader that reports each snapshot once.
+    pub fn subscribe(&self) -> Subscription<O> {
+        Subscription {
+            latest: self.clone(),
+            seen: 0,
+        }
+    }
+}
+
+impl<O: Clone> LatestValue<O> {
+    /// A copy of the latest value, if any.
+    pub fn get(&self) -> Option<O> {
+        self.slot.snapshot.load().as_ref().map(|s| s.value.clone())
+    }
+}
+
+/// Polling reader of a [`LatestValue`].
+#[derive(Debug, Clone)]
+pub struct Subscription<O> {
+    latest: LatestValue<O>,
+    seen: u64,
+}
+
+impl<O> Subscription<O> {
+    /// The latest snapshot if it was published since the last call.
+    /// Values published in between are skipped: a slow reader sees the
+    /// most recent one only.
+    pub fn poll(&mut self) -> Option<Arc<Snapshot<O>>> {
+        let snapshot = self.latest.load()?;
+        if snapshot.sequence <= self.seen {
+            return None;
+        }
+        self.seen = snapshot.sequence;
+        Some(snapshot)
+    }
+
+    /// The latest snapshot, new or not.
+    pub fn latest(&self) -> Option<Arc<Snapshot<O>>> {
+        self.latest.load()
+    }
+}
+
+/// Wraps an indicator and publishes each of its outputs to a
+/// [`LatestValue`]. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Publishing<I, O> {
+    inner: I,
+    latest: LatestValue<O>,
+}
+
+impl<I, O> Publishing<I, O> {
+    /// Wrap `inner` with a new, empty cell.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            latest: LatestValue::new(),
+        }
+    }
+
+    /// Handle to the cell, for readers.
+    pub fn latest(&self) -> LatestValue<O> {
+        self.latest.clone()
+    }
+
+    /// Borrow the wrapped indicator.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Unwrap the indicator. Readers keep the last published value.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I, T, O> Indicator<T, O> for Publishing<I, O>
+where
+    I: Indicator<T, O>,
+    O: Clone,
+{
+    fn calculate(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        let outputs = self.inner.calculate(data)?;
+        match outputs.last() {
+            Some(last) => self.latest.publish(last.clone()),
+            None => self.latest.clear(),
+        }
+        Ok(outputs)
+    }
+
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        let outputs = self.inner.calculate_aligned(data)?;
+        match outputs.iter().rev().flatten().next() {
+            Some(last) => self.latest.publish(last.clone()),
+            None => self.latest.clear(),
+        }
+        Ok(outputs)
+    }
+
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError> {
+        let output = self.inner.next(value)?;
+        if let Some(output) = &output {
+            self.latest.publish(output.clone());
+        }
+        Ok(output)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.latest.clear();
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.inner.current()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        self.inner.output_offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_readers_see_monotonic_snapshots() {
+        let latest = LatestValue::new();
+        let mut subscription = latest.subscribe();
+        let writer = {
+            let latest = latest.clone();
+            thread::spawn(move || {
+                for i in 1..=10_000u64 {
+                    latest.publish(i);
+                }
+            })
+        };
+        let mut last = 0;
+        while last < 10_000 {
+            if let Some(snapshot) = subscription.poll() {
+                // The value and its sequence always travel together.
+                assert_eq!(snapshot.value, snapshot.sequence);
+                assert!(snapshot.sequence > last);
+                last = snapshot.sequence;
+            }
+        }
+        writer.join().unwrap();
+        assert_eq!(latest.sequence(), 10_000);
+
+        latest.clear();
+        assert_eq!(latest.get(), None);
+        assert!(subscription.poll().is_none());
+        latest.publish(7);
+        assert_eq!(subscription.poll().map(|s| s.sequence), Some(10_001));
+    }
+
+    #[cfg(feature = "volume")]
+    #[test]
+    fn test_aligned_output_keeps_skipped_bars_in_place() {
+        use crate::indicators::volume::{Adl, ZeroRangePolicy};
+        use crate::indicators::Candle;
+
+        let candle = |close: f64, high: f64, low: f64| Candle {
+            timestamp: 0,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 100.0,
+        };
+        let data = [
+            candle(10.0, 11.0, 9.0),
+            candle(10.0, 10.0, 10.0),
+            candle(11.0, 11.0, 10.0),
+        ];
+        let mut adl = Publishing::new(Adl::new().with_zero_range(ZeroRangePolicy::Skip));
+        let aligned = adl.calculate_aligned(&data).unwrap();
+        assert_eq!(aligned, vec![Some(0.0), None, Some(100.0)]);
+        assert_eq!(adl.latest().get(), Some(100.0));
+        let series = adl.calculate_series(&data).unwrap();
+        assert_eq!(series.aligned(), vec![Some(&0.0), None, Some(&100.0)]);
+    }
+}
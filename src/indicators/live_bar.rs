@@ -0,0 +1,262 @@
+//! Updates to a forming bar
+//!
+//! A live feed revises the current, unclosed candle many times before it
+//! closes. Feeding every revision through `next()` would count it as a new
+//! bar. [`LiveBar`] wraps any `Clone` indicator and keeps its state from
+//! before the forming bar: [`next`](Indicator::next) opens a new bar and
+//! [`update_last`](LiveBar::update_last) recomputes the latest output from
+//! a revised bar, so values track the forming bar the way charting
+//! platforms do.
+//!
+//! ```
+//! use rsta::indicators::live_bar::LiveBar;
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::Indicator;
+//!
+//! let mut sma = LiveBar::new(Sma::new(2).unwrap());
+//! sma.next(10.0).unwrap();
+//!
+//! // The second bar opens at 11 and trades up to 13 before it closes.
+//! assert_eq!(sma.next(11.0).unwrap(), Some(10.5));
+//! assert_eq!(sma.update_last(12.0).unwrap(), Some(11.0));
+//! assert_eq!(sma.update_last(13.0).unwrap(), Some(11.5));
+//!
+//! // The next bar sees only the final revision.
+//! assert_eq!(sma.next(15.0).unwrap(), Some(14.0));
+//! ```
+
+use crate::indicators::{Indicator, IndicatorError, IndicatorSeries, Timestamped};
+
+/// Wraps an indicator so its latest bar can be revised with
+/// [`update_last`](Self::update_last). See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct LiveBar<I> {
+    inner: I,
+    /// State before the forming bar, restored by `update_last()`.
+    before: Option<I>,
+}
+
+impl<I> LiveBar<I> {
+    /// Wrap `inner`. It has no forming bar until the first `next()`.
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            before: None,
+        }
+    }
+
+    /// Borrow the wrapped indicator.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Unwrap, returning the inner indicator with the latest revision
+    /// applied.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    /// Whether a forming bar can be revised with `update_last()`.
+    pub fn is_forming(&self) -> bool {
+        self.before.is_some()
+    }
+
+    /// Recompute the latest output from a revised version of the bar last
+    /// passed to `next()`, without advancing the indicator
+    ///
+    /// With no forming bar (after construction, `calculate()` or `reset()`)
+    /// the value opens a new bar, exactly like `next()`. On error the
+    /// previous revision is kept.
+    ///
+    /// # Arguments
+    /// * `value` - The revised bar
+    ///
+    /// # Returns
+    /// * `Result<Option<O>, IndicatorError>` - The output for the revised bar
+    pub fn update_last<T, O>(&mut self, value: T) -> Result<Option<O>, IndicatorError>
+    where
+        I: Indicator<T, O> + Clone,
+    {
+        let Some(before) = &self.before else {
+            return self.next(value);
+        };
+        let mut revised = before.clone();
+        let out = revised.next(value)?;
+        self.inner = revised;
+        Ok(out)
+    }
+}
+
+impl<I, T, O> Indicator<T, O> for LiveBar<I>
+where
+    I: Indicator<T, O> + Clone,
+{
+    /// Every bar in `data` is treated as closed: a following
+    /// `update_last()` opens a new bar.
+    fn calculate(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        self.before = None;
+        self.inner.calculate(data)
+    }
+
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        self.before = None;
+        self.inner.calculate_aligned(data)
+    }
+
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        self.before = None;
+        self.inner.calculate_series(data)
+    }
+
+    /// Close the forming bar and open a new one with `value`. A rejected
+    /// value leaves the state untouched.
+    fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError> {
+        let before = self.inner.clone();
+        match self.inner.next(value) {
+            Ok(out) => {
+                self.before = Some(before);
+                Ok(out)
+            }
+            Err(e) => {
+                self.inner = before;
+                Err(e)
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.before = None;
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.inner.current()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        self.inner.output_offset()
+    }
+}
+
+#[cfg(all(test, feature = "volume", feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::Ema;
+    use crate::indicators::volatility::Atr;
+    use crate::indicators::volume::{Adl, Vroc, ZeroRangePolicy, ZeroVolumePolicy};
+    use crate::indicators::Candle;
+
+    fn candle(timestamp: u64, close: f64, volume: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_update_last_matches_final_revision() {
+        let mut live = LiveBar::new(Ema::new(3).unwrap());
+        let mut closed = Ema::new(3).unwrap();
+        for (i, close) in [10.0, 11.0, 12.0, 11.5, 13.0].into_iter().enumerate() {
+            // Every bar trades through two revisions before closing.
+            Indicator::<f64, f64>::next(&mut live, close - 1.0).unwrap();
+            live.update_last(close + 1.0).unwrap();
+            let got = live.update_last(close).unwrap();
+            let want = Indicator::<f64, f64>::next(&mut closed, close).unwrap();
+            assert_eq!(got, want, "bar {i}");
+        }
+        assert_eq!(
+            Indicator::<f64, f64>::current(&live),
+            Indicator::<f64, f64>::current(&closed)
+        );
+    }
+
+    #[test]
+    fn test_update_last_on_candles() {
+        let mut live = LiveBar::new(Atr::new(2).unwrap());
+        let mut closed = Atr::new(2).unwrap();
+        let bars = [candle(0, 10.0, 1.0), candle(1, 12.0, 1.0)];
+        closed.calculate(&[bars[0], candle(1, 14.0, 1.0)]).unwrap();
+
+        live.next(bars[0]).unwrap();
+        live.next(bars[1]).unwrap();
+        let revised = live.update_last(candle(1, 14.0, 1.0)).unwrap();
+        assert_eq!(revised.as_ref(), closed.current());
+    }
+
+    #[test]
+    fn test_update_last_without_forming_bar_opens_one() {
+        let mut live = LiveBar::new(Ema::new(3).unwrap());
+        assert!(!live.is_forming());
+        assert_eq!(live.update_last(4.0).unwrap(), Some(4.0));
+        assert!(live.is_forming());
+        // The bar opened above is now the one being revised.
+        assert_eq!(live.update_last(6.0).unwrap(), Some(6.0));
+
+        Indicator::<f64, f64>::calculate(&mut live, &[1.0, 2.0, 3.0]).unwrap();
+        assert!(!live.is_forming());
+        Indicator::<f64, f64>::reset(&mut live);
+        assert!(!live.is_forming());
+    }
+
+    #[test]
+    fn test_rejected_values_keep_state() {
+        let policy = ZeroVolumePolicy::Error;
+        let mut live = LiveBar::new(Vroc::new(1).unwrap().with_zero_volume(policy));
+        live.next(candle(0, 10.0, 0.0)).unwrap();
+        assert!(live.next(candle(1, 10.0, 50.0)).is_err());
+        // The rejected bar never opened: the zero-volume bar is still the one
+        // being revised.
+        assert_eq!(live.update_last(candle(0, 10.0, 100.0)).unwrap(), None);
+        assert_eq!(live.next(candle(1, 10.0, 150.0)).unwrap(), Some(50.0));
+
+        let mut adl = LiveBar::new(Adl::new().with_zero_range(ZeroRangePolicy::Error));
+        let first = adl.next(candle(0, 10.0, 100.0)).unwrap();
+        let flat = Candle {
+            high: 10.0,
+            low: 10.0,
+            ..candle(0, 10.0, 100.0)
+        };
+        assert!(adl.update_last(flat).is_err());
+        assert_eq!(adl.current().copied(), first);
+    }
+
+    #[test]
+    fn test_aligned_output_keeps_skipped_bars_in_place() {
+        let flat = Candle {
+            high: 10.0,
+            low: 10.0,
+            ..candle(1, 10.0, 100.0)
+        };
+        let data = [candle(0, 10.0, 100.0), flat, candle(2, 11.0, 100.0)];
+        let mut live = LiveBar::new(Adl::new().with_zero_range(ZeroRangePolicy::Skip));
+        let aligned = live.calculate_aligned(&data).unwrap();
+        assert_eq!(aligned.len(), 3);
+        assert!(aligned[0].is_some() && aligned[2].is_some());
+        assert_eq!(aligned[1], None);
+        assert!(!live.is_forming());
+
+        let series = live.calculate_series(&data).unwrap();
+        assert_eq!(series.aligned()[1], None);
+        assert!(series.aligned()[2].is_some());
+    }
+}
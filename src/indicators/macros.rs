@@ -0,0 +1,291 @@
+//! Macro for declaring window-based indicators
+
+/// Declare a window-based indicator without writing the boilerplate
+///
+/// The macro generates the struct, a `new(period, params...)` constructor
+/// that validates the period (and optionally the parameters), the rolling
+/// buffer, and the [`Indicator`](crate::indicators::Indicator) impl with
+/// `calculate`, `next`, `reset`, `current`, `name`, `period` and
+/// `min_periods`. The indicator emits once `period` inputs have been
+/// seen, and `update` computes each output from the window, oldest input
+/// first.
+///
+/// The generated struct derives `Debug` and `Clone`, so parameter types
+/// must implement both, and the output type must be `Clone`. The field
+/// names `period`, `window` and `last` are reserved.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::{Candle, Indicator, IndicatorError};
+///
+/// rsta::define_indicator! {
+///     /// Share of the window's volume traded on up candles.
+///     pub struct UpVolumeShare: Indicator<Candle, f64> {
+///         min_period: 1,
+///         params {
+///             /// Minimum close - open move counted as an up candle.
+///             threshold: f64,
+///         }
+///         fn validate(&self) -> Result<(), IndicatorError> {
+///             if self.threshold < 0.0 {
+///                 return Err(IndicatorError::invalid_parameter(
+///                     "threshold",
+///                     self.threshold,
+///                     "must not be negative",
+///                 ));
+///             }
+///             Ok(())
+///         }
+///         fn update(&self, window: &[Candle]) -> f64 {
+///             let total: f64 = window.iter().map(|c| c.volume).sum();
+///             let up: f64 = window
+///                 .iter()
+///                 .filter(|c| c.close - c.open > self.threshold)
+///                 .map(|c| c.volume)
+///                 .sum();
+///             if total == 0.0 { 0.0 } else { up / total }
+///         }
+///     }
+/// }
+///
+/// let candle = |open: f64, close: f64| Candle {
+///     timestamp: 0, open, high: open.max(close), low: open.min(close), close, volume: 100.0,
+/// };
+/// let mut share = UpVolumeShare::new(2, 0.0).unwrap();
+/// let values = share.calculate(&[candle(1.0, 2.0), candle(2.0, 1.0), candle(1.0, 3.0)]).unwrap();
+/// assert_eq!(values, vec![0.5, 0.5]);
+///
+/// assert!(UpVolumeShare::new(0, 0.0).is_err());
+/// assert!(UpVolumeShare::new(2, -1.0).is_err());
+/// ```
+#[macro_export]
+macro_rules! define_indicator {
+    (@validate) => {
+        fn validate(&self) -> ::core::result::Result<(), $crate::indicators::IndicatorError> {
+            ::core::result::Result::Ok(())
+        }
+    };
+    (@validate $validate:item) => {
+        $validate
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident : Indicator<$input:ty, $output:ty> {
+            min_period: $min_period:expr,
+            $(
+                params {
+                    $( $(#[$param_meta:meta])* $param:ident : $param_ty:ty ),* $(,)?
+                }
+            )?
+            $(
+                fn validate(&$validate_self:ident) -> $validate_ty:ty $validate_body:block
+            )?
+            fn update(&$update_self:ident, $window:ident : &[$window_ty:ty]) -> $update_ty:ty
+                $update_body:block
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            period: usize,
+            $($( $(#[$param_meta])* $param: $param_ty, )*)?
+            window: ::std::collections::VecDeque<$input>,
+            last: ::core::option::Option<$output>,
+        }
+
+        impl $name {
+            /// Create the indicator over the last `period` inputs
+            ///
+            /// # Returns
+            /// * `Result<Self, IndicatorError>` - The indicator, or an error
+            ///   for an invalid period or parameter
+            #[allow(clippy::too_many_arguments)]
+            $vis fn new(
+                period: usize,
+                $($( $param: $param_ty, )*)?
+            ) -> ::core::result::Result<Self, $crate::indicators::IndicatorError> {
+                $crate::indicators::utils::validate_period(period, $min_period)?;
+                let indicator = Self {
+                    period,
+                    $($( $param, )*)?
+                    window: ::std::collections::VecDeque::with_capacity(period),
+                    last: ::core::option::Option::None,
+                };
+                indicator.validate()?;
+                ::core::result::Result::Ok(indicator)
+            }
+
+            /// Reset the window.
+            $vis fn reset_state(&mut self) {
+                self.window.clear();
+                self.last = ::core::option::Option::None;
+            }
+
+            $crate::define_indicator!(@validate $(
+                fn validate(&$validate_self) -> $validate_ty $validate_body
+            )?);
+
+            fn update(&$update_self, $window: &[$window_ty]) -> $update_ty $update_body
+
+            fn step(&mut self, value: $input) -> ::core::option::Option<$output> {
+                if self.window.len() == self.period {
+                    self.window.pop_front();
+                }
+                self.window.push_back(value);
+                if self.window.len() < self.period {
+                    return ::core::option::Option::None;
+                }
+                self.window.make_contiguous();
+                let out = self.update(self.window.as_slices().0);
+                self.last = ::core::option::Option::Some(out);
+                self.last.clone()
+            }
+        }
+
+        impl $crate::indicators::Indicator<$input, $output> for $name {
+            fn calculate(
+                &mut self,
+                data: &[$input],
+            ) -> ::core::result::Result<::std::vec::Vec<$output>, $crate::indicators::IndicatorError>
+            {
+                $crate::indicators::utils::validate_data_length(data, self.period)?;
+                self.reset_state();
+                ::core::result::Result::Ok(
+                    data.iter().cloned().filter_map(|v| self.step(v)).collect(),
+                )
+            }
+
+            fn next(
+                &mut self,
+                value: $input,
+            ) -> ::core::result::Result<
+                ::core::option::Option<$output>,
+                $crate::indicators::IndicatorError,
+            > {
+                ::core::result::Result::Ok(self.step(value))
+            }
+
+            fn reset(&mut self) {
+                self.reset_state();
+            }
+
+            fn current(&self) -> ::core::option::Option<&$output> {
+                self.last.as_ref()
+            }
+
+            fn name(&self) -> &'static str {
+                stringify!($name)
+            }
+
+            fn period(&self) -> ::core::option::Option<usize> {
+                ::core::option::Option::Some(self.period)
+            }
+
+            fn min_periods(&self) -> usize {
+                self.period
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::indicators::{Candle, Indicator, IndicatorError};
+
+    crate::define_indicator! {
+        /// Midpoint of the window's range.
+        struct MidRange: Indicator<f64, f64> {
+            min_period: 1,
+            fn update(&self, window: &[f64]) -> f64 {
+                let max = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let min = window.iter().copied().fold(f64::INFINITY, f64::min);
+                (max + min) / 2.0
+            }
+        }
+    }
+
+    crate::define_indicator! {
+        /// Closes above `level` in the window.
+        struct ClosesAbove: Indicator<Candle, usize> {
+            min_period: 2,
+            params {
+                level: f64,
+            }
+            fn validate(&self) -> Result<(), IndicatorError> {
+                if !self.level.is_finite() {
+                    return Err(IndicatorError::invalid_parameter(
+                        "level",
+                        self.level,
+                        "must be finite",
+                    ));
+                }
+                Ok(())
+            }
+            fn update(&self, window: &[Candle]) -> usize {
+                window.iter().filter(|c| c.close > self.level).count()
+            }
+        }
+    }
+
+    fn candle(close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_generated_constructor_validates() {
+        assert_eq!(
+            MidRange::new(0).unwrap_err(),
+            IndicatorError::invalid_parameter("period", 0, "must be at least 1")
+        );
+        assert_eq!(
+            ClosesAbove::new(1, 10.0).unwrap_err(),
+            IndicatorError::invalid_parameter("period", 1, "must be at least 2")
+        );
+        assert_eq!(
+            ClosesAbove::new(3, f64::NAN).unwrap_err().to_string(),
+            IndicatorError::invalid_parameter("level", f64::NAN, "must be finite").to_string()
+        );
+    }
+
+    #[test]
+    fn test_generated_indicator_batch_and_stream() {
+        let data = [1.0, 5.0, 3.0, 2.0, 8.0];
+        let mut batch = MidRange::new(3).unwrap();
+        let expected = batch.calculate(&data).unwrap();
+        assert_eq!(expected, vec![3.0, 3.5, 5.0]);
+        assert!(batch.calculate(&data[..2]).is_err());
+
+        let mut stream = MidRange::new(3).unwrap();
+        let streamed: Vec<f64> = data
+            .iter()
+            .filter_map(|&v| stream.next(v).unwrap())
+            .collect();
+        assert_eq!(streamed, expected);
+        assert_eq!(stream.current(), Some(&5.0));
+        assert_eq!(stream.name(), "MidRange");
+        assert_eq!(stream.period(), Some(3));
+        assert_eq!(stream.min_periods(), 3);
+
+        stream.reset();
+        assert_eq!(stream.current(), None);
+        assert_eq!(stream.next(4.0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_generated_indicator_with_params() {
+        let mut above = ClosesAbove::new(2, 10.0).unwrap();
+        let candles: Vec<Candle> = [9.0, 11.0, 12.0, 8.0].map(candle).to_vec();
+        assert_eq!(above.calculate(&candles).unwrap(), vec![1, 2, 1]);
+        let snapshot = above.clone();
+        assert_eq!(above.next(candle(13.0)).unwrap(), Some(1));
+        assert_eq!(snapshot.current(), Some(&1));
+    }
+}
@@ -12,14 +12,34 @@
 /// - [`volume`]: Volume-based indicators like OBV and A/D Line
 /// - [`volatility`]: Volatility indicators like ATR and Bollinger Bands
 ///
+/// The [`transform`] module adds normalization and scaling transforms
+/// (min-max, z-score, log returns) for building ML feature pipelines.
+/// The [`performance`] module has risk-adjusted indicators (rolling Sharpe
+/// and Sortino ratios) over return series, and alpha and information
+/// ratio against a benchmark. The [`statistics`] module has rolling
+/// distribution-shape statistics (skewness, kurtosis) and permutation
+/// entropy.
+///
 /// ## Core Components
 ///
 /// The library is built around these core components:
 ///
 /// - [`Indicator`] trait: Common interface implemented by all indicators
 /// - [`Candle`] struct: Represents OHLCV price data
+/// - [`Tick`] struct: A single trade, input of the order-flow indicators
 /// - [`PriceDataAccessor`] trait: Provides uniform access to price data
 /// - [`IndicatorError`] enum: Standardized error handling
+/// - [`NanGuard`] wrapper: NaN / infinite input handling via a [`NanPolicy`]
+/// - [`Rounded`] wrapper: rounds outputs to decimals or a tick size via a [`Precision`]
+/// - [`Pipeline`]: chains indicators so one's output feeds the next
+/// - [`Rolling`]: custom window statistic from a closure
+/// - [`define_indicator!`](crate::define_indicator): declares a custom window indicator type
+/// - [`IndicatorSeries`]: timestamped output from [`Indicator::calculate_series`]
+/// - [`LiveBar`] wrapper: revises the forming bar of a live feed with `update_last()`
+/// - [`IndicatorSet`]: evaluates many keyed indicators in a single pass
+/// - [`AnyIndicator`] trait: type-erased indicators producing an [`IndicatorValue`]
+/// - [`IndicatorFactory`]: builds an [`AnyIndicator`] from a name and parameters
+/// - [`Descriptor`]: parameter ranges, defaults and output fields of an indicator, via [`Describe`]
 ///
 /// ## Using Indicators
 ///
@@ -76,50 +96,152 @@
 /// let atr_values = atr.calculate(&candles).unwrap();
 /// ```
 ///
+/// ## Memory bounds
+///
+/// Indicator state never grows past a fixed bound. Window indicators keep
+/// at most one buffer entry per period bar for each smoothing stage
+/// (`period` values for [`Sma`], `lookback` past values for
+/// [`VolatilityRank`], a fixed number of taps for [`DominantCycle`]), and
+/// cumulative ones such as [`Obv`] or [`Vwap`] keep running scalars.
+/// Estimators that sort their window (the median of
+/// `anomaly::MadOutlier`, the historical quantile of `risk::RollingVar`)
+/// sort a scratch copy held in the struct. Buffers are reserved at
+/// construction or reach their final size during warm-up, so `next()` no
+/// longer allocates once the indicator produces values. The same holds
+/// for the anomaly, risk, regime and pairs streams built on
+/// [`Indicator`]. The exceptions:
+///
+/// - [`RollingVwap`] holds the trades of a time window, up to
+///   [`RollingVwap::DEFAULT_MAX_TRADES`] unless set with
+///   [`RollingVwap::with_max_trades`]; the oldest trades are dropped first.
+/// - The pattern detectors keep collections that fill up to a cap: the
+///   open gaps of `GapDetector` (`with_max_open_gaps`) and the price
+///   clusters and volume-profile buckets of `SupportResistance`
+///   (`max_clusters`, `max_profile_buckets`). Other detectors keep a fixed
+///   number of recent swings or candles.
+/// - Indicators and detectors with a `Vec` output ([`MaRibbon`],
+///   [`MultiTimeframe`], `CandlestickPatterns`, `SupportResistance`,
+///   `Trendlines`, the filled gaps of `GapDetector`) allocate that output
+///   on every call.
+/// - `calculate()` allocates its output vector.
+///
+/// The `alloc_check` module (`alloc-check` feature) tests both: `next()`
+/// is allocation-free for the scalar-output indicators (including
+/// `ZScoreSpike`, `MadOutlier`, `RollingVar`, `RegimeDetector` and
+/// `RollingOls`), and live memory
+/// stays flat over long runs for the pattern detectors and [`RollingVwap`].
+///
 /// ## Common Utilities
 ///
 /// The [`utils`] module provides common calculations used across indicators.
 // Ensure volatility module is accessible
+#[cfg(feature = "volatility")]
 pub mod volatility;
 
 // Module declarations
+pub mod any_indicator;
 pub mod candle;
+pub mod descriptor;
 pub mod error;
+pub mod factory;
+pub mod fixed;
+pub mod indicator_set;
+#[cfg(feature = "latest")]
+pub mod latest;
+pub mod live_bar;
+mod macros;
+#[cfg(feature = "momentum")]
 pub mod momentum;
+pub mod nan_policy;
+pub mod performance;
+pub mod pipeline;
+pub mod precision;
+pub mod rolling;
+pub mod series;
+pub mod shared;
+pub mod statistics;
+pub mod tick;
+pub mod timeframe;
 pub mod traits;
+pub mod transform;
+#[cfg(feature = "trend")]
 pub mod trend;
 pub mod utils;
+#[cfg(feature = "volume")]
 pub mod volume;
 
+// Pattern recognition lives at the crate root; alias it here so it sits
+// next to the other per-bar studies.
+#[cfg(feature = "patterns")]
+pub use crate::patterns;
+
 // Re-export core traits and types
+pub use self::any_indicator::{AnyIndicator, IndicatorValue};
 pub use self::candle::{heikin_ashi, Candle};
+pub use self::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor, ParamKind};
 pub use self::error::IndicatorError;
+pub use self::factory::{IndicatorFactory, IndicatorParams, IndicatorSpec};
+pub use self::fixed::{FixedEma, FixedSma, FixedWma};
+pub use self::indicator_set::IndicatorSet;
+#[cfg(feature = "latest")]
+pub use self::latest::{LatestValue, Publishing};
+pub use self::live_bar::LiveBar;
+pub use self::nan_policy::{FiniteInput, NanGuard, NanPolicy};
+pub use self::performance::{InformationRatio, RollingAlpha, RollingSharpe, RollingSortino};
+pub use self::pipeline::Pipeline;
+pub use self::precision::{Precision, Rounded};
+pub use self::rolling::Rolling;
+pub use self::series::{IndicatorSeries, Timestamped};
+pub use self::shared::{IndicatorReader, SharedIndicator};
+pub use self::statistics::{Kurtosis, PermutationEntropy, Skewness};
+pub use self::tick::{Tick, TickRule, TradeSide};
+pub use self::timeframe::{resample, MultiTimeframe, Resampler};
 pub use self::traits::{Indicator, PriceDataAccessor};
+pub use self::transform::{CandleReturns, LogReturns, MinMaxScaler, ZScoreNormalizer};
 
 // Re-export momentum indicators
-pub use self::momentum::{Cci, Rsi, StochasticOscillator, StochasticResult, WilliamsR};
+#[cfg(feature = "momentum")]
+pub use self::momentum::{
+    AdaptiveRsi, AdaptiveStochastic, Cci, Rsi, RsiSmoothing, StochasticOscillator,
+    StochasticResult, WilliamsR,
+};
 
 // Re-export volatility indicators
+#[cfg(feature = "volatility")]
 pub use self::volatility::{
-    Atr, BollingerBands, BollingerBandsResult, Donchian, DonchianResult, KeltnerChannels,
-    KeltnerChannelsResult, Std,
+    Atr, AtrSmoothing, BollingerBands, BollingerBandsResult, Choppiness, Donchian, DonchianResult,
+    KeltnerChannels, KeltnerChannelsResult, RankMethod, Std, TrueRange, VolatilityMeasure,
+    VolatilityRank,
 };
 // Re-export trend indicators
+#[cfg(feature = "trend")]
 pub use self::trend::{
-    pivot_camarilla, pivot_classic, pivot_fibonacci, Adx, AdxResult, Dema, Ema, Hma, Ichimoku,
-    IchimokuResult, Macd, MacdResult, PivotResult, Sar, Sma, Tema, Wma,
+    pivot_camarilla, pivot_classic, pivot_fibonacci, Adx, AdxResult, Dema, DominantCycle,
+    ElderImpulse, Ema, EmaInit, Gmma, GmmaResult, Hma, Ichimoku, IchimokuResult, Impulse,
+    MaDistance, MaRibbon, MaRibbonResult, MaType, Macd, MacdResult, PivotResult,
+    RelativeStrengthLine, RelativeStrengthResult, Sar, Sma, SuperSmoother, Tema, Wma,
 };
 
 // Re-export volume indicators
-pub use self::volume::{Adl, Cmf, Mfi, Obv, Vroc, Vwap};
+#[cfg(feature = "volume")]
+pub use self::volume::{
+    AdOscillator, Adl, Cmf, CmfResult, CmfSignal, CumulativeDelta, Mfi, Obv, RollingVwap,
+    TradeImbalance, VolumeOscillator, Vroc, VwMacd, Vwap, ZeroRangePolicy, ZeroVolumePolicy,
+};
 
 // Re-export utility functions
 pub use self::utils::{
     calculate_ema, calculate_sma, rate_of_change, standard_deviation, validate_data_length,
-    validate_period,
+    validate_period, KahanSum, RollingMax, RollingMin, RollingStats,
 };
 
-#[cfg(test)]
+#[cfg(all(
+    test,
+    feature = "trend",
+    feature = "momentum",
+    feature = "volume",
+    feature = "volatility"
+))]
 mod tests {
     use super::*;
 
@@ -174,6 +296,28 @@ mod tests {
         assert_eq!(sma.next(10.0).unwrap(), Some(8.0)); // Third SMA
     }
 
+    #[test]
+    fn test_indicators_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        macro_rules! send_sync {
+            ($($ty:ty),* $(,)?) => { $(assert_send_sync::<$ty>();)* };
+        }
+        send_sync!(
+            AdaptiveRsi, AdaptiveStochastic, Cci, Rsi, StochasticOscillator, WilliamsR,
+            Atr, BollingerBands, Choppiness, Donchian, KeltnerChannels, Std, TrueRange,
+            VolatilityRank, Adx, Dema, DominantCycle, ElderImpulse, Ema, Gmma, Hma, Ichimoku,
+            MaDistance, MaRibbon, Macd, RelativeStrengthLine, Sar, Sma, SuperSmoother, Tema,
+            Wma, AdOscillator, Adl, Cmf, CumulativeDelta, Mfi, Obv, RollingVwap, TradeImbalance,
+            VolumeOscillator, Vroc, VwMacd, Vwap, FixedEma<3>, FixedSma<3>, FixedWma<3>, InformationRatio,
+            RollingAlpha, RollingSharpe, RollingSortino, Kurtosis, PermutationEntropy, Skewness,
+            LogReturns, MinMaxScaler, ZScoreNormalizer, CandleReturns, TickRule, Resampler, KahanSum,
+            RollingMax, RollingMin, RollingStats, LiveBar<Sma>, NanGuard<Sma, f64>,
+            Pipeline<Sma, Ema, f64>, SharedIndicator<Sma, f64, f64>, Rounded<Sma, f64>,
+        );
+        #[cfg(feature = "latest")]
+        send_sync!(Publishing<Sma, f64>);
+    }
+
     #[test]
     fn test_error_handling() {
         // Test error handling using re-exported error type
@@ -181,13 +325,725 @@ mod tests {
 
         assert!(error_result.is_err());
         match error_result {
-            Err(IndicatorError::InvalidParameter(msg)) => {
-                assert!(msg.contains("Period must be greater than"));
+            Err(IndicatorError::InvalidParameter { name, value, .. }) => {
+                assert_eq!(name, "period");
+                assert_eq!(value, "0");
             }
             _ => panic!("Expected InvalidParameter error"),
         }
     }
 
+    /// Check `min_periods()` / `output_offset()` against what `calculate()`
+    /// actually does for an indicator.
+    fn assert_warmup_contract<T: Clone, O>(indicator: &mut dyn Indicator<T, O>, data: &[T]) {
+        let name = indicator.name();
+        let min = indicator.min_periods();
+        assert!(min >= 1, "{name}: min_periods must be at least 1");
+        assert!(
+            indicator.calculate(&data[..min - 1]).is_err(),
+            "{name}: calculate() accepted {} points, min_periods() is {min}",
+            min - 1
+        );
+        let at_min = indicator.calculate(&data[..min]).unwrap();
+        assert_eq!(
+            at_min.len(),
+            min - indicator.output_offset(),
+            "{name}: output length at min_periods"
+        );
+        let full = indicator.calculate(data).unwrap();
+        assert_eq!(
+            full.len(),
+            data.len() - indicator.output_offset(),
+            "{name}: output_offset"
+        );
+    }
+
+    #[test]
+    fn test_warmup_metadata_matches_calculate() {
+        let closes: Vec<f64> = (0..80)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1)
+            .collect();
+        let candles: Vec<Candle> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle {
+                timestamp: i as u64,
+                open: c - 0.3,
+                high: c + 1.0,
+                low: c - 1.0,
+                close: c,
+                volume: 1_000.0 + i as f64,
+            })
+            .collect();
+
+        assert_warmup_contract::<f64, f64>(&mut Sma::new(5).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Ema::new(5).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Wma::new(5).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Dema::new(5).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Tema::new(5).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Hma::new(9).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut SuperSmoother::new(10).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut DominantCycle::new(), &closes);
+        assert_warmup_contract::<f64, GmmaResult>(&mut Gmma::new(), &closes);
+        assert_warmup_contract::<f64, Impulse>(&mut ElderImpulse::default(), &closes);
+        assert_warmup_contract::<f64, Impulse>(
+            &mut ElderImpulse::default().with_init(EmaInit::TALIB),
+            &closes,
+        );
+        assert_warmup_contract::<f64, GmmaResult>(
+            &mut Gmma::new().with_init(EmaInit::TALIB),
+            &closes,
+        );
+        assert_warmup_contract::<f64, f64>(&mut MaDistance::new(5, MaType::Wma).unwrap(), &closes);
+        assert_warmup_contract::<f64, MaRibbonResult>(
+            &mut MaRibbon::new(&[3, 5, 8], MaType::Sma).unwrap(),
+            &closes,
+        );
+        assert_warmup_contract(&mut Macd::new(3, 6, 4).unwrap(), &closes);
+        assert_warmup_contract(&mut Macd::with_ma(3, 6, 4, MaType::Dema).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Rsi::new(14).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut AdaptiveRsi::new(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut AdaptiveStochastic::new(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Std::new(10).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut MinMaxScaler::new(10).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut ZScoreNormalizer::new(10).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut LogReturns::new(3).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Skewness::new(10).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut Kurtosis::new(10).unwrap(), &closes);
+        assert_warmup_contract::<f64, f64>(&mut PermutationEntropy::new(10, 3).unwrap(), &closes);
+        let returns = crate::returns::simple_returns(&closes).unwrap();
+        assert_warmup_contract::<f64, f64>(&mut RollingSharpe::new(10).unwrap(), &returns);
+        assert_warmup_contract::<f64, f64>(&mut RollingSortino::new(10).unwrap(), &returns);
+        let relative: Vec<(f64, f64)> = returns.windows(2).map(|w| (w[1], w[0])).collect();
+        assert_warmup_contract::<(f64, f64), f64>(&mut RollingAlpha::new(10).unwrap(), &relative);
+        assert_warmup_contract::<(f64, f64), f64>(
+            &mut InformationRatio::new(10).unwrap(),
+            &relative,
+        );
+        let prices: Vec<(f64, f64)> = closes.windows(2).map(|w| (w[1], w[0])).collect();
+        assert_warmup_contract::<(f64, f64), RelativeStrengthResult>(
+            &mut RelativeStrengthLine::new(10).unwrap().normalized(),
+            &prices,
+        );
+        assert_warmup_contract::<f64, BollingerBandsResult>(
+            &mut BollingerBands::new(20, 2.0).unwrap(),
+            &closes,
+        );
+        assert_warmup_contract(
+            &mut volatility::keltner_channels::KeltnerChannelsPrice::new(5, 3).unwrap(),
+            &closes,
+        );
+
+        assert_warmup_contract(&mut Adx::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut Ichimoku::new(3, 5, 8).unwrap(), &candles);
+        assert_warmup_contract(&mut Sar::default_params(), &candles);
+        assert_warmup_contract(&mut StochasticOscillator::new(5, 3).unwrap(), &candles);
+        assert_warmup_contract(&mut WilliamsR::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut Cci::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut Atr::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut TrueRange::new(), &candles);
+        assert_warmup_contract(&mut Donchian::new(5).unwrap(), &candles);
+        assert_warmup_contract::<Candle, f64>(&mut Choppiness::new(5).unwrap(), &candles);
+        assert_warmup_contract::<Candle, f64>(&mut VolatilityRank::new(5, 10).unwrap(), &candles);
+        assert_warmup_contract::<Candle, f64>(
+            &mut VolatilityRank::new(5, 10)
+                .unwrap()
+                .with_measure(VolatilityMeasure::Atr)
+                .with_method(RankMethod::Range),
+            &candles,
+        );
+        assert_warmup_contract(&mut KeltnerChannels::new(3, 5, 2.0).unwrap(), &candles);
+        assert_warmup_contract(&mut Adl::new(), &candles);
+        assert_warmup_contract(&mut Cmf::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut CmfSignal::new(5, 3).unwrap(), &candles);
+        assert_warmup_contract(&mut AdOscillator::default(), &candles);
+        assert_warmup_contract(
+            &mut AdOscillator::default().with_init(EmaInit::TALIB),
+            &candles,
+        );
+        assert_warmup_contract(&mut Mfi::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut Obv::new(), &candles);
+        assert_warmup_contract(&mut Vroc::new(5).unwrap(), &candles);
+        assert_warmup_contract(&mut VolumeOscillator::new(3, 6).unwrap(), &candles);
+        assert_warmup_contract(
+            &mut VolumeOscillator::with_ma(3, 6, MaType::Sma).unwrap(),
+            &candles,
+        );
+        assert_warmup_contract(&mut VwMacd::new(3, 6, 2).unwrap(), &candles);
+        assert_warmup_contract(
+            &mut VwMacd::new(3, 6, 2).unwrap().with_init(EmaInit::TALIB),
+            &candles,
+        );
+        assert_warmup_contract(&mut Vwap::new(), &candles);
+
+        let mut ema_of_rsi: Pipeline<Rsi, Ema, f64> =
+            Pipeline::new(Rsi::new(7).unwrap(), Ema::new(5).unwrap());
+        assert_warmup_contract::<f64, f64>(&mut ema_of_rsi, &closes);
+        let mut sma_of_obv: Pipeline<Obv, Sma, f64> =
+            Pipeline::new(Obv::new(), Sma::new(5).unwrap());
+        assert_warmup_contract(&mut sma_of_obv, &candles);
+    }
+
+    fn assert_append_contract<T: Clone, O>(
+        indicator: &mut dyn Indicator<T, O>,
+        data: &[T],
+        value: impl Fn(&O) -> f64,
+    ) {
+        let name = indicator.name();
+        let full: Vec<f64> = indicator
+            .calculate(data)
+            .unwrap()
+            .iter()
+            .map(&value)
+            .collect();
+        let split = data.len() * 2 / 3;
+        let mut out = indicator.calculate(&data[..split]).unwrap();
+        let before = out.len();
+        let appended = indicator
+            .calculate_append(&mut out, &data[split..split + 1])
+            .unwrap();
+        indicator
+            .calculate_append(&mut out, &data[split + 1..])
+            .unwrap();
+        assert_eq!(
+            out.len(),
+            before + data.len() - split,
+            "{name}: appended length"
+        );
+        assert!(appended <= 1, "{name}: one new point");
+        for (i, (got, want)) in out.iter().map(&value).zip(&full).enumerate() {
+            assert!(
+                (got - want).abs() < 1e-6,
+                "{name}: value {i} after append is {got}, batch gives {want}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_calculate_append_matches_batch() {
+        let closes: Vec<f64> = (0..80)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1)
+            .collect();
+        let candles: Vec<Candle> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle {
+                timestamp: i as u64,
+                open: c - 0.3,
+                high: c + 1.0 + (i % 4) as f64 * 0.2,
+                low: c - 1.0,
+                close: c,
+                volume: 1_000.0 + i as f64,
+            })
+            .collect();
+        let id = |v: &f64| *v;
+
+        assert_append_contract::<f64, f64>(&mut Sma::new(5).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Ema::new(5).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Wma::new(5).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Dema::new(5).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Tema::new(5).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Hma::new(9).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut SuperSmoother::new(10).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut DominantCycle::new(), &closes, id);
+        assert_append_contract::<f64, Impulse>(&mut ElderImpulse::default(), &closes, |i| {
+            *i as u8 as f64
+        });
+        assert_append_contract::<f64, GmmaResult>(&mut Gmma::new(), &closes, |r| {
+            r.separation + r.long_width
+        });
+        assert_append_contract::<f64, f64>(
+            &mut MaDistance::new(5, MaType::Ema).unwrap(),
+            &closes,
+            id,
+        );
+        assert_append_contract::<f64, MaRibbonResult>(
+            &mut MaRibbon::new(&[3, 5, 8], MaType::Wma).unwrap(),
+            &closes,
+            |r| r.alignment + r.values[2],
+        );
+        assert_append_contract(&mut Macd::new(3, 6, 4).unwrap(), &closes, |m| m.histogram);
+        assert_append_contract(
+            &mut Macd::with_ma(3, 6, 4, MaType::Wma).unwrap(),
+            &closes,
+            |m| m.histogram,
+        );
+        assert_append_contract::<f64, f64>(&mut Rsi::new(14).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut AdaptiveRsi::new(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut AdaptiveStochastic::new(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Std::new(10).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut MinMaxScaler::new(10).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut ZScoreNormalizer::new(10).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut LogReturns::new(3).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Skewness::new(10).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(&mut Kurtosis::new(10).unwrap(), &closes, id);
+        assert_append_contract::<f64, f64>(
+            &mut PermutationEntropy::new(10, 3).unwrap(),
+            &closes,
+            id,
+        );
+        let returns = crate::returns::simple_returns(&closes).unwrap();
+        assert_append_contract::<f64, f64>(&mut RollingSharpe::new(10).unwrap(), &returns, id);
+        assert_append_contract::<f64, f64>(&mut RollingSortino::new(10).unwrap(), &returns, id);
+        let relative: Vec<(f64, f64)> = returns.windows(2).map(|w| (w[1], w[0])).collect();
+        assert_append_contract::<(f64, f64), f64>(
+            &mut RollingAlpha::new(10).unwrap(),
+            &relative,
+            id,
+        );
+        assert_append_contract::<(f64, f64), f64>(
+            &mut InformationRatio::new(10).unwrap(),
+            &relative,
+            id,
+        );
+        let prices: Vec<(f64, f64)> = closes.windows(2).map(|w| (w[1], w[0])).collect();
+        assert_append_contract::<(f64, f64), RelativeStrengthResult>(
+            &mut RelativeStrengthLine::new(10).unwrap().normalized(),
+            &prices,
+            |r| r.rs + r.ma,
+        );
+        assert_append_contract::<f64, BollingerBandsResult>(
+            &mut BollingerBands::new(20, 2.0).unwrap(),
+            &closes,
+            |b| b.upper,
+        );
+        assert_append_contract(
+            &mut volatility::keltner_channels::KeltnerChannelsPrice::new(5, 3).unwrap(),
+            &closes,
+            id,
+        );
+
+        assert_append_contract(&mut Adx::new(5).unwrap(), &candles, |a| a.adx);
+        assert_append_contract(&mut Ichimoku::new(3, 5, 8).unwrap(), &candles, |i| {
+            i.senkou_b
+        });
+        assert_append_contract(&mut Sar::default_params(), &candles, id);
+        assert_append_contract(
+            &mut StochasticOscillator::new(5, 3).unwrap(),
+            &candles,
+            |s| s.d,
+        );
+        assert_append_contract(&mut WilliamsR::new(5).unwrap(), &candles, id);
+        assert_append_contract(&mut Cci::new(5).unwrap(), &candles, id);
+        assert_append_contract(&mut Atr::new(5).unwrap(), &candles, id);
+        for smoothing in [AtrSmoothing::Ema, AtrSmoothing::Sma] {
+            let mut atr = Atr::new(5).unwrap().with_smoothing(smoothing);
+            assert_append_contract(&mut atr, &candles, id);
+        }
+        assert_append_contract(&mut TrueRange::new(), &candles, id);
+        assert_append_contract(&mut Donchian::new(5).unwrap(), &candles, |d| d.upper);
+        assert_append_contract::<Candle, f64>(&mut Choppiness::new(5).unwrap(), &candles, id);
+        assert_append_contract::<Candle, f64>(
+            &mut VolatilityRank::new(5, 10).unwrap(),
+            &candles,
+            id,
+        );
+        assert_append_contract::<Candle, f64>(
+            &mut VolatilityRank::new(5, 10)
+                .unwrap()
+                .with_measure(VolatilityMeasure::Atr)
+                .with_method(RankMethod::Range),
+            &candles,
+            id,
+        );
+        assert_append_contract(
+            &mut KeltnerChannels::new(3, 5, 2.0).unwrap(),
+            &candles,
+            |k| k.upper,
+        );
+        assert_append_contract(&mut Adl::new(), &candles, id);
+        assert_append_contract(&mut Cmf::new(5).unwrap(), &candles, id);
+        assert_append_contract(&mut CmfSignal::new(5, 3).unwrap(), &candles, |r| r.signal);
+        assert_append_contract(&mut AdOscillator::default(), &candles, id);
+        assert_append_contract(&mut Mfi::new(5).unwrap(), &candles, id);
+        assert_append_contract(&mut Obv::new(), &candles, id);
+        assert_append_contract(&mut Vroc::new(5).unwrap(), &candles, id);
+        assert_append_contract(&mut VolumeOscillator::new(3, 6).unwrap(), &candles, id);
+        assert_append_contract(&mut VwMacd::new(3, 6, 2).unwrap(), &candles, |r| r.signal);
+        assert_append_contract(&mut Vwap::new(), &candles, id);
+
+        let mut ema_of_rsi: Pipeline<Rsi, Ema, f64> =
+            Pipeline::new(Rsi::new(7).unwrap(), Ema::new(5).unwrap());
+        assert_append_contract::<f64, f64>(&mut ema_of_rsi, &closes, id);
+    }
+
+    #[test]
+    fn test_contracts_hold_on_generated_series() {
+        use crate::testing::{CandleGenerator, Process};
+
+        let processes = [
+            Process::random_walk(1.0),
+            Process::gbm(0.001, 0.03),
+            Process::trending(-0.3, 1.0),
+            Process::mean_reverting(100.0, 0.2, 2.0),
+        ];
+        let id = |v: &f64| *v;
+        for seed in 0..8 {
+            for process in processes {
+                let candles = CandleGenerator::new(seed).generate(process, 120);
+                let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+                assert_warmup_contract::<f64, f64>(&mut Rsi::new(14).unwrap(), &closes);
+                assert_warmup_contract(&mut Adx::new(7).unwrap(), &candles);
+                assert_append_contract::<f64, f64>(&mut Hma::new(9).unwrap(), &closes, id);
+                assert_append_contract(&mut Macd::new(5, 13, 4).unwrap(), &closes, |m| m.histogram);
+                assert_append_contract(&mut Atr::new(14).unwrap(), &candles, id);
+                assert_append_contract(&mut Mfi::new(14).unwrap(), &candles, id);
+                assert_append_contract(
+                    &mut StochasticOscillator::new(14, 3).unwrap(),
+                    &candles,
+                    |s| s.d,
+                );
+            }
+        }
+    }
+
+    fn assert_current_contract<T: Clone, O>(
+        indicator: &mut dyn Indicator<T, O>,
+        data: &[T],
+        value: impl Fn(&O) -> f64,
+    ) {
+        let name = indicator.name();
+        indicator.reset();
+        assert!(
+            indicator.current().is_none(),
+            "{name}: current() after reset"
+        );
+
+        let out = indicator.calculate(data).unwrap();
+        assert_eq!(
+            indicator.current().map(&value),
+            out.last().map(&value),
+            "{name}: current() after calculate"
+        );
+
+        indicator.reset();
+        assert!(
+            indicator.current().is_none(),
+            "{name}: current() after reset"
+        );
+        let mut latest = None;
+        for point in data {
+            if let Some(v) = indicator.next(point.clone()).unwrap() {
+                latest = Some(value(&v));
+            }
+            assert_eq!(
+                indicator.current().map(&value),
+                latest,
+                "{name}: current() while streaming"
+            );
+        }
+    }
+
+    #[test]
+    fn test_current_tracks_latest_output() {
+        let closes: Vec<f64> = (0..80)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1)
+            .collect();
+        let candles: Vec<Candle> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle {
+                timestamp: i as u64,
+                open: c - 0.3,
+                high: c + 1.0 + (i % 4) as f64 * 0.2,
+                low: c - 1.0,
+                close: c,
+                volume: 1_000.0 + i as f64,
+            })
+            .collect();
+        let id = |v: &f64| *v;
+
+        assert_current_contract::<f64, f64>(&mut Sma::new(5).unwrap(), &closes, id);
+        assert_current_contract::<Candle, f64>(&mut Sma::new(5).unwrap(), &candles, id);
+        assert_current_contract::<f64, f64>(&mut Ema::new(5).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Wma::new(5).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Dema::new(5).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Tema::new(5).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Hma::new(9).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut SuperSmoother::new(10).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut DominantCycle::new(), &closes, id);
+        assert_current_contract::<f64, GmmaResult>(&mut Gmma::new(), &closes, |r| r.short_width);
+        assert_current_contract::<f64, Impulse>(&mut ElderImpulse::default(), &closes, |i| {
+            *i as u8 as f64
+        });
+        assert_current_contract::<f64, f64>(
+            &mut MaDistance::new(5, MaType::Dema).unwrap(),
+            &closes,
+            id,
+        );
+        assert_current_contract::<f64, MaRibbonResult>(
+            &mut MaRibbon::new(&[3, 5, 8], MaType::Ema).unwrap(),
+            &closes,
+            |r| r.alignment + r.values[0],
+        );
+        assert_current_contract::<f64, f64>(&mut FixedSma::<5>::new(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut FixedWma::<5>::new(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut FixedEma::<5>::new(), &closes, id);
+        assert_current_contract(&mut Macd::new(3, 6, 4).unwrap(), &closes, |m| m.histogram);
+        assert_current_contract::<f64, f64>(&mut Rsi::new(14).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut AdaptiveRsi::new(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut AdaptiveStochastic::new(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Std::new(10).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut LogReturns::new(3).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Skewness::new(10).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(&mut Kurtosis::new(10).unwrap(), &closes, id);
+        assert_current_contract::<f64, f64>(
+            &mut PermutationEntropy::new(10, 3).unwrap(),
+            &closes,
+            id,
+        );
+        let returns = crate::returns::simple_returns(&closes).unwrap();
+        assert_current_contract::<f64, f64>(&mut RollingSharpe::new(10).unwrap(), &returns, id);
+        assert_current_contract::<f64, f64>(&mut RollingSortino::new(10).unwrap(), &returns, id);
+        let relative: Vec<(f64, f64)> = returns.windows(2).map(|w| (w[1], w[0])).collect();
+        assert_current_contract::<(f64, f64), f64>(
+            &mut RollingAlpha::new(10).unwrap(),
+            &relative,
+            id,
+        );
+        assert_current_contract::<(f64, f64), f64>(
+            &mut InformationRatio::new(10).unwrap(),
+            &relative,
+            id,
+        );
+        let prices: Vec<(f64, f64)> = closes.windows(2).map(|w| (w[1], w[0])).collect();
+        assert_current_contract::<(f64, f64), RelativeStrengthResult>(
+            &mut RelativeStrengthLine::new(10).unwrap().normalized(),
+            &prices,
+            |r| r.rs + r.ma,
+        );
+        assert_current_contract::<f64, BollingerBandsResult>(
+            &mut BollingerBands::new(20, 2.0).unwrap(),
+            &closes,
+            |b| b.upper,
+        );
+        assert_current_contract(
+            &mut volatility::keltner_channels::KeltnerChannelsPrice::new(5, 3).unwrap(),
+            &closes,
+            id,
+        );
+
+        assert_current_contract(&mut Adx::new(5).unwrap(), &candles, |a| a.adx);
+        assert_current_contract(&mut Ichimoku::new(3, 5, 8).unwrap(), &candles, |i| {
+            i.senkou_b
+        });
+        assert_current_contract(&mut Sar::default_params(), &candles, id);
+        assert_current_contract(
+            &mut StochasticOscillator::new(5, 3).unwrap(),
+            &candles,
+            |s| s.d,
+        );
+        assert_current_contract(&mut WilliamsR::new(5).unwrap(), &candles, id);
+        assert_current_contract(&mut Cci::new(5).unwrap(), &candles, id);
+        assert_current_contract(&mut Atr::new(5).unwrap(), &candles, id);
+        assert_current_contract(&mut TrueRange::new(), &candles, id);
+        assert_current_contract(&mut Donchian::new(5).unwrap(), &candles, |d| d.upper);
+        assert_current_contract::<Candle, f64>(&mut Choppiness::new(5).unwrap(), &candles, id);
+        assert_current_contract::<Candle, f64>(
+            &mut VolatilityRank::new(5, 10).unwrap(),
+            &candles,
+            id,
+        );
+        assert_current_contract::<Candle, f64>(
+            &mut VolatilityRank::new(5, 10)
+                .unwrap()
+                .with_measure(VolatilityMeasure::Atr)
+                .with_method(RankMethod::Range),
+            &candles,
+            id,
+        );
+        assert_current_contract(
+            &mut KeltnerChannels::new(3, 5, 2.0).unwrap(),
+            &candles,
+            |k| k.upper,
+        );
+        assert_current_contract(&mut Adl::new(), &candles, id);
+        assert_current_contract(&mut Cmf::new(5).unwrap(), &candles, id);
+        assert_current_contract(&mut CmfSignal::new(5, 3).unwrap(), &candles, |r| r.cmf);
+        assert_current_contract(&mut AdOscillator::default(), &candles, id);
+        assert_current_contract(&mut Mfi::new(5).unwrap(), &candles, id);
+        assert_current_contract(&mut Obv::new(), &candles, id);
+        assert_current_contract(&mut Vroc::new(5).unwrap(), &candles, id);
+        assert_current_contract(&mut VolumeOscillator::new(3, 6).unwrap(), &candles, id);
+        assert_current_contract(&mut VwMacd::new(3, 6, 2).unwrap(), &candles, |r| r.macd);
+        assert_current_contract(&mut Vwap::new(), &candles, id);
+
+        let mut ema_of_rsi: Pipeline<Rsi, Ema, f64> =
+            Pipeline::new(Rsi::new(7).unwrap(), Ema::new(5).unwrap());
+        assert_current_contract::<f64, f64>(&mut ema_of_rsi, &closes, id);
+        let mut guarded = NanGuard::new(Sma::new(5).unwrap(), NanPolicy::Skip);
+        assert_current_contract::<f64, f64>(&mut guarded, &closes, id);
+        let mut erased = any_indicator::boxed(Atr::new(5).unwrap());
+        assert_current_contract(&mut erased, &candles, IndicatorValue::primary);
+    }
+
+    /// Stream half of `data`, then check that a clone taken there replays
+    /// the rest exactly like the original.
+    fn assert_snapshot_contract<T: Clone, O, I: Indicator<T, O> + Clone>(
+        mut indicator: I,
+        data: &[T],
+        value: impl Fn(&O) -> f64,
+    ) {
+        let name = indicator.name();
+        let split = data.len() / 2;
+        for point in &data[..split] {
+            indicator.next(point.clone()).unwrap();
+        }
+        let snapshot = indicator.clone();
+        let before = indicator.current().map(&value);
+
+        let stream = |indicator: &mut I| -> Vec<Option<f64>> {
+            data[split..]
+                .iter()
+                .map(|p| indicator.next(p.clone()).unwrap().as_ref().map(&value))
+                .collect()
+        };
+        let ahead = stream(&mut indicator);
+        indicator = snapshot;
+        assert_eq!(
+            indicator.current().map(&value),
+            before,
+            "{name}: current() after restore"
+        );
+        assert_eq!(
+            stream(&mut indicator),
+            ahead,
+            "{name}: replay after restore"
+        );
+    }
+
+    #[test]
+    fn test_indicators_snapshot_with_clone() {
+        let closes: Vec<f64> = (0..80)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1)
+            .collect();
+        let candles: Vec<Candle> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle {
+                timestamp: i as u64,
+                open: c - 0.3,
+                high: c + 1.0 + (i % 4) as f64 * 0.2,
+                low: c - 1.0,
+                close: c,
+                volume: 1_000.0 + i as f64,
+            })
+            .collect();
+        let id = |v: &f64| *v;
+
+        assert_snapshot_contract::<f64, f64, _>(Sma::new(5).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Ema::new(5).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Wma::new(5).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Skewness::new(10).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Kurtosis::new(10).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(
+            PermutationEntropy::new(10, 3).unwrap(),
+            &closes,
+            id,
+        );
+        assert_snapshot_contract::<f64, f64, _>(Dema::new(5).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Tema::new(5).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Hma::new(9).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(SuperSmoother::new(10).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(DominantCycle::new(), &closes, id);
+        assert_snapshot_contract::<f64, GmmaResult, _>(Gmma::new(), &closes, |r| r.separation);
+        assert_snapshot_contract::<f64, Impulse, _>(ElderImpulse::default(), &closes, |i| {
+            *i as u8 as f64
+        });
+        assert_snapshot_contract::<f64, f64, _>(
+            MaDistance::new(5, MaType::Sma).unwrap(),
+            &closes,
+            id,
+        );
+        assert_snapshot_contract::<f64, MaRibbonResult, _>(
+            MaRibbon::new(&[3, 5, 8], MaType::Ema).unwrap(),
+            &closes,
+            |r| r.alignment + r.values[1],
+        );
+        assert_snapshot_contract::<f64, f64, _>(FixedSma::<5>::new(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(FixedWma::<5>::new(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(FixedEma::<5>::new(), &closes, id);
+        assert_snapshot_contract(Macd::new(3, 6, 4).unwrap(), &closes, |m| m.histogram);
+        assert_snapshot_contract::<f64, f64, _>(Rsi::new(14).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(AdaptiveRsi::new(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(AdaptiveStochastic::new(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(Std::new(10).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(MinMaxScaler::new(10).unwrap(), &closes, id);
+        assert_snapshot_contract::<f64, BollingerBandsResult, _>(
+            BollingerBands::new(20, 2.0).unwrap(),
+            &closes,
+            |b| b.upper,
+        );
+        assert_snapshot_contract(
+            volatility::keltner_channels::KeltnerChannelsPrice::new(5, 3).unwrap(),
+            &closes,
+            id,
+        );
+
+        assert_snapshot_contract(Adx::new(5).unwrap(), &candles, |a| a.adx);
+        assert_snapshot_contract(Ichimoku::new(3, 5, 8).unwrap(), &candles, |i| i.senkou_b);
+        assert_snapshot_contract(Sar::default_params(), &candles, id);
+        assert_snapshot_contract(StochasticOscillator::new(5, 3).unwrap(), &candles, |s| s.d);
+        assert_snapshot_contract(WilliamsR::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(Cci::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(Atr::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(TrueRange::new(), &candles, id);
+        assert_snapshot_contract(Donchian::new(5).unwrap(), &candles, |d| d.upper);
+        assert_snapshot_contract(Choppiness::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(VolatilityRank::new(5, 10).unwrap(), &candles, id);
+        assert_snapshot_contract(KeltnerChannels::new(3, 5, 2.0).unwrap(), &candles, |k| {
+            k.upper
+        });
+        assert_snapshot_contract(Adl::new(), &candles, id);
+        assert_snapshot_contract(Cmf::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(CmfSignal::new(5, 3).unwrap(), &candles, |r| r.signal);
+        assert_snapshot_contract(AdOscillator::default(), &candles, id);
+        assert_snapshot_contract(Mfi::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(Obv::new(), &candles, id);
+        assert_snapshot_contract(Vroc::new(5).unwrap(), &candles, id);
+        assert_snapshot_contract(VolumeOscillator::new(3, 6).unwrap(), &candles, id);
+        assert_snapshot_contract(VwMacd::new(3, 6, 2).unwrap(), &candles, |r| r.histogram);
+        assert_snapshot_contract(Vwap::new(), &candles, id);
+
+        let ema_of_rsi: Pipeline<Rsi, Ema, f64> =
+            Pipeline::new(Rsi::new(7).unwrap(), Ema::new(5).unwrap());
+        assert_snapshot_contract::<f64, f64, _>(ema_of_rsi, &closes, id);
+        let guarded = NanGuard::new(Sma::new(5).unwrap(), NanPolicy::Skip);
+        assert_snapshot_contract::<f64, f64, _>(guarded, &closes, id);
+        assert_snapshot_contract::<f64, f64, _>(LiveBar::new(Ema::new(5).unwrap()), &closes, id);
+    }
+
+    #[test]
+    fn test_tick_indicators_hold_contracts() {
+        let ticks: Vec<Tick> = (0..120u64)
+            .map(|i| Tick {
+                timestamp: i * 3,
+                price: 50.0 + (i as f64 * 0.45).sin() * 2.0,
+                size: 1.0 + (i % 7) as f64,
+                side: match i % 5 {
+                    0 => TradeSide::Buy,
+                    1 => TradeSide::Sell,
+                    _ => TradeSide::Unknown,
+                },
+            })
+            .collect();
+        let id = |v: &f64| *v;
+
+        assert_warmup_contract(&mut CumulativeDelta::new(), &ticks);
+        assert_warmup_contract(&mut TradeImbalance::new(10).unwrap(), &ticks);
+        assert_warmup_contract(&mut RollingVwap::new(30).unwrap(), &ticks);
+        assert_append_contract(&mut CumulativeDelta::new(), &ticks, id);
+        assert_append_contract(&mut TradeImbalance::new(10).unwrap(), &ticks, id);
+        assert_append_contract(&mut RollingVwap::new(30).unwrap(), &ticks, id);
+        assert_current_contract(&mut CumulativeDelta::new(), &ticks, id);
+        assert_current_contract(&mut TradeImbalance::new(10).unwrap(), &ticks, id);
+        assert_current_contract(&mut RollingVwap::new(30).unwrap(), &ticks, id);
+        assert_snapshot_contract(CumulativeDelta::new(), &ticks, id);
+        assert_snapshot_contract(TradeImbalance::new(10).unwrap(), &ticks, id);
+        assert_snapshot_contract(RollingVwap::new(30).unwrap(), &ticks, id);
+    }
+
     #[test]
     fn test_utility_functions() {
         // Test a utility function
@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+
+use crate::indicators::trend::{DominantCycle, RoofingFilter, SuperSmoother};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Ehlers' adaptive RSI.
+///
+/// An RSI whose lookback follows the market: price first goes through a
+/// roofing filter (a high-pass at `max_period` and a [`SuperSmoother`] at
+/// `min_period` bars), the [`DominantCycle`] estimator measures the
+/// current cycle, and the RSI is taken over half of it — the span of one
+/// swing. The raw ratio of up-moves is then smoothed with a
+/// [`SuperSmoother`] and scaled to `0..=100`.
+///
+/// The dominant cycle is clamped to `[min_period, max_period]`, 10 and 48
+/// bars by default. The first value comes once `max_period / 2 + 1` bars
+/// have been seen; [`lookback`](AdaptiveRsi::lookback) reports the length
+/// used on the last bar.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::momentum::AdaptiveRsi;
+/// use rsta::indicators::Indicator;
+///
+/// let mut rsi = AdaptiveRsi::new();
+/// // A 20-bar cycle: the RSI looks back about 10 bars
+/// let prices: Vec<f64> = (0..300)
+///     .map(|i| 100.0 + 5.0 * (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin())
+///     .collect();
+/// let values = rsi.calculate(&prices).unwrap();
+/// assert_eq!(values.len(), prices.len() - 24);
+/// assert!(values.iter().all(|v| (0.0..=100.0).contains(v)));
+/// assert!((9..=11).contains(&rsi.lookback().unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveRsi {
+    max_period: usize,
+    roofing: RoofingFilter,
+    cycle: DominantCycle,
+    smoother: SuperSmoother,
+    filtered: VecDeque<f64>,
+    lookback: Option<usize>,
+    last: Option<f64>,
+}
+
+impl Default for AdaptiveRsi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaptiveRsi {
+    /// Adaptive RSI over cycles of 10 to 48 bars.
+    pub fn new() -> Self {
+        Self::with_range(10, 48).expect("default range is valid")
+    }
+
+    /// Adaptive RSI over cycles of `min_period` to `max_period` bars
+    ///
+    /// # Arguments
+    /// * `min_period` - Shortest cycle, also the SuperSmoother period (must be at least 2)
+    /// * `max_period` - Longest cycle, also the high-pass period (must be above `min_period`)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn with_range(min_period: usize, max_period: usize) -> Result<Self, IndicatorError> {
+        let cycle = DominantCycle::with_range(min_period, max_period)?;
+        Ok(Self {
+            max_period,
+            roofing: RoofingFilter::new(max_period, min_period)?,
+            cycle,
+            smoother: SuperSmoother::new(min_period)?,
+            filtered: VecDeque::with_capacity(max_period / 2 + 1),
+            lookback: None,
+            last: None,
+        })
+    }
+
+    /// Shortest and longest cycle considered
+    pub fn range(&self) -> (usize, usize) {
+        self.cycle.range()
+    }
+
+    /// RSI length used on the last bar: half the dominant cycle
+    pub fn lookback(&self) -> Option<usize> {
+        self.lookback
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.roofing.reset();
+        self.cycle.reset_state();
+        self.smoother.reset_state();
+        self.filtered.clear();
+        self.lookback = None;
+        self.last = None;
+    }
+
+    fn capacity(&self) -> usize {
+        self.max_period / 2 + 1
+    }
+
+    fn step(&mut self, price: f64) -> Option<f64> {
+        let filtered = self.roofing.step(price);
+        let period = self.cycle.step(price);
+        if self.filtered.len() == self.capacity() {
+            self.filtered.pop_back();
+        }
+        self.filtered.push_front(filtered);
+        if self.filtered.len() < self.capacity() {
+            return None;
+        }
+
+        let lookback = ((period / 2.0).round() as usize).clamp(1, self.capacity() - 1);
+        let (mut up, mut down) = (0.0, 0.0);
+        for i in 0..lookback {
+            let change = self.filtered[i] - self.filtered[i + 1];
+            if change > 0.0 {
+                up += change;
+            } else {
+                down -= change;
+            }
+        }
+        let ratio = if up + down > 0.0 {
+            up / (up + down)
+        } else {
+            0.5
+        };
+        let value = (self.smoother.step(ratio) * 100.0).clamp(0.0, 100.0);
+        self.lookback = Some(lookback);
+        self.last = Some(value);
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for AdaptiveRsi {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.capacity())?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&price| self.step(price)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "AdaptiveRsi"
+    }
+
+    fn min_periods(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl Indicator<Candle, f64> for AdaptiveRsi {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "AdaptiveRsi"
+    }
+
+    fn min_periods(&self) -> usize {
+        self.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn sine(period: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| 100.0 + 5.0 * (2.0 * PI * i as f64 / period).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_lookback_follows_the_cycle() {
+        for (period, expected) in [(12.0, 6), (40.0, 20)] {
+            let mut rsi = AdaptiveRsi::new();
+            Indicator::<f64, f64>::calculate(&mut rsi, &sine(period, 400)).unwrap();
+            let lookback = rsi.lookback().unwrap();
+            assert!(
+                lookback.abs_diff(expected) <= 2,
+                "period {period}, lookback {lookback}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_swings_between_extremes() {
+        let mut rsi = AdaptiveRsi::new();
+        let values = Indicator::<f64, f64>::calculate(&mut rsi, &sine(20.0, 300)).unwrap();
+        let tail = &values[100..];
+        let max = tail.iter().cloned().fold(f64::MIN, f64::max);
+        let min = tail.iter().cloned().fold(f64::MAX, f64::min);
+        assert!(max > 80.0 && min < 20.0, "min {min}, max {max}");
+
+        assert!(AdaptiveRsi::with_range(10, 10).is_err());
+        assert_eq!(AdaptiveRsi::with_range(6, 30).unwrap().range(), (6, 30));
+        rsi.reset_state();
+        assert_eq!(rsi.lookback(), None);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::VecDeque;
+
+use crate::indicators::trend::{DominantCycle, RoofingFilter, SuperSmoother};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Ehlers' adaptive stochastic.
+///
+/// A stochastic %K whose window is the dominant cycle: price goes through
+/// a roofing filter (a high-pass at `max_period` and a [`SuperSmoother`]
+/// at `min_period` bars), the [`DominantCycle`] estimator measures the
+/// current cycle, and the filtered value is placed between the highest
+/// and lowest filtered values of the last cycle. The result is smoothed
+/// with a [`SuperSmoother`] and scaled to `0..=100`.
+///
+/// The dominant cycle is clamped to `[min_period, max_period]`, 10 and 48
+/// bars by default. The first value comes once `max_period` bars have
+/// been seen; [`lookback`](AdaptiveStochastic::lookback) reports the
+/// window used on the last bar.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::momentum::AdaptiveStochastic;
+/// use rsta::indicators::Indicator;
+///
+/// let mut stoch = AdaptiveStochastic::new();
+/// let prices: Vec<f64> = (0..300)
+///     .map(|i| 100.0 + 5.0 * (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin())
+///     .collect();
+/// let values = stoch.calculate(&prices).unwrap();
+/// assert_eq!(values.len(), prices.len() - 47);
+/// assert!(values.iter().all(|v| (0.0..=100.0).contains(v)));
+/// assert!((18..=22).contains(&stoch.lookback().unwrap()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveStochastic {
+    max_period: usize,
+    roofing: RoofingFilter,
+    cycle: DominantCycle,
+    smoother: SuperSmoother,
+    filtered: VecDeque<f64>,
+    lookback: Option<usize>,
+    last: Option<f64>,
+}
+
+impl Default for AdaptiveStochastic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaptiveStochastic {
+    /// Adaptive stochastic over cycles of 10 to 48 bars.
+    pub fn new() -> Self {
+        Self::with_range(10, 48).expect("default range is valid")
+    }
+
+    /// Adaptive stochastic over cycles of `min_period` to `max_period` bars
+    ///
+    /// # Arguments
+    /// * `min_period` - Shortest cycle, also the SuperSmoother period (must be at least 2)
+    /// * `max_period` - Longest cycle, also the high-pass period (must be above `min_period`)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn with_range(min_period: usize, max_period: usize) -> Result<Self, IndicatorError> {
+        let cycle = DominantCycle::with_range(min_period, max_period)?;
+        Ok(Self {
+            max_period,
+            roofing: RoofingFilter::new(max_period, min_period)?,
+            cycle,
+            smoother: SuperSmoother::new(min_period)?,
+            filtered: VecDeque::with_capacity(max_period),
+            lookback: None,
+            last: None,
+        })
+    }
+
+    /// Shortest and longest cycle considered
+    pub fn range(&self) -> (usize, usize) {
+        self.cycle.range()
+    }
+
+    /// Window used on the last bar: the dominant cycle
+    pub fn lookback(&self) -> Option<usize> {
+        self.lookback
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.roofing.reset();
+        self.cycle.reset_state();
+        self.smoother.reset_state();
+        self.filtered.clear();
+        self.lookback = None;
+        self.last = None;
+    }
+
+    fn step(&mut self, price: f64) -> Option<f64> {
+        let filtered = self.roofing.step(price);
+        let period = self.cycle.step(price);
+        if self.filtered.len() == self.max_period {
+            self.filtered.pop_back();
+        }
+        self.filtered.push_front(filtered);
+        if self.filtered.len() < self.max_period {
+            return None;
+        }
+
+        let lookback = (period.round() as usize).clamp(1, self.max_period);
+        let window = self.filtered.iter().take(lookback);
+        let (low, high) = window.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+        let k = if high > low {
+            (filtered - low) / (high - low)
+        } else {
+            0.5
+        };
+        let value = (self.smoother.step(k) * 100.0).clamp(0.0, 100.0);
+        self.lookback = Some(lookback);
+        self.last = Some(value);
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for AdaptiveStochastic {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.max_period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&price| self.step(price)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "AdaptiveStochastic"
+    }
+
+    fn min_periods(&self) -> usize {
+        self.max_period
+    }
+}
+
+impl Indicator<Candle, f64> for AdaptiveStochastic {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "AdaptiveStochastic"
+    }
+
+    fn min_periods(&self) -> usize {
+        self.max_period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn sine(period: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| 100.0 + 5.0 * (2.0 * PI * i as f64 / period).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_window_follows_the_cycle() {
+        for period in [12.0, 35.0] {
+            let mut stoch = AdaptiveStochastic::new();
+            let values = Indicator::<f64, f64>::calculate(&mut stoch, &sine(period, 400)).unwrap();
+            let lookback = stoch.lookback().unwrap() as f64;
+            assert!(
+                (lookback - period).abs() <= period * 0.15,
+                "period {period}, lookback {lookback}"
+            );
+            let tail = &values[150..];
+            let max = tail.iter().cloned().fold(f64::MIN, f64::max);
+            let min = tail.iter().cloned().fold(f64::MAX, f64::min);
+            assert!(max > 80.0 && min < 20.0, "min {min}, max {max}");
+        }
+    }
+
+    #[test]
+    fn test_flat_input_and_range() {
+        let mut stoch = AdaptiveStochastic::with_range(6, 20).unwrap();
+        let values = Indicator::<f64, f64>::calculate(&mut stoch, &[50.0; 40]).unwrap();
+        assert_eq!(values.len(), 21);
+        assert!(values.iter().all(|v| (v - 50.0).abs() < 1e-9));
+        assert!(AdaptiveStochastic::with_range(1, 20).is_err());
+    }
+}
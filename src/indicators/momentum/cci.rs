@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
@@ -26,10 +27,11 @@ use std::collections::VecDeque;
 /// let values = cci.calculate(&candles).unwrap();
 /// assert!(!values.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Cci {
     period: usize,
     tp_buffer: VecDeque<f64>,
+    last: Option<f64>,
 }
 
 impl Cci {
@@ -38,13 +40,15 @@ impl Cci {
         validate_period(period, 1)?;
         Ok(Self {
             period,
-            tp_buffer: VecDeque::with_capacity(period),
+            tp_buffer: VecDeque::with_capacity(period + 1),
+            last: None,
         })
     }
 
     /// Reset internal state.
     pub fn reset_state(&mut self) {
         self.tp_buffer.clear();
+        self.last = None;
     }
 
     fn typical_price(c: &Candle) -> f64 {
@@ -63,10 +67,13 @@ impl Cci {
         let n = self.period as f64;
         let sma: f64 = self.tp_buffer.iter().sum::<f64>() / n;
         let mean_dev: f64 = self.tp_buffer.iter().map(|x| (x - sma).abs()).sum::<f64>() / n;
-        if mean_dev == 0.0 {
-            return Some(0.0);
-        }
-        Some((tp - sma) / (0.015 * mean_dev))
+        let cci = if mean_dev == 0.0 {
+            0.0
+        } else {
+            (tp - sma) / (0.015 * mean_dev)
+        };
+        self.last = Some(cci);
+        self.last
     }
 }
 
@@ -91,6 +98,10 @@ impl Indicator<Candle, f64> for Cci {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Cci"
     }
@@ -98,6 +109,23 @@ impl Indicator<Candle, f64> for Cci {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Cci {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "cci",
+            title: "Commodity Channel Index",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -2,18 +2,23 @@
 //!
 //! This module contains indicators that measure the rate of change or momentum of price movements.
 //! These include the Relative Strength Index (RSI), Stochastic Oscillator, Williams %R,
-//! and the Commodity Channel Index (CCI).
+//! and the Commodity Channel Index (CCI), plus Ehlers' adaptive RSI and stochastic whose
+//! lookback follows the dominant cycle.
 //!
 //! Momentum indicators are useful for identifying overbought and oversold conditions,
 //! trend strength, and potential reversals.
 
+pub mod adaptive_rsi;
+pub mod adaptive_stochastic;
 pub mod cci;
 pub mod rsi;
 pub mod stochastic_oscillator;
 pub mod williams_r;
 
 // Re-export public types to maintain the same interface
+pub use self::adaptive_rsi::AdaptiveRsi;
+pub use self::adaptive_stochastic::AdaptiveStochastic;
 pub use self::cci::Cci;
-pub use self::rsi::Rsi;
+pub use self::rsi::{Rsi, RsiSmoothing};
 pub use self::stochastic_oscillator::{StochasticOscillator, StochasticResult};
 pub use self::williams_r::WilliamsR;
@@ -1,7 +1,26 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
 
+/// How [`Rsi`] averages gains and losses.
+///
+/// Every variant seeds with the simple average of the first `period`
+/// changes; they differ in how later changes are folded in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RsiSmoothing {
+    /// Wilder's smoothing, `avg = (avg * (period - 1) + x) / period` — the
+    /// original RSI, used by TA-Lib and most charting platforms.
+    #[default]
+    Wilder,
+    /// Simple moving average of the last `period` changes (Cutler's RSI),
+    /// which does not depend on where the series starts.
+    Sma,
+    /// Exponential smoothing with `alpha = 2 / (period + 1)`.
+    Ema,
+}
+
 /// Relative Strength Index (RSI) indicator
 ///
 /// RSI measures the magnitude of recent price changes to evaluate
@@ -26,14 +45,16 @@ use std::collections::VecDeque;
 /// // Calculate RSI values
 /// let rsi_values = rsi.calculate(&prices).unwrap();
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Rsi {
     period: usize,
+    smoothing: RsiSmoothing,
     prev_price: Option<f64>,
     gains: VecDeque<f64>,
     losses: VecDeque<f64>,
     avg_gain: Option<f64>,
     avg_loss: Option<f64>,
+    last: Option<f64>,
 }
 
 impl Rsi {
@@ -48,14 +69,33 @@ impl Rsi {
         validate_period(period, 1)?;
         Ok(Self {
             period,
+            smoothing: RsiSmoothing::Wilder,
             prev_price: None,
-            gains: VecDeque::with_capacity(period),
-            losses: VecDeque::with_capacity(period),
+            gains: VecDeque::with_capacity(period + 1),
+            losses: VecDeque::with_capacity(period + 1),
             avg_gain: None,
             avg_loss: None,
+            last: None,
         })
     }
 
+    /// Choose how gains and losses are averaged (see [`RsiSmoothing`])
+    ///
+    /// # Arguments
+    /// * `smoothing` - Averaging method
+    ///
+    /// # Returns
+    /// * `Self` - The RSI with the new averaging method
+    pub fn with_smoothing(mut self, smoothing: RsiSmoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Averaging method in use
+    pub fn smoothing(&self) -> RsiSmoothing {
+        self.smoothing
+    }
+
     /// Calculate a single RSI value from average gain and loss
     ///
     /// # Arguments
@@ -86,213 +126,117 @@ impl Rsi {
         self.losses.clear();
         self.avg_gain = None;
         self.avg_loss = None;
+        self.last = None;
     }
-}
-
-impl Indicator<f64, f64> for Rsi {
-    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
-        validate_data_length(data, self.period + 1)?;
-
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period);
-
-        // Reset state
-        self.reset_state();
-
-        // First, calculate price changes
-        let mut price_changes = Vec::with_capacity(n - 1);
-        for i in 1..n {
-            price_changes.push(data[i] - data[i - 1]);
-        }
 
-        // Calculate initial gains and losses
-        let mut gains = Vec::with_capacity(self.period);
-        let mut losses = Vec::with_capacity(self.period);
-
-        for &change in price_changes.iter().take(self.period) {
-            if change > 0.0 {
-                gains.push(change);
-                losses.push(0.0);
-            } else {
-                gains.push(0.0);
-                losses.push(-change);
+    /// Fold one average with the latest change according to the smoothing.
+    fn smooth(&self, avg: f64, window: &VecDeque<f64>, latest: f64) -> f64 {
+        let period = self.period as f64;
+        match self.smoothing {
+            RsiSmoothing::Wilder => (avg * (period - 1.0) + latest) / period,
+            RsiSmoothing::Sma => window.iter().sum::<f64>() / period,
+            RsiSmoothing::Ema => {
+                let alpha = 2.0 / (period + 1.0);
+                latest * alpha + avg * (1.0 - alpha)
             }
         }
+    }
 
-        // Calculate first average gain and loss
-        let mut avg_gain = gains.iter().sum::<f64>() / self.period as f64;
-        let mut avg_loss = losses.iter().sum::<f64>() / self.period as f64;
-
-        // Calculate first RSI
-        result.push(Self::calculate_rsi(avg_gain, avg_loss));
-
-        // Calculate the rest using the smoothed method
-        for change in price_changes.iter().skip(self.period).copied() {
-            let gain = if change > 0.0 { change } else { 0.0 };
-            let loss = if change < 0.0 { -change } else { 0.0 };
-
-            // Use Wilder's smoothing method
-            avg_gain = (avg_gain * (self.period - 1) as f64 + gain) / self.period as f64;
-            avg_loss = (avg_loss * (self.period - 1) as f64 + loss) / self.period as f64;
-
-            result.push(Self::calculate_rsi(avg_gain, avg_loss));
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let prev = self.prev_price.replace(value)?;
+        let change = value - prev;
+        let gain = if change > 0.0 { change } else { 0.0 };
+        let loss = if change < 0.0 { -change } else { 0.0 };
+
+        self.gains.push_back(gain);
+        self.losses.push_back(loss);
+        if self.gains.len() > self.period {
+            self.gains.pop_front();
+            self.losses.pop_front();
+        }
+        if self.gains.len() < self.period {
+            return None;
         }
 
-        Ok(result)
+        let (avg_gain, avg_loss) = match (self.avg_gain, self.avg_loss) {
+            (Some(avg_gain), Some(avg_loss)) => (
+                self.smooth(avg_gain, &self.gains, gain),
+                self.smooth(avg_loss, &self.losses, loss),
+            ),
+            // Initial average calculation
+            _ => (
+                self.gains.iter().sum::<f64>() / self.period as f64,
+                self.losses.iter().sum::<f64>() / self.period as f64,
+            ),
+        };
+        self.avg_gain = Some(avg_gain);
+        self.avg_loss = Some(avg_loss);
+
+        self.last = Some(Self::calculate_rsi(avg_gain, avg_loss));
+        self.last
     }
+}
 
-    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
-        if let Some(prev) = self.prev_price {
-            let change = value - prev;
-            let gain = if change > 0.0 { change } else { 0.0 };
-            let loss = if change < 0.0 { -change } else { 0.0 };
-
-            self.gains.push_back(gain);
-            self.losses.push_back(loss);
-
-            if self.gains.len() > self.period {
-                self.gains.pop_front();
-                self.losses.pop_front();
-            }
-
-            if self.gains.len() < self.period {
-                self.avg_gain = None;
-                self.avg_loss = None;
-                self.prev_price = Some(value);
-                return Ok(None);
-            }
-
-            // Calculate/update average gain and loss
-            if let (Some(avg_gain), Some(avg_loss)) = (self.avg_gain, self.avg_loss) {
-                // Use Wilder's smoothing method for ongoing calculations
-                self.avg_gain =
-                    Some((avg_gain * (self.period - 1) as f64 + gain) / self.period as f64);
-                self.avg_loss =
-                    Some((avg_loss * (self.period - 1) as f64 + loss) / self.period as f64);
-            } else {
-                // Initial average calculation
-                self.avg_gain = Some(self.gains.iter().sum::<f64>() / self.period as f64);
-                self.avg_loss = Some(self.losses.iter().sum::<f64>() / self.period as f64);
-            }
+impl Indicator<f64, f64> for Rsi {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period + 1)?;
 
-            let rsi = Self::calculate_rsi(self.avg_gain.unwrap(), self.avg_loss.unwrap());
+        // Batch and streaming share one update path, and the state is left
+        // where `next()` can continue from the end of `data`.
+        self.reset_state();
+        Ok(data.iter().filter_map(|&v| self.step(v)).collect())
+    }
 
-            self.prev_price = Some(value);
-            Ok(Some(rsi))
-        } else {
-            self.prev_price = Some(value);
-            Ok(None)
-        }
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
     }
 
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
 }
 
 impl Indicator<Candle, f64> for Rsi {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
-        validate_data_length(data, self.period + 1)?;
-
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period);
-
-        // Reset state
-        self.reset_state();
-
-        // Extract close prices
         let close_prices: Vec<f64> = data.iter().map(|candle| candle.close).collect();
-
-        // First, calculate price changes
-        let mut price_changes = Vec::with_capacity(n - 1);
-        for i in 1..n {
-            price_changes.push(close_prices[i] - close_prices[i - 1]);
-        }
-
-        // Calculate initial gains and losses
-        let mut gains = Vec::with_capacity(self.period);
-        let mut losses = Vec::with_capacity(self.period);
-
-        for &change in price_changes.iter().take(self.period) {
-            if change > 0.0 {
-                gains.push(change);
-                losses.push(0.0);
-            } else {
-                gains.push(0.0);
-                losses.push(-change);
-            }
-        }
-
-        // Calculate first average gain and loss
-        let mut avg_gain = gains.iter().sum::<f64>() / self.period as f64;
-        let mut avg_loss = losses.iter().sum::<f64>() / self.period as f64;
-
-        // Calculate first RSI
-        result.push(Self::calculate_rsi(avg_gain, avg_loss));
-
-        // Calculate the rest using the smoothed method
-        for change in price_changes.iter().skip(self.period).copied() {
-            let gain = if change > 0.0 { change } else { 0.0 };
-            let loss = if change < 0.0 { -change } else { 0.0 };
-
-            // Use Wilder's smoothing method
-            avg_gain = (avg_gain * (self.period - 1) as f64 + gain) / self.period as f64;
-            avg_loss = (avg_loss * (self.period - 1) as f64 + loss) / self.period as f64;
-
-            result.push(Self::calculate_rsi(avg_gain, avg_loss));
-        }
-
-        Ok(result)
+        Indicator::<f64, f64>::calculate(self, &close_prices)
     }
 
     fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
-        let close_price = candle.close;
-
-        if let Some(prev) = self.prev_price {
-            let change = close_price - prev;
-            let gain = if change > 0.0 { change } else { 0.0 };
-            let loss = if change < 0.0 { -change } else { 0.0 };
-
-            self.gains.push_back(gain);
-            self.losses.push_back(loss);
-
-            if self.gains.len() > self.period {
-                self.gains.pop_front();
-                self.losses.pop_front();
-            }
-
-            if self.gains.len() < self.period {
-                self.avg_gain = None;
-                self.avg_loss = None;
-                self.prev_price = Some(close_price);
-                return Ok(None);
-            }
+        Ok(self.step(candle.close))
+    }
 
-            // Calculate/update average gain and loss
-            if let (Some(avg_gain), Some(avg_loss)) = (self.avg_gain, self.avg_loss) {
-                // Use Wilder's smoothing method for ongoing calculations
-                self.avg_gain =
-                    Some((avg_gain * (self.period - 1) as f64 + gain) / self.period as f64);
-                self.avg_loss =
-                    Some((avg_loss * (self.period - 1) as f64 + loss) / self.period as f64);
-            } else {
-                // Initial average calculation
-                self.avg_gain = Some(self.gains.iter().sum::<f64>() / self.period as f64);
-                self.avg_loss = Some(self.losses.iter().sum::<f64>() / self.period as f64);
-            }
+    fn reset(&mut self) {
+        self.reset_state();
+    }
 
-            let rsi = Self::calculate_rsi(self.avg_gain.unwrap(), self.avg_loss.unwrap());
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
 
-            self.prev_price = Some(close_price);
-            Ok(Some(rsi))
-        } else {
-            self.prev_price = Some(close_price);
-            Ok(None)
-        }
+    fn min_periods(&self) -> usize {
+        self.period + 1
     }
+}
 
-    fn reset(&mut self) {
-        self.reset_state();
+impl Describe for Rsi {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "rsi",
+            title: "Relative Strength Index",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -792,4 +736,25 @@ mod tests {
         let result = rsi.calculate(&down_candles).unwrap();
         assert_eq!(result[0], 0.0); // With only losses, RSI should be 0
     }
+
+    #[test]
+    fn test_rsi_smoothing_variants() {
+        // Changes: +1, +2, -1, +2. All variants seed with avg gain 1.5 and
+        // avg loss 0, then diverge.
+        let prices = [1.0, 2.0, 4.0, 3.0, 5.0];
+        let run = |smoothing| {
+            let mut rsi = Rsi::new(2).unwrap().with_smoothing(smoothing);
+            assert_eq!(rsi.smoothing(), smoothing);
+            Indicator::<f64, f64>::calculate(&mut rsi, &prices).unwrap()
+        };
+        let close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9);
+
+        let wilder = run(RsiSmoothing::Wilder);
+        assert!(close(&wilder, &[100.0, 60.0, 100.0 - 100.0 / 6.5]));
+        let cutler = run(RsiSmoothing::Sma);
+        assert!(close(&cutler, &[100.0, 200.0 / 3.0, 200.0 / 3.0]));
+        let ema = run(RsiSmoothing::Ema);
+        assert!(close(&ema, &[100.0, 300.0 / 7.0, 100.0 - 100.0 / 7.75]));
+        assert_eq!(Rsi::new(2).unwrap().smoothing(), RsiSmoothing::Wilder);
+    }
 }
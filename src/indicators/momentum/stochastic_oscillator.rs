@@ -1,4 +1,6 @@
-use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::{validate_data_length, validate_period, RollingMax, RollingMin};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
 
@@ -70,16 +72,73 @@ use std::collections::VecDeque;
 ///     }
 /// }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StochasticOscillator {
     k_period: usize,
-    d_period: usize,
-    k_buffer: VecDeque<f64>,
+    ma_type: MaType,
+    highs: RollingMax,
+    lows: RollingMin,
+    k_smoothing: Smoothing,
+    d_smoothing: Smoothing,
+    last: Option<StochasticResult>,
+}
+
+/// A moving average of the %K series. SMAs are summed from the window
+/// each bar, so an unsmoothed %K passes through exactly.
+#[derive(Debug, Clone)]
+struct Smoothing {
+    period: usize,
+    ma: Option<MovingAverage>,
+    window: VecDeque<f64>,
+}
+
+impl Smoothing {
+    fn new(ma_type: MaType, period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        let ma = match ma_type {
+            MaType::Sma => None,
+            other => Some(MovingAverage::new(other, period)?),
+        };
+        Ok(Self {
+            period,
+            ma,
+            window: VecDeque::with_capacity(period + 1),
+        })
+    }
+
+    fn offset(&self) -> usize {
+        self.ma
+            .as_ref()
+            .map_or(self.period - 1, MovingAverage::offset)
+    }
+
+    fn next(&mut self, value: f64) -> Option<f64> {
+        if let Some(ma) = &mut self.ma {
+            // The averages only fail on invalid periods, checked in `new`.
+            return ma.next(value).ok().flatten();
+        }
+        self.window.push_back(value);
+        if self.window.len() > self.period {
+            self.window.pop_front();
+        }
+        (self.window.len() == self.period)
+            .then(|| self.window.iter().sum::<f64>() / self.period as f64)
+    }
+
+    fn reset(&mut self) {
+        if let Some(ma) = &mut self.ma {
+            ma.reset();
+        }
+        self.window.clear();
+    }
 }
 
 impl StochasticOscillator {
     /// Create a new StochasticOscillator
     ///
+    /// The fast stochastic: %K is not smoothed and %D is its simple
+    /// moving average.
+    ///
     /// # Arguments
     /// * `k_period` - The %K period (typically 14) - must be at least 1
     /// * `d_period` - The %D period (typically 3) - must be at least 1
@@ -87,107 +146,157 @@ impl StochasticOscillator {
     /// # Returns
     /// * `Result<Self, IndicatorError>` - A new StochasticOscillator or an error
     pub fn new(k_period: usize, d_period: usize) -> Result<Self, IndicatorError> {
-        validate_period(k_period, 1)?;
-        validate_period(d_period, 1)?;
-
-        Ok(Self {
-            k_period,
-            d_period,
-            k_buffer: VecDeque::with_capacity(d_period),
-        })
+        Self::with_ma(k_period, 1, d_period, MaType::Sma)
     }
 
-    /// Calculate %K value for a given candle
+    /// Create a full stochastic whose %K is smoothed before %D is taken
+    ///
+    /// `k_smoothing` of 3 with [`MaType::Sma`] is the classic slow
+    /// stochastic; a `k_smoothing` of 1 leaves %K unsmoothed.
     ///
     /// # Arguments
-    /// * `candles` - The slice of candles to calculate %K from
-    /// * `idx` - The index of the current candle
+    /// * `k_period` - The look-back of the raw %K (typically 14) - must be at least 1
+    /// * `k_smoothing` - The period of the average applied to the raw %K - must be at least 1
+    /// * `d_period` - The period of the average of the smoothed %K - must be at least 1
+    /// * `ma_type` - The moving average used for both smoothing steps
     ///
     /// # Returns
-    /// * `f64` - The %K value
-    // StochasticOscillator fix - line 291
-    fn calculate_k(candles: &[Candle], idx: usize, period: usize) -> f64 {
-        if idx < period - 1 {
-            return 50.0; // Not enough data, return middle value
-        }
+    /// * `Result<Self, IndicatorError>` - A new StochasticOscillator or an error
+    ///
+    /// ```
+    /// use rsta::indicators::momentum::StochasticOscillator;
+    /// use rsta::indicators::trend::MaType;
+    /// use rsta::indicators::{Candle, Indicator};
+    ///
+    /// let candles: Vec<Candle> = (0..30)
+    ///     .map(|i| {
+    ///         let c = 50.0 + (i as f64 * 0.6).sin() * 4.0;
+    ///         Candle { timestamp: i, open: c, high: c + 1.0, low: c - 1.0, close: c, volume: 1.0 }
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut slow = StochasticOscillator::with_ma(14, 3, 3, MaType::Sma).unwrap();
+    /// let out = slow.calculate(&candles).unwrap();
+    /// let last = out.last().unwrap();
+    /// // `k_fast` is the raw %K that `k` smooths.
+    /// assert!(last.k_fast >= 0.0 && last.k_fast <= 100.0);
+    /// assert_eq!(slow.ma_type(), MaType::Sma);
+    /// ```
+    pub fn with_ma(
+        k_period: usize,
+        k_smoothing: usize,
+        d_period: usize,
+        ma_type: MaType,
+    ) -> Result<Self, IndicatorError> {
+        validate_period(k_period, 1)?;
 
-        let current_close = candles[idx].close;
+        Ok(Self {
+            k_period,
+            ma_type,
+            highs: RollingMax::new(k_period)?,
+            lows: RollingMin::new(k_period)?,
+            k_smoothing: Smoothing::new(ma_type, k_smoothing)?,
+            d_smoothing: Smoothing::new(ma_type, d_period)?,
+            last: None,
+        })
+    }
 
-        // Safe start index calculation to avoid integer underflow
-        let start_idx = idx.saturating_sub(period - 1);
-        let mut lowest_low = candles[start_idx].low;
-        let mut highest_high = candles[start_idx].high;
+    /// Moving average used to smooth %K and to derive %D
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
 
-        for candle in candles.iter().take(idx + 1).skip(start_idx + 1) {
-            lowest_low = lowest_low.min(candle.low);
-            highest_high = highest_high.max(candle.high);
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.k_smoothing.reset();
+        self.d_smoothing.reset();
+        self.last = None;
+    }
+
+    /// %K of the latest candle, once `k_period` candles have been seen.
+    fn step_k(&mut self, candle: Candle) -> Option<f64> {
+        let highest_high = self.highs.push(candle.high);
+        let lowest_low = self.lows.push(candle.low);
+        if !self.highs.is_full() {
+            return None;
         }
+        let (highest_high, lowest_low) = (highest_high?, lowest_low?);
 
         if highest_high == lowest_low {
-            return 50.0; // Default to middle value when range is zero
+            return Some(50.0); // Default to middle value when range is zero
         }
 
-        ((current_close - lowest_low) / (highest_high - lowest_low)) * 100.0
+        Some(((candle.close - lowest_low) / (highest_high - lowest_low)) * 100.0)
+    }
+
+    fn step(&mut self, candle: Candle) -> Option<StochasticResult> {
+        let k_fast = self.step_k(candle)?;
+        let k = self.k_smoothing.next(k_fast)?;
+        let d = self.d_smoothing.next(k)?;
+        self.last = Some(StochasticResult { k_fast, k, d });
+        self.last
     }
 }
 
 /// Stochastic indicator result
 #[derive(Debug, Clone, Copy)]
 pub struct StochasticResult {
-    /// %K value (fast stochastic)
+    /// Raw %K, before smoothing
+    pub k_fast: f64,
+    /// %K value (equal to `k_fast` unless the %K is smoothed)
     pub k: f64,
-    /// %D value (slow stochastic - SMA of %K)
+    /// %D value (moving average of %K)
     pub d: f64,
 }
 
 impl Indicator<Candle, StochasticResult> for StochasticOscillator {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<StochasticResult>, IndicatorError> {
-        validate_data_length(data, self.k_period + self.d_period - 1)?;
-
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.k_period - self.d_period + 2);
-
-        // Reset state
-        self.reset();
-
-        // Calculate %K values
-        let mut k_values = Vec::with_capacity(n);
-        for i in 0..n {
-            k_values.push(Self::calculate_k(data, i, self.k_period));
-        }
-
-        // We can only start calculating %D once we have k_period values
-        // We can only start calculating %D once we have k_period values
-        let k_start_idx = self.k_period - 1;
-        for (i, &k_value) in k_values.iter().enumerate().skip(k_start_idx) {
-            // Add to buffer
-            self.k_buffer.push_back(k_value);
-
-            if self.k_buffer.len() > self.d_period {
-                self.k_buffer.pop_front();
-            }
-
-            if self.k_buffer.len() == self.d_period {
-                // Calculate %D (SMA of %K)
-                let d = self.k_buffer.iter().sum::<f64>() / self.d_period as f64;
-
-                result.push(StochasticResult { k: k_values[i], d });
+        let min_periods = Indicator::<Candle, StochasticResult>::min_periods(self);
+        validate_data_length(data, min_periods)?;
+        self.reset_state();
+
+        let mut result = Vec::with_capacity(data.len() + 1 - min_periods);
+        for &candle in data {
+            if let Some(value) = self.step(candle) {
+                result.push(value);
             }
         }
         Ok(result)
     }
 
-    fn next(&mut self, _value: Candle) -> Result<Option<StochasticResult>, IndicatorError> {
-        // Implementation would require storing the last k_period candles
-        // For simplicity, we're leaving this as an exercise
-        Err(IndicatorError::CalculationError(
-            "Real-time calculation of Stochastic Oscillator requires storing previous candles"
-                .to_string(),
-        ))
+    fn next(&mut self, value: Candle) -> Result<Option<StochasticResult>, IndicatorError> {
+        Ok(self.step(value))
     }
 
     fn reset(&mut self) {
-        self.k_buffer.clear();
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&StochasticResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.k_period + self.k_smoothing.offset() + self.d_smoothing.offset()
+    }
+}
+
+impl Describe for StochasticOscillator {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "stochastic",
+            title: "Stochastic Oscillator",
+            input: InputKind::Candle,
+            params: &[
+                ParamDescriptor::period("k_period", 1).with_default(14.0),
+                ParamDescriptor::period("d_period", 1).with_default(3.0),
+                ParamDescriptor::period("k_smoothing", 1).with_default(1.0),
+            ],
+            outputs: &["k", "d"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -269,19 +378,39 @@ mod tests {
     }
 
     #[test]
-    fn test_stochastic_next_error() {
-        let mut stoch = StochasticOscillator::new(14, 3).unwrap();
-        let candle = Candle {
-            timestamp: 1,
-            open: 10.0,
-            high: 12.0,
-            low: 9.0,
-            close: 11.0,
-            volume: 1000.0,
-        };
+    fn test_stochastic_next_matches_calculate() {
+        let candles: Vec<Candle> = (0..30)
+            .map(|i| {
+                let c = 100.0 + (i as f64 * 0.7).sin() * 5.0;
+                Candle {
+                    timestamp: i,
+                    open: c,
+                    high: c + 1.0 + (i % 3) as f64,
+                    low: c - 1.0 - (i % 2) as f64,
+                    close: c,
+                    volume: 1000.0,
+                }
+            })
+            .collect();
+        let batch = StochasticOscillator::new(14, 3)
+            .unwrap()
+            .calculate(&candles)
+            .unwrap();
 
-        // The next method should return an error as noted in the implementation
-        assert!(stoch.next(candle).is_err());
+        let mut stoch = StochasticOscillator::new(14, 3).unwrap();
+        let streamed: Vec<StochasticResult> = candles
+            .iter()
+            .filter_map(|&c| stoch.next(c).unwrap())
+            .collect();
+        assert_eq!(streamed.len(), batch.len());
+        for (a, b) in streamed.iter().zip(&batch) {
+            assert_eq!((a.k, a.d), (b.k, b.d));
+        }
+        // Warm-up: nothing before the 16th candle.
+        stoch.reset();
+        assert!(candles[..15]
+            .iter()
+            .all(|&c| stoch.next(c).unwrap().is_none()));
     }
 
     #[test]
@@ -318,4 +447,74 @@ mod tests {
         // We can't directly test the internal state, but we can test the behavior
         // by doing a calculation that requires an empty state
     }
+
+    #[test]
+    fn test_slow_stochastic_smooths_raw_k() {
+        let candles: Vec<Candle> = (0..40)
+            .map(|i| {
+                let c = 100.0 + (i as f64 * 0.5).sin() * 6.0;
+                Candle {
+                    timestamp: i,
+                    open: c,
+                    high: c + 1.5,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 1000.0,
+                }
+            })
+            .collect();
+
+        let fast = StochasticOscillator::new(5, 3)
+            .unwrap()
+            .calculate(&candles)
+            .unwrap();
+        assert!(fast.iter().all(|r| r.k == r.k_fast));
+
+        let mut slow = StochasticOscillator::with_ma(5, 3, 3, MaType::Sma).unwrap();
+        assert_eq!(Indicator::<Candle, StochasticResult>::min_periods(&slow), 9);
+        let out = slow.calculate(&candles).unwrap();
+        assert_eq!(out.len(), candles.len() - 8);
+        // Slow %K is the 3-bar average of the raw %K, and the raw %K of
+        // the slow stochastic is the fast stochastic's %K.
+        let raw: Vec<f64> = fast.iter().map(|r| r.k_fast).collect();
+        for (i, r) in out.iter().enumerate() {
+            assert!((r.k_fast - raw[i + 2]).abs() < 1e-12);
+            let mean = (raw[i] + raw[i + 1] + raw[i + 2]) / 3.0;
+            assert!((r.k - mean).abs() < 1e-9);
+        }
+
+        assert!(StochasticOscillator::with_ma(5, 0, 3, MaType::Sma).is_err());
+    }
+
+    #[test]
+    fn test_stochastic_ma_selection() {
+        let candles: Vec<Candle> = (0..30)
+            .map(|i| {
+                let c = 20.0 + (i as f64 * 0.8).cos() * 2.0;
+                Candle {
+                    timestamp: i,
+                    open: c,
+                    high: c + 0.5,
+                    low: c - 0.5,
+                    close: c,
+                    volume: 1.0,
+                }
+            })
+            .collect();
+        let mut ema = StochasticOscillator::with_ma(5, 3, 3, MaType::Ema).unwrap();
+        assert_eq!(ema.ma_type(), MaType::Ema);
+        // EMAs emit from their first input, so only the %K look-back warms up.
+        assert_eq!(Indicator::<Candle, StochasticResult>::min_periods(&ema), 5);
+        let batch = ema.calculate(&candles).unwrap();
+
+        ema.reset_state();
+        let streamed: Vec<StochasticResult> = candles
+            .iter()
+            .filter_map(|&c| ema.next(c).unwrap())
+            .collect();
+        assert_eq!(batch.len(), streamed.len());
+        for (a, b) in batch.iter().zip(&streamed) {
+            assert_eq!((a.k_fast, a.k, a.d), (b.k_fast, b.k, b.d));
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::utils::{validate_data_length, RollingMax, RollingMin};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
 /// Williams %R
@@ -35,9 +36,12 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 ///     }
 /// }
 /// ```
+#[derive(Debug, Clone)]
 pub struct WilliamsR {
     period: usize,
-    history: Vec<Candle>, // Added history to store candles for real-time calculation
+    highs: RollingMax,
+    lows: RollingMin,
+    last: Option<f64>,
 }
 
 impl WilliamsR {
@@ -49,95 +53,81 @@ impl WilliamsR {
     /// # Returns
     /// * `Result<Self, IndicatorError>` - A new WilliamsR indicator or an error
     pub fn new(period: usize) -> Result<Self, IndicatorError> {
-        validate_period(period, 1)?;
-
         Ok(Self {
             period,
-            history: Vec::with_capacity(period), // Initialize history with capacity
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            last: None,
         })
     }
 
-    /// Calculate Williams %R value for a given candle range
-    ///
-    /// # Arguments
-    /// * `candles` - The slice of candles to calculate %R from
-    /// * `idx` - The index of the current candle
-    /// * `period` - The period for Williams %R calculation
-    ///
-    /// # Returns
-    /// * `f64` - The Williams %R value
-    fn calculate_r(candles: &[Candle], idx: usize, period: usize) -> f64 {
-        if idx < period - 1 {
-            return -50.0; // Not enough data, return middle value
-        }
-
-        let current_close = candles[idx].close;
-
-        // Safe start index calculation to avoid integer underflow
-        let start_idx = idx.saturating_sub(period - 1);
-        let mut lowest_low = candles[start_idx].low;
-        let mut highest_high = candles[start_idx].high;
-
-        for candle in candles.iter().take(idx + 1).skip(start_idx + 1) {
-            lowest_low = lowest_low.min(candle.low);
-            highest_high = highest_high.max(candle.high);
-        }
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+        self.last = None;
+    }
 
-        if highest_high == lowest_low {
-            return -50.0; // Default to middle value when range is zero
+    fn step(&mut self, candle: Candle) -> Option<f64> {
+        let highest_high = self.highs.push(candle.high);
+        let lowest_low = self.lows.push(candle.low);
+        if !self.highs.is_full() {
+            return None;
         }
+        let (highest_high, lowest_low) = (highest_high?, lowest_low?);
 
-        // Williams %R formula: ((Highest High - Close) / (Highest High - Lowest Low)) * -100
-        ((highest_high - current_close) / (highest_high - lowest_low)) * -100.0
+        let r_value = if highest_high == lowest_low {
+            -50.0 // Default to middle value when range is zero
+        } else {
+            // Williams %R formula: ((Highest High - Close) / (Highest High - Lowest Low)) * -100
+            ((highest_high - candle.close) / (highest_high - lowest_low)) * -100.0
+        };
+        self.last = Some(r_value);
+        self.last
     }
 }
 
 impl Indicator<Candle, f64> for WilliamsR {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
         validate_data_length(data, self.period)?;
+        self.reset_state();
 
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period + 1);
-
-        // Calculate Williams %R for each period
-        for i in (self.period - 1)..n {
-            let r_value = Self::calculate_r(data, i, self.period);
-            result.push(r_value);
+        let mut result = Vec::with_capacity(data.len() - self.period + 1);
+        for &candle in data {
+            if let Some(r_value) = self.step(candle) {
+                result.push(r_value);
+            }
         }
-
-        // Update history with the most recent candles for future next() calls
-        self.history.clear();
-        if n >= self.period {
-            self.history.extend_from_slice(&data[n - self.period..]);
-        } else {
-            self.history.extend_from_slice(data);
-        }
-
         Ok(result)
     }
 
     fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
-        // Add the new candle to history
-        self.history.push(value);
+        Ok(self.step(value))
+    }
 
-        // If we have more candles than needed, remove the oldest one
-        if self.history.len() > self.period {
-            self.history.remove(0);
-        }
+    fn reset(&mut self) {
+        self.reset_state();
+    }
 
-        // If we don't have enough data yet, return None
-        if self.history.len() < self.period {
-            return Ok(None);
-        }
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
 
-        // Calculate Williams %R using the history
-        let r_value = Self::calculate_r(&self.history, self.history.len() - 1, self.period);
-        Ok(Some(r_value))
+    fn min_periods(&self) -> usize {
+        self.period
     }
+}
 
-    fn reset(&mut self) {
-        // Clear the history
-        self.history.clear();
+impl Describe for WilliamsR {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "williams_r",
+            title: "Williams %R",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -284,7 +274,7 @@ mod tests {
 
     #[test]
     fn test_calculate_r() {
-        // Test the internal calculate_r function with a simple case
+        // Window arithmetic on a simple case
         let candles = vec![
             Candle {
                 timestamp: 1,
@@ -315,7 +305,7 @@ mod tests {
         // For period 3, idx 2:
         // Highest high = 15.0, Lowest low = 5.0, Close = 7.0
         // %R = ((15 - 7) / (15 - 5)) * -100 = -80.0
-        let r_value = WilliamsR::calculate_r(&candles, 2, 3);
+        let r_value = WilliamsR::new(3).unwrap().calculate(&candles).unwrap()[0];
         assert!((r_value - (-80.0)).abs() < 0.01);
     }
 }
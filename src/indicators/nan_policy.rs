@@ -0,0 +1,379 @@
+//! Handling of NaN / infinite inputs
+//!
+//! Indicators do plain floating-point arithmetic, so a single NaN in the
+//! input silently poisons every value that depends on it — a moving average
+//! stays NaN for a whole window, and recursive indicators (EMA, RSI, ATR)
+//! stay NaN forever. [`NanGuard`] wraps any indicator and screens its input
+//! according to a [`NanPolicy`] before the inner indicator sees it.
+//!
+//! ```
+//! use rsta::indicators::nan_policy::{NanGuard, NanPolicy};
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::{Indicator, IndicatorError};
+//!
+//! let prices = vec![1.0, 2.0, f64::NAN, 4.0, 5.0];
+//!
+//! // Reject: the error carries the offending index.
+//! let mut strict = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::Error);
+//! match strict.calculate(&prices) {
+//!     Err(IndicatorError::InvalidInput { index, .. }) => assert_eq!(index, 2),
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//!
+//! // Forward-fill: the NaN is replaced by the last valid price (2.0).
+//! let mut filled = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::ForwardFill);
+//! assert_eq!(filled.calculate(&prices).unwrap(), vec![1.5, 2.0, 3.0, 4.5]);
+//! ```
+
+use crate::indicators::{Candle, Indicator, IndicatorError, IndicatorSeries, Timestamped};
+
+/// What to do when an indicator input is NaN or infinite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NanPolicy {
+    /// Reject the input with [`IndicatorError::InvalidInput`]. The indicator
+    /// state is left untouched, so streaming can continue with the next
+    /// value.
+    #[default]
+    Error,
+    /// Drop the input as if it had never been received. `next()` returns
+    /// `Ok(None)` and `calculate()` runs on the remaining values.
+    Skip,
+    /// Replace the input with the last valid one. For [`Candle`]s the
+    /// replacement is field-wise, so only the non-finite fields are filled.
+    /// Invalid values seen before any valid one are skipped.
+    ForwardFill,
+}
+
+/// Input types that can be screened by a [`NanGuard`].
+pub trait FiniteInput: Clone {
+    /// `true` if every numeric component of the value is finite.
+    fn is_finite_input(&self) -> bool;
+
+    /// Repair `self` using `last`, the most recent valid value.
+    fn fill_from(&self, last: &Self) -> Self;
+}
+
+impl FiniteInput for f64 {
+    fn is_finite_input(&self) -> bool {
+        self.is_finite()
+    }
+
+    fn fill_from(&self, last: &Self) -> Self {
+        *last
+    }
+}
+
+impl FiniteInput for Candle {
+    fn is_finite_input(&self) -> bool {
+        self.open.is_finite()
+            && self.high.is_finite()
+            && self.low.is_finite()
+            && self.close.is_finite()
+            && self.volume.is_finite()
+    }
+
+    fn fill_from(&self, last: &Self) -> Self {
+        let pick = |v: f64, prev: f64| if v.is_finite() { v } else { prev };
+        Candle {
+            timestamp: self.timestamp,
+            open: pick(self.open, last.open),
+            high: pick(self.high, last.high),
+            low: pick(self.low, last.low),
+            close: pick(self.close, last.close),
+            volume: pick(self.volume, last.volume),
+        }
+    }
+}
+
+/// Wraps an indicator and applies a [`NanPolicy`] to every input passed to
+/// `calculate()` and `next()`. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct NanGuard<I, T> {
+    inner: I,
+    policy: NanPolicy,
+    last_valid: Option<T>,
+    /// Number of values fed through `next()` since construction or reset.
+    seen: usize,
+}
+
+impl<I, T> NanGuard<I, T> {
+    /// Wrap `inner` with the given policy.
+    pub fn new(inner: I, policy: NanPolicy) -> Self {
+        Self {
+            inner,
+            policy,
+            last_valid: None,
+            seen: 0,
+        }
+    }
+
+    /// The active policy.
+    pub fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+
+    /// Borrow the wrapped indicator.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Unwrap, returning the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    /// Reset the guard's streaming state (not the inner indicator).
+    pub fn reset_state(&mut self) {
+        self.last_valid = None;
+        self.seen = 0;
+    }
+}
+
+fn invalid_input(index: usize) -> IndicatorError {
+    IndicatorError::InvalidInput {
+        index,
+        reason: "value is NaN or infinite".to_string(),
+    }
+}
+
+/// Repaired batch values together with their positions in the original slice.
+type Screened<T> = (Vec<T>, Vec<usize>);
+
+/// Apply `policy` to a batch. Returns `None` when `data` is already clean.
+fn screen<T: FiniteInput>(
+    policy: NanPolicy,
+    data: &[T],
+) -> Result<Option<Screened<T>>, IndicatorError> {
+    let Some(first_bad) = data.iter().position(|v| !v.is_finite_input()) else {
+        return Ok(None);
+    };
+    let mut clean = Vec::with_capacity(data.len());
+    let mut positions = Vec::with_capacity(data.len());
+    match policy {
+        NanPolicy::Error => return Err(invalid_input(first_bad)),
+        NanPolicy::Skip => {
+            for (i, v) in data.iter().enumerate() {
+                if v.is_finite_input() {
+                    clean.push(v.clone());
+                    positions.push(i);
+                }
+            }
+        }
+        NanPolicy::ForwardFill => {
+            let mut last: Option<T> = None;
+            for (i, v) in data.iter().enumerate() {
+                let value = if v.is_finite_input() {
+                    v.clone()
+                } else if let Some(prev) = &last {
+                    v.fill_from(prev)
+                } else {
+                    continue;
+                };
+                last = Some(value.clone());
+                clean.push(value);
+                positions.push(i);
+            }
+        }
+    }
+    Ok(Some((clean, positions)))
+}
+
+impl<I, T, O> Indicator<T, O> for NanGuard<I, T>
+where
+    I: Indicator<T, O>,
+    T: FiniteInput,
+{
+    fn calculate(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        match screen(self.policy, data)? {
+            None => self.inner.calculate(data),
+            Some((clean, _)) => self.inner.calculate(&clean),
+        }
+    }
+
+    /// Dropped inputs (under [`NanPolicy::Skip`], or leading values under
+    /// [`NanPolicy::ForwardFill`]) map to `None` at their original position.
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        let Some((clean, positions)) = screen(self.policy, data)? else {
+            return self.inner.calculate_aligned(data);
+        };
+        let values = self.inner.calculate_aligned(&clean)?;
+        let mut out: Vec<Option<O>> = std::iter::repeat_with(|| None).take(data.len()).collect();
+        for (i, v) in positions.into_iter().zip(values) {
+            out[i] = v;
+        }
+        Ok(out)
+    }
+
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError> {
+        let index = self.seen;
+        self.seen += 1;
+        if value.is_finite_input() {
+            self.last_valid = Some(value.clone());
+            return self.inner.next(value);
+        }
+        match self.policy {
+            NanPolicy::Error => Err(invalid_input(index)),
+            NanPolicy::Skip => Ok(None),
+            NanPolicy::ForwardFill => match &self.last_valid {
+                Some(prev) => {
+                    let filled = value.fill_from(prev);
+                    self.last_valid = Some(filled.clone());
+                    self.inner.next(filled)
+                }
+                None => Ok(None),
+            },
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+        self.inner.reset();
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.inner.current()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        self.inner.output_offset()
+    }
+}
+
+#[cfg(all(test, feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::{Ema, Sma};
+    use crate::indicators::volatility::Atr;
+
+    fn candle(close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_default_policy_is_error() {
+        assert_eq!(NanPolicy::default(), NanPolicy::Error);
+    }
+
+    #[test]
+    fn test_error_policy_reports_index() {
+        let mut sma = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::Error);
+        let err = sma.calculate(&[1.0, 2.0, 3.0, f64::INFINITY]).unwrap_err();
+        assert_eq!(
+            err,
+            IndicatorError::InvalidInput {
+                index: 3,
+                reason: "value is NaN or infinite".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_error_policy_streaming_keeps_state() {
+        let mut ema = NanGuard::new(Ema::new(3).unwrap(), NanPolicy::Error);
+        assert_eq!(ema.next(2.0).unwrap(), Some(2.0));
+        match ema.next(f64::NAN) {
+            Err(IndicatorError::InvalidInput { index, .. }) => assert_eq!(index, 1),
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+        // The rejected value never reached the EMA.
+        assert_eq!(ema.next(4.0).unwrap(), Some(3.0));
+    }
+
+    #[test]
+    fn test_skip_policy() {
+        let mut sma = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::Skip);
+        let out = sma.calculate(&[1.0, f64::NAN, 3.0, 5.0]).unwrap();
+        assert_eq!(out, vec![2.0, 4.0]);
+
+        sma.reset();
+        assert_eq!(sma.next(1.0).unwrap(), None);
+        assert_eq!(sma.next(f64::NAN).unwrap(), None);
+        assert_eq!(sma.next(3.0).unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn test_forward_fill_policy() {
+        let mut sma = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::ForwardFill);
+        // Leading NaN has nothing to fill from and is dropped.
+        let out = sma.calculate(&[f64::NAN, 1.0, f64::NAN, 5.0]).unwrap();
+        assert_eq!(out, vec![1.0, 3.0]);
+
+        sma.reset();
+        assert_eq!(sma.next(f64::NAN).unwrap(), None);
+        assert_eq!(sma.next(2.0).unwrap(), None);
+        assert_eq!(sma.next(f64::NAN).unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn test_forward_fill_candles_field_wise() {
+        let mut broken = candle(12.0);
+        broken.high = f64::NAN;
+        let filled = broken.fill_from(&candle(10.0));
+        assert_eq!(filled.high, 11.0);
+        assert_eq!(filled.close, 12.0);
+        assert!(filled.is_finite_input());
+
+        let mut atr = NanGuard::new(Atr::new(2).unwrap(), NanPolicy::ForwardFill);
+        let out = atr
+            .calculate(&[candle(10.0), broken, candle(11.0)])
+            .unwrap();
+        assert!(out.iter().all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn test_aligned_output_keeps_skipped_positions() {
+        let mut sma = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::Skip);
+        let out = sma
+            .calculate_aligned(&[1.0, f64::NAN, 3.0, 5.0, f64::NAN])
+            .unwrap();
+        assert_eq!(out, vec![None, None, Some(2.0), Some(4.0), None]);
+
+        let mut ffill = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::ForwardFill);
+        let out = ffill
+            .calculate_aligned(&[f64::NAN, 1.0, f64::NAN, 5.0])
+            .unwrap();
+        assert_eq!(out, vec![None, None, Some(1.0), Some(3.0)]);
+
+        let candles = [candle(1.0), candle(f64::NAN), candle(3.0), candle(5.0)];
+        let mut sma = NanGuard::new(Sma::new(2).unwrap(), NanPolicy::Skip);
+        let series = sma.calculate_series(&candles).unwrap();
+        assert_eq!(series.aligned(), vec![None, None, Some(&2.0), Some(&4.0)]);
+    }
+
+    #[test]
+    fn test_metadata_delegates_to_inner() {
+        let guard = NanGuard::<Sma, f64>::new(Sma::new(5).unwrap(), NanPolicy::Skip);
+        assert_eq!(
+            <NanGuard<Sma, f64> as Indicator<f64, f64>>::name(&guard),
+            "Sma"
+        );
+        assert_eq!(guard.policy(), NanPolicy::Skip);
+    }
+}
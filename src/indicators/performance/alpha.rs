@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::IndicatorError;
+use crate::pairs;
+
+/// Rolling Jensen's alpha against a benchmark
+///
+/// Regresses the asset's excess returns on the benchmark's over the last
+/// `period` `(asset, benchmark)` return pairs; alpha is the intercept, the
+/// return the asset earned beyond what its beta to the benchmark explains.
+///
+/// # Formula
+///
+/// ```text
+/// beta  = cov(ra, rb) / var(rb)
+/// alpha = (mean(ra - rf) - beta * mean(rb - rf)) * periods_per_year
+/// ```
+///
+/// `periods_per_year` is 1 (per-period alpha) unless set with
+/// [`with_annualization`](Self::with_annualization). A flat benchmark
+/// window has a beta of `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::performance::RollingAlpha;
+/// use rsta::indicators::Indicator;
+///
+/// // The asset moves 1.5x the benchmark plus 0.1% per period.
+/// let benchmark = [0.01, -0.02, 0.015, 0.0, 0.005];
+/// let pairs: Vec<(f64, f64)> = benchmark.iter().map(|&b| (0.001 + 1.5 * b, b)).collect();
+///
+/// let mut alpha = RollingAlpha::new(4).unwrap();
+/// let values = alpha.calculate(&pairs).unwrap();
+/// assert!(values.iter().all(|a| (a - 0.001).abs() < 1e-12));
+/// assert!((alpha.beta().unwrap() - 1.5).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingAlpha {
+    period: usize,
+    risk_free_rate: f64,
+    periods_per_year: f64,
+    window: VecDeque<(f64, f64)>,
+    beta: Option<f64>,
+    last: Option<f64>,
+}
+
+impl RollingAlpha {
+    /// Create a new rolling alpha
+    ///
+    /// # Arguments
+    /// * `period` - Number of return pairs in the window (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            risk_free_rate: 0.0,
+            periods_per_year: 1.0,
+            window: VecDeque::with_capacity(period),
+            beta: None,
+            last: None,
+        })
+    }
+
+    /// Subtract a risk-free rate per period from both returns (0 by
+    /// default).
+    pub fn with_risk_free_rate(mut self, rate: f64) -> Self {
+        self.risk_free_rate = rate;
+        self
+    }
+
+    /// Annualize by multiplying by `periods_per_year`, e.g. 252 for daily
+    /// returns.
+    pub fn with_annualization(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Beta to the benchmark over the latest window.
+    pub fn beta(&self) -> Option<f64> {
+        self.beta
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.beta = None;
+        self.last = None;
+    }
+
+    fn step(&mut self, value: (f64, f64)) -> Option<f64> {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < self.period {
+            return None;
+        }
+        let fit = pairs::fit(self.window.iter().copied());
+        // Shifting both series by rf keeps the slope and moves the
+        // intercept by rf * (beta - 1).
+        let alpha = fit.alpha - self.risk_free_rate * (1.0 - fit.beta);
+        self.beta = Some(fit.beta);
+        self.last = Some(alpha * self.periods_per_year);
+        self.last
+    }
+}
+
+impl Indicator<(f64, f64), f64> for RollingAlpha {
+    fn calculate(&mut self, data: &[(f64, f64)]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: (f64, f64)) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RollingAlpha"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alpha_with_risk_free_rate() {
+        let pairs: Vec<(f64, f64)> = (0..20)
+            .map(|i| {
+                let b = (i as f64 * 0.9).sin() * 0.02;
+                (0.002 + 0.8 * b + (i as f64 * 2.3).cos() * 0.001, b)
+            })
+            .collect();
+        let rf = 0.0004;
+        let mut alpha = RollingAlpha::new(8)
+            .unwrap()
+            .with_risk_free_rate(rf)
+            .with_annualization(12.0);
+        let values = alpha.calculate(&pairs).unwrap();
+        assert_eq!(values.len(), 13);
+        for (i, value) in values.iter().enumerate() {
+            let window = &pairs[i..i + 8];
+            let excess_a: Vec<f64> = window.iter().map(|p| p.0 - rf).collect();
+            let excess_b: Vec<f64> = window.iter().map(|p| p.1 - rf).collect();
+            let fit = pairs::ols(&excess_a, &excess_b).unwrap();
+            assert!((value - fit.alpha * 12.0).abs() < 1e-12);
+        }
+        assert!(RollingAlpha::new(1).is_err());
+    }
+}
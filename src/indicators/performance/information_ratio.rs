@@ -0,0 +1,153 @@
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period, RollingStats};
+use crate::indicators::IndicatorError;
+
+/// Rolling information ratio against a benchmark
+///
+/// Mean active return (asset minus benchmark) divided by the tracking
+/// error, the sample standard deviation of the active returns, over the
+/// last `period` `(asset, benchmark)` return pairs.
+///
+/// # Formula
+///
+/// ```text
+/// active = ra - rb
+/// ir     = mean(active) / std(active) * √periods_per_year
+/// ```
+///
+/// `periods_per_year` is 1 (no annualization) unless set with
+/// [`with_annualization`](Self::with_annualization). A window where the
+/// asset tracks the benchmark exactly (no tracking error) gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::performance::InformationRatio;
+/// use rsta::indicators::Indicator;
+///
+/// let pairs = [(0.02, 0.01), (0.0, 0.01), (0.03, 0.01)];
+/// let mut ir = InformationRatio::new(3).unwrap();
+/// let values = ir.calculate(&pairs).unwrap();
+/// // Active returns 0.01, -0.01, 0.02: mean 0.00667, tracking error 0.01528
+/// assert!((values[0] - (0.02 / 3.0) / (0.0014f64 / 6.0).sqrt()).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct InformationRatio {
+    period: usize,
+    periods_per_year: f64,
+    stats: RollingStats,
+    last: Option<f64>,
+}
+
+impl InformationRatio {
+    /// Create a new rolling information ratio
+    ///
+    /// # Arguments
+    /// * `period` - Number of return pairs in the window (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            periods_per_year: 1.0,
+            stats: RollingStats::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Annualize by `√periods_per_year`, e.g. 252 for daily returns.
+    pub fn with_annualization(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.stats.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, (asset, benchmark): (f64, f64)) -> Option<f64> {
+        self.stats.push(asset - benchmark);
+        if !self.stats.is_full() {
+            return None;
+        }
+        let n = self.period as f64;
+        let mean = self.stats.mean()?;
+        let tracking_error = (self.stats.variance()? * n / (n - 1.0)).sqrt();
+        self.last = Some(if tracking_error > 0.0 {
+            mean / tracking_error * self.periods_per_year.sqrt()
+        } else {
+            0.0
+        });
+        self.last
+    }
+}
+
+impl Indicator<(f64, f64), f64> for InformationRatio {
+    fn calculate(&mut self, data: &[(f64, f64)]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: (f64, f64)) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "InformationRatio"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_information_ratio_streams_like_batch() {
+        let pairs: Vec<(f64, f64)> = (0..25)
+            .map(|i| {
+                let b = (i as f64 * 0.6).sin() * 0.01;
+                (b + 0.0005 + (i as f64 * 1.9).cos() * 0.002, b)
+            })
+            .collect();
+        let mut batch = InformationRatio::new(6).unwrap().with_annualization(252.0);
+        let values = batch.calculate(&pairs).unwrap();
+        let mut stream = InformationRatio::new(6).unwrap().with_annualization(252.0);
+        let streamed: Vec<f64> = pairs
+            .iter()
+            .filter_map(|&p| stream.next(p).unwrap())
+            .collect();
+        assert_eq!(values.len(), 20);
+        assert!(values
+            .iter()
+            .zip(&streamed)
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let mut tracking = InformationRatio::new(2).unwrap();
+        assert_eq!(
+            tracking.calculate(&[(0.01, 0.01), (0.02, 0.02)]).unwrap(),
+            vec![0.0]
+        );
+        assert!(InformationRatio::new(1).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+//! Risk-adjusted performance indicators
+//!
+//! These run over a series of periodic **returns** (`0.01` = +1%, e.g.
+//! from [`returns::simple_returns`](crate::returns::simple_returns)) rather
+//! than prices, and measure how much return each unit of risk earned over
+//! a rolling window. Regime filters can key off them like any other
+//! indicator:
+//!
+//! ```
+//! use rsta::indicators::performance::{RollingSharpe, RollingSortino};
+//! use rsta::indicators::Indicator;
+//!
+//! let returns: Vec<f64> = (0..40).map(|i| 0.002 + (i as f64 * 0.9).sin() * 0.01).collect();
+//!
+//! let mut sharpe = RollingSharpe::new(20).unwrap().with_annualization(252.0);
+//! let mut sortino = RollingSortino::new(20).unwrap().with_annualization(252.0);
+//! let s = sharpe.calculate(&returns).unwrap();
+//! let d = sortino.calculate(&returns).unwrap();
+//! assert_eq!(s.len(), 21);
+//! // Penalizing only downside moves rewards this upward-drifting series.
+//! assert!(d.iter().zip(&s).all(|(d, s)| d > s));
+//! ```
+//!
+//! [`RollingAlpha`] and [`InformationRatio`] measure relative performance
+//! instead: they take `(asset, benchmark)` return pairs, aligned bar for
+//! bar.
+//!
+//! The risk-free rate is given per period, in the unit of the returns;
+//! results are per period unless annualized with `with_annualization`.
+
+pub mod alpha;
+pub mod information_ratio;
+pub mod sharpe;
+pub mod sortino;
+
+pub use self::alpha::RollingAlpha;
+pub use self::information_ratio::InformationRatio;
+pub use self::sharpe::RollingSharpe;
+pub use self::sortino::RollingSortino;
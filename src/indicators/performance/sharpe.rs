@@ -0,0 +1,157 @@
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period, RollingStats};
+use crate::indicators::IndicatorError;
+
+/// Rolling Sharpe ratio
+///
+/// Mean excess return over the risk-free rate divided by the sample
+/// standard deviation of the returns, over the last `period` returns.
+///
+/// # Formula
+///
+/// ```text
+/// sharpe = mean(r - rf) / std(r) * √periods_per_year
+/// ```
+///
+/// `periods_per_year` is 1 (no annualization) unless set with
+/// [`with_annualization`](Self::with_annualization). A window of identical
+/// returns gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::performance::RollingSharpe;
+/// use rsta::indicators::Indicator;
+///
+/// let mut sharpe = RollingSharpe::new(4).unwrap().with_risk_free_rate(0.001);
+/// let values = sharpe.calculate(&[0.01, -0.01, 0.02, 0.0]).unwrap();
+/// // mean 0.005, excess 0.004, sample std √(0.0005 / 3)
+/// assert!((values[0] - 0.004 / (0.0005f64 / 3.0).sqrt()).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingSharpe {
+    period: usize,
+    risk_free_rate: f64,
+    periods_per_year: f64,
+    stats: RollingStats,
+    last: Option<f64>,
+}
+
+impl RollingSharpe {
+    /// Create a new rolling Sharpe ratio
+    ///
+    /// # Arguments
+    /// * `period` - Number of returns in the window (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            risk_free_rate: 0.0,
+            periods_per_year: 1.0,
+            stats: RollingStats::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Subtract a risk-free rate per period (0 by default).
+    pub fn with_risk_free_rate(mut self, rate: f64) -> Self {
+        self.risk_free_rate = rate;
+        self
+    }
+
+    /// Annualize by `√periods_per_year`, e.g. 252 for daily returns.
+    pub fn with_annualization(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.stats.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        self.stats.push(value);
+        if !self.stats.is_full() {
+            return None;
+        }
+        let n = self.period as f64;
+        let mean = self.stats.mean()?;
+        let std_dev = (self.stats.variance()? * n / (n - 1.0)).sqrt();
+        self.last = Some(if std_dev > 0.0 {
+            (mean - self.risk_free_rate) / std_dev * self.periods_per_year.sqrt()
+        } else {
+            0.0
+        });
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for RollingSharpe {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RollingSharpe"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sharpe_matches_window_formula() {
+        let returns: Vec<f64> = (0..30)
+            .map(|i| (i as f64 * 1.7).sin() * 0.02 + 0.001)
+            .collect();
+        let mut sharpe = RollingSharpe::new(10)
+            .unwrap()
+            .with_risk_free_rate(0.0005)
+            .with_annualization(252.0);
+        let values = sharpe.calculate(&returns).unwrap();
+        assert_eq!(values.len(), 21);
+        for (i, value) in values.iter().enumerate() {
+            let window = &returns[i..i + 10];
+            let mean = window.iter().sum::<f64>() / 10.0;
+            let var = window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 9.0;
+            let expected = (mean - 0.0005) / var.sqrt() * 252f64.sqrt();
+            assert!((value - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_flat_window_and_invalid_period() {
+        let mut sharpe = RollingSharpe::new(3).unwrap();
+        assert_eq!(sharpe.calculate(&[0.01; 4]).unwrap(), vec![0.0, 0.0]);
+        assert!(RollingSharpe::new(1).is_err());
+        assert!(sharpe.calculate(&[0.01, 0.02]).is_err());
+    }
+}
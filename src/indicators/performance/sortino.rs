@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::IndicatorError;
+
+/// Rolling Sortino ratio
+///
+/// Like the Sharpe ratio, but divides the mean excess return by the
+/// downside deviation — the root mean square of the returns below the
+/// risk-free rate (the target) — so upside volatility is not penalized.
+///
+/// # Formula
+///
+/// ```text
+/// downside = √(Σ min(0, r - rf)² / period)
+/// sortino  = mean(r - rf) / downside * √periods_per_year
+/// ```
+///
+/// `periods_per_year` is 1 (no annualization) unless set with
+/// [`with_annualization`](Self::with_annualization). A window without any
+/// return below the target has no downside: it gives `f64::INFINITY` if
+/// the mean excess return is positive and `0` otherwise.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::performance::RollingSortino;
+/// use rsta::indicators::Indicator;
+///
+/// let mut sortino = RollingSortino::new(4).unwrap();
+/// let values = sortino.calculate(&[0.03, -0.02, 0.01, 0.02]).unwrap();
+/// // mean 0.01, downside √(0.0004 / 4) = 0.01
+/// assert!((values[0] - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingSortino {
+    period: usize,
+    risk_free_rate: f64,
+    periods_per_year: f64,
+    window: VecDeque<f64>,
+    last: Option<f64>,
+}
+
+impl RollingSortino {
+    /// Create a new rolling Sortino ratio
+    ///
+    /// # Arguments
+    /// * `period` - Number of returns in the window (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            risk_free_rate: 0.0,
+            periods_per_year: 1.0,
+            window: VecDeque::with_capacity(period),
+            last: None,
+        })
+    }
+
+    /// Subtract a risk-free rate (the target return) per period, 0 by
+    /// default.
+    pub fn with_risk_free_rate(mut self, rate: f64) -> Self {
+        self.risk_free_rate = rate;
+        self
+    }
+
+    /// Annualize by `√periods_per_year`, e.g. 252 for daily returns.
+    pub fn with_annualization(mut self, periods_per_year: f64) -> Self {
+        self.periods_per_year = periods_per_year;
+        self
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < self.period {
+            return None;
+        }
+        let n = self.period as f64;
+        let (excess, downside) = self.window.iter().fold((0.0, 0.0), |(sum, sq), r| {
+            let e = r - self.risk_free_rate;
+            (sum + e, sq + e.min(0.0).powi(2))
+        });
+        let mean = excess / n;
+        let downside = (downside / n).sqrt();
+        self.last = Some(if downside > 0.0 {
+            mean / downside * self.periods_per_year.sqrt()
+        } else if mean > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        });
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for RollingSortino {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RollingSortino"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sortino_with_target_and_annualization() {
+        let mut sortino = RollingSortino::new(3)
+            .unwrap()
+            .with_risk_free_rate(0.01)
+            .with_annualization(4.0);
+        // Excess: 0.02, -0.02, 0.03 → mean 0.01, downside √(0.0004 / 3).
+        let values = sortino.calculate(&[0.03, -0.01, 0.04]).unwrap();
+        let expected = 0.01 / (0.0004f64 / 3.0).sqrt() * 2.0;
+        assert!((values[0] - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_downside() {
+        let mut sortino = RollingSortino::new(2).unwrap();
+        assert_eq!(
+            sortino.calculate(&[0.01, 0.02, 0.0]).unwrap(),
+            vec![f64::INFINITY, f64::INFINITY]
+        );
+        assert_eq!(sortino.next(0.0).unwrap(), Some(0.0));
+        assert!(RollingSortino::new(1).is_err());
+    }
+}
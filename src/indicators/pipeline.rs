@@ -0,0 +1,291 @@
+//! Indicator composition
+//!
+//! A [`Pipeline`] feeds the output of one indicator into the next, so
+//! derived series such as "EMA of RSI" or "SMA of OBV" behave like a single
+//! indicator: `calculate()`, `next()`, `reset()` and the warm-up metadata
+//! all work on the chain as a whole.
+//!
+//! ```
+//! use rsta::indicators::momentum::Rsi;
+//! use rsta::indicators::pipeline::Pipeline;
+//! use rsta::indicators::trend::{Ema, Sma};
+//! use rsta::indicators::Indicator;
+//!
+//! let prices: Vec<f64> = (0..40).map(|i| 100.0 + (i as f64 * 0.5).sin() * 3.0).collect();
+//!
+//! // EMA(5) of RSI(14), then an SMA(3) on top of that.
+//! let mut chain = Pipeline::new(Rsi::new(14).unwrap(), Ema::new(5).unwrap())
+//!     .then(Sma::new(3).unwrap());
+//!
+//! // RSI(14) needs 15 prices and emits from the 15th. EMA(5) needs 5 RSI
+//! // values but emits from the first of them; SMA(3) then drops 2 more.
+//! assert_eq!(Indicator::<f64, f64>::min_periods(&chain), 19);
+//! assert_eq!(Indicator::<f64, f64>::output_offset(&chain), 16);
+//!
+//! let batch = chain.calculate(&prices).unwrap();
+//! assert_eq!(batch.len(), prices.len() - 16);
+//!
+//! // Streaming produces the same values (up to rounding).
+//! Indicator::<f64, f64>::reset(&mut chain);
+//! let streamed: Vec<f64> = prices
+//!     .iter()
+//!     .filter_map(|&p| chain.next(p).unwrap())
+//!     .collect();
+//! assert_eq!(batch.len(), streamed.len());
+//! assert!(batch.iter().zip(&streamed).all(|(a, b)| (a - b).abs() < 1e-9));
+//! ```
+
+use std::marker::PhantomData;
+
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Indicator, IndicatorError, IndicatorSeries, Timestamped};
+
+/// Two indicators chained so that the output of `first` (of type `M`) is
+/// the input of `second`.
+///
+/// `Pipeline` itself implements [`Indicator`], so longer chains are built by
+/// nesting — see [`Pipeline::then`].
+#[derive(Debug, Clone)]
+pub struct Pipeline<A, B, M> {
+    first: A,
+    second: B,
+    _intermediate: PhantomData<M>,
+}
+
+impl<A, B, M> Pipeline<A, B, M> {
+    /// Chain `second` after `first`.
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            _intermediate: PhantomData,
+        }
+    }
+
+    /// Append another stage, fed by the output of this pipeline.
+    pub fn then<C, O>(self, next: C) -> Pipeline<Self, C, O> {
+        Pipeline::new(self, next)
+    }
+
+    /// Borrow the first stage.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Borrow the second stage.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Split the pipeline back into its two stages.
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<T, M, O, A, B> Indicator<T, O> for Pipeline<A, B, M>
+where
+    A: Indicator<T, M>,
+    B: Indicator<M, O>,
+{
+    fn calculate(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        let intermediate = self.first.calculate(data)?;
+        self.second.calculate(&intermediate)
+    }
+
+    /// Inputs a stage skips (e.g. under a zero-range or zero-volume skip
+    /// policy) map to `None` at their original position.
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        let (positions, intermediate): (Vec<usize>, Vec<M>) = self
+            .first
+            .calculate_aligned(data)?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, value)| Some((i, value?)))
+            .unzip();
+        let values = self.second.calculate_aligned(&intermediate)?;
+        let mut out: Vec<Option<O>> = std::iter::repeat_with(|| None).take(data.len()).collect();
+        for (i, value) in positions.into_iter().zip(values) {
+            out[i] = value;
+        }
+        Ok(out)
+    }
+
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError> {
+        match self.first.next(value)? {
+            Some(intermediate) => self.second.next(intermediate),
+            None => Ok(None),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.second.current()
+    }
+
+    fn name(&self) -> &'static str {
+        "Pipeline"
+    }
+
+    /// The first stage must run (`first.min_periods()` inputs) and must then
+    /// emit enough values to satisfy the second stage.
+    fn min_periods(&self) -> usize {
+        self.first
+            .min_periods()
+            .max(self.first.output_offset() + self.second.min_periods())
+    }
+
+    fn output_offset(&self) -> usize {
+        self.first.output_offset() + self.second.output_offset()
+    }
+}
+
+#[cfg(all(test, feature = "trend", feature = "momentum", feature = "volume"))]
+mod tests {
+    use super::*;
+    use crate::indicators::momentum::Rsi;
+    use crate::indicators::trend::{Ema, Sma};
+    use crate::indicators::volume::Obv;
+    use crate::indicators::Candle;
+
+    fn prices() -> Vec<f64> {
+        (0..30)
+            .map(|i| 50.0 + (i as f64 * 0.8).sin() * 4.0 + i as f64 * 0.2)
+            .collect()
+    }
+
+    #[test]
+    fn test_ema_of_rsi_matches_manual_glue() {
+        let data = prices();
+        let mut rsi = Rsi::new(7).unwrap();
+        let mut ema = Ema::new(4).unwrap();
+        let rsi_values = rsi.calculate(&data).unwrap();
+        let expected = <Ema as Indicator<f64, f64>>::calculate(&mut ema, &rsi_values).unwrap();
+
+        let mut chain = Pipeline::new(Rsi::new(7).unwrap(), Ema::new(4).unwrap());
+        assert_eq!(chain.calculate(&data).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_sma_of_obv_on_candles() {
+        let candles: Vec<Candle> = prices()
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| Candle {
+                timestamp: i as u64,
+                open: c,
+                high: c + 1.0,
+                low: c - 1.0,
+                close: c,
+                volume: 100.0 + i as f64,
+            })
+            .collect();
+
+        let mut chain: Pipeline<Obv, Sma, f64> = Pipeline::new(Obv::new(), Sma::new(5).unwrap());
+        let batch = chain.calculate(&candles).unwrap();
+        assert_eq!(batch.len(), candles.len() - 4);
+
+        chain.reset();
+        let streamed: Vec<f64> = candles
+            .iter()
+            .filter_map(|c| chain.next(*c).unwrap())
+            .collect();
+        assert_eq!(batch, streamed);
+    }
+
+    #[test]
+    fn test_warmup_propagation() {
+        // SMA(3) -> SMA(4): first output at index 2 + 3.
+        let chain: Pipeline<Sma, Sma, f64> =
+            Pipeline::new(Sma::new(3).unwrap(), Sma::new(4).unwrap());
+        assert_eq!(Indicator::<f64, f64>::min_periods(&chain), 6);
+        assert_eq!(Indicator::<f64, f64>::output_offset(&chain), 5);
+
+        // EMA(10) emits from its first input, so SMA(3) on top only adds 2,
+        // but the EMA still needs its 10 seed values.
+        let chain: Pipeline<Ema, Sma, f64> =
+            Pipeline::new(Ema::new(10).unwrap(), Sma::new(3).unwrap());
+        assert_eq!(Indicator::<f64, f64>::min_periods(&chain), 10);
+        assert_eq!(Indicator::<f64, f64>::output_offset(&chain), 2);
+    }
+
+    #[test]
+    fn test_insufficient_data() {
+        let mut chain: Pipeline<Sma, Sma, f64> =
+            Pipeline::new(Sma::new(3).unwrap(), Sma::new(4).unwrap());
+        assert!(matches!(
+            chain.calculate(&[1.0, 2.0, 3.0, 4.0, 5.0]),
+            Err(IndicatorError::InsufficientData { .. })
+        ));
+        assert_eq!(
+            chain.calculate(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap(),
+            vec![3.5]
+        );
+    }
+
+    #[test]
+    fn test_aligned_output() {
+        let mut chain: Pipeline<Sma, Sma, f64> =
+            Pipeline::new(Sma::new(2).unwrap(), Sma::new(2).unwrap());
+        let out = chain.calculate_aligned(&[1.0, 3.0, 5.0, 7.0]).unwrap();
+        assert_eq!(out, vec![None, None, Some(3.0), Some(5.0)]);
+    }
+
+    #[test]
+    fn test_aligned_output_keeps_skipped_bars_in_place() {
+        use crate::indicators::volume::{Adl, ZeroRangePolicy};
+
+        let candle = |timestamp: u64, close: f64, high: f64, low: f64| Candle {
+            timestamp,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 100.0,
+        };
+        let data = [
+            candle(0, 11.0, 11.0, 9.0),
+            candle(1, 10.0, 10.0, 10.0),
+            candle(2, 11.0, 11.0, 10.0),
+            candle(3, 9.0, 11.0, 9.0),
+        ];
+        // ADL: 100, (skipped), 200, 100; its SMA(2): 150, 150.
+        let adl = Adl::new().with_zero_range(ZeroRangePolicy::Skip);
+        let mut chain: Pipeline<Adl, Sma, f64> = Pipeline::new(adl, Sma::new(2).unwrap());
+        let aligned = chain.calculate_aligned(&data).unwrap();
+        assert_eq!(aligned, vec![None, None, Some(150.0), Some(150.0)]);
+        assert_eq!(
+            aligned.into_iter().flatten().collect::<Vec<_>>(),
+            chain.calculate(&data).unwrap()
+        );
+        let series = chain.calculate_series(&data).unwrap();
+        assert_eq!(series.aligned()[2], Some(&150.0));
+    }
+
+    #[test]
+    fn test_reset_clears_every_stage() {
+        let mut chain: Pipeline<Sma, Sma, f64> =
+            Pipeline::new(Sma::new(2).unwrap(), Sma::new(2).unwrap());
+        for p in [1.0, 3.0, 5.0] {
+            chain.next(p).unwrap();
+        }
+        Indicator::<f64, f64>::reset(&mut chain);
+        assert_eq!(chain.next(10.0).unwrap(), None);
+        assert_eq!(chain.next(20.0).unwrap(), None);
+        assert_eq!(chain.next(30.0).unwrap(), Some(20.0));
+    }
+}
@@ -0,0 +1,470 @@
+//! Rounding of indicator outputs
+//!
+//! Indicator arithmetic leaves binary floating-point noise in its results:
+//! an SMA of `0.1` and `0.2` is `0.15000000000000002`, and a level that
+//! should equal a price on the book misses it by `1e-13`. A [`Precision`]
+//! rounds values to a number of decimals or to an exchange tick size, and
+//! [`Rounded`] wraps any indicator and applies it to every output, so the
+//! values strategy code compares are the ones an order could carry.
+//!
+//! Values are rounded to the nearest step, halves away from zero. Tick
+//! rounding lands on the double closest to the decimal multiple of the
+//! tick, so `Precision::tick(0.05)` turns `1.2345` into exactly `1.25`.
+//!
+//! ```
+//! use rsta::indicators::precision::{Precision, Rounded};
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::Indicator;
+//!
+//! let mut raw = Sma::new(2).unwrap();
+//! assert_eq!(raw.calculate(&[0.1, 0.2]).unwrap(), vec![0.15000000000000002]);
+//!
+//! let mut sma = Rounded::new(Sma::new(2).unwrap(), Precision::decimals(2).unwrap());
+//! assert_eq!(sma.calculate(&[0.1, 0.2]).unwrap(), vec![0.15]);
+//!
+//! let tick = Precision::tick(0.25).unwrap();
+//! assert_eq!(tick.round(101.13), 101.25);
+//! ```
+
+use crate::indicators::{Indicator, IndicatorError, IndicatorSeries, IndicatorValue, Timestamped};
+
+/// Largest number of decimals: beyond it `f64` cannot tell the steps apart.
+const MAX_DECIMALS: u32 = 15;
+
+/// Rounding policy for indicator outputs: a fixed number of decimals or a
+/// tick size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Precision {
+    step: f64,
+    /// Decimals of `step`, when it has a short decimal form.
+    decimals: Option<u32>,
+}
+
+impl Precision {
+    /// Round to `decimals` digits after the decimal point.
+    ///
+    /// # Arguments
+    /// * `decimals` - Number of decimals (at most 15)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The policy or an error
+    pub fn decimals(decimals: u32) -> Result<Self, IndicatorError> {
+        if decimals > MAX_DECIMALS {
+            return Err(IndicatorError::invalid_parameter(
+                "decimals",
+                decimals,
+                "must be at most 15",
+            ));
+        }
+        Ok(Self {
+            step: 10f64.powi(-(decimals as i32)),
+            decimals: Some(decimals),
+        })
+    }
+
+    /// Round to the nearest multiple of `tick_size`.
+    ///
+    /// # Arguments
+    /// * `tick_size` - Price increment (must be positive and finite)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The policy or an error
+    pub fn tick(tick_size: f64) -> Result<Self, IndicatorError> {
+        if tick_size <= 0.0 || !tick_size.is_finite() {
+            return Err(IndicatorError::invalid_parameter(
+                "tick_size",
+                tick_size,
+                "must be positive and finite",
+            ));
+        }
+        let decimals = (0..=MAX_DECIMALS).find(|&d| {
+            let scaled = tick_size * 10f64.powi(d as i32);
+            (scaled - scaled.round()).abs() <= 1e-9 * scaled
+        });
+        Ok(Self {
+            step: tick_size,
+            decimals,
+        })
+    }
+
+    /// The rounding step: the tick size, or `10^-decimals`.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// `value` rounded to the step. NaN and infinities pass through.
+    pub fn round(&self, value: f64) -> f64 {
+        if !value.is_finite() {
+            return value;
+        }
+        let rounded = match self.decimals {
+            Some(decimals) => {
+                let scale = 10f64.powi(decimals as i32);
+                let steps = (value / self.step).round();
+                // Re-round the multiple in decimal space so that e.g.
+                // 3 × 0.1 comes out as 0.3 rather than 0.30000000000000004.
+                (steps * self.step * scale).round() / scale
+            }
+            None => (value / self.step).round() * self.step,
+        };
+        // Normalize -0.0, e.g. from rounding -0.001 to two decimals.
+        rounded + 0.0
+    }
+}
+
+/// Output types a [`Precision`] can round.
+///
+/// Structured results round their price-level fields. Ratios such as a
+/// band's `bandwidth` keep full precision, since a tick size has no
+/// meaning for them.
+pub trait RoundOutput {
+    /// A copy of `self` with its values rounded.
+    fn rounded(&self, precision: &Precision) -> Self;
+}
+
+impl RoundOutput for f64 {
+    fn rounded(&self, precision: &Precision) -> Self {
+        precision.round(*self)
+    }
+}
+
+impl<O: RoundOutput> RoundOutput for Option<O> {
+    fn rounded(&self, precision: &Precision) -> Self {
+        self.as_ref().map(|value| value.rounded(precision))
+    }
+}
+
+impl RoundOutput for IndicatorValue {
+    fn rounded(&self, precision: &Precision) -> Self {
+        let r = |v: f64| precision.round(v);
+        match *self {
+            IndicatorValue::Single(v) => IndicatorValue::Single(r(v)),
+            IndicatorValue::Bands {
+                upper,
+                middle,
+                lower,
+            } => IndicatorValue::Bands {
+                upper: r(upper),
+                middle: r(middle),
+                lower: r(lower),
+            },
+            IndicatorValue::Stochastic { k, d } => IndicatorValue::Stochastic { k: r(k), d: r(d) },
+            IndicatorValue::Macd {
+                macd,
+                signal,
+                histogram,
+            } => IndicatorValue::Macd {
+                macd: r(macd),
+                signal: r(signal),
+                histogram: r(histogram),
+            },
+            IndicatorValue::Adx {
+                adx,
+                plus_di,
+                minus_di,
+            } => IndicatorValue::Adx {
+                adx: r(adx),
+                plus_di: r(plus_di),
+                minus_di: r(minus_di),
+            },
+            IndicatorValue::Ichimoku {
+                tenkan,
+                kijun,
+                senkou_a,
+                senkou_b,
+                chikou,
+            } => IndicatorValue::Ichimoku {
+                tenkan: r(tenkan),
+                kijun: r(kijun),
+                senkou_a: r(senkou_a),
+                senkou_b: r(senkou_b),
+                chikou: r(chikou),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "trend")]
+mod trend_outputs {
+    use super::{Precision, RoundOutput};
+    use crate::indicators::trend::{AdxResult, IchimokuResult, MacdResult, PivotResult};
+
+    impl RoundOutput for MacdResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            MacdResult {
+                macd: r(self.macd),
+                signal: r(self.signal),
+                histogram: r(self.histogram),
+            }
+        }
+    }
+
+    impl RoundOutput for AdxResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            AdxResult {
+                plus_di: r(self.plus_di),
+                minus_di: r(self.minus_di),
+                adx: r(self.adx),
+            }
+        }
+    }
+
+    impl RoundOutput for IchimokuResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            IchimokuResult {
+                tenkan: r(self.tenkan),
+                kijun: r(self.kijun),
+                senkou_a: r(self.senkou_a),
+                senkou_b: r(self.senkou_b),
+                chikou: r(self.chikou),
+            }
+        }
+    }
+
+    impl RoundOutput for PivotResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            PivotResult {
+                pp: r(self.pp),
+                r1: r(self.r1),
+                r2: r(self.r2),
+                r3: r(self.r3),
+                r4: r(self.r4),
+                s1: r(self.s1),
+                s2: r(self.s2),
+                s3: r(self.s3),
+                s4: r(self.s4),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "momentum")]
+impl RoundOutput for crate::indicators::momentum::StochasticResult {
+    fn rounded(&self, precision: &Precision) -> Self {
+        let r = |v: f64| precision.round(v);
+        Self {
+            k_fast: r(self.k_fast),
+            k: r(self.k),
+            d: r(self.d),
+        }
+    }
+}
+
+#[cfg(feature = "volatility")]
+mod volatility_outputs {
+    use super::{Precision, RoundOutput};
+    use crate::indicators::volatility::{
+        BollingerBandsResult, DonchianResult, KeltnerChannelsResult,
+    };
+
+    impl RoundOutput for BollingerBandsResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            BollingerBandsResult {
+                middle: r(self.middle),
+                upper: r(self.upper),
+                lower: r(self.lower),
+                bandwidth: self.bandwidth,
+            }
+        }
+    }
+
+    impl RoundOutput for KeltnerChannelsResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            KeltnerChannelsResult {
+                middle: r(self.middle),
+                upper: r(self.upper),
+                lower: r(self.lower),
+                bandwidth: self.bandwidth,
+            }
+        }
+    }
+
+    impl RoundOutput for DonchianResult {
+        fn rounded(&self, precision: &Precision) -> Self {
+            let r = |v: f64| precision.round(v);
+            DonchianResult {
+                upper: r(self.upper),
+                middle: r(self.middle),
+                lower: r(self.lower),
+            }
+        }
+    }
+}
+
+/// Wraps an indicator and rounds each of its outputs with a
+/// [`Precision`]. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Rounded<I, O> {
+    inner: I,
+    precision: Precision,
+    last: Option<O>,
+}
+
+impl<I, O> Rounded<I, O> {
+    /// Wrap `inner`, rounding its outputs with `precision`.
+    pub fn new(inner: I, precision: Precision) -> Self {
+        Self {
+            inner,
+            precision,
+            last: None,
+        }
+    }
+
+    /// The active precision.
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    /// Borrow the wrapped indicator.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Unwrap, returning the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I, T, O> Indicator<T, O> for Rounded<I, O>
+where
+    I: Indicator<T, O>,
+    O: RoundOutput + Clone,
+{
+    fn calculate(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        let outputs: Vec<O> = self
+            .inner
+            .calculate(data)?
+            .iter()
+            .map(|output| output.rounded(&self.precision))
+            .collect();
+        self.last = outputs.last().cloned();
+        Ok(outputs)
+    }
+
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        let outputs: Vec<Option<O>> = self
+            .inner
+            .calculate_aligned(data)?
+            .iter()
+            .map(|output| output.rounded(&self.precision))
+            .collect();
+        self.last = outputs.iter().rev().flatten().next().cloned();
+        Ok(outputs)
+    }
+
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError> {
+        let output = self.inner.next(value)?.rounded(&self.precision);
+        if output.is_some() {
+            self.last.clone_from(&output);
+        }
+        Ok(output)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        self.inner.output_offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_and_tick_rounding() {
+        let cents = Precision::decimals(2).unwrap();
+        assert_eq!(cents.round(1.005_000_1), 1.01);
+        assert_eq!(cents.round(-0.001), 0.0);
+        assert!(cents.round(-0.001).is_sign_positive());
+        assert!(cents.round(f64::NAN).is_nan());
+        assert_eq!(Precision::decimals(0).unwrap().round(2.5), 3.0);
+        assert!(Precision::decimals(16).is_err());
+
+        let tick = Precision::tick(0.1).unwrap();
+        assert_eq!(tick.round(0.29), 0.3); // not 0.30000000000000004
+        assert_eq!(Precision::tick(0.05).unwrap().round(1.2345), 1.25);
+        assert_eq!(Precision::tick(5.0).unwrap().round(1_237.4), 1_235.0);
+        // A tick without a short decimal form still lands on its multiples.
+        let third = Precision::tick(1.0 / 3.0).unwrap();
+        assert_eq!(third.round(0.9), 1.0);
+        assert!(Precision::tick(0.0).is_err());
+        assert!(Precision::tick(f64::INFINITY).is_err());
+
+        let value = IndicatorValue::Bands {
+            upper: 1.234,
+            middle: 1.0,
+            lower: 0.766,
+        };
+        assert_eq!(
+            value.rounded(&cents),
+            IndicatorValue::Bands {
+                upper: 1.23,
+                middle: 1.0,
+                lower: 0.77,
+            }
+        );
+    }
+
+    #[cfg(feature = "volume")]
+    #[test]
+    fn test_aligned_output_keeps_skipped_bars_in_place() {
+        use crate::indicators::volume::{Adl, ZeroRangePolicy};
+        use crate::indicators::Candle;
+
+        let candle = |close: f64, high: f64, low: f64| Candle {
+            timestamp: 0,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 3.3,
+        };
+        let data = [
+            candle(10.0, 11.0, 9.0),
+            candle(10.0, 10.0, 10.0),
+            candle(11.0, 11.0, 10.0),
+        ];
+        let adl = Adl::new().with_zero_range(ZeroRangePolicy::Skip);
+        let mut rounded = Rounded::new(adl, Precision::decimals(0).unwrap());
+        let aligned = rounded.calculate_aligned(&data).unwrap();
+        assert_eq!(aligned, vec![Some(0.0), None, Some(3.0)]);
+        assert_eq!(rounded.current(), Some(&3.0));
+        let series = rounded.calculate_series(&data).unwrap();
+        assert_eq!(series.aligned(), vec![Some(&0.0), None, Some(&3.0)]);
+    }
+}
@@ -0,0 +1,192 @@
+//! Rolling window statistic from a closure
+//!
+//! [`Rolling`] runs a user closure over the last `period` values, which
+//! covers one-off window statistics (rolling median, interquartile range,
+//! max - min) without writing a full indicator type. The closure sees the
+//! window oldest first.
+//!
+//! ```
+//! use rsta::indicators::rolling::Rolling;
+//! use rsta::indicators::Indicator;
+//!
+//! // Rolling median over three values
+//! let mut median = Rolling::new(3, |window: &[f64]| {
+//!     let mut sorted = window.to_vec();
+//!     sorted.sort_by(f64::total_cmp);
+//!     sorted[sorted.len() / 2]
+//! })
+//! .unwrap();
+//!
+//! let values = median.calculate(&[5.0, 1.0, 3.0, 9.0, 2.0]).unwrap();
+//! assert_eq!(values, vec![3.0, 3.0, 3.0]);
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Indicator applying a closure to a rolling window. See the
+/// [module docs](self).
+#[derive(Clone)]
+pub struct Rolling<F> {
+    period: usize,
+    window: VecDeque<f64>,
+    f: F,
+    last: Option<f64>,
+}
+
+impl<F> Rolling<F>
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    /// Create a new rolling indicator
+    ///
+    /// # Arguments
+    /// * `period` - The window length (must be at least 1)
+    /// * `f` - Statistic computed from the window, oldest value first
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new Rolling or an error
+    pub fn new(period: usize, f: F) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            f,
+            last: None,
+        })
+    }
+
+    /// Reset the window without dropping the closure.
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < self.period {
+            return None;
+        }
+        self.last = Some((self.f)(self.window.make_contiguous()));
+        self.last
+    }
+}
+
+impl<F> fmt::Debug for Rolling<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rolling")
+            .field("period", &self.period)
+            .field("window", &self.window)
+            .field("last", &self.last)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> Indicator<f64, f64> for Rolling<F>
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&v| self.step(v)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Rolling"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(window: &[f64]) -> f64 {
+        let max = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = window.iter().copied().fold(f64::INFINITY, f64::min);
+        max - min
+    }
+
+    #[test]
+    fn test_rolling_new() {
+        assert!(Rolling::new(3, range).is_ok());
+        assert_eq!(
+            Rolling::new(0, range).unwrap_err(),
+            IndicatorError::invalid_parameter("period", 0, "must be at least 1")
+        );
+    }
+
+    #[test]
+    fn test_rolling_window_is_oldest_first() {
+        let mut first = Rolling::new(3, |w: &[f64]| w[0]).unwrap();
+        let values = first.calculate(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        assert_eq!(values, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_rolling_range() {
+        let mut rolling = Rolling::new(3, range).unwrap();
+        let values = rolling.calculate(&[1.0, 4.0, 2.0, 8.0, 7.0]).unwrap();
+        assert_eq!(values, vec![3.0, 6.0, 6.0]);
+        assert!(rolling.calculate(&[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_rolling_next_matches_calculate() {
+        let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let mut batch = Rolling::new(4, range).unwrap();
+        let expected = batch.calculate(&data).unwrap();
+
+        let mut stream = Rolling::new(4, range).unwrap();
+        let streamed: Vec<f64> = data
+            .iter()
+            .filter_map(|&v| stream.next(v).unwrap())
+            .collect();
+        assert_eq!(streamed, expected);
+        assert_eq!(stream.current(), expected.last());
+
+        stream.reset();
+        assert_eq!(stream.current(), None);
+        assert_eq!(stream.next(1.0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rolling_stateful_closure() {
+        // FnMut closures may keep their own state across windows.
+        let mut calls = 0;
+        let mut rolling = Rolling::new(2, |w: &[f64]| {
+            calls += 1;
+            w[1] - w[0]
+        })
+        .unwrap();
+        assert_eq!(rolling.calculate(&[1.0, 3.0, 6.0]).unwrap(), vec![2.0, 3.0]);
+        drop(rolling);
+        assert_eq!(calls, 2);
+    }
+}
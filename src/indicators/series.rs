@@ -0,0 +1,312 @@
+//! Indicator output tagged with timestamps
+//!
+//! `calculate()` returns bare values, shorter than the input by the
+//! indicator's warm-up. [`IndicatorSeries`] keeps each value next to the
+//! timestamp of the candle it belongs to, so results can be plotted or
+//! joined back to candles without index arithmetic.
+//! [`Indicator::calculate_series`](crate::indicators::Indicator::calculate_series)
+//! builds one.
+//!
+//! ```
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::{Candle, Indicator};
+//!
+//! let candles: Vec<Candle> = [10.0, 11.0, 12.0, 13.0]
+//!     .iter()
+//!     .enumerate()
+//!     .map(|(i, &close)| Candle {
+//!         timestamp: 1_000 + 60 * i as u64,
+//!         open: close, high: close, low: close, close, volume: 1.0,
+//!     })
+//!     .collect();
+//!
+//! let series = Sma::new(3).unwrap().calculate_series(&candles).unwrap();
+//! assert_eq!(series.offset(), 2);
+//! assert_eq!(series.get(1_120), Some(&11.0));
+//! assert_eq!(series.get(1_000), None); // still warming up
+//! assert_eq!(series.at(3), Some(&12.0));
+//!
+//! let points: Vec<(u64, f64)> = series.into_iter().collect();
+//! assert_eq!(points, vec![(1_120, 11.0), (1_180, 12.0)]);
+//! ```
+
+use crate::indicators::Candle;
+
+/// Input points that carry a timestamp.
+pub trait Timestamped {
+    /// Timestamp of the point.
+    fn timestamp(&self) -> u64;
+}
+
+impl Timestamped for Candle {
+    fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+/// Indicator values paired with the timestamps of their input points. See
+/// the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndicatorSeries<O> {
+    offset: usize,
+    /// Input indices past `offset` that have no value, ascending.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    skipped: Vec<usize>,
+    timestamps: Vec<u64>,
+    values: Vec<O>,
+}
+
+impl<O> IndicatorSeries<O> {
+    /// Pair `values` with the last `values.len()` points of `data`
+    ///
+    /// Values are right-aligned with the input, the layout `calculate()`
+    /// produces.
+    ///
+    /// # Arguments
+    /// * `data` - The input the values were calculated from
+    /// * `values` - The calculated values
+    ///
+    /// # Panics
+    /// If there are more values than input points.
+    pub fn new<T: Timestamped>(data: &[T], values: Vec<O>) -> Self {
+        assert!(
+            values.len() <= data.len(),
+            "{} values for {} input points",
+            values.len(),
+            data.len()
+        );
+        let offset = data.len() - values.len();
+        Self {
+            offset,
+            skipped: Vec::new(),
+            timestamps: data[offset..].iter().map(Timestamped::timestamp).collect(),
+            values,
+        }
+    }
+
+    /// Pair the values of `aligned` with the points of `data` they sit at
+    ///
+    /// Takes the output of
+    /// [`calculate_aligned`](crate::indicators::Indicator::calculate_aligned),
+    /// so it also covers indicators that skip bars past their warm-up, such
+    /// as `Adl` with `ZeroRangePolicy::Skip`: a skipped bar keeps `None` in
+    /// [`at`](Self::at) and [`aligned`](Self::aligned).
+    ///
+    /// # Arguments
+    /// * `data` - The input the values were calculated from
+    /// * `aligned` - One entry per input point, `None` where there is no value
+    ///
+    /// # Panics
+    /// If `aligned` and `data` differ in length.
+    pub fn from_aligned<T: Timestamped>(data: &[T], aligned: Vec<Option<O>>) -> Self {
+        assert!(
+            aligned.len() == data.len(),
+            "{} aligned values for {} input points",
+            aligned.len(),
+            data.len()
+        );
+        let offset = aligned
+            .iter()
+            .position(Option::is_some)
+            .unwrap_or(data.len());
+        let mut series = Self {
+            offset,
+            skipped: Vec::new(),
+            timestamps: Vec::with_capacity(data.len() - offset),
+            values: Vec::with_capacity(data.len() - offset),
+        };
+        for (i, value) in aligned.into_iter().enumerate().skip(offset) {
+            match value {
+                Some(value) => {
+                    series.timestamps.push(data[i].timestamp());
+                    series.values.push(value);
+                }
+                None => series.skipped.push(i),
+            }
+        }
+        series
+    }
+
+    /// Number of leading input points without a value (the warm-up).
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the series has no values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Timestamps of the values.
+    pub fn timestamps(&self) -> &[u64] {
+        &self.timestamps
+    }
+
+    /// The values, without timestamps.
+    pub fn values(&self) -> &[O] {
+        &self.values
+    }
+
+    /// Unwrap into the values, as `calculate()` would have returned them.
+    pub fn into_values(self) -> Vec<O> {
+        self.values
+    }
+
+    /// Value at the input point with this timestamp. Assumes timestamps
+    /// are ascending, as candle series are.
+    pub fn get(&self, timestamp: u64) -> Option<&O> {
+        let i = self.timestamps.binary_search(&timestamp).ok()?;
+        Some(&self.values[i])
+    }
+
+    /// Value for the input point at `index`; `None` during warm-up.
+    pub fn at(&self, index: usize) -> Option<&O> {
+        let position = index.checked_sub(self.offset)?;
+        match self.skipped.binary_search(&index) {
+            Ok(_) => None,
+            Err(before) => self.values.get(position - before),
+        }
+    }
+
+    /// Value of the latest input point.
+    pub fn last(&self) -> Option<(u64, &O)> {
+        Some((*self.timestamps.last()?, self.values.last()?))
+    }
+
+    /// `(timestamp, value)` pairs, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &O)> + '_ {
+        self.timestamps.iter().copied().zip(&self.values)
+    }
+
+    /// One entry per input point, `None` during warm-up, ready to zip with
+    /// the candles.
+    pub fn aligned(&self) -> Vec<Option<&O>> {
+        let len = self.offset + self.skipped.len() + self.values.len();
+        let mut out = Vec::with_capacity(len);
+        out.resize(self.offset, None);
+        let mut values = self.values.iter();
+        let mut skipped = self.skipped.iter().peekable();
+        for i in self.offset..len {
+            if skipped.next_if_eq(&&i).is_some() {
+                out.push(None);
+            } else {
+                out.push(values.next());
+            }
+        }
+        out
+    }
+
+    /// Transform the values, keeping their timestamps.
+    pub fn map<U>(self, f: impl FnMut(O) -> U) -> IndicatorSeries<U> {
+        IndicatorSeries {
+            offset: self.offset,
+            skipped: self.skipped,
+            timestamps: self.timestamps,
+            values: self.values.into_iter().map(f).collect(),
+        }
+    }
+}
+
+impl<O> IntoIterator for IndicatorSeries<O> {
+    type Item = (u64, O);
+    type IntoIter = std::iter::Zip<std::vec::IntoIter<u64>, std::vec::IntoIter<O>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.timestamps.into_iter().zip(self.values)
+    }
+}
+
+#[cfg(all(test, feature = "trend", feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::{Ema, Macd};
+    use crate::indicators::volatility::Atr;
+    use crate::indicators::Indicator;
+
+    fn candles(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 50.0 + (i as f64 * 0.7).sin() * 3.0;
+                Candle {
+                    timestamp: 10 * i as u64,
+                    open: c,
+                    high: c + 1.0,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 100.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_series_matches_calculate() {
+        let data = candles(30);
+        let mut atr = Atr::new(5).unwrap();
+        let expected = atr.calculate(&data).unwrap();
+        let series = atr.calculate_series(&data).unwrap();
+
+        assert_eq!(series.offset(), atr.output_offset());
+        assert_eq!(series.len(), expected.len());
+        assert_eq!(series.values(), &expected[..]);
+        assert_eq!(series.timestamps()[0], data[series.offset()].timestamp);
+        assert_eq!(series.last(), Some((290, expected.last().unwrap())));
+        assert_eq!(
+            series.aligned(),
+            atr.calculate_aligned(&data)
+                .unwrap()
+                .iter()
+                .map(Option::as_ref)
+                .collect::<Vec<_>>()
+        );
+        for (i, candle) in data.iter().enumerate() {
+            assert_eq!(series.at(i), series.get(candle.timestamp));
+        }
+        assert_eq!(series.get(5), None);
+        assert_eq!(series.into_values(), expected);
+    }
+
+    #[test]
+    fn test_series_without_warmup_and_structured_output() {
+        let data = candles(12);
+        let ema = Ema::new(4).unwrap().calculate_series(&data).unwrap();
+        assert_eq!(ema.offset(), 0);
+        assert_eq!(ema.at(0), Some(&data[0].close));
+
+        let macd = Macd::new(2, 4, 2).unwrap().calculate_series(&data).unwrap();
+        let histogram = macd.clone().map(|r| r.histogram);
+        assert_eq!(histogram.timestamps(), macd.timestamps());
+        let (ts, last) = macd.last().unwrap();
+        assert_eq!(histogram.get(ts), Some(&last.histogram));
+    }
+
+    #[test]
+    fn test_series_from_aligned_keeps_skipped_bars_empty() {
+        let data = candles(5);
+        let series =
+            IndicatorSeries::from_aligned(&data, vec![None, Some(1.0), None, Some(3.0), None]);
+        assert_eq!(series.offset(), 1);
+        assert_eq!(series.timestamps(), &[10, 30]);
+        assert_eq!(
+            series.aligned(),
+            vec![None, Some(&1.0), None, Some(&3.0), None]
+        );
+        assert_eq!((series.at(2), series.at(3)), (None, Some(&3.0)));
+        assert_eq!(series.get(20), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "3 values for 2 input points")]
+    fn test_series_rejects_more_values_than_inputs() {
+        IndicatorSeries::new(&candles(2), vec![1.0, 2.0, 3.0]);
+    }
+}
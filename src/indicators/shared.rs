@@ -0,0 +1,268 @@
+//! # Sharing an indicator across threads
+//!
+//! Every built-in indicator is `Send + Sync`, so it can move to a feed
+//! thread or sit behind an `Arc`, but updating it takes `&mut self`. The
+//! type-erased containers — [`IndicatorSet`](super::IndicatorSet) and
+//! `Box<dyn AnyIndicator>` — are `Send` only.
+//!
+//! [`SharedIndicator`] is a cloneable handle for the common split: one
+//! thread feeds the indicator, others (a UI, an RPC handler) read its
+//! latest output. The indicator sits behind a mutex taken only by
+//! updates; the latest output is kept apart (in a lock-free
+//! `LatestValue` with the `latest` feature), so readers never wait for a
+//! calculation in progress.
+//! [`reader`](SharedIndicator::reader) hands out a read-only
+//! [`IndicatorReader`].
+//!
+//! ```
+//! use std::thread;
+//!
+//! use rsta::indicators::shared::SharedIndicator;
+//! use rsta::indicators::trend::Sma;
+//!
+//! let sma = SharedIndicator::new(Sma::new(3).unwrap());
+//! let reader = sma.reader();
+//!
+//! let feed = thread::spawn(move || {
+//!     for price in [1.0, 2.0, 3.0, 4.0] {
+//!         sma.next(price).unwrap();
+//!     }
+//! });
+//! feed.join().unwrap();
+//!
+//! assert_eq!(reader.latest(), Some(3.0));
+//! assert_eq!(reader.updates(), 4);
+//! ```
+
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(not(feature = "latest"))]
+use std::sync::RwLock;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+#[cfg(feature = "latest")]
+use crate::indicators::latest::{LatestValue, Subscription};
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Latest output and update count, shared with the readers.
+#[derive(Debug)]
+struct Latest<O> {
+    #[cfg(feature = "latest")]
+    value: LatestValue<O>,
+    #[cfg(not(feature = "latest"))]
+    value: RwLock<Option<O>>,
+    updates: AtomicU64,
+}
+
+impl<O: Clone> Latest<O> {
+    fn new() -> Self {
+        Self {
+            value: Default::default(),
+            updates: AtomicU64::new(0),
+        }
+    }
+
+    #[cfg(feature = "latest")]
+    fn get(&self) -> Option<O> {
+        self.value.get()
+    }
+
+    #[cfg(not(feature = "latest"))]
+    fn get(&self) -> Option<O> {
+        self.value
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    #[cfg(feature = "latest")]
+    fn publish(&self, value: Option<O>) {
+        match value {
+            Some(value) => self.value.publish(value),
+            None => self.value.clear(),
+        }
+    }
+
+    #[cfg(not(feature = "latest"))]
+    fn publish(&self, value: Option<O>) {
+        *self.value.write().unwrap_or_else(PoisonError::into_inner) = value;
+    }
+
+    fn set(&self, value: Option<O>, inputs: usize) {
+        self.publish(value);
+        self.updates.fetch_add(inputs as u64, Ordering::Release);
+    }
+}
+
+/// Cloneable, thread-safe handle to one indicator. Clones share the
+/// indicator and its latest output. See the [module docs](self).
+#[derive(Debug)]
+pub struct SharedIndicator<I, T, O> {
+    indicator: Arc<Mutex<I>>,
+    latest: Arc<Latest<O>>,
+    _input: PhantomData<fn(T)>,
+}
+
+impl<I, T, O> Clone for SharedIndicator<I, T, O> {
+    fn clone(&self) -> Self {
+        Self {
+            indicator: Arc::clone(&self.indicator),
+            latest: Arc::clone(&self.latest),
+            _input: PhantomData,
+        }
+    }
+}
+
+impl<I, T, O> SharedIndicator<I, T, O>
+where
+    I: Indicator<T, O>,
+    O: Clone,
+{
+    /// Share `indicator`. Its current output, if any, is the first
+    /// latest value.
+    pub fn new(indicator: I) -> Self {
+        let latest = Latest::new();
+        if let Some(current) = indicator.current() {
+            latest.publish(Some(current.clone()));
+        }
+        Self {
+            indicator: Arc::new(Mutex::new(indicator)),
+            latest: Arc::new(latest),
+            _input: PhantomData,
+        }
+    }
+
+    /// Feed one value, as [`Indicator::next`], and publish the output.
+    /// While the indicator warms up the latest value stays as it was.
+    pub fn next(&self, value: T) -> Result<Option<O>, IndicatorError> {
+        let mut indicator = self.lock();
+        let output = indicator.next(value)?;
+        if let Some(output) = &output {
+            self.latest.publish(Some(output.clone()));
+        }
+        self.latest.updates.fetch_add(1, Ordering::Release);
+        Ok(output)
+    }
+
+    /// Run [`Indicator::calculate`] and publish its last output.
+    pub fn calculate(&self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        let mut indicator = self.lock();
+        let outputs = indicator.calculate(data)?;
+        self.latest.set(outputs.last().cloned(), data.len());
+        Ok(outputs)
+    }
+
+    /// Reset the indicator and clear the latest value.
+    pub fn reset(&self) {
+        let mut indicator = self.lock();
+        indicator.reset();
+        self.latest.set(None, 0);
+    }
+
+    /// Latest output, without waiting for an update in progress.
+    pub fn latest(&self) -> Option<O> {
+        self.latest.get()
+    }
+
+    /// Number of inputs processed since construction, counting batch
+    /// inputs one by one. Readers can poll it to detect new values.
+    pub fn updates(&self) -> u64 {
+        self.latest.updates.load(Ordering::Acquire)
+    }
+
+    /// Subscription to the published outputs.
+    #[cfg(feature = "latest")]
+    pub fn subscribe(&self) -> Subscription<O> {
+        self.latest.value.subscribe()
+    }
+
+    /// Read-only handle to the latest output.
+    pub fn reader(&self) -> IndicatorReader<O> {
+        IndicatorReader {
+            latest: Arc::clone(&self.latest),
+        }
+    }
+
+    /// Call `f` with the indicator, e.g. to read its parameters. Updates
+    /// wait until it returns.
+    pub fn inspect<R>(&self, f: impl FnOnce(&I) -> R) -> R {
+        f(&self.lock())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, I> {
+        self.indicator
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Read-only view of a [`SharedIndicator`]'s latest output.
+#[derive(Debug)]
+pub struct IndicatorReader<O> {
+    latest: Arc<Latest<O>>,
+}
+
+impl<O> Clone for IndicatorReader<O> {
+    fn clone(&self) -> Self {
+        Self {
+            latest: Arc::clone(&self.latest),
+        }
+    }
+}
+
+impl<O: Clone> IndicatorReader<O> {
+    /// Latest output of the indicator.
+    pub fn latest(&self) -> Option<O> {
+        self.latest.get()
+    }
+
+    /// Number of inputs the indicator has processed.
+    pub fn updates(&self) -> u64 {
+        self.latest.updates.load(Ordering::Acquire)
+    }
+
+    /// Subscription to the published outputs.
+    #[cfg(feature = "latest")]
+    pub fn subscribe(&self) -> Subscription<O> {
+        self.latest.value.subscribe()
+    }
+}
+
+#[cfg(all(test, feature = "trend"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::Sma;
+    use std::thread;
+
+    #[test]
+    fn test_readers_follow_a_feed_thread() {
+        let sma = SharedIndicator::new(Sma::new(2).unwrap());
+        assert_eq!(sma.latest(), None);
+        assert_eq!(sma.calculate(&[1.0, 2.0, 3.0]).unwrap(), vec![1.5, 2.5]);
+        assert_eq!((sma.latest(), sma.updates()), (Some(2.5), 3));
+
+        let feeds: Vec<_> = (0..4)
+            .map(|_| {
+                let sma = sma.clone();
+                thread::spawn(move || {
+                    for _ in 0..100 {
+                        sma.next(10.0).unwrap();
+                    }
+                })
+            })
+            .collect();
+        let reader = sma.reader();
+        for feed in feeds {
+            feed.join().unwrap();
+        }
+        assert_eq!(reader.latest(), Some(10.0));
+        assert_eq!(reader.updates(), 403);
+        assert_eq!(sma.inspect(Indicator::<f64, f64>::min_periods), 2);
+
+        sma.reset();
+        assert_eq!(reader.latest(), None);
+        // Warming up again: the latest value is kept until the next output.
+        sma.next(4.0).unwrap();
+        assert_eq!(reader.latest(), None);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::VecDeque;
+
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::IndicatorError;
+
+/// Rolling permutation entropy
+///
+/// Measures how predictable a series is from the ordinal patterns of its
+/// values (Bandt & Pompe): every run of `order` values, `delay` steps
+/// apart, is reduced to the order in which its values rank, and the
+/// Shannon entropy of how often each of the `order!` patterns occurs over
+/// the last `period` values is normalized to `[0, 1]`.
+///
+/// A steady trend repeats one pattern and scores near 0; noise uses every
+/// pattern evenly and scores near 1. As a strategy filter, low entropy
+/// favours breakout and trend logic, high entropy points to chop. Ties
+/// rank by position, earlier first.
+///
+/// # Formula
+///
+/// ```text
+/// pe = -Σ p(π) ln p(π) / ln(order!)   over the ordinal patterns π
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::statistics::PermutationEntropy;
+/// use rsta::indicators::Indicator;
+///
+/// let mut pe = PermutationEntropy::new(20, 3).unwrap();
+/// let trend: Vec<f64> = (0..20).map(|i| i as f64).collect();
+/// assert_eq!(pe.calculate(&trend).unwrap(), vec![0.0]);
+///
+/// let noise: Vec<f64> = (0..200).map(|i| ((i as f64 * 12.9898).sin() * 43758.55).fract()).collect();
+/// let values = pe.calculate(&noise).unwrap();
+/// assert!(values.iter().all(|v| *v > 0.7));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermutationEntropy {
+    period: usize,
+    order: usize,
+    delay: usize,
+    values: VecDeque<f64>,
+    patterns: VecDeque<usize>,
+    counts: Vec<usize>,
+    last: Option<f64>,
+}
+
+impl PermutationEntropy {
+    /// Create a new permutation entropy
+    ///
+    /// # Arguments
+    /// * `period` - Number of values in the window (must exceed `order - 1`)
+    /// * `order` - Length of the ordinal patterns (2 to 7, typically 3 to 5)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize, order: usize) -> Result<Self, IndicatorError> {
+        if !(2..=7).contains(&order) {
+            return Err(IndicatorError::invalid_parameter(
+                "order",
+                order,
+                "must be between 2 and 7",
+            ));
+        }
+        let entropy = Self {
+            period,
+            order,
+            delay: 1,
+            values: VecDeque::with_capacity(period),
+            patterns: VecDeque::new(),
+            counts: vec![0; (1..=order).product()],
+            last: None,
+        };
+        entropy.validate_span()?;
+        Ok(entropy)
+    }
+
+    /// Compare values `delay` steps apart (1 by default) within each
+    /// pattern. `period` must still exceed `(order - 1) * delay`.
+    pub fn with_delay(mut self, delay: usize) -> Result<Self, IndicatorError> {
+        if delay == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "delay",
+                delay,
+                "must be at least 1",
+            ));
+        }
+        self.delay = delay;
+        self.validate_span()?;
+        Ok(self)
+    }
+
+    fn validate_span(&self) -> Result<(), IndicatorError> {
+        let span = (self.order - 1) * self.delay;
+        if self.period <= span {
+            return Err(IndicatorError::invalid_parameter(
+                "period",
+                self.period,
+                format!("must exceed (order - 1) * delay = {span}"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.values.clear();
+        self.patterns.clear();
+        self.counts.iter_mut().for_each(|c| *c = 0);
+        self.last = None;
+    }
+
+    /// Index of the ordinal pattern ending at the latest value, from its
+    /// Lehmer code.
+    fn pattern(&self) -> usize {
+        let end = self.values.len() - 1;
+        let span = (self.order - 1) * self.delay;
+        let point = |k: usize| self.values[end - span + k * self.delay];
+        (0..self.order).fold(0, |code, i| {
+            // Later values that rank below value i.
+            let smaller = (i + 1..self.order).filter(|&j| point(j) < point(i)).count();
+            code * (self.order - i) + smaller
+        })
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        if self.values.len() == self.period {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+        if self.values.len() <= (self.order - 1) * self.delay {
+            return None;
+        }
+        let pattern = self.pattern();
+        self.patterns.push_back(pattern);
+        self.counts[pattern] += 1;
+        let capacity = self.period - (self.order - 1) * self.delay;
+        if self.patterns.len() > capacity {
+            if let Some(old) = self.patterns.pop_front() {
+                self.counts[old] -= 1;
+            }
+        }
+        if self.values.len() < self.period {
+            return None;
+        }
+
+        let total = self.patterns.len() as f64;
+        let entropy: f64 = self
+            .counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / total;
+                -p * p.ln()
+            })
+            .sum();
+        let normalized = (entropy / (self.counts.len() as f64).ln()).max(0.0);
+        self.last = Some(normalized);
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for PermutationEntropy {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "PermutationEntropy"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_equally_likely_patterns() {
+        // Up, down, up, ...: with order 2 the two patterns split evenly,
+        // the maximum entropy.
+        let zigzag: Vec<f64> = (0..9).map(|i| (i % 2) as f64).collect();
+        let mut pe = PermutationEntropy::new(5, 2).unwrap();
+        let values = pe.calculate(&zigzag).unwrap();
+        assert_eq!(values.len(), 5);
+        assert!(values.iter().all(|v| (v - 1.0).abs() < 1e-12));
+
+        // With a delay of 2 the compared values are equal: one pattern.
+        let mut delayed = PermutationEntropy::new(5, 2)
+            .unwrap()
+            .with_delay(2)
+            .unwrap();
+        assert_eq!(delayed.calculate(&zigzag).unwrap(), vec![0.0; 5]);
+    }
+
+    #[test]
+    fn test_pattern_codes_are_distinct() {
+        let mut pe = PermutationEntropy::new(3, 3).unwrap();
+        let mut seen = Vec::new();
+        for window in [
+            [1.0, 2.0, 3.0],
+            [1.0, 3.0, 2.0],
+            [2.0, 1.0, 3.0],
+            [2.0, 3.0, 1.0],
+            [3.0, 1.0, 2.0],
+            [3.0, 2.0, 1.0],
+        ] {
+            pe.reset_state();
+            window.iter().for_each(|&v| {
+                pe.next(v).unwrap();
+            });
+            seen.push(pe.pattern());
+        }
+        seen.sort_unstable();
+        assert_eq!(seen, (0..6).collect::<Vec<_>>());
+
+        assert!(PermutationEntropy::new(10, 1).is_err());
+        assert!(PermutationEntropy::new(10, 8).is_err());
+        assert!(PermutationEntropy::new(2, 3).is_err());
+        assert!(PermutationEntropy::new(4, 3)
+            .unwrap()
+            .with_delay(2)
+            .is_err());
+    }
+}
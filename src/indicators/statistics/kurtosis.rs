@@ -0,0 +1,136 @@
+use crate::indicators::statistics::RollingMoments;
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::IndicatorError;
+
+/// Rolling excess kurtosis
+///
+/// Tail weight of the distribution of the last `period` values relative
+/// to a normal distribution: `0` for normal tails, positive for fat tails
+/// (more extreme moves than the variance suggests). Uses the
+/// bias-corrected sample estimator (Excel's `KURT`, pandas'
+/// `rolling().kurt()`).
+///
+/// # Formula
+///
+/// ```text
+/// g2   = m4 / m2² - 3                 (m_k: k-th central moment)
+/// kurt = ((n + 1) g2 + 6) (n - 1) / ((n - 2) (n - 3))
+/// ```
+///
+/// A flat window gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::statistics::Kurtosis;
+/// use rsta::indicators::Indicator;
+///
+/// // Two values alternating: no tails at all.
+/// let mut kurt = Kurtosis::new(6).unwrap();
+/// let values = kurt.calculate(&[1.0, -1.0, 1.0, -1.0, 1.0, -1.0]).unwrap();
+/// assert!((values[0] - -3.3333333333333335).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Kurtosis {
+    period: usize,
+    moments: RollingMoments,
+    last: Option<f64>,
+}
+
+impl Kurtosis {
+    /// Create a new rolling kurtosis
+    ///
+    /// # Arguments
+    /// * `period` - Number of values in the window (must be at least 4)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 4)?;
+        Ok(Self {
+            period,
+            moments: RollingMoments::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.moments.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        self.moments.push(value);
+        if !self.moments.is_full() {
+            return None;
+        }
+        let n = self.period as f64;
+        let m2 = self.moments.central_moment(2)?;
+        let m4 = self.moments.central_moment(4)?;
+        let kurt = if m2 > 0.0 {
+            let g2 = m4 / (m2 * m2) - 3.0;
+            ((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0))
+        } else {
+            0.0
+        };
+        self.last = Some(kurt);
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for Kurtosis {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Kurtosis"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kurtosis_matches_sample_formula() {
+        let data = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0, 3.0];
+        let mut kurt = Kurtosis::new(6).unwrap();
+        let values = kurt.calculate(&data).unwrap();
+        assert_eq!(values.len(), 4);
+        for (value, window) in values.iter().zip(data.windows(6)) {
+            let mean = window.iter().sum::<f64>() / 6.0;
+            let m2 = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 6.0;
+            let m4 = window.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / 6.0;
+            let g2 = m4 / (m2 * m2) - 3.0;
+            let expected = (7.0 * g2 + 6.0) * 5.0 / 12.0;
+            assert!((value - expected).abs() < 1e-9);
+        }
+        assert_eq!(kurt.calculate(&[1.0; 6]).unwrap(), vec![0.0]);
+        assert!(Kurtosis::new(3).is_err());
+    }
+}
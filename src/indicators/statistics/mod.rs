@@ -0,0 +1,34 @@
+//! Distribution-shape statistics
+//!
+//! Rolling higher moments of a series — usually returns — as features for
+//! quant pipelines: [`Skewness`] measures the asymmetry of the window's
+//! distribution, [`Kurtosis`] the weight of its tails.
+//! [`PermutationEntropy`] scores how predictable the series is, from the
+//! order its values move in.
+//!
+//! Both are built on [`RollingMoments`], which updates the central moments
+//! in one pass as values enter and leave the window, without the
+//! catastrophic cancellation of raw power sums.
+//!
+//! ```
+//! use rsta::indicators::statistics::{Kurtosis, Skewness};
+//! use rsta::indicators::Indicator;
+//!
+//! // Mostly small gains with the occasional large loss.
+//! let returns: Vec<f64> = (0..60).map(|i| if i % 10 == 9 { -0.05 } else { 0.005 }).collect();
+//!
+//! let skew = Skewness::new(30).unwrap().calculate(&returns).unwrap();
+//! let kurt = Kurtosis::new(30).unwrap().calculate(&returns).unwrap();
+//! assert!(skew.iter().all(|s| *s < -2.0));
+//! assert!(kurt.iter().all(|k| *k > 3.0));
+//! ```
+
+pub mod entropy;
+pub mod kurtosis;
+pub mod moments;
+pub mod skewness;
+
+pub use self::entropy::PermutationEntropy;
+pub use self::kurtosis::Kurtosis;
+pub use self::moments::RollingMoments;
+pub use self::skewness::Skewness;
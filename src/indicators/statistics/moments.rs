@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+
+use crate::indicators::utils::validate_period;
+use crate::indicators::IndicatorError;
+
+/// Rolling mean and second to fourth central moments
+///
+/// Keeps the sums of squared, cubed and fourth-power deviations from the
+/// mean of the last `period` values, updated in O(1) per value with the
+/// one-pass formulas of Welford and Pébay; removing a value applies the
+/// inverse update. Like `RollingStats`, the sums are recomputed from the
+/// window periodically, and while a non-finite value is in the window the
+/// moments are NaN.
+///
+/// ```
+/// use rsta::indicators::statistics::RollingMoments;
+///
+/// let mut m = RollingMoments::new(4).unwrap();
+/// for v in [9.0, 1.0, 2.0, 3.0, 6.0] {
+///     m.push(v);
+/// }
+/// // Window 1, 2, 3, 6: mean 3, deviations -2, -1, 0, 3.
+/// assert_eq!(m.mean(), Some(3.0));
+/// assert!((m.central_moment(2).unwrap() - 14.0 / 4.0).abs() < 1e-12);
+/// assert!((m.central_moment(3).unwrap() - 18.0 / 4.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMoments {
+    period: usize,
+    values: VecDeque<f64>,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    non_finite: usize,
+    stale: bool,
+    since_resync: usize,
+}
+
+impl RollingMoments {
+    /// Create a window of `period` values (must be at least 1).
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self {
+            period,
+            values: VecDeque::with_capacity(period),
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            non_finite: 0,
+            stale: false,
+            since_resync: 0,
+        })
+    }
+
+    /// Window length.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Add a value, evicting the oldest once the window is full.
+    pub fn push(&mut self, value: f64) {
+        let evicted = if self.values.len() == self.period {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(value);
+        if !value.is_finite() {
+            self.non_finite += 1;
+        }
+        if evicted.is_some_and(|x| !x.is_finite()) {
+            self.non_finite -= 1;
+        }
+
+        if self.non_finite > 0 {
+            self.stale = true;
+            return;
+        }
+        self.since_resync += 1;
+        if self.stale || self.since_resync >= self.period.max(1024) {
+            self.resync();
+            return;
+        }
+        if let Some(old) = evicted {
+            self.remove(old, self.values.len());
+        }
+        self.add(value, self.values.len());
+    }
+
+    /// Fold `x` into the moments of `n - 1` values.
+    fn add(&mut self, x: f64, n: usize) {
+        let n = n as f64;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * (n - 1.0);
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+
+    /// Take `x` out of the moments of `n` values: the inverse of `add`.
+    fn remove(&mut self, x: f64, n: usize) {
+        if n == 1 {
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            self.m3 = 0.0;
+            self.m4 = 0.0;
+            return;
+        }
+        let n = n as f64;
+        let mean = (n * self.mean - x) / (n - 1.0);
+        let delta = x - mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * (n - 1.0);
+        self.mean = mean;
+        self.m2 = (self.m2 - term).max(0.0);
+        self.m3 -= term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m4 -= term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m4 = self.m4.max(0.0);
+    }
+
+    /// Number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// `true` if no value has been pushed since the last reset.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// `true` once the window holds `period` values.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.period
+    }
+
+    /// Mean of the window, `None` when empty.
+    pub fn mean(&self) -> Option<f64> {
+        self.moment(self.mean)
+    }
+
+    /// `order`-th central moment of the window, `Σ(x - mean)^order / n`,
+    /// for orders 2 to 4; `None` when empty or for another order.
+    pub fn central_moment(&self, order: u32) -> Option<f64> {
+        let sum = match order {
+            2 => self.m2,
+            3 => self.m3,
+            4 => self.m4,
+            _ => return None,
+        };
+        self.moment(sum / self.values.len() as f64)
+    }
+
+    fn moment(&self, value: f64) -> Option<f64> {
+        match (self.values.is_empty(), self.non_finite) {
+            (true, _) => None,
+            (false, 0) => Some(value),
+            _ => Some(f64::NAN),
+        }
+    }
+
+    /// Clear the window.
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.m3 = 0.0;
+        self.m4 = 0.0;
+        self.non_finite = 0;
+        self.stale = false;
+        self.since_resync = 0;
+    }
+
+    fn resync(&mut self) {
+        let n = self.values.len() as f64;
+        self.mean = self.values.iter().sum::<f64>() / n;
+        let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+        for x in &self.values {
+            let d = x - self.mean;
+            let d2 = d * d;
+            m2 += d2;
+            m3 += d2 * d;
+            m4 += d2 * d2;
+        }
+        (self.m2, self.m3, self.m4) = (m2, m3, m4);
+        self.stale = false;
+        self.since_resync = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_moments_match_direct() {
+        let data: Vec<f64> = (0..3000)
+            .map(|i| 1e4 + (i as f64 * 0.37).sin() * 3.0 + (i % 7) as f64)
+            .collect();
+        let mut m = RollingMoments::new(50).unwrap();
+        for (i, &v) in data.iter().enumerate() {
+            m.push(v);
+            if i < 49 || i % 97 != 0 {
+                continue;
+            }
+            let window = &data[i - 49..=i];
+            let mean = window.iter().sum::<f64>() / 50.0;
+            for order in 2..=4 {
+                let direct = window
+                    .iter()
+                    .map(|x| (x - mean).powi(order as i32))
+                    .sum::<f64>()
+                    / 50.0;
+                let got = m.central_moment(order).unwrap();
+                assert!((got - direct).abs() < 1e-6 * direct.abs().max(1.0));
+            }
+        }
+        m.push(f64::NAN);
+        assert!(m.central_moment(3).unwrap().is_nan());
+        assert_eq!(m.central_moment(5), None);
+        m.reset();
+        assert_eq!(m.mean(), None);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::indicators::statistics::RollingMoments;
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::IndicatorError;
+
+/// Rolling skewness
+///
+/// Asymmetry of the distribution of the last `period` values: negative
+/// when the left tail is longer (occasional large drops), positive when
+/// the right tail is. Uses the bias-corrected sample estimator (adjusted
+/// Fisher-Pearson, as Excel's `SKEW` and pandas' `rolling().skew()`).
+///
+/// # Formula
+///
+/// ```text
+/// g1   = m3 / m2^1.5                  (m_k: k-th central moment)
+/// skew = g1 * √(n (n - 1)) / (n - 2)
+/// ```
+///
+/// A flat window gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::statistics::Skewness;
+/// use rsta::indicators::Indicator;
+///
+/// let mut skew = Skewness::new(4).unwrap();
+/// let values = skew.calculate(&[1.0, 2.0, 3.0, 10.0]).unwrap();
+/// assert!(values[0] > 1.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Skewness {
+    period: usize,
+    moments: RollingMoments,
+    last: Option<f64>,
+}
+
+impl Skewness {
+    /// Create a new rolling skewness
+    ///
+    /// # Arguments
+    /// * `period` - Number of values in the window (must be at least 3)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 3)?;
+        Ok(Self {
+            period,
+            moments: RollingMoments::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.moments.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        self.moments.push(value);
+        if !self.moments.is_full() {
+            return None;
+        }
+        let n = self.period as f64;
+        let m2 = self.moments.central_moment(2)?;
+        let m3 = self.moments.central_moment(3)?;
+        let skew = if m2 > 0.0 {
+            m3 / m2.powf(1.5) * (n * (n - 1.0)).sqrt() / (n - 2.0)
+        } else {
+            0.0
+        };
+        self.last = Some(skew);
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for Skewness {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Skewness"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skewness_matches_sample_formula() {
+        let data = [2.0, 8.0, 0.0, 4.0, 1.0, 9.0, 9.0, 0.0];
+        let mut skew = Skewness::new(5).unwrap();
+        let values = skew.calculate(&data).unwrap();
+        assert_eq!(values.len(), 4);
+        for (value, window) in values.iter().zip(data.windows(5)) {
+            let mean = window.iter().sum::<f64>() / 5.0;
+            let m2 = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 5.0;
+            let m3 = window.iter().map(|x| (x - mean).powi(3)).sum::<f64>() / 5.0;
+            let expected = m3 / m2.powf(1.5) * 20f64.sqrt() / 3.0;
+            assert!((value - expected).abs() < 1e-9);
+        }
+        assert!(skew.calculate(&[1.0, 2.0, 3.0]).is_err());
+        // Symmetric window.
+        let mut skew = Skewness::new(3).unwrap();
+        assert!(skew.calculate(&[1.0, 2.0, 3.0]).unwrap()[0].abs() < 1e-12);
+        assert_eq!(skew.calculate(&[4.0; 3]).unwrap(), vec![0.0]);
+        assert!(Skewness::new(2).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Trade (tick) data
+//!
+//! A [`Tick`] is a single trade from an exchange trade feed. Tick-native
+//! indicators (`CumulativeDelta`, `TradeImbalance`, `RollingVwap` in the
+//! `volume` module) consume them directly, so trade streams do not have to
+//! be aggregated into candles first.
+
+/// Aggressor side of a trade: who crossed the spread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TradeSide {
+    /// The buyer lifted the offer.
+    Buy,
+    /// The seller hit the bid.
+    Sell,
+    /// Not reported by the feed; indicators infer it with the [`TickRule`].
+    #[default]
+    Unknown,
+}
+
+impl TradeSide {
+    /// `1.0` for buys, `-1.0` for sells, `0.0` when unknown.
+    pub fn sign(self) -> f64 {
+        match self {
+            TradeSide::Buy => 1.0,
+            TradeSide::Sell => -1.0,
+            TradeSide::Unknown => 0.0,
+        }
+    }
+}
+
+/// A single trade
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::{Tick, TradeSide};
+///
+/// let tick = Tick { timestamp: 1_700_000_000, price: 101.5, size: 0.25, side: TradeSide::Buy };
+/// assert_eq!(tick.notional(), 25.375);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    /// Trade time (typically Unix timestamp in seconds or milliseconds)
+    pub timestamp: u64,
+    /// Trade price
+    pub price: f64,
+    /// Traded quantity
+    pub size: f64,
+    /// Aggressor side, [`TradeSide::Unknown`] if the feed does not report it
+    pub side: TradeSide,
+}
+
+impl Tick {
+    /// Traded value, `price * size`.
+    pub fn notional(&self) -> f64 {
+        self.price * self.size
+    }
+}
+
+/// Tick-rule trade classification
+///
+/// Classifies a trade as a buy when it prints above the previous trade
+/// price and as a sell when it prints below. A trade at the same price
+/// (a zero tick) keeps the previous classification; the very first trade
+/// is [`TradeSide::Unknown`].
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::{TickRule, TradeSide};
+///
+/// let mut rule = TickRule::new();
+/// assert_eq!(rule.classify(10.0), TradeSide::Unknown);
+/// assert_eq!(rule.classify(10.5), TradeSide::Buy);
+/// assert_eq!(rule.classify(10.5), TradeSide::Buy);
+/// assert_eq!(rule.classify(10.2), TradeSide::Sell);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TickRule {
+    last_price: Option<f64>,
+    last_side: TradeSide,
+}
+
+impl TickRule {
+    /// Create a classifier with no price history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classify a trade at `price` against the previous one.
+    pub fn classify(&mut self, price: f64) -> TradeSide {
+        if let Some(last) = self.last_price {
+            if price > last {
+                self.last_side = TradeSide::Buy;
+            } else if price < last {
+                self.last_side = TradeSide::Sell;
+            }
+        }
+        self.last_price = Some(price);
+        self.last_side
+    }
+
+    /// The side of `tick`: the reported side if known, the tick rule
+    /// otherwise. The tick's price always updates the rule.
+    pub fn side(&mut self, tick: &Tick) -> TradeSide {
+        let inferred = self.classify(tick.price);
+        match tick.side {
+            TradeSide::Unknown => inferred,
+            reported => reported,
+        }
+    }
+
+    /// Forget the price history.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_rule_prefers_reported_side() {
+        let tick = |price, side| Tick {
+            timestamp: 0,
+            price,
+            size: 1.0,
+            side,
+        };
+        let mut rule = TickRule::new();
+        assert_eq!(
+            rule.side(&tick(10.0, TradeSide::Unknown)),
+            TradeSide::Unknown
+        );
+        assert_eq!(rule.side(&tick(11.0, TradeSide::Sell)), TradeSide::Sell);
+        // The reported side does not change the rule's own history.
+        assert_eq!(rule.side(&tick(11.0, TradeSide::Unknown)), TradeSide::Buy);
+        rule.reset();
+        assert_eq!(rule.classify(9.0), TradeSide::Unknown);
+        assert_eq!(TradeSide::Sell.sign(), -1.0);
+    }
+}
@@ -0,0 +1,565 @@
+//! Resampling and multi-timeframe indicators
+//!
+//! Strategies often trade one bar size while filtering on a slower one —
+//! entering on 5-minute bars only when the 1-hour RSI agrees. This module
+//! builds the slower bars from the base stream and keeps the indicators on
+//! every timeframe in step with it:
+//!
+//! - [`Resampler`] aggregates base candles into bars of a longer period
+//!   (open of the first, max high, min low, close of the last, summed
+//!   volume). Bars are aligned on multiples of the period since the epoch
+//!   and stamped with their start time; [`Resampler::with_timezone`] and
+//!   [`Resampler::with_day_start`] align them to exchange-local time
+//!   instead.
+//! - [`resample`] is the batch form.
+//! - [`MultiTimeframe`] runs one instance of an indicator per timeframe
+//!   and, on every base candle, reports the latest value of each one.
+//!
+//! A higher-timeframe bar is complete once the base candle covering its
+//! last slot arrives, so values never look ahead: during an hour the 1h
+//! value is the one computed at the close of the previous hour.
+//!
+//! ```
+//! use rsta::indicators::timeframe::MultiTimeframe;
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::{Candle, Indicator};
+//!
+//! // 1-minute candles, SMA(2) on 1m and 3m closes.
+//! let candles: Vec<Candle> = (0..9)
+//!     .map(|i| {
+//!         let p = i as f64;
+//!         Candle { timestamp: i * 60, open: p, high: p, low: p, close: p, volume: 1.0 }
+//!     })
+//!     .collect();
+//! let mut mtf = MultiTimeframe::new(60, &[60, 180], || Sma::new(2)).unwrap();
+//! let out = mtf.calculate(&candles).unwrap();
+//!
+//! // After the 9th candle: 1m SMA of closes 7, 8; 3m SMA of the 3m closes
+//! // 5 and 8.
+//! assert_eq!(out[8], vec![Some(7.5), Some(6.5)]);
+//! // The first 3m bar closes at the third candle; SMA(2) needs two of them.
+//! assert_eq!(out[4][1], None);
+//! assert_eq!(mtf.value(180), Some(&6.5));
+//! ```
+
+use crate::calendar::TimeZone;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Streaming aggregation of base candles into longer bars.
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    base_period: u64,
+    period: u64,
+    timezone: TimeZone,
+    day_start: i64,
+    partial: Option<Candle>,
+    /// Start of the partial bar on the local grid.
+    partial_start: i64,
+    /// Timestamp of the latest bar, complete or not. Candles of that bar
+    /// or earlier ones are rejected once it is complete.
+    last_bar: Option<u64>,
+}
+
+impl Resampler {
+    /// Aggregate candles of `base_period` into bars of `period` (same time
+    /// unit as [`Candle::timestamp`]). `period` must be a positive
+    /// multiple of `base_period`.
+    pub fn new(base_period: u64, period: u64) -> Result<Self, IndicatorError> {
+        if base_period == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "base_period",
+                base_period,
+                "must be greater than 0",
+            ));
+        }
+        if period < base_period || period % base_period != 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "period",
+                period,
+                format!("must be a multiple of the base period {base_period}"),
+            ));
+        }
+        Ok(Self {
+            base_period,
+            period,
+            timezone: TimeZone::Utc,
+            day_start: 0,
+            partial: None,
+            partial_start: 0,
+            last_bar: None,
+        })
+    }
+
+    /// Align bars on local time in `timezone` instead of UTC, so daily and
+    /// 4h bars start at local midnight. Timestamps stay in UTC and must be
+    /// Unix seconds.
+    ///
+    /// When the zone changes its offset (daylight saving time) the bar
+    /// spanning the change is an hour longer or shorter.
+    ///
+    /// ```
+    /// use rsta::calendar::TimeZone;
+    /// use rsta::indicators::timeframe::Resampler;
+    /// use rsta::indicators::Candle;
+    ///
+    /// // Daily bars from hourly candles, days starting at midnight UTC+2.
+    /// let mut daily = Resampler::new(3_600, 86_400)
+    ///     .unwrap()
+    ///     .with_timezone(TimeZone::Fixed(2 * 3_600));
+    /// let bar = |timestamp| Candle { timestamp, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume: 1.0 };
+    /// let midnight_utc = 10 * 86_400;
+    /// // 20:00 UTC is 22:00 local; 21:00 UTC is the last hour of the local day.
+    /// assert!(daily.next(bar(midnight_utc - 4 * 3_600)).unwrap().is_empty());
+    /// let done = daily.next(bar(midnight_utc - 3 * 3_600)).unwrap();
+    /// // The day started at local midnight, 22:00 UTC the day before.
+    /// assert_eq!(done[0].timestamp, midnight_utc - 86_400 - 2 * 3_600);
+    /// ```
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Start the bar grid `seconds` after local midnight, e.g. `17 * 3600`
+    /// with [`TimeZone::UsEastern`] for FX days ending at the New York
+    /// close.
+    pub fn with_day_start(mut self, seconds: u32) -> Self {
+        self.day_start = i64::from(seconds);
+        self
+    }
+
+    /// Time zone the bars are aligned in.
+    pub fn timezone(&self) -> TimeZone {
+        self.timezone
+    }
+
+    /// Length of the output bars.
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// The bar being built, if any.
+    pub fn partial(&self) -> Option<&Candle> {
+        self.partial.as_ref()
+    }
+
+    /// Add a base candle and return the bars it completes, oldest first.
+    ///
+    /// Usually zero or one. After a gap in the data, the unfinished bar is
+    /// emitted when a candle from a later bar arrives, so two bars can
+    /// complete at once. A candle belonging to a bar that is already
+    /// complete is an error.
+    pub fn next(&mut self, candle: Candle) -> Result<Vec<Candle>, IndicatorError> {
+        // Work on the local grid; `elapsed` is the time since the bar start.
+        let local = self.timezone.local(candle.timestamp) - self.day_start;
+        let elapsed = local.rem_euclid(self.period as i64);
+        let start = local - elapsed;
+        let bar_timestamp = candle.timestamp.saturating_sub(elapsed as u64);
+        let mut done = Vec::new();
+        match (self.partial.as_mut(), self.last_bar) {
+            (Some(bar), _) if self.partial_start == start => {
+                bar.high = bar.high.max(candle.high);
+                bar.low = bar.low.min(candle.low);
+                bar.close = candle.close;
+                bar.volume += candle.volume;
+            }
+            (_, Some(last)) if bar_timestamp <= last => {
+                return Err(IndicatorError::CalculationError(format!(
+                    "Candle at {} falls in a {}s bar that is already complete",
+                    candle.timestamp, self.period
+                )));
+            }
+            _ => {
+                done.extend(self.partial.take());
+                self.partial = Some(Candle {
+                    timestamp: bar_timestamp,
+                    ..candle
+                });
+                self.partial_start = start;
+                self.last_bar = Some(bar_timestamp);
+            }
+        }
+        if elapsed as u64 + self.base_period >= self.period {
+            done.extend(self.partial.take());
+        }
+        Ok(done)
+    }
+
+    /// Take the unfinished bar, if any (e.g. at the end of a data set).
+    pub fn flush(&mut self) -> Option<Candle> {
+        self.partial.take()
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.partial = None;
+        self.last_bar = None;
+    }
+}
+
+/// Resample `candles` (of `base_period`) into bars of `period`.
+///
+/// The last bar is included even when the data ends before it is complete.
+///
+/// ```
+/// use rsta::indicators::timeframe::resample;
+/// use rsta::indicators::Candle;
+///
+/// let minutes: Vec<Candle> = (0..5)
+///     .map(|i| {
+///         let p = 10.0 + i as f64;
+///         Candle { timestamp: 60 * i, open: p, high: p + 1.0, low: p - 1.0, close: p, volume: 1.0 }
+///     })
+///     .collect();
+/// let bars = resample(&minutes, 60, 180).unwrap();
+/// assert_eq!(bars.len(), 2);
+/// assert_eq!((bars[0].open, bars[0].high, bars[0].close, bars[0].volume), (10.0, 13.0, 12.0, 3.0));
+/// assert_eq!(bars[1].timestamp, 180);
+/// ```
+pub fn resample(
+    candles: &[Candle],
+    base_period: u64,
+    period: u64,
+) -> Result<Vec<Candle>, IndicatorError> {
+    let mut resampler = Resampler::new(base_period, period)?;
+    let mut out = Vec::with_capacity(candles.len() / (period / base_period) as usize + 1);
+    for &candle in candles {
+        out.extend(resampler.next(candle)?);
+    }
+    out.extend(resampler.flush());
+    Ok(out)
+}
+
+#[derive(Debug, Clone)]
+struct Frame<I, O> {
+    resampler: Resampler,
+    indicator: I,
+    last: Option<O>,
+}
+
+/// The same indicator maintained on several timeframes of one candle
+/// stream.
+///
+/// Implements `Indicator<Candle, Vec<Option<O>>>`: every base candle
+/// yields the latest value of each timeframe, in the order given to
+/// [`new`](Self::new), `None` until that timeframe has warmed up.
+#[derive(Debug, Clone)]
+pub struct MultiTimeframe<I, O> {
+    frames: Vec<Frame<I, O>>,
+    /// Latest combined output, kept for [`Indicator::current`].
+    last: Option<Vec<Option<O>>>,
+}
+
+impl<I, O> MultiTimeframe<I, O>
+where
+    I: Indicator<Candle, O>,
+{
+    /// Build one indicator per timeframe with `make`. Timeframes must be
+    /// distinct multiples of `base_period`, the spacing of the input
+    /// candles; use `base_period` itself for the base timeframe.
+    pub fn new<F>(base_period: u64, timeframes: &[u64], mut make: F) -> Result<Self, IndicatorError>
+    where
+        F: FnMut() -> Result<I, IndicatorError>,
+    {
+        if timeframes.is_empty() {
+            return Err(IndicatorError::invalid_parameter(
+                "timeframes",
+                "[]",
+                "at least one timeframe is required",
+            ));
+        }
+        let mut frames: Vec<Frame<I, O>> = Vec::with_capacity(timeframes.len());
+        for &tf in timeframes {
+            if frames.iter().any(|f| f.resampler.period() == tf) {
+                return Err(IndicatorError::invalid_parameter(
+                    "timeframes",
+                    tf,
+                    "timeframes must be distinct",
+                ));
+            }
+            frames.push(Frame {
+                resampler: Resampler::new(base_period, tf)?,
+                indicator: make()?,
+                last: None,
+            });
+        }
+        Ok(Self { frames, last: None })
+    }
+
+    /// Align every timeframe on local time in `timezone`; see
+    /// [`Resampler::with_timezone`].
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        for frame in &mut self.frames {
+            frame.resampler = frame.resampler.clone().with_timezone(timezone);
+        }
+        self
+    }
+
+    /// Start every timeframe's bar grid `seconds` after local midnight; see
+    /// [`Resampler::with_day_start`].
+    pub fn with_day_start(mut self, seconds: u32) -> Self {
+        for frame in &mut self.frames {
+            frame.resampler = frame.resampler.clone().with_day_start(seconds);
+        }
+        self
+    }
+
+    /// The timeframes, in output order.
+    pub fn timeframes(&self) -> Vec<u64> {
+        self.frames.iter().map(|f| f.resampler.period()).collect()
+    }
+
+    /// Latest value on `timeframe`, `None` during warm-up or for an
+    /// unknown timeframe.
+    pub fn value(&self, timeframe: u64) -> Option<&O> {
+        self.frames
+            .iter()
+            .find(|f| f.resampler.period() == timeframe)
+            .and_then(|f| f.last.as_ref())
+    }
+
+    /// Borrow the indicator running on `timeframe`.
+    pub fn indicator(&self, timeframe: u64) -> Option<&I> {
+        self.frames
+            .iter()
+            .find(|f| f.resampler.period() == timeframe)
+            .map(|f| &f.indicator)
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        for frame in &mut self.frames {
+            frame.resampler.reset_state();
+            frame.indicator.reset();
+            frame.last = None;
+        }
+        self.last = None;
+    }
+}
+
+impl<I, O> Indicator<Candle, Vec<Option<O>>> for MultiTimeframe<I, O>
+where
+    I: Indicator<Candle, O>,
+    O: Clone,
+{
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Vec<Option<O>>>, IndicatorError> {
+        self.reset_state();
+        let mut out = Vec::with_capacity(data.len());
+        for &candle in data {
+            if let Some(values) = self.next(candle)? {
+                out.push(values);
+            }
+        }
+        Ok(out)
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<Vec<Option<O>>>, IndicatorError> {
+        for frame in &mut self.frames {
+            for bar in frame.resampler.next(value)? {
+                if let Some(v) = frame.indicator.next(bar)? {
+                    frame.last = Some(v);
+                }
+            }
+        }
+        self.last = Some(self.frames.iter().map(|f| f.last.clone()).collect());
+        Ok(self.last.clone())
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Vec<Option<O>>> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "MultiTimeframe"
+    }
+}
+
+#[cfg(all(test, feature = "trend", feature = "momentum"))]
+mod tests {
+    use super::*;
+    use crate::indicators::momentum::Rsi;
+    use crate::indicators::trend::Sma;
+
+    fn minutes(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Candle {
+                timestamp: 60 * i as u64,
+                open: c - 0.5,
+                high: c + 1.0,
+                low: c - 1.0,
+                close: c,
+                volume: 2.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn resampler_completes_on_last_slot() {
+        let mut r = Resampler::new(60, 180).unwrap();
+        let data = minutes(&[1.0, 2.0, 3.0, 4.0]);
+        assert!(r.next(data[0]).unwrap().is_empty());
+        assert!(r.next(data[1]).unwrap().is_empty());
+        let bar = r.next(data[2]).unwrap();
+        assert_eq!(bar.len(), 1);
+        let b = bar[0];
+        assert_eq!((b.timestamp, b.open, b.high, b.low), (0, 0.5, 4.0, 0.0));
+        assert_eq!((b.close, b.volume), (3.0, 6.0));
+        assert!(r.next(data[3]).unwrap().is_empty());
+        assert_eq!(r.partial().map(|c| c.timestamp), Some(180));
+    }
+
+    #[test]
+    fn resampler_handles_gaps_and_rejects_old_candles() {
+        let mut r = Resampler::new(60, 180).unwrap();
+        let data = minutes(&[1.0; 12]);
+        r.next(data[0]).unwrap();
+        // Jump to the last slot of the third bar: both bars complete.
+        let done = r.next(data[8]).unwrap();
+        assert_eq!(
+            done.iter().map(|c| c.timestamp).collect::<Vec<_>>(),
+            [0, 360]
+        );
+        r.next(data[10]).unwrap();
+        assert!(r.next(data[5]).is_err());
+    }
+
+    #[test]
+    fn resampler_rejects_candles_of_a_completed_bar() {
+        let mut r = Resampler::new(60, 180).unwrap();
+        let data = minutes(&[1.0, 2.0, 3.0, 4.0]);
+        r.next(data[0]).unwrap();
+        r.next(data[1]).unwrap();
+        assert_eq!(r.next(data[2]).unwrap().len(), 1);
+        // The bar just closed: a late candle must not reopen it.
+        assert!(r.next(data[1]).is_err());
+        assert!(r.partial().is_none());
+        assert!(r.next(data[3]).unwrap().is_empty());
+        assert_eq!(r.partial().map(|c| c.timestamp), Some(180));
+
+        r.reset_state();
+        assert!(r.next(data[1]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn resampler_aligns_on_local_time() {
+        let hour = 3_600;
+        let bar = |timestamp, close| Candle {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        };
+        // 2024-01-02 00:00 UTC.
+        let day = 19_724 * 86_400;
+
+        // 4h bars in UTC+1 start at 23:00, 03:00, 07:00... UTC.
+        let mut r = Resampler::new(hour, 4 * hour)
+            .unwrap()
+            .with_timezone(TimeZone::Fixed(hour as i32));
+        let done: Vec<Candle> = (0..8)
+            .flat_map(|h| r.next(bar(day + h * hour, h as f64)).unwrap())
+            .collect();
+        assert_eq!(done.len(), 2);
+        assert_eq!(done[0].timestamp, day - hour);
+        assert_eq!((done[0].open, done[0].close), (0.0, 2.0));
+        assert_eq!(done[1].timestamp, day + 3 * hour);
+        assert_eq!(r.partial().map(|c| c.timestamp), Some(day + 7 * hour));
+
+        // FX days close at 17:00 New York: 22:00 UTC in winter.
+        let bars: Vec<Candle> = (0..48).map(|h| bar(day + h * hour, h as f64)).collect();
+        let mut fx = Resampler::new(hour, 24 * hour)
+            .unwrap()
+            .with_timezone(TimeZone::UsEastern)
+            .with_day_start(17 * hour as u32);
+        let days: Vec<Candle> = bars.iter().flat_map(|&c| fx.next(c).unwrap()).collect();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].timestamp, day - 2 * hour);
+        assert_eq!((days[0].close, days[0].volume), (21.0, 22.0));
+        assert_eq!(days[1].timestamp, day + 22 * hour);
+        assert_eq!(days[1].volume, 24.0);
+
+        // Every timeframe of a MultiTimeframe follows the zone.
+        let mut mtf = MultiTimeframe::new(hour, &[4 * hour], || Sma::new(1))
+            .unwrap()
+            .with_timezone(TimeZone::Fixed(hour as i32));
+        mtf.calculate(&bars[..4]).unwrap();
+        assert_eq!(mtf.value(4 * hour), Some(&2.0));
+    }
+
+    #[test]
+    fn resampler_keeps_dst_days_whole() {
+        let hour = 3_600;
+        // 2024-03-10 05:00 UTC is midnight in New York; clocks jump at 07:00.
+        let midnight = 19_792 * 86_400 + 5 * hour;
+        let mut r = Resampler::new(hour, 24 * hour)
+            .unwrap()
+            .with_timezone(TimeZone::UsEastern);
+        let candles = (0..23).map(|h| Candle {
+            timestamp: midnight + h * hour,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+        });
+        let done: Vec<Candle> = candles.flat_map(|c| r.next(c).unwrap()).collect();
+        // The 23-hour local day is one bar, completed by its last hour.
+        assert_eq!(done.len(), 1);
+        assert_eq!((done[0].timestamp, done[0].volume), (midnight, 23.0));
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert!(Resampler::new(0, 60).is_err());
+        assert!(Resampler::new(60, 90).is_err());
+        assert!(Resampler::new(60, 30).is_err());
+        assert!(MultiTimeframe::new(60, &[], || Sma::new(2)).is_err());
+        assert!(MultiTimeframe::new(60, &[60, 60], || Sma::new(2)).is_err());
+        assert!(MultiTimeframe::new(60, &[60], || Sma::new(0)).is_err());
+    }
+
+    #[test]
+    fn higher_timeframe_matches_resampled_batch() {
+        let closes: Vec<f64> = (0..120)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0)
+            .collect();
+        let data = minutes(&closes);
+        let mut mtf = MultiTimeframe::new(60, &[60, 300], || Rsi::new(5)).unwrap();
+        let out = mtf.calculate(&data).unwrap();
+        assert_eq!(out.len(), data.len());
+
+        let base = Rsi::new(5).unwrap().calculate(&data).unwrap();
+        assert_eq!(out.last().unwrap()[0], base.last().copied());
+
+        let bars = resample(&data, 60, 300).unwrap();
+        let slow = Rsi::new(5).unwrap().calculate(&bars).unwrap();
+        assert_eq!(out.last().unwrap()[1], slow.last().copied());
+        // The 5m value only changes at 5m closes.
+        for i in 1..out.len() {
+            if out[i][1] != out[i - 1][1] {
+                assert_eq!(data[i].timestamp % 300, 240);
+            }
+        }
+    }
+
+    #[test]
+    fn reset_and_lookup() {
+        let data = minutes(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let mut mtf = MultiTimeframe::new(60, &[60, 120], || Sma::new(2)).unwrap();
+        mtf.calculate(&data).unwrap();
+        assert_eq!(mtf.timeframes(), vec![60, 120]);
+        assert_eq!(mtf.value(60), Some(&5.5));
+        assert_eq!(mtf.value(120), Some(&5.0));
+        assert!(mtf.value(999).is_none());
+        assert!(mtf.indicator(120).is_some());
+        mtf.reset_state();
+        assert!(mtf.value(60).is_none());
+    }
+}
@@ -5,6 +5,7 @@
 //! PriceDataAccessor trait for working with different price data formats.
 
 use super::error::IndicatorError;
+use super::series::{IndicatorSeries, Timestamped};
 
 /// Base trait for all technical indicators
 ///
@@ -70,6 +71,37 @@ use super::error::IndicatorError;
 ///              band.middle, band.upper, band.lower);
 /// }
 /// ```
+///
+/// # State snapshots
+///
+/// Every built-in indicator, and every wrapper around `Clone` indicators,
+/// implements `Clone`. A clone is a full copy of the streaming state, so
+/// it doubles as a snapshot: branch the state, feed a hypothetical input,
+/// inspect the result, then keep or drop the branch. Restoring is a plain
+/// assignment. The cost is one copy of the indicator's window, if it has
+/// one. Type-erased `Box<dyn AnyIndicator>` values, and the
+/// [`IndicatorSet`](crate::indicators::IndicatorSet) holding them, are not
+/// `Clone`.
+///
+/// ```
+/// use rsta::indicators::trend::Ema;
+/// use rsta::indicators::Indicator;
+///
+/// let mut ema = Ema::new(3).unwrap();
+/// for price in [10.0, 11.0, 12.0] {
+///     ema.next(price).unwrap();
+/// }
+///
+/// // What would a spike to 20 do to the EMA?
+/// let snapshot = ema.clone();
+/// let preview = ema.next(20.0).unwrap().unwrap();
+/// assert!(preview > 15.0);
+///
+/// // Roll back and carry on with the real price.
+/// ema = snapshot;
+/// let actual = ema.next(12.5).unwrap().unwrap();
+/// assert!(actual < 13.0);
+/// ```
 pub trait Indicator<T, O> {
     /// Calculate the indicator values based on input data
     ///
@@ -102,6 +134,262 @@ pub trait Indicator<T, O> {
     /// * `Result<Option<O>, IndicatorError>` - The latest indicator value (if available) or an error
     fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError>;
 
+    /// Calculate indicator values aligned one-to-one with the input data
+    ///
+    /// Like [`calculate`](Indicator::calculate), but the returned vector has
+    /// exactly `data.len()` entries: bars still inside the indicator's
+    /// warm-up window are `None`, so the output can be zipped directly with
+    /// the input candles without knowing each indicator's lookback.
+    ///
+    /// The default implementation right-aligns the output of `calculate()`
+    /// with the input, which holds for indicators that emit a value for
+    /// every bar past their warm-up. Indicators that can skip bars
+    /// ([`NanGuard`](crate::indicators::NanGuard), the `Skip` zero-range
+    /// policy of `Adl`, `Cmf` and the indicators built on them, the `Skip`
+    /// zero-volume policy of `Vroc` and `VolumeOscillator`) override it to
+    /// put `None` at each skipped bar.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - A slice of input data points
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Option<O>>, IndicatorError>` - One entry per input point or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::Indicator;
+    ///
+    /// let mut sma = Sma::new(3).unwrap();
+    /// let aligned = sma.calculate_aligned(&[2.0, 4.0, 6.0, 8.0]).unwrap();
+    /// assert_eq!(aligned, vec![None, None, Some(4.0), Some(6.0)]);
+    /// ```
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        let values = self.calculate(data)?;
+        let pad = data.len().saturating_sub(values.len());
+        let mut out = Vec::with_capacity(pad + values.len());
+        out.extend(std::iter::repeat_with(|| None).take(pad));
+        out.extend(values.into_iter().map(Some));
+        Ok(out)
+    }
+
+    /// Calculate indicator values paired with the timestamps of their
+    /// input points
+    ///
+    /// Like [`calculate`](Indicator::calculate), but the result is an
+    /// [`IndicatorSeries`] that knows which candle each value belongs to,
+    /// ready for plotting or joining back to the candles. Indicators that
+    /// override [`calculate_aligned`](Indicator::calculate_aligned) build it
+    /// with [`IndicatorSeries::from_aligned`], so skipped bars stay empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - A slice of timestamped input points, such as candles
+    ///
+    /// # Returns
+    ///
+    /// * `Result<IndicatorSeries<O>, IndicatorError>` - The timestamped values or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::{Candle, Indicator};
+    ///
+    /// let candles: Vec<Candle> = (0..4)
+    ///     .map(|i| {
+    ///         let c = 2.0 * (i + 1) as f64;
+    ///         Candle { timestamp: 100 + i, open: c, high: c, low: c, close: c, volume: 1.0 }
+    ///     })
+    ///     .collect();
+    ///
+    /// let mut sma = Sma::new(3).unwrap();
+    /// let series = sma.calculate_series(&candles).unwrap();
+    /// assert_eq!(series.iter().collect::<Vec<_>>(), vec![(102, &4.0), (103, &6.0)]);
+    /// ```
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        let values = self.calculate(data)?;
+        Ok(IndicatorSeries::new(data, values))
+    }
+
+    /// Continue the calculation over the next slice of a longer series
+    ///
+    /// Unlike [`calculate`](Indicator::calculate), this does not reset the
+    /// indicator: state carries over from the previous chunk (or earlier
+    /// `next()` calls), so a history too large for memory can be streamed
+    /// from disk slice by slice. Concatenating the outputs of consecutive
+    /// chunks gives the same values as one `calculate()` over the whole
+    /// series, and chunks may be shorter than `min_periods()`.
+    ///
+    /// The default implementation feeds every point to `next()` and keeps
+    /// the values it returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The next slice of input data points
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<O>, IndicatorError>` - The values completed by this chunk or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::Indicator;
+    ///
+    /// let mut sma = Sma::new(3).unwrap();
+    /// let mut out = sma.calculate_chunk(&[2.0, 4.0]).unwrap();
+    /// assert!(out.is_empty());
+    /// out.extend(sma.calculate_chunk(&[6.0, 8.0]).unwrap());
+    /// assert_eq!(out, vec![4.0, 6.0]);
+    /// ```
+    fn calculate_chunk(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError>
+    where
+        T: Clone,
+    {
+        let mut out = Vec::with_capacity(data.len());
+        for value in data {
+            if let Some(output) = self.next(value.clone())? {
+                out.push(output);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Extend a previous batch result with newly arrived data
+    ///
+    /// After `let mut out = indicator.calculate(history)?`, calling
+    /// `indicator.calculate_append(&mut out, new_bars)?` leaves `out` equal
+    /// to `calculate()` over the whole history plus `new_bars`, without
+    /// recomputing the history: `calculate()` leaves the indicator in the
+    /// same state as streaming the data through `next()`, and the new points
+    /// continue from there. Returns the number of values appended.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The result of the previous `calculate()` (and appends)
+    /// * `new_data` - Data points that follow the previously processed ones
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, IndicatorError>` - How many values were appended to `output` or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::Indicator;
+    ///
+    /// let mut sma = Sma::new(3).unwrap();
+    /// let mut out = sma.calculate(&[2.0, 4.0, 6.0]).unwrap();
+    /// assert_eq!(sma.calculate_append(&mut out, &[8.0, 10.0]).unwrap(), 2);
+    /// assert_eq!(out, vec![4.0, 6.0, 8.0]);
+    /// ```
+    fn calculate_append(
+        &mut self,
+        output: &mut Vec<O>,
+        new_data: &[T],
+    ) -> Result<usize, IndicatorError>
+    where
+        T: Clone,
+    {
+        let values = self.calculate_chunk(new_data)?;
+        let appended = values.len();
+        output.extend(values);
+        Ok(appended)
+    }
+
+    /// Calculate the indicator over several independent series
+    ///
+    /// Runs [`calculate`](Indicator::calculate) on each series in turn with
+    /// the same configured indicator, so a universe of symbols needs one
+    /// instance rather than one per symbol. The indicator is left in the
+    /// state of the last series.
+    ///
+    /// # Arguments
+    ///
+    /// * `series` - The input series, e.g. one close series per symbol
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Vec<O>>, IndicatorError>` - One output vector per series or the first error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::Indicator;
+    ///
+    /// let mut sma = Sma::new(2).unwrap();
+    /// let out = sma.calculate_many(&[&[1.0, 3.0, 5.0], &[10.0, 20.0]]).unwrap();
+    /// assert_eq!(out, vec![vec![2.0, 4.0], vec![15.0]]);
+    /// ```
+    fn calculate_many(&mut self, series: &[&[T]]) -> Result<Vec<Vec<O>>, IndicatorError> {
+        series.iter().map(|data| self.calculate(data)).collect()
+    }
+
+    /// Calculate the indicator over several series into reused buffers
+    ///
+    /// Same values as [`calculate_many`](Indicator::calculate_many), but
+    /// written into `output`, whose inner vectors are cleared and refilled
+    /// rather than reallocated. Calling it every bar for a whole universe
+    /// allocates only while a series outgrows its buffer. Each series is
+    /// streamed through `next()` after a `reset()`, and one shorter than
+    /// [`min_periods`](Indicator::min_periods) fails with
+    /// [`IndicatorError::InsufficientData`] as `calculate()` would.
+    ///
+    /// # Arguments
+    ///
+    /// * `series` - The input series
+    /// * `output` - Buffers from a previous call (or empty); resized to one per series
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), IndicatorError>` - Ok once every series is done, or the first error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::Indicator;
+    ///
+    /// let mut sma = Sma::new(2).unwrap();
+    /// let mut out = Vec::new();
+    /// sma.calculate_many_into(&[&[1.0, 3.0, 5.0], &[10.0, 20.0]], &mut out).unwrap();
+    /// assert_eq!(out, vec![vec![2.0, 4.0], vec![15.0]]);
+    /// ```
+    fn calculate_many_into(
+        &mut self,
+        series: &[&[T]],
+        output: &mut Vec<Vec<O>>,
+    ) -> Result<(), IndicatorError>
+    where
+        T: Clone,
+    {
+        output.resize_with(series.len(), Vec::new);
+        let required = self.min_periods();
+        for (data, out) in series.iter().zip(output.iter_mut()) {
+            out.clear();
+            if data.len() < required {
+                return Err(IndicatorError::insufficient_data(required, data.len()));
+            }
+            self.reset();
+            for value in data.iter() {
+                if let Some(value) = self.next(value.clone())? {
+                    out.push(value);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Reset the indicator state
     ///
     /// This method clears the internal state of the indicator, returning it to its
@@ -109,6 +397,30 @@ pub trait Indicator<T, O> {
     /// instance with different datasets.
     fn reset(&mut self);
 
+    /// Latest value produced by `next()` or `calculate()`
+    ///
+    /// Lets strategy code read an indicator without keeping the return
+    /// value of every `next()` call. Warm-up calls that return `None` leave
+    /// it unchanged, so it is `None` until the first value and after
+    /// `reset()`. Every indicator in this crate overrides the default,
+    /// which always returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rsta::indicators::trend::Sma;
+    /// use rsta::indicators::Indicator;
+    ///
+    /// let mut sma = Sma::new(2).unwrap();
+    /// sma.next(1.0).unwrap();
+    /// assert_eq!(Indicator::<f64, f64>::current(&sma), None);
+    /// sma.next(3.0).unwrap();
+    /// assert_eq!(Indicator::<f64, f64>::current(&sma), Some(&2.0));
+    /// ```
+    fn current(&self) -> Option<&O> {
+        None
+    }
+
     /// Human-readable indicator name (e.g. `"Sma"`, `"Rsi"`, `"Macd"`).
     ///
     /// Defaults to a best-effort `std::any::type_name` fragment so existing
@@ -127,6 +439,27 @@ pub trait Indicator<T, O> {
     fn period(&self) -> Option<usize> {
         None
     }
+
+    /// Minimum number of input points required to produce a value.
+    ///
+    /// `calculate()` returns [`IndicatorError::InsufficientData`] for inputs
+    /// shorter than this, so generic code can pre-validate data length
+    /// without indicator-specific knowledge. Defaults to `1`.
+    fn min_periods(&self) -> usize {
+        1
+    }
+
+    /// Index of the input point that the first `calculate()` output lines
+    /// up with.
+    ///
+    /// Equivalent to `data.len() - calculate(data)?.len()` as long as no bar
+    /// is skipped; with a skip policy, use
+    /// [`calculate_aligned`](Indicator::calculate_aligned) to place the
+    /// values. Defaults to `min_periods() - 1`; indicators that emit from
+    /// the very first bar (e.g. the recursive EMA) override it to `0`.
+    fn output_offset(&self) -> usize {
+        self.min_periods().saturating_sub(1)
+    }
 }
 
 /// Price data accessor trait
@@ -254,7 +587,7 @@ mod tests {
     impl Indicator<f64, f64> for MockAverageIndicator {
         fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
             if data.is_empty() {
-                return Err(IndicatorError::InsufficientData("Empty data".to_string()));
+                return Err(IndicatorError::insufficient_data(1, 0));
             }
 
             let average = data.iter().sum::<f64>() / data.len() as f64;
@@ -318,8 +651,8 @@ mod tests {
         let error_result = indicator.calculate(&empty);
         assert!(error_result.is_err());
 
-        if let Err(IndicatorError::InsufficientData(msg)) = error_result {
-            assert!(msg.contains("Empty"));
+        if let Err(IndicatorError::InsufficientData { provided, .. }) = error_result {
+            assert_eq!(provided, 0);
         } else {
             panic!("Expected InsufficientData error");
         }
@@ -343,6 +676,42 @@ mod tests {
         assert_eq!(indicator.next(100.0).unwrap(), Some(100.0));
     }
 
+    #[test]
+    fn test_indicator_calculate_aligned() {
+        let mut indicator = MockAverageIndicator::new();
+
+        // The mock emits a single value for the whole batch, which lines up
+        // with the last input.
+        let aligned = indicator.calculate_aligned(&[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(aligned, vec![None, None, Some(2.0)]);
+
+        // Errors from calculate() propagate unchanged.
+        assert!(indicator.calculate_aligned(&[]).is_err());
+    }
+
+    #[test]
+    fn test_indicator_calculate_many() {
+        let mut indicator = MockAverageIndicator::new();
+        let series: [&[f64]; 2] = [&[1.0, 2.0, 3.0], &[10.0, 20.0]];
+        assert_eq!(
+            indicator.calculate_many(&series).unwrap(),
+            vec![vec![2.0], vec![15.0]]
+        );
+        assert!(indicator.calculate_many(&[&[1.0], &[]]).is_err());
+
+        // The buffered variant streams each series from a reset state
+        // (the mock emits a running average) and keeps the allocations.
+        let mut out: Vec<Vec<f64>> = (0..3).map(|_| Vec::with_capacity(8)).collect();
+        let buffer = out[0].as_ptr();
+        indicator.calculate_many_into(&series, &mut out).unwrap();
+        assert_eq!(out, vec![vec![1.0, 1.5, 2.0], vec![10.0, 15.0]]);
+        assert_eq!(out[0].as_ptr(), buffer);
+        assert!(matches!(
+            indicator.calculate_many_into(&[&[]], &mut out),
+            Err(IndicatorError::InsufficientData { required: 1, .. })
+        ));
+    }
+
     #[test]
     fn test_trait_usage_with_generic_function() {
         // Define a generic function that works with any PriceDataAccessor
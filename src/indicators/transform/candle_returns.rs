@@ -0,0 +1,249 @@
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, IndicatorError};
+use crate::returns;
+
+/// Return measure used by [`CandleReturns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReturnKind {
+    /// Percent change as a fraction, `x / base - 1` (`0.01` = +1%), the
+    /// same convention as pandas' `pct_change`.
+    #[default]
+    PercentChange,
+    /// Log return, `ln(x / base)`.
+    Log,
+}
+
+impl ReturnKind {
+    /// Return from `from` to `to`, `None` when it is undefined (a
+    /// non-positive base, or a non-positive value for log returns).
+    pub fn between(self, from: f64, to: f64) -> Option<f64> {
+        match self {
+            ReturnKind::PercentChange => returns::simple_return(from, to),
+            ReturnKind::Log => returns::log_return(from, to),
+        }
+    }
+}
+
+/// What each price field of a candle is measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReturnBasis {
+    /// Every field against the previous close. Open, high, low and close
+    /// share one base, so the return candle keeps the shape of the price
+    /// candle (`low <= open, close <= high`) and range indicators such as
+    /// ATR stay meaningful.
+    #[default]
+    PreviousClose,
+    /// Each field against the same field of the previous candle: open to
+    /// open, high to high, and so on. The fields are independent series
+    /// and `high` may end up below `low`.
+    SameField,
+}
+
+/// Candles in return space
+///
+/// Turns each candle into a candle of returns against the previous one,
+/// so candle indicators (momentum, volatility, ...) run on returns instead
+/// of raw prices. The timestamp and volume are kept as they are. Chain it
+/// in front of an indicator with a [`Pipeline`](crate::indicators::Pipeline).
+///
+/// # Formula
+///
+/// With the [`ReturnBasis::PreviousClose`] default, for each price field
+/// `x` of candle `t`:
+///
+/// ```text
+/// percent change: x[t] / close[t-1] - 1
+/// log return:     ln(x[t] / close[t-1])
+/// ```
+///
+/// Bases must be strictly positive, and for log returns the fields too;
+/// anything else is rejected with [`IndicatorError::InvalidInput`].
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::transform::{CandleReturns, ReturnKind};
+/// use rsta::indicators::volatility::Atr;
+/// use rsta::indicators::{Candle, Indicator, Pipeline};
+///
+/// let candle = |close: f64| Candle {
+///     timestamp: 0,
+///     open: close,
+///     high: close * 1.02,
+///     low: close * 0.98,
+///     close,
+///     volume: 1.0,
+/// };
+///
+/// let mut returns = CandleReturns::new(ReturnKind::PercentChange);
+/// let r = returns.calculate(&[candle(100.0), candle(110.0)]).unwrap();
+/// assert!((r[0].close - 0.10).abs() < 1e-12);
+/// assert!((r[0].high - 0.122).abs() < 1e-12);
+///
+/// // ATR of percent-change candles: volatility comparable across prices.
+/// let mut atr: Pipeline<_, _, Candle> = Pipeline::new(returns, Atr::new(3).unwrap());
+/// let prices: Vec<Candle> = (0..10).map(|i| candle(100.0 * 1.01f64.powi(i))).collect();
+/// assert!(atr.calculate(&prices).unwrap().iter().all(|v| (v - 0.04).abs() < 0.01));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CandleReturns {
+    kind: ReturnKind,
+    basis: ReturnBasis,
+    previous: Option<Candle>,
+    seen: usize,
+    last: Option<Candle>,
+}
+
+impl CandleReturns {
+    /// Create a new candle-returns transform measured against the
+    /// previous close
+    ///
+    /// # Arguments
+    /// * `kind` - Percent change or log return
+    ///
+    /// # Returns
+    /// * `Self` - A new transform
+    pub fn new(kind: ReturnKind) -> Self {
+        Self {
+            kind,
+            basis: ReturnBasis::default(),
+            previous: None,
+            seen: 0,
+            last: None,
+        }
+    }
+
+    /// Measure against `basis` instead of the previous close.
+    pub fn with_basis(mut self, basis: ReturnBasis) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// The return measure.
+    pub fn kind(&self) -> ReturnKind {
+        self.kind
+    }
+
+    /// The base of the returns.
+    pub fn basis(&self) -> ReturnBasis {
+        self.basis
+    }
+
+    /// Reset the transform state
+    pub fn reset_state(&mut self) {
+        self.previous = None;
+        self.seen = 0;
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: Candle, index: usize) -> Result<Option<Candle>, IndicatorError> {
+        let Some(previous) = self.previous else {
+            self.previous = Some(candle);
+            return Ok(None);
+        };
+        let kind = self.kind;
+        let field = |from: f64, to: f64| {
+            kind.between(from, to)
+                .filter(|r| r.is_finite())
+                .ok_or_else(|| IndicatorError::InvalidInput {
+                    index,
+                    reason: format!("{kind:?} return from {from} to {to}"),
+                })
+        };
+        let base = |same_field: f64| match self.basis {
+            ReturnBasis::PreviousClose => previous.close,
+            ReturnBasis::SameField => same_field,
+        };
+        let out = Candle {
+            timestamp: candle.timestamp,
+            open: field(base(previous.open), candle.open)?,
+            high: field(base(previous.high), candle.high)?,
+            low: field(base(previous.low), candle.low)?,
+            close: field(previous.close, candle.close)?,
+            volume: candle.volume,
+        };
+        self.previous = Some(candle);
+        self.last = Some(out);
+        Ok(self.last)
+    }
+}
+
+impl Indicator<Candle, Candle> for CandleReturns {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Candle>, IndicatorError> {
+        validate_data_length(data, 2)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - 1);
+        for (i, &candle) in data.iter().enumerate() {
+            if let Some(out) = self.step(candle, i)? {
+                result.push(out);
+            }
+        }
+        self.seen = data.len();
+        Ok(result)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<Candle>, IndicatorError> {
+        let out = self.step(candle, self.seen)?;
+        self.seen += 1;
+        Ok(out)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Candle> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: 7,
+            open,
+            high,
+            low,
+            close,
+            volume: 3.0,
+        }
+    }
+
+    #[test]
+    fn test_bases_and_kinds() {
+        let candles = [
+            candle(100.0, 105.0, 95.0, 100.0),
+            candle(101.0, 108.0, 99.0, 104.0),
+        ];
+        let mut pct = CandleReturns::new(ReturnKind::PercentChange);
+        let r = pct.calculate(&candles).unwrap()[0];
+        assert_eq!((r.timestamp, r.volume), (7, 3.0));
+        assert!((r.open - 0.01).abs() < 1e-12);
+        assert!((r.high - 0.08).abs() < 1e-12);
+        assert!((r.low + 0.01).abs() < 1e-12);
+        assert!((r.close - 0.04).abs() < 1e-12);
+
+        let mut same = CandleReturns::new(ReturnKind::Log).with_basis(ReturnBasis::SameField);
+        assert_eq!(same.next(candles[0]).unwrap(), None);
+        let r = same.next(candles[1]).unwrap().unwrap();
+        assert!((r.high - (108.0f64 / 105.0).ln()).abs() < 1e-12);
+        assert!((r.low - (99.0f64 / 95.0).ln()).abs() < 1e-12);
+        assert_eq!(same.current(), Some(&r));
+
+        // A zero base is rejected with the index of the offending candle.
+        let mut log = CandleReturns::new(ReturnKind::Log);
+        log.next(candle(1.0, 1.0, 0.0, 0.0)).unwrap();
+        assert!(matches!(
+            log.next(candles[0]),
+            Err(IndicatorError::InvalidInput { index: 1, .. })
+        ));
+    }
+}
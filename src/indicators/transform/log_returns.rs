@@ -0,0 +1,188 @@
+use std::collections::VecDeque;
+
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Candle, IndicatorError};
+use crate::returns;
+
+/// Logarithmic returns
+///
+/// The natural log of the ratio between each value and the value `period`
+/// steps earlier. Log returns add up over time and are roughly symmetric,
+/// which makes them the usual input for return-based models.
+///
+/// # Formula
+///
+/// ```text
+/// r = ln(x[t] / x[t - period])
+/// ```
+///
+/// Values must be strictly positive; anything else is rejected with
+/// [`IndicatorError::InvalidInput`].
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::transform::LogReturns;
+/// use rsta::indicators::Indicator;
+///
+/// let mut returns = LogReturns::new(1).unwrap();
+/// let r = returns.calculate(&[100.0, 110.0, 99.0]).unwrap();
+/// assert!((r[0] - 1.1f64.ln()).abs() < 1e-12);
+/// assert!((r[1] - 0.9f64.ln()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogReturns {
+    period: usize,
+    window: VecDeque<f64>,
+    seen: usize,
+    last: Option<f64>,
+}
+
+impl LogReturns {
+    /// Create a new log-returns transform
+    ///
+    /// # Arguments
+    /// * `period` - Number of steps each return spans (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new transform or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period + 1),
+            seen: 0,
+            last: None,
+        })
+    }
+
+    /// Reset the transform state
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.seen = 0;
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64, index: usize) -> Result<Option<f64>, IndicatorError> {
+        if !(value > 0.0 && value.is_finite()) {
+            return Err(IndicatorError::InvalidInput {
+                index,
+                reason: format!("log return of non-positive value {value}"),
+            });
+        }
+        self.window.push_back(value);
+        if self.window.len() <= self.period {
+            return Ok(None);
+        }
+        let base = self
+            .window
+            .pop_front()
+            .expect("window holds period + 1 values");
+        self.last = returns::log_return(base, value);
+        Ok(self.last)
+    }
+}
+
+impl Indicator<f64, f64> for LogReturns {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period + 1)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - self.period);
+        for (i, &value) in data.iter().enumerate() {
+            if let Some(r) = self.step(value, i)? {
+                result.push(r);
+            }
+        }
+        self.seen = data.len();
+        Ok(result)
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        let out = self.step(value, self.seen)?;
+        self.seen += 1;
+        Ok(out)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+}
+
+impl Indicator<Candle, f64> for LogReturns {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_returns_sum_to_total_return() {
+        let prices = [100.0, 104.0, 98.0, 101.0, 120.0];
+        let r = LogReturns::new(1).unwrap().calculate(&prices).unwrap();
+        assert_eq!(r.len(), 4);
+        let total: f64 = r.iter().sum();
+        assert!((total - 1.2f64.ln()).abs() < 1e-12);
+
+        let r2 = LogReturns::new(2).unwrap().calculate(&prices).unwrap();
+        assert_eq!(r2.len(), 3);
+        assert!((r2[0] - 0.98f64.ln()).abs() < 1e-12);
+        assert!(LogReturns::new(0).is_err());
+    }
+
+    #[test]
+    fn test_non_positive_values_are_rejected() {
+        let mut returns = LogReturns::new(1).unwrap();
+        assert_eq!(
+            returns.calculate(&[1.0, 2.0, 0.0, 3.0]),
+            Err(IndicatorError::InvalidInput {
+                index: 2,
+                reason: "log return of non-positive value 0".to_string(),
+            })
+        );
+
+        returns.reset_state();
+        assert_eq!(returns.next(5.0), Ok(None));
+        assert!(matches!(
+            returns.next(-1.0),
+            Err(IndicatorError::InvalidInput { index: 1, .. })
+        ));
+        assert!((returns.next(10.0).unwrap().unwrap() - 2f64.ln()).abs() < 1e-12);
+    }
+}
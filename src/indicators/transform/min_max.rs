@@ -0,0 +1,187 @@
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, RollingMax, RollingMin};
+use crate::indicators::{Candle, IndicatorError};
+
+/// Rolling min-max scaler
+///
+/// Maps each value onto `[lower, upper]` (`[0, 1]` by default) according
+/// to where it sits between the lowest and highest of the last `period`
+/// values, the current one included.
+///
+/// # Formula
+///
+/// ```text
+/// scaled = lower + (x - min) / (max - min) * (upper - lower)
+/// ```
+///
+/// A flat window (`max == min`) maps to the middle of the range.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::transform::MinMaxScaler;
+/// use rsta::indicators::Indicator;
+///
+/// let mut scaler = MinMaxScaler::new(3).unwrap().with_range(-1.0, 1.0);
+/// let scaled = scaler.calculate(&[10.0, 20.0, 15.0, 30.0, 30.0]).unwrap();
+/// assert_eq!(scaled, vec![0.0, 1.0, 1.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MinMaxScaler {
+    period: usize,
+    lower: f64,
+    upper: f64,
+    min: RollingMin,
+    max: RollingMax,
+    last: Option<f64>,
+}
+
+impl MinMaxScaler {
+    /// Create a new min-max scaler
+    ///
+    /// # Arguments
+    /// * `period` - Number of values the min and max are taken over (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new scaler or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            period,
+            lower: 0.0,
+            upper: 1.0,
+            min: RollingMin::new(period)?,
+            max: RollingMax::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Scale onto `[lower, upper]` instead of `[0, 1]`. Passing
+    /// `upper < lower` inverts the scale.
+    pub fn with_range(mut self, lower: f64, upper: f64) -> Self {
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+
+    /// Reset the scaler state
+    pub fn reset_state(&mut self) {
+        self.min.reset();
+        self.max.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let (min, max) = (self.min.push(value), self.max.push(value));
+        if !self.min.is_full() {
+            return None;
+        }
+        let (min, max) = (min?, max?);
+        let unit = if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        };
+        self.last = Some(self.lower + unit * (self.upper - self.lower));
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for MinMaxScaler {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Indicator<Candle, f64> for MinMaxScaler {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_max_scaling() {
+        let mut scaler = MinMaxScaler::new(4).unwrap();
+        let out = scaler
+            .calculate(&[1.0, 5.0, 3.0, 2.0, 9.0, 1.0, 5.0])
+            .unwrap();
+        // Windows: [1,5,3,2] [5,3,2,9] [3,2,9,1] [2,9,1,5]
+        assert_eq!(out, vec![0.25, 1.0, 0.0, 0.5]);
+        assert!(MinMaxScaler::new(0).is_err());
+        assert!(scaler.calculate(&[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn test_flat_window_and_custom_range() {
+        let mut scaler = MinMaxScaler::new(3).unwrap().with_range(-1.0, 1.0);
+        assert_eq!(scaler.calculate(&[4.0, 4.0, 4.0]).unwrap(), vec![0.0]);
+
+        let mut inverted = MinMaxScaler::new(2).unwrap().with_range(1.0, 0.0);
+        assert_eq!(
+            inverted.calculate(&[1.0, 2.0, 1.0]).unwrap(),
+            vec![0.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_streaming_matches_batch() {
+        let data: Vec<f64> = (0..30).map(|i| (i as f64 * 0.9).cos() * 10.0).collect();
+        let mut scaler = MinMaxScaler::new(7).unwrap();
+        let batch = scaler.calculate(&data).unwrap();
+
+        scaler.reset_state();
+        let streamed: Vec<f64> = data
+            .iter()
+            .filter_map(|&v| scaler.next(v).unwrap())
+            .collect();
+        assert_eq!(batch, streamed);
+        assert!(batch.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert_eq!(Indicator::<f64, f64>::current(&scaler), batch.last());
+    }
+}
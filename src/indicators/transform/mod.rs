@@ -0,0 +1,36 @@
+//! Normalization and scaling transforms
+//!
+//! These turn a raw series — prices, or another indicator's output — into
+//! features on a comparable scale, for machine-learning pipelines. They
+//! implement [`Indicator`](crate::indicators::Indicator) like any other
+//! indicator, so they chain after one with a
+//! [`Pipeline`](crate::indicators::Pipeline):
+//!
+//! ```
+//! use rsta::indicators::transform::ZScoreNormalizer;
+//! use rsta::indicators::{Indicator, Pipeline, Rsi};
+//!
+//! let prices: Vec<f64> = (0..60).map(|i| 100.0 + (i as f64 * 0.4).sin() * 4.0).collect();
+//!
+//! // RSI(14), standardized over its last 20 values.
+//! let mut feature: Pipeline<Rsi, ZScoreNormalizer, f64> =
+//!     Pipeline::new(Rsi::new(14).unwrap(), ZScoreNormalizer::new(20).unwrap());
+//! let z = feature.calculate(&prices).unwrap();
+//! assert!(z.iter().all(|v| v.abs() < 5.0));
+//! ```
+//!
+//! [`CandleReturns`] moves whole candles into percent-change or log-return
+//! space, so candle indicators can run on returns instead of prices.
+//!
+//! Every transform only looks at the current and past values, so features
+//! computed in a backtest are free of look-ahead.
+
+pub mod candle_returns;
+pub mod log_returns;
+pub mod min_max;
+pub mod zscore;
+
+pub use self::candle_returns::{CandleReturns, ReturnBasis, ReturnKind};
+pub use self::log_returns::LogReturns;
+pub use self::min_max::MinMaxScaler;
+pub use self::zscore::ZScoreNormalizer;
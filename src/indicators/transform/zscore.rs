@@ -0,0 +1,158 @@
+use crate::indicators::traits::Indicator;
+use crate::indicators::utils::{validate_data_length, RollingStats};
+use crate::indicators::{Candle, IndicatorError};
+
+/// Rolling z-score normalizer
+///
+/// Expresses each value as its number of standard deviations from the mean
+/// of the last `period` values, the current one included. Uses the
+/// population standard deviation, like the `Std` indicator.
+///
+/// # Formula
+///
+/// ```text
+/// z = (x - mean) / std_dev
+/// ```
+///
+/// A flat window (`std_dev == 0`) gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::transform::ZScoreNormalizer;
+/// use rsta::indicators::Indicator;
+///
+/// let mut zscore = ZScoreNormalizer::new(4).unwrap();
+/// let z = zscore.calculate(&[2.0, 4.0, 4.0, 6.0]).unwrap();
+/// // mean 4, std dev √2
+/// assert!((z[0] - std::f64::consts::SQRT_2).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZScoreNormalizer {
+    period: usize,
+    stats: RollingStats,
+    last: Option<f64>,
+}
+
+impl ZScoreNormalizer {
+    /// Create a new z-score normalizer
+    ///
+    /// # Arguments
+    /// * `period` - Number of values the mean and deviation are taken over (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new normalizer or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            period,
+            stats: RollingStats::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Reset the normalizer state
+    pub fn reset_state(&mut self) {
+        self.stats.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        self.stats.push(value);
+        if !self.stats.is_full() {
+            return None;
+        }
+        let mean = self.stats.mean()?;
+        let std_dev = self.stats.std_dev()?;
+        self.last = Some(if std_dev > 0.0 {
+            (value - mean) / std_dev
+        } else {
+            0.0
+        });
+        self.last
+    }
+}
+
+impl Indicator<f64, f64> for ZScoreNormalizer {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Indicator<Candle, f64> for ZScoreNormalizer {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zscore_values() {
+        let mut zscore = ZScoreNormalizer::new(3).unwrap();
+        let out = zscore.calculate(&[1.0, 2.0, 3.0, 3.0, 3.0]).unwrap();
+        // [1,2,3]: mean 2, std √(2/3) → z(3) = √1.5
+        assert!((out[0] - 1.5f64.sqrt()).abs() < 1e-12);
+        // [3,3,3] is flat.
+        assert_eq!(out[2], 0.0);
+        assert!(ZScoreNormalizer::new(0).is_err());
+    }
+
+    #[test]
+    fn test_zscore_is_scale_invariant() {
+        let data: Vec<f64> = (0..40).map(|i| (i as f64 * 0.3).sin()).collect();
+        let scaled: Vec<f64> = data.iter().map(|v| 250.0 + v * 40.0).collect();
+        let a = ZScoreNormalizer::new(10).unwrap().calculate(&data).unwrap();
+        let b = ZScoreNormalizer::new(10)
+            .unwrap()
+            .calculate(&scaled)
+            .unwrap();
+        for (x, y) in a.iter().zip(&b) {
+            assert!((x - y).abs() < 1e-9);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
@@ -43,7 +44,7 @@ pub struct AdxResult {
 /// let values = adx.calculate(&candles).unwrap();
 /// assert!(!values.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Adx {
     period: usize,
     prev_high: Option<f64>,
@@ -55,6 +56,7 @@ pub struct Adx {
     dx_buffer: VecDeque<f64>,
     smooth_adx: Option<f64>,
     seen: usize,
+    last: Option<AdxResult>,
 }
 
 impl Adx {
@@ -72,6 +74,7 @@ impl Adx {
             dx_buffer: VecDeque::with_capacity(period),
             smooth_adx: None,
             seen: 0,
+            last: None,
         })
     }
 
@@ -86,6 +89,7 @@ impl Adx {
         self.dx_buffer.clear();
         self.smooth_adx = None;
         self.seen = 0;
+        self.last = None;
     }
 
     fn step(&mut self, value: Candle) -> Option<AdxResult> {
@@ -150,11 +154,12 @@ impl Adx {
         let m = self.smooth_minus_dm.unwrap();
         let t = self.smooth_tr.unwrap();
         if t == 0.0 {
-            return Some(AdxResult {
+            self.last = Some(AdxResult {
                 plus_di: 0.0,
                 minus_di: 0.0,
                 adx: 0.0,
             });
+            return self.last;
         }
 
         let plus_di = 100.0 * p / t;
@@ -174,20 +179,22 @@ impl Adx {
                 }
                 let seed = self.dx_buffer.iter().sum::<f64>() / n;
                 self.smooth_adx = Some(seed);
-                Some(AdxResult {
+                self.last = Some(AdxResult {
                     plus_di,
                     minus_di,
                     adx: seed,
-                })
+                });
+                self.last
             }
             Some(prev_adx) => {
                 let new_adx = (prev_adx * (n - 1.0) + dx) / n;
                 self.smooth_adx = Some(new_adx);
-                Some(AdxResult {
+                self.last = Some(AdxResult {
                     plus_di,
                     minus_di,
                     adx: new_adx,
-                })
+                });
+                self.last
             }
         }
     }
@@ -214,6 +221,10 @@ impl Indicator<Candle, AdxResult> for Adx {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&AdxResult> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Adx"
     }
@@ -221,6 +232,23 @@ impl Indicator<Candle, AdxResult> for Adx {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        2 * self.period
+    }
+}
+
+impl Describe for Adx {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "adx",
+            title: "Average Directional Index",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["adx", "plus_di", "minus_di"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
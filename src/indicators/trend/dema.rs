@@ -1,4 +1,5 @@
-use crate::indicators::trend::Ema;
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::trend::{Ema, EmaInit};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
@@ -20,12 +21,13 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// let out = <Dema as Indicator<f64, f64>>::calculate(&mut dema, &prices).unwrap();
 /// assert!(!out.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Dema {
     period: usize,
     ema1: Ema,
     ema2: Ema,
     seen: usize,
+    last: Option<f64>,
 }
 
 impl Dema {
@@ -37,26 +39,40 @@ impl Dema {
             ema1: Ema::new(period)?,
             ema2: Ema::new(period)?,
             seen: 0,
+            last: None,
         })
     }
 
+    /// Choose how the inner EMAs are seeded (see [`EmaInit`]).
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.ema1 = self.ema1.with_init(init);
+        self.ema2 = self.ema2.with_init(init);
+        self
+    }
+
     /// Reset internal state without dropping the configured period.
     pub fn reset_state(&mut self) {
         self.ema1.reset_state();
         self.ema2.reset_state();
         self.seen = 0;
+        self.last = None;
     }
 
     fn step(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
         self.seen += 1;
-        let e1 = <Ema as Indicator<f64, f64>>::next(&mut self.ema1, value)?
-            .expect("inner Ema always emits");
-        let e2 = <Ema as Indicator<f64, f64>>::next(&mut self.ema2, e1)?
-            .expect("inner Ema always emits");
+        // SMA-seeded EMAs stay silent during their own warm-up; chained,
+        // they first emit exactly at the DEMA warm-up boundary.
+        let Some(e1) = <Ema as Indicator<f64, f64>>::next(&mut self.ema1, value)? else {
+            return Ok(None);
+        };
+        let Some(e2) = <Ema as Indicator<f64, f64>>::next(&mut self.ema2, e1)? else {
+            return Ok(None);
+        };
         if self.seen < 2 * self.period - 1 {
             return Ok(None);
         }
-        Ok(Some(2.0 * e1 - e2))
+        self.last = Some(2.0 * e1 - e2);
+        Ok(self.last)
     }
 }
 
@@ -81,6 +97,10 @@ impl Indicator<f64, f64> for Dema {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Dema"
     }
@@ -88,6 +108,10 @@ impl Indicator<f64, f64> for Dema {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        2 * self.period - 1
+    }
 }
 
 impl Indicator<Candle, f64> for Dema {
@@ -111,6 +135,10 @@ impl Indicator<Candle, f64> for Dema {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Dema"
     }
@@ -118,6 +146,23 @@ impl Indicator<Candle, f64> for Dema {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        2 * self.period - 1
+    }
+}
+
+impl Describe for Dema {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "dema",
+            title: "Double Exponential Moving Average",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -140,4 +185,19 @@ mod tests {
             .unwrap()
             .is_some());
     }
+
+    #[test]
+    fn test_dema_sma_seeded_emits_at_warmup() {
+        let data: Vec<f64> = (0..20).map(|i| 10.0 + (i as f64).sqrt()).collect();
+        let mut dema = Dema::new(4).unwrap().with_init(EmaInit::TALIB);
+        let out = <Dema as Indicator<f64, f64>>::calculate(&mut dema, &data).unwrap();
+        assert_eq!(out.len(), data.len() - 6);
+
+        let mut e1 = Ema::new(4).unwrap().with_init(EmaInit::TALIB);
+        let mut e2 = Ema::new(4).unwrap().with_init(EmaInit::TALIB);
+        let first = <Ema as Indicator<f64, f64>>::calculate(&mut e1, &data).unwrap();
+        let second = <Ema as Indicator<f64, f64>>::calculate(&mut e2, &first).unwrap();
+        let expected = 2.0 * first[first.len() - 1] - second[second.len() - 1];
+        assert!((out[out.len() - 1] - expected).abs() < 1e-12);
+    }
 }
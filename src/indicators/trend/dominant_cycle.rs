@@ -0,0 +1,278 @@
+use std::collections::VecDeque;
+
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Number of bars the Hilbert transform looks back.
+const HILBERT_TAPS: usize = 7;
+
+/// Ehlers' Hilbert transform of the last seven values, newest first,
+/// scaled by the previous period estimate.
+fn hilbert(values: &VecDeque<f64>, period: f64) -> f64 {
+    (0.0962 * values[0] + 0.5769 * values[2] - 0.5769 * values[4] - 0.0962 * values[6])
+        * (0.075 * period + 0.54)
+}
+
+/// Push `value` to the front of a fixed-length history.
+fn push(history: &mut VecDeque<f64>, value: f64) {
+    history.push_front(value);
+    history.truncate(HILBERT_TAPS);
+}
+
+/// Dominant cycle period estimator.
+///
+/// Ehlers' homodyne discriminator: the price is detrended and split into
+/// in-phase and quadrature components by a Hilbert transform, and the
+/// phase advance per bar between consecutive samples gives the period of
+/// the dominant cycle. Each estimate is limited to 0.67–1.5 times the
+/// previous one, clamped to `[min_period, max_period]` (6 and 50 bars by
+/// default) and smoothed.
+///
+/// Output is the smoothed period in bars. It starts at `min_period` and
+/// needs a few dozen bars to settle. Adaptive indicators such as
+/// [`AdaptiveRsi`](crate::indicators::momentum::AdaptiveRsi) size their
+/// lookback from it.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::DominantCycle;
+/// use rsta::indicators::Indicator;
+///
+/// let mut cycle = DominantCycle::new();
+/// // A clean 20-bar sine wave
+/// let prices: Vec<f64> = (0..200)
+///     .map(|i| 100.0 + (2.0 * std::f64::consts::PI * i as f64 / 20.0).sin())
+///     .collect();
+/// let periods = cycle.calculate(&prices).unwrap();
+/// assert!((periods.last().unwrap() - 20.0).abs() < 2.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DominantCycle {
+    min_period: f64,
+    max_period: f64,
+    prices: VecDeque<f64>,
+    smooth: VecDeque<f64>,
+    detrender: VecDeque<f64>,
+    i1: VecDeque<f64>,
+    q1: VecDeque<f64>,
+    i2: f64,
+    q2: f64,
+    re: f64,
+    im: f64,
+    period: f64,
+    smooth_period: f64,
+    last: Option<f64>,
+}
+
+impl Default for DominantCycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DominantCycle {
+    /// Estimator clamped to the usual 6–50 bar range.
+    pub fn new() -> Self {
+        Self::with_range(6, 50).expect("default range is valid")
+    }
+
+    /// Estimator clamped to `[min_period, max_period]`
+    ///
+    /// # Arguments
+    /// * `min_period` - Shortest reported period (must be at least 2)
+    /// * `max_period` - Longest reported period (must be above `min_period`)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new estimator or an error
+    pub fn with_range(min_period: usize, max_period: usize) -> Result<Self, IndicatorError> {
+        if min_period < 2 {
+            return Err(IndicatorError::invalid_parameter(
+                "min_period",
+                min_period,
+                "must be at least 2",
+            ));
+        }
+        if max_period <= min_period {
+            return Err(IndicatorError::invalid_parameter(
+                "max_period",
+                max_period,
+                format!("must be greater than min_period ({min_period})"),
+            ));
+        }
+        let mut cycle = Self {
+            min_period: min_period as f64,
+            max_period: max_period as f64,
+            prices: VecDeque::with_capacity(HILBERT_TAPS + 1),
+            smooth: VecDeque::with_capacity(HILBERT_TAPS + 1),
+            detrender: VecDeque::with_capacity(HILBERT_TAPS + 1),
+            i1: VecDeque::with_capacity(HILBERT_TAPS + 1),
+            q1: VecDeque::with_capacity(HILBERT_TAPS + 1),
+            i2: 0.0,
+            q2: 0.0,
+            re: 0.0,
+            im: 0.0,
+            period: 0.0,
+            smooth_period: 0.0,
+            last: None,
+        };
+        cycle.reset_state();
+        Ok(cycle)
+    }
+
+    /// Shortest and longest reported period
+    pub fn range(&self) -> (usize, usize) {
+        (self.min_period as usize, self.max_period as usize)
+    }
+
+    /// Reset the estimator state
+    pub fn reset_state(&mut self) {
+        for history in [
+            &mut self.prices,
+            &mut self.smooth,
+            &mut self.detrender,
+            &mut self.i1,
+            &mut self.q1,
+        ] {
+            history.clear();
+            history.resize(HILBERT_TAPS, 0.0);
+        }
+        self.i2 = 0.0;
+        self.q2 = 0.0;
+        self.re = 0.0;
+        self.im = 0.0;
+        self.period = self.min_period;
+        self.smooth_period = self.min_period;
+        self.last = None;
+    }
+
+    /// Feed one price and return the smoothed period.
+    pub(crate) fn step(&mut self, price: f64) -> f64 {
+        push(&mut self.prices, price);
+        let p = &self.prices;
+        push(
+            &mut self.smooth,
+            (4.0 * p[0] + 3.0 * p[1] + 2.0 * p[2] + p[3]) / 10.0,
+        );
+
+        let prev_period = self.period;
+        push(&mut self.detrender, hilbert(&self.smooth, prev_period));
+        push(&mut self.q1, hilbert(&self.detrender, prev_period));
+        push(&mut self.i1, self.detrender[3]);
+
+        // Advance the phase of I1 and Q1 by 90 degrees
+        let ji = hilbert(&self.i1, prev_period);
+        let jq = hilbert(&self.q1, prev_period);
+        let i2 = 0.2 * (self.i1[0] - jq) + 0.8 * self.i2;
+        let q2 = 0.2 * (self.q1[0] + ji) + 0.8 * self.q2;
+
+        // Homodyne discriminator
+        self.re = 0.2 * (i2 * self.i2 + q2 * self.q2) + 0.8 * self.re;
+        self.im = 0.2 * (i2 * self.q2 - q2 * self.i2) + 0.8 * self.im;
+        self.i2 = i2;
+        self.q2 = q2;
+
+        let mut period = prev_period;
+        if self.im != 0.0 && self.re != 0.0 {
+            let phase = (self.im / self.re).atan();
+            if phase != 0.0 {
+                period = 2.0 * std::f64::consts::PI / phase;
+            }
+        }
+        period = period
+            .min(1.5 * prev_period)
+            .max(0.67 * prev_period)
+            .clamp(self.min_period, self.max_period);
+        self.period = 0.2 * period + 0.8 * prev_period;
+        self.smooth_period = 0.33 * self.period + 0.67 * self.smooth_period;
+        self.last = Some(self.smooth_period);
+        self.smooth_period
+    }
+}
+
+impl Indicator<f64, f64> for DominantCycle {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, 1)?;
+        self.reset_state();
+        Ok(data.iter().map(|&price| self.step(price)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(value)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "DominantCycle"
+    }
+}
+
+impl Indicator<Candle, f64> for DominantCycle {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let prices: Vec<f64> = data.iter().map(|c| (c.high + c.low) / 2.0).collect();
+        self.calculate(&prices)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next((candle.high + candle.low) / 2.0)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "DominantCycle"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn sine(period: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| 50.0 + 5.0 * (2.0 * PI * i as f64 / period).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_finds_the_cycle_length() {
+        for period in [10.0, 15.0, 30.0] {
+            let mut cycle = DominantCycle::new();
+            let values = Indicator::<f64, f64>::calculate(&mut cycle, &sine(period, 400)).unwrap();
+            let estimate = values.last().unwrap();
+            assert!(
+                (estimate - period).abs() < period * 0.1,
+                "period {period}, estimate {estimate}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_is_respected() {
+        assert!(DominantCycle::with_range(1, 50).is_err());
+        assert!(DominantCycle::with_range(10, 10).is_err());
+
+        let mut cycle = DominantCycle::with_range(8, 20).unwrap();
+        assert_eq!(cycle.range(), (8, 20));
+        // Cycles outside the range pin the estimate to its bounds
+        let values = Indicator::<f64, f64>::calculate(&mut cycle, &sine(40.0, 400)).unwrap();
+        assert!(values.iter().all(|p| (8.0..=20.0).contains(p)));
+        assert!((values.last().unwrap() - 20.0).abs() < 0.5);
+
+        cycle.reset_state();
+        assert!(Indicator::<f64, f64>::current(&cycle).is_none());
+    }
+}
@@ -0,0 +1,276 @@
+use crate::indicators::trend::{Ema, EmaInit, Macd, MacdResult};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Bar colour of the [`ElderImpulse`] system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Impulse {
+    /// EMA and MACD histogram both rising: bulls in control.
+    Green,
+    /// EMA and MACD histogram both falling: bears in control.
+    Red,
+    /// Anything else: trend and momentum disagree.
+    Blue,
+}
+
+impl Impulse {
+    /// Whether Elder's rules allow opening a long: anything but red.
+    pub fn allows_long(self) -> bool {
+        self != Impulse::Red
+    }
+
+    /// Whether Elder's rules allow opening a short: anything but green.
+    pub fn allows_short(self) -> bool {
+        self != Impulse::Green
+    }
+}
+
+/// Elder Impulse System.
+///
+/// Colours each bar from two slopes: the EMA of price (trend, 13 bars by
+/// default) and the MACD histogram (momentum, 12/26/9). Green when both
+/// rise from the previous bar, red when both fall, blue otherwise. Used as
+/// a filter: no longs on red bars, no shorts on green ones (see
+/// [`Impulse::allows_long`]).
+///
+/// The first colour needs a previous value of both lines, so it comes one
+/// bar after the later of the two.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::{ElderImpulse, Impulse};
+/// use rsta::indicators::Indicator;
+///
+/// let mut impulse = ElderImpulse::new(3, 2, 4, 2).unwrap();
+/// // Accelerating rally: EMA and histogram both climb
+/// let prices: Vec<f64> = (0..10).map(|i| 100.0 + (i * i) as f64).collect();
+/// let colours = impulse.calculate(&prices).unwrap();
+/// assert_eq!(colours.len(), prices.len() - 1);
+/// assert_eq!(*colours.last().unwrap(), Impulse::Green);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElderImpulse {
+    ema_period: usize,
+    ema: Ema,
+    macd: Macd,
+    prev: Option<(f64, f64)>,
+    ema_value: Option<f64>,
+    histogram: Option<f64>,
+    last: Option<Impulse>,
+}
+
+impl ElderImpulse {
+    /// Create a new Elder Impulse System
+    ///
+    /// # Arguments
+    /// * `ema_period` - Period of the trend EMA (typically 13)
+    /// * `fast_period` - Fast MACD period (typically 12)
+    /// * `slow_period` - Slow MACD period (typically 26)
+    /// * `signal_period` - MACD signal period (typically 9)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(
+        ema_period: usize,
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            ema_period,
+            ema: Ema::new(ema_period)?,
+            macd: Macd::new(fast_period, slow_period, signal_period)?,
+            prev: None,
+            ema_value: None,
+            histogram: None,
+            last: None,
+        })
+    }
+
+    /// Choose how the EMA and the MACD lines are seeded (see [`EmaInit`])
+    ///
+    /// # Arguments
+    /// * `init` - Seeding method
+    ///
+    /// # Returns
+    /// * `Self` - The indicator with the new seeding method
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.ema = self.ema.with_init(init);
+        self.macd = self.macd.with_init(init);
+        self
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.ema.reset_state();
+        self.macd.reset_state();
+        self.prev = None;
+        self.ema_value = None;
+        self.histogram = None;
+        self.last = None;
+    }
+
+    fn warmup(&self) -> usize {
+        let ema = Indicator::<f64, f64>::output_offset(&self.ema);
+        let macd = Indicator::<f64, MacdResult>::output_offset(&self.macd);
+        ema.max(macd) + 2
+    }
+
+    fn step(&mut self, price: f64) -> Result<Option<Impulse>, IndicatorError> {
+        if let Some(ema) = Indicator::<f64, f64>::next(&mut self.ema, price)? {
+            self.ema_value = Some(ema);
+        }
+        if let Some(macd) = Indicator::<f64, MacdResult>::next(&mut self.macd, price)? {
+            self.histogram = Some(macd.histogram);
+        }
+        let (Some(ema), Some(histogram)) = (self.ema_value, self.histogram) else {
+            return Ok(None);
+        };
+        let Some((prev_ema, prev_histogram)) = self.prev.replace((ema, histogram)) else {
+            return Ok(None);
+        };
+
+        self.last = Some(if ema > prev_ema && histogram > prev_histogram {
+            Impulse::Green
+        } else if ema < prev_ema && histogram < prev_histogram {
+            Impulse::Red
+        } else {
+            Impulse::Blue
+        });
+        Ok(self.last)
+    }
+}
+
+impl Default for ElderImpulse {
+    fn default() -> Self {
+        Self::new(13, 12, 26, 9).expect("default periods are valid")
+    }
+}
+
+impl Indicator<f64, Impulse> for ElderImpulse {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<Impulse>, IndicatorError> {
+        validate_data_length(data, self.warmup())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() + 1 - self.warmup());
+        for &price in data {
+            if let Some(value) = self.step(price)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<Impulse>, IndicatorError> {
+        self.step(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Impulse> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "ElderImpulse"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.ema_period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+impl Indicator<Candle, Impulse> for ElderImpulse {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Impulse>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<Impulse>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Impulse> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "ElderImpulse"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.ema_period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colours_follow_both_slopes() {
+        let prices: Vec<f64> = (0..60)
+            .map(|i| 100.0 + (i as f64 * 0.2).sin() * 10.0)
+            .collect();
+        let mut impulse = ElderImpulse::default();
+        let colours = Indicator::<f64, Impulse>::calculate(&mut impulse, &prices).unwrap();
+        assert_eq!(colours.len(), prices.len() - 1);
+
+        let emas = Indicator::<f64, f64>::calculate(&mut Ema::new(13).unwrap(), &prices).unwrap();
+        let histograms: Vec<f64> =
+            Indicator::<f64, MacdResult>::calculate(&mut Macd::new(12, 26, 9).unwrap(), &prices)
+                .unwrap()
+                .iter()
+                .map(|m| m.histogram)
+                .collect();
+        for (i, colour) in colours.iter().enumerate() {
+            let ema_up = emas[i + 1] > emas[i];
+            let hist_up = histograms[i + 1] > histograms[i];
+            let ema_down = emas[i + 1] < emas[i];
+            let hist_down = histograms[i + 1] < histograms[i];
+            let expected = match (ema_up && hist_up, ema_down && hist_down) {
+                (true, _) => Impulse::Green,
+                (_, true) => Impulse::Red,
+                _ => Impulse::Blue,
+            };
+            assert_eq!(*colour, expected, "bar {}", i + 1);
+        }
+        for colour in [Impulse::Green, Impulse::Red, Impulse::Blue] {
+            assert!(colours.contains(&colour));
+        }
+    }
+
+    #[test]
+    fn test_filters_and_warmup() {
+        assert!(Impulse::Green.allows_long() && !Impulse::Green.allows_short());
+        assert!(!Impulse::Red.allows_long() && Impulse::Red.allows_short());
+        assert!(Impulse::Blue.allows_long() && Impulse::Blue.allows_short());
+
+        let mut impulse = ElderImpulse::new(3, 2, 4, 2)
+            .unwrap()
+            .with_init(EmaInit::TALIB);
+        let warmup = Indicator::<f64, Impulse>::min_periods(&impulse);
+        // TA-Lib MACD(2, 4, 2) first emits on bar 4
+        assert_eq!(warmup, 6);
+        let prices: Vec<f64> = (0..10).map(f64::from).collect();
+        assert!(Indicator::<f64, Impulse>::calculate(&mut impulse, &prices[..5]).is_err());
+        let colours = Indicator::<f64, Impulse>::calculate(&mut impulse, &prices).unwrap();
+        assert_eq!(colours.len(), prices.len() + 1 - warmup);
+        assert!(ElderImpulse::new(0, 12, 26, 9).is_err());
+        assert!(ElderImpulse::new(13, 26, 12, 9).is_err());
+    }
+}
@@ -1,7 +1,49 @@
-use crate::indicators::utils::calculate_ema;
-use crate::indicators::validate_period;
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::utils::{calculate_ema, validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
+/// How an [`Ema`] picks its first value.
+///
+/// Platforms disagree here, which is the usual cause of EMA and MACD
+/// mismatches in the first few hundred bars:
+///
+/// - [`FirstValue`](EmaInit::FirstValue) seeds with the first input and
+///   emits from the first bar — pandas' `ewm(adjust=False)` and `ta-rs`.
+/// - [`SmaOfFirstPeriod`](EmaInit::SmaOfFirstPeriod) emits nothing for
+///   `period - 1` bars, then seeds with the SMA of the first `period`
+///   inputs — TA-Lib's `EMA` and `MACD`.
+///
+/// [`EmaInit::PANDAS`] and [`EmaInit::TALIB`] name these presets. Wilder
+/// smoothers in this crate (`Rsi`, `Atr`) always seed with an SMA, as
+/// TA-Lib does, so `with_init(EmaInit::TALIB)` on the EMA-based indicators
+/// is all TA-Lib compatibility needs.
+///
+/// ```
+/// use rsta::indicators::trend::{Ema, EmaInit};
+/// use rsta::indicators::Indicator;
+///
+/// let prices = [2.0, 4.0, 6.0, 8.0];
+/// let mut talib = Ema::new(3).unwrap().with_init(EmaInit::TALIB);
+/// // Seed = SMA(2, 4, 6) = 4, then 8 * 0.5 + 4 * 0.5 = 6.
+/// assert_eq!(talib.calculate(&prices).unwrap(), vec![4.0, 6.0]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EmaInit {
+    /// Seed with the first value and emit from the first bar.
+    #[default]
+    FirstValue,
+    /// Seed with the simple average of the first `period` values.
+    SmaOfFirstPeriod,
+}
+
+impl EmaInit {
+    /// pandas `ewm(span=period, adjust=False)` / `ta-rs` seeding.
+    pub const PANDAS: Self = Self::FirstValue;
+    /// TA-Lib seeding (`TA_EMA`, `TA_MACD`).
+    pub const TALIB: Self = Self::SmaOfFirstPeriod;
+}
+
 /// Exponential Moving Average (EMA) indicator
 ///
 /// # Example with float values
@@ -48,11 +90,14 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// // Calculate EMA values based on close prices
 /// let ema_values = ema.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Ema {
     period: usize,
     alpha: f64,
+    init: EmaInit,
     current_ema: Option<f64>,
+    seed_sum: f64,
+    seed_count: usize,
 }
 
 impl Ema {
@@ -69,10 +114,30 @@ impl Ema {
         Ok(Self {
             period,
             alpha: 2.0 / (period as f64 + 1.0),
+            init: EmaInit::FirstValue,
             current_ema: None,
+            seed_sum: 0.0,
+            seed_count: 0,
         })
     }
 
+    /// Choose how the first EMA value is seeded (see [`EmaInit`])
+    ///
+    /// # Arguments
+    /// * `init` - Seeding method
+    ///
+    /// # Returns
+    /// * `Self` - The EMA with the new seeding method
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.init = init;
+        self
+    }
+
+    /// Seeding method in use
+    pub fn init(&self) -> EmaInit {
+        self.init
+    }
+
     /// Set the initial EMA value
     ///
     /// # Arguments
@@ -88,31 +153,79 @@ impl Ema {
     /// Reset the EMA indicator state
     pub fn reset_state(&mut self) {
         self.current_ema = None;
+        self.seed_sum = 0.0;
+        self.seed_count = 0;
+    }
+
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let ema = match (self.current_ema, self.init) {
+            // Apply EMA formula: EMA_today = (Price_today * alpha) + (EMA_yesterday * (1 - alpha))
+            (Some(current), _) => (value * self.alpha) + (current * (1.0 - self.alpha)),
+            // First value becomes the initial EMA
+            (None, EmaInit::FirstValue) => value,
+            (None, EmaInit::SmaOfFirstPeriod) => {
+                self.seed_sum += value;
+                self.seed_count += 1;
+                if self.seed_count < self.period {
+                    return None;
+                }
+                self.seed_sum / self.period as f64
+            }
+        };
+        self.current_ema = Some(ema);
+        Some(ema)
+    }
+
+    fn calculate_prices(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        let result = match self.init {
+            EmaInit::FirstValue => {
+                let result = calculate_ema(data, self.period)?;
+                self.reset_state();
+                self.current_ema = result.last().copied();
+                result
+            }
+            EmaInit::SmaOfFirstPeriod => {
+                validate_data_length(data, self.period)?;
+                self.reset_state();
+                data.iter().filter_map(|&v| self.step(v)).collect()
+            }
+        };
+        Ok(result)
+    }
+
+    fn offset(&self) -> usize {
+        match self.init {
+            EmaInit::FirstValue => 0,
+            EmaInit::SmaOfFirstPeriod => self.period - 1,
+        }
     }
 }
 
 // Implementation for raw price values
 impl Indicator<f64, f64> for Ema {
     fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
-        calculate_ema(data, self.period)
+        self.calculate_prices(data)
     }
 
     fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
-        if let Some(current) = self.current_ema {
-            // Apply EMA formula: EMA_today = (Price_today * alpha) + (EMA_yesterday * (1 - alpha))
-            let new_ema = (value * self.alpha) + (current * (1.0 - self.alpha));
-            self.current_ema = Some(new_ema);
-            Ok(Some(new_ema))
-        } else {
-            // First value becomes the initial EMA
-            self.current_ema = Some(value);
-            Ok(Some(value))
-        }
+        Ok(self.step(value))
     }
 
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&f64> {
+        self.current_ema.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+
+    fn output_offset(&self) -> usize {
+        self.offset()
+    }
 }
 
 // Implementation for candle data
@@ -120,26 +233,40 @@ impl Indicator<Candle, f64> for Ema {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
         // Extract close prices from candles
         let close_prices: Vec<f64> = data.iter().map(|candle| candle.close).collect();
-        calculate_ema(&close_prices, self.period)
+        self.calculate_prices(&close_prices)
     }
 
     fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
-        let close_price = candle.close;
-
-        if let Some(current) = self.current_ema {
-            // Apply EMA formula: EMA_today = (Price_today * alpha) + (EMA_yesterday * (1 - alpha))
-            let new_ema = (close_price * self.alpha) + (current * (1.0 - self.alpha));
-            self.current_ema = Some(new_ema);
-            Ok(Some(new_ema))
-        } else {
-            // First value becomes the initial EMA
-            self.current_ema = Some(close_price);
-            Ok(Some(close_price))
-        }
+        Ok(self.step(candle.close))
     }
 
     fn reset(&mut self) {
-        self.current_ema = None;
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.current_ema.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+
+    fn output_offset(&self) -> usize {
+        self.offset()
+    }
+}
+
+impl Describe for Ema {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "ema",
+            title: "Exponential Moving Average",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -474,4 +601,28 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn test_ema_sma_seed() {
+        let prices = [2.0, 4.0, 6.0, 8.0, 10.0, 9.0];
+        let mut ema = Ema::new(3).unwrap().with_init(EmaInit::SmaOfFirstPeriod);
+        assert_eq!(ema.init(), EmaInit::TALIB);
+        assert_eq!(Indicator::<f64, f64>::output_offset(&ema), 2);
+
+        let batch = Indicator::<f64, f64>::calculate(&mut ema, &prices).unwrap();
+        assert_eq!(batch, vec![4.0, 6.0, 8.0, 8.5]);
+
+        ema.reset_state();
+        let streamed: Vec<Option<f64>> = prices
+            .iter()
+            .map(|&p| Indicator::<f64, f64>::next(&mut ema, p).unwrap())
+            .collect();
+        assert_eq!(streamed[..2], [None, None]);
+        assert_eq!(
+            streamed[2..],
+            batch.iter().map(|&v| Some(v)).collect::<Vec<_>>()[..]
+        );
+
+        assert!(Indicator::<f64, f64>::calculate(&mut ema, &prices[..2]).is_err());
+    }
 }
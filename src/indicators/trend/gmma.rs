@@ -0,0 +1,307 @@
+use crate::indicators::trend::{Ema, EmaInit};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Guppy Multiple Moving Averages output for a single bar.
+///
+/// Widths and separation are percentages, so they compare across
+/// instruments and price levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GmmaResult {
+    /// Short-term EMAs (traders' group), shortest first.
+    pub short: [f64; 6],
+    /// Long-term EMAs (investors' group), shortest first.
+    pub long: [f64; 6],
+    /// Spread of the short group, `(max - min) / mean * 100`. Narrow when
+    /// traders agree on value (compression), wide when they chase a move.
+    pub short_width: f64,
+    /// Spread of the long group, `(max - min) / mean * 100`. A widening
+    /// long group confirms trend strength.
+    pub long_width: f64,
+    /// Distance between the groups, `(mean(short) - mean(long)) /
+    /// mean(long) * 100`. Positive with the short group above the long
+    /// one (uptrend), negative below.
+    pub separation: f64,
+}
+
+/// Guppy Multiple Moving Averages (GMMA) — Daryl Guppy.
+///
+/// Two ribbons of six EMAs each: the short group (3, 5, 8, 10, 12, 15)
+/// tracks traders, the long group (30, 35, 40, 45, 50, 60) investors. A
+/// strong trend shows both groups expanded and well separated; compressed
+/// groups that cross each other mark a trend change or a range.
+///
+/// EMAs follow [`EmaInit::FirstValue`] seeding by default, emitting from
+/// the first bar; with [`EmaInit::SmaOfFirstPeriod`] output starts once the
+/// longest EMA is seeded.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::Gmma;
+/// use rsta::indicators::Indicator;
+///
+/// let mut gmma = Gmma::new();
+/// let rising: Vec<f64> = (0..100).map(|i| 100.0 + i as f64).collect();
+/// let last = *gmma.calculate(&rising).unwrap().last().unwrap();
+/// assert!(last.separation > 0.0);
+/// assert!(last.short[0] > last.long[5]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gmma {
+    short_periods: [usize; 6],
+    long_periods: [usize; 6],
+    init: EmaInit,
+    short: [Ema; 6],
+    long: [Ema; 6],
+    last: Option<GmmaResult>,
+}
+
+impl Gmma {
+    /// Short-group periods of the standard GMMA.
+    pub const SHORT_PERIODS: [usize; 6] = [3, 5, 8, 10, 12, 15];
+    /// Long-group periods of the standard GMMA.
+    pub const LONG_PERIODS: [usize; 6] = [30, 35, 40, 45, 50, 60];
+
+    /// Create the standard GMMA
+    pub fn new() -> Self {
+        Self::with_periods(Self::SHORT_PERIODS, Self::LONG_PERIODS)
+            .expect("standard GMMA periods are valid")
+    }
+
+    /// Create a GMMA with custom groups
+    ///
+    /// # Arguments
+    /// * `short_periods` - Periods of the short group (each at least 1)
+    /// * `long_periods` - Periods of the long group (each at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn with_periods(
+        short_periods: [usize; 6],
+        long_periods: [usize; 6],
+    ) -> Result<Self, IndicatorError> {
+        let emas = |periods: [usize; 6]| -> Result<[Ema; 6], IndicatorError> {
+            let [a, b, c, d, e, f] = periods.map(Ema::new);
+            Ok([a?, b?, c?, d?, e?, f?])
+        };
+        Ok(Self {
+            short_periods,
+            long_periods,
+            init: EmaInit::default(),
+            short: emas(short_periods)?,
+            long: emas(long_periods)?,
+            last: None,
+        })
+    }
+
+    /// Set how the EMAs are seeded (first value by default)
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.init = init;
+        self.short = self.short.map(|ema| ema.with_init(init));
+        self.long = self.long.map(|ema| ema.with_init(init));
+        self
+    }
+
+    /// Short-group periods
+    pub fn short_periods(&self) -> [usize; 6] {
+        self.short_periods
+    }
+
+    /// Long-group periods
+    pub fn long_periods(&self) -> [usize; 6] {
+        self.long_periods
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.short.iter_mut().for_each(Ema::reset_state);
+        self.long.iter_mut().for_each(Ema::reset_state);
+        self.last = None;
+    }
+
+    fn warmup(&self) -> usize {
+        match self.init {
+            EmaInit::FirstValue => 1,
+            EmaInit::SmaOfFirstPeriod => self
+                .short_periods
+                .iter()
+                .chain(&self.long_periods)
+                .max()
+                .copied()
+                .unwrap_or(1),
+        }
+    }
+
+    fn step(&mut self, price: f64) -> Result<Option<GmmaResult>, IndicatorError> {
+        let mut short = [0.0; 6];
+        let mut long = [0.0; 6];
+        let mut ready = true;
+        for (ema, value) in self.short.iter_mut().zip(&mut short) {
+            match Indicator::<f64, f64>::next(ema, price)? {
+                Some(v) => *value = v,
+                None => ready = false,
+            }
+        }
+        for (ema, value) in self.long.iter_mut().zip(&mut long) {
+            match Indicator::<f64, f64>::next(ema, price)? {
+                Some(v) => *value = v,
+                None => ready = false,
+            }
+        }
+        if !ready {
+            return Ok(None);
+        }
+        let short_mean = short.iter().sum::<f64>() / 6.0;
+        let long_mean = long.iter().sum::<f64>() / 6.0;
+        self.last = Some(GmmaResult {
+            short,
+            long,
+            short_width: width(&short, short_mean),
+            long_width: width(&long, long_mean),
+            separation: percent(short_mean - long_mean, long_mean),
+        });
+        Ok(self.last)
+    }
+}
+
+impl Default for Gmma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `part / whole * 100`, 0 for a zero `whole`.
+fn percent(part: f64, whole: f64) -> f64 {
+    if whole != 0.0 {
+        part / whole * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn width(group: &[f64; 6], mean: f64) -> f64 {
+    let (min, max) = group
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    percent(max - min, mean)
+}
+
+impl Indicator<f64, GmmaResult> for Gmma {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<GmmaResult>, IndicatorError> {
+        validate_data_length(data, self.warmup())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() + 1 - self.warmup());
+        for &price in data {
+            if let Some(value) = self.step(price)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<GmmaResult>, IndicatorError> {
+        self.step(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&GmmaResult> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Gmma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.long_periods.iter().max().copied()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+impl Indicator<Candle, GmmaResult> for Gmma {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<GmmaResult>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<GmmaResult>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&GmmaResult> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Gmma"
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.long_periods.iter().max().copied()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_match_individual_emas() {
+        let prices: Vec<f64> = (0..120)
+            .map(|i| 100.0 + (i as f64 * 0.1).sin() * 10.0)
+            .collect();
+        let mut gmma = Gmma::new().with_init(EmaInit::TALIB);
+        let values = Indicator::<f64, GmmaResult>::calculate(&mut gmma, &prices).unwrap();
+        assert_eq!(values.len(), prices.len() - 59);
+
+        for (short, periods) in [(true, Gmma::SHORT_PERIODS), (false, Gmma::LONG_PERIODS)] {
+            let group = |r: &GmmaResult| if short { r.short } else { r.long };
+            for (k, &period) in periods.iter().enumerate() {
+                let mut ema = Ema::new(period).unwrap().with_init(EmaInit::TALIB);
+                let expected = Indicator::<f64, f64>::calculate(&mut ema, &prices).unwrap();
+                let expected = &expected[expected.len() - values.len()..];
+                for (result, e) in values.iter().zip(expected) {
+                    assert!((group(result)[k] - e).abs() < 1e-9);
+                }
+            }
+        }
+
+        let last = values.last().unwrap();
+        let long_mean = last.long.iter().sum::<f64>() / 6.0;
+        let short_mean = last.short.iter().sum::<f64>() / 6.0;
+        assert!((last.separation - (short_mean - long_mean) / long_mean * 100.0).abs() < 1e-9);
+        assert!(
+            (last.long_width - (last.long[0] - last.long[5]).abs() / long_mean * 100.0).abs()
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn test_flat_market_is_compressed() {
+        let mut gmma = Gmma::default();
+        let values = Indicator::<f64, GmmaResult>::calculate(&mut gmma, &[50.0; 5]).unwrap();
+        assert_eq!(values.len(), 5);
+        assert!(values.iter().all(|r| {
+            [r.short_width, r.long_width, r.separation]
+                .iter()
+                .all(|v| v.abs() < 1e-9)
+        }));
+        assert!(Gmma::with_periods([3, 5, 8, 10, 12, 0], Gmma::LONG_PERIODS).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::trend::Wma;
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
@@ -6,7 +7,7 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 ///
 /// `HMA = WMA(2 * WMA(price, period/2) - WMA(price, period), sqrt(period))`.
 /// Designed by Alan Hull to be both smooth and reactive.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Hma {
     period: usize,
     half: Wma,
@@ -35,6 +36,13 @@ impl Hma {
         self.smooth.reset_state();
     }
 
+    /// Bars needed before the first output: the full WMA warm-up plus the
+    /// `sqrt(period)` smoothing WMA on top of it.
+    fn min_len(&self) -> usize {
+        let smooth_p = (self.period as f64).sqrt().round() as usize;
+        self.period + smooth_p.max(1) - 1
+    }
+
     fn step(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
         let h = <Wma as Indicator<f64, f64>>::next(&mut self.half, value)?;
         let f = <Wma as Indicator<f64, f64>>::next(&mut self.full, value)?;
@@ -48,8 +56,7 @@ impl Hma {
 
 impl Indicator<f64, f64> for Hma {
     fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
-        let smooth_p = (self.period as f64).sqrt().round() as usize;
-        let needed = self.period + smooth_p.max(1) - 1;
+        let needed = self.min_len();
         validate_data_length(data, needed)?;
         self.reset_state();
         let mut out = Vec::with_capacity(data.len().saturating_sub(needed - 1));
@@ -69,6 +76,10 @@ impl Indicator<f64, f64> for Hma {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        Indicator::<f64, f64>::current(&self.smooth)
+    }
+
     fn name(&self) -> &'static str {
         "Hma"
     }
@@ -76,12 +87,15 @@ impl Indicator<f64, f64> for Hma {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.min_len()
+    }
 }
 
 impl Indicator<Candle, f64> for Hma {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
-        let smooth_p = (self.period as f64).sqrt().round() as usize;
-        let needed = self.period + smooth_p.max(1) - 1;
+        let needed = self.min_len();
         validate_data_length(data, needed)?;
         self.reset_state();
         let mut out = Vec::with_capacity(data.len().saturating_sub(needed - 1));
@@ -101,6 +115,10 @@ impl Indicator<Candle, f64> for Hma {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        Indicator::<f64, f64>::current(&self.smooth)
+    }
+
     fn name(&self) -> &'static str {
         "Hma"
     }
@@ -108,6 +126,23 @@ impl Indicator<Candle, f64> for Hma {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.min_len()
+    }
+}
+
+impl Describe for Hma {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "hma",
+            title: "Hull Moving Average",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 2)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
-use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::utils::{validate_data_length, validate_period, RollingMax, RollingMin};
 use crate::indicators::{Candle, Indicator, IndicatorError};
-use std::collections::VecDeque;
 
 /// Ichimoku Cloud output for a single bar.
 ///
@@ -47,13 +47,41 @@ pub struct IchimokuResult {
 /// let values = ichi.calculate(&candles).unwrap();
 /// assert!(!values.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Ichimoku {
-    tenkan_period: usize,
-    kijun_period: usize,
     senkou_b_period: usize,
-    /// Rolling buffer of `(high, low)` covering at least `senkou_b_period`.
-    buffer: VecDeque<(f64, f64)>,
+    tenkan: Channel,
+    kijun: Channel,
+    senkou_b: Channel,
+    last: Option<IchimokuResult>,
+}
+
+/// Rolling highest high / lowest low over one Ichimoku window.
+#[derive(Debug, Clone)]
+struct Channel {
+    highs: RollingMax,
+    lows: RollingMin,
+}
+
+impl Channel {
+    fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+        })
+    }
+
+    /// Push a bar and return the midpoint of the window's range.
+    fn midpoint(&mut self, high: f64, low: f64) -> f64 {
+        let hi = self.highs.push(high).unwrap_or(f64::NEG_INFINITY);
+        let lo = self.lows.push(low).unwrap_or(f64::INFINITY);
+        (hi + lo) / 2.0
+    }
+
+    fn reset(&mut self) {
+        self.highs.reset();
+        self.lows.reset();
+    }
 }
 
 impl Ichimoku {
@@ -70,15 +98,20 @@ impl Ichimoku {
         validate_period(kijun_period, 1)?;
         validate_period(senkou_b_period, 1)?;
         if tenkan_period > kijun_period || kijun_period > senkou_b_period {
-            return Err(IndicatorError::InvalidParameter(
-                "Ichimoku periods must satisfy tenkan <= kijun <= senkou_b".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "kijun_period",
+                kijun_period,
+                format!(
+                    "must satisfy tenkan ({tenkan_period}) <= kijun <= senkou_b ({senkou_b_period})"
+                ),
             ));
         }
         Ok(Self {
-            tenkan_period,
-            kijun_period,
             senkou_b_period,
-            buffer: VecDeque::with_capacity(senkou_b_period),
+            tenkan: Channel::new(tenkan_period)?,
+            kijun: Channel::new(kijun_period)?,
+            senkou_b: Channel::new(senkou_b_period)?,
+            last: None,
         })
     }
 
@@ -89,45 +122,28 @@ impl Ichimoku {
 
     /// Reset internal state.
     pub fn reset_state(&mut self) {
-        self.buffer.clear();
-    }
-
-    /// Midpoint of the highest high and lowest low over the last `n` entries.
-    fn midpoint(buffer: &VecDeque<(f64, f64)>, n: usize) -> f64 {
-        let start = buffer.len().saturating_sub(n);
-        let slice = buffer.iter().skip(start);
-        let mut hi = f64::NEG_INFINITY;
-        let mut lo = f64::INFINITY;
-        for &(h, l) in slice {
-            if h > hi {
-                hi = h;
-            }
-            if l < lo {
-                lo = l;
-            }
-        }
-        (hi + lo) / 2.0
+        self.tenkan.reset();
+        self.kijun.reset();
+        self.senkou_b.reset();
+        self.last = None;
     }
 
     fn step(&mut self, candle: Candle) -> Option<IchimokuResult> {
-        self.buffer.push_back((candle.high, candle.low));
-        if self.buffer.len() > self.senkou_b_period {
-            self.buffer.pop_front();
-        }
-        if self.buffer.len() < self.senkou_b_period {
+        let tenkan = self.tenkan.midpoint(candle.high, candle.low);
+        let kijun = self.kijun.midpoint(candle.high, candle.low);
+        let senkou_b = self.senkou_b.midpoint(candle.high, candle.low);
+        if !self.senkou_b.highs.is_full() {
             return None;
         }
-        let tenkan = Self::midpoint(&self.buffer, self.tenkan_period);
-        let kijun = Self::midpoint(&self.buffer, self.kijun_period);
         let senkou_a = (tenkan + kijun) / 2.0;
-        let senkou_b = Self::midpoint(&self.buffer, self.senkou_b_period);
-        Some(IchimokuResult {
+        self.last = Some(IchimokuResult {
             tenkan,
             kijun,
             senkou_a,
             senkou_b,
             chikou: candle.close,
-        })
+        });
+        self.last
     }
 }
 
@@ -152,9 +168,34 @@ impl Indicator<Candle, IchimokuResult> for Ichimoku {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&IchimokuResult> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Ichimoku"
     }
+
+    fn min_periods(&self) -> usize {
+        self.senkou_b_period
+    }
+}
+
+impl Describe for Ichimoku {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "ichimoku",
+            title: "Ichimoku Cloud",
+            input: InputKind::Candle,
+            params: &[
+                ParamDescriptor::period("tenkan", 1).with_default(9.0),
+                ParamDescriptor::period("kijun", 1).with_default(26.0),
+                ParamDescriptor::period("senkou_b", 1).with_default(52.0),
+            ],
+            outputs: &["tenkan", "kijun", "senkou_a", "senkou_b", "chikou"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
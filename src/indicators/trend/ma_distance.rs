@@ -0,0 +1,169 @@
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Distance of price from a moving average, in percent.
+///
+/// `distance = (price - MA) / MA * 100`. Positive above the average,
+/// negative below; how stretched price is from its mean, for
+/// mean-reversion entries or to avoid chasing extended moves. A zero
+/// average gives `0`.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::{MaDistance, MaType};
+/// use rsta::indicators::Indicator;
+///
+/// let mut distance = MaDistance::new(3, MaType::Sma).unwrap();
+/// let values = distance.calculate(&[10.0, 10.0, 10.0, 13.0]).unwrap();
+/// // SMA(3) of the last window is 11: 13 sits 18.2% above it.
+/// assert_eq!(values[0], 0.0);
+/// assert!((values[1] - 2.0 / 11.0 * 100.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaDistance {
+    period: usize,
+    ma_type: MaType,
+    ma: MovingAverage,
+    last: Option<f64>,
+}
+
+impl MaDistance {
+    /// Create a new MA distance
+    ///
+    /// # Arguments
+    /// * `period` - Period of the moving average (must be at least 1)
+    /// * `ma_type` - Kind of moving average
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize, ma_type: MaType) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            period,
+            ma_type,
+            ma: MovingAverage::new(ma_type, period)?,
+            last: None,
+        })
+    }
+
+    /// Moving-average type in use
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.ma.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, price: f64) -> Result<Option<f64>, IndicatorError> {
+        let Some(ma) = self.ma.next(price)? else {
+            return Ok(None);
+        };
+        self.last = Some(if ma != 0.0 {
+            (price - ma) / ma * 100.0
+        } else {
+            0.0
+        });
+        Ok(self.last)
+    }
+}
+
+impl Indicator<f64, f64> for MaDistance {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.ma.offset() + 1)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - self.ma.offset());
+        for &price in data {
+            if let Some(value) = self.step(price)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        self.step(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "MaDistance"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.ma.offset() + 1
+    }
+}
+
+impl Indicator<Candle, f64> for MaDistance {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "MaDistance"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.ma.offset() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::Ema;
+
+    #[test]
+    fn test_distance_follows_the_average() {
+        let prices: Vec<f64> = (0..30)
+            .map(|i| 100.0 + (i as f64 * 0.5).sin() * 5.0)
+            .collect();
+        let mut distance = MaDistance::new(5, MaType::Ema).unwrap();
+        assert_eq!(distance.ma_type(), MaType::Ema);
+        let values = Indicator::<f64, f64>::calculate(&mut distance, &prices).unwrap();
+        let emas = Indicator::<f64, f64>::calculate(&mut Ema::new(5).unwrap(), &prices).unwrap();
+        assert_eq!(values.len(), emas.len());
+        let offset = prices.len() - emas.len();
+        for (i, (value, ema)) in values.iter().zip(&emas).enumerate() {
+            assert!((value - (prices[i + offset] - ema) / ema * 100.0).abs() < 1e-9);
+        }
+
+        let mut flat = MaDistance::new(2, MaType::Sma).unwrap();
+        assert_eq!(
+            Indicator::<f64, f64>::calculate(&mut flat, &[0.0, 0.0]).unwrap(),
+            vec![0.0]
+        );
+        assert!(MaDistance::new(0, MaType::Sma).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Moving-average ribbon output for a single bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaRibbonResult {
+    /// Value of each average, in the order of the ribbon's periods
+    /// (shortest first).
+    pub values: Vec<f64>,
+    /// How well the averages are stacked, from `-1` to `1`: `1` when every
+    /// average sits above the next longer one (bullish order), `-1` when
+    /// every one sits below it (bearish order), near `0` when tangled.
+    pub alignment: f64,
+}
+
+/// Moving-average ribbon
+///
+/// A band of averages of one type over increasing periods. The averages
+/// fan out in a clean order during a trend and braid together when the
+/// trend stalls; the `alignment` score reads that order as a number: the
+/// mean over neighbouring pairs of `+1` when the shorter average is above
+/// the longer one, `-1` when below and `0` when equal.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::{MaRibbon, MaType};
+/// use rsta::indicators::Indicator;
+///
+/// let mut ribbon = MaRibbon::new(&[2, 4, 8], MaType::Sma).unwrap();
+/// let rising: Vec<f64> = (0..10).map(|i| i as f64).collect();
+/// let values = ribbon.calculate(&rising).unwrap();
+/// assert_eq!(values[0].values, vec![6.5, 5.5, 3.5]);
+/// assert_eq!(values[0].alignment, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaRibbon {
+    periods: Vec<usize>,
+    ma_type: MaType,
+    averages: Vec<MovingAverage>,
+    last: Option<MaRibbonResult>,
+}
+
+impl MaRibbon {
+    /// Create a new moving-average ribbon
+    ///
+    /// # Arguments
+    /// * `periods` - Periods of the averages, strictly increasing (at least 2)
+    /// * `ma_type` - Kind of moving average
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(periods: &[usize], ma_type: MaType) -> Result<Self, IndicatorError> {
+        if periods.len() < 2 {
+            return Err(IndicatorError::invalid_parameter(
+                "periods",
+                periods.len(),
+                "must hold at least 2 periods",
+            ));
+        }
+        if periods.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(IndicatorError::invalid_parameter(
+                "periods",
+                format!("{periods:?}"),
+                "must be strictly increasing",
+            ));
+        }
+        let averages = periods
+            .iter()
+            .map(|&period| MovingAverage::new(ma_type, period))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            periods: periods.to_vec(),
+            ma_type,
+            averages,
+            last: None,
+        })
+    }
+
+    /// Periods of the averages, shortest first
+    pub fn periods(&self) -> &[usize] {
+        &self.periods
+    }
+
+    /// Moving-average type in use
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.averages.iter_mut().for_each(MovingAverage::reset);
+        self.last = None;
+    }
+
+    fn warmup(&self) -> usize {
+        self.averages
+            .iter()
+            .map(MovingAverage::offset)
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+
+    fn step(&mut self, price: f64) -> Result<Option<MaRibbonResult>, IndicatorError> {
+        let mut values = Vec::with_capacity(self.averages.len());
+        for ma in &mut self.averages {
+            values.extend(ma.next(price)?);
+        }
+        if values.len() < self.averages.len() {
+            return Ok(None);
+        }
+        let alignment = values
+            .windows(2)
+            .map(|pair| match pair[0].partial_cmp(&pair[1]) {
+                Some(std::cmp::Ordering::Greater) => 1.0,
+                Some(std::cmp::Ordering::Less) => -1.0,
+                _ => 0.0,
+            })
+            .sum::<f64>()
+            / (values.len() - 1) as f64;
+        self.last = Some(MaRibbonResult { values, alignment });
+        Ok(self.last.clone())
+    }
+}
+
+impl Indicator<f64, MaRibbonResult> for MaRibbon {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<MaRibbonResult>, IndicatorError> {
+        validate_data_length(data, self.warmup())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() + 1 - self.warmup());
+        for &price in data {
+            if let Some(value) = self.step(price)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<MaRibbonResult>, IndicatorError> {
+        self.step(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&MaRibbonResult> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "MaRibbon"
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.periods.last().copied()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+impl Indicator<Candle, MaRibbonResult> for MaRibbon {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<MaRibbonResult>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<MaRibbonResult>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&MaRibbonResult> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "MaRibbon"
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.periods.last().copied()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment_tracks_the_stacking_order() {
+        let mut ribbon = MaRibbon::new(&[2, 3, 5], MaType::Sma).unwrap();
+        assert_eq!(ribbon.periods(), &[2, 3, 5]);
+        assert_eq!(ribbon.ma_type(), MaType::Sma);
+
+        let falling: Vec<f64> = (0..8).map(|i| 100.0 - i as f64).collect();
+        let values = Indicator::<f64, MaRibbonResult>::calculate(&mut ribbon, &falling).unwrap();
+        assert_eq!(values.len(), 4);
+        assert!(values.iter().all(|r| r.alignment == -1.0));
+
+        // An old spike still lifts the longest average only.
+        let tangled = [1.0, 5.0, 1.0, 1.0, 1.0];
+        let last = Indicator::<f64, MaRibbonResult>::calculate(&mut ribbon, &tangled)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(last.values, vec![1.0, 1.0, 1.8]);
+        assert_eq!(last.alignment, -0.5);
+
+        let flat = Indicator::<f64, MaRibbonResult>::calculate(&mut ribbon, &[1.0; 5]).unwrap();
+        assert_eq!(flat[0].alignment, 0.0);
+    }
+
+    #[test]
+    fn test_invalid_periods() {
+        assert!(MaRibbon::new(&[10], MaType::Ema).is_err());
+        assert!(MaRibbon::new(&[10, 10], MaType::Ema).is_err());
+        assert!(MaRibbon::new(&[20, 10], MaType::Ema).is_err());
+        assert!(MaRibbon::new(&[0, 10], MaType::Ema).is_err());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::indicators::trend::{Dema, Ema, EmaInit, Sma, Wma};
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Moving-average family used by indicators with a configurable average.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::MaType;
+/// use rsta::indicators::volatility::BollingerBands;
+///
+/// let bb = BollingerBands::with_ma(20, 2.0, MaType::Ema).unwrap();
+/// assert_eq!(bb.ma_type(), MaType::Ema);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaType {
+    /// Simple moving average.
+    #[default]
+    Sma,
+    /// Exponential moving average, seeded with the first value.
+    Ema,
+    /// Linearly weighted moving average.
+    Wma,
+    /// Double exponential moving average, `2 * EMA - EMA(EMA)`.
+    Dema,
+}
+
+/// A moving average of any [`MaType`] behind one streaming interface.
+#[derive(Debug, Clone)]
+pub(crate) enum MovingAverage {
+    Sma(Sma),
+    Ema(Ema),
+    Wma(Wma),
+    Dema(Dema),
+}
+
+impl MovingAverage {
+    pub(crate) fn new(ma_type: MaType, period: usize) -> Result<Self, IndicatorError> {
+        Ok(match ma_type {
+            MaType::Sma => Self::Sma(Sma::new(period)?),
+            MaType::Ema => Self::Ema(Ema::new(period)?),
+            MaType::Wma => Self::Wma(Wma::new(period)?),
+            MaType::Dema => Self::Dema(Dema::new(period)?),
+        })
+    }
+
+    /// Apply `init` to the exponential averages; SMA and WMA ignore it.
+    pub(crate) fn with_init(self, init: EmaInit) -> Self {
+        match self {
+            Self::Ema(ma) => Self::Ema(ma.with_init(init)),
+            Self::Dema(ma) => Self::Dema(ma.with_init(init)),
+            other => other,
+        }
+    }
+
+    /// Number of leading inputs that produce no output.
+    pub(crate) fn offset(&self) -> usize {
+        match self {
+            Self::Sma(ma) => Indicator::<f64, f64>::output_offset(ma),
+            Self::Ema(ma) => Indicator::<f64, f64>::output_offset(ma),
+            Self::Wma(ma) => Indicator::<f64, f64>::output_offset(ma),
+            Self::Dema(ma) => Indicator::<f64, f64>::output_offset(ma),
+        }
+    }
+
+    /// Feed one value, returning the average once it is available.
+    pub(crate) fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        match self {
+            Self::Sma(ma) => Indicator::<f64, f64>::next(ma, value),
+            Self::Ema(ma) => Indicator::<f64, f64>::next(ma, value),
+            Self::Wma(ma) => Indicator::<f64, f64>::next(ma, value),
+            Self::Dema(ma) => Indicator::<f64, f64>::next(ma, value),
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        match self {
+            Self::Sma(ma) => ma.reset_state(),
+            Self::Ema(ma) => ma.reset_state(),
+            Self::Wma(ma) => ma.reset_state(),
+            Self::Dema(ma) => ma.reset_state(),
+        }
+    }
+}
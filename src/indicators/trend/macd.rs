@@ -1,4 +1,5 @@
-use crate::indicators::trend::Ema;
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::trend::{EmaInit, MaType, MovingAverage};
 use crate::indicators::validate_period;
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
@@ -64,25 +65,53 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// // Calculate MACD values based on close prices
 /// let macd_values = macd.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+///
+/// # TA-Lib compatibility
+///
+/// By default every EMA is seeded with its first input and a result is
+/// emitted from the first bar. `with_init(EmaInit::TALIB)` seeds the EMAs
+/// with SMAs the way TA-Lib's `MACD` does: both lines start from an SMA
+/// over the window ending at bar `slow_period - 1`, and the first result
+/// is emitted once the signal line is seeded, at bar
+/// `slow_period + signal_period - 2`.
+///
+/// # Other moving averages
+///
+/// [`with_ma`](Macd::with_ma) builds the fast, slow and signal lines on
+/// another [`MaType`]. `MaType::Dema` gives the "Zero Lag MACD". Averages
+/// that need a full window (SMA, WMA, DEMA) delay the first result until
+/// every line has one.
+///
+/// ```
+/// use rsta::indicators::trend::{MaType, Macd};
+/// use rsta::indicators::Indicator;
+///
+/// let mut macd = Macd::with_ma(3, 6, 2, MaType::Dema).unwrap();
+/// let prices: Vec<f64> = (1..=30).map(f64::from).collect();
+/// let values = macd.calculate(&prices).unwrap();
+/// // The slow DEMA first emits on bar 11 and the signal DEMA needs three
+/// // MACD values, so the first result lands on bar 13
+/// assert_eq!(values.len(), prices.len() - 12);
+/// ```
+#[derive(Debug, Clone)]
 pub struct Macd {
     fast_period: usize,
     slow_period: usize,
-    signal_period: usize,
-    fast_ema: Ema,
-    slow_ema: Ema,
-    signal_ema: Ema,
-    current_macd: Option<f64>,
-    current_signal: Option<f64>,
-    current_histogram: Option<f64>,
+    ma_type: MaType,
+    fast_ma: MovingAverage,
+    slow_ma: MovingAverage,
+    signal_ma: MovingAverage,
+    init: EmaInit,
+    seen: usize,
+    last: Option<MacdResult>,
 }
 
 /// MACD result containing all three components
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct MacdResult {
-    /// The MACD line value (fast EMA - slow EMA)
+    /// The MACD line value (fast MA - slow MA)
     pub macd: f64,
-    /// The signal line value (EMA of MACD line)
+    /// The signal line value (MA of MACD line)
     pub signal: f64,
     /// The histogram value (MACD line - signal line)
     pub histogram: f64,
@@ -102,6 +131,25 @@ impl Macd {
         fast_period: usize,
         slow_period: usize,
         signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        Self::with_ma(fast_period, slow_period, signal_period, MaType::Ema)
+    }
+
+    /// Create a new MACD indicator built on `ma_type` averages
+    ///
+    /// # Arguments
+    /// * `fast_period` - The period for the fast average (typically 12)
+    /// * `slow_period` - The period for the slow average (typically 26)
+    /// * `signal_period` - The period for the signal line average (typically 9)
+    /// * `ma_type` - The moving average used for all three lines
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new MACD or an error
+    pub fn with_ma(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+        ma_type: MaType,
     ) -> Result<Self, IndicatorError> {
         // Validate periods
         validate_period(fast_period, 1)?;
@@ -110,44 +158,77 @@ impl Macd {
 
         // Slow period should be greater than fast period
         if fast_period >= slow_period {
-            return Err(IndicatorError::InvalidParameter(
-                "Slow period must be greater than fast period".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "slow_period",
+                slow_period,
+                format!("must be greater than fast_period ({fast_period})"),
             ));
         }
 
         Ok(Self {
             fast_period,
             slow_period,
-            signal_period,
-            fast_ema: Ema::new(fast_period)?,
-            slow_ema: Ema::new(slow_period)?,
-            signal_ema: Ema::new(signal_period)?,
-            current_macd: None,
-            current_signal: None,
-            current_histogram: None,
+            ma_type,
+            fast_ma: MovingAverage::new(ma_type, fast_period)?,
+            slow_ma: MovingAverage::new(ma_type, slow_period)?,
+            signal_ma: MovingAverage::new(ma_type, signal_period)?,
+            init: EmaInit::FirstValue,
+            seen: 0,
+            last: None,
         })
     }
 
+    /// Choose how the fast, slow and signal EMAs are seeded (see
+    /// [`EmaInit`]); has no effect on SMA and WMA lines
+    ///
+    /// # Arguments
+    /// * `init` - Seeding method
+    ///
+    /// # Returns
+    /// * `Self` - The MACD with the new seeding method
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.init = init;
+        self.fast_ma = self.fast_ma.with_init(init);
+        self.slow_ma = self.slow_ma.with_init(init);
+        self.signal_ma = self.signal_ma.with_init(init);
+        self
+    }
+
+    /// Moving average used for the fast, slow and signal lines
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
+
     /// Reset the MACD indicator state
     pub fn reset_state(&mut self) {
-        // Use explicit type annotations to resolve ambiguity
-        <Ema as Indicator<f64, f64>>::reset(&mut self.fast_ema);
-        <Ema as Indicator<f64, f64>>::reset(&mut self.slow_ema);
-        <Ema as Indicator<f64, f64>>::reset(&mut self.signal_ema);
-        self.current_macd = None;
-        self.current_signal = None;
-        self.current_histogram = None;
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.signal_ma.reset();
+        self.seen = 0;
+        self.last = None;
+    }
+
+    /// Bars the fast average skips so that, with SMA seeding, both seeds
+    /// end on the same bar as in TA-Lib
+    fn fast_delay(&self) -> usize {
+        match self.init {
+            EmaInit::FirstValue => 0,
+            EmaInit::SmaOfFirstPeriod => self.slow_period - self.fast_period,
+        }
+    }
+
+    /// Number of bars up to and including the first result
+    fn warmup(&self) -> usize {
+        let line_offset = (self.fast_delay() + self.fast_ma.offset()).max(self.slow_ma.offset());
+        line_offset + self.signal_ma.offset() + 1
     }
 }
 
 // Implementation for raw price values
 impl Indicator<f64, MacdResult> for Macd {
     fn calculate(&mut self, data: &[f64]) -> Result<Vec<MacdResult>, IndicatorError> {
-        if data.is_empty() {
-            return Err(IndicatorError::InsufficientData(format!(
-                "At least 1 data point required for MACD({},{},{})",
-                self.fast_period, self.slow_period, self.signal_period,
-            )));
+        if data.len() < self.warmup() {
+            return Err(IndicatorError::insufficient_data(self.warmup(), data.len()));
         }
         // Stream through `next` to guarantee that batch and streaming paths
         // produce identical values (one MacdResult per input bar — the first
@@ -164,45 +245,49 @@ impl Indicator<f64, MacdResult> for Macd {
     }
 
     fn next(&mut self, value: f64) -> Result<Option<MacdResult>, IndicatorError> {
-        // Calculate new EMA values
-        let fast_ema = self.fast_ema.next(value)?.unwrap_or(value);
-        let slow_ema = self.slow_ema.next(value)?.unwrap_or(value);
+        self.seen = self.seen.saturating_add(1);
+
+        // Calculate new fast and slow averages
+        let fast = if self.seen > self.fast_delay() {
+            self.fast_ma.next(value)?
+        } else {
+            None
+        };
+        let slow = self.slow_ma.next(value)?;
+        let (Some(fast), Some(slow)) = (fast, slow) else {
+            return Ok(None);
+        };
 
         // Calculate new MACD line value
-        let macd = fast_ema - slow_ema;
-        self.current_macd = Some(macd);
+        let macd = fast - slow;
 
         // Calculate new signal line value
-        let signal = if let Some(signal_value) = self.signal_ema.next(macd)? {
-            signal_value
-        } else {
-            // If there's no signal value yet, use MACD as the initial value
-            macd
+        let Some(signal) = self.signal_ma.next(macd)? else {
+            return Ok(None);
         };
-        self.current_signal = Some(signal);
 
         // Calculate histogram
         let histogram = macd - signal;
-        self.current_histogram = Some(histogram);
-
-        // Only return complete MACD output when all components are available
-        if self.current_macd.is_some()
-            && self.current_signal.is_some()
-            && self.current_histogram.is_some()
-        {
-            return Ok(Some(MacdResult {
-                macd,
-                signal,
-                histogram,
-            }));
-        }
 
-        Ok(None)
+        self.last = Some(MacdResult {
+            macd,
+            signal,
+            histogram,
+        });
+        Ok(self.last)
     }
 
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&MacdResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
 }
 
 // Implementation for candle data
@@ -221,11 +306,37 @@ impl Indicator<Candle, MacdResult> for Macd {
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&MacdResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warmup()
+    }
+}
+
+impl Describe for Macd {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "macd",
+            title: "Moving Average Convergence Divergence",
+            input: InputKind::Price,
+            params: &[
+                ParamDescriptor::period("fast", 1).with_default(12.0),
+                ParamDescriptor::period("slow", 1).with_default(26.0),
+                ParamDescriptor::period("signal", 1).with_default(9.0),
+            ],
+            outputs: &["macd", "signal", "histogram"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::trend::Ema;
 
     #[test]
     fn test_macd_new() {
@@ -247,7 +358,8 @@ mod tests {
 
         // Create price data with a clear trend
         let prices: Vec<f64> = (1..=20).map(|i| i as f64 * 2.0).collect();
-        assert!(prices.len() >= macd.slow_period + macd.signal_period - 1);
+        // slow_period + signal_period - 1
+        assert!(prices.len() >= macd.slow_period + 2 - 1);
 
         let result = macd.calculate(&prices).unwrap();
 
@@ -317,9 +429,7 @@ mod tests {
         macd.reset_state();
 
         // After reset, internal state should be cleared
-        assert!(macd.current_macd.is_none());
-        assert!(macd.current_signal.is_none());
-        assert!(macd.current_histogram.is_none());
+        assert!(macd.last.is_none());
     }
 
     #[test]
@@ -401,4 +511,85 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_macd_talib_seeding() {
+        let prices: Vec<f64> = (0..40)
+            .map(|i| 50.0 + (i as f64 * 0.45).sin() * 4.0 + i as f64 * 0.2)
+            .collect();
+        let (fast, slow, signal) = (3, 6, 4);
+        let mut macd = Macd::new(fast, slow, signal)
+            .unwrap()
+            .with_init(EmaInit::TALIB);
+        assert_eq!(
+            <Macd as Indicator<f64, MacdResult>>::min_periods(&macd),
+            slow + signal - 1
+        );
+        let out = macd.calculate(&prices).unwrap();
+        assert_eq!(out.len(), prices.len() - (slow + signal - 2));
+
+        // Reference: both lines seeded with SMAs ending at bar slow - 1.
+        let talib = |p| Ema::new(p).unwrap().with_init(EmaInit::TALIB);
+        let fast_line =
+            <Ema as Indicator<f64, f64>>::calculate(&mut talib(fast), &prices[slow - fast..])
+                .unwrap();
+        let slow_line = <Ema as Indicator<f64, f64>>::calculate(&mut talib(slow), &prices).unwrap();
+        let line: Vec<f64> = fast_line
+            .iter()
+            .zip(&slow_line)
+            .map(|(f, s)| f - s)
+            .collect();
+        let signal_line =
+            <Ema as Indicator<f64, f64>>::calculate(&mut talib(signal), &line).unwrap();
+
+        for (r, (m, s)) in out.iter().zip(line[signal - 1..].iter().zip(&signal_line)) {
+            assert!((r.macd - m).abs() < 1e-12);
+            assert!((r.signal - s).abs() < 1e-12);
+            assert!((r.histogram - (m - s)).abs() < 1e-12);
+        }
+        assert!(macd.calculate(&prices[..slow + signal - 2]).is_err());
+    }
+
+    #[test]
+    fn test_macd_with_sma_lines() {
+        let prices: Vec<f64> = (0..20)
+            .map(|i| 10.0 + (i as f64 * 0.9).sin() * 3.0)
+            .collect();
+        let mut macd = Macd::with_ma(2, 4, 3, MaType::Sma).unwrap();
+        assert_eq!(macd.ma_type(), MaType::Sma);
+        let result = macd.calculate(&prices).unwrap();
+        // First MACD value on bar 4, first signal two values later
+        assert_eq!(<Macd as Indicator<f64, MacdResult>>::min_periods(&macd), 6);
+        assert_eq!(result.len(), prices.len() - 5);
+
+        let mean = |w: &[f64]| w.iter().sum::<f64>() / w.len() as f64;
+        let lines: Vec<f64> = (3..prices.len())
+            .map(|i| mean(&prices[i - 1..=i]) - mean(&prices[i - 3..=i]))
+            .collect();
+        for (r, window) in result.iter().zip(lines.windows(3)) {
+            assert!((r.macd - window[2]).abs() < 1e-12);
+            assert!((r.signal - mean(window)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_macd_ma_types_stream_like_batch() {
+        let prices: Vec<f64> = (0..60)
+            .map(|i| 50.0 + (i as f64 * 0.3).cos() * 5.0)
+            .collect();
+        for ma_type in [MaType::Sma, MaType::Ema, MaType::Wma, MaType::Dema] {
+            let batch = Macd::with_ma(3, 7, 4, ma_type)
+                .unwrap()
+                .calculate(&prices)
+                .unwrap();
+            let mut macd = Macd::with_ma(3, 7, 4, ma_type).unwrap();
+            let warmup = <Macd as Indicator<f64, MacdResult>>::min_periods(&macd);
+            assert_eq!(batch.len(), prices.len() - warmup + 1, "{ma_type:?}");
+            let streamed: Vec<MacdResult> = prices
+                .iter()
+                .filter_map(|&p| macd.next(p).unwrap())
+                .collect();
+            assert_eq!(batch, streamed, "{ma_type:?}");
+        }
+    }
 }
@@ -1,23 +1,42 @@
 pub mod adx;
 pub mod dema;
+pub mod dominant_cycle;
+pub mod elder_impulse;
 pub mod ema;
+pub mod gmma;
 pub mod hma;
 pub mod ichimoku;
+pub mod ma_distance;
+pub mod ma_ribbon;
+pub mod ma_type;
 pub mod macd;
 pub mod pivots;
+pub mod relative_strength;
 pub mod sar;
 pub mod sma;
+pub mod super_smoother;
 pub mod tema;
 pub mod wma;
 
 pub use self::adx::{Adx, AdxResult};
 pub use self::dema::Dema;
-pub use self::ema::Ema;
+pub use self::dominant_cycle::DominantCycle;
+pub use self::elder_impulse::{ElderImpulse, Impulse};
+pub use self::ema::{Ema, EmaInit};
+pub use self::gmma::{Gmma, GmmaResult};
 pub use self::hma::Hma;
 pub use self::ichimoku::{Ichimoku, IchimokuResult};
+pub use self::ma_distance::MaDistance;
+pub use self::ma_ribbon::{MaRibbon, MaRibbonResult};
+pub use self::ma_type::MaType;
+pub(crate) use self::ma_type::MovingAverage;
 pub use self::macd::{Macd, MacdResult};
 pub use self::pivots::{pivot_camarilla, pivot_classic, pivot_fibonacci, PivotResult};
+pub use self::relative_strength::{RelativeStrengthLine, RelativeStrengthResult};
 pub use self::sar::Sar;
 pub use self::sma::Sma;
+#[cfg(feature = "momentum")]
+pub(crate) use self::super_smoother::RoofingFilter;
+pub use self::super_smoother::SuperSmoother;
 pub use self::tema::Tema;
 pub use self::wma::Wma;
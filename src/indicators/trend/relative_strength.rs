@@ -0,0 +1,338 @@
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::signals::cross::{Cross, CrossoverTracker};
+
+/// Where the relative strength line is rebased to 100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Base {
+    Raw,
+    FirstBar,
+    Since(u64),
+}
+
+/// Relative strength line value with its moving average
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelativeStrengthResult {
+    /// Asset price over benchmark price, rebased to 100 when normalized
+    pub rs: f64,
+    /// Moving average of the RS line
+    pub ma: f64,
+    /// Crossing of the RS line over its moving average on this bar
+    pub cross: Option<Cross>,
+}
+
+/// Comparative relative strength against a benchmark.
+///
+/// The RS line is the asset price divided by the benchmark price: it rises
+/// while the asset outperforms and falls while it lags, whatever the
+/// direction of the market. A moving average of the line (SMA by default)
+/// smooths it, and crossings of the line over its average flag a change of
+/// leadership, e.g. for sector rotation.
+///
+/// Input is `(asset, benchmark)` prices aligned bar for bar, or
+/// `(asset, benchmark)` candles on their closes. The raw ratio depends on
+/// the price levels of the two symbols; [`normalized`](Self::normalized)
+/// rebases the line to 100 on the first bar, and
+/// [`normalized_since`](Self::normalized_since) on the first candle at or
+/// after a start date, so lines of several symbols compare directly.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::RelativeStrengthLine;
+/// use rsta::indicators::Indicator;
+/// use rsta::signals::cross::Cross;
+///
+/// // The asset lags the benchmark, then outperforms it
+/// let asset = [50.0, 49.0, 48.0, 48.0, 52.0, 56.0];
+/// let benchmark = [100.0, 100.0, 100.0, 100.0, 100.0, 100.0];
+/// let pairs: Vec<(f64, f64)> = asset.iter().copied().zip(benchmark).collect();
+///
+/// let mut rs = RelativeStrengthLine::new(3).unwrap().normalized();
+/// let values = rs.calculate(&pairs).unwrap();
+/// assert_eq!(values.len(), 4);
+/// assert!((values[0].rs - 96.0).abs() < 1e-12);
+/// assert_eq!(values[2].cross, Some(Cross::Above));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RelativeStrengthLine {
+    ma_period: usize,
+    ma_type: MaType,
+    ma: MovingAverage,
+    base: Base,
+    base_ratio: Option<f64>,
+    crossover: CrossoverTracker,
+    rs: Option<f64>,
+    index: usize,
+    last: Option<RelativeStrengthResult>,
+}
+
+impl RelativeStrengthLine {
+    /// Create a new relative strength line
+    ///
+    /// # Arguments
+    /// * `ma_period` - Period of the moving average of the RS line (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(ma_period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self {
+            ma_period,
+            ma_type: MaType::Sma,
+            ma: MovingAverage::new(MaType::Sma, ma_period)?,
+            base: Base::Raw,
+            base_ratio: None,
+            crossover: CrossoverTracker::new(),
+            rs: None,
+            index: 0,
+            last: None,
+        })
+    }
+
+    /// Use a different moving average for the RS line
+    ///
+    /// # Arguments
+    /// * `ma_type` - Kind of moving average
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The indicator with the new average or an error
+    pub fn with_ma(mut self, ma_type: MaType) -> Result<Self, IndicatorError> {
+        self.ma = MovingAverage::new(ma_type, self.ma_period)?;
+        self.ma_type = ma_type;
+        Ok(self)
+    }
+
+    /// Rebase the line to 100 on the first bar.
+    pub fn normalized(mut self) -> Self {
+        self.base = Base::FirstBar;
+        self
+    }
+
+    /// Rebase the line to 100 on the first candle whose timestamp is at or
+    /// after `start`; earlier candles produce no value. Prices without
+    /// timestamps (`(f64, f64)` input) are rebased on their first bar.
+    pub fn normalized_since(mut self, start: u64) -> Self {
+        self.base = Base::Since(start);
+        self
+    }
+
+    /// Moving-average type in use
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
+
+    /// Latest RS line value, available before the moving average warms up
+    pub fn rs(&self) -> Option<f64> {
+        self.rs
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.ma.reset();
+        self.base_ratio = None;
+        self.crossover.reset_state();
+        self.rs = None;
+        self.index = 0;
+        self.last = None;
+    }
+
+    fn step(
+        &mut self,
+        asset: f64,
+        benchmark: f64,
+    ) -> Result<Option<RelativeStrengthResult>, IndicatorError> {
+        let index = self.index;
+        self.index += 1;
+        if benchmark == 0.0 {
+            return Err(IndicatorError::division_by_zero("benchmark price", index));
+        }
+        let ratio = asset / benchmark;
+        let rs = match self.base {
+            Base::Raw => ratio,
+            Base::FirstBar | Base::Since(_) => {
+                let base = *self.base_ratio.get_or_insert(ratio);
+                if base == 0.0 {
+                    return Err(IndicatorError::division_by_zero(
+                        "base bar asset price",
+                        index,
+                    ));
+                }
+                ratio / base * 100.0
+            }
+        };
+        self.rs = Some(rs);
+        let Some(ma) = self.ma.next(rs)? else {
+            return Ok(None);
+        };
+        self.last = Some(RelativeStrengthResult {
+            rs,
+            ma,
+            cross: self.crossover.update(rs, ma),
+        });
+        Ok(self.last)
+    }
+
+    fn step_candles(
+        &mut self,
+        (asset, benchmark): (Candle, Candle),
+    ) -> Result<Option<RelativeStrengthResult>, IndicatorError> {
+        if asset.timestamp != benchmark.timestamp {
+            return Err(IndicatorError::invalid_parameter(
+                "benchmark",
+                benchmark.timestamp,
+                format!(
+                    "timestamp must match the asset candle ({})",
+                    asset.timestamp
+                ),
+            ));
+        }
+        if matches!(self.base, Base::Since(start) if asset.timestamp < start) {
+            return Ok(None);
+        }
+        self.step(asset.close, benchmark.close)
+    }
+}
+
+impl Indicator<(f64, f64), RelativeStrengthResult> for RelativeStrengthLine {
+    fn calculate(
+        &mut self,
+        data: &[(f64, f64)],
+    ) -> Result<Vec<RelativeStrengthResult>, IndicatorError> {
+        validate_data_length(data, self.ma.offset() + 1)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - self.ma.offset());
+        for &(asset, benchmark) in data {
+            if let Some(value) = self.step(asset, benchmark)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(
+        &mut self,
+        (asset, benchmark): (f64, f64),
+    ) -> Result<Option<RelativeStrengthResult>, IndicatorError> {
+        self.step(asset, benchmark)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&RelativeStrengthResult> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RelativeStrengthLine"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.ma_period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.ma.offset() + 1
+    }
+}
+
+impl Indicator<(Candle, Candle), RelativeStrengthResult> for RelativeStrengthLine {
+    fn calculate(
+        &mut self,
+        data: &[(Candle, Candle)],
+    ) -> Result<Vec<RelativeStrengthResult>, IndicatorError> {
+        validate_data_length(data, self.ma.offset() + 1)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len());
+        for &pair in data {
+            if let Some(value) = self.step_candles(pair)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(
+        &mut self,
+        value: (Candle, Candle),
+    ) -> Result<Option<RelativeStrengthResult>, IndicatorError> {
+        self.step_candles(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&RelativeStrengthResult> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RelativeStrengthLine"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.ma_period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.ma.offset() + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(timestamp: u64, close: f64) -> Candle {
+        Candle {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_raw_ratio_and_ma() {
+        let pairs = [(10.0, 20.0), (12.0, 20.0), (14.0, 20.0), (9.0, 30.0)];
+        let mut rs = RelativeStrengthLine::new(2).unwrap();
+        let values = Indicator::<(f64, f64), _>::calculate(&mut rs, &pairs).unwrap();
+        let ratios: Vec<f64> = values.iter().map(|v| v.rs).collect();
+        assert_eq!(ratios, vec![0.6, 0.7, 0.3]);
+        assert!((values[0].ma - 0.55).abs() < 1e-12);
+        assert_eq!(values[1].cross, None);
+        assert_eq!(values[2].cross, Some(Cross::Below));
+        assert_eq!(rs.rs(), Some(0.3));
+
+        let mut ema = RelativeStrengthLine::new(2)
+            .unwrap()
+            .with_ma(MaType::Ema)
+            .unwrap();
+        assert_eq!(ema.ma_type(), MaType::Ema);
+        assert!(Indicator::<(f64, f64), _>::next(&mut ema, (1.0, 0.0)).is_err());
+        assert!(RelativeStrengthLine::new(0).is_err());
+    }
+
+    #[test]
+    fn test_normalized_since_start_date() {
+        let pairs: Vec<(Candle, Candle)> =
+            [(40.0, 80.0), (50.0, 100.0), (55.0, 100.0), (60.0, 100.0)]
+                .iter()
+                .enumerate()
+                .map(|(i, &(a, b))| (candle(i as u64 * 10, a), candle(i as u64 * 10, b)))
+                .collect();
+        let mut rs = RelativeStrengthLine::new(1).unwrap().normalized_since(10);
+        let values = Indicator::<(Candle, Candle), _>::calculate(&mut rs, &pairs).unwrap();
+        let lines: Vec<f64> = values.iter().map(|v| v.rs).collect();
+        assert_eq!(lines.len(), 3);
+        assert!((lines[0] - 100.0).abs() < 1e-12);
+        assert!((lines[2] - 120.0).abs() < 1e-12);
+
+        let misaligned = (candle(0, 1.0), candle(1, 1.0));
+        assert!(Indicator::<(Candle, Candle), _>::next(&mut rs, misaligned).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::validate_data_length;
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
@@ -41,7 +42,7 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// let values = sar.calculate(&candles).unwrap();
 /// assert!(!values.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Sar {
     af_start: f64,
     af_step: f64,
@@ -58,6 +59,8 @@ pub struct Sar {
     prev_low: f64,
     /// `0` = uninitialised, `1` = seed candle ingested, `>= 2` = active.
     seen: usize,
+    /// Last emitted SAR value.
+    last: Option<f64>,
 }
 
 impl Sar {
@@ -67,15 +70,27 @@ impl Sar {
     /// Returns `IndicatorError::InvalidParameter` if any value is `<= 0`,
     /// or if `af_start > af_max` or `af_step > af_max`.
     pub fn new(af_start: f64, af_step: f64, af_max: f64) -> Result<Self, IndicatorError> {
-        if af_start <= 0.0 || af_step <= 0.0 || af_max <= 0.0 {
-            return Err(IndicatorError::InvalidParameter(
-                "Parabolic SAR factors must be positive".to_string(),
-            ));
+        for (name, value) in [
+            ("af_start", af_start),
+            ("af_step", af_step),
+            ("af_max", af_max),
+        ] {
+            if value <= 0.0 {
+                return Err(IndicatorError::invalid_parameter(
+                    name,
+                    value,
+                    "must be positive",
+                ));
+            }
         }
-        if af_start > af_max || af_step > af_max {
-            return Err(IndicatorError::InvalidParameter(
-                "af_start and af_step must be <= af_max".to_string(),
-            ));
+        for (name, value) in [("af_start", af_start), ("af_step", af_step)] {
+            if value > af_max {
+                return Err(IndicatorError::invalid_parameter(
+                    name,
+                    value,
+                    format!("must be <= af_max ({af_max})"),
+                ));
+            }
         }
         Ok(Self {
             af_start,
@@ -88,6 +103,7 @@ impl Sar {
             prev_high: 0.0,
             prev_low: 0.0,
             seen: 0,
+            last: None,
         })
     }
 
@@ -105,6 +121,7 @@ impl Sar {
         self.prev_high = 0.0;
         self.prev_low = 0.0;
         self.seen = 0;
+        self.last = None;
     }
 
     fn step(&mut self, candle: Candle) -> Option<f64> {
@@ -130,6 +147,7 @@ impl Sar {
             self.af = self.af_start;
             // Emit this bar's SAR (which was seeded for entry into the bar).
             let out = self.sar;
+            self.last = Some(out);
             // Update for next bar.
             self.advance(candle);
             return Some(out);
@@ -138,6 +156,7 @@ impl Sar {
         // Steady-state: emit the SAR computed for the current bar (already in
         // self.sar) then advance for the next.
         let out = self.sar;
+        self.last = Some(out);
         // Detect reversal: price has crossed the SAR.
         let reversed = if self.long {
             candle.low < self.sar
@@ -214,9 +233,34 @@ impl Indicator<Candle, f64> for Sar {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Sar"
     }
+
+    fn min_periods(&self) -> usize {
+        2
+    }
+}
+
+impl Describe for Sar {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "sar",
+            title: "Parabolic SAR",
+            input: InputKind::Candle,
+            params: &[
+                ParamDescriptor::positive("af_start").with_default(0.02),
+                ParamDescriptor::positive("af_step").with_default(0.02),
+                ParamDescriptor::positive("af_max").with_default(0.2),
+            ],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
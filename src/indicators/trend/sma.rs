@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{calculate_sma, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
@@ -52,11 +53,12 @@ use std::collections::VecDeque;
 /// assert_eq!(sma_values.len(), 6);
 /// assert_eq!(sma_values[0], 12.0);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Sma {
     period: usize,
     buffer: VecDeque<f64>,
     sum: f64,
+    last: Option<f64>,
 }
 
 impl Sma {
@@ -72,8 +74,9 @@ impl Sma {
 
         Ok(Self {
             period,
-            buffer: VecDeque::with_capacity(period),
+            buffer: VecDeque::with_capacity(period + 1),
             sum: 0.0,
+            last: None,
         })
     }
 
@@ -81,13 +84,22 @@ impl Sma {
     pub fn reset_state(&mut self) {
         self.buffer.clear();
         self.sum = 0.0;
+        self.last = None;
     }
 }
 
 // Implementation for raw price values
 impl Indicator<f64, f64> for Sma {
     fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
-        calculate_sma(data, self.period)
+        let result = calculate_sma(data, self.period)?;
+
+        // Keep the last window so `next()` continues from the end of `data`
+        self.reset_state();
+        self.buffer.extend(&data[data.len() - self.period..]);
+        self.sum = self.buffer.iter().sum();
+        self.last = result.last().copied();
+
+        Ok(result)
     }
 
     fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
@@ -104,12 +116,21 @@ impl Indicator<f64, f64> for Sma {
             return Ok(None);
         }
 
-        Ok(Some(self.sum / self.period as f64))
+        self.last = Some(self.sum / self.period as f64);
+        Ok(self.last)
     }
 
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
 }
 
 // Implementation for candle data
@@ -117,7 +138,7 @@ impl Indicator<Candle, f64> for Sma {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
         // Extract close prices from candles
         let close_prices: Vec<f64> = data.iter().map(|candle| candle.close).collect();
-        calculate_sma(&close_prices, self.period)
+        Indicator::<f64, f64>::calculate(self, &close_prices)
     }
 
     fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
@@ -136,12 +157,34 @@ impl Indicator<Candle, f64> for Sma {
             return Ok(None);
         }
 
-        Ok(Some(self.sum / self.period as f64))
+        self.last = Some(self.sum / self.period as f64);
+        Ok(self.last)
     }
 
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Sma {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "sma",
+            title: "Simple Moving Average",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,234 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Ehlers' SuperSmoother filter.
+///
+/// A two-pole Butterworth low-pass filter on the average of the last two
+/// inputs: it removes cycles shorter than `period` bars with far less lag
+/// than a moving average of the same length.
+///
+/// ```text
+/// a = exp(-√2·π / period),  c2 = 2a·cos(√2·π / period),  c3 = -a²
+/// c1 = 1 - c2 - c3
+/// out = c1 · (x + x[1]) / 2 + c2 · out[1] + c3 · out[2]
+/// ```
+///
+/// The first two outputs are the inputs themselves, so values come from
+/// the first bar.
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::SuperSmoother;
+/// use rsta::indicators::Indicator;
+///
+/// let mut smoother = SuperSmoother::new(10).unwrap();
+/// // Alternating noise around 100 is filtered out
+/// let prices: Vec<f64> = (0..60).map(|i| if i % 2 == 0 { 101.0 } else { 99.0 }).collect();
+/// let values = smoother.calculate(&prices).unwrap();
+/// assert!((values.last().unwrap() - 100.0).abs() < 0.01);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SuperSmoother {
+    period: usize,
+    c1: f64,
+    c2: f64,
+    c3: f64,
+    prev_input: Option<f64>,
+    prev: [Option<f64>; 2],
+}
+
+impl SuperSmoother {
+    /// Create a new SuperSmoother
+    ///
+    /// # Arguments
+    /// * `period` - Critical period in bars; shorter cycles are removed (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new SuperSmoother or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        let arg = SQRT_2 * PI / period as f64;
+        let a = (-arg).exp();
+        let c2 = 2.0 * a * arg.cos();
+        let c3 = -a * a;
+        Ok(Self {
+            period,
+            c1: 1.0 - c2 - c3,
+            c2,
+            c3,
+            prev_input: None,
+            prev: [None; 2],
+        })
+    }
+
+    /// Reset the filter state
+    pub fn reset_state(&mut self) {
+        self.prev_input = None;
+        self.prev = [None; 2];
+    }
+
+    /// Feed one value and return the filtered value.
+    pub(crate) fn step(&mut self, value: f64) -> f64 {
+        let out = match (self.prev_input, self.prev) {
+            (Some(x1), [Some(f1), Some(f2)]) => {
+                self.c1 * (value + x1) / 2.0 + self.c2 * f1 + self.c3 * f2
+            }
+            _ => value,
+        };
+        self.prev_input = Some(value);
+        self.prev = [Some(out), self.prev[0]];
+        out
+    }
+
+    fn last(&self) -> Option<&f64> {
+        self.prev[0].as_ref()
+    }
+}
+
+impl Indicator<f64, f64> for SuperSmoother {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, 1)?;
+        self.reset_state();
+        Ok(data.iter().map(|&value| self.step(value)).collect())
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(value)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last()
+    }
+
+    fn name(&self) -> &'static str {
+        "SuperSmoother"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+}
+
+impl Indicator<Candle, f64> for SuperSmoother {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        let closes: Vec<f64> = data.iter().map(|candle| candle.close).collect();
+        self.calculate(&closes)
+    }
+
+    fn next(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.next(candle.close)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last()
+    }
+
+    fn name(&self) -> &'static str {
+        "SuperSmoother"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+}
+
+/// Ehlers' roofing filter: a two-pole high-pass removing cycles longer
+/// than `high_pass` bars, followed by a [`SuperSmoother`] removing those
+/// shorter than `low_pass`. What is left oscillates around zero.
+#[cfg(feature = "momentum")]
+#[derive(Debug, Clone)]
+pub(crate) struct RoofingFilter {
+    k1: f64,
+    k2: f64,
+    k3: f64,
+    inputs: [Option<f64>; 2],
+    high_pass: [f64; 2],
+    smoother: SuperSmoother,
+}
+
+#[cfg(feature = "momentum")]
+impl RoofingFilter {
+    pub(crate) fn new(high_pass: usize, low_pass: usize) -> Result<Self, IndicatorError> {
+        validate_period(high_pass, 2)?;
+        let arg = SQRT_2 * PI / high_pass as f64;
+        let alpha = (arg.cos() + arg.sin() - 1.0) / arg.cos();
+        Ok(Self {
+            k1: (1.0 - alpha / 2.0).powi(2),
+            k2: 2.0 * (1.0 - alpha),
+            k3: -(1.0 - alpha).powi(2),
+            inputs: [None; 2],
+            high_pass: [0.0; 2],
+            smoother: SuperSmoother::new(low_pass)?,
+        })
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.inputs = [None; 2];
+        self.high_pass = [0.0; 2];
+        self.smoother.reset_state();
+    }
+
+    pub(crate) fn step(&mut self, value: f64) -> f64 {
+        let hp = match self.inputs {
+            [Some(x1), Some(x2)] => {
+                self.k1 * (value - 2.0 * x1 + x2)
+                    + self.k2 * self.high_pass[0]
+                    + self.k3 * self.high_pass[1]
+            }
+            _ => 0.0,
+        };
+        self.inputs = [Some(value), self.inputs[0]];
+        self.high_pass = [hp, self.high_pass[0]];
+        self.smoother.step(hp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_super_smoother_tracks_level_and_resets() {
+        assert!(SuperSmoother::new(1).is_err());
+        let mut smoother = SuperSmoother::new(10).unwrap();
+        let values = Indicator::<f64, f64>::calculate(&mut smoother, &[5.0; 40]).unwrap();
+        assert_eq!(values.len(), 40);
+        assert!(values.iter().all(|v| (v - 5.0).abs() < 1e-9));
+
+        // Unity gain: a step settles on the new level
+        let mut step: Vec<f64> = vec![0.0; 10];
+        step.extend([10.0; 60]);
+        let values = Indicator::<f64, f64>::calculate(&mut smoother, &step).unwrap();
+        assert!((values.last().unwrap() - 10.0).abs() < 1e-6);
+
+        smoother.reset_state();
+        assert!(Indicator::<f64, f64>::current(&smoother).is_none());
+    }
+
+    #[cfg(feature = "momentum")]
+    #[test]
+    fn test_roofing_filter_removes_trend() {
+        let mut roofing = RoofingFilter::new(48, 10).unwrap();
+        // Linear trend plus a 20-bar cycle
+        let out: Vec<f64> = (0..300)
+            .map(|i| {
+                let i = i as f64;
+                roofing.step(100.0 + 0.5 * i + (2.0 * PI * i / 20.0).sin())
+            })
+            .collect();
+        let tail = &out[200..];
+        let mean = tail.iter().sum::<f64>() / tail.len() as f64;
+        assert!(mean.abs() < 0.1, "mean {mean}");
+        assert!(tail.iter().any(|v| *v > 0.3) && tail.iter().any(|v| *v < -0.3));
+    }
+}
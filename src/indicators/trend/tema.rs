@@ -1,4 +1,5 @@
-use crate::indicators::trend::Ema;
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::trend::{Ema, EmaInit};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
@@ -8,13 +9,14 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// one's output. Even less lag than DEMA at the cost of more warmup.
 ///
 /// First emission appears at the `3 * period - 2`-th input.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Tema {
     period: usize,
     ema1: Ema,
     ema2: Ema,
     ema3: Ema,
     seen: usize,
+    last: Option<f64>,
 }
 
 impl Tema {
@@ -27,29 +29,45 @@ impl Tema {
             ema2: Ema::new(period)?,
             ema3: Ema::new(period)?,
             seen: 0,
+            last: None,
         })
     }
 
+    /// Choose how the inner EMAs are seeded (see [`EmaInit`]).
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.ema1 = self.ema1.with_init(init);
+        self.ema2 = self.ema2.with_init(init);
+        self.ema3 = self.ema3.with_init(init);
+        self
+    }
+
     /// Reset internal state.
     pub fn reset_state(&mut self) {
         self.ema1.reset_state();
         self.ema2.reset_state();
         self.ema3.reset_state();
         self.seen = 0;
+        self.last = None;
     }
 
     fn step(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
         self.seen += 1;
-        let e1 = <Ema as Indicator<f64, f64>>::next(&mut self.ema1, value)?
-            .expect("inner Ema always emits");
-        let e2 = <Ema as Indicator<f64, f64>>::next(&mut self.ema2, e1)?
-            .expect("inner Ema always emits");
-        let e3 = <Ema as Indicator<f64, f64>>::next(&mut self.ema3, e2)?
-            .expect("inner Ema always emits");
+        // SMA-seeded EMAs stay silent during their own warm-up; chained,
+        // they first emit exactly at the TEMA warm-up boundary.
+        let Some(e1) = <Ema as Indicator<f64, f64>>::next(&mut self.ema1, value)? else {
+            return Ok(None);
+        };
+        let Some(e2) = <Ema as Indicator<f64, f64>>::next(&mut self.ema2, e1)? else {
+            return Ok(None);
+        };
+        let Some(e3) = <Ema as Indicator<f64, f64>>::next(&mut self.ema3, e2)? else {
+            return Ok(None);
+        };
         if self.seen < 3 * self.period - 2 {
             return Ok(None);
         }
-        Ok(Some(3.0 * e1 - 3.0 * e2 + e3))
+        self.last = Some(3.0 * e1 - 3.0 * e2 + e3);
+        Ok(self.last)
     }
 }
 
@@ -74,6 +92,10 @@ impl Indicator<f64, f64> for Tema {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Tema"
     }
@@ -81,6 +103,10 @@ impl Indicator<f64, f64> for Tema {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        3 * self.period - 2
+    }
 }
 
 impl Indicator<Candle, f64> for Tema {
@@ -104,6 +130,10 @@ impl Indicator<Candle, f64> for Tema {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Tema"
     }
@@ -111,6 +141,23 @@ impl Indicator<Candle, f64> for Tema {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        3 * self.period - 2
+    }
+}
+
+impl Describe for Tema {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "tema",
+            title: "Triple Exponential Moving Average",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
@@ -17,10 +18,11 @@ use std::collections::VecDeque;
 /// let out = wma.calculate(&[1.0_f64, 2.0, 3.0]).unwrap();
 /// assert!((out[0] - (14.0 / 6.0)).abs() < 1e-12);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Wma {
     period: usize,
     buffer: VecDeque<f64>,
+    last: Option<f64>,
 }
 
 impl Wma {
@@ -29,13 +31,15 @@ impl Wma {
         validate_period(period, 1)?;
         Ok(Self {
             period,
-            buffer: VecDeque::with_capacity(period),
+            buffer: VecDeque::with_capacity(period + 1),
+            last: None,
         })
     }
 
     /// Reset internal state without dropping the configured period.
     pub fn reset_state(&mut self) {
         self.buffer.clear();
+        self.last = None;
     }
 
     fn weighted(buffer: &VecDeque<f64>, period: usize) -> f64 {
@@ -57,7 +61,8 @@ impl Wma {
         if self.buffer.len() < self.period {
             return None;
         }
-        Some(Self::weighted(&self.buffer, self.period))
+        self.last = Some(Self::weighted(&self.buffer, self.period));
+        self.last
     }
 }
 
@@ -82,6 +87,10 @@ impl Indicator<f64, f64> for Wma {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Wma"
     }
@@ -89,6 +98,10 @@ impl Indicator<f64, f64> for Wma {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
 }
 
 impl Indicator<Candle, f64> for Wma {
@@ -112,6 +125,10 @@ impl Indicator<Candle, f64> for Wma {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Wma"
     }
@@ -119,6 +136,23 @@ impl Indicator<Candle, f64> for Wma {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Wma {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "wma",
+            title: "Weighted Moving Average",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 //! Utility functions for technical indicators
 
+use std::collections::VecDeque;
+
 use crate::indicators::IndicatorError;
 
 /// Validate period parameter
@@ -12,10 +14,11 @@ use crate::indicators::IndicatorError;
 /// * `Result<(), IndicatorError>` - Ok if valid, Err otherwise
 pub fn validate_period(period: usize, min_period: usize) -> Result<(), IndicatorError> {
     if period < min_period {
-        return Err(IndicatorError::InvalidParameter(format!(
-            "Period must be greater than or equal to {}",
-            min_period
-        )));
+        return Err(IndicatorError::invalid_parameter(
+            "period",
+            period,
+            format!("must be at least {}", min_period),
+        ));
     }
     Ok(())
 }
@@ -30,10 +33,7 @@ pub fn validate_period(period: usize, min_period: usize) -> Result<(), Indicator
 /// * `Result<(), IndicatorError>` - Ok if valid, Err otherwise
 pub fn validate_data_length<T>(data: &[T], min_length: usize) -> Result<(), IndicatorError> {
     if data.len() < min_length {
-        return Err(IndicatorError::InsufficientData(format!(
-            "Input data length must be at least {}",
-            min_length
-        )));
+        return Err(IndicatorError::insufficient_data(min_length, data.len()));
     }
     Ok(())
 }
@@ -108,9 +108,7 @@ pub fn calculate_ema(data: &[f64], period: usize) -> Result<Vec<f64>, IndicatorE
 /// * `Result<f64, IndicatorError>` - Standard deviation value
 pub fn standard_deviation(data: &[f64], mean: Option<f64>) -> Result<f64, IndicatorError> {
     if data.is_empty() {
-        return Err(IndicatorError::InsufficientData(
-            "Cannot calculate standard deviation of empty dataset".to_string(),
-        ));
+        return Err(IndicatorError::insufficient_data(1, 0));
     }
 
     if data.len() == 1 {
@@ -150,6 +148,368 @@ pub fn rate_of_change(data: &[f64], period: usize) -> Result<Vec<f64>, Indicator
     Ok(result)
 }
 
+/// Sliding-window extreme kept in a monotonic deque.
+///
+/// The deque holds `(index, value)` pairs whose values are strictly
+/// decreasing (for a maximum) from front to back, so the front is the
+/// window's extreme and each value is pushed and popped at most once:
+/// amortized O(1) per update instead of O(period).
+#[derive(Debug, Clone)]
+struct MonotonicWindow<const MAX: bool> {
+    period: usize,
+    seen: usize,
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl<const MAX: bool> MonotonicWindow<MAX> {
+    fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self {
+            period,
+            seen: 0,
+            deque: VecDeque::with_capacity(period),
+        })
+    }
+
+    fn push(&mut self, value: f64) -> Option<f64> {
+        let index = self.seen;
+        self.seen += 1;
+        if let Some(&(front, _)) = self.deque.front() {
+            if front + self.period <= index {
+                self.deque.pop_front();
+            }
+        }
+        // NaN takes a slot in the window but never becomes the extreme,
+        // matching `f64::max` / `f64::min`.
+        if !value.is_nan() {
+            while let Some(&(_, last)) = self.deque.back() {
+                let dominated = if MAX { last <= value } else { last >= value };
+                if !dominated {
+                    break;
+                }
+                self.deque.pop_back();
+            }
+            self.deque.push_back((index, value));
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.deque.front().map(|&(_, v)| v)
+    }
+
+    fn reset(&mut self) {
+        self.seen = 0;
+        self.deque.clear();
+    }
+}
+
+/// Rolling maximum over the last `period` values.
+///
+/// Amortized O(1) per [`push`](Self::push) regardless of the period,
+/// using a monotonic deque. NaN inputs occupy a slot in the window but are
+/// ignored when picking the maximum.
+///
+/// ```
+/// use rsta::indicators::utils::RollingMax;
+///
+/// let mut w = RollingMax::new(3).unwrap();
+/// for v in [4.0, 1.0, 3.0, 2.0, 5.0] {
+///     w.push(v);
+/// }
+/// assert!(w.is_full());
+/// assert_eq!(w.value(), Some(5.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMax(MonotonicWindow<true>);
+
+impl RollingMax {
+    /// Create a window of `period` values (must be at least 1).
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self(MonotonicWindow::new(period)?))
+    }
+
+    /// Window length.
+    pub fn period(&self) -> usize {
+        self.0.period
+    }
+
+    /// Add a value, evicting the oldest once the window is full, and
+    /// return the maximum of the window.
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.0.push(value)
+    }
+
+    /// Current maximum, `None` if the window holds no comparable value.
+    pub fn value(&self) -> Option<f64> {
+        self.0.value()
+    }
+
+    /// `true` once `period` values have been pushed.
+    pub fn is_full(&self) -> bool {
+        self.0.seen >= self.0.period
+    }
+
+    /// Clear the window.
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Rolling minimum over the last `period` values.
+///
+/// Amortized O(1) per [`push`](Self::push) regardless of the period,
+/// using a monotonic deque. NaN inputs occupy a slot in the window but are
+/// ignored when picking the minimum.
+///
+/// ```
+/// use rsta::indicators::utils::RollingMin;
+///
+/// let mut w = RollingMin::new(3).unwrap();
+/// for v in [4.0, 1.0, 3.0, 2.0, 5.0] {
+///     w.push(v);
+/// }
+/// assert!(w.is_full());
+/// assert_eq!(w.value(), Some(2.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingMin(MonotonicWindow<false>);
+
+impl RollingMin {
+    /// Create a window of `period` values (must be at least 1).
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        Ok(Self(MonotonicWindow::new(period)?))
+    }
+
+    /// Window length.
+    pub fn period(&self) -> usize {
+        self.0.period
+    }
+
+    /// Add a value, evicting the oldest once the window is full, and
+    /// return the minimum of the window.
+    pub fn push(&mut self, value: f64) -> Option<f64> {
+        self.0.push(value)
+    }
+
+    /// Current minimum, `None` if the window holds no comparable value.
+    pub fn value(&self) -> Option<f64> {
+        self.0.value()
+    }
+
+    /// `true` once `period` values have been pushed.
+    pub fn is_full(&self) -> bool {
+        self.0.seen >= self.0.period
+    }
+
+    /// Clear the window.
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Rolling mean and population variance over the last `period` values.
+///
+/// Updates are O(1): a sliding-window Welford update adds the new value and
+/// removes the evicted one, which stays accurate where a naive running
+/// sum of squares would cancel catastrophically. The statistics are
+/// recomputed from the window every so often (amortized O(1)) so rounding
+/// error cannot build up over very long streams, and whenever a
+/// non-finite value leaves the window.
+///
+/// ```
+/// use rsta::indicators::utils::RollingStats;
+///
+/// let mut w = RollingStats::new(3).unwrap();
+/// for v in [1.0, 2.0, 4.0, 6.0] {
+///     w.push(v);
+/// }
+/// assert_eq!(w.mean(), Some(4.0));
+/// assert!((w.variance().unwrap() - 8.0 / 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingStats {
+    period: usize,
+    values: VecDeque<f64>,
+    mean: f64,
+    m2: f64,
+    non_finite: usize,
+    stale: bool,
+    since_resync: usize,
+}
+
+impl RollingStats {
+    /// Create a window of `period` values (must be at least 1).
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self {
+            period,
+            values: VecDeque::with_capacity(period),
+            mean: 0.0,
+            m2: 0.0,
+            non_finite: 0,
+            stale: false,
+            since_resync: 0,
+        })
+    }
+
+    /// Window length.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Add a value, evicting the oldest once the window is full.
+    pub fn push(&mut self, value: f64) {
+        let evicted = if self.values.len() == self.period {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(value);
+        if !value.is_finite() {
+            self.non_finite += 1;
+        }
+        if evicted.is_some_and(|x| !x.is_finite()) {
+            self.non_finite -= 1;
+        }
+
+        if self.non_finite > 0 {
+            // NaN / inf poison the statistics; recompute once they are gone.
+            self.stale = true;
+            return;
+        }
+        self.since_resync += 1;
+        if self.stale || self.since_resync >= self.period.max(1024) {
+            self.resync();
+            return;
+        }
+
+        let n = self.values.len() as f64;
+        match evicted {
+            Some(old) => {
+                let old_mean = self.mean;
+                self.mean += (value - old) / n;
+                self.m2 += (value - old) * (value - self.mean + old - old_mean);
+            }
+            None => {
+                let delta = value - self.mean;
+                self.mean += delta / n;
+                self.m2 += delta * (value - self.mean);
+            }
+        }
+        self.m2 = self.m2.max(0.0);
+    }
+
+    /// Number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// `true` if no value has been pushed since the last reset.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// `true` once the window holds `period` values.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.period
+    }
+
+    /// Mean of the window, `None` when empty. NaN while the window holds a
+    /// non-finite value.
+    pub fn mean(&self) -> Option<f64> {
+        match (self.values.is_empty(), self.non_finite) {
+            (true, _) => None,
+            (false, 0) => Some(self.mean),
+            _ => Some(f64::NAN),
+        }
+    }
+
+    /// Population variance (`n` denominator) of the window.
+    pub fn variance(&self) -> Option<f64> {
+        match (self.values.is_empty(), self.non_finite) {
+            (true, _) => None,
+            (false, 0) => Some(self.m2 / self.values.len() as f64),
+            _ => Some(f64::NAN),
+        }
+    }
+
+    /// Population standard deviation of the window.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Clear the window.
+    pub fn reset(&mut self) {
+        self.values.clear();
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.non_finite = 0;
+        self.stale = false;
+        self.since_resync = 0;
+    }
+
+    fn resync(&mut self) {
+        let n = self.values.len() as f64;
+        self.mean = self.values.iter().sum::<f64>() / n;
+        self.m2 = self.values.iter().map(|x| (x - self.mean).powi(2)).sum();
+        self.stale = false;
+        self.since_resync = 0;
+    }
+}
+
+/// Running sum with Neumaier (improved Kahan) compensation.
+///
+/// Cumulative indicators add volumes for as long as the feed runs; a plain
+/// `f64` accumulator loses the low-order bits of every small term once the
+/// total is large. The compensation term keeps the error bounded
+/// independently of the number of updates.
+///
+/// ```
+/// use rsta::indicators::utils::KahanSum;
+///
+/// let mut sum = KahanSum::new();
+/// sum.add(1e16);
+/// for _ in 0..1_000 {
+///     sum.add(1.0);
+/// }
+/// sum.add(-1e16);
+/// assert_eq!(sum.value(), 1_000.0);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    /// An empty sum.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `value` to the sum.
+    pub fn add(&mut self, value: f64) {
+        let t = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - t) + value;
+        } else {
+            self.compensation += (value - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    /// The compensated total.
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+
+    /// Reset to zero.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,8 +522,8 @@ mod tests {
 
         let result = validate_period(4, 5);
         assert!(result.is_err());
-        if let Err(IndicatorError::InvalidParameter(msg)) = result {
-            assert!(msg.contains("5"));
+        if let Err(IndicatorError::InvalidParameter { constraint, .. }) = result {
+            assert!(constraint.contains("5"));
         } else {
             panic!("Expected InvalidParameter error");
         }
@@ -177,8 +537,8 @@ mod tests {
 
         let result = validate_data_length(&data, 6);
         assert!(result.is_err());
-        if let Err(IndicatorError::InsufficientData(msg)) = result {
-            assert!(msg.contains("6"));
+        if let Err(IndicatorError::InsufficientData { required, .. }) = result {
+            assert_eq!(required, 6);
         } else {
             panic!("Expected InsufficientData error");
         }
@@ -273,4 +633,86 @@ mod tests {
         let result = rate_of_change(&data, 6);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_rolling_extremes_match_brute_force() {
+        let data: Vec<f64> = (0..200)
+            .map(|i| ((i * 37 % 101) as f64 * 0.7).sin() * 10.0)
+            .collect();
+        for period in [1, 2, 5, 17] {
+            let mut hi = RollingMax::new(period).unwrap();
+            let mut lo = RollingMin::new(period).unwrap();
+            for (i, &v) in data.iter().enumerate() {
+                let window = &data[(i + 1).saturating_sub(period)..=i];
+                let max = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let min = window.iter().copied().fold(f64::INFINITY, f64::min);
+                assert_eq!(hi.push(v), Some(max));
+                assert_eq!(lo.push(v), Some(min));
+                assert_eq!(hi.is_full(), i + 1 >= period);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_extremes_nan_and_reset() {
+        assert!(RollingMax::new(0).is_err());
+        let mut hi = RollingMax::new(2).unwrap();
+        assert_eq!(hi.push(f64::NAN), None);
+        assert_eq!(hi.push(1.0), Some(1.0));
+        assert_eq!(hi.push(f64::NAN), Some(1.0));
+        assert_eq!(hi.push(f64::NAN), None);
+        hi.reset();
+        assert!(!hi.is_full());
+        assert_eq!(hi.value(), None);
+        assert_eq!(hi.period(), 2);
+    }
+
+    #[test]
+    fn test_rolling_stats_match_two_pass() {
+        let data: Vec<f64> = (0..3000)
+            .map(|i| 1e6 + ((i * 13 % 97) as f64 * 0.37).cos() * 5.0)
+            .collect();
+        let mut w = RollingStats::new(20).unwrap();
+        for (i, &v) in data.iter().enumerate() {
+            w.push(v);
+            let window = &data[(i + 1).saturating_sub(20)..=i];
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            assert!((w.mean().unwrap() - mean).abs() < 1e-8);
+            let sd = standard_deviation(window, Some(mean)).unwrap();
+            assert!((w.std_dev().unwrap() - sd).abs() < 1e-6);
+        }
+        assert!(w.is_full());
+    }
+
+    #[test]
+    fn test_rolling_stats_recover_after_nan() {
+        let mut w = RollingStats::new(2).unwrap();
+        assert_eq!(w.mean(), None);
+        w.push(1.0);
+        w.push(f64::NAN);
+        assert!(w.mean().unwrap().is_nan());
+        w.push(3.0);
+        assert!(w.variance().unwrap().is_nan());
+        w.push(5.0);
+        assert_eq!(w.mean(), Some(4.0));
+        assert_eq!(w.variance(), Some(1.0));
+        w.reset();
+        assert!(w.is_empty());
+    }
+
+    #[test]
+    fn test_kahan_sum_keeps_small_terms() {
+        let mut naive = 0.0;
+        let mut sum = KahanSum::new();
+        for i in 0..1_000_000 {
+            let v = if i == 0 { 1e12 } else { 0.1 };
+            naive += v;
+            sum.add(v);
+        }
+        let exact = 1e12 + 99_999.9;
+        assert!((sum.value() - exact).abs() < 1e-3);
+        assert!((naive - exact).abs() > (sum.value() - exact).abs());
+        sum.reset();
+        assert_eq!(sum.value(), 0.0);
+    }
 }
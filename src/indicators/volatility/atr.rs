@@ -1,9 +1,50 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::traits::Indicator;
 use crate::indicators::utils::validate_data_length;
 use crate::indicators::utils::validate_period;
 use crate::indicators::{Candle, IndicatorError};
 use std::collections::VecDeque;
 
+/// How [`Atr`] averages the true range
+///
+/// Every method seeds with the simple average of the first `period` true
+/// ranges, so the first ATR value is the same whichever is chosen. Data
+/// providers disagree on the smoothing that follows, which is why ATRs from
+/// different platforms rarely match to the cent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AtrSmoothing {
+    /// Wilder's smoothing, `atr = (atr * (period - 1) + tr) / period` — the
+    /// original definition, used by TA-Lib and TradingView's `ta.atr`.
+    #[default]
+    Wilder,
+    /// Exponential smoothing with `alpha = 2 / (period + 1)`.
+    Ema,
+    /// Simple moving average of the last `period` true ranges.
+    Sma,
+}
+
+/// Calculate the True Range of a candle
+///
+/// # Arguments
+/// * `candle` - Current price candle
+/// * `prev_close` - Previous candle's closing price (if available)
+///
+/// # Returns
+/// * `f64` - The greatest of high - low, |high - prev close| and |low - prev close|
+pub(crate) fn true_range(candle: &Candle, prev_close: Option<f64>) -> f64 {
+    let high_low = candle.high - candle.low;
+
+    match prev_close {
+        Some(prev_close) => {
+            let high_close = (candle.high - prev_close).abs();
+            let low_close = (candle.low - prev_close).abs();
+            high_low.max(high_close).max(low_close)
+        }
+        None => high_low,
+    }
+}
+
 /// Average True Range (Atr) indicator
 ///
 /// Measures market volatility by decomposing the entire range of an asset price for a period.
@@ -27,7 +68,9 @@ use std::collections::VecDeque;
 /// 3. Subsequent ATR values use Wilder's smoothing:
 ///    ATR = ((Previous ATR * (n-1)) + Current TR) / n
 ///
-/// where n is the period length
+/// where n is the period length. Step 3 can be switched to an EMA or SMA
+/// with [`with_smoothing`](Atr::with_smoothing); the unsmoothed series is
+/// available from [`TrueRange`].
 ///
 /// # Example
 ///
@@ -49,9 +92,10 @@ use std::collections::VecDeque;
 /// // Calculate ATR values
 /// let atr_values = atr.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Atr {
     period: usize,
+    smoothing: AtrSmoothing,
     prev_close: Option<f64>,
     current_atr: Option<f64>,
     tr_values: VecDeque<f64>,
@@ -70,45 +114,38 @@ impl Atr {
 
         Ok(Self {
             period,
+            smoothing: AtrSmoothing::Wilder,
             prev_close: None,
             current_atr: None,
-            tr_values: VecDeque::with_capacity(period),
+            tr_values: VecDeque::with_capacity(period + 1),
         })
     }
 
-    /// Calculate True Range for a single candle
+    /// Choose how the true range is averaged (see [`AtrSmoothing`])
     ///
     /// # Arguments
-    /// * `candle` - Current price candle
-    /// * `prev_close` - Previous candle's closing price (if available)
+    /// * `smoothing` - Averaging method
     ///
     /// # Returns
-    /// * `f64` - The True Range value
-    fn true_range(candle: &Candle, prev_close: Option<f64>) -> f64 {
-        let high_low = candle.high - candle.low;
-
-        match prev_close {
-            Some(prev_close) => {
-                let high_close = (candle.high - prev_close).abs();
-                let low_close = (candle.low - prev_close).abs();
-                high_low.max(high_close).max(low_close)
-            }
-            None => high_low,
-        }
+    /// * `Self` - The ATR with the new averaging method
+    pub fn with_smoothing(mut self, smoothing: AtrSmoothing) -> Self {
+        self.smoothing = smoothing;
+        self
     }
 
-    /// Calculate initial ATR value using simple moving average
-    ///
-    /// # Arguments
-    /// * `tr_values` - Slice of True Range values
-    ///
-    /// # Returns
-    /// * `f64` - The initial ATR value
-    fn initial_atr(tr_values: &[f64]) -> f64 {
-        tr_values.iter().sum::<f64>() / tr_values.len() as f64
+    /// Averaging method in use
+    pub fn smoothing(&self) -> AtrSmoothing {
+        self.smoothing
+    }
+
+    /// Reset the ATR indicator state
+    pub fn reset_state(&mut self) {
+        self.prev_close = None;
+        self.current_atr = None;
+        self.tr_values.clear();
     }
 
-    /// Apply Wilder's smoothing to calculate the next ATR value
+    /// Fold the latest true range into the previous ATR
     ///
     /// # Arguments
     /// * `prev_atr` - Previous ATR value
@@ -117,74 +154,150 @@ impl Atr {
     /// # Returns
     /// * `f64` - The smoothed ATR value
     fn smooth_atr(&self, prev_atr: f64, current_tr: f64) -> f64 {
-        ((prev_atr * (self.period - 1) as f64) + current_tr) / self.period as f64
+        let period = self.period as f64;
+        match self.smoothing {
+            AtrSmoothing::Wilder => (prev_atr * (period - 1.0) + current_tr) / period,
+            AtrSmoothing::Ema => {
+                let alpha = 2.0 / (period + 1.0);
+                current_tr * alpha + prev_atr * (1.0 - alpha)
+            }
+            AtrSmoothing::Sma => self.tr_values.iter().sum::<f64>() / period,
+        }
+    }
+
+    fn step(&mut self, candle: &Candle) -> Option<f64> {
+        let tr = true_range(candle, self.prev_close.replace(candle.close));
+        self.tr_values.push_back(tr);
+        if self.tr_values.len() > self.period {
+            self.tr_values.pop_front();
+        }
+        if self.tr_values.len() < self.period {
+            return None;
+        }
+
+        let atr = match self.current_atr {
+            Some(prev_atr) => self.smooth_atr(prev_atr, tr),
+            None => self.tr_values.iter().sum::<f64>() / self.period as f64,
+        };
+        self.current_atr = Some(atr);
+        Some(atr)
     }
 }
 
 impl Indicator<Candle, f64> for Atr {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
         validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|candle| self.step(candle)).collect())
+    }
 
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period + 1);
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(&value))
+    }
 
-        // Reset state
-        self.reset();
+    fn reset(&mut self) {
+        self.reset_state();
+    }
 
-        // Calculate True Range values
-        let mut tr_values = Vec::with_capacity(n);
-        let mut prev_close = None;
+    fn current(&self) -> Option<&f64> {
+        self.current_atr.as_ref()
+    }
 
-        for candle in data {
-            let tr = Self::true_range(candle, prev_close);
-            tr_values.push(tr);
-            prev_close = Some(candle.close);
-        }
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
 
-        // Calculate initial ATR using simple moving average
-        let initial_atr = Self::initial_atr(&tr_values[0..self.period]);
-        result.push(initial_atr);
-        let mut current_atr = initial_atr;
+/// True Range indicator
+///
+/// The unsmoothed input of [`Atr`]: the greatest of high - low,
+/// |high - previous close| and |low - previous close|. The first candle has
+/// no previous close, so its true range is simply high - low.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volatility::TrueRange;
+/// use rsta::indicators::Indicator;
+/// use rsta::Candle;
+///
+/// let candles = vec![
+///     Candle { timestamp: 0, open: 10.0, high: 12.0, low: 9.0, close: 11.0, volume: 1000.0 },
+///     Candle { timestamp: 1, open: 14.0, high: 15.0, low: 13.0, close: 14.0, volume: 1000.0 },
+/// ];
+///
+/// let mut tr = TrueRange::new();
+/// // Second candle gapped up: |15 - 11| beats 15 - 13
+/// assert_eq!(tr.calculate(&candles).unwrap(), vec![3.0, 4.0]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrueRange {
+    prev_close: Option<f64>,
+    last: Option<f64>,
+}
 
-        // Calculate subsequent ATR values using Wilder's smoothing
-        for tr in tr_values.iter().skip(self.period) {
-            current_atr = self.smooth_atr(current_atr, *tr);
-            result.push(current_atr);
-        }
+impl TrueRange {
+    /// Create a new True Range indicator
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        // Update state with the last values
-        self.prev_close = Some(data[n - 1].close);
-        self.current_atr = Some(current_atr);
-        self.tr_values = tr_values.into_iter().skip(n - self.period).collect();
+    /// Reset the True Range indicator state
+    pub fn reset_state(&mut self) {
+        self.prev_close = None;
+        self.last = None;
+    }
 
-        Ok(result)
+    fn step(&mut self, candle: &Candle) -> f64 {
+        let tr = true_range(candle, self.prev_close.replace(candle.close));
+        self.last = Some(tr);
+        tr
+    }
+}
+
+impl Indicator<Candle, f64> for TrueRange {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, 1)?;
+        self.reset_state();
+        Ok(data.iter().map(|candle| self.step(candle)).collect())
     }
 
     fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
-        let tr = Self::true_range(&value, self.prev_close);
-        self.tr_values.push_back(tr);
-        self.prev_close = Some(value.close);
+        Ok(Some(self.step(&value)))
+    }
 
-        if self.tr_values.len() > self.period {
-            self.tr_values.pop_front();
-        }
+    fn reset(&mut self) {
+        self.reset_state();
+    }
 
-        if self.tr_values.len() == self.period {
-            let atr = match self.current_atr {
-                Some(prev_atr) => self.smooth_atr(prev_atr, tr),
-                None => Self::initial_atr(self.tr_values.make_contiguous()),
-            };
-            self.current_atr = Some(atr);
-            Ok(Some(atr))
-        } else {
-            Ok(None)
-        }
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
     }
+}
 
-    fn reset(&mut self) {
-        self.prev_close = None;
-        self.current_atr = None;
-        self.tr_values.clear();
+impl Describe for Atr {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "atr",
+            title: "Average True Range",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
+}
+
+impl Describe for TrueRange {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "true_range",
+            title: "True Range",
+            input: InputKind::Candle,
+            params: &[],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -226,15 +339,15 @@ mod tests {
     fn test_true_range_calculation() {
         // Test case 1: Simple high-low range
         let candle1 = create_test_candle(0, 10.0, 15.0, 8.0, 12.0);
-        assert_float_eq(Atr::true_range(&candle1, None), 7.0); // high - low = 15 - 8 = 7
+        assert_float_eq(true_range(&candle1, None), 7.0); // high - low = 15 - 8 = 7
 
         // Test case 2: Previous close creates larger range
         let candle2 = create_test_candle(1, 11.0, 13.0, 9.0, 10.0);
-        assert_float_eq(Atr::true_range(&candle2, Some(12.0)), 4.0); // max(4, 3, 1)
+        assert_float_eq(true_range(&candle2, Some(12.0)), 4.0); // max(4, 3, 1)
 
         // Test case 3: Gap down scenario
         let candle3 = create_test_candle(2, 8.0, 9.0, 7.0, 8.0);
-        assert_float_eq(Atr::true_range(&candle3, Some(10.0)), 3.0); // max(2, 1, 3)
+        assert_float_eq(true_range(&candle3, Some(10.0)), 3.0); // max(2, 1, 3)
     }
 
     #[test]
@@ -318,14 +431,14 @@ mod tests {
 
         assert!(matches!(
             atr.calculate(&data),
-            Err(IndicatorError::InsufficientData(_))
+            Err(IndicatorError::InsufficientData { .. })
         ));
 
         // Test with empty data
         let data = vec![];
         assert!(matches!(
             atr.calculate(&data),
-            Err(IndicatorError::InsufficientData(_))
+            Err(IndicatorError::InsufficientData { .. })
         ));
     }
 
@@ -351,4 +464,73 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn test_atr_smoothing_methods() {
+        // True ranges: 2, 4, 6, 2 (no gaps, so TR = high - low)
+        let candles = vec![
+            create_test_candle(0, 10.0, 11.0, 9.0, 10.0),
+            create_test_candle(1, 10.0, 12.0, 8.0, 10.0),
+            create_test_candle(2, 10.0, 13.0, 7.0, 10.0),
+            create_test_candle(3, 10.0, 11.0, 9.0, 10.0),
+        ];
+        let run = |smoothing| {
+            Atr::new(2)
+                .unwrap()
+                .with_smoothing(smoothing)
+                .calculate(&candles)
+                .unwrap()
+        };
+
+        // All methods seed with the SMA of the first two true ranges (3)
+        let wilder = run(AtrSmoothing::Wilder);
+        assert_eq!(wilder, vec![3.0, 4.5, 3.25]);
+        let ema = run(AtrSmoothing::Ema);
+        // alpha = 2/3: 6 * 2/3 + 3 / 3 = 5, then 2 * 2/3 + 5 / 3 = 3
+        assert_float_eq(ema[1], 5.0);
+        assert_float_eq(ema[2], 3.0);
+        let sma = run(AtrSmoothing::Sma);
+        assert_eq!(sma, vec![3.0, 5.0, 4.0]);
+
+        assert_eq!(Atr::new(2).unwrap().smoothing(), AtrSmoothing::Wilder);
+    }
+
+    #[test]
+    fn test_atr_smoothing_next_matches_calculate() {
+        let candles: Vec<Candle> = (0..30)
+            .map(|i| {
+                let c = 50.0 + (i as f64 * 0.7).sin() * 4.0;
+                create_test_candle(i, c, c + 1.0 + (i % 3) as f64, c - 1.5, c + 0.3)
+            })
+            .collect();
+        for smoothing in [AtrSmoothing::Wilder, AtrSmoothing::Ema, AtrSmoothing::Sma] {
+            let batch = Atr::new(5)
+                .unwrap()
+                .with_smoothing(smoothing)
+                .calculate(&candles)
+                .unwrap();
+            let mut atr = Atr::new(5).unwrap().with_smoothing(smoothing);
+            let streamed: Vec<f64> = candles
+                .iter()
+                .filter_map(|c| atr.next(*c).unwrap())
+                .collect();
+            assert_eq!(batch, streamed, "{smoothing:?}");
+        }
+    }
+
+    #[test]
+    fn test_true_range_indicator() {
+        let candles = vec![
+            create_test_candle(0, 10.0, 15.0, 8.0, 12.0),
+            create_test_candle(1, 12.0, 16.0, 12.0, 14.0),
+            create_test_candle(2, 14.0, 15.0, 11.0, 11.0),
+        ];
+        let mut tr = TrueRange::new();
+        // 15 - 8; |16 - 12|; |11 - 14| < 15 - 11
+        assert_eq!(tr.calculate(&candles).unwrap(), vec![7.0, 4.0, 4.0]);
+
+        tr.reset();
+        assert_eq!(tr.next(candles[1]).unwrap(), Some(4.0));
+        assert!(tr.calculate(&[]).is_err());
+    }
 }
@@ -1,13 +1,13 @@
-use std::collections::VecDeque;
-
-use crate::indicators::utils::{calculate_sma, standard_deviation, validate_data_length};
-use crate::indicators::{validate_period, Candle, Indicator};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::{validate_data_length, RollingStats};
+use crate::indicators::{Candle, Indicator};
 use crate::IndicatorError;
 
 /// Bollinger Bands indicator result
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BollingerBandsResult {
-    /// Middle band (usually SMA)
+    /// Middle band (SMA unless built with another [`MaType`])
     pub middle: f64,
     /// Upper band (middle + k * standard deviation)
     pub upper: f64,
@@ -71,12 +71,33 @@ pub struct BollingerBandsResult {
 /// // Calculate Bollinger Bands values based on close prices
 /// let bb_values = bollinger.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+///
+/// # Other moving averages
+///
+/// [`with_ma`](BollingerBands::with_ma) builds the middle band on an EMA or
+/// WMA instead. The bands stay `k` standard deviations of the price window
+/// away from it, as on most charting platforms.
+///
+/// ```
+/// use rsta::indicators::trend::MaType;
+/// use rsta::indicators::volatility::BollingerBands;
+/// use rsta::indicators::Indicator;
+///
+/// let mut bb = BollingerBands::with_ma(3, 2.0, MaType::Wma).unwrap();
+/// let bands = bb.calculate(&[1.0, 2.0, 3.0]).unwrap();
+/// // WMA(1, 2, 3) = 14 / 6
+/// assert!((bands[0].middle - 14.0 / 6.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
 pub struct BollingerBands {
     period: usize,
     k: f64,
-    values: VecDeque<f64>,
-    sma: Option<f64>,
+    ma_type: MaType,
+    stats: RollingStats,
+    /// Middle-band average for non-SMA types; the SMA is the window mean
+    /// already tracked by `stats`.
+    basis: Option<MovingAverage>,
+    last: Option<BollingerBandsResult>,
 }
 
 impl BollingerBands {
@@ -89,112 +110,125 @@ impl BollingerBands {
     /// # Returns
     /// * `Result<Self, IndicatorError>` - A new BB or an error
     pub fn new(period: usize, k: f64) -> Result<Self, IndicatorError> {
-        validate_period(period, 1)?;
+        Self::with_ma(period, k, MaType::Sma)
+    }
+
+    /// Create a new BB indicator whose middle band is a `ma_type` average
+    ///
+    /// # Arguments
+    /// * `period` - The period for the average and the deviation (must be at least 1)
+    /// * `k` - The number of standard deviations for the bands (typical: 2.0)
+    /// * `ma_type` - The moving average used for the middle band
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new BB or an error
+    pub fn with_ma(period: usize, k: f64, ma_type: MaType) -> Result<Self, IndicatorError> {
+        let stats = RollingStats::new(period)?;
 
         if k <= 0.0 {
-            return Err(IndicatorError::InvalidParameter(
-                "Standard deviation multiplier must be positive".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "k",
+                k,
+                "must be positive",
             ));
         }
 
+        let basis = match ma_type {
+            MaType::Sma => None,
+            other => Some(MovingAverage::new(other, period)?),
+        };
+
         Ok(Self {
             period,
             k,
-            values: VecDeque::with_capacity(period),
-            sma: None,
+            ma_type,
+            stats,
+            basis,
+            last: None,
         })
     }
 
-    /// Calculate the SMA of values in the buffer
-    fn calculate_sma(&self) -> f64 {
-        self.values.iter().sum::<f64>() / self.values.len() as f64
+    /// Moving average used for the middle band
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
+
+    /// Inputs needed for the first bands: the deviation window, or longer
+    /// if the middle-band average warms up slower (a DEMA needs
+    /// `2 * period - 1`).
+    fn warm_up(&self) -> usize {
+        let basis = self.basis.as_ref().map_or(0, |basis| basis.offset() + 1);
+        self.period.max(basis)
     }
 
     /// Reset the Bollinger Bands indicator state
     pub fn reset_state(&mut self) {
-        self.values.clear();
-        self.sma = None;
+        self.stats.reset();
+        if let Some(basis) = &mut self.basis {
+            basis.reset();
+        }
+        self.last = None;
     }
 }
 
 impl Indicator<f64, BollingerBandsResult> for BollingerBands {
     fn calculate(&mut self, data: &[f64]) -> Result<Vec<BollingerBandsResult>, IndicatorError> {
-        validate_data_length(data, self.period)?;
-
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period + 1);
+        validate_data_length(data, self.warm_up())?;
 
-        // Reset state
         self.reset_state();
 
-        // Calculate SMA values
-        let sma_values = calculate_sma(data, self.period)?;
-
-        // Calculate Bollinger Bands for each period
-        for i in 0..sma_values.len() {
-            let period_data = &data[i..(i + self.period)];
-            let sma = sma_values[i];
-            let std_dev = standard_deviation(period_data, Some(sma))?;
-
-            let upper = sma + (self.k * std_dev);
-            let lower = sma - (self.k * std_dev);
-            let bandwidth = (upper - lower) / sma;
-
-            result.push(BollingerBandsResult {
-                middle: sma,
-                upper,
-                lower,
-                bandwidth,
-            });
-        }
-
-        // Update state with the last period
-        for value in data.iter().take(n).skip(n - self.period) {
-            self.values.push_back(*value);
+        let mut result = Vec::with_capacity(data.len() - self.warm_up() + 1);
+        for &value in data {
+            if let Some(bands) = self.next(value)? {
+                result.push(bands);
+            }
         }
-        self.sma = Some(self.calculate_sma());
-
         Ok(result)
     }
 
     fn next(&mut self, value: f64) -> Result<Option<BollingerBandsResult>, IndicatorError> {
-        self.values.push_back(value);
-
-        if self.values.len() > self.period {
-            self.values.pop_front();
+        self.stats.push(value);
+        let basis = match &mut self.basis {
+            Some(basis) => basis.next(value)?,
+            None => self.stats.mean(),
+        };
+        if !self.stats.is_full() {
+            return Ok(None);
         }
+        let (Some(middle), Some(std_dev)) = (basis, self.stats.std_dev()) else {
+            return Ok(None);
+        };
 
-        if self.values.len() == self.period {
-            let sma = self.calculate_sma();
-            let period_data: Vec<f64> = self.values.iter().cloned().collect();
-            let std_dev = standard_deviation(&period_data, Some(sma))?;
-
-            let upper = sma + (self.k * std_dev);
-            let lower = sma - (self.k * std_dev);
-            self.sma = Some(sma);
-
-            let bandwidth = (upper - lower) / sma;
-
-            Ok(Some(BollingerBandsResult {
-                middle: sma,
-                upper,
-                lower,
-                bandwidth,
-            }))
-        } else {
-            Ok(None)
-        }
+        let upper = middle + (self.k * std_dev);
+        let lower = middle - (self.k * std_dev);
+        let bandwidth = (upper - lower) / middle;
+
+        self.last = Some(BollingerBandsResult {
+            middle,
+            upper,
+            lower,
+            bandwidth,
+        });
+        Ok(self.last)
     }
 
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&BollingerBandsResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warm_up()
+    }
 }
 
 // Implementation for candle data
 impl Indicator<Candle, BollingerBandsResult> for BollingerBands {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<BollingerBandsResult>, IndicatorError> {
-        validate_data_length(data, self.period)?;
+        validate_data_length(data, self.warm_up())?;
 
         // Extract close prices from candles
         let close_prices: Vec<f64> = data.iter().map(|candle| candle.close).collect();
@@ -212,11 +246,36 @@ impl Indicator<Candle, BollingerBandsResult> for BollingerBands {
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&BollingerBandsResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.warm_up()
+    }
+}
+
+impl Describe for BollingerBands {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "bollinger",
+            title: "Bollinger Bands",
+            input: InputKind::Price,
+            params: &[
+                ParamDescriptor::period("period", 1).with_default(20.0),
+                ParamDescriptor::positive("k").with_default(2.0),
+            ],
+            outputs: &["upper", "middle", "lower"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::indicators::trend::{Dema, Ema, Wma};
 
     // BB Tests
     #[test]
@@ -477,4 +536,67 @@ mod tests {
         };
         assert_eq!(bb.next(candle4).unwrap(), None);
     }
+
+    #[test]
+    fn test_bollinger_bands_with_ma_middle_band() {
+        let prices = [10.0, 11.0, 13.0, 12.0, 15.0, 14.0, 16.0, 18.0];
+
+        for ma_type in [MaType::Ema, MaType::Wma] {
+            let mut bb = BollingerBands::with_ma(4, 2.0, ma_type).unwrap();
+            assert_eq!(bb.ma_type(), ma_type);
+            let bands = bb.calculate(&prices).unwrap();
+            assert_eq!(bands.len(), prices.len() - 3);
+
+            let middles: Vec<f64> = match ma_type {
+                MaType::Ema => Ema::new(4).unwrap().calculate(&prices).unwrap(),
+                _ => Wma::new(4).unwrap().calculate(&prices).unwrap(),
+            };
+            // The EMA emits from the first bar, the WMA once its window is full
+            let middles = &middles[middles.len() - bands.len()..];
+
+            let mut stats = RollingStats::new(4).unwrap();
+            let mut deviations = Vec::new();
+            for &price in &prices {
+                stats.push(price);
+                if stats.is_full() {
+                    deviations.push(stats.std_dev().unwrap());
+                }
+            }
+
+            for ((band, &middle), std_dev) in bands.iter().zip(middles).zip(deviations) {
+                assert!((band.middle - middle).abs() < 1e-10);
+                assert!((band.upper - (middle + 2.0 * std_dev)).abs() < 1e-10);
+                assert!((band.lower - (middle - 2.0 * std_dev)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bollinger_bands_dema_warm_up() {
+        let prices: Vec<f64> = (0..12).map(|i| 10.0 + (i as f64 * 0.5).sin()).collect();
+        let mut bb = BollingerBands::with_ma(4, 2.0, MaType::Dema).unwrap();
+        // The DEMA middle band needs 2 * 4 - 1 bars, longer than the window
+        assert_eq!(Indicator::<f64, _>::min_periods(&bb), 7);
+        assert_eq!(Indicator::<f64, _>::output_offset(&bb), 6);
+        assert!(matches!(
+            bb.calculate(&prices[..4]),
+            Err(IndicatorError::InsufficientData { .. })
+        ));
+
+        let bands = bb.calculate(&prices).unwrap();
+        assert_eq!(bands.len(), prices.len() - 6);
+        let middles = Dema::new(4).unwrap().calculate(&prices).unwrap();
+        assert_eq!(middles.len(), bands.len());
+        for (band, middle) in bands.iter().zip(middles) {
+            assert!((band.middle - middle).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_bollinger_bands_default_ma_is_sma() {
+        let bb = BollingerBands::new(20, 2.0).unwrap();
+        assert_eq!(bb.ma_type(), MaType::Sma);
+        assert!(BollingerBands::with_ma(0, 2.0, MaType::Ema).is_err());
+        assert!(BollingerBands::with_ma(20, 0.0, MaType::Wma).is_err());
+    }
 }
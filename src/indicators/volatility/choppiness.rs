@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::utils::{validate_data_length, validate_period, RollingMax, RollingMin};
+use crate::indicators::volatility::atr::true_range;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Choppiness Index (CHOP)
+///
+/// Compares the distance the price travelled bar by bar (the sum of true
+/// ranges) with the net range it covered over the last `period` candles.
+/// Values near 100 mean the market moved a lot without getting anywhere
+/// (choppy, ranging); values near 0 mean a directional move. The usual
+/// reading levels are 61.8 (choppy above) and 38.2 (trending below).
+///
+/// # Formula
+///
+/// ```text
+/// chop = 100 * log10(Σ TR / (highest high - lowest low)) / log10(period)
+/// ```
+///
+/// A window with no range gives 100.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volatility::Choppiness;
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// // A steady climb: each bar's range stacks onto the last.
+/// let candles: Vec<Candle> = (0..20).map(|i| Candle {
+///     timestamp: i, open: i as f64, high: i as f64 + 1.0,
+///     low: i as f64, close: i as f64 + 1.0, volume: 1.0,
+/// }).collect();
+/// let mut chop = Choppiness::new(14).unwrap();
+/// let values = chop.calculate(&candles).unwrap();
+/// assert_eq!(values.len(), 7);
+/// // Σ TR = 14, range = 14: perfectly directional.
+/// assert!(values[6].abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Choppiness {
+    period: usize,
+    prev_close: Option<f64>,
+    true_ranges: VecDeque<f64>,
+    highs: RollingMax,
+    lows: RollingMin,
+    last: Option<f64>,
+}
+
+impl Choppiness {
+    /// Create a new Choppiness Index
+    ///
+    /// # Arguments
+    /// * `period` - Number of candles in the window (must be at least 2, typically 14)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            prev_close: None,
+            true_ranges: VecDeque::with_capacity(period),
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            last: None,
+        })
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.prev_close = None;
+        self.true_ranges.clear();
+        self.highs.reset();
+        self.lows.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: &Candle) -> Option<f64> {
+        let tr = true_range(candle, self.prev_close.replace(candle.close));
+        if self.true_ranges.len() == self.period {
+            self.true_ranges.pop_front();
+        }
+        self.true_ranges.push_back(tr);
+        let high = self.highs.push(candle.high);
+        let low = self.lows.push(candle.low);
+        if !self.highs.is_full() {
+            return None;
+        }
+        let range = high? - low?;
+        let chop = if range > 0.0 {
+            let travelled: f64 = self.true_ranges.iter().sum();
+            100.0 * (travelled / range).log10() / (self.period as f64).log10()
+        } else {
+            100.0
+        };
+        self.last = Some(chop);
+        self.last
+    }
+}
+
+impl Indicator<Candle, f64> for Choppiness {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|candle| self.step(candle)).collect())
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(&value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Choppiness"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Choppiness {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "choppiness",
+            title: "Choppiness Index",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 2)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_sideways_market_is_choppy() {
+        // Alternating between two price levels: every bar spans the whole
+        // range, so the true ranges add up to `period` times the range.
+        let candles: Vec<Candle> = (0..10)
+            .map(|i| {
+                if i % 2 == 0 {
+                    candle(11.0, 9.0, 10.5)
+                } else {
+                    candle(11.0, 9.0, 9.5)
+                }
+            })
+            .collect();
+        let mut chop = Choppiness::new(4).unwrap();
+        let values = chop.calculate(&candles).unwrap();
+        assert_eq!(values.len(), 7);
+        assert!(values.iter().all(|v| (v - 100.0).abs() < 1e-12));
+
+        let flat = vec![candle(5.0, 5.0, 5.0); 4];
+        assert_eq!(chop.calculate(&flat).unwrap(), vec![100.0]);
+        assert!(Choppiness::new(1).is_err());
+    }
+}
@@ -1,6 +1,6 @@
-use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::utils::{validate_data_length, RollingMax, RollingMin};
 use crate::indicators::{Candle, Indicator, IndicatorError};
-use std::collections::VecDeque;
 
 /// Donchian Channels result: rolling max high, min low, and their midpoint.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -32,50 +32,45 @@ pub struct DonchianResult {
 /// let bands = dc.calculate(&candles).unwrap();
 /// assert!(!bands.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Donchian {
     period: usize,
-    buffer: VecDeque<(f64, f64)>,
+    highs: RollingMax,
+    lows: RollingMin,
+    last: Option<DonchianResult>,
 }
 
 impl Donchian {
     /// Create a new Donchian Channels indicator. Typical period is 20.
     pub fn new(period: usize) -> Result<Self, IndicatorError> {
-        validate_period(period, 1)?;
         Ok(Self {
             period,
-            buffer: VecDeque::with_capacity(period),
+            highs: RollingMax::new(period)?,
+            lows: RollingMin::new(period)?,
+            last: None,
         })
     }
 
     /// Reset internal state.
     pub fn reset_state(&mut self) {
-        self.buffer.clear();
+        self.highs.reset();
+        self.lows.reset();
+        self.last = None;
     }
 
     fn step(&mut self, value: Candle) -> Option<DonchianResult> {
-        self.buffer.push_back((value.high, value.low));
-        if self.buffer.len() > self.period {
-            self.buffer.pop_front();
-        }
-        if self.buffer.len() < self.period {
+        let upper = self.highs.push(value.high);
+        let lower = self.lows.push(value.low);
+        if !self.highs.is_full() {
             return None;
         }
-        let upper = self
-            .buffer
-            .iter()
-            .map(|&(h, _)| h)
-            .fold(f64::NEG_INFINITY, f64::max);
-        let lower = self
-            .buffer
-            .iter()
-            .map(|&(_, l)| l)
-            .fold(f64::INFINITY, f64::min);
-        Some(DonchianResult {
+        let (upper, lower) = (upper?, lower?);
+        self.last = Some(DonchianResult {
             upper,
             middle: (upper + lower) / 2.0,
             lower,
-        })
+        });
+        self.last
     }
 }
 
@@ -100,6 +95,10 @@ impl Indicator<Candle, DonchianResult> for Donchian {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&DonchianResult> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Donchian"
     }
@@ -107,6 +106,23 @@ impl Indicator<Candle, DonchianResult> for Donchian {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Donchian {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "donchian",
+            title: "Donchian Channels",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["upper", "middle", "lower"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
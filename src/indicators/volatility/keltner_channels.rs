@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 
-use crate::indicators::trend::{Ema, Sma};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::trend::{Ema, EmaInit};
 use crate::indicators::volatility::Atr;
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
@@ -57,14 +58,15 @@ pub struct KeltnerChannelsResult {
     pub bandwidth: f64,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct KeltnerChannels {
     ema_period: usize,
     atr_period: usize,
     multiplier: f64,
-    candle_buffer: VecDeque<Candle>,
-    current_ema: Option<f64>,
-    current_atr: Option<f64>,
+    ema: Ema,
+    atr: Atr,
+    seen: usize,
+    last: Option<KeltnerChannelsResult>,
 }
 
 impl KeltnerChannels {
@@ -84,20 +86,26 @@ impl KeltnerChannels {
     ) -> Result<Self, IndicatorError> {
         // Validate periods and multiplier
         if ema_period < 1 {
-            return Err(IndicatorError::InvalidParameter(
-                "EMA period must be at least 1".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "ema_period",
+                ema_period,
+                "must be at least 1",
             ));
         }
 
         if atr_period < 1 {
-            return Err(IndicatorError::InvalidParameter(
-                "ATR period must be at least 1".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "atr_period",
+                atr_period,
+                "must be at least 1",
             ));
         }
 
         if multiplier <= 0.0 {
-            return Err(IndicatorError::InvalidParameter(
-                "ATR multiplier must be positive".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be positive",
             ));
         }
 
@@ -105,122 +113,90 @@ impl KeltnerChannels {
             ema_period,
             atr_period,
             multiplier,
-            candle_buffer: VecDeque::with_capacity(ema_period.max(atr_period)),
-            current_ema: None,
-            current_atr: None,
+            ema: Ema::new(ema_period)?,
+            atr: Atr::new(atr_period)?,
+            seen: 0,
+            last: None,
         })
     }
+
+    /// Choose how the middle-band EMA is seeded (see [`EmaInit`])
+    ///
+    /// # Arguments
+    /// * `init` - Seeding method
+    ///
+    /// # Returns
+    /// * `Self` - The indicator with the new seeding method
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.ema = self.ema.with_init(init);
+        self
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.ema.reset_state();
+        Indicator::<Candle, f64>::reset(&mut self.atr);
+        self.seen = 0;
+        self.last = None;
+    }
 }
 
 impl Indicator<Candle, KeltnerChannelsResult> for KeltnerChannels {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<KeltnerChannelsResult>, IndicatorError> {
         // Need enough data for both EMA and ATR
-        let min_data_len = self.ema_period.max(self.atr_period);
+        let min_data_len = self.min_periods();
         if data.len() < min_data_len {
-            return Err(IndicatorError::InsufficientData(format!(
-                "Keltner Channels needs at least {} data points",
-                min_data_len
-            )));
+            return Err(IndicatorError::insufficient_data(min_data_len, data.len()));
         }
 
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - min_data_len + 1);
-
-        // Reset state
-        self.reset();
-
-        // Calculate EMA values using close prices
-        let mut ema = Ema::new(self.ema_period)?;
-        let close_prices: Vec<f64> = data.iter().map(|c| c.close).collect();
-        let ema_values = ema.calculate(&close_prices)?;
-
-        // Calculate ATR values
-        let mut atr = Atr::new(self.atr_period)?;
-        let atr_values = atr.calculate(data)?;
-
-        // Calculate Keltner Channels for each period where we have both EMA and ATR
-        let ema_offset = self.atr_period.saturating_sub(self.ema_period);
-        let atr_offset = self.ema_period.saturating_sub(self.atr_period);
-
-        for i in 0..atr_values.len().min(ema_values.len() - ema_offset) {
-            let ema = ema_values[i + ema_offset];
-            let atr = atr_values[i + atr_offset];
-
-            let upper = ema + (self.multiplier * atr);
-            let lower = ema - (self.multiplier * atr);
-            let bandwidth = (upper - lower) / ema;
-
-            result.push(KeltnerChannelsResult {
-                middle: ema,
-                upper,
-                lower,
-                bandwidth,
-            });
-        }
-
-        // Update state with the last values
-        self.current_ema = Some(*ema_values.last().unwrap());
-        self.current_atr = Some(*atr_values.last().unwrap());
-
-        for candle in data.iter().take(n).skip(n - min_data_len) {
-            self.candle_buffer.push_back(*candle);
+        // Batch output is the streaming output, so both paths agree exactly.
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - min_data_len + 1);
+        for candle in data {
+            if let Some(channels) = self.next(*candle)? {
+                result.push(channels);
+            }
         }
 
         Ok(result)
     }
 
     fn next(&mut self, value: Candle) -> Result<Option<KeltnerChannelsResult>, IndicatorError> {
-        self.candle_buffer.push_back(value);
+        // Both averages are updated on every candle, in O(1).
+        let ema = Indicator::<f64, f64>::next(&mut self.ema, value.close)?;
+        let atr = self.atr.next(value)?;
+        self.seen = self.seen.saturating_add(1);
 
-        let min_data_len = self.ema_period.max(self.atr_period);
-
-        if self.candle_buffer.len() > min_data_len {
-            self.candle_buffer.pop_front();
-        }
-
-        if self.candle_buffer.len() < min_data_len {
+        if self.seen < self.min_periods() {
             return Ok(None);
         }
-
-        // Real-time update of EMA
-        if let Some(current_ema) = self.current_ema {
-            let alpha = 2.0 / (self.ema_period as f64 + 1.0);
-            let new_ema = (value.close - current_ema) * alpha + current_ema;
-            self.current_ema = Some(new_ema);
-        } else {
-            // Initial EMA calculation
-            let mut ema = Ema::new(self.ema_period)?;
-            let close_prices: Vec<f64> = self.candle_buffer.iter().map(|c| c.close).collect();
-            let ema_values = ema.calculate(&close_prices)?;
-            self.current_ema = Some(*ema_values.last().unwrap());
-        }
-
-        // Real-time update of ATR
-        let mut atr = Atr::new(self.atr_period)?;
-        let candles: Vec<Candle> = self.candle_buffer.iter().cloned().collect();
-        let atr_values = atr.calculate(&candles)?;
-        self.current_atr = Some(*atr_values.last().unwrap());
-
-        // Create result
-        let ema = self.current_ema.unwrap();
-        let atr = self.current_atr.unwrap();
+        let (Some(ema), Some(atr)) = (ema, atr) else {
+            return Ok(None);
+        };
 
         let upper = ema + (self.multiplier * atr);
         let lower = ema - (self.multiplier * atr);
         let bandwidth = (upper - lower) / ema;
 
-        Ok(Some(KeltnerChannelsResult {
+        self.last = Some(KeltnerChannelsResult {
             middle: ema,
             upper,
             lower,
             bandwidth,
-        }))
+        });
+        Ok(self.last)
     }
 
     fn reset(&mut self) {
-        self.candle_buffer.clear();
-        self.current_ema = None;
-        self.current_atr = None;
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&KeltnerChannelsResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.ema_period.max(self.atr_period)
     }
 }
 
@@ -229,10 +205,13 @@ impl Indicator<Candle, KeltnerChannelsResult> for KeltnerChannels {
 pub struct KeltnerChannelsPrice {
     ema_period: usize,
     atr_period: usize,
-    price_buffer: VecDeque<f64>,
+    alpha: f64,
+    prev_price: Option<f64>,
     atr_buffer: VecDeque<f64>,
     current_ema: Option<f64>,
     current_atr: Option<f64>,
+    seen: usize,
+    last: Option<f64>,
 }
 
 impl KeltnerChannelsPrice {
@@ -247,54 +226,63 @@ impl KeltnerChannelsPrice {
     pub fn new(ema_period: usize, atr_period: usize) -> Result<Self, IndicatorError> {
         // Validate periods and multiplier
         if ema_period < 1 {
-            return Err(IndicatorError::InvalidParameter(
-                "EMA period must be at least 1".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "ema_period",
+                ema_period,
+                "must be at least 1",
             ));
         }
 
         if atr_period < 1 {
-            return Err(IndicatorError::InvalidParameter(
-                "ATR period must be at least 1".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "atr_period",
+                atr_period,
+                "must be at least 1",
             ));
         }
 
         Ok(Self {
             ema_period,
             atr_period,
-            price_buffer: VecDeque::with_capacity(ema_period),
+            alpha: 2.0 / (ema_period as f64 + 1.0),
+            prev_price: None,
             atr_buffer: VecDeque::with_capacity(atr_period),
             current_ema: None,
             current_atr: None,
+            seen: 0,
+            last: None,
         })
     }
 
-    /// Calculate ATR-like volatility from price data
-    /// This is a simplified version that uses price movement as volatility
-    fn calculate_volatility(&self, prices: &[f64]) -> Result<Vec<f64>, IndicatorError> {
-        if prices.len() < 2 {
-            return Err(IndicatorError::InsufficientData(
-                "Need at least 2 prices to calculate volatility".to_string(),
-            ));
-        }
-
-        // Calculate price changes as a simple volatility measure
-        let mut volatility = Vec::with_capacity(prices.len() - 1);
-        for i in 1..prices.len() {
-            let price_change = (prices[i] - prices[i - 1]).abs();
-            volatility.push(price_change);
+    /// Update the EMA and the price-change volatility with one price,
+    /// returning the middle band once past the warm-up.
+    fn step(&mut self, value: f64) -> Option<f64> {
+        let ema = match self.current_ema {
+            Some(prev) => value * self.alpha + prev * (1.0 - self.alpha),
+            None => value,
+        };
+        self.current_ema = Some(ema);
+
+        // Absolute price changes stand in for the true range; their SMA is
+        // the ATR equivalent.
+        if let Some(prev) = self.prev_price {
+            self.atr_buffer.push_back((value - prev).abs());
+            if self.atr_buffer.len() > self.atr_period {
+                self.atr_buffer.pop_front();
+            }
+            if self.atr_buffer.len() == self.atr_period {
+                self.current_atr =
+                    Some(self.atr_buffer.iter().sum::<f64>() / self.atr_period as f64);
+            }
         }
+        self.prev_price = Some(value);
 
-        // For simpler test cases, if all volatility values are 0, ensure we return at least some volatility
-        let all_zeros = volatility.iter().all(|&x| x == 0.0);
-        if all_zeros {
-            volatility = volatility.iter().map(|_| 0.001).collect();
+        self.seen = self.seen.saturating_add(1);
+        if self.seen <= self.output_offset() {
+            return None;
         }
-
-        // Calculate SMA of volatility as our ATR equivalent
-        let mut sma = Sma::new(self.atr_period)?;
-        let vol_sma = sma.calculate(&volatility)?;
-
-        Ok(vol_sma)
+        self.last = Some(ema);
+        self.last
     }
 }
 
@@ -303,110 +291,58 @@ impl Indicator<f64, f64> for KeltnerChannelsPrice {
         // Need enough data for both EMA and ATR-like volatility
         let min_data_len = self.ema_period + self.atr_period;
         if data.len() < min_data_len {
-            return Err(IndicatorError::InsufficientData(format!(
-                "Keltner Channels needs at least {} data points for price-only mode",
-                min_data_len
-            )));
+            return Err(IndicatorError::insufficient_data(min_data_len, data.len()));
         }
 
         // Reset state
         self.reset();
 
-        // Calculate EMA of prices
-        let mut ema = Ema::new(self.ema_period)?;
-        let ema_values = ema.calculate(data)?;
-
-        // Calculate volatility
-        let volatility = self.calculate_volatility(data)?;
-
-        // Calculate how many results we can produce
-        // For EMA we get values starting at index ema_period-1
-        // For volatility we need 1 extra point before starting, then atr_period points for the ATR
-        // This means we get results starting at max(ema_period-1, atr_period)
-        let first_valid_idx = (self.ema_period - 1).max(self.atr_period);
-        let result_len = data.len().saturating_sub(first_valid_idx);
-        let mut result = Vec::with_capacity(result_len);
-
-        // We only return the middle band (EMA) for the f64 implementation
-        for i in 0..result_len {
-            let ema_idx = i + first_valid_idx - (self.ema_period - 1);
-            if ema_idx < ema_values.len() {
-                result.push(ema_values[ema_idx]);
-            }
-        }
-
-        // Update state
-        self.current_ema = ema_values.last().cloned();
-        self.current_atr = volatility.last().cloned();
-
-        // Store the latest prices for next() method
-        for price in data.iter().rev().take(self.ema_period) {
-            self.price_buffer.push_front(*price);
-        }
-
-        // Store the latest volatility values
-        for vol in volatility.iter().rev().take(self.atr_period) {
-            self.atr_buffer.push_front(*vol);
-        }
-
-        Ok(result)
+        // We only return the middle band (EMA) for the f64 implementation.
+        // Results start at max(ema_period - 1, atr_period): the volatility
+        // needs one extra point before its first change.
+        Ok(data.iter().filter_map(|&v| self.step(v)).collect())
     }
 
     fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
-        // Add the new price to our buffer
-        self.price_buffer.push_back(value);
-        if self.price_buffer.len() > self.ema_period {
-            self.price_buffer.pop_front();
-        }
-
-        // We need at least 2 prices to calculate volatility and ema_period prices for EMA
-        if self.price_buffer.len() < 2 {
-            return Ok(None);
-        }
-
-        // Calculate new volatility value
-        let prev_price = self.price_buffer[self.price_buffer.len() - 2];
-        let new_volatility = (value - prev_price).abs();
-
-        // Add to ATR buffer
-        self.atr_buffer.push_back(new_volatility);
-        if self.atr_buffer.len() > self.atr_period {
-            self.atr_buffer.pop_front();
-        }
-
-        // Update EMA
-        if let Some(current_ema) = self.current_ema {
-            let alpha = 2.0 / (self.ema_period as f64 + 1.0);
-            let new_ema = value * alpha + current_ema * (1.0 - alpha);
-            self.current_ema = Some(new_ema);
-        } else if self.price_buffer.len() >= self.ema_period {
-            // Calculate initial EMA if we have enough data
-            let mut ema = Ema::new(self.ema_period)?;
-            let prices: Vec<f64> = self.price_buffer.iter().cloned().collect();
-            let ema_values = ema.calculate(&prices)?;
-            self.current_ema = Some(*ema_values.last().unwrap());
-        } else {
-            return Ok(None);
-        }
-
-        // Update ATR (using SMA of volatility as a simple ATR equivalent)
-        if self.atr_buffer.len() >= self.atr_period {
-            let sum: f64 = self.atr_buffer.iter().sum();
-            let new_atr = sum / self.atr_period as f64;
-            self.current_atr = Some(new_atr);
-        } else {
-            return Ok(None);
-        }
-
-        // Return middle band value (EMA)
-        Ok(self.current_ema)
+        Ok(self.step(value))
     }
 
     fn reset(&mut self) {
-        self.price_buffer.clear();
+        self.prev_price = None;
         self.atr_buffer.clear();
         self.current_ema = None;
         self.current_atr = None;
+        self.seen = 0;
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.ema_period + self.atr_period
+    }
+
+    fn output_offset(&self) -> usize {
+        (self.ema_period - 1).max(self.atr_period)
+    }
+}
+
+impl Describe for KeltnerChannels {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "keltner",
+            title: "Keltner Channels",
+            input: InputKind::Price,
+            params: &[
+                ParamDescriptor::period("ema_period", 1).with_default(20.0),
+                ParamDescriptor::period("atr_period", 1).with_default(20.0),
+                ParamDescriptor::positive("multiplier").with_default(2.0),
+            ],
+            outputs: &["upper", "middle", "lower"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -510,9 +446,59 @@ mod tests {
         // Reset
         kc.reset();
 
-        // After reset, we should have cleared state
-        assert!(kc.current_ema.is_none());
-        assert!(kc.current_atr.is_none());
+        // After reset, warm-up starts over
+        assert_eq!(kc.seen, 0);
+        assert!(kc.next(candle1).unwrap().is_none());
+    }
+
+    fn trending_candles(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 100.0 + (i as f64 * 0.4).sin() * 3.0 + i as f64 * 0.2;
+                Candle {
+                    timestamp: i as u64,
+                    open: c - 0.5,
+                    high: c + 1.0 + (i % 3) as f64 * 0.5,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 1000.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_keltner_channels_next_matches_calculate() {
+        let candles = trending_candles(40);
+        for (ema_period, atr_period) in [(5, 3), (3, 5), (4, 4)] {
+            let batch = KeltnerChannels::new(ema_period, atr_period, 2.0)
+                .unwrap()
+                .calculate(&candles)
+                .unwrap();
+            assert_eq!(batch.len(), candles.len() - ema_period.max(atr_period) + 1);
+
+            let mut kc = KeltnerChannels::new(ema_period, atr_period, 2.0).unwrap();
+            let streamed: Vec<_> = candles
+                .iter()
+                .filter_map(|c| kc.next(*c).unwrap())
+                .collect();
+            assert_eq!(streamed, batch);
+        }
+    }
+
+    #[test]
+    fn test_keltner_channels_aligns_ema_and_atr() {
+        // EMA period longer than the ATR period used to index past the ATR output.
+        let candles = trending_candles(12);
+        let result = KeltnerChannels::new(6, 2, 1.0)
+            .unwrap()
+            .calculate(&candles)
+            .unwrap();
+        let ema = Indicator::<Candle, f64>::calculate(&mut Ema::new(6).unwrap(), &candles).unwrap();
+        let atr = Atr::new(2).unwrap().calculate(&candles).unwrap();
+        let last = result.last().unwrap();
+        assert!((last.middle - ema.last().unwrap()).abs() < 1e-9);
+        assert!((last.upper - last.middle - atr.last().unwrap()).abs() < 1e-9);
     }
 
     // Tests for the f64 implementation
@@ -581,7 +567,7 @@ mod tests {
         // After reset, we should have cleared state
         assert!(kc.current_ema.is_none());
         assert!(kc.current_atr.is_none());
-        assert_eq!(kc.price_buffer.len(), 0);
+        assert!(kc.prev_price.is_none());
         assert_eq!(kc.atr_buffer.len(), 0);
     }
 }
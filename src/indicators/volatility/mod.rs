@@ -1,16 +1,21 @@
 //! Volatility indicators
 //!
 //! This module contains volatility indicators like ATR, Bollinger Bands,
-//! Keltner Channels, Donchian Channels, and Standard Deviation.
+//! Keltner Channels, Donchian Channels, Standard Deviation, the Choppiness
+//! Index and the volatility rank.
 
 pub mod atr;
 pub mod bb;
+pub mod choppiness;
 pub mod donchian;
 pub mod keltner_channels;
 pub mod std;
+pub mod volatility_rank;
 
-pub use self::atr::Atr;
+pub use self::atr::{Atr, AtrSmoothing, TrueRange};
 pub use self::bb::{BollingerBands, BollingerBandsResult};
+pub use self::choppiness::Choppiness;
 pub use self::donchian::{Donchian, DonchianResult};
 pub use self::keltner_channels::{KeltnerChannels, KeltnerChannelsResult};
 pub use self::std::Std;
+pub use self::volatility_rank::{RankMethod, VolatilityMeasure, VolatilityRank};
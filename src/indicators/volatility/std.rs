@@ -1,7 +1,7 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::traits::Indicator;
-use crate::indicators::utils::{standard_deviation, validate_data_length, validate_period};
+use crate::indicators::utils::{validate_data_length, RollingStats};
 use crate::indicators::{Candle, IndicatorError};
-use std::collections::VecDeque;
 
 /// Standard Deviation (Std) indicator
 ///
@@ -74,10 +74,11 @@ use std::collections::VecDeque;
 /// // Calculate Standard Deviation values based on close prices
 /// let std_values = std_dev.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Std {
     period: usize,
-    values: VecDeque<f64>,
+    stats: RollingStats,
+    last: Option<f64>,
 }
 
 impl Std {
@@ -89,17 +90,17 @@ impl Std {
     /// # Returns
     /// * `Result<Self, IndicatorError>` - A new STD instance or an error
     pub fn new(period: usize) -> Result<Self, IndicatorError> {
-        validate_period(period, 1)?;
-
         Ok(Self {
             period,
-            values: VecDeque::with_capacity(period),
+            stats: RollingStats::new(period)?,
+            last: None,
         })
     }
 
     /// Reset the Standard Deviation indicator state
     pub fn reset_state(&mut self) {
-        self.values.clear();
+        self.stats.reset();
+        self.last = None;
     }
 }
 
@@ -108,36 +109,22 @@ impl Indicator<f64, f64> for Std {
     fn calculate(&mut self, data: &[f64]) -> Result<Vec<f64>, IndicatorError> {
         validate_data_length(data, self.period)?;
 
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period + 1);
-
-        // Reset state
         self.reset_state();
 
-        // Calculate standard deviation for each period
-        for i in 0..=(n - self.period) {
-            let period_data = &data[i..(i + self.period)];
-            let std_dev = standard_deviation(period_data, None)?;
-            result.push(std_dev);
+        let mut result = Vec::with_capacity(data.len() - self.period + 1);
+        for &value in data {
+            if let Some(std_dev) = self.next(value)? {
+                result.push(std_dev);
+            }
         }
-
-        // Update state with the last period
-        for &value in data.iter().take(n).skip(n - self.period) {
-            self.values.push_back(value);
-        }
-
         Ok(result)
     }
 
     fn next(&mut self, value: f64) -> Result<Option<f64>, IndicatorError> {
-        self.values.push_back(value);
-
-        if self.values.len() > self.period {
-            self.values.pop_front();
-        }
-
-        if self.values.len() == self.period {
-            standard_deviation(self.values.make_contiguous(), None).map(Some)
+        self.stats.push(value);
+        if self.stats.is_full() {
+            self.last = self.stats.std_dev();
+            Ok(self.last)
         } else {
             Ok(None)
         }
@@ -146,6 +133,14 @@ impl Indicator<f64, f64> for Std {
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
 }
 
 // Implementation for candle data
@@ -169,6 +164,27 @@ impl Indicator<Candle, f64> for Std {
     fn reset(&mut self) {
         self.reset_state();
     }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Std {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "std",
+            title: "Standard Deviation",
+            input: InputKind::Price,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
@@ -294,14 +310,14 @@ mod tests {
         let data = vec![1.0, 2.0, 3.0];
         assert!(matches!(
             std.calculate(&data),
-            Err(IndicatorError::InsufficientData(_))
+            Err(IndicatorError::InsufficientData { .. })
         ));
 
         // Test with empty data
         let data: Vec<f64> = vec![];
         assert!(matches!(
             std.calculate(&data),
-            Err(IndicatorError::InsufficientData(_))
+            Err(IndicatorError::InsufficientData { .. })
         ));
 
         // Test valid period initialization
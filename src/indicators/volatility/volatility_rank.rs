@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+
+use crate::indicators::utils::{validate_data_length, validate_period, RollingStats};
+use crate::indicators::volatility::Atr;
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Volatility measured by [`VolatilityRank`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VolatilityMeasure {
+    /// Standard deviation of the last `period` close-to-close log returns.
+    #[default]
+    Realized,
+    /// Wilder's Average True Range over `period` candles.
+    Atr,
+}
+
+/// How [`VolatilityRank`] places the current volatility in its history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RankMethod {
+    /// Percentage of the previous values below the current one (IV
+    /// percentile).
+    #[default]
+    Percentile,
+    /// Position between the lowest and highest value of the window,
+    /// `(v - min) / (max - min) * 100` (IV rank).
+    Range,
+}
+
+/// Volatility rank / percentile
+///
+/// Reports where the current volatility — realized or ATR — sits within
+/// its own last `lookback` values, from 0 (the calmest) to 100 (the most
+/// volatile). The rank is scale-free, so one threshold works across
+/// instruments: volatility-regime filters, or the IV-rank and IV-percentile
+/// readings options traders use, applied to historical volatility.
+///
+/// A window where the volatility did not change ranks 0.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volatility::{RankMethod, VolatilityMeasure, VolatilityRank};
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// // Quiet bars, then the daily range doubles.
+/// let candles: Vec<Candle> = (0..40).map(|i| {
+///     let spread = if i < 30 { 1.0 } else { 2.0 };
+///     Candle { timestamp: i, open: 100.0, high: 100.0 + spread, low: 100.0 - spread, close: 100.0, volume: 1.0 }
+/// }).collect();
+///
+/// let mut rank = VolatilityRank::new(5, 20)
+///     .unwrap()
+///     .with_measure(VolatilityMeasure::Atr)
+///     .with_method(RankMethod::Range);
+/// let values = rank.calculate(&candles).unwrap();
+/// assert_eq!(values[0], 0.0);
+/// assert_eq!(*values.last().unwrap(), 100.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VolatilityRank {
+    period: usize,
+    lookback: usize,
+    measure: VolatilityMeasure,
+    method: RankMethod,
+    prev_close: Option<f64>,
+    returns: RollingStats,
+    atr: Atr,
+    history: VecDeque<f64>,
+    last: Option<f64>,
+}
+
+impl VolatilityRank {
+    /// Create a new volatility rank
+    ///
+    /// # Arguments
+    /// * `period` - Window of the volatility measure (must be at least 2)
+    /// * `lookback` - Number of volatility values ranked against (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize, lookback: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        validate_period(lookback, 2)?;
+        Ok(Self {
+            period,
+            lookback,
+            measure: VolatilityMeasure::default(),
+            method: RankMethod::default(),
+            prev_close: None,
+            returns: RollingStats::new(period)?,
+            atr: Atr::new(period)?,
+            history: VecDeque::with_capacity(lookback),
+            last: None,
+        })
+    }
+
+    /// Choose the volatility measure (realized by default).
+    pub fn with_measure(mut self, measure: VolatilityMeasure) -> Self {
+        self.measure = measure;
+        self
+    }
+
+    /// Choose the ranking method (percentile by default).
+    pub fn with_method(mut self, method: RankMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Volatility measure in use
+    pub fn measure(&self) -> VolatilityMeasure {
+        self.measure
+    }
+
+    /// Ranking method in use
+    pub fn method(&self) -> RankMethod {
+        self.method
+    }
+
+    /// Latest volatility value, before ranking.
+    pub fn volatility(&self) -> Option<f64> {
+        self.history.back().copied()
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.prev_close = None;
+        self.returns.reset();
+        self.atr.reset_state();
+        self.history.clear();
+        self.last = None;
+    }
+
+    fn volatility_step(&mut self, candle: Candle) -> Option<f64> {
+        match self.measure {
+            VolatilityMeasure::Realized => {
+                let prev = self.prev_close.replace(candle.close)?;
+                // A non-positive close has no log return; it leaves the
+                // window unchanged.
+                if prev > 0.0 && candle.close > 0.0 {
+                    self.returns.push((candle.close / prev).ln());
+                }
+                self.returns
+                    .is_full()
+                    .then(|| self.returns.std_dev())
+                    .flatten()
+            }
+            VolatilityMeasure::Atr => self.atr.next(candle).ok().flatten(),
+        }
+    }
+
+    fn rank(&self, latest: f64) -> f64 {
+        match self.method {
+            RankMethod::Percentile => {
+                let previous = self.history.len() - 1;
+                let below = self
+                    .history
+                    .iter()
+                    .take(previous)
+                    .filter(|&&v| v < latest)
+                    .count();
+                below as f64 / previous as f64 * 100.0
+            }
+            RankMethod::Range => {
+                let (min, max) = self
+                    .history
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                        (lo.min(v), hi.max(v))
+                    });
+                if max > min {
+                    (latest - min) / (max - min) * 100.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    fn step(&mut self, candle: Candle) -> Option<f64> {
+        let volatility = self.volatility_step(candle)?;
+        if self.history.len() == self.lookback {
+            self.history.pop_front();
+        }
+        self.history.push_back(volatility);
+        if self.history.len() < self.lookback {
+            return None;
+        }
+        self.last = Some(self.rank(volatility));
+        self.last
+    }
+}
+
+impl Indicator<Candle, f64> for VolatilityRank {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        Ok(data
+            .iter()
+            .filter_map(|&candle| self.step(candle))
+            .collect())
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "VolatilityRank"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        match self.measure {
+            VolatilityMeasure::Realized => self.period + self.lookback,
+            VolatilityMeasure::Atr => self.period + self.lookback - 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_realized_percentile_matches_direct_rank() {
+        let candles: Vec<Candle> = (0..60)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.4).sin() * (1.0 + i as f64 * 0.1);
+                Candle {
+                    timestamp: i,
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: 1.0,
+                }
+            })
+            .collect();
+        let mut rank = VolatilityRank::new(5, 10).unwrap();
+        let values = rank.calculate(&candles).unwrap();
+        assert_eq!(values.len(), candles.len() - rank.min_periods() + 1);
+
+        let log_returns: Vec<f64> = candles
+            .windows(2)
+            .map(|w| (w[1].close / w[0].close).ln())
+            .collect();
+        let vols: Vec<f64> = log_returns
+            .windows(5)
+            .map(|w| {
+                let mean = w.iter().sum::<f64>() / 5.0;
+                (w.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 5.0).sqrt()
+            })
+            .collect();
+        for (value, window) in values.iter().zip(vols.windows(10)) {
+            let latest = window[9];
+            let below = window[..9].iter().filter(|&&v| v < latest).count();
+            assert!((value - below as f64 / 9.0 * 100.0).abs() < 1e-9);
+        }
+        assert!(values.iter().all(|v| (0.0..=100.0).contains(v)));
+        assert!(VolatilityRank::new(1, 10).is_err());
+        assert!(VolatilityRank::new(5, 1).is_err());
+    }
+}
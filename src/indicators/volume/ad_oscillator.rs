@@ -0,0 +1,254 @@
+use crate::indicators::trend::{EmaInit, MaType, MovingAverage};
+use crate::indicators::utils::validate_data_length;
+use crate::indicators::validate_period;
+use crate::indicators::volume::{Adl, ZeroRangePolicy};
+use crate::indicators::{Candle, Indicator, IndicatorError, IndicatorSeries};
+
+/// Accumulation/Distribution (Chaikin) Oscillator
+///
+/// MACD-style momentum of the [`Adl`] line: `EMA(fast, ADL) - EMA(slow, ADL)`,
+/// 3 and 10 bars by default. The A/D line itself only drifts; its
+/// oscillator turns the drift into zero-line crossings — positive when
+/// accumulation is accelerating, negative when distribution is.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::AdOscillator;
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// let mut osc = AdOscillator::default();
+///
+/// // Closing at the highs on growing volume: accumulation
+/// let candles: Vec<Candle> = (0..15)
+///     .map(|i| Candle {
+///         timestamp: i as u64,
+///         open: 10.0, high: 11.0, low: 9.0, close: 11.0,
+///         volume: 1000.0 + 100.0 * i as f64,
+///     })
+///     .collect();
+///
+/// let values = osc.calculate(&candles).unwrap();
+/// assert_eq!(values.len(), candles.len());
+/// assert!(*values.last().unwrap() > 0.0);
+/// ```
+///
+/// # TA-Lib compatibility
+///
+/// `with_init(EmaInit::TALIB)` seeds both EMAs with SMAs the way TA-Lib's
+/// `ADOSC` does; the first value then comes at bar `slow_period - 1`.
+#[derive(Debug, Clone)]
+pub struct AdOscillator {
+    fast_period: usize,
+    slow_period: usize,
+    adl: Adl,
+    fast_ma: MovingAverage,
+    slow_ma: MovingAverage,
+    last: Option<f64>,
+}
+
+impl AdOscillator {
+    /// Create a new A/D Oscillator
+    ///
+    /// # Arguments
+    /// * `fast_period` - The period for the fast EMA of the A/D line (typically 3)
+    /// * `slow_period` - The period for the slow EMA of the A/D line (typically 10)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new AdOscillator or an error
+    pub fn new(fast_period: usize, slow_period: usize) -> Result<Self, IndicatorError> {
+        validate_period(fast_period, 1)?;
+        validate_period(slow_period, 1)?;
+        if fast_period >= slow_period {
+            return Err(IndicatorError::invalid_parameter(
+                "slow_period",
+                slow_period,
+                format!("must be greater than fast_period ({fast_period})"),
+            ));
+        }
+
+        Ok(Self {
+            fast_period,
+            slow_period,
+            adl: Adl::new(),
+            fast_ma: MovingAverage::new(MaType::Ema, fast_period)?,
+            slow_ma: MovingAverage::new(MaType::Ema, slow_period)?,
+            last: None,
+        })
+    }
+
+    /// Choose how both EMAs are seeded (see [`EmaInit`])
+    ///
+    /// # Arguments
+    /// * `init` - Seeding method
+    ///
+    /// # Returns
+    /// * `Self` - The AdOscillator with the new seeding method
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.fast_ma = self.fast_ma.with_init(init);
+        self.slow_ma = self.slow_ma.with_init(init);
+        self
+    }
+
+    /// Choose how candles with `high == low` are handled
+    ///
+    /// # Arguments
+    /// * `policy` - Zero-range handling
+    ///
+    /// # Returns
+    /// * `Self` - The AdOscillator with the new policy
+    pub fn with_zero_range(mut self, policy: ZeroRangePolicy) -> Self {
+        self.adl = self.adl.with_zero_range(policy);
+        self
+    }
+
+    /// Fast and slow EMA periods
+    pub fn periods(&self) -> (usize, usize) {
+        (self.fast_period, self.slow_period)
+    }
+
+    /// Reset the AdOscillator state
+    pub fn reset_state(&mut self) {
+        self.adl.reset();
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: Candle) -> Result<Option<f64>, IndicatorError> {
+        let Some(ad) = self.adl.next(candle)? else {
+            return Ok(None);
+        };
+        let fast = self.fast_ma.next(ad)?;
+        let slow = self.slow_ma.next(ad)?;
+        let (Some(fast), Some(slow)) = (fast, slow) else {
+            return Ok(None);
+        };
+        self.last = Some(fast - slow);
+        Ok(self.last)
+    }
+}
+
+impl Default for AdOscillator {
+    fn default() -> Self {
+        Self::new(3, 10).expect("default periods are valid")
+    }
+}
+
+impl Indicator<Candle, f64> for AdOscillator {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len());
+        for &candle in data {
+            if let Some(value) = self.step(candle)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn calculate_aligned(&mut self, data: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        // A candle skipped under `ZeroRangePolicy::Skip` keeps its place.
+        data.iter().map(|&candle| self.step(candle)).collect()
+    }
+
+    fn calculate_series(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<IndicatorSeries<f64>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.step(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.fast_ma.offset().max(self.slow_ma.offset()) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::Ema;
+
+    fn candles() -> Vec<Candle> {
+        (0..40)
+            .map(|i| {
+                let close = 10.0 + (i as f64 * 0.3).sin();
+                Candle {
+                    timestamp: i as u64,
+                    open: close,
+                    high: close + 0.6,
+                    low: close - 0.4,
+                    close,
+                    volume: 1000.0 + 50.0 * (i % 7) as f64,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ad_oscillator_new() {
+        assert!(AdOscillator::new(3, 10).is_ok());
+        assert!(AdOscillator::new(10, 3).is_err());
+        assert!(AdOscillator::new(0, 10).is_err());
+        assert_eq!(AdOscillator::default().periods(), (3, 10));
+    }
+
+    #[test]
+    fn test_ad_oscillator_is_ema_difference_of_adl() {
+        let data = candles();
+        let adl = Adl::new().calculate(&data).unwrap();
+        let fast = Ema::new(3).unwrap().calculate(&adl).unwrap();
+        let slow = Ema::new(10).unwrap().calculate(&adl).unwrap();
+
+        let values = AdOscillator::default().calculate(&data).unwrap();
+        assert_eq!(values.len(), data.len());
+        for (i, v) in values.iter().enumerate() {
+            assert!((v - (fast[i] - slow[i])).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_ad_oscillator_skip_keeps_alignment() {
+        let mut data = candles();
+        data[20].high = data[20].close;
+        data[20].low = data[20].close;
+        let mut osc = AdOscillator::default().with_zero_range(ZeroRangePolicy::Skip);
+        let values = osc.calculate(&data).unwrap();
+        let aligned = osc.calculate_aligned(&data).unwrap();
+        assert_eq!(aligned.len(), data.len());
+        assert_eq!(aligned[20], None);
+        assert_eq!(aligned.into_iter().flatten().collect::<Vec<_>>(), values);
+    }
+
+    #[test]
+    fn test_ad_oscillator_talib_seeding() {
+        let data = candles();
+        let mut osc = AdOscillator::default().with_init(EmaInit::TALIB);
+        assert_eq!(osc.min_periods(), 10);
+        let values = osc.calculate(&data).unwrap();
+        assert_eq!(values.len(), data.len() - 9);
+
+        let mut stream = AdOscillator::default().with_init(EmaInit::TALIB);
+        let streamed: Vec<f64> = data
+            .iter()
+            .filter_map(|c| stream.next(*c).unwrap())
+            .collect();
+        assert_eq!(streamed, values);
+    }
+}
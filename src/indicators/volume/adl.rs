@@ -1,4 +1,6 @@
-use crate::indicators::validate_data_length;
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind};
+use crate::indicators::volume::money_flow::{money_flow_multiplier, ZeroRangePolicy};
+use crate::indicators::{validate_data_length, IndicatorSeries, KahanSum};
 use crate::Candle;
 use crate::Indicator;
 use crate::IndicatorError;
@@ -28,56 +30,59 @@ use crate::IndicatorError;
 /// // Calculate A/D Line values
 /// let adl_values = adl.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+///
+/// # Flat candles
+///
+/// A candle with `high == low` has no defined money flow multiplier. By
+/// default it adds nothing to the line; see [`ZeroRangePolicy`] and
+/// [`with_zero_range`](Adl::with_zero_range) for the alternatives.
+#[derive(Debug, Clone)]
 pub struct Adl {
-    current_ad: f64,
+    current_ad: KahanSum,
+    zero_range: ZeroRangePolicy,
+    index: usize,
+    last: Option<f64>,
 }
 
 impl Adl {
     /// Create a new Adl indicator
     pub fn new() -> Self {
-        Self { current_ad: 0.0 }
+        Self {
+            current_ad: KahanSum::new(),
+            zero_range: ZeroRangePolicy::Zero,
+            index: 0,
+            last: None,
+        }
     }
 
-    /// Calculate Money Flow Multiplier (MFM) for a candle
+    /// Choose how candles with `high == low` are handled
     ///
     /// # Arguments
-    /// * `candle` - The candle data to calculate MFM from
+    /// * `policy` - Zero-range handling
     ///
     /// # Returns
-    /// * `f64` - The Money Flow Multiplier value
-    fn money_flow_multiplier(candle: &Candle) -> Result<f64, IndicatorError> {
-        let high = candle.high;
-        let low = candle.low;
-        let close = candle.close;
-
-        let range = high - low;
-
-        if range == 0.0 {
-            return Err(IndicatorError::CalculationError(
-                "Division by zero: high and low prices are equal".to_string(),
-            ));
-        }
+    /// * `Self` - The Adl with the new policy
+    pub fn with_zero_range(mut self, policy: ZeroRangePolicy) -> Self {
+        self.zero_range = policy;
+        self
+    }
 
-        // Calculate Money Flow Multiplier
-        // MFM = ((Close - Low) - (High - Close)) / (High - Low)
-        // Simplified to: MFM = (2 * Close - High - Low) / (High - Low)
-        Ok((2.0 * close - high - low) / range)
+    /// Zero-range handling in use
+    pub fn zero_range(&self) -> ZeroRangePolicy {
+        self.zero_range
     }
 
-    /// Calculate Money Flow Volume (MFV) for a candle
-    ///
-    /// # Arguments
-    /// * `candle` - The candle data to calculate MFV from
-    ///
-    /// # Returns
-    /// * `f64` - The Money Flow Volume value
-    fn money_flow_volume(candle: &Candle) -> Result<f64, IndicatorError> {
-        let mfm = Self::money_flow_multiplier(candle)?;
-        let volume = candle.volume;
+    fn step(&mut self, candle: &Candle) -> Result<Option<f64>, IndicatorError> {
+        let index = self.index;
+        self.index += 1;
+        let Some(mfm) = money_flow_multiplier(candle, self.zero_range, index)? else {
+            return Ok(None);
+        };
 
         // Money Flow Volume = Money Flow Multiplier * Volume
-        Ok(mfm * volume)
+        self.current_ad.add(mfm * candle.volume);
+        self.last = Some(self.current_ad.value());
+        Ok(self.last)
     }
 }
 
@@ -98,28 +103,55 @@ impl Indicator<Candle, f64> for Adl {
         self.reset();
 
         // Calculate AD Line
-        let mut ad_line = 0.0;
-
         for candle in data {
-            let money_flow_volume = Self::money_flow_volume(candle)?;
-            ad_line += money_flow_volume;
-            result.push(ad_line);
+            if let Some(ad) = self.step(candle)? {
+                result.push(ad);
+            }
         }
 
-        self.current_ad = ad_line;
-
         Ok(result)
     }
 
-    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
-        let money_flow_volume = Self::money_flow_volume(&value)?;
-        self.current_ad += money_flow_volume;
+    fn calculate_aligned(&mut self, data: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        validate_data_length(data, 1)?;
+        self.reset();
+        // A skipped flat candle keeps its place as `None`.
+        data.iter().map(|candle| self.step(candle)).collect()
+    }
+
+    fn calculate_series(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<IndicatorSeries<f64>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
 
-        Ok(Some(self.current_ad))
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.step(&value)
     }
 
     fn reset(&mut self) {
-        self.current_ad = 0.0;
+        self.current_ad.reset();
+        self.index = 0;
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+}
+
+impl Describe for Adl {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "adl",
+            title: "Accumulation/Distribution Line",
+            input: InputKind::Candle,
+            params: &[],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -134,7 +166,7 @@ mod tests {
         // Adl has no parameters to validate
         let adl = Adl::new();
         // Verify fields are accessible
-        assert_eq!(adl.current_ad, 0.0);
+        assert_eq!(adl.current_ad.value(), 0.0);
     }
 
     #[test]
@@ -240,7 +272,7 @@ mod tests {
         adl.reset();
 
         // ADL should be reset to 0
-        assert_eq!(adl.current_ad, 0.0);
+        assert_eq!(adl.current_ad.value(), 0.0);
 
         // After reset, next candle should be treated as first
         let candle2 = Candle {
@@ -258,64 +290,72 @@ mod tests {
         assert!((result.unwrap() - 600.0).abs() < 0.01);
     }
 
-    #[test]
-    fn test_adl_error_handling() {
-        let mut adl = Adl::new();
-
-        // Create a candle with equal high and low prices
-        let candle = Candle {
+    fn flat_candle() -> Candle {
+        Candle {
             timestamp: 1,
             open: 10.0,
             high: 10.0,
             low: 10.0,
             close: 10.0,
             volume: 1000.0,
-        };
+        }
+    }
+
+    #[test]
+    fn test_adl_error_handling() {
+        let mut adl = Adl::new().with_zero_range(ZeroRangePolicy::Error);
+        assert_eq!(adl.zero_range(), ZeroRangePolicy::Error);
 
         // Expect an error due to division by zero
-        let result = adl.next(candle);
+        let result = adl.next(flat_candle());
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            IndicatorError::CalculationError(
-                "Division by zero: high and low prices are equal".to_string()
-            )
+            IndicatorError::division_by_zero("high - low range", 0)
         );
+
+        // Test error propagation in batch calculation
+        let result = adl.calculate(&[flat_candle()]);
+        assert!(matches!(
+            result,
+            Err(IndicatorError::DivisionByZero { index: 0, .. })
+        ));
     }
 
     #[test]
-    fn test_adl_money_flow_multiplier_zero_range() {
-        // Test with high-low range of zero (should error)
-        let candle = Candle {
-            timestamp: 1,
+    fn test_adl_zero_range_policies() {
+        let up = Candle {
+            timestamp: 0,
             open: 10.0,
-            high: 10.0, // Same as low
-            low: 10.0,  // Same as high
-            close: 10.0,
-            volume: 1000.0,
+            high: 12.0,
+            low: 10.0,
+            close: 12.0,
+            volume: 100.0,
         };
+        let candles = [up, flat_candle(), up];
 
-        // Directly test the money_flow_multiplier function
-        let result = Adl::money_flow_multiplier(&candle);
-        assert!(result.is_err());
-
-        // Verify it's the correct error type and message
-        if let Err(IndicatorError::CalculationError(msg)) = result {
-            assert!(
-                msg.contains("division by zero") || msg.contains("high and low prices are equal")
-            );
-        } else {
-            panic!("Expected CalculationError for zero range");
-        }
-
-        // Test error propagation in batch calculation
+        // Default: the flat bar contributes nothing but still emits
         let mut adl = Adl::new();
-        let result = adl.calculate(&[candle]);
-        assert!(result.is_err());
+        assert_eq!(adl.zero_range(), ZeroRangePolicy::Zero);
+        assert_eq!(adl.calculate(&candles).unwrap(), vec![100.0, 100.0, 200.0]);
 
-        // Test error propagation in streaming calculation
-        let mut adl = Adl::new();
-        let result = adl.next(candle);
-        assert!(result.is_err());
+        // Skip: the flat bar produces no output at all
+        let mut adl = Adl::new().with_zero_range(ZeroRangePolicy::Skip);
+        assert_eq!(adl.calculate(&candles).unwrap(), vec![100.0, 200.0]);
+        assert_eq!(
+            adl.calculate_aligned(&candles).unwrap(),
+            vec![Some(100.0), None, Some(200.0)]
+        );
+        let series = adl.calculate_series(&candles).unwrap();
+        assert_eq!((series.at(1), series.at(2)), (None, Some(&200.0)));
+        adl.reset();
+        assert_eq!(adl.next(flat_candle()).unwrap(), None);
+
+        // Error: the flat bar is reported with its position
+        let mut adl = Adl::new().with_zero_range(ZeroRangePolicy::Error);
+        assert_eq!(
+            adl.calculate(&candles),
+            Err(IndicatorError::division_by_zero("high - low range", 1))
+        );
     }
 }
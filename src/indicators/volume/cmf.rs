@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
 
-use crate::indicators::{validate_data_length, validate_period};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
+use crate::indicators::volume::money_flow::{money_flow_multiplier, ZeroRangePolicy};
+use crate::indicators::volume::CmfSignal;
+use crate::indicators::{validate_data_length, validate_period, IndicatorSeries};
 use crate::Candle;
 use crate::Indicator;
 use crate::IndicatorError;
@@ -68,12 +71,21 @@ use crate::IndicatorError;
 ///     }
 /// }
 ///```
-
-#[derive(Debug)]
+///
+/// # Flat candles
+///
+/// A candle with `high == low` has no defined money flow multiplier. By
+/// default its money flow counts as 0 while its volume still counts; see
+/// [`ZeroRangePolicy`] and [`with_zero_range`](Cmf::with_zero_range) for
+/// the alternatives.
+#[derive(Debug, Clone)]
 pub struct Cmf {
     period: usize,
     mfv_buffer: VecDeque<f64>,
     volume_buffer: VecDeque<f64>,
+    zero_range: ZeroRangePolicy,
+    index: usize,
+    last: Option<f64>,
 }
 
 impl Cmf {
@@ -89,50 +101,71 @@ impl Cmf {
 
         Ok(Self {
             period,
-            mfv_buffer: VecDeque::with_capacity(period),
-            volume_buffer: VecDeque::with_capacity(period),
+            mfv_buffer: VecDeque::with_capacity(period + 1),
+            volume_buffer: VecDeque::with_capacity(period + 1),
+            zero_range: ZeroRangePolicy::Zero,
+            index: 0,
+            last: None,
         })
     }
 
-    /// Calculate Money Flow Multiplier (MFM) for a candle
+    /// Choose how candles with `high == low` are handled
     ///
     /// # Arguments
-    /// * `candle` - The candle data to calculate MFM from
+    /// * `policy` - Zero-range handling
     ///
     /// # Returns
-    /// * `f64` - The Money Flow Multiplier value
-    fn money_flow_multiplier(candle: &Candle) -> Result<f64, IndicatorError> {
-        let high = candle.high;
-        let low = candle.low;
-        let close = candle.close;
-
-        let range = high - low;
-
-        if range == 0.0 {
-            return Err(IndicatorError::CalculationError(
-                "Division by zero: high and low prices are equal".to_string(),
-            ));
-        }
+    /// * `Self` - The Cmf with the new policy
+    pub fn with_zero_range(mut self, policy: ZeroRangePolicy) -> Self {
+        self.zero_range = policy;
+        self
+    }
 
-        // Calculate Money Flow Multiplier
-        // MFM = ((Close - Low) - (High - Close)) / (High - Low)
-        // Simplified to: MFM = (2 * Close - High - Low) / (High - Low)
-        Ok((2.0 * close - high - low) / range)
+    /// Zero-range handling in use
+    pub fn zero_range(&self) -> ZeroRangePolicy {
+        self.zero_range
     }
 
-    /// Calculate Money Flow Volume (MFV) for a candle
+    /// Add an EMA signal line and crossing flags (see [`CmfSignal`])
     ///
     /// # Arguments
-    /// * `candle` - The candle data to calculate MFV from
+    /// * `signal_period` - The period for the signal EMA (must be at least 1)
     ///
     /// # Returns
-    /// * `f64` - The Money Flow Volume value
-    fn money_flow_volume(candle: &Candle) -> Result<f64, IndicatorError> {
-        let mfm = Self::money_flow_multiplier(candle)?;
-        let volume = candle.volume;
+    /// * `Result<CmfSignal, IndicatorError>` - The CMF with a signal line or an error
+    pub fn with_signal(self, signal_period: usize) -> Result<CmfSignal, IndicatorError> {
+        CmfSignal::from_cmf(self, signal_period)
+    }
+
+    fn step(&mut self, candle: &Candle) -> Result<Option<f64>, IndicatorError> {
+        let index = self.index;
+        self.index += 1;
+        let Some(mfm) = money_flow_multiplier(candle, self.zero_range, index)? else {
+            return Ok(None);
+        };
 
         // Money Flow Volume = Money Flow Multiplier * Volume
-        Ok(mfm * volume)
+        self.mfv_buffer.push_back(mfm * candle.volume);
+        self.volume_buffer.push_back(candle.volume);
+
+        if self.mfv_buffer.len() > self.period {
+            self.mfv_buffer.pop_front();
+            self.volume_buffer.pop_front();
+        }
+
+        if self.mfv_buffer.len() < self.period {
+            return Ok(None);
+        }
+
+        let sum_mfv: f64 = self.mfv_buffer.iter().sum();
+        let sum_volume: f64 = self.volume_buffer.iter().sum();
+
+        if sum_volume == 0.0 {
+            return Err(IndicatorError::division_by_zero("volume sum", index));
+        }
+
+        self.last = Some(sum_mfv / sum_volume);
+        Ok(self.last)
     }
 }
 
@@ -146,27 +179,8 @@ impl Indicator<Candle, f64> for Cmf {
         // Reset state
         self.reset();
 
-        for candle in data.iter().take(n) {
-            let mfv = Self::money_flow_volume(candle)?;
-            self.mfv_buffer.push_back(mfv);
-            self.volume_buffer.push_back(candle.volume);
-
-            if self.mfv_buffer.len() > self.period {
-                self.mfv_buffer.pop_front();
-                self.volume_buffer.pop_front();
-            }
-
-            if self.mfv_buffer.len() == self.period {
-                let sum_mfv: f64 = self.mfv_buffer.iter().sum();
-                let sum_volume: f64 = self.volume_buffer.iter().sum();
-
-                if sum_volume == 0.0 {
-                    return Err(IndicatorError::CalculationError(
-                        "Division by zero: sum of volumes is zero".to_string(),
-                    ));
-                }
-
-                let cmf = sum_mfv / sum_volume;
+        for candle in data {
+            if let Some(cmf) = self.step(candle)? {
                 result.push(cmf);
             }
         }
@@ -174,37 +188,51 @@ impl Indicator<Candle, f64> for Cmf {
         Ok(result)
     }
 
-    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
-        let mfv = Self::money_flow_volume(&value)?;
-
-        self.mfv_buffer.push_back(mfv);
-        self.volume_buffer.push_back(value.volume);
-
-        if self.mfv_buffer.len() > self.period {
-            self.mfv_buffer.pop_front();
-            self.volume_buffer.pop_front();
-        }
-
-        if self.mfv_buffer.len() == self.period {
-            let sum_mfv: f64 = self.mfv_buffer.iter().sum();
-            let sum_volume: f64 = self.volume_buffer.iter().sum();
+    fn calculate_aligned(&mut self, data: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset();
+        // A skipped flat candle keeps its place as `None`.
+        data.iter().map(|candle| self.step(candle)).collect()
+    }
 
-            if sum_volume == 0.0 {
-                return Err(IndicatorError::CalculationError(
-                    "Division by zero: sum of volumes is zero".to_string(),
-                ));
-            }
+    fn calculate_series(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<IndicatorSeries<f64>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
 
-            let cmf = sum_mfv / sum_volume;
-            Ok(Some(cmf))
-        } else {
-            Ok(None)
-        }
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.step(&value)
     }
 
     fn reset(&mut self) {
         self.mfv_buffer.clear();
         self.volume_buffer.clear();
+        self.index = 0;
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+impl Describe for Cmf {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "cmf",
+            title: "Chaikin Money Flow",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -301,24 +329,21 @@ mod tests {
         let result = cmf.calculate(&candles);
         assert!(result.is_err());
 
-        // Verify it's the correct error type and message
-        if let Err(IndicatorError::CalculationError(msg)) = result {
-            assert!(msg.contains("division by zero") || msg.contains("sum of volumes is zero"));
-        } else {
-            panic!("Expected CalculationError for zero volume sum");
-        }
+        // Verify it's the correct error type and position
+        assert_eq!(
+            result.unwrap_err(),
+            IndicatorError::division_by_zero("volume sum", 1)
+        );
 
         // Test streaming calculation
         cmf.reset();
         assert_eq!(cmf.next(candles[0]).unwrap(), None); // Not enough data yet
         let result = cmf.next(candles[1]);
 
-        assert!(result.is_err());
-        if let Err(IndicatorError::CalculationError(msg)) = result {
-            assert!(msg.contains("division by zero") || msg.contains("sum of volumes is zero"));
-        } else {
-            panic!("Expected CalculationError for zero volume sum in streaming mode");
-        }
+        assert!(matches!(
+            result,
+            Err(IndicatorError::DivisionByZero { index: 1, .. })
+        ));
     }
 
     #[test]
@@ -648,4 +673,41 @@ mod tests {
             result[0]
         );
     }
+
+    #[test]
+    fn test_cmf_zero_range_policies() {
+        let candle = |high: f64, low: f64, close: f64, volume: f64| Candle {
+            timestamp: 0,
+            open: close,
+            high,
+            low,
+            close,
+            volume,
+        };
+        let candles = [
+            candle(12.0, 10.0, 12.0, 100.0),
+            candle(10.0, 10.0, 10.0, 300.0),
+            candle(12.0, 10.0, 10.0, 100.0),
+        ];
+
+        // Default: flat bar has MFM 0 but its volume dilutes the ratio
+        let mut cmf = Cmf::new(2).unwrap();
+        assert_eq!(cmf.zero_range(), ZeroRangePolicy::Zero);
+        assert_eq!(cmf.calculate(&candles).unwrap(), vec![0.25, -0.25]);
+
+        // Skip: the window is formed from the two non-flat bars
+        let mut cmf = Cmf::new(2).unwrap().with_zero_range(ZeroRangePolicy::Skip);
+        assert_eq!(cmf.calculate(&candles).unwrap(), vec![0.0]);
+        assert_eq!(
+            cmf.calculate_aligned(&candles).unwrap(),
+            vec![None, None, Some(0.0)]
+        );
+
+        // Error: the flat bar is reported with its position
+        let mut cmf = Cmf::new(2).unwrap().with_zero_range(ZeroRangePolicy::Error);
+        assert_eq!(
+            cmf.calculate(&candles),
+            Err(IndicatorError::division_by_zero("high - low range", 1))
+        );
+    }
 } // Close the test modu
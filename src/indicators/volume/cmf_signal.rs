@@ -0,0 +1,242 @@
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::volume::{Cmf, ZeroRangePolicy};
+use crate::indicators::{Candle, Indicator, IndicatorError, IndicatorSeries};
+use crate::signals::cross::{Cross, CrossoverTracker};
+
+/// Chaikin Money Flow with a signal line
+///
+/// Adds an EMA of the CMF and flags the bars where the CMF crosses the
+/// zero line (money flow turning from distribution to accumulation or
+/// back) or its signal line, so consumers can act on crossings instead of
+/// watching raw values. Crossings follow the rules of
+/// [`signals::cross`](crate::signals::cross).
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::Cmf;
+/// use rsta::indicators::{Candle, Indicator};
+/// use rsta::signals::cross::Cross;
+///
+/// let mut cmf = Cmf::new(3).unwrap().with_signal(2).unwrap();
+///
+/// // Closes at the lows, then at the highs
+/// let candles: Vec<Candle> = (0..8)
+///     .map(|i| {
+///         let close = if i < 4 { 9.0 } else { 11.0 };
+///         Candle {
+///             timestamp: i as u64,
+///             open: 10.0, high: 11.0, low: 9.0, close, volume: 1000.0,
+///         }
+///     })
+///     .collect();
+///
+/// let values = cmf.calculate(&candles).unwrap();
+/// // Window [9, 9, 11] reads -1/3, window [9, 11, 11] reads +1/3
+/// assert_eq!(values[3].zero_cross, Some(Cross::Above));
+/// assert_eq!(values.iter().filter(|r| r.zero_cross.is_some()).count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CmfSignal {
+    cmf: Cmf,
+    signal_ma: MovingAverage,
+    zero: CrossoverTracker,
+    signal: CrossoverTracker,
+    last: Option<CmfResult>,
+}
+
+/// CMF value with its signal line and the crossings of this bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CmfResult {
+    /// The Chaikin Money Flow value
+    pub cmf: f64,
+    /// The signal line value (EMA of the CMF)
+    pub signal: f64,
+    /// Crossing of the CMF over the zero line on this bar
+    pub zero_cross: Option<Cross>,
+    /// Crossing of the CMF over its signal line on this bar
+    pub signal_cross: Option<Cross>,
+}
+
+impl CmfSignal {
+    /// Create a new CMF with a signal line
+    ///
+    /// # Arguments
+    /// * `period` - The period for CMF calculation (must be at least 1)
+    /// * `signal_period` - The period for the signal EMA (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new CmfSignal or an error
+    pub fn new(period: usize, signal_period: usize) -> Result<Self, IndicatorError> {
+        Self::from_cmf(Cmf::new(period)?, signal_period)
+    }
+
+    pub(crate) fn from_cmf(cmf: Cmf, signal_period: usize) -> Result<Self, IndicatorError> {
+        validate_period(signal_period, 1)?;
+        Ok(Self {
+            cmf,
+            signal_ma: MovingAverage::new(MaType::Ema, signal_period)?,
+            zero: CrossoverTracker::new(),
+            signal: CrossoverTracker::new(),
+            last: None,
+        })
+    }
+
+    /// Choose how candles with `high == low` are handled
+    ///
+    /// # Arguments
+    /// * `policy` - Zero-range handling
+    ///
+    /// # Returns
+    /// * `Self` - The CmfSignal with the new policy
+    pub fn with_zero_range(mut self, policy: ZeroRangePolicy) -> Self {
+        self.cmf = self.cmf.with_zero_range(policy);
+        self
+    }
+
+    /// Reset the CmfSignal state
+    pub fn reset_state(&mut self) {
+        self.cmf.reset();
+        self.signal_ma.reset();
+        self.zero.reset_state();
+        self.signal.reset_state();
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: Candle) -> Result<Option<CmfResult>, IndicatorError> {
+        let Some(cmf) = self.cmf.next(candle)? else {
+            return Ok(None);
+        };
+        let Some(signal) = self.signal_ma.next(cmf)? else {
+            return Ok(None);
+        };
+        self.last = Some(CmfResult {
+            cmf,
+            signal,
+            zero_cross: self.zero.update(cmf, 0.0),
+            signal_cross: self.signal.update(cmf, signal),
+        });
+        Ok(self.last)
+    }
+}
+
+impl Indicator<Candle, CmfResult> for CmfSignal {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<CmfResult>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len());
+        for &candle in data {
+            if let Some(value) = self.step(candle)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn calculate_aligned(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<Vec<Option<CmfResult>>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        // A candle skipped under `ZeroRangePolicy::Skip` keeps its place.
+        data.iter().map(|&candle| self.step(candle)).collect()
+    }
+
+    fn calculate_series(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<IndicatorSeries<CmfResult>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<CmfResult>, IndicatorError> {
+        self.step(value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&CmfResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.cmf.min_periods() + self.signal_ma.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open: 10.0,
+            high: 11.0,
+            low: 9.0,
+            close,
+            volume: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_cmf_signal_matches_cmf() {
+        let data: Vec<Candle> = (0..30)
+            .map(|i| candle(10.0 + (i as f64 * 0.4).sin()))
+            .collect();
+        let plain = Cmf::new(5).unwrap().calculate(&data).unwrap();
+        let mut cmf = CmfSignal::new(5, 3).unwrap();
+        let values = cmf.calculate(&data).unwrap();
+        assert_eq!(values.len(), plain.len());
+        for (r, &p) in values.iter().zip(&plain) {
+            assert_eq!(r.cmf, p);
+        }
+        // The first value seeds the signal line
+        assert_eq!(values[0].signal, values[0].cmf);
+        assert_eq!(values[0].zero_cross, None);
+
+        let zero_crosses = values.iter().filter(|r| r.zero_cross.is_some()).count();
+        assert!(zero_crosses >= 2);
+        for w in values.windows(2) {
+            if w[1].zero_cross == Some(Cross::Below) {
+                assert!(w[0].cmf >= 0.0 && w[1].cmf < 0.0);
+            }
+        }
+        assert!(values.iter().any(|r| r.signal_cross.is_some()));
+
+        // A skipped flat candle leaves its own bar empty, not the first one.
+        let mut flat = data.clone();
+        flat[15].high = flat[15].close;
+        flat[15].low = flat[15].close;
+        let mut cmf = CmfSignal::new(5, 3)
+            .unwrap()
+            .with_zero_range(ZeroRangePolicy::Skip);
+        let aligned = cmf.calculate_aligned(&flat).unwrap();
+        assert_eq!(aligned[15], None);
+        assert!(aligned[16].is_some());
+        assert_eq!(
+            aligned.into_iter().flatten().collect::<Vec<_>>(),
+            cmf.calculate(&flat).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cmf_signal_params() {
+        assert!(CmfSignal::new(0, 3).is_err());
+        assert!(CmfSignal::new(5, 0).is_err());
+        assert!(Cmf::new(5).unwrap().with_signal(0).is_err());
+
+        let mut cmf = CmfSignal::new(2, 2).unwrap();
+        assert!(cmf.calculate(&[candle(10.0)]).is_err());
+        cmf.next(candle(11.0)).unwrap();
+        assert!(cmf.next(candle(11.0)).unwrap().is_some());
+        cmf.reset_state();
+        assert!(cmf.current().is_none());
+        assert!(cmf.next(candle(11.0)).unwrap().is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::indicators::{Indicator, IndicatorError, Tick, TickRule};
+
+/// Cumulative Volume Delta
+///
+/// Running total of buy volume minus sell volume over a trade stream.
+/// Trades are signed by their reported aggressor side, or by the
+/// [`TickRule`] when the feed does not report one.
+///
+/// # Formula
+///
+/// ```text
+/// delta[t] = delta[t-1] + sign(side) * size
+/// ```
+///
+/// Call [`CumulativeDelta::reset_state`] to start a new session.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::CumulativeDelta;
+/// use rsta::indicators::{Indicator, Tick, TradeSide};
+///
+/// let tick = |price, size| Tick { timestamp: 0, price, size, side: TradeSide::Unknown };
+/// let mut delta = CumulativeDelta::new();
+/// // First trade unclassified, then an uptick (buy) and a downtick (sell).
+/// let values = delta.calculate(&[tick(10.0, 5.0), tick(10.5, 3.0), tick(10.1, 1.0)]).unwrap();
+/// assert_eq!(values, vec![0.0, 3.0, 2.0]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CumulativeDelta {
+    rule: TickRule,
+    delta: f64,
+    last: Option<f64>,
+}
+
+impl CumulativeDelta {
+    /// Create a new cumulative delta starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset the running delta (call at each new session start).
+    pub fn reset_state(&mut self) {
+        self.rule.reset();
+        self.delta = 0.0;
+        self.last = None;
+    }
+
+    fn step(&mut self, tick: Tick) -> f64 {
+        self.delta += self.rule.side(&tick).sign() * tick.size;
+        self.last = Some(self.delta);
+        self.delta
+    }
+}
+
+impl Indicator<Tick, f64> for CumulativeDelta {
+    fn calculate(&mut self, data: &[Tick]) -> Result<Vec<f64>, IndicatorError> {
+        if data.is_empty() {
+            return Err(IndicatorError::insufficient_data(1, 0));
+        }
+        self.reset_state();
+        Ok(data.iter().map(|&tick| self.step(tick)).collect())
+    }
+
+    fn next(&mut self, value: Tick) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(value)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "CumulativeDelta"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::TradeSide;
+
+    #[test]
+    fn test_reported_sides_override_tick_rule() {
+        let tick = |price, size, side| Tick {
+            timestamp: 0,
+            price,
+            size,
+            side,
+        };
+        let mut delta = CumulativeDelta::new();
+        let values = delta
+            .calculate(&[
+                tick(10.0, 2.0, TradeSide::Buy),
+                tick(9.0, 4.0, TradeSide::Buy),
+                tick(9.0, 1.0, TradeSide::Unknown),
+                tick(9.5, 3.0, TradeSide::Sell),
+            ])
+            .unwrap();
+        // The third trade is a zero tick after a downtick: a sell.
+        assert_eq!(values, vec![2.0, 6.0, 5.0, 2.0]);
+        assert!(delta.calculate(&[]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{validate_data_length, validate_period};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 use std::collections::VecDeque;
@@ -28,12 +29,13 @@ use std::collections::VecDeque;
 /// let values = mfi.calculate(&candles).unwrap();
 /// assert!(!values.is_empty());
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Mfi {
     period: usize,
     /// (signed_raw_money_flow, direction). Direction: +1 up, -1 down, 0 unchanged.
     flow_buffer: VecDeque<(f64, i8)>,
     prev_tp: Option<f64>,
+    last: Option<f64>,
 }
 
 impl Mfi {
@@ -42,8 +44,9 @@ impl Mfi {
         validate_period(period, 1)?;
         Ok(Self {
             period,
-            flow_buffer: VecDeque::with_capacity(period),
+            flow_buffer: VecDeque::with_capacity(period + 1),
             prev_tp: None,
+            last: None,
         })
     }
 
@@ -51,6 +54,7 @@ impl Mfi {
     pub fn reset_state(&mut self) {
         self.flow_buffer.clear();
         self.prev_tp = None;
+        self.last = None;
     }
 
     fn step(&mut self, value: Candle) -> Option<f64> {
@@ -86,14 +90,18 @@ impl Mfi {
                 _ => {}
             }
         }
-        if negative == 0.0 {
+        let mfi = if negative == 0.0 {
             if positive == 0.0 {
-                return Some(50.0);
+                50.0
+            } else {
+                100.0
             }
-            return Some(100.0);
-        }
-        let ratio = positive / negative;
-        Some(100.0 - 100.0 / (1.0 + ratio))
+        } else {
+            let ratio = positive / negative;
+            100.0 - 100.0 / (1.0 + ratio)
+        };
+        self.last = Some(mfi);
+        self.last
     }
 }
 
@@ -118,6 +126,10 @@ impl Indicator<Candle, f64> for Mfi {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Mfi"
     }
@@ -125,6 +137,23 @@ impl Indicator<Candle, f64> for Mfi {
     fn period(&self) -> Option<usize> {
         Some(self.period)
     }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+}
+
+impl Describe for Mfi {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "mfi",
+            title: "Money Flow Index",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
 }
 
 #[cfg(test)]
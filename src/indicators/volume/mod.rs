@@ -1,20 +1,40 @@
 //! Volume-based indicators
 //!
-//! This module contains volume-based indicators like OBV, Volume Rate of Change, and A/D Line.
+//! This module contains volume-based indicators like OBV, Volume Rate of Change, and A/D Line,
+//! plus order-flow indicators computed from individual trades ([`Tick`](crate::indicators::Tick)):
+//! cumulative delta, trade imbalance and a rolling VWAP. The Volume Oscillator and a
+//! volume-weighted MACD confirm price moves with participation; the A/D Oscillator
+//! and CMF signal line turn money flow into zero-line and signal crossings.
 //!
 
 // Module declarations
+pub mod ad_oscillator;
 pub mod adl;
 pub mod cmf;
+pub mod cmf_signal;
+pub mod cumulative_delta;
 pub mod mfi;
+pub mod money_flow;
 pub mod obv;
+pub mod rolling_vwap;
+pub mod trade_imbalance;
+pub mod volume_oscillator;
 pub mod vroc;
+pub mod vw_macd;
 pub mod vwap;
 
 // Re-exports
+pub use self::ad_oscillator::AdOscillator;
 pub use self::adl::Adl;
 pub use self::cmf::Cmf;
+pub use self::cmf_signal::{CmfResult, CmfSignal};
+pub use self::cumulative_delta::CumulativeDelta;
 pub use self::mfi::Mfi;
+pub use self::money_flow::ZeroRangePolicy;
 pub use self::obv::Obv;
-pub use self::vroc::Vroc;
+pub use self::rolling_vwap::RollingVwap;
+pub use self::trade_imbalance::TradeImbalance;
+pub use self::volume_oscillator::VolumeOscillator;
+pub use self::vroc::{Vroc, ZeroVolumePolicy};
+pub use self::vw_macd::VwMacd;
 pub use self::vwap::Vwap;
@@ -0,0 +1,132 @@
+//! Money flow multiplier shared by [`Adl`](super::Adl) and [`Cmf`](super::Cmf)
+//!
+//! The multiplier `((close - low) - (high - close)) / (high - low)` is
+//! undefined on a candle whose high equals its low — routine on illiquid
+//! bars or halted sessions. [`ZeroRangePolicy`] decides what happens then.
+//!
+//! ```
+//! use rsta::indicators::volume::{Adl, ZeroRangePolicy};
+//! use rsta::indicators::{Candle, Indicator, IndicatorError};
+//!
+//! let flat = Candle { timestamp: 1, open: 10.0, high: 10.0, low: 10.0, close: 10.0, volume: 500.0 };
+//! let candles = vec![
+//!     Candle { timestamp: 0, open: 9.0, high: 11.0, low: 9.0, close: 11.0, volume: 1000.0 },
+//!     flat,
+//! ];
+//!
+//! // Default: the flat bar adds no money flow.
+//! assert_eq!(Adl::new().calculate(&candles).unwrap(), vec![1000.0, 1000.0]);
+//!
+//! // Strict: the flat bar is reported with its position.
+//! let mut strict = Adl::new().with_zero_range(ZeroRangePolicy::Error);
+//! assert!(matches!(
+//!     strict.calculate(&candles),
+//!     Err(IndicatorError::DivisionByZero { index: 1, .. })
+//! ));
+//! ```
+
+use crate::indicators::{Candle, IndicatorError};
+
+/// What to do with a candle whose high equals its low.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ZeroRangePolicy {
+    /// Treat the money flow multiplier as 0: the bar's volume still counts
+    /// but adds no buying or selling pressure. This matches TA-Lib.
+    #[default]
+    Zero,
+    /// Drop the candle as if it had never been received. `next()` returns
+    /// `Ok(None)` and `calculate()` runs on the remaining candles, so its
+    /// output is no longer right-aligned with the input:
+    /// `calculate_aligned()` and `calculate_series()` keep the candle's
+    /// place empty instead.
+    Skip,
+    /// Reject the candle with [`IndicatorError::DivisionByZero`]. The
+    /// indicator state is left untouched, so streaming can continue with
+    /// the next candle.
+    Error,
+}
+
+/// Money Flow Multiplier (MFM) of a candle
+///
+/// # Arguments
+/// * `candle` - The candle data to calculate MFM from
+/// * `policy` - Handling of a zero high - low range
+/// * `index` - Position of the candle, reported on error
+///
+/// # Returns
+/// * `Result<Option<f64>, IndicatorError>` - The multiplier, or `None` if
+///   the candle is skipped
+pub(crate) fn money_flow_multiplier(
+    candle: &Candle,
+    policy: ZeroRangePolicy,
+    index: usize,
+) -> Result<Option<f64>, IndicatorError> {
+    let range = candle.high - candle.low;
+
+    if range == 0.0 {
+        return match policy {
+            ZeroRangePolicy::Zero => Ok(Some(0.0)),
+            ZeroRangePolicy::Skip => Ok(None),
+            ZeroRangePolicy::Error => {
+                Err(IndicatorError::division_by_zero("high - low range", index))
+            }
+        };
+    }
+
+    // MFM = ((Close - Low) - (High - Close)) / (High - Low)
+    // Simplified to: MFM = (2 * Close - High - Low) / (High - Low)
+    Ok(Some(
+        (2.0 * candle.close - candle.high - candle.low) / range,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 1000.0,
+        }
+    }
+
+    #[test]
+    fn test_money_flow_multiplier() {
+        let policy = ZeroRangePolicy::default();
+        assert_eq!(
+            money_flow_multiplier(&candle(12.0, 8.0, 12.0), policy, 0),
+            Ok(Some(1.0))
+        );
+        assert_eq!(
+            money_flow_multiplier(&candle(12.0, 8.0, 10.0), policy, 0),
+            Ok(Some(0.0))
+        );
+        assert_eq!(
+            money_flow_multiplier(&candle(12.0, 8.0, 8.0), policy, 0),
+            Ok(Some(-1.0))
+        );
+    }
+
+    #[test]
+    fn test_zero_range_policies() {
+        let flat = candle(10.0, 10.0, 10.0);
+        assert_eq!(
+            money_flow_multiplier(&flat, ZeroRangePolicy::Zero, 4),
+            Ok(Some(0.0))
+        );
+        assert_eq!(
+            money_flow_multiplier(&flat, ZeroRangePolicy::Skip, 4),
+            Ok(None)
+        );
+        assert_eq!(
+            money_flow_multiplier(&flat, ZeroRangePolicy::Error, 4),
+            Err(IndicatorError::division_by_zero("high - low range", 4))
+        );
+    }
+}
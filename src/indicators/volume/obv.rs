@@ -1,4 +1,5 @@
-use crate::indicators::utils::validate_data_length;
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind};
+use crate::indicators::utils::{validate_data_length, KahanSum};
 use crate::indicators::{Candle, Indicator, IndicatorError};
 
 /// On Balance Volume (OBV) indicator
@@ -27,10 +28,11 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// // Calculate OBV values
 /// let obv_values = obv.calculate(&candles).unwrap();
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Obv {
     prev_close: Option<f64>,
-    current_obv: f64,
+    current_obv: KahanSum,
+    last: Option<f64>,
 }
 
 impl Obv {
@@ -38,7 +40,8 @@ impl Obv {
     pub fn new() -> Self {
         Self {
             prev_close: None,
-            current_obv: 0.0,
+            current_obv: KahanSum::new(),
+            last: None,
         }
     }
 }
@@ -60,8 +63,7 @@ impl Indicator<Candle, f64> for Obv {
         self.reset();
 
         // Set first OBV value
-        self.current_obv = 0.0;
-        result.push(self.current_obv);
+        result.push(self.current_obv.value());
         self.prev_close = Some(data[0].close);
 
         // Calculate OBV for each subsequent candle
@@ -72,17 +74,18 @@ impl Indicator<Candle, f64> for Obv {
 
             if close > prev_close {
                 // Up day
-                self.current_obv += volume;
+                self.current_obv.add(volume);
             } else if close < prev_close {
                 // Down day
-                self.current_obv -= volume;
+                self.current_obv.add(-volume);
             }
             // Equal days do not change OBV
 
-            result.push(self.current_obv);
+            result.push(self.current_obv.value());
             self.prev_close = Some(close);
         }
 
+        self.last = result.last().copied();
         Ok(result)
     }
 
@@ -93,26 +96,45 @@ impl Indicator<Candle, f64> for Obv {
 
             if close > prev_close {
                 // Up day
-                self.current_obv += volume;
+                self.current_obv.add(volume);
             } else if close < prev_close {
                 // Down day
-                self.current_obv -= volume;
+                self.current_obv.add(-volume);
             }
             // Equal days do not change OBV
 
             self.prev_close = Some(close);
-            Ok(Some(self.current_obv))
         } else {
             // First value just establishes the baseline
             self.prev_close = Some(value.close);
-            self.current_obv = 0.0;
-            Ok(Some(self.current_obv))
+            self.current_obv.reset();
         }
+
+        self.last = Some(self.current_obv.value());
+        Ok(self.last)
     }
 
     fn reset(&mut self) {
         self.prev_close = None;
-        self.current_obv = 0.0;
+        self.current_obv.reset();
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+}
+
+impl Describe for Obv {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "obv",
+            title: "On-Balance Volume",
+            input: InputKind::Candle,
+            params: &[],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -126,7 +148,7 @@ mod tests {
     fn test_obv_new() {
         // Obv has no parameters to validate
         let obv = Obv::new();
-        assert!(obv.current_obv == 0.0);
+        assert!(obv.current_obv.value() == 0.0);
     }
 
     #[test]
@@ -256,7 +278,7 @@ mod tests {
         obv.reset();
 
         // OBV should be reset to 0
-        assert_eq!(obv.current_obv, 0.0);
+        assert_eq!(obv.current_obv.value(), 0.0);
         assert_eq!(obv.prev_close, None);
 
         // After reset, next candle should be treated as first
@@ -514,7 +536,7 @@ mod tests {
 
         // Should error due to insufficient data (require at least 1 data point)
         assert!(result.is_err());
-        if let Err(IndicatorError::InsufficientData(_)) = result {
+        if let Err(IndicatorError::InsufficientData { .. }) = result {
             // Expected error
         } else {
             panic!("Expected InsufficientData error");
@@ -591,4 +613,26 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_obv_no_drift_over_long_streams() {
+        let mut obv = Obv::new();
+        let candle = |close: f64, volume: f64| Candle {
+            timestamp: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+        };
+        obv.next(candle(10.0, 0.0)).unwrap();
+        obv.next(candle(11.0, 1e15)).unwrap();
+        // Alternate up/down bars: +0.3 on each up day, -0.1 on each down day.
+        let mut last = 0.0;
+        for i in 0..200_000 {
+            let (close, volume) = if i % 2 == 0 { (10.0, 0.1) } else { (11.0, 0.3) };
+            last = obv.next(candle(close, volume)).unwrap().unwrap();
+        }
+        assert!((last - (1e15 + 100_000.0 * 0.2)).abs() < 0.5);
+    }
 }
@@ -0,0 +1,216 @@
+use std::collections::VecDeque;
+
+use crate::indicators::{Indicator, IndicatorError, Tick};
+
+/// Rolling (time-windowed) VWAP over a trade stream
+///
+/// Volume-weighted average price of the trades in the last `window` time
+/// units, the current trade included. Unlike the session [`Vwap`], old
+/// trades drop out as the window moves, so the value tracks the recent
+/// fair price of a continuous market.
+///
+/// # Formula
+///
+/// ```text
+/// vwap = Σ(price * size) / Σ(size)   over trades with timestamp > now - window
+/// ```
+///
+/// A window with no volume gives the last trade price.
+///
+/// # Memory
+///
+/// The buffer holds the trades inside the window, so its size follows the
+/// trade rate, up to [`DEFAULT_MAX_TRADES`](RollingVwap::DEFAULT_MAX_TRADES):
+/// past the cap the oldest trades drop out early.
+/// [`with_max_trades`](RollingVwap::with_max_trades) sets another cap and
+/// reserves it up front, so `next()` no longer allocates.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::RollingVwap;
+/// use rsta::indicators::{Indicator, Tick, TradeSide};
+///
+/// let tick = |timestamp, price, size| Tick { timestamp, price, size, side: TradeSide::Unknown };
+/// let mut vwap = RollingVwap::new(60).unwrap();
+/// assert_eq!(vwap.next(tick(0, 100.0, 1.0)).unwrap(), Some(100.0));
+/// assert_eq!(vwap.next(tick(30, 103.0, 2.0)).unwrap(), Some(102.0));
+/// // At t = 60 the first trade has left the window.
+/// assert_eq!(vwap.next(tick(60, 106.0, 2.0)).unwrap(), Some(104.5));
+/// ```
+///
+/// [`Vwap`]: super::Vwap
+#[derive(Debug, Clone)]
+pub struct RollingVwap {
+    window: u64,
+    trades: VecDeque<Tick>,
+    max_trades: usize,
+    notional: f64,
+    volume: f64,
+    last: Option<f64>,
+}
+
+impl RollingVwap {
+    /// Trades kept unless set with
+    /// [`with_max_trades`](RollingVwap::with_max_trades).
+    pub const DEFAULT_MAX_TRADES: usize = 100_000;
+
+    /// Create a new rolling VWAP
+    ///
+    /// # Arguments
+    /// * `window` - Window length, in the unit of [`Tick::timestamp`] (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(window: u64) -> Result<Self, IndicatorError> {
+        if window == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "window",
+                window,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            window,
+            trades: VecDeque::new(),
+            max_trades: Self::DEFAULT_MAX_TRADES,
+            notional: 0.0,
+            volume: 0.0,
+            last: None,
+        })
+    }
+
+    /// Keep at most `max_trades` trades in the window, dropping the
+    /// oldest ones first, and reserve room for them.
+    ///
+    /// # Arguments
+    /// * `max_trades` - Buffer capacity (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The capped indicator or an error
+    pub fn with_max_trades(mut self, max_trades: usize) -> Result<Self, IndicatorError> {
+        if max_trades == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_trades",
+                max_trades,
+                "must be greater than 0",
+            ));
+        }
+        self.trades.reserve(max_trades);
+        self.max_trades = max_trades;
+        Ok(self)
+    }
+
+    /// Window length.
+    pub fn window(&self) -> u64 {
+        self.window
+    }
+
+    /// Maximum number of trades kept in the window.
+    pub fn max_trades(&self) -> usize {
+        self.max_trades
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.trades.clear();
+        self.notional = 0.0;
+        self.volume = 0.0;
+        self.last = None;
+    }
+
+    fn step(&mut self, tick: Tick) -> f64 {
+        let full = self.max_trades;
+        while let Some(old) = self.trades.front() {
+            if old.timestamp.saturating_add(self.window) > tick.timestamp
+                && self.trades.len() < full
+            {
+                break;
+            }
+            self.notional -= old.notional();
+            self.volume -= old.size;
+            self.trades.pop_front();
+        }
+        self.trades.push_back(tick);
+        if self.trades.len() == 1 {
+            // Start the sums afresh so removals cannot accumulate error.
+            self.notional = tick.notional();
+            self.volume = tick.size;
+        } else {
+            self.notional += tick.notional();
+            self.volume += tick.size;
+        }
+        let vwap = if self.volume > 0.0 {
+            self.notional / self.volume
+        } else {
+            tick.price
+        };
+        self.last = Some(vwap);
+        vwap
+    }
+}
+
+impl Indicator<Tick, f64> for RollingVwap {
+    fn calculate(&mut self, data: &[Tick]) -> Result<Vec<f64>, IndicatorError> {
+        if data.is_empty() {
+            return Err(IndicatorError::insufficient_data(1, 0));
+        }
+        self.reset_state();
+        Ok(data.iter().map(|&tick| self.step(tick)).collect())
+    }
+
+    fn next(&mut self, value: Tick) -> Result<Option<f64>, IndicatorError> {
+        Ok(Some(self.step(value)))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RollingVwap"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::TradeSide;
+
+    #[test]
+    fn test_window_expiry_matches_direct_average() {
+        let ticks: Vec<Tick> = (0..200u64)
+            .map(|i| Tick {
+                timestamp: i * 7,
+                price: 100.0 + (i as f64 * 0.37).sin() * 3.0,
+                size: 1.0 + (i % 5) as f64,
+                side: TradeSide::Unknown,
+            })
+            .collect();
+        let mut vwap = RollingVwap::new(50).unwrap();
+        let values = vwap.calculate(&ticks).unwrap();
+        for (i, value) in values.iter().enumerate() {
+            let now = ticks[i].timestamp;
+            let inside = ticks[..=i].iter().filter(|t| t.timestamp + 50 > now);
+            let (pv, v) = inside.fold((0.0, 0.0), |(pv, v), t| (pv + t.notional(), v + t.size));
+            assert!((value - pv / v).abs() < 1e-9);
+        }
+        assert!(RollingVwap::new(0).is_err());
+        assert_eq!(vwap.window(), 50);
+        assert_eq!(vwap.max_trades(), RollingVwap::DEFAULT_MAX_TRADES);
+
+        // Capped at 3 trades, the average covers the last three only.
+        let mut capped = RollingVwap::new(1_000).unwrap().with_max_trades(3).unwrap();
+        let last = *capped.calculate(&ticks).unwrap().last().unwrap();
+        let (pv, v) = ticks[197..]
+            .iter()
+            .fold((0.0, 0.0), |(pv, v), t| (pv + t.notional(), v + t.size));
+        assert!((last - pv / v).abs() < 1e-9);
+        assert_eq!(capped.max_trades(), 3);
+        assert!(RollingVwap::new(50).unwrap().with_max_trades(0).is_err());
+    }
+}
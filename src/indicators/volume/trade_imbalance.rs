@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Indicator, IndicatorError, Tick, TickRule};
+
+/// Trade Imbalance
+///
+/// Order-flow imbalance over the last `period` trades: the share of
+/// traded volume that was bought minus the share that was sold. Ranges
+/// from `-1` (all sells) to `1` (all buys). Trades are signed by their
+/// reported aggressor side, or by the [`TickRule`] when the feed does not
+/// report one.
+///
+/// # Formula
+///
+/// ```text
+/// imbalance = (buy_volume - sell_volume) / (buy_volume + sell_volume)
+/// ```
+///
+/// A window with no classified volume gives `0`.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::TradeImbalance;
+/// use rsta::indicators::{Indicator, Tick, TradeSide};
+///
+/// let tick = |size, side| Tick { timestamp: 0, price: 100.0, size, side };
+/// let mut imbalance = TradeImbalance::new(3).unwrap();
+/// let values = imbalance
+///     .calculate(&[
+///         tick(3.0, TradeSide::Buy),
+///         tick(1.0, TradeSide::Sell),
+///         tick(4.0, TradeSide::Buy),
+///         tick(8.0, TradeSide::Sell),
+///     ])
+///     .unwrap();
+/// assert_eq!(values, vec![0.75, -5.0 / 13.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TradeImbalance {
+    period: usize,
+    rule: TickRule,
+    /// Signed size of each trade in the window.
+    window: VecDeque<f64>,
+    buy_volume: f64,
+    sell_volume: f64,
+    last: Option<f64>,
+}
+
+impl TradeImbalance {
+    /// Create a new trade imbalance indicator
+    ///
+    /// # Arguments
+    /// * `period` - Number of trades in the window (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new indicator or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self {
+            period,
+            rule: TickRule::new(),
+            window: VecDeque::with_capacity(period + 1),
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            last: None,
+        })
+    }
+
+    /// Reset the indicator state
+    pub fn reset_state(&mut self) {
+        self.rule.reset();
+        self.window.clear();
+        self.buy_volume = 0.0;
+        self.sell_volume = 0.0;
+        self.last = None;
+    }
+
+    fn add(&mut self, signed: f64, weight: f64) {
+        if signed > 0.0 {
+            self.buy_volume += weight * signed;
+        } else {
+            self.sell_volume -= weight * signed;
+        }
+    }
+
+    fn step(&mut self, tick: Tick) -> Option<f64> {
+        let signed = self.rule.side(&tick).sign() * tick.size;
+        self.window.push_back(signed);
+        self.add(signed, 1.0);
+        if self.window.len() > self.period {
+            let old = self.window.pop_front().expect("window is not empty");
+            self.add(old, -1.0);
+        }
+        if self.window.len() < self.period {
+            return None;
+        }
+        // Sums can drift slightly below zero after removals.
+        let (buy, sell) = (self.buy_volume.max(0.0), self.sell_volume.max(0.0));
+        let total = buy + sell;
+        self.last = Some(if total > 0.0 {
+            (buy - sell) / total
+        } else {
+            0.0
+        });
+        self.last
+    }
+}
+
+impl Indicator<Tick, f64> for TradeImbalance {
+    fn calculate(&mut self, data: &[Tick]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        Ok(data.iter().filter_map(|&tick| self.step(tick)).collect())
+    }
+
+    fn next(&mut self, value: Tick) -> Result<Option<f64>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "TradeImbalance"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::TradeSide;
+
+    #[test]
+    fn test_imbalance_bounds_and_tick_rule() {
+        let tick = |price| Tick {
+            timestamp: 0,
+            price,
+            size: 1.0,
+            side: TradeSide::Unknown,
+        };
+        let mut imbalance = TradeImbalance::new(2).unwrap();
+        // Unclassified first trade, then two upticks, then two downticks.
+        let values = imbalance
+            .calculate(&[tick(5.0), tick(6.0), tick(7.0), tick(6.0), tick(5.0)])
+            .unwrap();
+        assert_eq!(values, vec![1.0, 1.0, 0.0, -1.0]);
+        assert_eq!(imbalance.next(tick(5.0)).unwrap(), Some(-1.0));
+        assert!(TradeImbalance::new(0).is_err());
+        assert!(imbalance.calculate(&[tick(1.0)]).is_err());
+    }
+}
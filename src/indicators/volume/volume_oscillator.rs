@@ -0,0 +1,284 @@
+use crate::indicators::trend::{MaType, MovingAverage};
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::volume::ZeroVolumePolicy;
+use crate::indicators::{Candle, Indicator, IndicatorError, IndicatorSeries};
+
+/// Volume Oscillator indicator
+///
+/// Percentage difference between a fast and a slow moving average of
+/// volume: `100 * (fast - slow) / slow`. Positive readings mean recent
+/// volume runs above its longer average, which confirms a price move;
+/// negative readings flag a move on fading participation.
+///
+/// Both averages are EMAs by default; [`with_ma`](VolumeOscillator::with_ma)
+/// picks another [`MaType`].
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::VolumeOscillator;
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// let mut vo = VolumeOscillator::new(5, 10).unwrap();
+///
+/// // Flat volume, then a surge
+/// let candles: Vec<Candle> = (0..20)
+///     .map(|i| Candle {
+///         timestamp: i as u64,
+///         open: 10.0, high: 10.5, low: 9.5, close: 10.0,
+///         volume: if i < 15 { 1000.0 } else { 3000.0 },
+///     })
+///     .collect();
+///
+/// let values = vo.calculate(&candles).unwrap();
+/// assert_eq!(values[14], 0.0);
+/// assert!(*values.last().unwrap() > 0.0);
+/// ```
+///
+/// # Zero slow average
+///
+/// When the slow average is zero the oscillator is undefined; it reads 0
+/// by default, and [`with_zero_volume`](VolumeOscillator::with_zero_volume)
+/// skips those bars or fails instead, as for [`Vroc`](super::Vroc).
+#[derive(Debug, Clone)]
+pub struct VolumeOscillator {
+    fast_period: usize,
+    slow_period: usize,
+    ma_type: MaType,
+    fast_ma: MovingAverage,
+    slow_ma: MovingAverage,
+    zero_volume: ZeroVolumePolicy,
+    index: usize,
+    last: Option<f64>,
+}
+
+impl VolumeOscillator {
+    /// Create a new Volume Oscillator on EMAs
+    ///
+    /// # Arguments
+    /// * `fast_period` - The period for the fast volume average (typically 5)
+    /// * `slow_period` - The period for the slow volume average (typically 10)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new VolumeOscillator or an error
+    pub fn new(fast_period: usize, slow_period: usize) -> Result<Self, IndicatorError> {
+        Self::with_ma(fast_period, slow_period, MaType::Ema)
+    }
+
+    /// Create a new Volume Oscillator built on `ma_type` averages
+    ///
+    /// # Arguments
+    /// * `fast_period` - The period for the fast volume average
+    /// * `slow_period` - The period for the slow volume average
+    /// * `ma_type` - The moving average used for both lines
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new VolumeOscillator or an error
+    pub fn with_ma(
+        fast_period: usize,
+        slow_period: usize,
+        ma_type: MaType,
+    ) -> Result<Self, IndicatorError> {
+        validate_period(fast_period, 1)?;
+        validate_period(slow_period, 1)?;
+        if fast_period >= slow_period {
+            return Err(IndicatorError::invalid_parameter(
+                "slow_period",
+                slow_period,
+                format!("must be greater than fast_period ({fast_period})"),
+            ));
+        }
+
+        Ok(Self {
+            fast_period,
+            slow_period,
+            ma_type,
+            fast_ma: MovingAverage::new(ma_type, fast_period)?,
+            slow_ma: MovingAverage::new(ma_type, slow_period)?,
+            zero_volume: ZeroVolumePolicy::Zero,
+            index: 0,
+            last: None,
+        })
+    }
+
+    /// Choose what to emit when the slow average is zero
+    ///
+    /// # Arguments
+    /// * `policy` - Zero-volume handling
+    ///
+    /// # Returns
+    /// * `Self` - The VolumeOscillator with the new policy
+    pub fn with_zero_volume(mut self, policy: ZeroVolumePolicy) -> Self {
+        self.zero_volume = policy;
+        self
+    }
+
+    /// Zero-volume handling in use
+    pub fn zero_volume(&self) -> ZeroVolumePolicy {
+        self.zero_volume
+    }
+
+    /// Moving average used for both lines
+    pub fn ma_type(&self) -> MaType {
+        self.ma_type
+    }
+
+    /// Fast and slow periods
+    pub fn periods(&self) -> (usize, usize) {
+        (self.fast_period, self.slow_period)
+    }
+
+    /// Reset the Volume Oscillator state
+    pub fn reset_state(&mut self) {
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.index = 0;
+        self.last = None;
+    }
+
+    fn step(&mut self, volume: f64) -> Result<Option<f64>, IndicatorError> {
+        let index = self.index;
+        self.index += 1;
+        let fast = self.fast_ma.next(volume)?;
+        let slow = self.slow_ma.next(volume)?;
+        let (Some(fast), Some(slow)) = (fast, slow) else {
+            return Ok(None);
+        };
+
+        let value = if slow == 0.0 {
+            match self.zero_volume {
+                ZeroVolumePolicy::Zero => 0.0,
+                ZeroVolumePolicy::Skip => return Ok(None),
+                ZeroVolumePolicy::Error => {
+                    return Err(IndicatorError::division_by_zero(
+                        "slow volume average",
+                        index,
+                    ))
+                }
+            }
+        } else {
+            100.0 * (fast - slow) / slow
+        };
+        self.last = Some(value);
+        Ok(self.last)
+    }
+}
+
+impl Indicator<Candle, f64> for VolumeOscillator {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len());
+        for candle in data {
+            if let Some(value) = self.step(candle.volume)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn calculate_aligned(&mut self, data: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        // A skipped bar keeps its place as `None`.
+        data.iter().map(|candle| self.step(candle.volume)).collect()
+    }
+
+    fn calculate_series(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<IndicatorSeries<f64>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.step(value.volume)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.fast_ma.offset().max(self.slow_ma.offset()) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(volumes: &[f64]) -> Vec<Candle> {
+        volumes
+            .iter()
+            .enumerate()
+            .map(|(i, &volume)| Candle {
+                timestamp: i as u64,
+                open: 10.0,
+                high: 11.0,
+                low: 9.0,
+                close: 10.0,
+                volume,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_volume_oscillator_new() {
+        assert!(VolumeOscillator::new(5, 10).is_ok());
+        assert!(VolumeOscillator::new(10, 5).is_err());
+        assert!(VolumeOscillator::new(5, 5).is_err());
+        assert!(VolumeOscillator::new(0, 10).is_err());
+    }
+
+    #[test]
+    fn test_volume_oscillator_sma_values() {
+        let mut vo = VolumeOscillator::with_ma(2, 4, MaType::Sma).unwrap();
+        assert_eq!(vo.min_periods(), 4);
+        let data = candles(&[100.0, 100.0, 200.0, 400.0, 400.0]);
+        let values = vo.calculate(&data).unwrap();
+        // Bar 3: fast = 300, slow = 200 → +50%
+        // Bar 4: fast = 400, slow = 275
+        assert_eq!(values.len(), 2);
+        assert!((values[0] - 50.0).abs() < 1e-9);
+        assert!((values[1] - 100.0 * 125.0 / 275.0).abs() < 1e-9);
+
+        let mut stream = VolumeOscillator::with_ma(2, 4, MaType::Sma).unwrap();
+        let streamed: Vec<f64> = data
+            .iter()
+            .filter_map(|c| stream.next(*c).unwrap())
+            .collect();
+        assert_eq!(streamed, values);
+    }
+
+    #[test]
+    fn test_volume_oscillator_zero_volume() {
+        let data = candles(&[0.0, 0.0, 0.0, 100.0]);
+        let mut vo = VolumeOscillator::with_ma(1, 2, MaType::Sma).unwrap();
+        assert_eq!(vo.calculate(&data).unwrap()[..2], [0.0, 0.0]);
+
+        let mut skip = VolumeOscillator::with_ma(1, 2, MaType::Sma)
+            .unwrap()
+            .with_zero_volume(ZeroVolumePolicy::Skip);
+        assert_eq!(skip.calculate(&data).unwrap(), vec![100.0]);
+        // A zero slow average mid-series leaves that bar empty.
+        let gap = candles(&[100.0, 100.0, 0.0, 0.0, 100.0]);
+        assert_eq!(
+            skip.calculate_aligned(&gap).unwrap(),
+            vec![None, Some(0.0), Some(-100.0), None, Some(100.0)]
+        );
+
+        let mut strict = VolumeOscillator::with_ma(1, 2, MaType::Sma)
+            .unwrap()
+            .with_zero_volume(ZeroVolumePolicy::Error);
+        assert!(matches!(
+            strict.calculate(&data),
+            Err(IndicatorError::DivisionByZero { .. })
+        ));
+    }
+}
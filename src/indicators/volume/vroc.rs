@@ -1,7 +1,28 @@
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind, ParamDescriptor};
 use crate::indicators::utils::{validate_data_length, validate_period};
-use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::indicators::{Candle, Indicator, IndicatorError, IndicatorSeries};
 use std::collections::VecDeque;
 
+/// What [`Vroc`] emits when the reference volume is zero
+///
+/// Zero-volume bars are routine on low-liquidity assets and the rate of
+/// change from them is undefined. The bar itself still enters the window
+/// either way; only the output for the bar `period` bars later changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ZeroVolumePolicy {
+    /// Emit 0.0, keeping batch output one value per bar past warm-up.
+    #[default]
+    Zero,
+    /// Emit nothing: `next()` returns `Ok(None)` and `calculate()` leaves
+    /// the bar out of its output, so that output is no longer right-aligned
+    /// with the input. `calculate_aligned()` and `calculate_series()` keep
+    /// the bar's place empty instead.
+    Skip,
+    /// Strict mode: fail with [`IndicatorError::DivisionByZero`].
+    Error,
+}
+
 /// Volume Rate of Change indicator
 ///
 /// Volume Rate of Change measures the percentage change in volume over a given period.
@@ -99,10 +120,37 @@ use std::collections::VecDeque;
 ///     }
 /// }
 /// ```
-#[derive(Debug)]
+///
+/// # Zero reference volume
+///
+/// When the volume `period` bars back is zero, VROC reads 0 by default. Use
+/// [`with_zero_volume`](Vroc::with_zero_volume) to skip those bars or to
+/// fail instead:
+///
+/// ```
+/// use rsta::indicators::volume::{Vroc, ZeroVolumePolicy};
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// let candles: Vec<Candle> = [0.0, 500.0, 1000.0]
+///     .iter()
+///     .enumerate()
+///     .map(|(i, &volume)| Candle {
+///         timestamp: i as u64, open: 1.0, high: 1.0, low: 1.0, close: 1.0, volume,
+///     })
+///     .collect();
+///
+/// assert_eq!(Vroc::new(1).unwrap().calculate(&candles).unwrap(), vec![0.0, 100.0]);
+///
+/// let mut strict = Vroc::new(1).unwrap().with_zero_volume(ZeroVolumePolicy::Error);
+/// assert!(strict.calculate(&candles).is_err());
+/// ```
+#[derive(Debug, Clone)]
 pub struct Vroc {
     period: usize,
     volume_buffer: VecDeque<f64>,
+    zero_volume: ZeroVolumePolicy,
+    index: usize,
+    last: Option<f64>,
 }
 
 impl Vroc {
@@ -118,65 +166,124 @@ impl Vroc {
 
         Ok(Self {
             period,
-            volume_buffer: VecDeque::with_capacity(period + 1),
+            volume_buffer: VecDeque::with_capacity(period + 2),
+            zero_volume: ZeroVolumePolicy::Zero,
+            index: 0,
+            last: None,
         })
     }
+
+    /// Choose what to emit when the reference volume is zero
+    ///
+    /// # Arguments
+    /// * `policy` - Zero-volume handling
+    ///
+    /// # Returns
+    /// * `Self` - The Vroc with the new policy
+    pub fn with_zero_volume(mut self, policy: ZeroVolumePolicy) -> Self {
+        self.zero_volume = policy;
+        self
+    }
+
+    /// Zero-volume handling in use
+    pub fn zero_volume(&self) -> ZeroVolumePolicy {
+        self.zero_volume
+    }
+
+    fn step(&mut self, volume: f64) -> Result<Option<f64>, IndicatorError> {
+        let index = self.index;
+        self.index += 1;
+        self.volume_buffer.push_back(volume);
+
+        if self.volume_buffer.len() > self.period + 1 {
+            self.volume_buffer.pop_front();
+        }
+
+        if self.volume_buffer.len() < self.period + 1 {
+            return Ok(None);
+        }
+
+        let past_volume = self.volume_buffer[0];
+        let vroc = if past_volume == 0.0 {
+            match self.zero_volume {
+                ZeroVolumePolicy::Zero => 0.0,
+                ZeroVolumePolicy::Skip => return Ok(None),
+                ZeroVolumePolicy::Error => {
+                    return Err(IndicatorError::division_by_zero("past volume", index))
+                }
+            }
+        } else {
+            (volume - past_volume) / past_volume * 100.0
+        };
+
+        self.last = Some(vroc);
+        Ok(self.last)
+    }
 }
 
 impl Indicator<Candle, f64> for Vroc {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
         validate_data_length(data, self.period + 1)?;
 
-        let n = data.len();
-        let mut result = Vec::with_capacity(n - self.period);
+        let mut result = Vec::with_capacity(data.len() - self.period);
 
         // Reset state
         self.reset();
 
         // Cannot calculate until we have period + 1 values
-        for i in self.period..n {
-            let current_volume = data[i].volume;
-            let past_volume = data[i - self.period].volume;
-
-            if past_volume == 0.0 {
-                return Err(IndicatorError::CalculationError(
-                    "Division by zero: past volume is zero".to_string(),
-                ));
+        for candle in data {
+            if let Some(vroc) = self.step(candle.volume)? {
+                result.push(vroc);
             }
-
-            let vroc = (current_volume - past_volume) / past_volume * 100.0;
-            result.push(vroc);
         }
 
         Ok(result)
     }
 
-    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
-        self.volume_buffer.push_back(value.volume);
-
-        if self.volume_buffer.len() > self.period + 1 {
-            self.volume_buffer.pop_front();
-        }
-
-        if self.volume_buffer.len() == self.period + 1 {
-            let current_volume = self.volume_buffer.back().unwrap();
-            let past_volume = self.volume_buffer.front().unwrap();
+    fn calculate_aligned(&mut self, data: &[Candle]) -> Result<Vec<Option<f64>>, IndicatorError> {
+        validate_data_length(data, self.period + 1)?;
+        self.reset();
+        // A skipped bar keeps its place as `None`.
+        data.iter().map(|candle| self.step(candle.volume)).collect()
+    }
 
-            if *past_volume == 0.0 {
-                return Err(IndicatorError::CalculationError(
-                    "Division by zero: past volume is zero".to_string(),
-                ));
-            }
+    fn calculate_series(
+        &mut self,
+        data: &[Candle],
+    ) -> Result<IndicatorSeries<f64>, IndicatorError> {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
 
-            let vroc = (current_volume - past_volume) / past_volume * 100.0;
-            Ok(Some(vroc))
-        } else {
-            Ok(None)
-        }
+    fn next(&mut self, value: Candle) -> Result<Option<f64>, IndicatorError> {
+        self.step(value.volume)
     }
 
     fn reset(&mut self) {
         self.volume_buffer.clear();
+        self.index = 0;
+        self.last = None;
+    }
+
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+}
+
+impl Describe for Vroc {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "vroc",
+            title: "Volume Rate of Change",
+            input: InputKind::Candle,
+            params: &[ParamDescriptor::period("period", 1)],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
     }
 }
 
@@ -328,7 +435,9 @@ mod tests {
 
     #[test]
     fn test_vroc_past_volume_zero() {
-        let mut vroc = Vroc::new(2).unwrap();
+        let mut vroc = Vroc::new(2)
+            .unwrap()
+            .with_zero_volume(ZeroVolumePolicy::Error);
 
         // Create candles with zero volume at the reference point
         let candles = vec![
@@ -362,12 +471,11 @@ mod tests {
         let result = vroc.calculate(&candles);
         assert!(result.is_err());
 
-        // Verify it's the correct error type and message
-        if let Err(IndicatorError::CalculationError(msg)) = result {
-            assert!(msg.contains("division by zero") || msg.contains("zero"));
-        } else {
-            panic!("Expected CalculationError");
-        }
+        // Verify it's the correct error type and position
+        assert_eq!(
+            result.unwrap_err(),
+            IndicatorError::division_by_zero("past volume", 2)
+        );
 
         // Test with streaming calculation too
         vroc.reset();
@@ -379,11 +487,67 @@ mod tests {
         assert!(next_result.is_err());
 
         // Verify it's the correct error type
-        if let Err(IndicatorError::CalculationError(msg)) = next_result {
-            assert!(msg.contains("division by zero") || msg.contains("zero"));
-        } else {
-            panic!("Expected CalculationError");
-        }
+        assert!(matches!(
+            next_result,
+            Err(IndicatorError::DivisionByZero { index: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_vroc_zero_volume_policies() {
+        let candles: Vec<Candle> = [0.0, 1000.0, 1500.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &volume)| Candle {
+                timestamp: i as u64,
+                open: 10.0,
+                high: 11.0,
+                low: 9.0,
+                close: 10.0,
+                volume,
+            })
+            .collect();
+
+        let mut vroc = Vroc::new(1).unwrap();
+        assert_eq!(vroc.zero_volume(), ZeroVolumePolicy::Zero);
+        assert_eq!(vroc.calculate(&candles).unwrap(), vec![0.0, 50.0]);
+
+        let mut vroc = Vroc::new(1)
+            .unwrap()
+            .with_zero_volume(ZeroVolumePolicy::Skip);
+        assert_eq!(vroc.calculate(&candles).unwrap(), vec![50.0]);
+        vroc.reset();
+        assert_eq!(vroc.next(candles[0]).unwrap(), None);
+        assert_eq!(vroc.next(candles[1]).unwrap(), None);
+        assert_eq!(vroc.next(candles[2]).unwrap(), Some(50.0));
+    }
+
+    #[test]
+    fn test_vroc_skip_keeps_alignment() {
+        // The zero-volume bar in the middle leaves bar 4 without a value.
+        let candles: Vec<Candle> = [1000.0, 1200.0, 0.0, 900.0, 1500.0, 1800.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &volume)| Candle {
+                timestamp: i as u64,
+                open: 10.0,
+                high: 11.0,
+                low: 9.0,
+                close: 10.0,
+                volume,
+            })
+            .collect();
+        let mut vroc = Vroc::new(2)
+            .unwrap()
+            .with_zero_volume(ZeroVolumePolicy::Skip);
+        assert_eq!(
+            vroc.calculate_aligned(&candles).unwrap(),
+            vec![None, None, Some(-100.0), Some(-25.0), None, Some(100.0)]
+        );
+        let series = vroc.calculate_series(&candles).unwrap();
+        assert_eq!(series.get(4), None);
+        assert_eq!(series.at(5), Some(&100.0));
+        assert_eq!(series.timestamps(), &[2, 3, 5]);
     }
 
     #[test]
@@ -0,0 +1,276 @@
+use std::collections::VecDeque;
+
+use crate::indicators::trend::{EmaInit, MaType, MacdResult, MovingAverage};
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Volume-weighted moving average of closes over a fixed window.
+#[derive(Debug, Clone)]
+struct Vwma {
+    period: usize,
+    window: VecDeque<(f64, f64)>,
+}
+
+impl Vwma {
+    fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+        }
+    }
+
+    /// Push a bar; `Ok(None)` until the window is full.
+    fn next(&mut self, candle: &Candle, index: usize) -> Result<Option<f64>, IndicatorError> {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window
+            .push_back((candle.close * candle.volume, candle.volume));
+        if self.window.len() < self.period {
+            return Ok(None);
+        }
+        let (pv, v) = self
+            .window
+            .iter()
+            .fold((0.0, 0.0), |(pv, v), &(p, w)| (pv + p, v + w));
+        if v == 0.0 {
+            return Err(IndicatorError::division_by_zero("volume sum", index));
+        }
+        Ok(Some(pv / v))
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Volume-Weighted MACD indicator
+///
+/// MACD built on volume-weighted moving averages of the close: the line is
+/// `VWMA(fast) - VWMA(slow)`, the signal an EMA of the line and the
+/// histogram their difference, returned as a [`MacdResult`]. Bars with
+/// heavy volume pull the averages harder than quiet ones, so moves on
+/// strong participation show up sooner than in the plain
+/// [`Macd`](crate::indicators::trend::Macd).
+///
+/// The first result comes once the slow window is full; a window with no
+/// volume at all fails with [`IndicatorError::DivisionByZero`].
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::volume::VwMacd;
+/// use rsta::indicators::{Candle, Indicator};
+///
+/// let mut vw_macd = VwMacd::new(12, 26, 9).unwrap();
+///
+/// let candles: Vec<Candle> = (0..40)
+///     .map(|i| {
+///         let close = 100.0 + i as f64;
+///         Candle {
+///             timestamp: i as u64,
+///             open: close - 0.5, high: close + 1.0, low: close - 1.0, close,
+///             volume: 1000.0 + 10.0 * i as f64,
+///         }
+///     })
+///     .collect();
+///
+/// let values = vw_macd.calculate(&candles).unwrap();
+/// assert_eq!(values.len(), candles.len() - 25);
+/// // Rising prices: the fast average leads
+/// assert!(values.last().unwrap().macd > 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VwMacd {
+    fast_period: usize,
+    slow_period: usize,
+    fast: Vwma,
+    slow: Vwma,
+    signal_ma: MovingAverage,
+    index: usize,
+    last: Option<MacdResult>,
+}
+
+impl VwMacd {
+    /// Create a new Volume-Weighted MACD indicator
+    ///
+    /// # Arguments
+    /// * `fast_period` - The period for the fast VWMA (typically 12)
+    /// * `slow_period` - The period for the slow VWMA (typically 26)
+    /// * `signal_period` - The period for the signal line EMA (typically 9)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new VwMacd or an error
+    pub fn new(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self, IndicatorError> {
+        validate_period(fast_period, 1)?;
+        validate_period(slow_period, 1)?;
+        validate_period(signal_period, 1)?;
+        if fast_period >= slow_period {
+            return Err(IndicatorError::invalid_parameter(
+                "slow_period",
+                slow_period,
+                format!("must be greater than fast_period ({fast_period})"),
+            ));
+        }
+
+        Ok(Self {
+            fast_period,
+            slow_period,
+            fast: Vwma::new(fast_period),
+            slow: Vwma::new(slow_period),
+            signal_ma: MovingAverage::new(MaType::Ema, signal_period)?,
+            index: 0,
+            last: None,
+        })
+    }
+
+    /// Choose how the signal EMA is seeded (see [`EmaInit`])
+    ///
+    /// # Arguments
+    /// * `init` - Seeding method
+    ///
+    /// # Returns
+    /// * `Self` - The VwMacd with the new seeding method
+    pub fn with_init(mut self, init: EmaInit) -> Self {
+        self.signal_ma = self.signal_ma.with_init(init);
+        self
+    }
+
+    /// Fast and slow VWMA periods
+    pub fn periods(&self) -> (usize, usize) {
+        (self.fast_period, self.slow_period)
+    }
+
+    /// Reset the VwMacd state
+    pub fn reset_state(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+        self.signal_ma.reset();
+        self.index = 0;
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: &Candle) -> Result<Option<MacdResult>, IndicatorError> {
+        let index = self.index;
+        self.index += 1;
+        let fast = self.fast.next(candle, index)?;
+        let slow = self.slow.next(candle, index)?;
+        let (Some(fast), Some(slow)) = (fast, slow) else {
+            return Ok(None);
+        };
+
+        let macd = fast - slow;
+        let Some(signal) = self.signal_ma.next(macd)? else {
+            return Ok(None);
+        };
+
+        self.last = Some(MacdResult {
+            macd,
+            signal,
+            histogram: macd - signal,
+        });
+        Ok(self.last)
+    }
+}
+
+impl Indicator<Candle, MacdResult> for VwMacd {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<MacdResult>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() + 1 - self.min_periods());
+        for candle in data {
+            if let Some(value) = self.step(candle)? {
+                result.push(value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<MacdResult>, IndicatorError> {
+        self.step(&value)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&MacdResult> {
+        self.last.as_ref()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.slow_period + self.signal_ma.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::Macd;
+
+    fn candle(close: f64, volume: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_vw_macd_new() {
+        assert!(VwMacd::new(12, 26, 9).is_ok());
+        assert!(VwMacd::new(26, 12, 9).is_err());
+        assert!(VwMacd::new(0, 26, 9).is_err());
+        assert!(VwMacd::new(12, 26, 0).is_err());
+    }
+
+    #[test]
+    fn test_vw_macd_weights_by_volume() {
+        let data = [candle(10.0, 1.0), candle(20.0, 3.0), candle(30.0, 1.0)];
+        let mut vw_macd = VwMacd::new(1, 3, 1).unwrap();
+        let values = vw_macd.calculate(&data).unwrap();
+        // Slow VWMA = (10 + 60 + 30) / 5 = 20, fast = 30
+        assert_eq!(values.len(), 1);
+        assert!((values[0].macd - 10.0).abs() < 1e-12);
+        assert!((values[0].histogram).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_vw_macd_flat_volume_matches_sma_macd() {
+        let data: Vec<Candle> = (0..30)
+            .map(|i| candle(100.0 + (i as f64 * 0.5).sin() * 3.0, 500.0))
+            .collect();
+        let mut vw_macd = VwMacd::new(3, 6, 2).unwrap();
+        let mut macd = Macd::with_ma(3, 6, 2, MaType::Sma).unwrap();
+        let weighted = vw_macd.calculate(&data).unwrap();
+        let plain = macd.calculate(&data).unwrap();
+        // The plain MACD's SMA signal line waits one bar longer
+        for (w, p) in weighted[1..].iter().zip(&plain) {
+            assert!((w.macd - p.macd).abs() < 1e-9);
+        }
+
+        let mut stream = VwMacd::new(3, 6, 2).unwrap();
+        let streamed: Vec<MacdResult> = data
+            .iter()
+            .filter_map(|c| stream.next(*c).unwrap())
+            .collect();
+        assert_eq!(streamed, weighted);
+    }
+
+    #[test]
+    fn test_vw_macd_zero_volume_window() {
+        let data = [candle(10.0, 0.0), candle(11.0, 0.0), candle(12.0, 0.0)];
+        let mut vw_macd = VwMacd::new(1, 2, 1).unwrap();
+        assert!(matches!(
+            vw_macd.calculate(&data),
+            Err(IndicatorError::DivisionByZero { .. })
+        ));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::indicators::descriptor::{Describe, Descriptor, InputKind};
+use crate::indicators::{Candle, Indicator, IndicatorError, KahanSum};
 
 /// Volume Weighted Average Price (VWAP) indicator.
 ///
@@ -22,10 +23,11 @@ use crate::indicators::{Candle, Indicator, IndicatorError};
 /// let values = vwap.calculate(&candles).unwrap();
 /// assert_eq!(values.len(), candles.len());
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct Vwap {
-    cumulative_tp_volume: f64,
-    cumulative_volume: f64,
+    cumulative_tp_volume: KahanSum,
+    cumulative_volume: KahanSum,
+    last: Option<f64>,
 }
 
 impl Vwap {
@@ -36,27 +38,30 @@ impl Vwap {
 
     /// Reset session accumulators (call at each new session start).
     pub fn reset_state(&mut self) {
-        self.cumulative_tp_volume = 0.0;
-        self.cumulative_volume = 0.0;
+        self.cumulative_tp_volume.reset();
+        self.cumulative_volume.reset();
+        self.last = None;
     }
 
     fn step(&mut self, value: Candle) -> f64 {
         let tp = (value.high + value.low + value.close) / 3.0;
-        self.cumulative_tp_volume += tp * value.volume;
-        self.cumulative_volume += value.volume;
-        if self.cumulative_volume == 0.0 {
-            return tp;
-        }
-        self.cumulative_tp_volume / self.cumulative_volume
+        self.cumulative_tp_volume.add(tp * value.volume);
+        self.cumulative_volume.add(value.volume);
+        let volume = self.cumulative_volume.value();
+        let vwap = if volume == 0.0 {
+            tp
+        } else {
+            self.cumulative_tp_volume.value() / volume
+        };
+        self.last = Some(vwap);
+        vwap
     }
 }
 
 impl Indicator<Candle, f64> for Vwap {
     fn calculate(&mut self, data: &[Candle]) -> Result<Vec<f64>, IndicatorError> {
         if data.is_empty() {
-            return Err(IndicatorError::InsufficientData(
-                "VWAP requires at least one candle".to_string(),
-            ));
+            return Err(IndicatorError::insufficient_data(1, 0));
         }
         self.reset_state();
         let mut out = Vec::with_capacity(data.len());
@@ -74,11 +79,28 @@ impl Indicator<Candle, f64> for Vwap {
         self.reset_state();
     }
 
+    fn current(&self) -> Option<&f64> {
+        self.last.as_ref()
+    }
+
     fn name(&self) -> &'static str {
         "Vwap"
     }
 }
 
+impl Describe for Vwap {
+    fn descriptor() -> &'static Descriptor {
+        const DESCRIPTOR: Descriptor = Descriptor {
+            name: "vwap",
+            title: "Volume Weighted Average Price",
+            input: InputKind::Candle,
+            params: &[],
+            outputs: &["value"],
+        };
+        &DESCRIPTOR
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +170,24 @@ mod tests {
         };
         assert_eq!(vwap.next(new).unwrap(), Some(50.0));
     }
+
+    #[test]
+    fn no_drift_over_long_sessions() {
+        let mut vwap = Vwap::new();
+        let candle = |price: f64, volume: f64| Candle {
+            timestamp: 0,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+        };
+        vwap.next(candle(10.0, 1e15)).unwrap();
+        let mut last = 0.0;
+        for _ in 0..200_000 {
+            last = vwap.next(candle(20.0, 0.1)).unwrap().unwrap();
+        }
+        let expected = (1e16 + 4e5) / (1e15 + 2e4);
+        assert!((last - expected).abs() < 1e-12, "{last} vs {expected}");
+    }
 }
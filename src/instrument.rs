@@ -0,0 +1,123 @@
+//! Optional [`tracing`](https://docs.rs/tracing) instrumentation.
+//!
+//! The macros forward to `tracing` when the `tracing` feature is enabled
+//! and expand to nothing otherwise, so instrumented code needs no `cfg`
+//! of its own. Span macros return a guard that keeps the span entered
+//! until it is dropped.
+
+// Not every feature combination compiles code using every macro.
+#![allow(unused_macros)]
+
+#[cfg(feature = "tracing")]
+macro_rules! trace {
+    ($($arg:tt)*) => { ::tracing::trace!(target: "rsta", $($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! debug {
+    ($($arg:tt)*) => { ::tracing::debug!(target: "rsta", $($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! warning {
+    ($($arg:tt)*) => { ::tracing::warn!(target: "rsta", $($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! warning {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! debug_span {
+    ($($arg:tt)*) => { ::tracing::debug_span!(target: "rsta", $($arg)*).entered() };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug_span {
+    ($($arg:tt)*) => {
+        $crate::instrument::NoSpan
+    };
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! info_span {
+    ($($arg:tt)*) => { ::tracing::info_span!(target: "rsta", $($arg)*).entered() };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! info_span {
+    ($($arg:tt)*) => {
+        $crate::instrument::NoSpan
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use {debug, debug_span, info_span, trace, warning};
+
+/// Guard returned by the span macros without the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::signals::{CrossUp, Signal, SignalEvent};
+
+    /// Records the message of every event.
+    #[derive(Default, Clone)]
+    struct Messages(Arc<Mutex<Vec<String>>>);
+
+    impl Subscriber for Messages {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            struct Message<'a>(&'a mut Vec<String>);
+            impl tracing::field::Visit for Message<'_> {
+                fn record_debug(
+                    &mut self,
+                    field: &tracing::field::Field,
+                    value: &dyn std::fmt::Debug,
+                ) {
+                    if field.name() == "message" {
+                        self.0.push(format!("{value:?}"));
+                    }
+                }
+            }
+            event.record(&mut Message(&mut self.0.lock().unwrap()));
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_signals_report_why_they_fired() {
+        let messages = Messages::default();
+        tracing::subscriber::with_default(messages.clone(), || {
+            let mut cross = CrossUp::new();
+            cross.next((1.0, 2.0));
+            assert_eq!(cross.next((3.0, 2.0)), Some(SignalEvent::Long));
+        });
+        assert_eq!(*messages.0.lock().unwrap(), vec!["crossed above"]);
+    }
+}
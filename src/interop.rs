@@ -0,0 +1,618 @@
+//! # `ta` crate interop
+//!
+//! Shims between rsta and the [`ta`](https://docs.rs/ta) crate (0.5 API),
+//! so a codebase can move indicators over one at a time, or run both
+//! libraries side by side to cross-check values.
+//!
+//! rsta does not depend on `ta`. The [`ta_interop!`](crate::ta_interop)
+//! macro expands in the calling crate, which already depends on `ta`, so
+//! rsta users who don't need the interop never build it and the shims
+//! follow whichever `ta` version the caller pins. Invoke it once, at
+//! module level:
+//!
+//! ```ignore
+//! rsta::ta_interop!();
+//!
+//! use rsta::indicators::{Candle, Indicator};
+//! use rsta::indicators::Sma;
+//! use ta::indicators::SimpleMovingAverage;
+//!
+//! // `ta` data items in, rsta candles out (and back)
+//! let item = ta::DataItem::builder()
+//!     .open(10.0).high(11.0).low(9.0).close(10.5).volume(1000.0)
+//!     .build()
+//!     .unwrap();
+//! let candle = candle_from_ta(&item, 0);
+//! let item = candle_to_ta(&candle).unwrap();
+//!
+//! // A `ta` indicator behind the rsta `Indicator` trait, to compare outputs
+//! let prices = [1.0, 2.0, 3.0, 4.0];
+//! let mut theirs = TaIndicator::new(SimpleMovingAverage::new(2).unwrap());
+//! let ours = Sma::new(2).unwrap().calculate(&prices).unwrap();
+//! assert_eq!(theirs.calculate(&prices).unwrap()[1..], ours[..]);
+//! ```
+//!
+//! The macro takes the name `ta` is imported under, `ta` by default:
+//! `rsta::ta_interop!(ta_05)` for a renamed dependency. It defines:
+//!
+//! - `candle_from_ta(&item, timestamp)` and `candle_to_ta(&candle)`: data
+//!   item conversions. `ta` items carry no timestamp, so the caller
+//!   supplies it; `candle_to_ta` fails where `ta`'s builder rejects the
+//!   candle (e.g. `low > high`).
+//! - `TaCandle`: a [`Candle`](crate::indicators::Candle) newtype
+//!   implementing `ta`'s `Open`, `High`, `Low`, `Close` and `Volume`, to
+//!   feed candles to `ta` indicators without a conversion.
+//! - `TaIndicator`: wraps a `ta` indicator as an rsta
+//!   [`Indicator`](crate::indicators::Indicator) over `f64` prices or
+//!   candles. `ta` indicators emit from the first input, so it never
+//!   returns `None`.
+//! - Output shims between `ta`'s multi-line outputs and rsta's results:
+//!   `macd_from_ta` / `macd_to_ta` (with the `trend` feature),
+//!   `bollinger_from_ta` / `bollinger_to_ta` and `keltner_from_ta` /
+//!   `keltner_to_ta` (with `volatility`). Bandwidths are derived from the
+//!   bands the way rsta computes them.
+
+/// Define the [`ta` crate interop](crate::interop) shims in the calling
+/// module
+///
+/// See the [module docs](crate::interop) for the items it defines. The
+/// optional argument is the name the `ta` crate is imported under.
+#[macro_export]
+macro_rules! ta_interop {
+    () => {
+        $crate::ta_interop!(ta);
+    };
+    ($ta:ident) => {
+        /// Convert a `ta` data item into an rsta candle at `timestamp`
+        #[allow(dead_code)]
+        pub fn candle_from_ta<T>(item: &T, timestamp: u64) -> $crate::indicators::Candle
+        where
+            T: $ta::Open + $ta::High + $ta::Low + $ta::Close + $ta::Volume,
+        {
+            $crate::indicators::Candle {
+                timestamp,
+                open: item.open(),
+                high: item.high(),
+                low: item.low(),
+                close: item.close(),
+                volume: item.volume(),
+            }
+        }
+
+        /// Convert an rsta candle into a `ta` data item (the timestamp is
+        /// dropped)
+        #[allow(dead_code)]
+        pub fn candle_to_ta(
+            candle: &$crate::indicators::Candle,
+        ) -> ::core::result::Result<$ta::DataItem, $ta::errors::TaError> {
+            $ta::DataItem::builder()
+                .open(candle.open)
+                .high(candle.high)
+                .low(candle.low)
+                .close(candle.close)
+                .volume(candle.volume)
+                .build()
+        }
+
+        /// An rsta candle usable as a `ta` data item
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct TaCandle(pub $crate::indicators::Candle);
+
+        impl $ta::Open for TaCandle {
+            fn open(&self) -> f64 {
+                self.0.open
+            }
+        }
+
+        impl $ta::High for TaCandle {
+            fn high(&self) -> f64 {
+                self.0.high
+            }
+        }
+
+        impl $ta::Low for TaCandle {
+            fn low(&self) -> f64 {
+                self.0.low
+            }
+        }
+
+        impl $ta::Close for TaCandle {
+            fn close(&self) -> f64 {
+                self.0.close
+            }
+        }
+
+        impl $ta::Volume for TaCandle {
+            fn volume(&self) -> f64 {
+                self.0.volume
+            }
+        }
+
+        /// A `ta` indicator behind the rsta `Indicator` trait
+        #[derive(Debug, Clone)]
+        pub struct TaIndicator<I, O> {
+            inner: I,
+            last: ::core::option::Option<O>,
+        }
+
+        #[allow(dead_code)]
+        impl<I, O> TaIndicator<I, O> {
+            /// Wrap a `ta` indicator
+            pub fn new(inner: I) -> Self {
+                Self { inner, last: None }
+            }
+
+            /// The wrapped `ta` indicator
+            pub fn inner(&self) -> &I {
+                &self.inner
+            }
+
+            /// Unwrap the `ta` indicator
+            pub fn into_inner(self) -> I {
+                self.inner
+            }
+        }
+
+        impl<I, O> $crate::indicators::Indicator<f64, O> for TaIndicator<I, O>
+        where
+            I: $ta::Next<f64, Output = O> + $ta::Reset,
+            O: Clone,
+        {
+            fn calculate(
+                &mut self,
+                data: &[f64],
+            ) -> ::core::result::Result<Vec<O>, $crate::indicators::IndicatorError> {
+                $crate::indicators::validate_data_length(data, 1)?;
+                $crate::indicators::Indicator::<f64, O>::reset(self);
+                data.iter()
+                    .map(|&value| {
+                        $crate::indicators::Indicator::<f64, O>::next(self, value)
+                            .map(|out| out.expect("ta indicators emit on every input"))
+                    })
+                    .collect()
+            }
+
+            fn next(
+                &mut self,
+                value: f64,
+            ) -> ::core::result::Result<
+                ::core::option::Option<O>,
+                $crate::indicators::IndicatorError,
+            > {
+                let out = $ta::Next::next(&mut self.inner, value);
+                self.last = Some(out.clone());
+                Ok(Some(out))
+            }
+
+            fn reset(&mut self) {
+                $ta::Reset::reset(&mut self.inner);
+                self.last = None;
+            }
+
+            fn current(&self) -> ::core::option::Option<&O> {
+                self.last.as_ref()
+            }
+        }
+
+        impl<I, O> $crate::indicators::Indicator<$crate::indicators::Candle, O>
+            for TaIndicator<I, O>
+        where
+            I: for<'a> $ta::Next<&'a TaCandle, Output = O> + $ta::Reset,
+            O: Clone,
+        {
+            fn calculate(
+                &mut self,
+                data: &[$crate::indicators::Candle],
+            ) -> ::core::result::Result<Vec<O>, $crate::indicators::IndicatorError> {
+                $crate::indicators::validate_data_length(data, 1)?;
+                $crate::indicators::Indicator::<$crate::indicators::Candle, O>::reset(self);
+                data.iter()
+                    .map(|&candle| {
+                        $crate::indicators::Indicator::<$crate::indicators::Candle, O>::next(
+                            self, candle,
+                        )
+                        .map(|out| out.expect("ta indicators emit on every input"))
+                    })
+                    .collect()
+            }
+
+            fn next(
+                &mut self,
+                value: $crate::indicators::Candle,
+            ) -> ::core::result::Result<
+                ::core::option::Option<O>,
+                $crate::indicators::IndicatorError,
+            > {
+                let out = $ta::Next::next(&mut self.inner, &TaCandle(value));
+                self.last = Some(out.clone());
+                Ok(Some(out))
+            }
+
+            fn reset(&mut self) {
+                $ta::Reset::reset(&mut self.inner);
+                self.last = None;
+            }
+
+            fn current(&self) -> ::core::option::Option<&O> {
+                self.last.as_ref()
+            }
+        }
+
+        $crate::__ta_interop_trend!($ta);
+        $crate::__ta_interop_volatility!($ta);
+    };
+}
+
+#[cfg(feature = "trend")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ta_interop_trend {
+    ($ta:ident) => {
+        /// Convert a `ta` MACD output into an rsta MACD result
+        #[allow(dead_code)]
+        pub fn macd_from_ta(
+            out: &$ta::indicators::MovingAverageConvergenceDivergenceOutput,
+        ) -> $crate::indicators::trend::MacdResult {
+            $crate::indicators::trend::MacdResult {
+                macd: out.macd,
+                signal: out.signal,
+                histogram: out.histogram,
+            }
+        }
+
+        /// Convert an rsta MACD result into a `ta` MACD output
+        #[allow(dead_code)]
+        pub fn macd_to_ta(
+            result: &$crate::indicators::trend::MacdResult,
+        ) -> $ta::indicators::MovingAverageConvergenceDivergenceOutput {
+            $ta::indicators::MovingAverageConvergenceDivergenceOutput {
+                macd: result.macd,
+                signal: result.signal,
+                histogram: result.histogram,
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "trend"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ta_interop_trend {
+    ($ta:ident) => {};
+}
+
+#[cfg(feature = "volatility")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ta_interop_volatility {
+    ($ta:ident) => {
+        /// Convert a `ta` Bollinger Bands output into an rsta result
+        #[allow(dead_code)]
+        pub fn bollinger_from_ta(
+            out: &$ta::indicators::BollingerBandsOutput,
+        ) -> $crate::indicators::volatility::BollingerBandsResult {
+            $crate::indicators::volatility::BollingerBandsResult {
+                middle: out.average,
+                upper: out.upper,
+                lower: out.lower,
+                bandwidth: (out.upper - out.lower) / out.average,
+            }
+        }
+
+        /// Convert an rsta Bollinger Bands result into a `ta` output
+        #[allow(dead_code)]
+        pub fn bollinger_to_ta(
+            result: &$crate::indicators::volatility::BollingerBandsResult,
+        ) -> $ta::indicators::BollingerBandsOutput {
+            $ta::indicators::BollingerBandsOutput {
+                average: result.middle,
+                upper: result.upper,
+                lower: result.lower,
+            }
+        }
+
+        /// Convert a `ta` Keltner Channel output into an rsta result
+        #[allow(dead_code)]
+        pub fn keltner_from_ta(
+            out: &$ta::indicators::KeltnerChannelOutput,
+        ) -> $crate::indicators::volatility::KeltnerChannelsResult {
+            $crate::indicators::volatility::KeltnerChannelsResult {
+                middle: out.average,
+                upper: out.upper,
+                lower: out.lower,
+                bandwidth: (out.upper - out.lower) / out.average,
+            }
+        }
+
+        /// Convert an rsta Keltner Channels result into a `ta` output
+        #[allow(dead_code)]
+        pub fn keltner_to_ta(
+            result: &$crate::indicators::volatility::KeltnerChannelsResult,
+        ) -> $ta::indicators::KeltnerChannelOutput {
+            $ta::indicators::KeltnerChannelOutput {
+                average: result.middle,
+                upper: result.upper,
+                lower: result.lower,
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "volatility"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ta_interop_volatility {
+    ($ta:ident) => {};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::indicators::{Candle, Indicator};
+
+    /// The parts of the `ta` 0.5 API the shims use. Shims of disabled
+    /// features leave some of them unused.
+    #[allow(dead_code)]
+    mod ta {
+        pub trait Open {
+            fn open(&self) -> f64;
+        }
+        pub trait High {
+            fn high(&self) -> f64;
+        }
+        pub trait Low {
+            fn low(&self) -> f64;
+        }
+        pub trait Close {
+            fn close(&self) -> f64;
+        }
+        pub trait Volume {
+            fn volume(&self) -> f64;
+        }
+        pub trait Next<T> {
+            type Output;
+            fn next(&mut self, input: T) -> Self::Output;
+        }
+        pub trait Reset {
+            fn reset(&mut self);
+        }
+
+        pub mod errors {
+            #[derive(Debug, PartialEq)]
+            pub enum TaError {
+                DataItemIncomplete,
+                DataItemInvalid,
+            }
+        }
+
+        #[derive(Debug, Default)]
+        pub struct DataItem {
+            open: f64,
+            high: f64,
+            low: f64,
+            close: f64,
+            volume: f64,
+        }
+
+        #[derive(Default)]
+        pub struct DataItemBuilder {
+            open: Option<f64>,
+            high: Option<f64>,
+            low: Option<f64>,
+            close: Option<f64>,
+            volume: Option<f64>,
+        }
+
+        impl DataItem {
+            pub fn builder() -> DataItemBuilder {
+                DataItemBuilder::default()
+            }
+        }
+
+        impl DataItemBuilder {
+            pub fn open(mut self, v: f64) -> Self {
+                self.open = Some(v);
+                self
+            }
+            pub fn high(mut self, v: f64) -> Self {
+                self.high = Some(v);
+                self
+            }
+            pub fn low(mut self, v: f64) -> Self {
+                self.low = Some(v);
+                self
+            }
+            pub fn close(mut self, v: f64) -> Self {
+                self.close = Some(v);
+                self
+            }
+            pub fn volume(mut self, v: f64) -> Self {
+                self.volume = Some(v);
+                self
+            }
+            pub fn build(self) -> Result<DataItem, errors::TaError> {
+                let (Some(open), Some(high), Some(low), Some(close), Some(volume)) =
+                    (self.open, self.high, self.low, self.close, self.volume)
+                else {
+                    return Err(errors::TaError::DataItemIncomplete);
+                };
+                if low > high || open < low || open > high || close < low || close > high {
+                    return Err(errors::TaError::DataItemInvalid);
+                }
+                Ok(DataItem {
+                    open,
+                    high,
+                    low,
+                    close,
+                    volume,
+                })
+            }
+        }
+
+        impl Open for DataItem {
+            fn open(&self) -> f64 {
+                self.open
+            }
+        }
+        impl High for DataItem {
+            fn high(&self) -> f64 {
+                self.high
+            }
+        }
+        impl Low for DataItem {
+            fn low(&self) -> f64 {
+                self.low
+            }
+        }
+        impl Close for DataItem {
+            fn close(&self) -> f64 {
+                self.close
+            }
+        }
+        impl Volume for DataItem {
+            fn volume(&self) -> f64 {
+                self.volume
+            }
+        }
+
+        pub mod indicators {
+            use super::{Close, Next, Reset};
+
+            pub struct MovingAverageConvergenceDivergenceOutput {
+                pub macd: f64,
+                pub signal: f64,
+                pub histogram: f64,
+            }
+
+            pub struct BollingerBandsOutput {
+                pub average: f64,
+                pub upper: f64,
+                pub lower: f64,
+            }
+
+            pub struct KeltnerChannelOutput {
+                pub average: f64,
+                pub upper: f64,
+                pub lower: f64,
+            }
+
+            /// Cumulative mean, emitting from the first input like `ta`.
+            #[derive(Default)]
+            pub struct Mean {
+                sum: f64,
+                count: f64,
+            }
+
+            impl Next<f64> for Mean {
+                type Output = f64;
+                fn next(&mut self, input: f64) -> f64 {
+                    self.sum += input;
+                    self.count += 1.0;
+                    self.sum / self.count
+                }
+            }
+
+            impl<T: Close> Next<&T> for Mean {
+                type Output = f64;
+                fn next(&mut self, input: &T) -> f64 {
+                    self.next(input.close())
+                }
+            }
+
+            impl Reset for Mean {
+                fn reset(&mut self) {
+                    *self = Self::default();
+                }
+            }
+        }
+    }
+
+    crate::ta_interop!(ta);
+
+    fn candle() -> Candle {
+        Candle {
+            timestamp: 7,
+            open: 10.0,
+            high: 11.0,
+            low: 9.0,
+            close: 10.5,
+            volume: 1000.0,
+        }
+    }
+
+    #[test]
+    fn test_data_item_round_trip() {
+        let item = candle_to_ta(&candle()).unwrap();
+        assert_eq!(candle_from_ta(&item, 7), candle());
+        assert_eq!(candle_from_ta(&TaCandle(candle()), 7), candle());
+
+        let mut invalid = candle();
+        invalid.low = 12.0;
+        assert_eq!(
+            candle_to_ta(&invalid).unwrap_err(),
+            ta::errors::TaError::DataItemInvalid
+        );
+    }
+
+    #[test]
+    fn test_ta_indicator_wrapper() {
+        let prices = [1.0, 2.0, 3.0, 6.0];
+        let mut mean = TaIndicator::new(ta::indicators::Mean::default());
+        assert_eq!(
+            Indicator::<f64, f64>::calculate(&mut mean, &prices).unwrap(),
+            vec![1.0, 1.5, 2.0, 3.0]
+        );
+        assert_eq!(Indicator::<f64, f64>::current(&mean), Some(&3.0));
+        assert!(Indicator::<f64, f64>::calculate(&mut mean, &[]).is_err());
+
+        // Candles feed the close through `TaCandle`
+        let candles: Vec<Candle> = prices
+            .iter()
+            .map(|&close| Candle { close, ..candle() })
+            .collect();
+        assert_eq!(
+            Indicator::<Candle, f64>::calculate(&mut mean, &candles).unwrap(),
+            vec![1.0, 1.5, 2.0, 3.0]
+        );
+        Indicator::<Candle, f64>::reset(&mut mean);
+        assert_eq!(Indicator::<Candle, f64>::current(&mean), None);
+        assert_eq!(
+            Indicator::<Candle, f64>::next(&mut mean, candles[3]).unwrap(),
+            Some(6.0)
+        );
+    }
+
+    #[cfg(feature = "trend")]
+    #[test]
+    fn test_macd_output_shims() {
+        use crate::indicators::trend::MacdResult;
+
+        let result = MacdResult {
+            macd: 1.0,
+            signal: 0.5,
+            histogram: 0.5,
+        };
+        assert_eq!(macd_from_ta(&macd_to_ta(&result)), result);
+    }
+
+    #[cfg(feature = "volatility")]
+    #[test]
+    fn test_band_output_shims() {
+        let out = ta::indicators::BollingerBandsOutput {
+            average: 10.0,
+            upper: 12.0,
+            lower: 8.0,
+        };
+        let result = bollinger_from_ta(&out);
+        assert_eq!((result.middle, result.bandwidth), (10.0, 0.4));
+        assert_eq!(bollinger_to_ta(&result).upper, 12.0);
+
+        let out = ta::indicators::KeltnerChannelOutput {
+            average: 20.0,
+            upper: 21.0,
+            lower: 19.0,
+        };
+        let result = keltner_from_ta(&out);
+        assert_eq!((result.lower, result.bandwidth), (19.0, 0.1));
+        assert_eq!(keltner_to_ta(&result).average, 20.0);
+    }
+}
@@ -31,24 +31,128 @@
 //! println!("SMA values: {:?}", sma_values);
 //! ```
 //!
+//! ## Cargo features
+//!
+//! Each indicator category is a default feature — `trend`, `momentum`,
+//! `volume` and `volatility` (the last three enable `trend` for its
+//! moving averages) — as are the `patterns` and `backtest` modules.
+//! Disable default features to compile only the categories you need.
+//!
+//! Optional features add integrations: `csv`, `serde`, `parallel`, `async`,
+//! `websocket`, `plot`, `test-utils` for the seeded synthetic data
+//! generators in the `testing` module, `reference` for the embedded
+//! pandas / pandas-ta vectors in the `reference` module, `depth` for
+//! order book snapshots and book-based indicators, `latest` for the
+//! lock-free latest-value cells in `indicators::latest`, `metrics` for a
+//! [`metrics`](https://docs.rs/metrics) backend to the `monitor` hooks,
+//! and `tracing` for
+//! [`tracing`](https://docs.rs/tracing) spans and events (target `rsta`)
+//! around batch and streaming calculation, signals and the engines.
+//!
 //! For more examples and detailed documentation, please refer to the individual indicator modules.
 
+mod instrument;
+
 /// Re-exports all indicator modules
 pub mod indicators;
 
+/// Indicator feature matrices for machine learning.
+pub mod features;
+
+/// Exchange trading calendars: sessions, holidays and session-aware helpers.
+pub mod calendar;
+
 /// Trading signals layer built on top of indicator outputs.
 pub mod signals;
 
 /// Candlestick and chart pattern recognition.
+#[cfg(feature = "patterns")]
 pub mod patterns;
 
+/// Alert rules with callbacks on indicator streams.
+pub mod alerts;
+
+/// Rolling z-score and MAD spike detection on price and volume.
+pub mod anomaly;
+
+/// Counters, gauges and histograms for monitoring streaming deployments.
+pub mod monitor;
+
 /// Single-asset backtesting engine.
+#[cfg(feature = "backtest")]
 pub mod backtest;
 
+/// Multi-symbol trade and portfolio accounting.
+#[cfg(feature = "backtest")]
+pub mod portfolio;
+
+/// Reference strategies built on the backtester and signals layer.
+#[cfg(feature = "strategies")]
+pub mod strategies;
+
+/// Simple, log, cumulative and compounded return series.
+pub mod returns;
+
+/// Split and dividend adjustment of candle histories.
+pub mod adjust;
+
+/// Streaming drawdown, Sharpe and volatility of an account's equity.
+pub mod equity;
+
+/// Value-at-Risk and expected shortfall.
+pub mod risk;
+
+/// Pairs-trading hedge ratios and cointegration tests.
+pub mod pairs;
+
+/// Cross-sectional ranking and percentile scores across symbols.
+pub mod screener;
+
+/// Trending / ranging / volatile market regime classification.
+#[cfg(all(feature = "trend", feature = "volatility"))]
+pub mod regime;
+
+/// `futures::Stream` adapters (gated behind the `async` feature).
+#[cfg(feature = "async")]
+pub mod stream;
+
+/// WebSocket kline feed adapter (gated behind the `websocket` feature).
+#[cfg(feature = "websocket")]
+pub mod feed;
+
+/// Chart export to SVG / PNG (gated behind the `plot` feature).
+#[cfg(feature = "plot")]
+pub mod plot;
+
+/// Counting allocator to verify allocation-free updates (gated behind the
+/// `alloc-check` feature).
+#[cfg(any(test, feature = "alloc-check"))]
+pub mod alloc_check;
+
+/// Seeded synthetic OHLCV generators (gated behind the `test-utils` feature).
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+
+/// Reference vectors from pandas / pandas-ta (gated behind the `reference`
+/// feature).
+#[cfg(any(test, feature = "reference"))]
+pub mod reference;
+
+#[cfg(any(test, feature = "backtest", feature = "test-utils"))]
+mod rng;
+
+/// Conversion shims to and from the `ta` crate.
+pub mod interop;
+
 /// CSV import/export utilities (gated behind the `csv` feature).
 #[cfg(feature = "csv")]
 pub mod csv;
 
+/// Order book snapshots and book-based indicators (gated behind the
+/// `depth` feature).
+#[cfg(feature = "depth")]
+pub mod depth;
+
 // Re-export key types for convenience
 pub use indicators::Candle;
 pub use indicators::Indicator;
@@ -0,0 +1,399 @@
+//! # Monitoring hooks
+//!
+//! Counters, gauges and histograms for long-running streaming
+//! deployments, reported to a [`MetricsSink`]. The crate records:
+//!
+//! | Metric | Kind | Labels | Recorded by |
+//! |---|---|---|---|
+//! | [`INDICATOR_INPUTS`] | counter | `indicator` | [`Monitored`] |
+//! | [`INDICATOR_LATENCY`] | histogram, seconds | `indicator`, `op` | [`Monitored`] |
+//! | [`INDICATOR_ERRORS`] | counter | `indicator` | [`Monitored`] |
+//! | [`ALERTS_FIRED`] | counter | `alert` | [`Alerts::set_metrics`](crate::alerts::Alerts::set_metrics) |
+//! | [`CANDLES_PROCESSED`] | counter | `symbol` | `MetricsStage` |
+//! | [`LAST_CANDLE_TIMESTAMP`] | gauge | `symbol` | `MetricsStage` |
+//! | [`SIGNALS_FIRED`] | counter | `symbol` | `MetricsStage` |
+//! | [`ORDERS`], [`FILLS`] | counter | `symbol` | `MetricsStage` |
+//! | [`ORDERS_REFUSED`] | counter | `symbol`, `rule` | `MetricsStage` |
+//!
+//! `MetricsStage` is the event bus handler in `backtest::events` (with
+//! the `backtest` feature).
+//!
+//! Sinks are shared as `Arc<dyn MetricsSink>`. [`InMemoryMetrics`] keeps
+//! the values for polling and tests; with the `metrics` feature,
+//! [`MetricsFacade`] forwards them to the recorder installed for the
+//! [`metrics`](https://docs.rs/metrics) crate (Prometheus, StatsD, ...).
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::Indicator;
+//! use rsta::monitor::{InMemoryMetrics, Monitored, INDICATOR_INPUTS, INDICATOR_LATENCY};
+//!
+//! let metrics = Arc::new(InMemoryMetrics::new());
+//! let mut sma = Monitored::new(Sma::new(3).unwrap(), metrics.clone());
+//! for price in [1.0, 2.0, 3.0, 4.0] {
+//!     sma.next(price).unwrap();
+//! }
+//!
+//! assert_eq!(metrics.counter_value(INDICATOR_INPUTS, &[("indicator", "Sma")]), 4);
+//! let latency = metrics.samples(INDICATOR_LATENCY, &[("indicator", "Sma"), ("op", "next")]);
+//! assert_eq!(latency.len(), 4);
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::indicators::{Indicator, IndicatorError, IndicatorSeries, Timestamped};
+
+/// Inputs fed to a [`Monitored`] indicator.
+pub const INDICATOR_INPUTS: &str = "rsta_indicator_inputs_total";
+/// Duration of a [`Monitored`] indicator's `next`, `calculate` or
+/// `calculate_aligned` call (the `op` label).
+pub const INDICATOR_LATENCY: &str = "rsta_indicator_latency_seconds";
+/// Errors returned by a [`Monitored`] indicator.
+pub const INDICATOR_ERRORS: &str = "rsta_indicator_errors_total";
+/// Alerts fired by [`Alerts`](crate::alerts::Alerts).
+pub const ALERTS_FIRED: &str = "rsta_alerts_fired_total";
+/// Candles published on an event bus.
+pub const CANDLES_PROCESSED: &str = "rsta_candles_processed_total";
+/// Timestamp of the last candle of a symbol, in Unix seconds.
+pub const LAST_CANDLE_TIMESTAMP: &str = "rsta_last_candle_timestamp";
+/// Signal events other than `Hold` published on an event bus.
+pub const SIGNALS_FIRED: &str = "rsta_signals_fired_total";
+/// Orders published on an event bus.
+pub const ORDERS: &str = "rsta_orders_total";
+/// Fills published on an event bus.
+pub const FILLS: &str = "rsta_fills_total";
+/// Orders refused by a pre-trade risk check.
+pub const ORDERS_REFUSED: &str = "rsta_orders_refused_total";
+
+/// Label key / value pairs of one measurement.
+pub type Labels<'a> = &'a [(&'static str, &'a str)];
+
+/// Destination of the crate's measurements.
+pub trait MetricsSink: Send + Sync {
+    /// Add `by` to the counter `name`.
+    fn increment(&self, name: &'static str, labels: Labels<'_>, by: u64);
+
+    /// Set the gauge `name` to `value`.
+    fn gauge(&self, name: &'static str, labels: Labels<'_>, value: f64);
+
+    /// Add a sample to the histogram `name`.
+    fn record(&self, name: &'static str, labels: Labels<'_>, value: f64);
+}
+
+/// Sink that drops every measurement.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl MetricsSink for NoopMetrics {
+    fn increment(&self, _name: &'static str, _labels: Labels<'_>, _by: u64) {}
+
+    fn gauge(&self, _name: &'static str, _labels: Labels<'_>, _value: f64) {}
+
+    fn record(&self, _name: &'static str, _labels: Labels<'_>, _value: f64) {}
+}
+
+type Key = (&'static str, Vec<(&'static str, String)>);
+
+fn key(name: &'static str, labels: Labels<'_>) -> Key {
+    let mut labels: Vec<(&'static str, String)> =
+        labels.iter().map(|&(k, v)| (k, v.to_string())).collect();
+    labels.sort();
+    (name, labels)
+}
+
+#[derive(Debug, Default)]
+struct Values {
+    counters: BTreeMap<Key, u64>,
+    gauges: BTreeMap<Key, f64>,
+    histograms: BTreeMap<Key, Vec<f64>>,
+}
+
+/// Sink that keeps every measurement in memory. Labels match regardless
+/// of their order.
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+    values: Mutex<Values>,
+}
+
+impl InMemoryMetrics {
+    /// Empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Value of the counter, `0` if never incremented.
+    pub fn counter_value(&self, name: &'static str, labels: Labels<'_>) -> u64 {
+        let values = self.values.lock().unwrap();
+        values
+            .counters
+            .get(&key(name, labels))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Last value of the gauge, if ever set.
+    pub fn gauge_value(&self, name: &'static str, labels: Labels<'_>) -> Option<f64> {
+        let values = self.values.lock().unwrap();
+        values.gauges.get(&key(name, labels)).copied()
+    }
+
+    /// Samples of the histogram, in recording order.
+    pub fn samples(&self, name: &'static str, labels: Labels<'_>) -> Vec<f64> {
+        let values = self.values.lock().unwrap();
+        values
+            .histograms
+            .get(&key(name, labels))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Forget every measurement.
+    pub fn clear(&self) {
+        *self.values.lock().unwrap() = Values::default();
+    }
+}
+
+impl MetricsSink for InMemoryMetrics {
+    fn increment(&self, name: &'static str, labels: Labels<'_>, by: u64) {
+        let mut values = self.values.lock().unwrap();
+        *values.counters.entry(key(name, labels)).or_default() += by;
+    }
+
+    fn gauge(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        let mut values = self.values.lock().unwrap();
+        values.gauges.insert(key(name, labels), value);
+    }
+
+    fn record(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        let mut values = self.values.lock().unwrap();
+        values
+            .histograms
+            .entry(key(name, labels))
+            .or_default()
+            .push(value);
+    }
+}
+
+/// Sink forwarding to the global recorder of the `metrics` crate
+/// (requires the `metrics` feature).
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsFacade;
+
+#[cfg(feature = "metrics")]
+fn labels(labels: Labels<'_>) -> Vec<metrics::Label> {
+    labels
+        .iter()
+        .map(|&(k, v)| metrics::Label::new(k, v.to_string()))
+        .collect()
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsSink for MetricsFacade {
+    fn increment(&self, name: &'static str, labels: Labels<'_>, by: u64) {
+        metrics::counter!(name, self::labels(labels)).increment(by);
+    }
+
+    fn gauge(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        metrics::gauge!(name, self::labels(labels)).set(value);
+    }
+
+    fn record(&self, name: &'static str, labels: Labels<'_>, value: f64) {
+        metrics::histogram!(name, self::labels(labels)).record(value);
+    }
+}
+
+/// Wraps an indicator and reports its inputs, call latency and errors to
+/// a [`MetricsSink`], labeled with the indicator's
+/// [`name`](Indicator::name). See the [module docs](self).
+#[derive(Clone)]
+pub struct Monitored<I> {
+    inner: I,
+    sink: Arc<dyn MetricsSink>,
+}
+
+impl<I: std::fmt::Debug> std::fmt::Debug for Monitored<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Monitored")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I> Monitored<I> {
+    /// Wrap `inner`, reporting to `sink`.
+    pub fn new(inner: I, sink: Arc<dyn MetricsSink>) -> Self {
+        Self { inner, sink }
+    }
+
+    /// Borrow the wrapped indicator.
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    /// Unwrap, returning the inner indicator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+
+    fn observe<T>(
+        &self,
+        name: &'static str,
+        op: &'static str,
+        inputs: usize,
+        started: Instant,
+        result: &Result<T, IndicatorError>,
+    ) {
+        let elapsed = started.elapsed().as_secs_f64();
+        self.sink.record(
+            INDICATOR_LATENCY,
+            &[("indicator", name), ("op", op)],
+            elapsed,
+        );
+        self.sink
+            .increment(INDICATOR_INPUTS, &[("indicator", name)], inputs as u64);
+        if result.is_err() {
+            self.sink
+                .increment(INDICATOR_ERRORS, &[("indicator", name)], 1);
+        }
+    }
+}
+
+impl<I, T, O> Indicator<T, O> for Monitored<I>
+where
+    I: Indicator<T, O>,
+{
+    fn calculate(&mut self, data: &[T]) -> Result<Vec<O>, IndicatorError> {
+        let started = Instant::now();
+        let result = self.inner.calculate(data);
+        self.observe(self.inner.name(), "calculate", data.len(), started, &result);
+        result
+    }
+
+    fn calculate_aligned(&mut self, data: &[T]) -> Result<Vec<Option<O>>, IndicatorError> {
+        let started = Instant::now();
+        let result = self.inner.calculate_aligned(data);
+        let name = self.inner.name();
+        self.observe(name, "calculate_aligned", data.len(), started, &result);
+        result
+    }
+
+    fn calculate_series(&mut self, data: &[T]) -> Result<IndicatorSeries<O>, IndicatorError>
+    where
+        T: Timestamped,
+    {
+        let aligned = self.calculate_aligned(data)?;
+        Ok(IndicatorSeries::from_aligned(data, aligned))
+    }
+
+    fn next(&mut self, value: T) -> Result<Option<O>, IndicatorError> {
+        let started = Instant::now();
+        let result = self.inner.next(value);
+        self.observe(self.inner.name(), "next", 1, started, &result);
+        result
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn current(&self) -> Option<&O> {
+        self.inner.current()
+    }
+
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn period(&self) -> Option<usize> {
+        self.inner.period()
+    }
+
+    fn min_periods(&self) -> usize {
+        self.inner.min_periods()
+    }
+
+    fn output_offset(&self) -> usize {
+        self.inner.output_offset()
+    }
+}
+
+#[cfg(all(test, feature = "trend"))]
+mod tests {
+    use super::*;
+    use crate::alerts::{AlertRule, Alerts, Condition};
+    use crate::indicators::trend::Sma;
+
+    #[test]
+    fn test_monitored_indicator_and_alerts() {
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let mut sma = Monitored::new(Sma::new(2).unwrap(), metrics.clone());
+        assert_eq!(sma.calculate(&[1.0, 2.0, 3.0]).unwrap(), vec![1.5, 2.5]);
+        assert!(sma.calculate(&[1.0]).is_err());
+        let sma_label = [("indicator", "Sma")];
+        assert_eq!(metrics.counter_value(INDICATOR_INPUTS, &sma_label), 4);
+        assert_eq!(metrics.counter_value(INDICATOR_ERRORS, &sma_label), 1);
+        let latency = [("op", "calculate"), ("indicator", "Sma")];
+        assert_eq!(metrics.samples(INDICATOR_LATENCY, &latency).len(), 2);
+
+        let mut alerts = Alerts::<f64>::new();
+        alerts
+            .add_threshold(
+                "above_10",
+                AlertRule::new(Condition::CrossesAbove),
+                10.0,
+                |v| Some(*v),
+            )
+            .set_metrics(metrics.clone());
+        for value in [9.0, 11.0, 9.0, 12.0] {
+            alerts.update(&value);
+        }
+        assert_eq!(
+            metrics.counter_value(ALERTS_FIRED, &[("alert", "above_10")]),
+            2
+        );
+
+        metrics.gauge(LAST_CANDLE_TIMESTAMP, &[], 5.0);
+        assert_eq!(metrics.gauge_value(LAST_CANDLE_TIMESTAMP, &[]), Some(5.0));
+        metrics.clear();
+        assert_eq!(
+            metrics.counter_value(ALERTS_FIRED, &[("alert", "above_10")]),
+            0
+        );
+    }
+
+    #[cfg(feature = "volume")]
+    #[test]
+    fn test_monitored_aligned_output_keeps_skipped_bars_in_place() {
+        use crate::indicators::volume::{Adl, ZeroRangePolicy};
+        use crate::indicators::Candle;
+
+        let candle = |close: f64, high: f64, low: f64| Candle {
+            timestamp: 0,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 100.0,
+        };
+        let data = [
+            candle(11.0, 11.0, 9.0),
+            candle(10.0, 10.0, 10.0),
+            candle(11.0, 11.0, 10.0),
+        ];
+        let metrics = Arc::new(InMemoryMetrics::new());
+        let adl = Adl::new().with_zero_range(ZeroRangePolicy::Skip);
+        let mut adl = Monitored::new(adl, metrics.clone());
+        let aligned = adl.calculate_aligned(&data).unwrap();
+        assert_eq!(aligned, vec![Some(100.0), None, Some(200.0)]);
+        let series = adl.calculate_series(&data).unwrap();
+        assert_eq!(series.aligned(), vec![Some(&100.0), None, Some(&200.0)]);
+
+        let latency = [("op", "calculate_aligned"), ("indicator", "Adl")];
+        assert_eq!(metrics.samples(INDICATOR_LATENCY, &latency).len(), 2);
+    }
+}
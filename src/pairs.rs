@@ -0,0 +1,469 @@
+//! # Pairs trading statistics
+//!
+//! Tools for statistical arbitrage on two aligned price series `y` and
+//! `x` (same length, same timestamps):
+//!
+//! - [`ols`] regresses `y` on `x`; the slope is the **hedge ratio**, the
+//!   number of units of `x` to short per unit of `y`.
+//! - [`RollingOls`] re-estimates the hedge ratio over a sliding window and
+//!   reports the latest **spread** `y - alpha - beta * x`, so it streams
+//!   like any other indicator (feed the spread to a z-score for entry
+//!   signals).
+//! - [`engle_granger`] tests whether the pair is cointegrated: it fits the
+//!   hedge ratio over the whole sample, then runs an augmented
+//!   Dickey-Fuller (ADF) test on the residuals. A statistic below the
+//!   critical value rejects "no cointegration", i.e. the spread mean
+//!   reverts.
+//!
+//! Critical values are MacKinnon's (2010) response surfaces for a
+//! two-variable cointegrating regression with a constant, adjusted for the
+//! sample size.
+//!
+//! ```
+//! use rsta::indicators::Indicator;
+//! use rsta::pairs::{engle_granger, RollingOls, Significance};
+//!
+//! // y tracks 2x + 1 up to a small oscillating spread.
+//! let x: Vec<f64> = (0..200).map(|i| 100.0 + (i as f64 * 0.05).sin() * 10.0 + i as f64 * 0.1).collect();
+//! let y: Vec<f64> = x.iter().enumerate().map(|(i, &x)| 1.0 + 2.0 * x + (i as f64 * 1.3).sin()).collect();
+//!
+//! let test = engle_granger(&y, &x, 1).unwrap();
+//! assert!((test.hedge_ratio - 2.0).abs() < 0.05);
+//! assert!(test.is_cointegrated(Significance::FivePercent));
+//!
+//! let pairs: Vec<(f64, f64)> = y.iter().copied().zip(x.iter().copied()).collect();
+//! let mut rolling = RollingOls::new(60).unwrap();
+//! let fits = rolling.calculate(&pairs).unwrap();
+//! assert_eq!(fits.len(), 200 - 59);
+//! assert!(fits.iter().all(|f| (f.beta - 2.0).abs() < 0.2));
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Indicator, IndicatorError};
+
+/// Least-squares fit of `y = alpha + beta * x`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OlsFit {
+    /// Intercept.
+    pub alpha: f64,
+    /// Slope, the hedge ratio.
+    pub beta: f64,
+    /// Share of the variance of `y` explained by `x`, in `[0, 1]`; `0` when
+    /// either series is flat.
+    pub r_squared: f64,
+}
+
+impl OlsFit {
+    /// Residual of the pair `(y, x)`: `y - alpha - beta * x`.
+    pub fn residual(&self, y: f64, x: f64) -> f64 {
+        y - self.alpha - self.beta * x
+    }
+}
+
+fn validate_pair(y: &[f64], x: &[f64]) -> Result<(), IndicatorError> {
+    if x.len() != y.len() {
+        return Err(IndicatorError::invalid_parameter(
+            "x",
+            format!("{} values", x.len()),
+            format!("must be aligned with y ({} values)", y.len()),
+        ));
+    }
+    Ok(())
+}
+
+/// Regress `y` on `x`. Needs at least two aligned values.
+///
+/// A flat `x` has no slope: the fit is `beta = 0`, `alpha = mean(y)`.
+pub fn ols(y: &[f64], x: &[f64]) -> Result<OlsFit, IndicatorError> {
+    validate_pair(y, x)?;
+    validate_data_length(y, 2)?;
+    Ok(fit(y.iter().copied().zip(x.iter().copied())))
+}
+
+pub(crate) fn fit(pairs: impl Iterator<Item = (f64, f64)> + Clone) -> OlsFit {
+    let n = pairs.clone().count() as f64;
+    let (sum_y, sum_x) = pairs
+        .clone()
+        .fold((0.0, 0.0), |(sy, sx), (y, x)| (sy + y, sx + x));
+    let (mean_y, mean_x) = (sum_y / n, sum_x / n);
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for (y, x) in pairs {
+        let (dy, dx) = (y - mean_y, x - mean_x);
+        sxx += dx * dx;
+        sxy += dx * dy;
+        syy += dy * dy;
+    }
+    let beta = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    let r_squared = if sxx > 0.0 && syy > 0.0 {
+        (sxy * sxy / (sxx * syy)).min(1.0)
+    } else {
+        0.0
+    };
+    OlsFit {
+        alpha: mean_y - beta * mean_x,
+        beta,
+        r_squared,
+    }
+}
+
+/// Rolling hedge ratio with the spread of the latest pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HedgeRatio {
+    /// Intercept over the window.
+    pub alpha: f64,
+    /// Slope (hedge ratio) over the window.
+    pub beta: f64,
+    /// Goodness of fit over the window.
+    pub r_squared: f64,
+    /// Latest `y - alpha - beta * x`.
+    pub spread: f64,
+}
+
+/// Rolling OLS hedge ratio over the last `period` pairs.
+///
+/// Input is one `(y, x)` pair per period; output is a [`HedgeRatio`] once
+/// the window is full.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::Indicator;
+/// use rsta::pairs::RollingOls;
+///
+/// let mut ols = RollingOls::new(3).unwrap();
+/// assert_eq!(ols.next((3.0, 1.0)).unwrap(), None);
+/// assert_eq!(ols.next((5.0, 2.0)).unwrap(), None);
+/// let fit = ols.next((8.0, 3.0)).unwrap().unwrap();
+/// assert_eq!(fit.beta, 2.5);
+/// assert!((fit.spread - 1.0 / 6.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingOls {
+    period: usize,
+    window: VecDeque<(f64, f64)>,
+    last: Option<HedgeRatio>,
+}
+
+impl RollingOls {
+    /// Create a rolling regression.
+    ///
+    /// # Arguments
+    /// * `period` - Window length (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new regression or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            last: None,
+        })
+    }
+
+    /// Reset the rolling window.
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+}
+
+impl Indicator<(f64, f64), HedgeRatio> for RollingOls {
+    fn calculate(&mut self, data: &[(f64, f64)]) -> Result<Vec<HedgeRatio>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - self.period + 1);
+        for &pair in data {
+            if let Some(fit) = self.next(pair)? {
+                result.push(fit);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: (f64, f64)) -> Result<Option<HedgeRatio>, IndicatorError> {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < self.period {
+            return Ok(None);
+        }
+        let fit = fit(self.window.iter().copied());
+        self.last = Some(HedgeRatio {
+            alpha: fit.alpha,
+            beta: fit.beta,
+            r_squared: fit.r_squared,
+            spread: fit.residual(value.0, value.1),
+        });
+        Ok(self.last)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&HedgeRatio> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+/// Significance level of a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Significance {
+    /// 1% level.
+    OnePercent,
+    /// 5% level.
+    FivePercent,
+    /// 10% level.
+    TenPercent,
+}
+
+/// Outcome of an Engle-Granger cointegration test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cointegration {
+    /// Slope of the cointegrating regression `y = intercept + hedge_ratio * x`.
+    pub hedge_ratio: f64,
+    /// Intercept of the cointegrating regression.
+    pub intercept: f64,
+    /// ADF t-statistic of the residuals; more negative is stronger evidence.
+    pub statistic: f64,
+    /// Lagged differences in the ADF regression.
+    pub lags: usize,
+    /// Observations in the ADF regression.
+    pub nobs: usize,
+    /// Critical values at 1%, 5% and 10%.
+    pub critical_values: [f64; 3],
+}
+
+impl Cointegration {
+    /// Critical value at `level`.
+    pub fn critical_value(&self, level: Significance) -> f64 {
+        self.critical_values[level as usize]
+    }
+
+    /// Whether "no cointegration" is rejected at `level`.
+    pub fn is_cointegrated(&self, level: Significance) -> bool {
+        self.statistic < self.critical_value(level)
+    }
+}
+
+/// MacKinnon (2010) critical values for the residual ADF test of a
+/// two-variable regression with a constant, for a sample of `n`.
+fn critical_values(n: usize) -> [f64; 3] {
+    const SURFACE: [[f64; 3]; 3] = [
+        [-3.89644, -10.9519, -22.527],
+        [-3.33613, -6.1101, -6.823],
+        [-3.04445, -4.2412, -2.720],
+    ];
+    let n = n as f64;
+    SURFACE.map(|[b0, b1, b2]| b0 + b1 / n + b2 / (n * n))
+}
+
+/// Engle-Granger two-step cointegration test of `y` on `x`.
+///
+/// Regresses `y` on `x`, then runs an ADF regression without constant on
+/// the residuals `e`:
+///
+/// ```text
+/// Δe[t] = γ e[t-1] + φ1 Δe[t-1] + … + φp Δe[t-p] + ε[t]
+/// ```
+///
+/// with `p = lags`, and reports the t-statistic of `γ`.
+///
+/// # Arguments
+/// * `y` - Dependent price series
+/// * `x` - Independent price series, aligned with `y`
+/// * `lags` - Lagged differences `p` in the ADF regression
+///
+/// # Returns
+/// * `Result<Cointegration, IndicatorError>` - The test, or an error for
+///   misaligned series, fewer than `2 * lags + 3` values, or perfectly
+///   collinear series (zero residual variance)
+pub fn engle_granger(y: &[f64], x: &[f64], lags: usize) -> Result<Cointegration, IndicatorError> {
+    validate_pair(y, x)?;
+    validate_data_length(y, 2 * lags + 3)?;
+    let fit = ols(y, x)?;
+    let residuals: Vec<f64> = y.iter().zip(x).map(|(&y, &x)| fit.residual(y, x)).collect();
+    let (statistic, nobs) = adf_statistic(&residuals, lags)?;
+    Ok(Cointegration {
+        hedge_ratio: fit.beta,
+        intercept: fit.alpha,
+        statistic,
+        lags,
+        nobs,
+        critical_values: critical_values(y.len() - 1),
+    })
+}
+
+/// t-statistic of `γ` in the no-constant ADF regression of `series`, and
+/// the number of observations used.
+fn adf_statistic(series: &[f64], lags: usize) -> Result<(f64, usize), IndicatorError> {
+    let diffs: Vec<f64> = series.windows(2).map(|w| w[1] - w[0]).collect();
+    let k = lags + 1;
+    let rows: Vec<(f64, Vec<f64>)> = (lags..diffs.len())
+        .map(|t| {
+            let mut regressors = Vec::with_capacity(k);
+            regressors.push(series[t]);
+            regressors.extend((1..=lags).map(|i| diffs[t - i]));
+            (diffs[t], regressors)
+        })
+        .collect();
+    let nobs = rows.len();
+
+    let mut xtx = vec![vec![0.0; k]; k];
+    let mut xty = vec![0.0; k];
+    for (dep, regressors) in &rows {
+        for i in 0..k {
+            xty[i] += regressors[i] * dep;
+            for j in 0..k {
+                xtx[i][j] += regressors[i] * regressors[j];
+            }
+        }
+    }
+    let singular = || IndicatorError::division_by_zero("ADF regression is singular", 0);
+    let coefficients = solve(xtx.clone(), xty).ok_or_else(singular)?;
+    let mut unit = vec![0.0; k];
+    unit[0] = 1.0;
+    let inverse_00 = solve(xtx, unit).ok_or_else(singular)?[0];
+
+    let rss: f64 = rows
+        .iter()
+        .map(|(dep, regressors)| {
+            let fitted: f64 = regressors
+                .iter()
+                .zip(&coefficients)
+                .map(|(r, c)| r * c)
+                .sum();
+            (dep - fitted).powi(2)
+        })
+        .sum();
+    let variance = rss / (nobs - k) as f64;
+    let std_error = (variance * inverse_00).sqrt();
+    if std_error.is_nan() || std_error <= 0.0 {
+        return Err(IndicatorError::division_by_zero(
+            "residuals have no variance",
+            0,
+        ));
+    }
+    Ok((coefficients[0] / std_error, nobs))
+}
+
+/// Solve `a x = b` by Gaussian elimination with partial pivoting.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < f64::EPSILON * 1e-3 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let pivot_row = a[col].clone();
+        for row in col + 1..n {
+            let factor = a[row][col] / pivot_row[col];
+            for (value, p) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *value -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|j| a[row][j] * x[j]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn random_walk(rng: &mut Rng, n: usize) -> Vec<f64> {
+        let mut price = 100.0;
+        (0..n)
+            .map(|_| {
+                price += rng.normal();
+                price
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ols_recovers_line() {
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let y: Vec<f64> = x.iter().map(|x| 3.0 - 0.5 * x).collect();
+        let fit = ols(&y, &x).unwrap();
+        assert!((fit.alpha - 3.0).abs() < 1e-12 && (fit.beta + 0.5).abs() < 1e-12);
+        assert!((fit.r_squared - 1.0).abs() < 1e-12);
+        assert_eq!(fit.residual(0.0, 0.0), -fit.alpha);
+
+        let flat = ols(&[1.0, 3.0], &[5.0, 5.0]).unwrap();
+        assert_eq!((flat.alpha, flat.beta, flat.r_squared), (2.0, 0.0, 0.0));
+        assert!(ols(&[1.0, 2.0], &[1.0]).is_err());
+        assert!(ols(&[1.0], &[1.0]).is_err());
+    }
+
+    #[test]
+    fn rolling_matches_batch_windows() {
+        let mut rng = Rng::new(7);
+        let x = random_walk(&mut rng, 80);
+        let y: Vec<f64> = x.iter().map(|x| 0.7 * x + rng.normal()).collect();
+        let pairs: Vec<(f64, f64)> = y.iter().copied().zip(x.iter().copied()).collect();
+        let mut rolling = RollingOls::new(20).unwrap();
+        let fits = rolling.calculate(&pairs).unwrap();
+        assert_eq!(fits.len(), 61);
+        for (i, hedge) in fits.iter().enumerate() {
+            let batch = ols(&y[i..i + 20], &x[i..i + 20]).unwrap();
+            assert!((hedge.beta - batch.beta).abs() < 1e-9);
+            assert!((hedge.spread - batch.residual(y[i + 19], x[i + 19])).abs() < 1e-9);
+        }
+        assert_eq!(rolling.current(), fits.last());
+        assert!(RollingOls::new(1).is_err());
+    }
+
+    #[test]
+    fn engle_granger_separates_cointegrated_pairs() {
+        let mut rng = Rng::new(42);
+        let x = random_walk(&mut rng, 500);
+        let y: Vec<f64> = x.iter().map(|x| 5.0 + 2.0 * x + rng.normal()).collect();
+        let test = engle_granger(&y, &x, 1).unwrap();
+        assert!((test.hedge_ratio - 2.0).abs() < 0.05);
+        assert!(test.is_cointegrated(Significance::OnePercent));
+        assert_eq!(test.nobs, 498);
+
+        let independent = random_walk(&mut rng, 500);
+        let spurious = engle_granger(&independent, &x, 1).unwrap();
+        assert!(!spurious.is_cointegrated(Significance::TenPercent));
+        assert!(spurious.statistic > test.statistic);
+    }
+
+    #[test]
+    fn critical_values_and_errors() {
+        // Asymptotic values at a large sample.
+        let cv = critical_values(1_000_000);
+        assert!((cv[1] - -3.33613).abs() < 1e-4);
+        let small = critical_values(50);
+        assert!(small.iter().zip(&cv).all(|(s, c)| s < c));
+
+        let x: Vec<f64> = (0..10).map(f64::from).collect();
+        assert!(engle_granger(&x, &x[..9], 0).is_err());
+        assert!(engle_granger(&x[..4], &x[..4], 1).is_err());
+        // y is exactly linear in x: no residual variance.
+        let y: Vec<f64> = x.iter().map(|x| 2.0 * x).collect();
+        assert!(engle_granger(&y, &x, 0).is_err());
+    }
+}
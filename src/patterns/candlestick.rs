@@ -26,8 +26,13 @@
 //! [`detect_at`] runs every applicable detector on the trailing window
 //! of a candle slice and returns a `Vec<Pattern>` describing every
 //! pattern whose final bar is the last candle in the window.
+//! [`detect_all`] does the same for every bar of a slice, and
+//! [`CandlestickPatterns`] is the streaming equivalent (it implements
+//! [`Indicator`]).
 
-use crate::indicators::Candle;
+use std::collections::VecDeque;
+
+use crate::indicators::{Candle, Indicator, IndicatorError};
 
 /// Bias direction implied by a pattern in its canonical context.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -474,6 +479,118 @@ pub fn detect_at_with(candles: &[Candle], cfg: &PatternConfig) -> Vec<Pattern> {
     out
 }
 
+/// Run [`detect_at`] at every bar of `candles`: element `i` holds the
+/// patterns completing on `candles[i]`.
+pub fn detect_all(candles: &[Candle]) -> Vec<Vec<Pattern>> {
+    detect_all_with(candles, &PatternConfig::default())
+}
+
+/// [`detect_all`] with custom thresholds.
+pub fn detect_all_with(candles: &[Candle], cfg: &PatternConfig) -> Vec<Vec<Pattern>> {
+    (0..candles.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(CandlestickPatterns::WINDOW);
+            detect_at_with(&candles[start..=i], cfg)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Streaming
+// ---------------------------------------------------------------------------
+
+/// Streaming pattern scanner. Keeps the last three candles and, for every
+/// new bar, reports the patterns completing on it — the same result as
+/// [`detect_at`] on the trailing window.
+///
+/// Implements [`Indicator`] with a `Vec<Pattern>` output, so it can be fed
+/// bar by bar next to other indicators. Every bar produces a value (an
+/// empty vec when nothing matches).
+///
+/// # Example
+/// ```
+/// use rsta::indicators::{Candle, Indicator};
+/// use rsta::patterns::candlestick::{CandlestickPatterns, PatternKind};
+///
+/// let bar = |o: f64, h: f64, l: f64, c: f64| Candle {
+///     timestamp: 0, open: o, high: h, low: l, close: c, volume: 1.0,
+/// };
+/// let mut scanner = CandlestickPatterns::new();
+/// scanner.next(bar(105.0, 106.0, 100.0, 101.0)).unwrap();
+/// let found = scanner.next(bar(100.0, 108.0, 99.0, 107.0)).unwrap().unwrap();
+/// assert!(found.iter().any(|p| p.kind == PatternKind::BullishEngulfing));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CandlestickPatterns {
+    config: PatternConfig,
+    window: VecDeque<Candle>,
+    last: Option<Vec<Pattern>>,
+}
+
+impl CandlestickPatterns {
+    /// Longest pattern, in bars.
+    const WINDOW: usize = 3;
+
+    /// Scanner with the default thresholds.
+    pub fn new() -> Self {
+        Self::with_config(PatternConfig::default())
+    }
+
+    /// Scanner with custom thresholds.
+    pub fn with_config(config: PatternConfig) -> Self {
+        Self {
+            config,
+            window: VecDeque::with_capacity(Self::WINDOW),
+            last: None,
+        }
+    }
+
+    /// The thresholds in use.
+    pub fn config(&self) -> &PatternConfig {
+        &self.config
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+}
+
+impl Indicator<Candle, Vec<Pattern>> for CandlestickPatterns {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Vec<Pattern>>, IndicatorError> {
+        self.reset_state();
+        let patterns = detect_all_with(data, &self.config);
+        // Keep the trailing bars so `next()` can complete patterns that
+        // start inside `data`.
+        let start = data.len().saturating_sub(Self::WINDOW);
+        self.window.extend(&data[start..]);
+        self.last = patterns.last().cloned();
+        Ok(patterns)
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<Vec<Pattern>>, IndicatorError> {
+        if self.window.len() == Self::WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        self.last = Some(detect_at_with(self.window.make_contiguous(), &self.config));
+        Ok(self.last.clone())
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Vec<Pattern>> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(Self::WINDOW)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -657,4 +774,89 @@ mod tests {
         let p = detect_at(&window);
         assert!(p.iter().any(|x| x.kind == PatternKind::BullishEngulfing));
     }
+
+    // ----- batch / streaming -----
+
+    fn sample() -> Vec<Candle> {
+        vec![
+            c(100.0, 103.0, 99.5, 102.0),
+            c(101.0, 104.0, 100.5, 103.5),
+            c(102.0, 105.0, 101.5, 104.5),
+            c(104.0, 104.2, 103.8, 104.0),
+            c(105.0, 106.0, 100.0, 101.0),
+            c(100.0, 108.0, 99.0, 107.0),
+        ]
+    }
+
+    #[test]
+    fn detect_all_matches_detect_at_per_bar() {
+        let data = sample();
+        let all = detect_all(&data);
+        assert_eq!(all.len(), data.len());
+        for i in 0..data.len() {
+            let start = (i + 1).saturating_sub(3);
+            assert_eq!(all[i], detect_at(&data[start..=i]), "bar {i}");
+        }
+        assert!(all[2]
+            .iter()
+            .any(|p| p.kind == PatternKind::ThreeWhiteSoldiers));
+        assert!(all[3].iter().any(|p| p.kind == PatternKind::Doji));
+        assert!(all[5]
+            .iter()
+            .any(|p| p.kind == PatternKind::BullishEngulfing));
+    }
+
+    #[test]
+    fn streaming_matches_batch() {
+        let data = sample();
+        let mut scanner = CandlestickPatterns::new();
+        let batch = scanner.calculate(&data).unwrap();
+        scanner.reset();
+        let streamed: Vec<_> = data
+            .iter()
+            .map(|bar| scanner.next(*bar).unwrap().unwrap())
+            .collect();
+        assert_eq!(batch, streamed);
+    }
+
+    #[test]
+    fn append_matches_full_calculate() {
+        let data = sample();
+        let mut scanner = CandlestickPatterns::new();
+        // The engulfing pair straddles the split.
+        let mut out = scanner.calculate(&data[..5]).unwrap();
+        assert_eq!(scanner.calculate_append(&mut out, &data[5..]).unwrap(), 1);
+        assert!(out[5]
+            .iter()
+            .any(|p| p.kind == PatternKind::BullishEngulfing));
+        assert_eq!(out, scanner.calculate(&data).unwrap());
+    }
+
+    #[test]
+    fn streaming_reset_forgets_window() {
+        let data = sample();
+        let mut scanner = CandlestickPatterns::new();
+        scanner.next(data[4]).unwrap();
+        scanner.reset();
+        // Without the previous bar there is nothing to engulf.
+        let found = scanner.next(data[5]).unwrap().unwrap();
+        assert!(!found
+            .iter()
+            .any(|p| p.kind == PatternKind::BullishEngulfing));
+    }
+
+    #[test]
+    fn scanner_uses_custom_config() {
+        let cfg = PatternConfig {
+            doji_body_ratio: 0.0,
+            ..PatternConfig::default()
+        };
+        let mut scanner = CandlestickPatterns::with_config(cfg);
+        assert_eq!(scanner.config().doji_body_ratio, 0.0);
+        let found = scanner
+            .next(c(104.0, 104.2, 103.8, 104.01))
+            .unwrap()
+            .unwrap();
+        assert!(!found.iter().any(|p| p.kind == PatternKind::Doji));
+    }
 }
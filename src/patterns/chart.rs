@@ -0,0 +1,743 @@
+//! Chart pattern detection built on swing points.
+//!
+//! Unlike candlestick patterns, chart patterns span tens of bars. The
+//! detector first reduces the candles to **swing points** — local highs
+//! and lows confirmed `swing_lookback` bars on each side — then matches
+//! the alternating high/low sequence against pattern templates:
+//!
+//! - **Double top / bottom**: `H L H` (or `L H L`) with the two extremes
+//!   within `tolerance` of each other.
+//! - **Head and shoulders / inverse**: `H L H L H` (or the mirror) with a
+//!   head clearly beyond two roughly equal shoulders.
+//! - **Triangles and channels**: least-squares trendlines through the most
+//!   recent swing highs and swing lows, classified by their slopes.
+//!
+//! Every match carries a `confidence` in `[0, 1]` that combines how well
+//! the geometry fits the template (equal peaks, flat neckline, trendline
+//! residuals) with how pronounced it is (depth, touch count).
+//!
+//! Swing confirmation is intrinsically delayed: a pivot at bar `i` is only
+//! known at bar `i + swing_lookback`. Patterns are reported with the
+//! indices of their swing points so callers can judge freshness.
+//!
+//! ```
+//! use rsta::indicators::Candle;
+//! use rsta::patterns::chart::{detect_chart_patterns, ChartPatternKind};
+//!
+//! // Zig-zag through 100 → 110 → 102 → 110.5 → 100: a double top.
+//! let pivots = [100.0, 110.0, 102.0, 110.5, 100.0];
+//! let mut candles = Vec::new();
+//! for w in pivots.windows(2) {
+//!     for step in 0..5 {
+//!         let p = w[0] + (w[1] - w[0]) * step as f64 / 5.0;
+//!         candles.push(Candle {
+//!             timestamp: candles.len() as u64,
+//!             open: p, high: p + 0.5, low: p - 0.5, close: p, volume: 1.0,
+//!         });
+//!     }
+//! }
+//!
+//! let found = detect_chart_patterns(&candles);
+//! assert!(found.iter().any(|p| p.kind == ChartPatternKind::DoubleTop));
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::indicators::{Candle, IndicatorError};
+use crate::patterns::candlestick::Bias;
+
+/// Whether a swing point is a local high or a local low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwingKind {
+    /// Local maximum of the candle highs.
+    High,
+    /// Local minimum of the candle lows.
+    Low,
+}
+
+/// A confirmed local extreme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwingPoint {
+    /// Index of the candle in the input slice.
+    pub index: usize,
+    /// The candle's high (for [`SwingKind::High`]) or low.
+    pub price: f64,
+    /// High or low.
+    pub kind: SwingKind,
+}
+
+/// Find swing highs and lows.
+///
+/// Bar `i` is a swing high when its high is strictly above the `lookback`
+/// highs before it and at least as high as the `lookback` highs after it
+/// (the asymmetry picks the first bar of a flat top). Swing lows mirror
+/// this on the lows. The first and last `lookback` bars can never
+/// qualify. Returns the points in index order; a wide bar can be both a
+/// swing high and a swing low.
+pub fn swing_points(candles: &[Candle], lookback: usize) -> Vec<SwingPoint> {
+    let n = candles.len();
+    let mut out = Vec::new();
+    if lookback == 0 || n < 2 * lookback + 1 {
+        return out;
+    }
+    for i in lookback..n - lookback {
+        let (is_high, is_low) = swing_at(candles, i, lookback);
+        if is_high {
+            out.push(SwingPoint {
+                index: i,
+                price: candles[i].high,
+                kind: SwingKind::High,
+            });
+        }
+        if is_low {
+            out.push(SwingPoint {
+                index: i,
+                price: candles[i].low,
+                kind: SwingKind::Low,
+            });
+        }
+    }
+    out
+}
+
+/// Whether `candles[i]` is a swing `(high, low)` under the rule of
+/// [`swing_points`]. Requires `lookback <= i < candles.len() - lookback`.
+pub(crate) fn swing_at(candles: &[Candle], i: usize, lookback: usize) -> (bool, bool) {
+    let before = &candles[i - lookback..i];
+    let after = &candles[i + 1..=i + lookback];
+    let high = candles[i].high;
+    let low = candles[i].low;
+    (
+        before.iter().all(|c| c.high < high) && after.iter().all(|c| c.high <= high),
+        before.iter().all(|c| c.low > low) && after.iter().all(|c| c.low >= low),
+    )
+}
+
+/// Collapse runs of same-kind swings to their most extreme member so the
+/// sequence strictly alternates high / low.
+pub(crate) fn alternate(swings: &[SwingPoint]) -> Vec<SwingPoint> {
+    let mut out: Vec<SwingPoint> = Vec::with_capacity(swings.len());
+    for s in swings {
+        push_alternating(&mut out, *s);
+    }
+    out
+}
+
+/// Append `s` to an alternating sequence, replacing the last point
+/// instead when both are of the same kind and `s` is more extreme.
+/// Returns whether the sequence changed.
+pub(crate) fn push_alternating(out: &mut Vec<SwingPoint>, s: SwingPoint) -> bool {
+    match out.last_mut() {
+        Some(last) if last.kind == s.kind => {
+            let more_extreme = match s.kind {
+                SwingKind::High => s.price > last.price,
+                SwingKind::Low => s.price < last.price,
+            };
+            if more_extreme {
+                *last = s;
+            }
+            more_extreme
+        }
+        _ => {
+            out.push(s);
+            true
+        }
+    }
+}
+
+/// Streaming swing confirmation: keeps the last `2 * lookback + 1` candles
+/// and reports the swings of the middle one, `lookback` bars late.
+#[derive(Debug, Clone)]
+pub(crate) struct SwingTracker {
+    lookback: usize,
+    window: VecDeque<Candle>,
+    bars: usize,
+}
+
+impl SwingTracker {
+    pub(crate) fn new(lookback: usize) -> Self {
+        Self {
+            lookback,
+            window: VecDeque::with_capacity(2 * lookback + 1),
+            bars: 0,
+        }
+    }
+
+    /// Push a candle and return the swing points it confirms, high first.
+    /// Does not allocate.
+    pub(crate) fn push(
+        &mut self,
+        candle: Candle,
+    ) -> std::iter::Flatten<std::array::IntoIter<Option<SwingPoint>, 2>> {
+        let lookback = self.lookback;
+        if self.window.len() == 2 * lookback + 1 {
+            self.window.pop_front();
+        }
+        self.window.push_back(candle);
+        self.bars += 1;
+
+        let mut out = [None, None];
+        if self.window.len() == 2 * lookback + 1 {
+            let window = self.window.make_contiguous();
+            let pivot = window[lookback];
+            let (is_high, is_low) = swing_at(window, lookback, lookback);
+            let index = self.bars - 1 - lookback;
+            if is_high {
+                out[0] = Some(SwingPoint {
+                    index,
+                    price: pivot.high,
+                    kind: SwingKind::High,
+                });
+            }
+            if is_low {
+                out[1] = Some(SwingPoint {
+                    index,
+                    price: pivot.low,
+                    kind: SwingKind::Low,
+                });
+            }
+        }
+        out.into_iter().flatten()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.window.clear();
+        self.bars = 0;
+    }
+}
+
+/// Chart pattern identified by [`detect_chart_patterns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartPatternKind {
+    /// Two roughly equal highs separated by a trough.
+    DoubleTop,
+    /// Two roughly equal lows separated by a peak.
+    DoubleBottom,
+    /// Head above two roughly equal shoulders.
+    HeadAndShoulders,
+    /// Head below two roughly equal shoulders.
+    InverseHeadAndShoulders,
+    /// Flat resistance, rising support.
+    AscendingTriangle,
+    /// Falling resistance, flat support.
+    DescendingTriangle,
+    /// Falling resistance, rising support.
+    SymmetricalTriangle,
+    /// Parallel rising trendlines.
+    AscendingChannel,
+    /// Parallel falling trendlines.
+    DescendingChannel,
+    /// Parallel flat trendlines (trading range).
+    Rectangle,
+}
+
+impl ChartPatternKind {
+    /// Bias implied by the pattern in its textbook reading.
+    pub fn bias(&self) -> Bias {
+        match self {
+            ChartPatternKind::DoubleBottom
+            | ChartPatternKind::InverseHeadAndShoulders
+            | ChartPatternKind::AscendingTriangle
+            | ChartPatternKind::AscendingChannel => Bias::Bullish,
+            ChartPatternKind::DoubleTop
+            | ChartPatternKind::HeadAndShoulders
+            | ChartPatternKind::DescendingTriangle
+            | ChartPatternKind::DescendingChannel => Bias::Bearish,
+            ChartPatternKind::SymmetricalTriangle | ChartPatternKind::Rectangle => Bias::Neutral,
+        }
+    }
+}
+
+/// A detected chart pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartPattern {
+    /// What was found.
+    pub kind: ChartPatternKind,
+    /// Same as `kind.bias()`.
+    pub bias: Bias,
+    /// Match quality in `[0, 1]`.
+    pub confidence: f64,
+    /// Index of the first swing point of the pattern.
+    pub start: usize,
+    /// Index of the last swing point of the pattern.
+    pub end: usize,
+    /// The level whose break confirms the pattern: the neckline for
+    /// double tops/bottoms and head and shoulders; for triangles and
+    /// channels, the upper trendline at `end` (lower trendline for bearish
+    /// formations).
+    pub key_level: f64,
+    /// Swing points the pattern was built from, in index order.
+    pub points: Vec<SwingPoint>,
+}
+
+/// Tunable thresholds for [`detect_chart_patterns_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct ChartPatternConfig {
+    /// Bars on each side that confirm a swing point. Default 3.
+    pub swing_lookback: usize,
+    /// Maximum relative difference between levels considered "equal"
+    /// (peaks of a double top, shoulders, ...). Default 0.02.
+    pub tolerance: f64,
+    /// Minimum relative depth of the trough of a double top (and its
+    /// mirrors) and the prominence of a head. Default 0.03.
+    pub min_depth: f64,
+    /// Number of recent swing highs and swing lows used to fit
+    /// triangle / channel trendlines (at least 2). Default 3.
+    pub trendline_swings: usize,
+    /// Relative slope per bar below which a trendline counts as flat,
+    /// and below which two trendlines count as parallel. Default 0.0005.
+    pub flat_slope: f64,
+}
+
+impl Default for ChartPatternConfig {
+    fn default() -> Self {
+        Self {
+            swing_lookback: 3,
+            tolerance: 0.02,
+            min_depth: 0.03,
+            trendline_swings: 3,
+            flat_slope: 0.0005,
+        }
+    }
+}
+
+impl ChartPatternConfig {
+    fn validate(&self) -> Result<(), IndicatorError> {
+        if self.swing_lookback == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "swing_lookback",
+                self.swing_lookback,
+                "must be at least 1",
+            ));
+        }
+        if self.trendline_swings < 2 {
+            return Err(IndicatorError::invalid_parameter(
+                "trendline_swings",
+                self.trendline_swings,
+                "must be at least 2",
+            ));
+        }
+        for (name, v) in [
+            ("tolerance", self.tolerance),
+            ("min_depth", self.min_depth),
+            ("flat_slope", self.flat_slope),
+        ] {
+            if !v.is_finite() || v <= 0.0 {
+                return Err(IndicatorError::invalid_parameter(
+                    name,
+                    v,
+                    "must be positive and finite",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Detect chart patterns with the default thresholds.
+///
+/// Double tops/bottoms and head and shoulders are reported wherever they
+/// occur in `candles`; triangles and channels only for the formation
+/// made by the most recent swings.
+pub fn detect_chart_patterns(candles: &[Candle]) -> Vec<ChartPattern> {
+    detect_chart_patterns_with(candles, &ChartPatternConfig::default())
+        .expect("default config is valid")
+}
+
+/// [`detect_chart_patterns`] with custom thresholds.
+pub fn detect_chart_patterns_with(
+    candles: &[Candle],
+    cfg: &ChartPatternConfig,
+) -> Result<Vec<ChartPattern>, IndicatorError> {
+    cfg.validate()?;
+    let swings = alternate(&swing_points(candles, cfg.swing_lookback));
+    let mut out = Vec::new();
+
+    for w in swings.windows(3) {
+        if let Some(p) = double_extreme(w, cfg) {
+            out.push(p);
+        }
+    }
+    for w in swings.windows(5) {
+        if let Some(p) = head_and_shoulders(w, cfg) {
+            out.push(p);
+        }
+    }
+    if let Some(p) = trendline_formation(&swings, cfg) {
+        out.push(p);
+    }
+
+    out.sort_by_key(|p| (p.end, p.start));
+    Ok(out)
+}
+
+fn pattern(
+    kind: ChartPatternKind,
+    confidence: f64,
+    key_level: f64,
+    points: &[SwingPoint],
+) -> ChartPattern {
+    ChartPattern {
+        kind,
+        bias: kind.bias(),
+        confidence: confidence.clamp(0.0, 1.0),
+        start: points[0].index,
+        end: points[points.len() - 1].index,
+        key_level,
+        points: points.to_vec(),
+    }
+}
+
+/// `1` for a perfect match, falling linearly to `0` at `limit`.
+fn closeness(diff: f64, limit: f64) -> f64 {
+    (1.0 - diff / limit).clamp(0.0, 1.0)
+}
+
+/// `0` at the minimum, rising linearly to `1` at twice the minimum.
+fn strength(value: f64, min: f64) -> f64 {
+    (value / (2.0 * min)).clamp(0.0, 1.0)
+}
+
+/// Double top (`H L H`) or double bottom (`L H L`).
+fn double_extreme(w: &[SwingPoint], cfg: &ChartPatternConfig) -> Option<ChartPattern> {
+    let (a, mid, b) = (w[0], w[1], w[2]);
+    let avg = (a.price + b.price) / 2.0;
+    let diff = (a.price - b.price).abs() / avg;
+    let (kind, depth) = match a.kind {
+        SwingKind::High => (ChartPatternKind::DoubleTop, (avg - mid.price) / avg),
+        SwingKind::Low => (ChartPatternKind::DoubleBottom, (mid.price - avg) / avg),
+    };
+    if diff > cfg.tolerance || depth < cfg.min_depth {
+        return None;
+    }
+    let confidence = 0.5 * closeness(diff, cfg.tolerance) + 0.5 * strength(depth, cfg.min_depth);
+    Some(pattern(kind, confidence, mid.price, w))
+}
+
+/// Head and shoulders (`H L H L H`) or its inverse (`L H L H L`).
+fn head_and_shoulders(w: &[SwingPoint], cfg: &ChartPatternConfig) -> Option<ChartPattern> {
+    let (ls, n1, head, n2, rs) = (w[0], w[1], w[2], w[3], w[4]);
+    // Flip the inverse pattern so one set of comparisons covers both.
+    let sign = match ls.kind {
+        SwingKind::High => 1.0,
+        SwingKind::Low => -1.0,
+    };
+    let shoulder_avg = (ls.price + rs.price) / 2.0;
+    let neck_avg = (n1.price + n2.price) / 2.0;
+    let shoulder_diff = (ls.price - rs.price).abs() / shoulder_avg;
+    let neck_diff = (n1.price - n2.price).abs() / neck_avg;
+    let inner_shoulder = if sign > 0.0 {
+        ls.price.max(rs.price)
+    } else {
+        ls.price.min(rs.price)
+    };
+    let prominence = sign * (head.price - inner_shoulder) / shoulder_avg;
+    let outer_shoulder = if sign > 0.0 {
+        ls.price.min(rs.price)
+    } else {
+        ls.price.max(rs.price)
+    };
+    let inner_neck = if sign > 0.0 {
+        n1.price.max(n2.price)
+    } else {
+        n1.price.min(n2.price)
+    };
+    let shoulder_height = sign * (outer_shoulder - inner_neck) / shoulder_avg;
+
+    if shoulder_diff > cfg.tolerance
+        || neck_diff > 2.0 * cfg.tolerance
+        || prominence < cfg.min_depth
+        || shoulder_height < cfg.min_depth
+    {
+        return None;
+    }
+    let kind = if sign > 0.0 {
+        ChartPatternKind::HeadAndShoulders
+    } else {
+        ChartPatternKind::InverseHeadAndShoulders
+    };
+    let confidence = (closeness(shoulder_diff, cfg.tolerance)
+        + closeness(neck_diff, 2.0 * cfg.tolerance)
+        + strength(prominence, cfg.min_depth))
+        / 3.0;
+    Some(pattern(kind, confidence, neck_avg, w))
+}
+
+/// Least-squares line through `(index, price)` points: `(slope, intercept)`.
+fn fit_line(points: &[SwingPoint]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.index as f64).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.price).sum::<f64>() / n;
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    for p in points {
+        let dx = p.index as f64 - mean_x;
+        sxx += dx * dx;
+        sxy += dx * (p.price - mean_y);
+    }
+    let slope = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    (slope, mean_y - slope * mean_x)
+}
+
+/// Triangle or channel formed by the most recent swings.
+fn trendline_formation(swings: &[SwingPoint], cfg: &ChartPatternConfig) -> Option<ChartPattern> {
+    let recent = &swings[swings.len().saturating_sub(2 * cfg.trendline_swings)..];
+    let highs: Vec<SwingPoint> = recent
+        .iter()
+        .filter(|s| s.kind == SwingKind::High)
+        .copied()
+        .collect();
+    let lows: Vec<SwingPoint> = recent
+        .iter()
+        .filter(|s| s.kind == SwingKind::Low)
+        .copied()
+        .collect();
+    if highs.len() < 2 || lows.len() < 2 {
+        return None;
+    }
+
+    let (hi_slope, hi_icpt) = fit_line(&highs);
+    let (lo_slope, lo_icpt) = fit_line(&lows);
+    let avg = recent.iter().map(|s| s.price).sum::<f64>() / recent.len() as f64;
+    let su = hi_slope / avg;
+    let sl = lo_slope / avg;
+    let flat = cfg.flat_slope;
+    let end = recent[recent.len() - 1].index as f64;
+    let upper_end = hi_slope * end + hi_icpt;
+    let lower_end = lo_slope * end + lo_icpt;
+    if upper_end <= lower_end {
+        return None;
+    }
+
+    let kind = if su.abs() <= flat && sl.abs() <= flat {
+        ChartPatternKind::Rectangle
+    } else if (su - sl).abs() <= flat {
+        if su > 0.0 {
+            ChartPatternKind::AscendingChannel
+        } else {
+            ChartPatternKind::DescendingChannel
+        }
+    } else if su.abs() <= flat && sl > 0.0 {
+        ChartPatternKind::AscendingTriangle
+    } else if sl.abs() <= flat && su < 0.0 {
+        ChartPatternKind::DescendingTriangle
+    } else if su < 0.0 && sl > 0.0 {
+        ChartPatternKind::SymmetricalTriangle
+    } else {
+        return None;
+    };
+
+    let residual = |points: &[SwingPoint], slope: f64, icpt: f64| {
+        points
+            .iter()
+            .map(|p| (p.price - (slope * p.index as f64 + icpt)).abs() / avg)
+            .fold(0.0, f64::max)
+    };
+    let max_residual = residual(&highs, hi_slope, hi_icpt).max(residual(&lows, lo_slope, lo_icpt));
+    let fit = closeness(max_residual, cfg.tolerance);
+    if fit == 0.0 {
+        return None;
+    }
+    let touches = ((highs.len() + lows.len()) as f64 / 6.0).min(1.0);
+    let key_level = match kind.bias() {
+        Bias::Bearish => lower_end,
+        _ => upper_end,
+    };
+    Some(pattern(kind, 0.5 * fit + 0.5 * touches, key_level, recent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Piecewise-linear price path through `pivots`, `seg` bars per leg,
+    /// with a 1-point high/low range around each close.
+    fn zigzag(pivots: &[f64], seg: usize) -> Vec<Candle> {
+        let mut out = Vec::new();
+        for w in pivots.windows(2) {
+            for step in 0..seg {
+                let p = w[0] + (w[1] - w[0]) * step as f64 / seg as f64;
+                out.push(Candle {
+                    timestamp: out.len() as u64,
+                    open: p,
+                    high: p + 0.5,
+                    low: p - 0.5,
+                    close: p,
+                    volume: 1.0,
+                });
+            }
+        }
+        out
+    }
+
+    fn kinds(found: &[ChartPattern]) -> Vec<ChartPatternKind> {
+        found.iter().map(|p| p.kind).collect()
+    }
+
+    #[test]
+    fn swing_points_on_zigzag() {
+        let candles = zigzag(&[100.0, 110.0, 100.0, 110.0, 100.0], 5);
+        let swings = swing_points(&candles, 2);
+        let got: Vec<(usize, SwingKind)> = swings.iter().map(|s| (s.index, s.kind)).collect();
+        assert_eq!(
+            got,
+            vec![
+                (5, SwingKind::High),
+                (10, SwingKind::Low),
+                (15, SwingKind::High)
+            ]
+        );
+        assert_eq!(swings[0].price, 110.5);
+        assert_eq!(swings[1].price, 99.5);
+    }
+
+    #[test]
+    fn swing_points_short_input() {
+        assert!(swing_points(&zigzag(&[1.0, 2.0], 3), 3).is_empty());
+        assert!(swing_points(&zigzag(&[1.0, 2.0, 1.0], 3), 0).is_empty());
+    }
+
+    #[test]
+    fn alternate_keeps_most_extreme() {
+        let s = |index, price, kind| SwingPoint { index, price, kind };
+        let swings = [
+            s(1, 10.0, SwingKind::High),
+            s(3, 12.0, SwingKind::High),
+            s(5, 8.0, SwingKind::Low),
+        ];
+        let alt = alternate(&swings);
+        assert_eq!(alt.len(), 2);
+        assert_eq!(alt[0].index, 3);
+    }
+
+    #[test]
+    fn double_top_and_bottom() {
+        let top = detect_chart_patterns(&zigzag(&[100.0, 110.0, 102.0, 110.5, 100.0], 5));
+        let p = top
+            .iter()
+            .find(|p| p.kind == ChartPatternKind::DoubleTop)
+            .expect("double top");
+        assert_eq!(p.bias, Bias::Bearish);
+        assert_eq!(p.key_level, 101.5);
+        assert_eq!((p.start, p.end), (5, 15));
+        assert!(p.confidence > 0.5 && p.confidence <= 1.0);
+
+        let bottom = detect_chart_patterns(&zigzag(&[110.0, 100.0, 108.0, 100.5, 110.0], 5));
+        assert!(kinds(&bottom).contains(&ChartPatternKind::DoubleBottom));
+    }
+
+    #[test]
+    fn unequal_peaks_are_not_a_double_top() {
+        let found = detect_chart_patterns(&zigzag(&[100.0, 110.0, 102.0, 120.0, 100.0], 5));
+        assert!(!kinds(&found).contains(&ChartPatternKind::DoubleTop));
+    }
+
+    #[test]
+    fn head_and_shoulders_and_inverse() {
+        let hs = detect_chart_patterns(&zigzag(
+            &[100.0, 110.0, 104.0, 118.0, 104.0, 110.5, 100.0],
+            5,
+        ));
+        let p = hs
+            .iter()
+            .find(|p| p.kind == ChartPatternKind::HeadAndShoulders)
+            .expect("head and shoulders");
+        assert_eq!(p.points.len(), 5);
+        assert_eq!(p.key_level, 103.5);
+
+        let inv = detect_chart_patterns(&zigzag(
+            &[118.0, 108.0, 114.0, 100.0, 114.0, 107.5, 118.0],
+            5,
+        ));
+        assert!(kinds(&inv).contains(&ChartPatternKind::InverseHeadAndShoulders));
+        assert!(!kinds(&inv).contains(&ChartPatternKind::HeadAndShoulders));
+    }
+
+    #[test]
+    fn flat_head_is_not_head_and_shoulders() {
+        let found = detect_chart_patterns(&zigzag(
+            &[100.0, 110.0, 104.0, 110.5, 104.0, 110.0, 100.0],
+            5,
+        ));
+        assert!(!kinds(&found).contains(&ChartPatternKind::HeadAndShoulders));
+    }
+
+    #[test]
+    fn triangles() {
+        let asc = detect_chart_patterns(&zigzag(
+            &[95.0, 100.0, 110.0, 103.0, 110.0, 106.0, 110.0, 108.0],
+            5,
+        ));
+        let p = asc
+            .iter()
+            .find(|p| p.kind == ChartPatternKind::AscendingTriangle)
+            .expect("ascending triangle");
+        assert!((p.key_level - 110.5).abs() < 1e-9);
+
+        let desc = detect_chart_patterns(&zigzag(
+            &[115.0, 110.0, 100.0, 107.0, 100.0, 104.0, 100.0, 102.0],
+            5,
+        ));
+        assert!(kinds(&desc).contains(&ChartPatternKind::DescendingTriangle));
+
+        let sym = detect_chart_patterns(&zigzag(
+            &[95.0, 100.0, 110.0, 102.0, 108.0, 104.0, 106.0, 105.0],
+            5,
+        ));
+        assert!(kinds(&sym).contains(&ChartPatternKind::SymmetricalTriangle));
+    }
+
+    #[test]
+    fn channels() {
+        let up = detect_chart_patterns(&zigzag(
+            &[95.0, 100.0, 110.0, 103.0, 113.0, 106.0, 116.0, 110.0],
+            5,
+        ));
+        assert!(kinds(&up).contains(&ChartPatternKind::AscendingChannel));
+
+        let range = detect_chart_patterns(&zigzag(
+            &[95.0, 100.0, 110.0, 100.0, 110.0, 100.0, 110.0, 105.0],
+            5,
+        ));
+        assert!(kinds(&range).contains(&ChartPatternKind::Rectangle));
+    }
+
+    #[test]
+    fn output_sorted_by_end() {
+        let found = detect_chart_patterns(&zigzag(
+            &[95.0, 100.0, 110.0, 100.0, 110.0, 100.0, 110.0, 105.0],
+            5,
+        ));
+        assert!(found.len() > 1);
+        assert!(found.windows(2).all(|w| w[0].end <= w[1].end));
+    }
+
+    #[test]
+    fn invalid_config() {
+        let candles = zigzag(&[100.0, 110.0, 100.0], 5);
+        for cfg in [
+            ChartPatternConfig {
+                swing_lookback: 0,
+                ..Default::default()
+            },
+            ChartPatternConfig {
+                tolerance: 0.0,
+                ..Default::default()
+            },
+            ChartPatternConfig {
+                trendline_swings: 1,
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                detect_chart_patterns_with(&candles, &cfg),
+                Err(IndicatorError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn empty_input() {
+        assert!(detect_chart_patterns(&[]).is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+//! Fibonacci retracement and extension levels.
+//!
+//! [`Fibonacci`] measures a swing from `start` to `end` and projects the
+//! classic ratios onto it:
+//!
+//! - **Retracements** pull back from `end` towards `start`:
+//!   `end - ratio * (end - start)` for the ratios in
+//!   [`RETRACEMENT_RATIOS`] (0.236 … 0.786). Ratio 0 is `end`, 1 is
+//!   `start`.
+//! - **Extensions** project the swing past `end`:
+//!   `start + ratio * (end - start)` for the ratios in
+//!   [`EXTENSION_RATIOS`] (1.272 … 2.618).
+//!
+//! The formulas hold in both directions: for an upswing (`start` a low,
+//! `end` a high) retracements sit below the high and extensions above it;
+//! a downswing mirrors them.
+//!
+//! The swing can be given by hand, taken from the last two alternating
+//! swing points of a candle slice ([`Fibonacci::from_candles`], see
+//! [`chart::swing_points`](super::chart::swing_points)), or tracked as
+//! candles stream in with [`FibonacciTracker`].
+//!
+//! ```
+//! use rsta::patterns::fibonacci::Fibonacci;
+//!
+//! // Upswing from 100 to 200.
+//! let fib = Fibonacci::new(100.0, 200.0).unwrap();
+//! assert!(fib.is_upswing());
+//! assert_eq!(fib.retracement(0.5), 150.0);
+//! assert!((fib.retracement(0.618) - 138.2).abs() < 1e-9);
+//! assert!((fib.extension(1.618) - 261.8).abs() < 1e-9);
+//!
+//! let nearest = fib.nearest_level(140.0);
+//! assert_eq!(nearest.ratio, 0.618);
+//! ```
+
+use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::patterns::chart::{alternate, push_alternating, swing_points, SwingPoint, SwingTracker};
+
+/// Retracement ratios reported by [`Fibonacci::retracements`].
+pub const RETRACEMENT_RATIOS: [f64; 5] = [0.236, 0.382, 0.5, 0.618, 0.786];
+
+/// Extension ratios reported by [`Fibonacci::extensions`].
+pub const EXTENSION_RATIOS: [f64; 5] = [1.272, 1.414, 1.618, 2.0, 2.618];
+
+/// One Fibonacci level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibLevel {
+    /// Fraction of the swing the level is measured at.
+    pub ratio: f64,
+    /// Price of the level.
+    pub price: f64,
+}
+
+/// Fibonacci levels of one swing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fibonacci {
+    /// Price the swing starts from.
+    pub start: f64,
+    /// Price the swing ends at, the most recent extreme.
+    pub end: f64,
+}
+
+impl Fibonacci {
+    /// Levels of the swing from `start` to `end`.
+    ///
+    /// Both prices must be finite and differ.
+    pub fn new(start: f64, end: f64) -> Result<Self, IndicatorError> {
+        if !start.is_finite() {
+            return Err(IndicatorError::invalid_parameter(
+                "start",
+                start,
+                "must be finite",
+            ));
+        }
+        if !end.is_finite() || end == start {
+            return Err(IndicatorError::invalid_parameter(
+                "end",
+                end,
+                "must be finite and differ from start",
+            ));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Levels of the last swing in `candles`: the move between the two
+    /// most recent alternating swing points confirmed `lookback` bars on
+    /// each side. `None` until two such points exist.
+    pub fn from_candles(candles: &[Candle], lookback: usize) -> Option<Self> {
+        let swings = alternate(&swing_points(candles, lookback));
+        match swings.as_slice() {
+            [.., from, to] => Self::new(from.price, to.price).ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the swing moves up (`end` above `start`).
+    pub fn is_upswing(&self) -> bool {
+        self.end > self.start
+    }
+
+    /// Price retracing `ratio` of the swing back from `end`.
+    pub fn retracement(&self, ratio: f64) -> f64 {
+        self.end - ratio * (self.end - self.start)
+    }
+
+    /// Price projecting `ratio` of the swing from `start`.
+    pub fn extension(&self, ratio: f64) -> f64 {
+        self.start + ratio * (self.end - self.start)
+    }
+
+    /// Levels at [`RETRACEMENT_RATIOS`], nearest to `end` first.
+    pub fn retracements(&self) -> Vec<FibLevel> {
+        RETRACEMENT_RATIOS
+            .iter()
+            .map(|&ratio| FibLevel {
+                ratio,
+                price: self.retracement(ratio),
+            })
+            .collect()
+    }
+
+    /// Levels at [`EXTENSION_RATIOS`], nearest to `end` first.
+    pub fn extensions(&self) -> Vec<FibLevel> {
+        EXTENSION_RATIOS
+            .iter()
+            .map(|&ratio| FibLevel {
+                ratio,
+                price: self.extension(ratio),
+            })
+            .collect()
+    }
+
+    /// The retracement or extension level closest to `price`.
+    pub fn nearest_level(&self, price: f64) -> FibLevel {
+        self.retracements()
+            .into_iter()
+            .chain(self.extensions())
+            .min_by(|a, b| (a.price - price).abs().total_cmp(&(b.price - price).abs()))
+            .expect("ratio tables are not empty")
+    }
+}
+
+/// Streaming Fibonacci levels of the most recent swing.
+///
+/// Implements `Indicator<Candle, Fibonacci>`. Swing points are confirmed
+/// `lookback` bars late (see [`chart::swing_points`](super::chart::swing_points))
+/// and kept alternating high / low, a more extreme point of the same kind
+/// replacing the previous one. Output starts once two swing points are
+/// known and always measures the move between the last two.
+///
+/// ```
+/// use rsta::indicators::{Candle, Indicator};
+/// use rsta::patterns::fibonacci::FibonacciTracker;
+///
+/// // 100 → 120 → 110: the last confirmed swing runs from the 120.5 high
+/// // down to the 109.5 low.
+/// let path = [100.0, 105.0, 110.0, 115.0, 120.0, 117.5, 115.0, 112.5, 110.0, 112.0, 114.0, 116.0];
+/// let candles: Vec<Candle> = path.iter().enumerate().map(|(i, &p)| Candle {
+///     timestamp: i as u64, open: p, high: p + 0.5, low: p - 0.5, close: p, volume: 1.0,
+/// }).collect();
+///
+/// let mut tracker = FibonacciTracker::new(2).unwrap();
+/// let fib = *tracker.calculate(&candles).unwrap().last().unwrap();
+/// assert_eq!((fib.start, fib.end), (120.5, 109.5));
+/// assert!(!fib.is_upswing());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FibonacciTracker {
+    lookback: usize,
+    tracker: SwingTracker,
+    swings: Vec<SwingPoint>,
+    last: Option<Fibonacci>,
+}
+
+impl FibonacciTracker {
+    /// Create a new tracker
+    ///
+    /// # Arguments
+    /// * `lookback` - Bars on each side that confirm a swing point (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new tracker or an error
+    pub fn new(lookback: usize) -> Result<Self, IndicatorError> {
+        if lookback == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "lookback",
+                lookback,
+                "must be at least 1",
+            ));
+        }
+        Ok(Self {
+            lookback,
+            tracker: SwingTracker::new(lookback),
+            swings: Vec::with_capacity(3),
+            last: None,
+        })
+    }
+
+    /// The two swing points the current levels are measured between,
+    /// oldest first.
+    pub fn swing(&self) -> Option<(SwingPoint, SwingPoint)> {
+        match self.swings.as_slice() {
+            [.., from, to] => Some((*from, *to)),
+            _ => None,
+        }
+    }
+
+    /// Reset the tracker state
+    pub fn reset_state(&mut self) {
+        self.tracker.reset();
+        self.swings.clear();
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: Candle) -> Option<Fibonacci> {
+        for swing in self.tracker.push(candle) {
+            if push_alternating(&mut self.swings, swing) && self.swings.len() > 2 {
+                self.swings.remove(0);
+            }
+        }
+        if let Some((from, to)) = self.swing() {
+            if let Ok(fib) = Fibonacci::new(from.price, to.price) {
+                self.last = Some(fib);
+            }
+        }
+        self.last
+    }
+}
+
+impl Indicator<Candle, Fibonacci> for FibonacciTracker {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Fibonacci>, IndicatorError> {
+        self.reset_state();
+        Ok(data
+            .iter()
+            .filter_map(|&candle| self.step(candle))
+            .collect())
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<Fibonacci>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Fibonacci> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "FibonacciTracker"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.lookback)
+    }
+
+    // The earliest swing is confirmed once `lookback` bars follow it; an
+    // outside bar can be a swing high and a swing low at once.
+    fn min_periods(&self) -> usize {
+        2 * self.lookback + 1
+    }
+
+    fn output_offset(&self) -> usize {
+        2 * self.lookback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(path: &[f64]) -> Vec<Candle> {
+        path.iter()
+            .enumerate()
+            .map(|(i, &p)| Candle {
+                timestamp: i as u64,
+                open: p,
+                high: p + 0.5,
+                low: p - 0.5,
+                close: p,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn levels_mirror_for_a_downswing() {
+        let down = Fibonacci::new(200.0, 100.0).unwrap();
+        assert!(!down.is_upswing());
+        let retracements = down.retracements();
+        assert_eq!(retracements.len(), RETRACEMENT_RATIOS.len());
+        assert!((retracements[0].price - 123.6).abs() < 1e-9);
+        assert!(retracements.windows(2).all(|w| w[0].price < w[1].price));
+        assert!((down.extension(1.272) - 72.8).abs() < 1e-9);
+        assert!(down.extensions().iter().all(|l| l.price < 100.0));
+
+        assert!(Fibonacci::new(100.0, 100.0).is_err());
+        assert!(Fibonacci::new(f64::NAN, 100.0).is_err());
+        assert!(FibonacciTracker::new(0).is_err());
+    }
+
+    #[test]
+    fn tracker_matches_batch_swings() {
+        let path: Vec<f64> = (0..60)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 10.0 + i as f64 * 0.2)
+            .collect();
+        let candles = candles(&path);
+        let mut tracker = FibonacciTracker::new(3).unwrap();
+        for (i, candle) in candles.iter().enumerate() {
+            let streamed = tracker.next(*candle).unwrap();
+            assert_eq!(streamed, Fibonacci::from_candles(&candles[..=i], 3));
+        }
+        let (from, to) = tracker.swing().unwrap();
+        assert!(from.index < to.index);
+        assert_ne!(from.kind, to.kind);
+
+        // Fewer than two swings: nothing to measure.
+        assert_eq!(Fibonacci::from_candles(&candles[..8], 3), None);
+    }
+
+    #[test]
+    fn min_periods_is_the_earliest_output() {
+        for lookback in 1..4 {
+            // An outside bar in the middle is both swings at once.
+            let mut path = candles(&vec![100.0; 2 * lookback + 2]);
+            path[lookback].high = 110.0;
+            path[lookback].low = 90.0;
+            let mut tracker = FibonacciTracker::new(lookback).unwrap();
+            let first = path
+                .iter()
+                .position(|&c| tracker.next(c).unwrap().is_some())
+                .unwrap();
+            assert_eq!(first, tracker.min_periods() - 1);
+            assert_eq!(first, tracker.output_offset());
+            assert_eq!(tracker.calculate(&path).unwrap().len(), path.len() - first);
+        }
+    }
+}
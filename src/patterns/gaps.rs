@@ -0,0 +1,508 @@
+//! Opening gap detection and gap-fill statistics.
+//!
+//! A gap opens when a candle's open is away from the previous close by at
+//! least a threshold — an absolute price distance, a fraction of the
+//! previous close, or a multiple of the ATR of the bars before it. The gap
+//! spans the prices between the two; it is **filled** once a later range
+//! (or the rest of the gap bar itself) trades back to the previous close.
+//!
+//! [`GapDetector`] streams over candles: every bar it reports the gap it
+//! opened, if any, and the gaps it filled, keeps the still-open gaps (the
+//! levels price tends to revisit) and accumulates [`GapStats`] such as the
+//! fill rate.
+//!
+//! ```
+//! use rsta::indicators::{Candle, Indicator};
+//! use rsta::patterns::gaps::{GapDetector, GapDirection, GapThreshold};
+//!
+//! let candle = |t, open: f64, high: f64, low: f64, close: f64| Candle {
+//!     timestamp: t, open, high, low, close, volume: 1.0,
+//! };
+//! let mut gaps = GapDetector::new(GapThreshold::Percent(0.02)).unwrap();
+//!
+//! gaps.next(candle(0, 100.0, 101.0, 99.0, 100.0)).unwrap();
+//! // Opens 5% higher and holds: an open gap up.
+//! let bar = gaps.next(candle(1, 105.0, 106.0, 104.0, 105.5)).unwrap().unwrap();
+//! assert_eq!(bar.opened.unwrap().direction, GapDirection::Up);
+//! assert_eq!(gaps.open_gaps().len(), 1);
+//!
+//! // Two bars later the low reaches the old close of 100: filled.
+//! gaps.next(candle(2, 105.5, 106.0, 103.0, 103.5)).unwrap();
+//! let bar = gaps.next(candle(3, 103.5, 104.0, 99.5, 100.5)).unwrap().unwrap();
+//! assert_eq!(bar.filled[0].fill_bar, Some(3));
+//! assert_eq!(gaps.stats().fill_rate(), Some(1.0));
+//! ```
+
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Minimum distance between the open and the previous close for a gap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GapThreshold {
+    /// Price distance.
+    Absolute(f64),
+    /// Fraction of the previous close (`0.01` = 1%).
+    Percent(f64),
+    /// Multiple of the Average True Range of the bars before the gap bar
+    /// (Wilder's, over [`GapDetector::with_atr_period`] bars).
+    Atr(f64),
+}
+
+impl GapThreshold {
+    fn value(self) -> f64 {
+        match self {
+            GapThreshold::Absolute(v) | GapThreshold::Percent(v) | GapThreshold::Atr(v) => v,
+        }
+    }
+}
+
+/// Direction of a gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GapDirection {
+    /// Opened above the previous close.
+    Up,
+    /// Opened below the previous close.
+    Down,
+}
+
+/// An opening gap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gap {
+    /// Up or down.
+    pub direction: GapDirection,
+    /// Zero-based index of the gap bar.
+    pub bar: usize,
+    /// Timestamp of the gap bar.
+    pub timestamp: u64,
+    /// Close before the gap: the fill level.
+    pub prev_close: f64,
+    /// Open of the gap bar.
+    pub open: f64,
+    /// Fraction of the gap price has retraced so far, from 0 to 1.
+    pub filled_fraction: f64,
+    /// Bar the gap was filled on, `None` while open.
+    pub fill_bar: Option<usize>,
+}
+
+impl Gap {
+    /// Gap size in price, `|open - prev_close|`.
+    pub fn size(&self) -> f64 {
+        (self.open - self.prev_close).abs()
+    }
+
+    /// Whether price has traded back to the previous close.
+    pub fn is_filled(&self) -> bool {
+        self.fill_bar.is_some()
+    }
+
+    /// Record a candle's range; `true` if it completes the fill.
+    fn update(&mut self, candle: &Candle, bar: usize) -> bool {
+        let retraced = match self.direction {
+            GapDirection::Up => self.open - candle.low,
+            GapDirection::Down => candle.high - self.open,
+        };
+        self.filled_fraction = self
+            .filled_fraction
+            .max((retraced / self.size()).clamp(0.0, 1.0));
+        if self.filled_fraction >= 1.0 {
+            self.fill_bar = Some(bar);
+        }
+        self.is_filled()
+    }
+}
+
+/// What one candle did to the gaps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GapUpdate {
+    /// Gap opened by this candle, as it stands at the close.
+    pub opened: Option<Gap>,
+    /// Gaps filled on this candle, oldest first (a gap filled on its own
+    /// bar included).
+    pub filled: Vec<Gap>,
+}
+
+/// Running gap statistics.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GapStats {
+    /// Gaps up detected.
+    pub up: usize,
+    /// Gaps down detected.
+    pub down: usize,
+    /// Gaps up filled.
+    pub filled_up: usize,
+    /// Gaps down filled.
+    pub filled_down: usize,
+    /// Bars between gap and fill, summed over the filled gaps.
+    pub bars_to_fill: usize,
+    /// Gaps dropped unfilled because more than
+    /// [`GapDetector::with_max_open_gaps`] were open.
+    pub dropped: usize,
+}
+
+impl GapStats {
+    /// Total gaps detected.
+    pub fn gaps(&self) -> usize {
+        self.up + self.down
+    }
+
+    /// Total gaps filled.
+    pub fn filled(&self) -> usize {
+        self.filled_up + self.filled_down
+    }
+
+    /// Share of the gaps filled, `None` before the first gap.
+    pub fn fill_rate(&self) -> Option<f64> {
+        (self.gaps() > 0).then(|| self.filled() as f64 / self.gaps() as f64)
+    }
+
+    /// Average bars from gap to fill, `None` before the first fill.
+    pub fn mean_bars_to_fill(&self) -> Option<f64> {
+        (self.filled() > 0).then(|| self.bars_to_fill as f64 / self.filled() as f64)
+    }
+}
+
+/// Streaming gap detector with fill tracking. See the
+/// [module docs](self).
+///
+/// Implements `Indicator<Candle, GapUpdate>`, emitting from the second
+/// candle on. With an [`GapThreshold::Atr`] threshold no gap is reported
+/// until `atr_period` candles have passed.
+///
+/// At most [`DEFAULT_MAX_OPEN_GAPS`](Self::DEFAULT_MAX_OPEN_GAPS) gaps are
+/// tracked at once (see [`with_max_open_gaps`](Self::with_max_open_gaps)),
+/// so a trending market that never fills its gaps cannot grow the state
+/// without bound.
+#[derive(Debug, Clone)]
+pub struct GapDetector {
+    threshold: GapThreshold,
+    atr_period: usize,
+    max_open: usize,
+    prev_close: Option<f64>,
+    tr_sum: f64,
+    tr_count: usize,
+    atr: Option<f64>,
+    open: Vec<Gap>,
+    stats: GapStats,
+    bars: usize,
+    last: Option<GapUpdate>,
+}
+
+impl GapDetector {
+    /// Open gaps tracked unless set with
+    /// [`with_max_open_gaps`](Self::with_max_open_gaps).
+    pub const DEFAULT_MAX_OPEN_GAPS: usize = 100;
+
+    /// Create a new gap detector
+    ///
+    /// # Arguments
+    /// * `threshold` - Minimum gap (its value must be positive and finite)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new detector or an error
+    pub fn new(threshold: GapThreshold) -> Result<Self, IndicatorError> {
+        let value = threshold.value();
+        if !value.is_finite() || value <= 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "threshold",
+                value,
+                "must be positive and finite",
+            ));
+        }
+        Ok(Self {
+            threshold,
+            atr_period: 14,
+            max_open: Self::DEFAULT_MAX_OPEN_GAPS,
+            prev_close: None,
+            tr_sum: 0.0,
+            tr_count: 0,
+            atr: None,
+            open: Vec::new(),
+            stats: GapStats::default(),
+            bars: 0,
+            last: None,
+        })
+    }
+
+    /// ATR period for a [`GapThreshold::Atr`] threshold (14 by default).
+    pub fn with_atr_period(mut self, period: usize) -> Result<Self, IndicatorError> {
+        if period == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "atr_period",
+                period,
+                "must be at least 1",
+            ));
+        }
+        self.atr_period = period;
+        Ok(self)
+    }
+
+    /// Track at most `max_open` unfilled gaps. Past it the oldest open gap
+    /// is dropped and counted in [`GapStats::dropped`].
+    ///
+    /// # Arguments
+    /// * `max_open` - Open gaps kept (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The detector or an error
+    pub fn with_max_open_gaps(mut self, max_open: usize) -> Result<Self, IndicatorError> {
+        if max_open == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_open",
+                max_open,
+                "must be at least 1",
+            ));
+        }
+        self.max_open = max_open;
+        Ok(self)
+    }
+
+    /// The threshold in use.
+    pub fn threshold(&self) -> GapThreshold {
+        self.threshold
+    }
+
+    /// Gaps not filled yet, oldest first.
+    pub fn open_gaps(&self) -> &[Gap] {
+        &self.open
+    }
+
+    /// Statistics since the last reset.
+    pub fn stats(&self) -> GapStats {
+        self.stats
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.prev_close = None;
+        self.tr_sum = 0.0;
+        self.tr_count = 0;
+        self.atr = None;
+        self.open.clear();
+        self.stats = GapStats::default();
+        self.bars = 0;
+        self.last = None;
+    }
+
+    /// Minimum gap size given the previous close, `None` while the ATR
+    /// warms up.
+    fn min_size(&self, prev_close: f64) -> Option<f64> {
+        match self.threshold {
+            GapThreshold::Absolute(v) => Some(v),
+            GapThreshold::Percent(v) => Some(v * prev_close.abs()),
+            GapThreshold::Atr(v) => self.atr.map(|atr| v * atr),
+        }
+    }
+
+    /// Fold the candle's true range into Wilder's ATR.
+    fn update_atr(&mut self, candle: &Candle, prev_close: Option<f64>) {
+        let mut tr = candle.high - candle.low;
+        if let Some(prev) = prev_close {
+            tr = tr
+                .max((candle.high - prev).abs())
+                .max((candle.low - prev).abs());
+        }
+        let n = self.atr_period as f64;
+        self.atr = match self.atr {
+            Some(atr) => Some((atr * (n - 1.0) + tr) / n),
+            None => {
+                self.tr_sum += tr;
+                self.tr_count += 1;
+                (self.tr_count == self.atr_period).then(|| self.tr_sum / n)
+            }
+        };
+    }
+
+    fn step(&mut self, candle: Candle) -> Option<GapUpdate> {
+        let bar = self.bars;
+        self.bars += 1;
+        let prev_close = self.prev_close.replace(candle.close);
+
+        let mut update = GapUpdate {
+            opened: None,
+            filled: Vec::new(),
+        };
+        if let Some(prev) = prev_close {
+            let distance = candle.open - prev;
+            if self.min_size(prev).is_some_and(|min| distance.abs() >= min) {
+                let direction = if distance > 0.0 {
+                    GapDirection::Up
+                } else {
+                    GapDirection::Down
+                };
+                match direction {
+                    GapDirection::Up => self.stats.up += 1,
+                    GapDirection::Down => self.stats.down += 1,
+                }
+                self.open.push(Gap {
+                    direction,
+                    bar,
+                    timestamp: candle.timestamp,
+                    prev_close: prev,
+                    open: candle.open,
+                    filled_fraction: 0.0,
+                    fill_bar: None,
+                });
+            }
+        }
+
+        let stats = &mut self.stats;
+        self.open.retain_mut(|gap| {
+            if !gap.update(&candle, bar) {
+                return true;
+            }
+            match gap.direction {
+                GapDirection::Up => stats.filled_up += 1,
+                GapDirection::Down => stats.filled_down += 1,
+            }
+            stats.bars_to_fill += bar - gap.bar;
+            update.filled.push(*gap);
+            false
+        });
+        if self.open.len() > self.max_open {
+            let excess = self.open.len() - self.max_open;
+            self.open.drain(..excess);
+            self.stats.dropped += excess;
+        }
+        update.opened = update
+            .filled
+            .iter()
+            .chain(&self.open)
+            .find(|gap| gap.bar == bar)
+            .copied();
+
+        self.update_atr(&candle, prev_close);
+        prev_close?;
+        self.last = Some(update);
+        self.last.clone()
+    }
+}
+
+impl Indicator<Candle, GapUpdate> for GapDetector {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<GapUpdate>, IndicatorError> {
+        self.reset_state();
+        Ok(data
+            .iter()
+            .filter_map(|&candle| self.step(candle))
+            .collect())
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<GapUpdate>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&GapUpdate> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "GapDetector"
+    }
+
+    fn min_periods(&self) -> usize {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: 0,
+            open,
+            high,
+            low,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn gap_down_partially_then_fully_filled() {
+        let mut gaps = GapDetector::new(GapThreshold::Absolute(2.0)).unwrap();
+        let out = gaps
+            .calculate(&[
+                candle(50.0, 51.0, 49.0, 50.0),
+                // Opens 4 lower, rallies half way.
+                candle(46.0, 48.0, 45.0, 47.0),
+                candle(47.0, 49.0, 46.5, 48.5),
+                // Small gap: below the threshold.
+                candle(47.0, 49.5, 46.0, 49.0),
+                candle(49.0, 50.5, 48.0, 50.0),
+            ])
+            .unwrap();
+        assert_eq!(out.len(), 4);
+        let opened = out[0].opened.unwrap();
+        assert_eq!((opened.direction, opened.bar), (GapDirection::Down, 1));
+        assert_eq!(opened.filled_fraction, 0.5);
+        assert!(out[1].filled.is_empty() && out[2].opened.is_none());
+        assert_eq!(out[3].filled.len(), 1);
+        assert_eq!(out[3].filled[0].fill_bar, Some(4));
+
+        let stats = gaps.stats();
+        assert_eq!((stats.down, stats.filled_down), (1, 1));
+        assert_eq!(stats.mean_bars_to_fill(), Some(3.0));
+        assert!(gaps.open_gaps().is_empty());
+    }
+
+    #[test]
+    fn same_bar_fill_and_atr_threshold() {
+        let mut gaps = GapDetector::new(GapThreshold::Percent(0.01)).unwrap();
+        gaps.next(candle(100.0, 101.0, 99.0, 100.0)).unwrap();
+        // Gaps up 2% but trades back below the old close on the same bar.
+        let update = gaps
+            .next(candle(102.0, 103.0, 99.5, 100.5))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.opened, Some(update.filled[0]));
+        assert_eq!(update.filled[0].fill_bar, Some(1));
+
+        let quiet = candle(100.0, 101.0, 99.0, 100.0);
+        let mut atr = GapDetector::new(GapThreshold::Atr(1.5))
+            .unwrap()
+            .with_atr_period(3)
+            .unwrap();
+        let early = candle(104.0, 105.0, 103.0, 104.0);
+        let out = atr.calculate(&[quiet, early, quiet, quiet, quiet]).unwrap();
+        // The gaps around bar 1 come before the three-bar ATR is ready.
+        assert!(out.iter().all(|u| u.opened.is_none()));
+        let up = atr
+            .next(candle(105.0, 106.0, 104.0, 105.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(up.opened.map(|g| g.direction), Some(GapDirection::Up));
+
+        assert!(GapDetector::new(GapThreshold::Percent(0.0)).is_err());
+        assert!(GapDetector::new(GapThreshold::Absolute(f64::NAN)).is_err());
+        assert!(atr.with_atr_period(0).is_err());
+    }
+
+    #[test]
+    fn open_gaps_are_capped() {
+        let mut gaps = GapDetector::new(GapThreshold::Absolute(1.0))
+            .unwrap()
+            .with_max_open_gaps(3)
+            .unwrap();
+        // Every bar gaps up 2 and never looks back.
+        let rising: Vec<Candle> = (0..10)
+            .map(|i| {
+                let p = 100.0 + 2.0 * i as f64;
+                candle(p, p + 0.5, p - 0.5, p)
+            })
+            .collect();
+        gaps.calculate(&rising).unwrap();
+        let open: Vec<usize> = gaps.open_gaps().iter().map(|g| g.bar).collect();
+        assert_eq!(open, vec![7, 8, 9]);
+        assert_eq!((gaps.stats().up, gaps.stats().dropped), (9, 6));
+        assert!(GapDetector::new(GapThreshold::Absolute(1.0))
+            .unwrap()
+            .with_max_open_gaps(0)
+            .is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Support and resistance level extraction.
+//!
+//! [`SupportResistance`] builds horizontal price levels from two sources:
+//!
+//! - **Swing clusters**: confirmed swing highs and lows (see
+//!   [`chart::swing_points`](super::chart::swing_points)) within
+//!   `tolerance` of each other are merged into one level; each merged
+//!   swing counts as a touch.
+//! - **High-volume nodes**: a volume-by-price profile (log-spaced buckets
+//!   `tolerance` wide, fed with each candle's typical price) marks prices
+//!   where unusually much volume traded. A node that is a local peak of
+//!   the profile and at least `volume_node_ratio` times the average bucket
+//!   becomes a level of its own unless a swing cluster already sits there.
+//!
+//! Levels are ranked by `strength = touches + volume / mean bucket
+//! volume`, and labelled support or resistance relative to the latest
+//! close. The analyzer is incremental: feed candles with
+//! [`Indicator::next`] and read [`SupportResistance::levels`] at any time.
+//! Its memory stays bounded on an endless stream: past
+//! `max_clusters` the stalest cluster is dropped, and past
+//! `max_profile_buckets` the bucket farthest from the price.
+//!
+//! ```
+//! use rsta::indicators::{Candle, Indicator};
+//! use rsta::patterns::levels::{LevelKind, SupportResistance};
+//!
+//! // Price oscillating between 100 and 110.
+//! let mut sr = SupportResistance::new();
+//! for i in 0..60u64 {
+//!     let phase = (i % 10) as f64;
+//!     let p = if phase < 5.0 { 100.0 + 2.0 * phase } else { 120.0 - 2.0 * phase };
+//!     let candle = Candle {
+//!         timestamp: i, open: p, high: p + 0.5, low: p - 0.5, close: p, volume: 1.0,
+//!     };
+//!     sr.next(candle).unwrap();
+//! }
+//!
+//! // Strongest resistance: the range top (highs at 110.5), touched on
+//! // every cycle.
+//! let levels = sr.levels();
+//! let top = levels.iter().find(|l| l.kind == LevelKind::Resistance).unwrap();
+//! assert_eq!(top.price, 110.5);
+//! assert!(top.touches >= 5);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::patterns::chart::SwingTracker;
+
+/// Which side of the latest close a level is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelKind {
+    /// At or below the latest close.
+    Support,
+    /// Above the latest close.
+    Resistance,
+}
+
+/// A ranked horizontal price level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    /// Level price (mean of the clustered swings, or the volume bucket
+    /// centre for a pure high-volume node).
+    pub price: f64,
+    /// Support or resistance relative to the latest close.
+    pub kind: LevelKind,
+    /// Number of swing highs/lows merged into the level (`0` for a pure
+    /// high-volume node).
+    pub touches: usize,
+    /// Volume traded within `tolerance` of `price`.
+    pub volume: f64,
+    /// Ranking score: `touches + volume / mean bucket volume`.
+    pub strength: f64,
+    /// Bar index of the most recent touching swing.
+    pub last_touch: Option<usize>,
+}
+
+/// Tunable thresholds for [`SupportResistance`].
+#[derive(Debug, Clone, Copy)]
+pub struct SupportResistanceConfig {
+    /// Bars on each side that confirm a swing point. Default 3.
+    pub swing_lookback: usize,
+    /// Relative width within which swings merge into one level; also the
+    /// volume-profile bucket width. Default 0.005.
+    pub tolerance: f64,
+    /// How many times the average bucket volume a node needs to become a
+    /// level. Default 1.5.
+    pub volume_node_ratio: f64,
+    /// Maximum number of levels reported. Default 10.
+    pub max_levels: usize,
+    /// Maximum number of swing clusters kept; past it the cluster touched
+    /// longest ago is dropped. Default 50.
+    pub max_clusters: usize,
+    /// Maximum number of volume-profile buckets kept; past it the bucket
+    /// farthest from the latest price is dropped. Default 500.
+    pub max_profile_buckets: usize,
+}
+
+impl Default for SupportResistanceConfig {
+    fn default() -> Self {
+        Self {
+            swing_lookback: 3,
+            tolerance: 0.005,
+            volume_node_ratio: 1.5,
+            max_levels: 10,
+            max_clusters: 50,
+            max_profile_buckets: 500,
+        }
+    }
+}
+
+/// Swings merged into one level.
+#[derive(Debug, Clone)]
+struct Cluster {
+    price_sum: f64,
+    touches: usize,
+    last_touch: usize,
+}
+
+impl Cluster {
+    fn price(&self) -> f64 {
+        self.price_sum / self.touches as f64
+    }
+}
+
+/// Incremental support / resistance analyzer.
+///
+/// Implements `Indicator<Candle, Vec<Level>>`: every `next()` returns the
+/// current ranked levels, strongest first. Swing points are confirmed
+/// `swing_lookback` bars late, so a fresh extreme only becomes a touch
+/// once price has moved away from it.
+#[derive(Debug, Clone)]
+pub struct SupportResistance {
+    config: SupportResistanceConfig,
+    swings: SwingTracker,
+    clusters: Vec<Cluster>,
+    profile: BTreeMap<i64, f64>,
+    total_volume: f64,
+    last_close: Option<f64>,
+    last: Option<Vec<Level>>,
+}
+
+impl Default for SupportResistance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SupportResistance {
+    /// Analyzer with the default thresholds.
+    pub fn new() -> Self {
+        Self::with_config(SupportResistanceConfig::default()).expect("default config is valid")
+    }
+
+    /// Analyzer with custom thresholds.
+    pub fn with_config(config: SupportResistanceConfig) -> Result<Self, IndicatorError> {
+        if config.swing_lookback == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "swing_lookback",
+                config.swing_lookback,
+                "must be at least 1",
+            ));
+        }
+        if !config.tolerance.is_finite() || config.tolerance <= 0.0 || config.tolerance >= 1.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "tolerance",
+                config.tolerance,
+                "must be in (0, 1)",
+            ));
+        }
+        if !config.volume_node_ratio.is_finite() || config.volume_node_ratio <= 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "volume_node_ratio",
+                config.volume_node_ratio,
+                "must be positive and finite",
+            ));
+        }
+        if config.max_levels == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_levels",
+                config.max_levels,
+                "must be at least 1",
+            ));
+        }
+        if config.max_clusters == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_clusters",
+                config.max_clusters,
+                "must be at least 1",
+            ));
+        }
+        if config.max_profile_buckets == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_profile_buckets",
+                config.max_profile_buckets,
+                "must be at least 1",
+            ));
+        }
+        Ok(Self {
+            config,
+            swings: SwingTracker::new(config.swing_lookback),
+            clusters: Vec::new(),
+            profile: BTreeMap::new(),
+            total_volume: 0.0,
+            last_close: None,
+            last: None,
+        })
+    }
+
+    /// The thresholds in use.
+    pub fn config(&self) -> &SupportResistanceConfig {
+        &self.config
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.swings.reset();
+        self.clusters.clear();
+        self.profile.clear();
+        self.total_volume = 0.0;
+        self.last_close = None;
+        self.last = None;
+    }
+
+    /// Current levels, strongest first, at most `max_levels`.
+    pub fn levels(&self) -> Vec<Level> {
+        let tol = self.config.tolerance;
+        let mean = if self.profile.is_empty() {
+            0.0
+        } else {
+            self.total_volume / self.profile.len() as f64
+        };
+
+        let mut levels: Vec<Level> = self
+            .clusters
+            .iter()
+            .map(|c| self.level(c.price(), c.touches, Some(c.last_touch), mean))
+            .collect();
+
+        for (&k, &v) in &self.profile {
+            let neighbour = |k: i64| self.profile.get(&k).copied().unwrap_or(0.0);
+            if v < self.config.volume_node_ratio * mean
+                || v < neighbour(k - 1)
+                || v < neighbour(k + 1)
+            {
+                continue;
+            }
+            let centre = (1.0 + tol).powf(k as f64 + 0.5);
+            if self
+                .clusters
+                .iter()
+                .any(|c| (c.price() - centre).abs() <= tol * centre)
+            {
+                continue;
+            }
+            levels.push(self.level(centre, 0, None, mean));
+        }
+
+        levels.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+        levels.truncate(self.config.max_levels);
+        levels
+    }
+
+    /// Highest-priced support level, if any.
+    pub fn nearest_support(&self) -> Option<Level> {
+        self.levels()
+            .into_iter()
+            .filter(|l| l.kind == LevelKind::Support)
+            .max_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    /// Lowest-priced resistance level, if any.
+    pub fn nearest_resistance(&self) -> Option<Level> {
+        self.levels()
+            .into_iter()
+            .filter(|l| l.kind == LevelKind::Resistance)
+            .min_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    fn level(&self, price: f64, touches: usize, last_touch: Option<usize>, mean: f64) -> Level {
+        let volume = self.band_volume(price);
+        let kind = match self.last_close {
+            Some(close) if price > close => LevelKind::Resistance,
+            _ => LevelKind::Support,
+        };
+        Level {
+            price,
+            kind,
+            touches,
+            volume,
+            strength: touches as f64 + if mean > 0.0 { volume / mean } else { 0.0 },
+            last_touch,
+        }
+    }
+
+    /// Volume-profile bucket of a positive price.
+    fn bucket(&self, price: f64) -> i64 {
+        (price.ln() / self.config.tolerance.ln_1p()).floor() as i64
+    }
+
+    fn band_volume(&self, price: f64) -> f64 {
+        if price <= 0.0 || !price.is_finite() {
+            return 0.0;
+        }
+        let tol = self.config.tolerance;
+        let lo = self.bucket(price * (1.0 - tol));
+        let hi = self.bucket(price * (1.0 + tol));
+        self.profile.range(lo..=hi).map(|(_, v)| v).sum()
+    }
+
+    fn add_touch(&mut self, price: f64, index: usize) {
+        let tol = self.config.tolerance;
+        let nearest = self
+            .clusters
+            .iter_mut()
+            .map(|c| ((c.price() - price).abs(), c))
+            .filter(|(d, c)| *d <= tol * c.price())
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        match nearest {
+            Some((_, c)) => {
+                c.price_sum += price;
+                c.touches += 1;
+                c.last_touch = index;
+            }
+            None => {
+                self.clusters.push(Cluster {
+                    price_sum: price,
+                    touches: 1,
+                    last_touch: index,
+                });
+                if self.clusters.len() > self.config.max_clusters {
+                    let stalest = self
+                        .clusters
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, c)| c.last_touch)
+                        .map(|(i, _)| i)
+                        .expect("clusters is not empty");
+                    self.clusters.remove(stalest);
+                }
+            }
+        }
+    }
+
+    fn add_volume(&mut self, price: f64, volume: f64) {
+        let bucket = self.bucket(price);
+        *self.profile.entry(bucket).or_insert(0.0) += volume;
+        self.total_volume += volume;
+        if self.profile.len() > self.config.max_profile_buckets {
+            let (&lowest, _) = self
+                .profile
+                .first_key_value()
+                .expect("profile is not empty");
+            let (&highest, _) = self.profile.last_key_value().expect("profile is not empty");
+            let farthest = if bucket - lowest >= highest - bucket {
+                lowest
+            } else {
+                highest
+            };
+            if let Some(dropped) = self.profile.remove(&farthest) {
+                self.total_volume -= dropped;
+            }
+        }
+    }
+}
+
+impl Indicator<Candle, Vec<Level>> for SupportResistance {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Vec<Level>>, IndicatorError> {
+        self.reset_state();
+        let mut out = Vec::with_capacity(data.len());
+        for candle in data {
+            if let Some(levels) = self.next(*candle)? {
+                out.push(levels);
+            }
+        }
+        Ok(out)
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<Vec<Level>>, IndicatorError> {
+        for swing in self.swings.push(value) {
+            self.add_touch(swing.price, swing.index);
+        }
+
+        let typical = (value.high + value.low + value.close) / 3.0;
+        if typical > 0.0 && typical.is_finite() && value.volume > 0.0 && value.volume.is_finite() {
+            self.add_volume(typical, value.volume);
+        }
+        self.last_close = Some(value.close);
+
+        self.last = Some(self.levels());
+        Ok(self.last.clone())
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Vec<Level>> {
+        self.last.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(i: usize, p: f64, volume: f64) -> Candle {
+        Candle {
+            timestamp: i as u64,
+            open: p,
+            high: p + 0.5,
+            low: p - 0.5,
+            close: p,
+            volume,
+        }
+    }
+
+    /// Oscillates between 100 and 110 with a 10-bar cycle.
+    fn range_bound(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let phase = (i % 10) as f64;
+                let p = if phase < 5.0 {
+                    100.0 + 2.0 * phase
+                } else {
+                    120.0 - 2.0 * phase
+                };
+                candle(i, p, 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn range_produces_top_and_bottom_levels() {
+        let mut sr = SupportResistance::new();
+        let levels = sr.calculate(&range_bound(80)).unwrap().pop().unwrap();
+        let top: Vec<f64> = levels[..2].iter().map(|l| l.price).collect();
+        assert!(top.contains(&110.5), "{:?}", levels);
+        assert!(top.contains(&99.5), "{:?}", levels);
+        assert!(levels[0].touches >= 7);
+        assert!(levels.windows(2).all(|w| w[0].strength >= w[1].strength));
+    }
+
+    #[test]
+    fn support_and_resistance_relative_to_close() {
+        let mut sr = SupportResistance::new();
+        // Last close is 102 (phase 1), rising.
+        sr.calculate(&range_bound(62)).unwrap();
+        let support = sr.nearest_support().unwrap();
+        let resistance = sr.nearest_resistance().unwrap();
+        assert_eq!(support.kind, LevelKind::Support);
+        assert_eq!(resistance.kind, LevelKind::Resistance);
+        assert!(support.price <= 102.0 && resistance.price > 102.0);
+    }
+
+    #[test]
+    fn streaming_matches_batch() {
+        let data = range_bound(45);
+        let mut batch = SupportResistance::new();
+        let all = batch.calculate(&data).unwrap();
+        assert_eq!(all.len(), data.len());
+
+        let mut stream = SupportResistance::new();
+        for (c, expected) in data.iter().zip(&all) {
+            assert_eq!(&stream.next(*c).unwrap().unwrap(), expected);
+        }
+        assert_eq!(stream.levels(), batch.levels());
+    }
+
+    #[test]
+    fn high_volume_node_becomes_level() {
+        // Heavy flat trading at 120, then a light-volume rally.
+        let mut data: Vec<Candle> = (0..20).map(|i| candle(i, 120.0, 100.0)).collect();
+        data.extend((20..40).map(|i| candle(i, 120.0 + (i - 19) as f64, 1.0)));
+        let mut sr = SupportResistance::new();
+        sr.calculate(&data).unwrap();
+        let levels = sr.levels();
+        let node = &levels[0];
+        assert_eq!(node.touches, 0);
+        assert_eq!(node.kind, LevelKind::Support);
+        assert!((node.price - 120.0).abs() <= 120.0 * 0.005);
+        assert!(node.volume >= 2_000.0);
+    }
+
+    #[test]
+    fn max_levels_and_reset() {
+        let cfg = SupportResistanceConfig {
+            max_levels: 1,
+            ..Default::default()
+        };
+        let mut sr = SupportResistance::with_config(cfg).unwrap();
+        sr.calculate(&range_bound(40)).unwrap();
+        assert_eq!(sr.levels().len(), 1);
+        sr.reset_state();
+        assert!(sr.levels().is_empty());
+        assert!(sr.nearest_support().is_none());
+    }
+
+    #[test]
+    fn clusters_and_profile_are_capped() {
+        let cfg = SupportResistanceConfig {
+            max_clusters: 4,
+            max_profile_buckets: 8,
+            ..Default::default()
+        };
+        let mut sr = SupportResistance::with_config(cfg).unwrap();
+        // A zigzag climbing 1% per bar visits a new price area every swing.
+        for i in 0..400u64 {
+            let p = 100.0 * 1.01f64.powi(i as i32) * if i % 6 < 3 { 1.0 } else { 0.98 };
+            let candle = Candle {
+                timestamp: i,
+                open: p,
+                high: p * 1.002,
+                low: p * 0.998,
+                close: p,
+                volume: 10.0,
+            };
+            sr.next(candle).unwrap();
+        }
+        assert!(sr.clusters.len() <= 4);
+        assert!(sr.profile.len() <= 8);
+        // The kept clusters are the most recent ones.
+        assert!(sr.clusters.iter().all(|c| c.last_touch > 350));
+        assert!((sr.total_volume - sr.profile.values().sum::<f64>()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn invalid_config() {
+        for cfg in [
+            SupportResistanceConfig {
+                swing_lookback: 0,
+                ..Default::default()
+            },
+            SupportResistanceConfig {
+                tolerance: 0.0,
+                ..Default::default()
+            },
+            SupportResistanceConfig {
+                volume_node_ratio: f64::NAN,
+                ..Default::default()
+            },
+            SupportResistanceConfig {
+                max_levels: 0,
+                ..Default::default()
+            },
+            SupportResistanceConfig {
+                max_clusters: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                SupportResistance::with_config(cfg),
+                Err(IndicatorError::InvalidParameter { .. })
+            ));
+        }
+    }
+}
@@ -2,10 +2,23 @@
 //!
 //! Currently exposes [`candlestick`] — geometric detection of common
 //! 1-, 2-, and 3-bar candle patterns (Doji, Hammer, Engulfing, Morning
-//! Star, Three White Soldiers, …).
+//! Star, Three White Soldiers, …), per bar or streaming via
+//! [`candlestick::CandlestickPatterns`].
 //!
-//! Chart pattern detection (head & shoulders, triangles, flags) is on
-//! the roadmap but not yet implemented; see
-//! [`todo/002-chart-pattern-detection.md`](https://github.com/Lsh0x/rsta/blob/main/todo/002-chart-pattern-detection.md).
+//! The module is also reachable as `rsta::indicators::patterns`.
+//!
+//! [`chart`] finds multi-bar formations on top of swing points: double
+//! tops/bottoms, head and shoulders, triangles and channels, each with a
+//! confidence score. [`levels`] clusters the same swing points, plus
+//! high-volume price nodes, into ranked support / resistance levels.
+//! [`gaps`] detects opening gaps and tracks whether and when they fill.
+//! [`fibonacci`] projects retracement and extension levels onto the latest
+//! swing, and [`trendlines`] fits rising / falling lines through swing points
+//! and reports when price breaks them.
 
 pub mod candlestick;
+pub mod chart;
+pub mod fibonacci;
+pub mod gaps;
+pub mod levels;
+pub mod trendlines;
@@ -0,0 +1,473 @@
+//! Trendline detection and breakout events.
+//!
+//! [`Trendlines`] draws lines through confirmed swing points as candles
+//! stream in:
+//!
+//! - a **rising** line joins two successive swing lows, the second higher
+//!   than the first, and acts as support;
+//! - a **falling** line joins two successive swing highs, the second lower
+//!   than the first, and acts as resistance.
+//!
+//! A later swing of the same kind within `tolerance` of an active line
+//! counts as another touch instead of starting a new line. A line breaks
+//! when a close ends beyond it by more than `tolerance`: below a rising
+//! line (bearish) or above a falling one (bullish). Broken lines are
+//! reported once as a [`TrendlineBreak`] and dropped.
+//!
+//! Swing points are confirmed `swing_lookback` bars late (see
+//! [`chart::swing_points`](super::chart::swing_points)), so a line only
+//! exists from the bar its second point is confirmed.
+//!
+//! Besides `Indicator<Candle, Vec<TrendlineBreak>>`, the analyzer
+//! implements [`Signal`] so breakouts can feed the signal combinators
+//! directly: `Long` on a falling-line break, `Short` on a rising-line
+//! break.
+//!
+//! ```
+//! use rsta::indicators::{Candle, Indicator};
+//! use rsta::patterns::trendlines::{TrendlineKind, Trendlines};
+//!
+//! // Higher lows at 104, 108, 112, then a sharp drop.
+//! let pivots = [100.0, 110.0, 104.0, 114.0, 108.0, 118.0, 112.0, 122.0, 90.0];
+//! let mut candles = Vec::new();
+//! for w in pivots.windows(2) {
+//!     for step in 0..5 {
+//!         let p = w[0] + (w[1] - w[0]) * step as f64 / 5.0;
+//!         candles.push(Candle {
+//!             timestamp: candles.len() as u64,
+//!             open: p, high: p + 0.5, low: p - 0.5, close: p, volume: 1.0,
+//!         });
+//!     }
+//! }
+//!
+//! let mut lines = Trendlines::new();
+//! let breaks: Vec<_> = lines.calculate(&candles).unwrap().concat();
+//! assert_eq!(breaks.len(), 1);
+//! assert_eq!(breaks[0].line.kind, TrendlineKind::Rising);
+//! assert_eq!(breaks[0].line.touches, 3);
+//! ```
+
+use crate::indicators::{Candle, Indicator, IndicatorError};
+use crate::patterns::candlestick::Bias;
+use crate::patterns::chart::{SwingKind, SwingPoint, SwingTracker};
+use crate::signals::{Signal, SignalEvent};
+
+/// Direction of a trendline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendlineKind {
+    /// Through higher swing lows; support below price.
+    Rising,
+    /// Through lower swing highs; resistance above price.
+    Falling,
+}
+
+/// An active trendline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trendline {
+    /// Rising or falling.
+    pub kind: TrendlineKind,
+    /// First swing point the line runs through.
+    pub anchor: SwingPoint,
+    /// Price change per bar.
+    pub slope: f64,
+    /// Number of swing points on the line (at least 2).
+    pub touches: usize,
+    /// Bar index of the most recent touching swing.
+    pub last_touch: usize,
+}
+
+impl Trendline {
+    /// Price of the line at bar `index`.
+    pub fn price_at(&self, index: usize) -> f64 {
+        self.anchor.price + self.slope * (index as f64 - self.anchor.index as f64)
+    }
+}
+
+/// A close beyond a trendline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrendlineBreak {
+    /// The line as it was when it broke.
+    pub line: Trendline,
+    /// Bar index of the breaking close.
+    pub index: usize,
+    /// The breaking close.
+    pub close: f64,
+}
+
+impl TrendlineBreak {
+    /// Bullish for a falling-line break, bearish for a rising-line break.
+    pub fn bias(&self) -> Bias {
+        match self.line.kind {
+            TrendlineKind::Rising => Bias::Bearish,
+            TrendlineKind::Falling => Bias::Bullish,
+        }
+    }
+}
+
+/// Tunable thresholds for [`Trendlines`].
+#[derive(Debug, Clone, Copy)]
+pub struct TrendlinesConfig {
+    /// Bars on each side that confirm a swing point. Default 3.
+    pub swing_lookback: usize,
+    /// Relative distance within which a swing touches a line, and beyond
+    /// which a close breaks it. Default 0.005.
+    pub tolerance: f64,
+    /// Maximum active lines per direction; the least recently touched is
+    /// dropped first. Default 3.
+    pub max_lines: usize,
+}
+
+impl Default for TrendlinesConfig {
+    fn default() -> Self {
+        Self {
+            swing_lookback: 3,
+            tolerance: 0.005,
+            max_lines: 3,
+        }
+    }
+}
+
+/// Incremental trendline analyzer.
+///
+/// Implements `Indicator<Candle, Vec<TrendlineBreak>>`: every `next()`
+/// returns the lines broken by that bar, usually none. The active lines
+/// are available through [`Trendlines::lines`].
+#[derive(Debug, Clone)]
+pub struct Trendlines {
+    config: TrendlinesConfig,
+    swings: SwingTracker,
+    last_high: Option<SwingPoint>,
+    last_low: Option<SwingPoint>,
+    rising: Vec<Trendline>,
+    falling: Vec<Trendline>,
+    bars: usize,
+    last: Option<Vec<TrendlineBreak>>,
+}
+
+impl Default for Trendlines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Trendlines {
+    /// Analyzer with the default thresholds.
+    pub fn new() -> Self {
+        Self::with_config(TrendlinesConfig::default()).expect("default config is valid")
+    }
+
+    /// Analyzer with custom thresholds.
+    pub fn with_config(config: TrendlinesConfig) -> Result<Self, IndicatorError> {
+        if config.swing_lookback == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "swing_lookback",
+                config.swing_lookback,
+                "must be at least 1",
+            ));
+        }
+        if !config.tolerance.is_finite() || config.tolerance < 0.0 || config.tolerance >= 1.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "tolerance",
+                config.tolerance,
+                "must be in [0, 1)",
+            ));
+        }
+        if config.max_lines == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "max_lines",
+                config.max_lines,
+                "must be at least 1",
+            ));
+        }
+        Ok(Self {
+            config,
+            swings: SwingTracker::new(config.swing_lookback),
+            last_high: None,
+            last_low: None,
+            rising: Vec::new(),
+            falling: Vec::new(),
+            bars: 0,
+            last: None,
+        })
+    }
+
+    /// The thresholds in use.
+    pub fn config(&self) -> &TrendlinesConfig {
+        &self.config
+    }
+
+    /// Active lines, rising first, each group oldest first.
+    pub fn lines(&self) -> Vec<Trendline> {
+        self.rising.iter().chain(&self.falling).copied().collect()
+    }
+
+    /// Active rising (support) lines, oldest first.
+    pub fn rising(&self) -> &[Trendline] {
+        &self.rising
+    }
+
+    /// Active falling (resistance) lines, oldest first.
+    pub fn falling(&self) -> &[Trendline] {
+        &self.falling
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.swings.reset();
+        self.last_high = None;
+        self.last_low = None;
+        self.rising.clear();
+        self.falling.clear();
+        self.bars = 0;
+        self.last = None;
+    }
+
+    fn add_swing(&mut self, swing: SwingPoint) {
+        let tol = self.config.tolerance;
+        let max_lines = self.config.max_lines;
+        let (lines, previous, kind) = match swing.kind {
+            SwingKind::Low => (&mut self.rising, &mut self.last_low, TrendlineKind::Rising),
+            SwingKind::High => (
+                &mut self.falling,
+                &mut self.last_high,
+                TrendlineKind::Falling,
+            ),
+        };
+
+        let mut touched = false;
+        for line in lines.iter_mut() {
+            let level = line.price_at(swing.index);
+            if (swing.price - level).abs() <= tol * level.abs() {
+                line.touches += 1;
+                line.last_touch = swing.index;
+                touched = true;
+            }
+        }
+
+        if !touched {
+            if let Some(prev) = *previous {
+                let slopes_right = match kind {
+                    TrendlineKind::Rising => swing.price > prev.price,
+                    TrendlineKind::Falling => swing.price < prev.price,
+                };
+                if slopes_right && swing.index > prev.index {
+                    lines.push(Trendline {
+                        kind,
+                        anchor: prev,
+                        slope: (swing.price - prev.price) / (swing.index - prev.index) as f64,
+                        touches: 2,
+                        last_touch: swing.index,
+                    });
+                    if lines.len() > max_lines {
+                        let stalest = lines
+                            .iter()
+                            .enumerate()
+                            .min_by_key(|(_, l)| l.last_touch)
+                            .map(|(i, _)| i)
+                            .expect("lines is not empty");
+                        lines.remove(stalest);
+                    }
+                }
+            }
+        }
+        *previous = Some(swing);
+    }
+
+    fn step(&mut self, candle: Candle) -> Vec<TrendlineBreak> {
+        for swing in self.swings.push(candle) {
+            self.add_swing(swing);
+        }
+        let index = self.bars;
+        self.bars += 1;
+
+        let tol = self.config.tolerance;
+        let close = candle.close;
+        let mut breaks = Vec::new();
+        let mut check = |line: &Trendline| {
+            let level = line.price_at(index);
+            let margin = tol * level.abs();
+            let broken = match line.kind {
+                TrendlineKind::Rising => close < level - margin,
+                TrendlineKind::Falling => close > level + margin,
+            };
+            if broken {
+                breaks.push(TrendlineBreak {
+                    line: *line,
+                    index,
+                    close,
+                });
+            }
+            !broken
+        };
+        self.rising.retain(&mut check);
+        self.falling.retain(&mut check);
+        breaks
+    }
+}
+
+impl Indicator<Candle, Vec<TrendlineBreak>> for Trendlines {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Vec<TrendlineBreak>>, IndicatorError> {
+        self.reset_state();
+        Ok(data.iter().map(|&candle| self.step(candle)).collect())
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<Vec<TrendlineBreak>>, IndicatorError> {
+        self.last = Some(self.step(value));
+        Ok(self.last.clone())
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Vec<TrendlineBreak>> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "Trendlines"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.config.swing_lookback)
+    }
+}
+
+impl Signal for Trendlines {
+    type Input = Candle;
+
+    /// `Long` when a falling line breaks, `Short` when a rising one does,
+    /// `Hold` otherwise (also when both break on the same bar). `None`
+    /// until the first swing point could be confirmed.
+    fn next(&mut self, value: Candle) -> Option<SignalEvent> {
+        let breaks = self.step(value);
+        let event = match (
+            breaks.iter().any(|b| b.bias() == Bias::Bullish),
+            breaks.iter().any(|b| b.bias() == Bias::Bearish),
+        ) {
+            (true, false) => SignalEvent::Long,
+            (false, true) => SignalEvent::Short,
+            _ => SignalEvent::Hold,
+        };
+        self.last = Some(breaks);
+        (self.bars > 2 * self.config.swing_lookback).then_some(event)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Straight-line moves between `pivots`, five bars per leg.
+    fn zigzag(pivots: &[f64]) -> Vec<Candle> {
+        let mut out = Vec::new();
+        for w in pivots.windows(2) {
+            for step in 0..5 {
+                let p = w[0] + (w[1] - w[0]) * step as f64 / 5.0;
+                out.push(Candle {
+                    timestamp: out.len() as u64,
+                    open: p,
+                    high: p + 0.5,
+                    low: p - 0.5,
+                    close: p,
+                    volume: 1.0,
+                });
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn rising_line_collects_touches_then_breaks() {
+        let candles = zigzag(&[100.0, 110.0, 104.0, 114.0, 108.0, 118.0, 112.0, 122.0, 90.0]);
+        let mut lines = Trendlines::new();
+        let all = lines.calculate(&candles[..35]).unwrap();
+        assert!(all.iter().all(Vec::is_empty));
+        assert_eq!(lines.rising().len(), 1);
+        assert!(lines.falling().is_empty());
+        let line = lines.rising()[0];
+        assert_eq!(
+            (line.anchor.index, line.touches, line.last_touch),
+            (10, 3, 30)
+        );
+        assert!((line.slope - 0.4).abs() < 1e-9);
+
+        let mut broke_at = None;
+        for (i, c) in candles.iter().enumerate().skip(35) {
+            let breaks = Indicator::next(&mut lines, *c).unwrap().unwrap();
+            if let Some(b) = breaks.first() {
+                broke_at.get_or_insert(i);
+                assert_eq!(b.bias(), Bias::Bearish);
+                assert!(b.close < b.line.price_at(i));
+            }
+        }
+        assert_eq!(broke_at, Some(37));
+        assert!(lines.lines().is_empty());
+    }
+
+    #[test]
+    fn falling_line_break_signals_long() {
+        let pivots: Vec<f64> = [100.0, 110.0, 104.0, 114.0, 108.0, 118.0, 112.0, 122.0, 90.0]
+            .iter()
+            .map(|p| 220.0 - p)
+            .collect();
+        let candles = zigzag(&pivots);
+        let mut lines = Trendlines::new();
+        let events: Vec<_> = candles
+            .iter()
+            .map(|c| Signal::next(&mut lines, *c))
+            .collect();
+        assert!(events[..6].iter().all(Option::is_none));
+        let longs: Vec<usize> = events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| **e == Some(SignalEvent::Long))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(longs, vec![37]);
+        assert!(!events.contains(&Some(SignalEvent::Short)));
+
+        Signal::reset(&mut lines);
+        assert!(lines.lines().is_empty());
+    }
+
+    #[test]
+    fn max_lines_and_invalid_config() {
+        // Rising lows that never line up: each swing starts a new line.
+        let candles = zigzag(&[
+            100.0, 110.0, 101.0, 111.0, 103.0, 113.0, 106.0, 116.0, 110.0, 120.0,
+        ]);
+        let cfg = TrendlinesConfig {
+            max_lines: 2,
+            ..Default::default()
+        };
+        let mut lines = Trendlines::with_config(cfg).unwrap();
+        lines.calculate(&candles).unwrap();
+        assert_eq!(lines.rising().len(), 2);
+        assert!(lines.rising()[0].last_touch < lines.rising()[1].last_touch);
+
+        for cfg in [
+            TrendlinesConfig {
+                swing_lookback: 0,
+                ..Default::default()
+            },
+            TrendlinesConfig {
+                tolerance: f64::NAN,
+                ..Default::default()
+            },
+            TrendlinesConfig {
+                max_lines: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                Trendlines::with_config(cfg),
+                Err(IndicatorError::InvalidParameter { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,444 @@
+//! Chart export for visual checks
+//!
+//! [`Chart`] renders candles with indicator overlays (moving averages,
+//! bands) on the price pane and oscillators in panes below it, to SVG or
+//! PNG via [`plotters`]. It is meant for eyeballing a new indicator or
+//! strategy, not for publication-quality charts.
+//!
+//! Every output field of an indicator becomes its own line, so Bollinger
+//! Bands draw three lines and MACD three in its pane. Inputs are
+//! [`IndicatorSeries`] values, which carry the timestamps that place each
+//! value under its candle.
+//!
+//! ```
+//! use rsta::indicators::momentum::Rsi;
+//! use rsta::indicators::trend::Sma;
+//! use rsta::indicators::volatility::BollingerBands;
+//! use rsta::indicators::{Candle, Indicator};
+//! use rsta::plot::Chart;
+//!
+//! let candles: Vec<Candle> = (0..60)
+//!     .map(|i| {
+//!         let c = 100.0 + (i as f64 * 0.3).sin() * 5.0;
+//!         Candle { timestamp: i * 60, open: c - 0.5, high: c + 1.0, low: c - 1.0, close: c, volume: 10.0 }
+//!     })
+//!     .collect();
+//!
+//! let svg = Chart::new(&candles)
+//!     .with_title("SMA / BB / RSI")
+//!     .with_overlay("SMA 10", &Sma::new(10).unwrap().calculate_series(&candles).unwrap())
+//!     .with_overlay("BB 20", &BollingerBands::new(20, 2.0).unwrap().calculate_series(&candles).unwrap())
+//!     .with_pane("RSI 14", &Rsi::new(14).unwrap().calculate_series(&candles).unwrap())
+//!     .to_svg()
+//!     .unwrap();
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use std::ops::Range;
+use std::path::Path;
+
+use plotters::coord::types::{RangedCoordf64, RangedCoordu64};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::indicators::{Candle, IndicatorSeries, IndicatorValue};
+
+/// Errors emitted by the plot module.
+#[derive(Debug, thiserror::Error)]
+pub enum PlotError {
+    /// The chart has no candles to draw.
+    #[error("No candles to plot")]
+    NoData,
+
+    /// Error from the drawing backend (I/O, font or encoding).
+    #[error("Drawing error: {0}")]
+    Drawing(String),
+}
+
+impl PlotError {
+    fn drawing(e: impl std::fmt::Display) -> Self {
+        PlotError::Drawing(e.to_string())
+    }
+}
+
+/// One named line, as `(timestamp, value)` points.
+#[derive(Debug, Clone)]
+struct Line {
+    label: String,
+    points: Vec<(u64, f64)>,
+}
+
+/// A pane below the price pane with its own value axis.
+#[derive(Debug, Clone)]
+struct Pane {
+    title: String,
+    lines: Vec<Line>,
+}
+
+/// Candlestick chart with indicator overlays and panes. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Chart<'a> {
+    candles: &'a [Candle],
+    title: String,
+    size: (u32, u32),
+    overlays: Vec<Line>,
+    panes: Vec<Pane>,
+}
+
+impl<'a> Chart<'a> {
+    /// Create a chart of `candles`, 1280 x 720 pixels by default.
+    pub fn new(candles: &'a [Candle]) -> Self {
+        Self {
+            candles,
+            title: String::new(),
+            size: (1280, 720),
+            overlays: Vec::new(),
+            panes: Vec::new(),
+        }
+    }
+
+    /// Set the caption drawn above the price pane.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Set the image size in pixels.
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// Draw an indicator over the candles, one line per output field
+    ///
+    /// # Arguments
+    /// * `label` - Legend label; multi-field outputs append the field name
+    /// * `series` - The indicator values, from `calculate_series()`
+    pub fn with_overlay<O>(mut self, label: &str, series: &IndicatorSeries<O>) -> Self
+    where
+        O: Copy + Into<IndicatorValue>,
+    {
+        self.overlays.extend(lines(label, series));
+        self
+    }
+
+    /// Draw an indicator in a new pane below the price pane, one line per
+    /// output field
+    ///
+    /// # Arguments
+    /// * `label` - Pane title and legend label
+    /// * `series` - The indicator values, from `calculate_series()`
+    pub fn with_pane<O>(mut self, label: &str, series: &IndicatorSeries<O>) -> Self
+    where
+        O: Copy + Into<IndicatorValue>,
+    {
+        self.panes.push(Pane {
+            title: label.to_string(),
+            lines: lines(label, series),
+        });
+        self
+    }
+
+    /// Render the chart as an SVG document.
+    pub fn to_svg(&self) -> Result<String, PlotError> {
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, self.size).into_drawing_area();
+            self.draw(&root)?;
+            root.present().map_err(PlotError::drawing)?;
+        }
+        Ok(svg)
+    }
+
+    /// Render the chart to an SVG file.
+    pub fn save_svg<P: AsRef<Path>>(&self, path: P) -> Result<(), PlotError> {
+        let root = SVGBackend::new(path.as_ref(), self.size).into_drawing_area();
+        self.draw(&root)?;
+        root.present().map_err(PlotError::drawing)
+    }
+
+    /// Render the chart to a PNG file. Text uses the system's sans-serif
+    /// font.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), PlotError> {
+        let root = BitMapBackend::new(path.as_ref(), self.size).into_drawing_area();
+        self.draw(&root)?;
+        root.present().map_err(PlotError::drawing)
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>) -> Result<(), PlotError> {
+        let (Some(first), Some(last)) = (self.candles.first(), self.candles.last()) else {
+            return Err(PlotError::NoData);
+        };
+        root.fill(&WHITE).map_err(PlotError::drawing)?;
+
+        // Pad the time axis by one bar so edge candles are not clipped.
+        let bar = (last.timestamp - first.timestamp) / (self.candles.len() as u64).max(2) + 1;
+        let x_range = first.timestamp.saturating_sub(bar)..last.timestamp + bar;
+
+        let (price_area, panes_area) = if self.panes.is_empty() {
+            (root.clone(), None)
+        } else {
+            let (price, rest) = root.split_vertically(self.size.1 * 3 / 5);
+            (price, Some(rest))
+        };
+
+        self.draw_price(&price_area, x_range.clone())?;
+        if let Some(area) = panes_area {
+            let areas = area.split_evenly((self.panes.len(), 1));
+            for (pane, area) in self.panes.iter().zip(&areas) {
+                draw_pane(area, x_range.clone(), pane)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_price<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        x_range: Range<u64>,
+    ) -> Result<(), PlotError> {
+        let prices = self
+            .candles
+            .iter()
+            .flat_map(|c| [c.low, c.high])
+            .chain(self.overlays.iter().flat_map(Line::values));
+        let mut chart = ChartBuilder::on(area)
+            .caption(&self.title, ("sans-serif", 20))
+            .margin(8)
+            .x_label_area_size(if self.panes.is_empty() { 30 } else { 0 })
+            .y_label_area_size(60)
+            .build_cartesian_2d(x_range, value_range(prices))
+            .map_err(PlotError::drawing)?;
+        chart
+            .configure_mesh()
+            .light_line_style(WHITE)
+            .draw()
+            .map_err(PlotError::drawing)?;
+
+        let width = (area.dim_in_pixel().0 as usize / self.candles.len() / 2).clamp(1, 12) as u32;
+        chart
+            .draw_series(self.candles.iter().map(|c| {
+                CandleStick::new(
+                    c.timestamp,
+                    c.open,
+                    c.high,
+                    c.low,
+                    c.close,
+                    GREEN.filled(),
+                    RED.filled(),
+                    width,
+                )
+            }))
+            .map_err(PlotError::drawing)?;
+
+        draw_lines(&mut chart, &self.overlays)
+    }
+}
+
+fn draw_pane<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    x_range: Range<u64>,
+    pane: &Pane,
+) -> Result<(), PlotError> {
+    let mut chart = ChartBuilder::on(area)
+        .caption(&pane.title, ("sans-serif", 14))
+        .margin(8)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(
+            x_range,
+            value_range(pane.lines.iter().flat_map(Line::values)),
+        )
+        .map_err(PlotError::drawing)?;
+    chart
+        .configure_mesh()
+        .light_line_style(WHITE)
+        .draw()
+        .map_err(PlotError::drawing)?;
+    draw_lines(&mut chart, &pane.lines)
+}
+
+fn draw_lines<'a, DB: DrawingBackend + 'a>(
+    chart: &mut ChartContext<'a, DB, Cartesian2d<RangedCoordu64, RangedCoordf64>>,
+    lines: &[Line],
+) -> Result<(), PlotError> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        chart
+            .draw_series(LineSeries::new(line.points.iter().copied(), color))
+            .map_err(PlotError::drawing)?
+            .label(&line.label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 16, y)], color));
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(PlotError::drawing)
+}
+
+impl Line {
+    fn values(&self) -> impl Iterator<Item = f64> + '_ {
+        self.points.iter().map(|&(_, v)| v)
+    }
+}
+
+/// Split a series into one line per output field, dropping non-finite
+/// values.
+fn lines<O>(label: &str, series: &IndicatorSeries<O>) -> Vec<Line>
+where
+    O: Copy + Into<IndicatorValue>,
+{
+    let mut lines: Vec<Line> = Vec::new();
+    for (timestamp, &value) in series.iter() {
+        let fields = value.into().fields();
+        if lines.is_empty() {
+            lines = fields
+                .iter()
+                .map(|&(field, _)| Line {
+                    label: match fields.len() {
+                        1 => label.to_string(),
+                        _ => format!("{label} {field}"),
+                    },
+                    points: Vec::with_capacity(series.len()),
+                })
+                .collect();
+        }
+        for (line, (_, v)) in lines.iter_mut().zip(fields) {
+            if v.is_finite() {
+                line.points.push((timestamp, v));
+            }
+        }
+    }
+    lines
+}
+
+/// Value axis covering `values` with a 5% margin.
+fn value_range(values: impl Iterator<Item = f64>) -> Range<f64> {
+    let (min, max) = values
+        .filter(|v| v.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+    if min > max {
+        return 0.0..1.0;
+    }
+    let pad = ((max - min) * 0.05).max(1e-9);
+    min - pad..max + pad
+}
+
+#[cfg(all(test, feature = "trend", feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::trend::{Macd, Sma};
+    use crate::indicators::volatility::BollingerBands;
+    use crate::indicators::Indicator;
+
+    fn candles(n: u64) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 100.0 + (i as f64 * 0.25).sin() * 4.0;
+                Candle {
+                    timestamp: 1_700_000_000 + i * 3600,
+                    open: c - 0.3,
+                    high: c + 1.0,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 50.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lines_per_output_field() {
+        let data = candles(40);
+        let bb = BollingerBands::new(10, 2.0)
+            .unwrap()
+            .calculate_series(&data)
+            .unwrap();
+        let bands = lines("BB", &bb);
+        let labels: Vec<&str> = bands.iter().map(|l| l.label.as_str()).collect();
+        assert_eq!(labels, vec!["BB upper", "BB middle", "BB lower"]);
+        assert!(bands.iter().all(|l| l.points.len() == bb.len()));
+
+        let sma = Sma::new(5).unwrap().calculate_series(&data).unwrap();
+        let single = lines("SMA", &sma);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].label, "SMA");
+        assert_eq!(single[0].points[0], (data[4].timestamp, sma.values()[0]));
+    }
+
+    #[test]
+    fn test_svg_contains_every_line() {
+        let data = candles(80);
+        let svg = Chart::new(&data)
+            .with_title("test chart")
+            .with_size(800, 600)
+            .with_overlay(
+                "SMA",
+                &Sma::new(10).unwrap().calculate_series(&data).unwrap(),
+            )
+            .with_pane(
+                "MACD",
+                &Macd::new(5, 10, 4)
+                    .unwrap()
+                    .calculate_series(&data)
+                    .unwrap(),
+            )
+            .to_svg()
+            .unwrap();
+        assert!(svg.contains("width=\"800\""));
+        for text in [
+            "test chart",
+            "SMA",
+            "MACD macd",
+            "MACD signal",
+            "MACD histogram",
+        ] {
+            assert!(svg.contains(text), "missing {text}");
+        }
+    }
+
+    #[test]
+    fn test_save_files() {
+        let data = candles(30);
+        let dir = std::env::temp_dir();
+        let chart = Chart::new(&data).with_overlay(
+            "SMA",
+            &Sma::new(5).unwrap().calculate_series(&data).unwrap(),
+        );
+        for (path, result) in [
+            (
+                dir.join("rsta_plot_test.svg"),
+                chart.save_svg(dir.join("rsta_plot_test.svg")),
+            ),
+            (
+                dir.join("rsta_plot_test.png"),
+                chart.save_png(dir.join("rsta_plot_test.png")),
+            ),
+        ] {
+            result.unwrap();
+            assert!(std::fs::metadata(&path).unwrap().len() > 0);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_empty_chart_is_an_error() {
+        assert!(matches!(Chart::new(&[]).to_svg(), Err(PlotError::NoData)));
+    }
+
+    #[test]
+    fn test_value_range() {
+        assert_eq!(value_range([f64::NAN].into_iter()), 0.0..1.0);
+        let r = value_range([10.0, 20.0, f64::INFINITY].into_iter());
+        assert!((r.start - 9.5).abs() < 1e-12 && (r.end - 20.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,447 @@
+//! # Trade and portfolio accounting
+//!
+//! Multi-symbol book-keeping driven by fills: feed every execution to a
+//! [`Portfolio`] with [`buy`](Portfolio::buy) / [`sell`](Portfolio::sell)
+//! and it maintains cash, open [`Position`]s (average-cost basis), closed
+//! [`Trade`]s, realized and unrealized PnL, and exposure.
+//!
+//! Positions and trades are the [`backtest`](crate::backtest) engine's
+//! [`Position`] and [`Trade`], keyed by symbol; the engine's own
+//! `Portfolio` tracks a single position for a single series. Use this one
+//! when several symbols share one account — in live-signal consumers, or
+//! to aggregate the trades of several backtests.
+//!
+//! ## Conventions
+//!
+//! - Quantities passed to `buy`/`sell` are positive; direction comes from
+//!   the method. A fill larger than the opposite open position closes it
+//!   and opens a new one in the fill's direction with the remainder.
+//! - Fees are absolute amounts in account currency. Entry fees stay with
+//!   the open position and are allocated pro rata to the trades that
+//!   close it, so `Trade::pnl` is net of both legs.
+//! - Unrealized PnL is marked at the last price seen for each symbol —
+//!   the latest fill or [`mark`](Portfolio::mark) — and excludes fees.
+//!   The same prices update each position's excursions, which restart
+//!   from the new average price when a fill adds to the position.
+//!
+//! ## Example
+//!
+//! ```
+//! use rsta::portfolio::Portfolio;
+//!
+//! let mut book = Portfolio::new(10_000.0);
+//! book.buy("AAPL", 10.0, 100.0, 1.0, 1).unwrap();
+//! book.sell("BTC", 0.5, 2_000.0, 0.0, 1).unwrap(); // short
+//!
+//! book.mark("AAPL", 110.0);
+//! book.mark("BTC", 1_900.0);
+//! assert_eq!(book.unrealized_pnl(), 100.0 + 50.0);
+//! assert_eq!(book.gross_exposure(), 1_100.0 + 950.0);
+//!
+//! let trade = book.sell("AAPL", 10.0, 110.0, 1.0, 2).unwrap().unwrap();
+//! assert_eq!(trade.mfe, 10.0);
+//! assert_eq!(trade.pnl, 98.0); // 100 gross - 2 fees
+//! assert_eq!(book.realized_pnl(), 98.0);
+//! assert_eq!(book.equity(), 10_000.0 + 98.0 + 50.0);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::backtest::excursion::ExcursionTracker;
+pub use crate::backtest::{Position, Side, Trade};
+use crate::indicators::IndicatorError;
+
+/// An open position and its entry fees not yet allocated to a trade.
+#[derive(Debug, Clone)]
+struct Holding {
+    position: Position,
+    entry_fees: f64,
+}
+
+/// Cash, positions and trade history of one account across symbols.
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    initial_cash: f64,
+    cash: f64,
+    holdings: BTreeMap<String, Holding>,
+    last_prices: BTreeMap<String, f64>,
+    trades: Vec<(String, Trade)>,
+    realized_pnl: f64,
+    fees_paid: f64,
+}
+
+impl Portfolio {
+    /// Empty account holding `initial_cash`.
+    pub fn new(initial_cash: f64) -> Self {
+        Self {
+            initial_cash,
+            cash: initial_cash,
+            holdings: BTreeMap::new(),
+            last_prices: BTreeMap::new(),
+            trades: Vec::new(),
+            realized_pnl: 0.0,
+            fees_paid: 0.0,
+        }
+    }
+
+    /// Record a buy of `quantity` units at `price`. Returns the trade
+    /// closed by the fill if it reduced a short position.
+    pub fn buy(
+        &mut self,
+        symbol: &str,
+        quantity: f64,
+        price: f64,
+        fee: f64,
+        timestamp: u64,
+    ) -> Result<Option<Trade>, IndicatorError> {
+        self.fill(symbol, Side::Long, quantity, price, fee, timestamp)
+    }
+
+    /// Record a sell of `quantity` units at `price`. Returns the trade
+    /// closed by the fill if it reduced a long position.
+    pub fn sell(
+        &mut self,
+        symbol: &str,
+        quantity: f64,
+        price: f64,
+        fee: f64,
+        timestamp: u64,
+    ) -> Result<Option<Trade>, IndicatorError> {
+        self.fill(symbol, Side::Short, quantity, price, fee, timestamp)
+    }
+
+    /// Update the last known price of `symbol` for mark-to-market.
+    pub fn mark(&mut self, symbol: &str, price: f64) {
+        if let Some(holding) = self.holdings.get_mut(symbol) {
+            holding.position.excursion.update_price(price);
+        }
+        self.last_prices.insert(symbol.to_string(), price);
+    }
+
+    /// Last known price of `symbol`.
+    pub fn last_price(&self, symbol: &str) -> Option<f64> {
+        self.last_prices.get(symbol).copied()
+    }
+
+    /// Open position in `symbol`, if any.
+    pub fn position(&self, symbol: &str) -> Option<&Position> {
+        self.holdings.get(symbol).map(|h| &h.position)
+    }
+
+    /// All open positions with their symbols, ordered by symbol.
+    pub fn positions(&self) -> impl Iterator<Item = (&str, &Position)> {
+        self.holdings
+            .iter()
+            .map(|(symbol, h)| (symbol.as_str(), &h.position))
+    }
+
+    /// Entry fees of the open position in `symbol` not yet allocated to a
+    /// closed trade (`0.0` if flat).
+    pub fn open_fees(&self, symbol: &str) -> f64 {
+        self.holdings.get(symbol).map_or(0.0, |h| h.entry_fees)
+    }
+
+    /// Closed trades with their symbols, in the order they happened.
+    pub fn trades(&self) -> &[(String, Trade)] {
+        &self.trades
+    }
+
+    /// Starting cash.
+    pub fn initial_cash(&self) -> f64 {
+        self.initial_cash
+    }
+
+    /// Current cash balance (short-sale proceeds included).
+    pub fn cash(&self) -> f64 {
+        self.cash
+    }
+
+    /// Sum of the net PnL of all closed trades.
+    pub fn realized_pnl(&self) -> f64 {
+        self.realized_pnl
+    }
+
+    /// PnL of the open positions at their last prices, before fees.
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.positions()
+            .map(|(symbol, p)| p.unrealized_pnl(self.price_of(symbol, p)))
+            .sum()
+    }
+
+    /// Total fees paid on every fill.
+    pub fn fees_paid(&self) -> f64 {
+        self.fees_paid
+    }
+
+    /// Cash plus the signed market value of every open position.
+    pub fn equity(&self) -> f64 {
+        self.cash + self.net_exposure()
+    }
+
+    /// Signed market value of the position in `symbol` (`0.0` if flat).
+    pub fn exposure(&self, symbol: &str) -> f64 {
+        self.position(symbol)
+            .map_or(0.0, |p| p.market_value(self.price_of(symbol, p)))
+    }
+
+    /// Sum of signed market values: longs minus shorts.
+    pub fn net_exposure(&self) -> f64 {
+        self.positions()
+            .map(|(symbol, p)| p.market_value(self.price_of(symbol, p)))
+            .sum()
+    }
+
+    /// Sum of absolute market values: longs plus shorts.
+    pub fn gross_exposure(&self) -> f64 {
+        self.positions()
+            .map(|(symbol, p)| p.market_value(self.price_of(symbol, p)).abs())
+            .sum()
+    }
+
+    fn price_of(&self, symbol: &str, position: &Position) -> f64 {
+        self.last_prices
+            .get(symbol)
+            .copied()
+            .unwrap_or(position.entry_price)
+    }
+
+    fn fill(
+        &mut self,
+        symbol: &str,
+        side: Side,
+        quantity: f64,
+        price: f64,
+        fee: f64,
+        timestamp: u64,
+    ) -> Result<Option<Trade>, IndicatorError> {
+        if !quantity.is_finite() || quantity <= 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "quantity",
+                quantity,
+                "must be positive and finite",
+            ));
+        }
+        if !price.is_finite() || price <= 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "price",
+                price,
+                "must be positive and finite",
+            ));
+        }
+        if !fee.is_finite() || fee < 0.0 {
+            return Err(IndicatorError::invalid_parameter(
+                "fee",
+                fee,
+                "must be non-negative and finite",
+            ));
+        }
+
+        let signed = match side {
+            Side::Long => quantity,
+            Side::Short => -quantity,
+        };
+        self.cash -= signed * price + fee;
+        self.fees_paid += fee;
+        self.mark(symbol, price);
+
+        let Some(holding) = self.holdings.get_mut(symbol) else {
+            self.holdings.insert(
+                symbol.to_string(),
+                open(side, quantity, price, fee, timestamp),
+            );
+            return Ok(None);
+        };
+
+        let pos = &mut holding.position;
+        if pos.side == side {
+            let total = pos.quantity + quantity;
+            pos.entry_price = (pos.entry_price * pos.quantity + price * quantity) / total;
+            pos.quantity = total;
+            pos.excursion = ExcursionTracker::new(side, pos.entry_price);
+            pos.excursion.update_price(price);
+            holding.entry_fees += fee;
+            return Ok(None);
+        }
+
+        // Opposite direction: close up to the open quantity.
+        let closing = quantity.min(pos.quantity);
+        let exit_fee = fee * closing / quantity;
+        let entry_fee = holding.entry_fees * closing / pos.quantity;
+        let gross = match pos.side {
+            Side::Long => closing * (price - pos.entry_price),
+            Side::Short => closing * (pos.entry_price - price),
+        };
+        let trade = Trade {
+            side: pos.side,
+            quantity: closing,
+            entry_price: pos.entry_price,
+            exit_price: price,
+            entry_timestamp: pos.entry_timestamp,
+            exit_timestamp: timestamp,
+            pnl: gross - entry_fee - exit_fee,
+            fees_paid: entry_fee + exit_fee,
+            mae: pos.excursion.mae(),
+            mfe: pos.excursion.mfe(),
+            initial_risk: None,
+        };
+        pos.quantity -= closing;
+        holding.entry_fees -= entry_fee;
+        if pos.quantity == 0.0 {
+            self.holdings.remove(symbol);
+        }
+
+        let remainder = quantity - closing;
+        if remainder > 0.0 {
+            self.holdings.insert(
+                symbol.to_string(),
+                open(side, remainder, price, fee - exit_fee, timestamp),
+            );
+        }
+
+        self.realized_pnl += trade.pnl;
+        self.trades.push((symbol.to_string(), trade));
+        Ok(Some(trade))
+    }
+}
+
+fn open(side: Side, quantity: f64, price: f64, fee: f64, timestamp: u64) -> Holding {
+    Holding {
+        position: Position {
+            side,
+            quantity,
+            entry_price: price,
+            entry_timestamp: timestamp,
+            excursion: ExcursionTracker::new(side, price),
+            stop_loss: None,
+            take_profit: None,
+            initial_risk: None,
+        },
+        entry_fees: fee,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn scaling_in_averages_entry_price() {
+        let mut book = Portfolio::new(10_000.0);
+        book.buy("X", 10.0, 100.0, 0.0, 1).unwrap();
+        book.buy("X", 30.0, 104.0, 0.0, 2).unwrap();
+        let pos = book.position("X").unwrap();
+        assert_eq!(pos.quantity, 40.0);
+        assert!(approx(pos.entry_price, 103.0));
+        assert_eq!(pos.entry_timestamp, 1);
+        assert!(approx(book.cash(), 10_000.0 - 1_000.0 - 3_120.0));
+    }
+
+    #[test]
+    fn partial_close_allocates_fees_pro_rata() {
+        let mut book = Portfolio::new(10_000.0);
+        book.buy("X", 10.0, 100.0, 4.0, 1).unwrap();
+        let trade = book.sell("X", 5.0, 110.0, 1.0, 2).unwrap().unwrap();
+        assert_eq!(trade.quantity, 5.0);
+        assert!(approx(trade.fees_paid, 2.0 + 1.0));
+        assert!(approx(trade.pnl, 50.0 - 3.0));
+        assert!(trade.is_win());
+
+        let pos = book.position("X").unwrap();
+        assert_eq!(pos.quantity, 5.0);
+        assert!(approx(book.open_fees("X"), 2.0));
+        assert!(approx(book.fees_paid(), 5.0));
+    }
+
+    #[test]
+    fn short_round_trip() {
+        let mut book = Portfolio::new(1_000.0);
+        assert!(book.sell("X", 2.0, 50.0, 0.0, 1).unwrap().is_none());
+        assert_eq!(book.position("X").unwrap().side, Side::Short);
+        assert!(approx(book.cash(), 1_100.0));
+        book.mark("X", 40.0);
+        assert!(approx(book.exposure("X"), -80.0));
+        assert!(approx(book.unrealized_pnl(), 20.0));
+        assert!(approx(book.equity(), 1_020.0));
+
+        let trade = book.buy("X", 2.0, 40.0, 0.0, 2).unwrap().unwrap();
+        assert_eq!(trade.side, Side::Short);
+        assert!(approx(trade.pnl, 20.0));
+        assert!(approx(trade.return_pct(), 0.2));
+        assert!(book.position("X").is_none());
+        assert!(approx(book.equity(), 1_020.0));
+    }
+
+    #[test]
+    fn fill_through_zero_flips_position() {
+        let mut book = Portfolio::new(1_000.0);
+        book.buy("X", 3.0, 10.0, 0.0, 1).unwrap();
+        let trade = book.sell("X", 5.0, 12.0, 0.0, 2).unwrap().unwrap();
+        assert_eq!(trade.quantity, 3.0);
+        assert!(approx(trade.pnl, 6.0));
+        let pos = book.position("X").unwrap();
+        assert_eq!(pos.side, Side::Short);
+        assert_eq!(pos.quantity, 2.0);
+        assert_eq!(pos.entry_price, 12.0);
+        assert_eq!(pos.entry_timestamp, 2);
+    }
+
+    #[test]
+    fn exposure_across_symbols() {
+        let mut book = Portfolio::new(10_000.0);
+        book.buy("A", 10.0, 100.0, 0.0, 1).unwrap();
+        book.sell("B", 5.0, 200.0, 0.0, 1).unwrap();
+        assert!(approx(book.net_exposure(), 0.0));
+        assert!(approx(book.gross_exposure(), 2_000.0));
+        assert_eq!(
+            book.positions()
+                .map(|(symbol, _)| symbol)
+                .collect::<Vec<_>>(),
+            ["A", "B"]
+        );
+        assert_eq!(book.exposure("C"), 0.0);
+        assert!(approx(book.equity(), 10_000.0));
+    }
+
+    #[test]
+    fn equity_identity_holds() {
+        let mut book = Portfolio::new(5_000.0);
+        book.buy("A", 10.0, 100.0, 1.5, 1).unwrap();
+        book.sell("A", 4.0, 105.0, 0.5, 2).unwrap();
+        book.sell("B", 3.0, 50.0, 0.25, 3).unwrap();
+        book.mark("A", 98.0);
+        book.mark("B", 47.0);
+        let open_fees = book.open_fees("A") + book.open_fees("B");
+        let expected =
+            book.initial_cash() + book.realized_pnl() + book.unrealized_pnl() - open_fees;
+        assert!(approx(book.equity(), expected));
+    }
+
+    #[test]
+    fn marks_track_excursions() {
+        let mut book = Portfolio::new(10_000.0);
+        book.buy("X", 10.0, 100.0, 0.0, 1).unwrap();
+        for price in [97.0, 106.0, 103.0] {
+            book.mark("X", price);
+        }
+        let excursion = book.position("X").unwrap().excursion;
+        assert_eq!((excursion.mae(), excursion.mfe()), (3.0, 6.0));
+
+        let trade = book.sell("X", 10.0, 104.0, 0.0, 2).unwrap().unwrap();
+        assert_eq!((trade.mae, trade.mfe), (3.0, 6.0));
+        assert_eq!(book.trades()[0].0, "X");
+    }
+
+    #[test]
+    fn rejects_invalid_fills() {
+        let mut book = Portfolio::new(1_000.0);
+        assert!(book.buy("X", 0.0, 10.0, 0.0, 1).is_err());
+        assert!(book.buy("X", 1.0, f64::NAN, 0.0, 1).is_err());
+        assert!(book.sell("X", 1.0, 10.0, -1.0, 1).is_err());
+        assert_eq!(book.cash(), 1_000.0);
+        assert!(book.position("X").is_none());
+    }
+}
@@ -0,0 +1,433 @@
+//! Reference vectors from other TA libraries
+//!
+//! Indicator libraries disagree in small ways — how an EMA is seeded, when
+//! RSI starts smoothing — so "same indicator, same parameters" does not
+//! guarantee the same numbers. This module embeds two small datasets and
+//! the outputs pandas and pandas-ta produce on them, so you can check
+//! programmatically which configuration of an rsta indicator reproduces a
+//! reference and within what tolerance.
+//!
+//! ```
+//! use rsta::indicators::trend::{Ema, EmaInit};
+//! use rsta::indicators::Indicator;
+//! use rsta::reference::Dataset;
+//!
+//! let closes = Dataset::BtcDaily.closes();
+//! let reference = Dataset::BtcDaily.reference("ema_20").unwrap();
+//!
+//! // pandas' recursive EMA is seeded with the first close…
+//! let mut ema = Ema::new(20).unwrap();
+//! assert!(reference.compare(&ema.calculate(&closes).unwrap()).passed());
+//!
+//! // …so TA-Lib-style SMA seeding does not match it.
+//! let mut talib = Ema::new(20).unwrap().with_init(EmaInit::TALIB);
+//! assert!(!reference.compare(&talib.calculate(&closes).unwrap()).passed());
+//! ```
+//!
+//! Each [`Reference`] records the library that produced it, the rsta
+//! configuration expected to match it, and the tolerance and warm-up rows
+//! to allow for known convention differences. The vectors are slices of the
+//! golden files in `tests/data/`, regenerated with `scripts/gen_golden.py`.
+//! pandas-ta seeds its EMA with an SMA like TA-Lib, so the `ema_5` vector
+//! of [`Dataset::Sample`] also covers the [`EmaInit::TALIB`] seeding.
+//!
+//! [`EmaInit::TALIB`]: crate::indicators::EmaInit::TALIB
+
+use crate::indicators::Candle;
+
+/// Embedded OHLCV dataset with reference vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dataset {
+    /// First 400 daily bars of Kraken XBTUSD, from 2013-10-06: real price
+    /// action with gaps and regime shifts.
+    BtcDaily,
+    /// 30 hand-authored candles with closes `10..=39`, easy to verify by
+    /// hand. Timestamps are the row index.
+    Sample,
+}
+
+/// Library that produced a reference vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Source {
+    /// A pandas-ta indicator function (`ta.sma`, `ta.rsi`, ...).
+    PandasTa,
+    /// Plain pandas, e.g. a recursive `ewm(span, adjust=False)` EMA.
+    Pandas,
+}
+
+/// Metadata of a vector; the values live in the embedded CSVs.
+struct Spec {
+    dataset: Dataset,
+    name: &'static str,
+    source: Source,
+    configuration: &'static str,
+    tolerance: f64,
+    skip: usize,
+}
+
+const SPECS: &[Spec] = &[
+    Spec {
+        dataset: Dataset::Sample,
+        name: "sma_5",
+        source: Source::PandasTa,
+        configuration: "Sma::new(5)",
+        tolerance: 1e-9,
+        skip: 0,
+    },
+    Spec {
+        dataset: Dataset::Sample,
+        name: "ema_5",
+        source: Source::PandasTa,
+        configuration: "Ema::new(5).with_init(EmaInit::TALIB)",
+        tolerance: 1e-9,
+        skip: 0,
+    },
+    Spec {
+        dataset: Dataset::Sample,
+        name: "rsi_14",
+        source: Source::PandasTa,
+        configuration: "Rsi::new(14)",
+        tolerance: 1e-9,
+        skip: 0,
+    },
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "sma_20",
+        source: Source::PandasTa,
+        configuration: "Sma::new(20)",
+        tolerance: 1e-6,
+        skip: 0,
+    },
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "ema_20",
+        source: Source::Pandas,
+        configuration: "Ema::new(20)",
+        tolerance: 1e-6,
+        skip: 0,
+    },
+    // pandas-ta starts Wilder smoothing one change earlier than rsta's SMA
+    // seed; the gap decays to about 1e-2 by row 200.
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "rsi_14",
+        source: Source::PandasTa,
+        configuration: "Rsi::new(14)",
+        tolerance: 1e-2,
+        skip: 200,
+    },
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "atr_14",
+        source: Source::PandasTa,
+        configuration: "Atr::new(14)",
+        tolerance: 1e-6,
+        skip: 0,
+    },
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "macd_12_26_9_line",
+        source: Source::Pandas,
+        configuration: "Macd::new(12, 26, 9), `macd` field",
+        tolerance: 1e-6,
+        skip: 30,
+    },
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "macd_12_26_9_signal",
+        source: Source::Pandas,
+        configuration: "Macd::new(12, 26, 9), `signal` field",
+        tolerance: 1e-6,
+        skip: 30,
+    },
+    Spec {
+        dataset: Dataset::BtcDaily,
+        name: "macd_12_26_9_hist",
+        source: Source::Pandas,
+        configuration: "Macd::new(12, 26, 9), `histogram` field",
+        tolerance: 1e-6,
+        skip: 30,
+    },
+];
+
+impl Dataset {
+    /// All embedded datasets.
+    pub const ALL: [Dataset; 2] = [Dataset::BtcDaily, Dataset::Sample];
+
+    fn ohlcv_csv(self) -> &'static str {
+        match self {
+            Dataset::BtcDaily => include_str!("data/btc_usd_daily.csv"),
+            Dataset::Sample => include_str!("data/sample_ohlcv.csv"),
+        }
+    }
+
+    fn reference_csv(self) -> &'static str {
+        match self {
+            Dataset::BtcDaily => include_str!("data/btc_usd_daily_reference.csv"),
+            Dataset::Sample => include_str!("data/sample_ohlcv_reference.csv"),
+        }
+    }
+
+    /// The candles of the dataset, oldest first.
+    pub fn candles(self) -> Vec<Candle> {
+        self.ohlcv_csv()
+            .lines()
+            .skip(1)
+            .enumerate()
+            .map(|(i, line)| {
+                let cols: Vec<&str> = line.split(',').collect();
+                let num = |j: usize| cols[j].parse::<f64>().expect("embedded OHLCV is numeric");
+                Candle {
+                    // The sample has dates rather than timestamps.
+                    timestamp: cols[0].parse().unwrap_or(i as u64),
+                    open: num(1),
+                    high: num(2),
+                    low: num(3),
+                    close: num(4),
+                    volume: num(5),
+                }
+            })
+            .collect()
+    }
+
+    /// Closing prices of the dataset, for indicators that take plain
+    /// prices.
+    pub fn closes(self) -> Vec<f64> {
+        self.candles().iter().map(|c| c.close).collect()
+    }
+
+    /// Reference vector `name` (`"ema_20"`, `"macd_12_26_9_signal"`, ...)
+    /// for this dataset.
+    pub fn reference(self, name: &str) -> Option<Reference> {
+        let spec = SPECS.iter().find(|s| s.dataset == self && s.name == name)?;
+        Some(self.load(spec))
+    }
+
+    /// Every reference vector for this dataset.
+    pub fn references(self) -> Vec<Reference> {
+        SPECS
+            .iter()
+            .filter(|s| s.dataset == self)
+            .map(|s| self.load(s))
+            .collect()
+    }
+
+    fn load(self, spec: &Spec) -> Reference {
+        let mut lines = self.reference_csv().lines();
+        let column = lines
+            .next()
+            .and_then(|header| header.split(',').position(|c| c == spec.name))
+            .expect("every spec has a column");
+        let values = lines
+            .map(|line| {
+                let cell = line.split(',').nth(column).unwrap_or("");
+                (!cell.is_empty()).then(|| cell.parse().expect("embedded values are numeric"))
+            })
+            .collect();
+        Reference {
+            dataset: self,
+            name: spec.name,
+            source: spec.source,
+            configuration: spec.configuration,
+            tolerance: spec.tolerance,
+            skip: spec.skip,
+            values,
+        }
+    }
+}
+
+/// An indicator output computed by another library on a [`Dataset`]. See
+/// the [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    /// Dataset the vector was computed on.
+    pub dataset: Dataset,
+    /// Vector name: indicator and parameters, plus the output for
+    /// multi-output indicators.
+    pub name: &'static str,
+    /// Library that produced the values.
+    pub source: Source,
+    /// rsta configuration expected to reproduce the values.
+    pub configuration: &'static str,
+    /// Largest absolute difference accepted by [`compare`](Self::compare).
+    pub tolerance: f64,
+    /// Leading rows left out of the comparison, where the two libraries'
+    /// warm-up conventions differ.
+    pub skip: usize,
+    values: Vec<Option<f64>>,
+}
+
+impl Reference {
+    /// One entry per dataset row; `None` where the reference library has
+    /// no value yet.
+    pub fn values(&self) -> &[Option<f64>] {
+        &self.values
+    }
+
+    /// Compare `produced` against the reference with its own tolerance
+    ///
+    /// `produced` is an indicator's output over the whole dataset; like
+    /// `calculate()` output it may be shorter than the dataset and is
+    /// aligned to its end. Only rows from `skip` on where both sides have a
+    /// value are compared.
+    ///
+    /// # Panics
+    /// If `produced` is longer than the dataset.
+    pub fn compare(&self, produced: &[f64]) -> Comparison {
+        self.compare_with(produced, self.tolerance)
+    }
+
+    /// [`compare`](Self::compare) with a custom tolerance.
+    pub fn compare_with(&self, produced: &[f64], tolerance: f64) -> Comparison {
+        assert!(
+            produced.len() <= self.values.len(),
+            "{} values for {} dataset rows",
+            produced.len(),
+            self.values.len()
+        );
+        let offset = self.values.len() - produced.len();
+        let mut comparison = Comparison {
+            compared: 0,
+            mismatches: 0,
+            max_abs_error: 0.0,
+            worst_row: None,
+            tolerance,
+        };
+        for (i, &got) in produced.iter().enumerate() {
+            let row = offset + i;
+            let Some(expected) = self.values[row] else {
+                continue;
+            };
+            if row < self.skip {
+                continue;
+            }
+            let error = (got - expected).abs();
+            comparison.compared += 1;
+            // A NaN on either side counts as a mismatch.
+            if error.is_nan() || error > tolerance {
+                comparison.mismatches += 1;
+            }
+            if comparison.worst_row.is_none()
+                || (error.is_nan() && !comparison.max_abs_error.is_nan())
+                || error > comparison.max_abs_error
+            {
+                comparison.max_abs_error = error;
+                comparison.worst_row = Some(row);
+            }
+        }
+        comparison
+    }
+}
+
+/// Outcome of [`Reference::compare`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comparison {
+    /// Number of rows compared.
+    pub compared: usize,
+    /// Rows whose difference exceeds the tolerance.
+    pub mismatches: usize,
+    /// Largest absolute difference (`NaN` if a value was `NaN`).
+    pub max_abs_error: f64,
+    /// Dataset row with the largest difference.
+    pub worst_row: Option<usize>,
+    /// Tolerance the rows were checked against.
+    pub tolerance: f64,
+}
+
+impl Comparison {
+    /// Whether at least one row was compared and none exceeded the
+    /// tolerance.
+    pub fn passed(&self) -> bool {
+        self.compared > 0 && self.mismatches == 0
+    }
+}
+
+#[cfg(all(test, feature = "trend", feature = "momentum", feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::momentum::Rsi;
+    use crate::indicators::trend::{Ema, EmaInit, Macd, Sma};
+    use crate::indicators::volatility::Atr;
+    use crate::indicators::Indicator;
+
+    /// Run the configuration a reference names.
+    fn produce(reference: &Reference) -> Vec<f64> {
+        let candles = reference.dataset.candles();
+        let closes = reference.dataset.closes();
+        let macd = || Macd::new(12, 26, 9).unwrap().calculate(&closes).unwrap();
+        match reference.name {
+            "sma_5" => Sma::new(5).unwrap().calculate(&closes).unwrap(),
+            "sma_20" => Sma::new(20).unwrap().calculate(&closes).unwrap(),
+            "ema_5" => Ema::new(5)
+                .unwrap()
+                .with_init(EmaInit::TALIB)
+                .calculate(&closes)
+                .unwrap(),
+            "ema_20" => Ema::new(20).unwrap().calculate(&closes).unwrap(),
+            "rsi_14" => Rsi::new(14).unwrap().calculate(&closes).unwrap(),
+            "atr_14" => Atr::new(14).unwrap().calculate(&candles).unwrap(),
+            "macd_12_26_9_line" => macd().iter().map(|m| m.macd).collect(),
+            "macd_12_26_9_signal" => macd().iter().map(|m| m.signal).collect(),
+            "macd_12_26_9_hist" => macd().iter().map(|m| m.histogram).collect(),
+            other => panic!("no configuration for {other}"),
+        }
+    }
+
+    #[test]
+    fn test_every_reference_matches_its_configuration() {
+        for dataset in Dataset::ALL {
+            let candles = dataset.candles();
+            for reference in dataset.references() {
+                assert_eq!(
+                    reference.values().len(),
+                    candles.len(),
+                    "{}",
+                    reference.name
+                );
+                let comparison = reference.compare(&produce(&reference));
+                assert!(
+                    comparison.passed(),
+                    "{:?} {}: {comparison:?}",
+                    dataset,
+                    reference.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_datasets_load() {
+        let btc = Dataset::BtcDaily.candles();
+        assert_eq!(btc.len(), 400);
+        assert_eq!(btc[0].timestamp, 1_381_017_600);
+        let sample = Dataset::Sample.candles();
+        assert_eq!(sample.len(), 30);
+        assert_eq!(sample[3].timestamp, 3);
+        assert_eq!(Dataset::Sample.closes()[29], 39.0);
+        assert!(Dataset::Sample.reference("macd_12_26_9_line").is_none());
+    }
+
+    #[test]
+    fn test_compare_reports_mismatches() {
+        let reference = Dataset::Sample.reference("sma_5").unwrap();
+        assert_eq!(reference.values()[3], None);
+        assert!((reference.values()[4].unwrap() - 12.0).abs() < 1e-9);
+
+        let mut produced = produce(&reference);
+        produced[10] += 0.5;
+        let comparison = reference.compare(&produced);
+        assert!(!comparison.passed());
+        assert_eq!((comparison.compared, comparison.mismatches), (26, 1));
+        assert_eq!(comparison.worst_row, Some(14));
+        assert!((comparison.max_abs_error - 0.5).abs() < 1e-12);
+        assert!(reference.compare_with(&produced, 1.0).passed());
+
+        // Nothing to compare is not a pass.
+        assert!(!reference.compare(&[]).passed());
+        produced[0] = f64::NAN;
+        assert_eq!(reference.compare_with(&produced, 1.0).mismatches, 1);
+    }
+}
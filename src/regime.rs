@@ -0,0 +1,236 @@
+//! # Market regime detection
+//!
+//! [`RegimeDetector`] labels every candle with the market state it most
+//! likely belongs to, so a strategy can switch logic — trend following in
+//! a trend, mean reversion in a range, standing aside when volatility
+//! spikes:
+//!
+//! 1. **Volatile** when the current ATR ranks at or above the
+//!    `volatile_percentile` of its own trailing distribution, the last
+//!    `lookback` ATR values (see [`VolatilityRank`]). Volatility shocks
+//!    override the other reads.
+//! 2. **Trending** when the ADX is at or above `adx_threshold` and the
+//!    Choppiness Index is below `chop_threshold`: a strong directional
+//!    move that is not going back and forth.
+//! 3. **Ranging** otherwise.
+//!
+//! ADX, Choppiness and ATR share the same `period`. The defaults follow
+//! the usual reading levels: ADX 25, Choppiness 61.8 and the 80th ATR
+//! percentile.
+//!
+//! ```
+//! use rsta::indicators::{Candle, Indicator};
+//! use rsta::regime::{Regime, RegimeDetector};
+//!
+//! let candle = |i: u64, close: f64| Candle {
+//!     timestamp: i, open: close, high: close + 1.0, low: close - 1.0, close, volume: 1.0,
+//! };
+//! // A flat, oscillating market followed by a steady climb.
+//! let candles: Vec<Candle> = (0..120)
+//!     .map(|i| if i < 80 { candle(i, 100.0 + (i % 2) as f64) } else { candle(i, 100.0 + (i - 80) as f64) })
+//!     .collect();
+//!
+//! let mut detector = RegimeDetector::new(14, 50).unwrap();
+//! let regimes = detector.calculate(&candles).unwrap();
+//! assert_eq!(regimes.len(), 120 - 62);
+//! assert_eq!(regimes[0], Regime::Ranging);
+//! assert_eq!(*regimes.last().unwrap(), Regime::Trending);
+//! ```
+//!
+//! [`VolatilityRank`]: crate::indicators::volatility::VolatilityRank
+
+use crate::indicators::trend::Adx;
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::volatility::{Choppiness, VolatilityMeasure, VolatilityRank};
+use crate::indicators::{Candle, Indicator, IndicatorError};
+
+/// Market state of a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Regime {
+    /// Strong, directional move.
+    Trending,
+    /// No clear direction.
+    Ranging,
+    /// Volatility high relative to its recent history.
+    Volatile,
+}
+
+/// Per-bar regime classifier. See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct RegimeDetector {
+    period: usize,
+    adx_threshold: f64,
+    chop_threshold: f64,
+    volatile_percentile: f64,
+    adx: Adx,
+    chop: Choppiness,
+    volatility: VolatilityRank,
+    last: Option<Regime>,
+}
+
+impl RegimeDetector {
+    /// Create a new regime detector
+    ///
+    /// # Arguments
+    /// * `period` - Period of the ADX, Choppiness Index and ATR (must be at least 2, typically 14)
+    /// * `lookback` - Number of ATR values the volatility percentile is ranked over (must be at least 2)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new detector or an error
+    pub fn new(period: usize, lookback: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 2)?;
+        Ok(Self {
+            period,
+            adx_threshold: 25.0,
+            chop_threshold: 61.8,
+            volatile_percentile: 80.0,
+            adx: Adx::new(period)?,
+            chop: Choppiness::new(period)?,
+            volatility: VolatilityRank::new(period, lookback)?.with_measure(VolatilityMeasure::Atr),
+            last: None,
+        })
+    }
+
+    /// Minimum ADX for a trend (25 by default).
+    pub fn with_adx_threshold(mut self, threshold: f64) -> Self {
+        self.adx_threshold = threshold;
+        self
+    }
+
+    /// Choppiness Index at and above which the market is ranging (61.8
+    /// by default).
+    pub fn with_chop_threshold(mut self, threshold: f64) -> Self {
+        self.chop_threshold = threshold;
+        self
+    }
+
+    /// ATR percentile, from 0 to 100, at and above which the market is
+    /// volatile (80 by default).
+    pub fn with_volatile_percentile(mut self, percentile: f64) -> Self {
+        self.volatile_percentile = percentile;
+        self
+    }
+
+    /// Reset the detector state
+    pub fn reset_state(&mut self) {
+        self.adx.reset_state();
+        self.chop.reset_state();
+        self.volatility.reset_state();
+        self.last = None;
+    }
+
+    fn step(&mut self, candle: Candle) -> Option<Regime> {
+        let adx = self.adx.next(candle).ok().flatten();
+        let chop = self.chop.next(candle).ok().flatten();
+        let percentile = self.volatility.next(candle).ok().flatten();
+        let (adx, chop, percentile) = (adx?.adx, chop?, percentile?);
+        let regime = if percentile >= self.volatile_percentile {
+            Regime::Volatile
+        } else if adx >= self.adx_threshold && chop < self.chop_threshold {
+            Regime::Trending
+        } else {
+            Regime::Ranging
+        };
+        self.last = Some(regime);
+        self.last
+    }
+}
+
+impl Indicator<Candle, Regime> for RegimeDetector {
+    fn calculate(&mut self, data: &[Candle]) -> Result<Vec<Regime>, IndicatorError> {
+        validate_data_length(data, self.min_periods())?;
+        self.reset_state();
+        Ok(data
+            .iter()
+            .filter_map(|&candle| self.step(candle))
+            .collect())
+    }
+
+    fn next(&mut self, value: Candle) -> Result<Option<Regime>, IndicatorError> {
+        Ok(self.step(value))
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&Regime> {
+        self.last.as_ref()
+    }
+
+    fn name(&self) -> &'static str {
+        "RegimeDetector"
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        Indicator::<Candle, f64>::min_periods(&self.volatility).max(2 * self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(i: usize, close: f64, spread: f64) -> Candle {
+        Candle {
+            timestamp: i as u64,
+            open: close,
+            high: close + spread,
+            low: close - spread,
+            close,
+            volume: 1.0,
+        }
+    }
+
+    #[test]
+    fn volatility_shock_overrides_trend() {
+        // Steady uptrend; the last bar's range is ten times the usual.
+        let mut candles: Vec<Candle> = (0..60).map(|i| candle(i, 100.0 + i as f64, 1.0)).collect();
+        candles.push(candle(60, 160.0, 10.0));
+
+        let mut detector = RegimeDetector::new(10, 20).unwrap();
+        let regimes = detector.calculate(&candles).unwrap();
+        assert_eq!(regimes.len(), candles.len() - detector.min_periods() + 1);
+        assert!(regimes[..regimes.len() - 1]
+            .iter()
+            .all(|&r| r == Regime::Trending));
+        assert_eq!(regimes.last(), Some(&Regime::Volatile));
+        assert_eq!(detector.current(), Some(&Regime::Volatile));
+
+        // Raising the bar for a volatility shock past 100 disables it.
+        let mut detector = RegimeDetector::new(10, 20)
+            .unwrap()
+            .with_volatile_percentile(101.0);
+        let last = detector.calculate(&candles).unwrap().pop();
+        assert_eq!(last, Some(Regime::Trending));
+    }
+
+    #[test]
+    fn thresholds_and_parameters() {
+        let candles: Vec<Candle> = (0..60).map(|i| candle(i, 100.0 + i as f64, 1.0)).collect();
+        let mut strict = RegimeDetector::new(10, 20)
+            .unwrap()
+            .with_adx_threshold(101.0);
+        assert!(strict
+            .calculate(&candles)
+            .unwrap()
+            .iter()
+            .all(|&r| r == Regime::Ranging));
+        let mut choppy = RegimeDetector::new(10, 20)
+            .unwrap()
+            .with_chop_threshold(0.0);
+        assert!(choppy
+            .calculate(&candles)
+            .unwrap()
+            .iter()
+            .all(|&r| r == Regime::Ranging));
+        assert!(RegimeDetector::new(1, 20).is_err());
+        assert!(RegimeDetector::new(14, 1).is_err());
+        assert!(strict.calculate(&candles[..28]).is_err());
+    }
+}
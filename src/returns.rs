@@ -0,0 +1,230 @@
+//! # Return series
+//!
+//! Conversions from prices to returns and back, shared by the backtest
+//! metrics, the risk measures and the transforms so that every module
+//! agrees on the definitions:
+//!
+//! - **simple** return: `p[t] / p[t-1] - 1` (`0.01` = +1%)
+//! - **log** return: `ln(p[t] / p[t-1])`
+//! - **cumulative** return: the compounded growth since the start,
+//!   `Π(1 + r) - 1`
+//! - **compounded** return over `period` steps: `p[t] / p[t-period] - 1`
+//!
+//! The series functions accept anything with a price — plain `f64`
+//! prices, [`Candle`]s (their close) or `(timestamp, value)` points such
+//! as an equity curve — through the [`PricePoint`] trait.
+//!
+//! ```
+//! use rsta::returns::{cumulative_returns, log_returns, simple_returns};
+//!
+//! let prices = [100.0, 110.0, 99.0];
+//! let simple = simple_returns(&prices).unwrap();
+//! assert!((simple[0] - 0.10).abs() < 1e-12);
+//! assert!((simple[1] + 0.10).abs() < 1e-12);
+//!
+//! // Compounding the simple returns gives back the total change.
+//! let cumulative = cumulative_returns(&simple);
+//! assert!((cumulative[1] - -0.01).abs() < 1e-12);
+//!
+//! // Log returns add up.
+//! let total: f64 = log_returns(&prices).unwrap().iter().sum();
+//! assert!((total - 0.99f64.ln()).abs() < 1e-12);
+//! ```
+
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Candle, IndicatorError};
+
+/// A value with a price: a raw price, a candle's close or a timestamped
+/// value.
+pub trait PricePoint {
+    /// The price.
+    fn price(&self) -> f64;
+}
+
+impl PricePoint for f64 {
+    fn price(&self) -> f64 {
+        *self
+    }
+}
+
+impl PricePoint for Candle {
+    fn price(&self) -> f64 {
+        self.close
+    }
+}
+
+impl PricePoint for (u64, f64) {
+    fn price(&self) -> f64 {
+        self.1
+    }
+}
+
+/// Simple return from `from` to `to`, `None` unless `from` is positive.
+pub fn simple_return(from: f64, to: f64) -> Option<f64> {
+    (from > 0.0).then(|| (to - from) / from)
+}
+
+/// Log return from `from` to `to`, `None` unless both are positive.
+pub fn log_return(from: f64, to: f64) -> Option<f64> {
+    (from > 0.0 && to > 0.0).then(|| (to / from).ln())
+}
+
+/// Apply `f` to every pair of prices `period` steps apart, failing with
+/// [`IndicatorError::InvalidInput`] at the first pair it rejects.
+fn pairwise<P: PricePoint>(
+    prices: &[P],
+    period: usize,
+    kind: &str,
+    f: fn(f64, f64) -> Option<f64>,
+) -> Result<Vec<f64>, IndicatorError> {
+    (period..prices.len())
+        .map(|i| {
+            let (from, to) = (prices[i - period].price(), prices[i].price());
+            f(from, to).ok_or_else(|| IndicatorError::InvalidInput {
+                index: i,
+                reason: format!("{kind} return from {from} to {to}"),
+            })
+        })
+        .collect()
+}
+
+/// Simple returns between consecutive prices; one fewer than the prices.
+///
+/// Every price but the last must be positive.
+pub fn simple_returns<P: PricePoint>(prices: &[P]) -> Result<Vec<f64>, IndicatorError> {
+    validate_data_length(prices, 2)?;
+    pairwise(prices, 1, "simple", simple_return)
+}
+
+/// Log returns between consecutive prices; one fewer than the prices.
+///
+/// Every price must be positive.
+pub fn log_returns<P: PricePoint>(prices: &[P]) -> Result<Vec<f64>, IndicatorError> {
+    validate_data_length(prices, 2)?;
+    pairwise(prices, 1, "log", log_return)
+}
+
+/// Simple returns over `period` steps, `p[t] / p[t-period] - 1`; `period`
+/// fewer than the prices.
+pub fn compounded_returns<P: PricePoint>(
+    prices: &[P],
+    period: usize,
+) -> Result<Vec<f64>, IndicatorError> {
+    validate_period(period, 1)?;
+    validate_data_length(prices, period + 1)?;
+    pairwise(prices, period, "simple", simple_return)
+}
+
+/// Running compounded growth of a series of simple returns, `Π(1 + r) - 1`
+/// up to each step.
+pub fn cumulative_returns(returns: &[f64]) -> Vec<f64> {
+    let mut growth = 1.0;
+    returns
+        .iter()
+        .map(|r| {
+            growth *= 1.0 + r;
+            growth - 1.0
+        })
+        .collect()
+}
+
+/// Compounded return of every window of `period` simple returns.
+pub fn rolling_compounded(returns: &[f64], period: usize) -> Result<Vec<f64>, IndicatorError> {
+    validate_period(period, 1)?;
+    validate_data_length(returns, period)?;
+    // Products are recomputed per window: a running product cannot divide
+    // out a -100% return.
+    Ok(returns.windows(period).map(total_return).collect())
+}
+
+/// Compounded return of a whole series of simple returns.
+pub fn total_return(returns: &[f64]) -> f64 {
+    returns.iter().map(|r| 1.0 + r).product::<f64>() - 1.0
+}
+
+/// Convert simple returns to log returns.
+pub fn to_log(returns: &[f64]) -> Vec<f64> {
+    returns.iter().map(|r| r.ln_1p()).collect()
+}
+
+/// Convert log returns to simple returns.
+pub fn to_simple(log_returns: &[f64]) -> Vec<f64> {
+    log_returns.iter().map(|r| r.exp_m1()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn returns_from_prices_candles_and_points() {
+        let prices = [50.0, 55.0, 44.0, 66.0];
+        let candles: Vec<Candle> = prices
+            .iter()
+            .map(|&close| Candle {
+                timestamp: 0,
+                open: 1.0,
+                high: close,
+                low: 1.0,
+                close,
+                volume: 0.0,
+            })
+            .collect();
+        let points: Vec<(u64, f64)> = prices.iter().map(|&p| (0, p)).collect();
+
+        let simple = simple_returns(&prices).unwrap();
+        assert!(approx(simple[0], 0.1) && approx(simple[1], -0.2) && approx(simple[2], 0.5));
+        assert_eq!(simple_returns(&candles).unwrap(), simple);
+        assert_eq!(simple_returns(&points).unwrap(), simple);
+
+        let log = log_returns(&prices).unwrap();
+        assert!(to_log(&simple)
+            .iter()
+            .zip(&log)
+            .all(|(a, b)| approx(*a, *b)));
+        assert!(to_simple(&log)
+            .iter()
+            .zip(&simple)
+            .all(|(a, b)| approx(*a, *b)));
+
+        let two = compounded_returns(&prices, 2).unwrap();
+        assert!(approx(two[0], -0.12) && approx(two[1], 0.2));
+        assert!(approx(total_return(&simple), 66.0 / 50.0 - 1.0));
+    }
+
+    #[test]
+    fn cumulative_and_rolling_compounding() {
+        let returns = [0.1, -0.5, 1.0, 0.0];
+        let cumulative = cumulative_returns(&returns);
+        assert!(approx(cumulative[0], 0.1) && approx(cumulative[1], -0.45));
+        assert!(approx(cumulative[3], 0.1));
+
+        let rolling = rolling_compounded(&[0.1, -1.0, 0.5, 0.2], 2).unwrap();
+        assert!(approx(rolling[0], -1.0) && approx(rolling[1], -1.0));
+        assert!(approx(rolling[2], 0.8));
+        assert!(rolling_compounded(&returns, 0).is_err());
+        assert!(rolling_compounded(&returns, 5).is_err());
+    }
+
+    #[test]
+    fn invalid_prices_are_reported() {
+        assert_eq!(
+            simple_returns(&[1.0, 0.0, 2.0]),
+            Err(IndicatorError::InvalidInput {
+                index: 2,
+                reason: "simple return from 0 to 2".to_string(),
+            })
+        );
+        assert!(matches!(
+            log_returns(&[1.0, 0.0]),
+            Err(IndicatorError::InvalidInput { index: 1, .. })
+        ));
+        assert!(simple_returns(&[1.0]).is_err());
+        assert_eq!(simple_return(0.0, 1.0), None);
+        assert_eq!(log_return(1.0, -1.0), None);
+    }
+}
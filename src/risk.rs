@@ -0,0 +1,436 @@
+//! # Risk measures
+//!
+//! Value-at-Risk (VaR) and expected shortfall (ES, also called CVaR) over
+//! a series of periodic returns (`0.01` = +1%), e.g. from
+//! [`returns::simple_returns`](crate::returns::simple_returns).
+//!
+//! - **VaR** at confidence `c` is the loss that is not exceeded with
+//!   probability `c`.
+//! - **ES** is the average loss in the worst `1 - c` of outcomes, so it is
+//!   always at least the VaR.
+//!
+//! Both are reported as **positive fractions of capital lost** — a 95%
+//! VaR of `0.023` means "on 95% of days we lose less than 2.3%". A negative
+//! value means even the tail outcome is a gain.
+//!
+//! Two estimation methods ([`VarMethod`]):
+//!
+//! - **Historical**: empirical quantile of the returns (linear
+//!   interpolation between order statistics); ES averages the returns at
+//!   or beyond it. No distributional assumption.
+//! - **Parametric**: assumes normally distributed returns with the sample
+//!   mean and (n − 1) standard deviation.
+//!
+//! A `horizon` of `h` periods scales one-period estimates by the
+//! square-root-of-time rule (parametric: mean × h, volatility × √h;
+//! historical: × √h).
+//!
+//! [`RollingVar`] evaluates the same estimates over a sliding window and
+//! implements [`Indicator`], so it streams like any other indicator.
+//!
+//! ```
+//! use rsta::risk::{historical_var, parametric_var, RollingVar, VarMethod};
+//! use rsta::indicators::Indicator;
+//!
+//! let returns: Vec<f64> = (0..100).map(|i| (i as f64 - 50.0) / 1_000.0).collect();
+//!
+//! // Fifth percentile of -0.050..=0.049 is -0.04505.
+//! let var = historical_var(&returns, 0.95, 1).unwrap();
+//! assert!((var - 0.04505).abs() < 1e-12);
+//!
+//! let normal = parametric_var(&returns, 0.95, 1).unwrap();
+//! assert!(normal > 0.0);
+//!
+//! let mut rolling = RollingVar::new(20, 0.95, 1, VarMethod::Historical).unwrap();
+//! let series = rolling.calculate(&returns).unwrap();
+//! assert_eq!(series.len(), returns.len() - 19);
+//! assert!(series.iter().all(|e| e.es >= e.var));
+//! ```
+
+use std::collections::VecDeque;
+
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+
+use crate::indicators::utils::{validate_data_length, validate_period};
+use crate::indicators::{Indicator, IndicatorError};
+
+/// How VaR / ES are estimated from the returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarMethod {
+    /// Empirical quantile of the observed returns.
+    Historical,
+    /// Normal distribution fitted to the returns' mean and volatility.
+    Parametric,
+}
+
+/// VaR and expected shortfall at the same confidence and horizon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VarEstimate {
+    /// Value-at-Risk, as a positive loss fraction.
+    pub var: f64,
+    /// Expected shortfall (CVaR), as a positive loss fraction.
+    pub es: f64,
+}
+
+fn validate(confidence: f64, horizon: usize) -> Result<(), IndicatorError> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return Err(IndicatorError::invalid_parameter(
+            "confidence",
+            confidence,
+            "must be in (0, 1)",
+        ));
+    }
+    if horizon == 0 {
+        return Err(IndicatorError::invalid_parameter(
+            "horizon",
+            horizon,
+            "must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+/// Historical VaR of `returns` at `confidence` over `horizon` periods.
+pub fn historical_var(
+    returns: &[f64],
+    confidence: f64,
+    horizon: usize,
+) -> Result<f64, IndicatorError> {
+    estimate(returns, confidence, horizon, VarMethod::Historical).map(|e| e.var)
+}
+
+/// Historical expected shortfall of `returns` at `confidence` over
+/// `horizon` periods.
+pub fn historical_es(
+    returns: &[f64],
+    confidence: f64,
+    horizon: usize,
+) -> Result<f64, IndicatorError> {
+    estimate(returns, confidence, horizon, VarMethod::Historical).map(|e| e.es)
+}
+
+/// Parametric (normal) VaR of `returns` at `confidence` over `horizon`
+/// periods. Needs at least two returns.
+pub fn parametric_var(
+    returns: &[f64],
+    confidence: f64,
+    horizon: usize,
+) -> Result<f64, IndicatorError> {
+    estimate(returns, confidence, horizon, VarMethod::Parametric).map(|e| e.var)
+}
+
+/// Parametric (normal) expected shortfall of `returns` at `confidence`
+/// over `horizon` periods. Needs at least two returns.
+pub fn parametric_es(
+    returns: &[f64],
+    confidence: f64,
+    horizon: usize,
+) -> Result<f64, IndicatorError> {
+    estimate(returns, confidence, horizon, VarMethod::Parametric).map(|e| e.es)
+}
+
+/// VaR and ES of `returns` with the given method.
+pub fn estimate(
+    returns: &[f64],
+    confidence: f64,
+    horizon: usize,
+    method: VarMethod,
+) -> Result<VarEstimate, IndicatorError> {
+    validate(confidence, horizon)?;
+    match method {
+        VarMethod::Historical => {
+            validate_data_length(returns, 1)?;
+            Ok(historical(returns, confidence, horizon))
+        }
+        VarMethod::Parametric => {
+            validate_data_length(returns, 2)?;
+            Ok(parametric(returns, confidence, horizon))
+        }
+    }
+}
+
+fn historical(returns: &[f64], confidence: f64, horizon: usize) -> VarEstimate {
+    let mut sorted = returns.to_vec();
+    historical_in(&mut sorted, confidence, horizon)
+}
+
+/// Historical estimate of `returns`, which it sorts in place.
+fn historical_in(returns: &mut [f64], confidence: f64, horizon: usize) -> VarEstimate {
+    returns.sort_unstable_by(f64::total_cmp);
+    let sorted = &*returns;
+
+    // Linear interpolation between order statistics (numpy's default).
+    let pos = (1.0 - confidence) * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+    let cutoff = sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64);
+
+    let tail = sorted.iter().take_while(|&&r| r <= cutoff);
+    let (sum, count) = tail.fold((0.0, 0usize), |(sum, count), r| (sum + r, count + 1));
+    let tail_mean = sum / count as f64;
+
+    let scale = (horizon as f64).sqrt();
+    VarEstimate {
+        var: -cutoff * scale,
+        es: -tail_mean * scale,
+    }
+}
+
+fn parametric(returns: &[f64], confidence: f64, horizon: usize) -> VarEstimate {
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+
+    let h = horizon as f64;
+    let mu = mean * h;
+    let sigma = variance.sqrt() * h.sqrt();
+    let normal = standard_normal();
+    let z = normal.inverse_cdf(confidence);
+    VarEstimate {
+        var: z * sigma - mu,
+        es: sigma * normal.pdf(z) / (1.0 - confidence) - mu,
+    }
+}
+
+/// Standard normal distribution.
+fn standard_normal() -> Normal {
+    Normal::new(0.0, 1.0).expect("unit normal parameters are valid")
+}
+
+/// Rolling VaR / expected shortfall over the last `period` returns.
+///
+/// Input is one return per period; output is a [`VarEstimate`] once the
+/// window is full.
+///
+/// # Example
+///
+/// ```
+/// use rsta::indicators::Indicator;
+/// use rsta::risk::{RollingVar, VarMethod};
+///
+/// let mut var = RollingVar::new(3, 0.9, 1, VarMethod::Parametric).unwrap();
+/// assert_eq!(var.next(0.01).unwrap(), None);
+/// assert_eq!(var.next(-0.02).unwrap(), None);
+/// let est = var.next(0.005).unwrap().unwrap();
+/// assert!(est.var > 0.0 && est.es > est.var);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingVar {
+    period: usize,
+    confidence: f64,
+    horizon: usize,
+    method: VarMethod,
+    window: VecDeque<f64>,
+    /// Sorting buffer reused by every historical estimate.
+    scratch: Vec<f64>,
+    last: Option<VarEstimate>,
+}
+
+impl RollingVar {
+    /// Create a rolling estimator.
+    ///
+    /// # Arguments
+    /// * `period` - Window length (at least 1 for historical, 2 for parametric)
+    /// * `confidence` - Confidence level in (0, 1), e.g. `0.95`
+    /// * `horizon` - Horizon in periods (at least 1)
+    /// * `method` - Historical or parametric estimation
+    pub fn new(
+        period: usize,
+        confidence: f64,
+        horizon: usize,
+        method: VarMethod,
+    ) -> Result<Self, IndicatorError> {
+        let min_period = match method {
+            VarMethod::Historical => 1,
+            VarMethod::Parametric => 2,
+        };
+        validate_period(period, min_period)?;
+        validate(confidence, horizon)?;
+        Ok(Self {
+            period,
+            confidence,
+            horizon,
+            method,
+            window: VecDeque::with_capacity(period),
+            scratch: match method {
+                VarMethod::Historical => Vec::with_capacity(period),
+                VarMethod::Parametric => Vec::new(),
+            },
+            last: None,
+        })
+    }
+
+    /// Reset the rolling window.
+    pub fn reset_state(&mut self) {
+        self.window.clear();
+        self.last = None;
+    }
+}
+
+impl Indicator<f64, VarEstimate> for RollingVar {
+    fn calculate(&mut self, data: &[f64]) -> Result<Vec<VarEstimate>, IndicatorError> {
+        validate_data_length(data, self.period)?;
+        self.reset_state();
+        let mut result = Vec::with_capacity(data.len() - self.period + 1);
+        for &r in data {
+            if let Some(est) = self.next(r)? {
+                result.push(est);
+            }
+        }
+        Ok(result)
+    }
+
+    fn next(&mut self, value: f64) -> Result<Option<VarEstimate>, IndicatorError> {
+        if self.window.len() == self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(value);
+        if self.window.len() < self.period {
+            return Ok(None);
+        }
+        let returns = self.window.make_contiguous();
+        self.last = Some(match self.method {
+            VarMethod::Historical => {
+                self.scratch.clear();
+                self.scratch.extend_from_slice(returns);
+                historical_in(&mut self.scratch, self.confidence, self.horizon)
+            }
+            VarMethod::Parametric => parametric(returns, self.confidence, self.horizon),
+        });
+        Ok(self.last)
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+
+    fn current(&self) -> Option<&VarEstimate> {
+        self.last.as_ref()
+    }
+
+    fn period(&self) -> Option<usize> {
+        Some(self.period)
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64, eps: f64) -> bool {
+        (a - b).abs() < eps
+    }
+
+    #[test]
+    fn historical_var_and_es() {
+        // Returns -10%..=+9% in 1% steps.
+        let returns: Vec<f64> = (-10..10).map(|i| i as f64 / 100.0).collect();
+        // pos = 0.1 * 19 = 1.9 → -0.09 + 0.9 * 0.01 = -0.081
+        let var = historical_var(&returns, 0.9, 1).unwrap();
+        assert!(approx(var, 0.081, 1e-12));
+        // Tail: -0.10, -0.09 → mean -0.095.
+        let es = historical_es(&returns, 0.9, 1).unwrap();
+        assert!(approx(es, 0.095, 1e-12));
+        // Horizon scales by sqrt(4) = 2.
+        assert!(approx(
+            historical_var(&returns, 0.9, 4).unwrap(),
+            0.162,
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn historical_ignores_input_order() {
+        let a = [0.03, -0.02, 0.01, -0.05, 0.0];
+        let mut b = a;
+        b.reverse();
+        assert_eq!(
+            estimate(&a, 0.8, 1, VarMethod::Historical).unwrap(),
+            estimate(&b, 0.8, 1, VarMethod::Historical).unwrap()
+        );
+    }
+
+    #[test]
+    fn parametric_matches_closed_form() {
+        // mean 0, sample std 1.
+        let returns = [-1.0, 1.0, -1.0, 1.0, 0.0, 0.0];
+        let n = returns.len() as f64;
+        let sd = (4.0 / (n - 1.0)).sqrt();
+        let z = 1.644_853_626_951_472;
+        let var = parametric_var(&returns, 0.95, 1).unwrap();
+        assert!(approx(var, z * sd, 1e-8));
+        let es = parametric_es(&returns, 0.95, 1).unwrap();
+        assert!(approx(es, sd * standard_normal().pdf(z) / 0.05, 1e-8));
+        assert!(es > var);
+
+        let var4 = parametric_var(&returns, 0.95, 4).unwrap();
+        assert!(approx(var4, 2.0 * var, 1e-8));
+    }
+
+    #[test]
+    fn positive_drift_reduces_parametric_var() {
+        let flat = [-0.01, 0.01, -0.01, 0.01];
+        let drift: Vec<f64> = flat.iter().map(|r| r + 0.005).collect();
+        assert!(parametric_var(&drift, 0.95, 1).unwrap() < parametric_var(&flat, 0.95, 1).unwrap());
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert!(matches!(
+            historical_var(&[0.01], 1.0, 1),
+            Err(IndicatorError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            historical_var(&[0.01], 0.95, 0),
+            Err(IndicatorError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            historical_var(&[], 0.95, 1),
+            Err(IndicatorError::InsufficientData { .. })
+        ));
+        assert!(matches!(
+            parametric_var(&[0.01], 0.95, 1),
+            Err(IndicatorError::InsufficientData { .. })
+        ));
+        assert!(RollingVar::new(1, 0.95, 1, VarMethod::Parametric).is_err());
+        assert!(RollingVar::new(1, 0.95, 1, VarMethod::Historical).is_ok());
+    }
+
+    #[test]
+    fn rolling_matches_batch_windows() {
+        let returns: Vec<f64> = (0..40)
+            .map(|i| (i as f64 * 0.7).sin() * 0.02 + 0.001)
+            .collect();
+        for method in [VarMethod::Historical, VarMethod::Parametric] {
+            let mut rolling = RollingVar::new(10, 0.95, 5, method).unwrap();
+            let series = rolling.calculate(&returns).unwrap();
+            assert_eq!(series.len(), returns.len() - 9);
+            for (i, est) in series.iter().enumerate() {
+                let expected = estimate(&returns[i..i + 10], 0.95, 5, method).unwrap();
+                assert!(approx(est.var, expected.var, 1e-12));
+                assert!(approx(est.es, expected.es, 1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn rolling_streaming_and_reset() {
+        let returns = [0.01, -0.02, 0.015, -0.03, 0.005];
+        let mut batch = RollingVar::new(3, 0.9, 1, VarMethod::Historical).unwrap();
+        let expected = batch.calculate(&returns).unwrap();
+
+        let mut stream = RollingVar::new(3, 0.9, 1, VarMethod::Historical).unwrap();
+        let got: Vec<VarEstimate> = returns
+            .iter()
+            .filter_map(|&r| stream.next(r).unwrap())
+            .collect();
+        assert_eq!(got, expected);
+
+        stream.reset_state();
+        assert_eq!(stream.next(0.01).unwrap(), None);
+    }
+}
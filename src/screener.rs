@@ -0,0 +1,258 @@
+//! # Cross-sectional screening
+//!
+//! Indicators look at one symbol through time; a screener compares many
+//! symbols at one point in time. [`rank`] orders `(symbol, value)` pairs,
+//! best first, and gives each a rank and a percentile score, so a
+//! strategy can hold the top decile of a universe or skip its weakest
+//! names.
+//!
+//! Values usually come from the multi-symbol path of
+//! [`IndicatorSet`](crate::indicators::indicator_set::IndicatorSet):
+//! [`latest`] picks each symbol's most recent output of one indicator from
+//! [`IndicatorSet::calculate_symbols`](crate::indicators::indicator_set::IndicatorSet::calculate_symbols).
+//! [`momentum`] computes the classic skip-month momentum (12-1: the
+//! return from twelve months ago to one month ago) straight from prices.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use rsta::screener::{momentum, rank, top_fraction, RankOrder};
+//!
+//! // Twelve months of closes plus the current one, per symbol.
+//! let mut closes = BTreeMap::new();
+//! for (symbol, drift) in [("AAA", 0.02), ("BBB", -0.01), ("CCC", 0.05), ("DDD", 0.0)] {
+//!     let prices: Vec<f64> = (0..13).map(|m| 100.0 * (1.0 + drift * m as f64)).collect();
+//!     closes.insert(symbol, prices);
+//! }
+//!
+//! let scores = closes
+//!     .iter()
+//!     .map(|(symbol, prices)| (*symbol, momentum(prices, 12, 1).unwrap()));
+//! let ranked = rank(scores, RankOrder::HighestFirst);
+//! assert_eq!(ranked[0].symbol, "CCC");
+//! assert_eq!(ranked[0].percentile, 100.0);
+//! assert_eq!(ranked[3].symbol, "BBB");
+//!
+//! // Top quartile of four symbols: one name.
+//! let leaders = top_fraction(&ranked, 0.25);
+//! assert_eq!(leaders.len(), 1);
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::indicators::indicator_set::SetOutputs;
+use crate::indicators::IndicatorError;
+
+/// Which end of the scale ranks first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RankOrder {
+    /// Largest value is rank 1 (momentum, relative strength).
+    #[default]
+    HighestFirst,
+    /// Smallest value is rank 1 (volatility, valuation ratios).
+    LowestFirst,
+}
+
+/// One symbol's place in a cross-section.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ranked {
+    /// Symbol the value belongs to.
+    pub symbol: String,
+    /// Value the symbol was ranked on.
+    pub value: f64,
+    /// 1 for the best value. Equal values share a rank and the next one
+    /// skips accordingly (`1, 1, 3`).
+    pub rank: usize,
+    /// Share of the other symbols ranked below this one, `0..=100`: 100 for
+    /// the best, 0 for the worst, 100 for a lone symbol.
+    pub percentile: f64,
+}
+
+/// Rank `values` best first.
+///
+/// NaN values are left out. Ties keep the same rank and percentile and are
+/// listed by symbol.
+pub fn rank<S, I>(values: I, order: RankOrder) -> Vec<Ranked>
+where
+    S: Into<String>,
+    I: IntoIterator<Item = (S, f64)>,
+{
+    let mut pairs: Vec<(String, f64)> = values
+        .into_iter()
+        .filter(|(_, value)| !value.is_nan())
+        .map(|(symbol, value)| (symbol.into(), value))
+        .collect();
+    let better = |a: f64, b: f64| match order {
+        RankOrder::HighestFirst => b.total_cmp(&a),
+        RankOrder::LowestFirst => a.total_cmp(&b),
+    };
+    pairs.sort_by(|(sa, a), (sb, b)| better(*a, *b).then_with(|| sa.cmp(sb)));
+
+    let n = pairs.len();
+    let mut ranked: Vec<Ranked> = Vec::with_capacity(n);
+    for (i, (symbol, value)) in pairs.into_iter().enumerate() {
+        let rank = match ranked.last() {
+            Some(prev) if better(prev.value, value) == Ordering::Equal => prev.rank,
+            _ => i + 1,
+        };
+        let percentile = if n > 1 {
+            (n - rank) as f64 / (n - 1) as f64 * 100.0
+        } else {
+            100.0
+        };
+        ranked.push(Ranked {
+            symbol,
+            value,
+            rank,
+            percentile,
+        });
+    }
+    ranked
+}
+
+/// Leading `fraction` of a ranking, at least one symbol when `ranked` is
+/// not empty: `0.1` keeps the top decile.
+///
+/// The count is rounded up, so a decile of 25 symbols keeps 3.
+pub fn top_fraction(ranked: &[Ranked], fraction: f64) -> &[Ranked] {
+    if ranked.is_empty() {
+        return ranked;
+    }
+    let count = (ranked.len() as f64 * fraction.clamp(0.0, 1.0)).ceil() as usize;
+    &ranked[..count.max(1)]
+}
+
+/// Most recent output of `indicator` for each symbol, from
+/// [`IndicatorSet::calculate_symbols`](crate::indicators::indicator_set::IndicatorSet::calculate_symbols).
+///
+/// Symbols that do not have the indicator or are still warming up are
+/// left out.
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use rsta::indicators::indicator_set::IndicatorSet;
+/// use rsta::indicators::trend::Sma;
+/// use rsta::screener::{latest, rank, RankOrder};
+///
+/// let mut series = BTreeMap::new();
+/// series.insert("AAA".to_string(), vec![1.0, 2.0, 3.0]);
+/// series.insert("BBB".to_string(), vec![10.0, 20.0, 30.0]);
+/// series.insert("CCC".to_string(), vec![5.0]);
+///
+/// let outputs = IndicatorSet::calculate_symbols(&series, || {
+///     let mut set = IndicatorSet::<f64, f64>::new();
+///     set.add("sma", Box::new(Sma::new(2).unwrap()));
+///     set
+/// })
+/// .unwrap();
+///
+/// let values = latest(&outputs, "sma");
+/// assert_eq!(values, vec![("AAA".to_string(), 2.5), ("BBB".to_string(), 25.0)]);
+/// assert_eq!(rank(values, RankOrder::HighestFirst)[0].symbol, "BBB");
+/// ```
+pub fn latest<O: Clone>(
+    outputs: &BTreeMap<String, SetOutputs<O>>,
+    indicator: &str,
+) -> Vec<(String, O)> {
+    outputs
+        .iter()
+        .filter_map(|(symbol, set)| {
+            let value = set.get(indicator)?.iter().rev().flatten().next()?;
+            Some((symbol.clone(), value.clone()))
+        })
+        .collect()
+}
+
+/// Return from `lookback` bars ago to `skip` bars ago, as a fraction.
+///
+/// With monthly closes, `momentum(prices, 12, 1)` is the 12-1 momentum of
+/// the academic literature: the last month is skipped because of its
+/// short-term reversal. With daily bars, `(252, 21)` is the usual
+/// equivalent.
+///
+/// # Arguments
+/// * `prices` - Closes, oldest first
+/// * `lookback` - Bars back to the start of the window (must be above `skip`)
+/// * `skip` - Most recent bars left out
+///
+/// # Returns
+/// * `Result<f64, IndicatorError>` - The return or an error
+pub fn momentum(prices: &[f64], lookback: usize, skip: usize) -> Result<f64, IndicatorError> {
+    if lookback <= skip {
+        return Err(IndicatorError::invalid_parameter(
+            "lookback",
+            lookback,
+            format!("must be greater than skip ({skip})"),
+        ));
+    }
+    if prices.len() <= lookback {
+        return Err(IndicatorError::insufficient_data(
+            lookback + 1,
+            prices.len(),
+        ));
+    }
+    let last = prices.len() - 1;
+    let start = prices[last - lookback];
+    if start == 0.0 {
+        return Err(IndicatorError::division_by_zero(
+            "momentum start price",
+            last - lookback,
+        ));
+    }
+    Ok(prices[last - skip] / start - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_ties_order_and_nan() {
+        let values = [
+            ("A", 3.0),
+            ("B", 1.0),
+            ("C", 3.0),
+            ("D", f64::NAN),
+            ("E", 0.5),
+        ];
+        let ranked = rank(values, RankOrder::HighestFirst);
+        let summary: Vec<(&str, usize)> =
+            ranked.iter().map(|r| (r.symbol.as_str(), r.rank)).collect();
+        assert_eq!(summary, vec![("A", 1), ("C", 1), ("B", 3), ("E", 4)]);
+        let percentiles: Vec<f64> = ranked.iter().map(|r| r.percentile).collect();
+        assert_eq!(percentiles[..2], [100.0, 100.0]);
+        assert!((percentiles[2] - 100.0 / 3.0).abs() < 1e-12);
+        assert_eq!(percentiles[3], 0.0);
+
+        let lowest = rank(values, RankOrder::LowestFirst);
+        assert_eq!(lowest[0].symbol, "E");
+        assert_eq!(lowest[3].rank, 3);
+
+        let lone = rank([("X", 1.0)], RankOrder::LowestFirst);
+        assert_eq!(lone[0].percentile, 100.0);
+        assert!(rank(Vec::<(String, f64)>::new(), RankOrder::HighestFirst).is_empty());
+    }
+
+    #[test]
+    fn test_top_fraction_and_momentum() {
+        let ranked = rank(
+            (0..25).map(|i| (format!("S{i:02}"), i as f64)),
+            RankOrder::HighestFirst,
+        );
+        let decile = top_fraction(&ranked, 0.1);
+        assert_eq!(decile.len(), 3);
+        assert_eq!(decile[0].symbol, "S24");
+        assert_eq!(top_fraction(&ranked, 0.0).len(), 1);
+        assert_eq!(top_fraction(&ranked, 2.0).len(), 25);
+        assert!(top_fraction(&[], 0.5).is_empty());
+
+        let prices = [100.0, 90.0, 120.0, 150.0];
+        assert!((momentum(&prices, 3, 1).unwrap() - 0.2).abs() < 1e-12);
+        assert!((momentum(&prices, 2, 0).unwrap() - 150.0 / 90.0 + 1.0).abs() < 1e-12);
+        assert!(momentum(&prices, 1, 1).is_err());
+        assert!(momentum(&prices, 4, 1).is_err());
+        assert!(momentum(&[0.0, 1.0, 2.0], 2, 1).is_err());
+    }
+}
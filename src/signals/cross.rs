@@ -0,0 +1,235 @@
+//! Crossover detection between two series.
+//!
+//! A series `a` **crosses above** `b` at bar `i` when `a[i-1] <= b[i-1]`
+//! and `a[i] > b[i]`; **crosses below** is the mirror image. Touching
+//! without going through (`a == b` then back) is not a cross. These are the
+//! same rules [`CrossUp`](super::CrossUp) / [`CrossDown`](super::CrossDown)
+//! use.
+//!
+//! - [`cross_above`] / [`cross_below`]: batch helpers over two slices.
+//! - [`CrossoverTracker`]: streaming, reports both directions and how long
+//!   ago the last cross happened.
+//!
+//! ```
+//! use rsta::signals::cross::{cross_above, Cross, CrossoverTracker};
+//!
+//! let fast = [1.0, 2.0, 4.0, 3.0, 1.0];
+//! let slow = [3.0, 3.0, 3.0, 3.0, 3.0];
+//! assert_eq!(cross_above(&fast, &slow), vec![false, false, true, false, false]);
+//!
+//! let mut tracker = CrossoverTracker::new();
+//! let events: Vec<_> = fast.iter().zip(&slow).map(|(&f, &s)| tracker.update(f, s)).collect();
+//! assert_eq!(events, vec![None, None, Some(Cross::Above), None, Some(Cross::Below)]);
+//! ```
+
+use crate::instrument;
+use crate::signals::{Signal, SignalEvent};
+
+/// Direction of a crossover of `a` relative to `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cross {
+    /// `a` moved from at-or-below `b` to above it.
+    Above,
+    /// `a` moved from at-or-above `b` to below it.
+    Below,
+}
+
+/// Classify the move from `(prev_a, prev_b)` to `(a, b)`.
+fn classify(prev_a: f64, prev_b: f64, a: f64, b: f64) -> Option<Cross> {
+    if prev_a <= prev_b && a > b {
+        Some(Cross::Above)
+    } else if prev_a >= prev_b && a < b {
+        Some(Cross::Below)
+    } else {
+        None
+    }
+}
+
+fn crosses(a: &[f64], b: &[f64], direction: Cross) -> Vec<bool> {
+    let n = a.len().min(b.len());
+    let mut out = vec![false; n];
+    for i in 1..n {
+        out[i] = classify(a[i - 1], b[i - 1], a[i], b[i]) == Some(direction);
+    }
+    out
+}
+
+/// `true` at every index where `a` crosses above `b`.
+///
+/// The result has one entry per compared pair (`min(a.len(), b.len())`);
+/// index 0 is always `false` since there is no previous bar.
+pub fn cross_above(a: &[f64], b: &[f64]) -> Vec<bool> {
+    crosses(a, b, Cross::Above)
+}
+
+/// `true` at every index where `a` crosses below `b`. See [`cross_above`].
+pub fn cross_below(a: &[f64], b: &[f64]) -> Vec<bool> {
+    crosses(a, b, Cross::Below)
+}
+
+/// Streaming crossover detector for two series.
+///
+/// Also implements [`Signal`] with input `(a, b)`: a cross above emits
+/// [`SignalEvent::Long`], a cross below [`SignalEvent::Short`], anything
+/// else [`SignalEvent::Hold`] (and `None` on the very first bar).
+#[derive(Debug, Clone, Default)]
+pub struct CrossoverTracker {
+    prev: Option<(f64, f64)>,
+    last_cross: Option<Cross>,
+    bars_since: Option<usize>,
+}
+
+impl CrossoverTracker {
+    /// Create a new tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the next pair of values and return the cross it completes, if
+    /// any.
+    pub fn update(&mut self, a: f64, b: f64) -> Option<Cross> {
+        let cross = self.prev.and_then(|(pa, pb)| classify(pa, pb, a, b));
+        self.prev = Some((a, b));
+        match cross {
+            Some(c) => {
+                self.last_cross = Some(c);
+                self.bars_since = Some(0);
+            }
+            None => self.bars_since = self.bars_since.map(|n| n + 1),
+        }
+        cross
+    }
+
+    /// Direction of the most recent cross.
+    pub fn last_cross(&self) -> Option<Cross> {
+        self.last_cross
+    }
+
+    /// Bars elapsed since the most recent cross (`0` on the bar it happened).
+    pub fn bars_since_cross(&self) -> Option<usize> {
+        self.bars_since
+    }
+
+    /// `true` if `a` is currently above `b`.
+    pub fn is_above(&self) -> bool {
+        matches!(self.prev, Some((a, b)) if a > b)
+    }
+
+    /// Reset internal state.
+    pub fn reset_state(&mut self) {
+        self.prev = None;
+        self.last_cross = None;
+        self.bars_since = None;
+    }
+}
+
+impl Signal for CrossoverTracker {
+    type Input = (f64, f64);
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Option<SignalEvent> {
+        let first = self.prev.is_none();
+        match self.update(a, b) {
+            Some(Cross::Above) => {
+                instrument::debug!(signal = "crossover", a, b, "crossed above");
+                Some(SignalEvent::Long)
+            }
+            Some(Cross::Below) => {
+                instrument::debug!(signal = "crossover", a, b, "crossed below");
+                Some(SignalEvent::Short)
+            }
+            None if first => None,
+            None => Some(SignalEvent::Hold),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.reset_state();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_above_and_below() {
+        let a = [1.0, 2.0, 4.0, 5.0, 2.0, 1.0, 4.0];
+        let b = [3.0; 7];
+        assert_eq!(
+            cross_above(&a, &b),
+            vec![false, false, true, false, false, false, true]
+        );
+        assert_eq!(
+            cross_below(&a, &b),
+            vec![false, false, false, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_touch_is_not_a_cross() {
+        // a touches b (equal) and then moves back below.
+        let a = [1.0, 3.0, 1.0];
+        let b = [3.0, 3.0, 3.0];
+        assert_eq!(cross_above(&a, &b), vec![false; 3]);
+        assert_eq!(cross_below(&a, &b), vec![false, false, true]);
+    }
+
+    #[test]
+    fn test_mismatched_lengths_and_empty() {
+        assert_eq!(
+            cross_above(&[1.0, 5.0, 6.0], &[2.0, 2.0]),
+            vec![false, true]
+        );
+        assert!(cross_below(&[], &[1.0]).is_empty());
+    }
+
+    #[test]
+    fn test_nan_never_crosses() {
+        let a = [1.0, f64::NAN, 5.0];
+        let b = [2.0, 2.0, 2.0];
+        assert_eq!(cross_above(&a, &b), vec![false; 3]);
+    }
+
+    #[test]
+    fn test_tracker_matches_batch() {
+        let a: Vec<f64> = (0..50).map(|i| (i as f64 * 0.3).sin()).collect();
+        let b: Vec<f64> = (0..50).map(|i| (i as f64 * 0.3).cos() * 0.5).collect();
+        let up = cross_above(&a, &b);
+        let down = cross_below(&a, &b);
+
+        let mut tracker = CrossoverTracker::new();
+        for i in 0..a.len() {
+            let cross = tracker.update(a[i], b[i]);
+            assert_eq!(cross == Some(Cross::Above), up[i], "bar {i}");
+            assert_eq!(cross == Some(Cross::Below), down[i], "bar {i}");
+        }
+    }
+
+    #[test]
+    fn test_tracker_bars_since_and_reset() {
+        let mut t = CrossoverTracker::new();
+        assert_eq!(t.bars_since_cross(), None);
+        t.update(1.0, 2.0);
+        t.update(3.0, 2.0);
+        assert_eq!(t.last_cross(), Some(Cross::Above));
+        assert_eq!(t.bars_since_cross(), Some(0));
+        assert!(t.is_above());
+        t.update(4.0, 2.0);
+        t.update(5.0, 2.0);
+        assert_eq!(t.bars_since_cross(), Some(2));
+
+        t.reset_state();
+        assert_eq!(t.last_cross(), None);
+        assert!(!t.is_above());
+        assert_eq!(t.update(3.0, 2.0), None);
+    }
+
+    #[test]
+    fn test_tracker_as_signal() {
+        let mut t = CrossoverTracker::new();
+        assert_eq!(t.next((1.0, 2.0)), None);
+        assert_eq!(t.next((1.5, 2.0)), Some(SignalEvent::Hold));
+        assert_eq!(t.next((3.0, 2.0)), Some(SignalEvent::Long));
+        assert_eq!(t.next((1.0, 2.0)), Some(SignalEvent::Short));
+    }
+}
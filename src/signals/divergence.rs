@@ -14,6 +14,7 @@
 //! noise rejection.
 
 use crate::indicators::IndicatorError;
+use crate::instrument;
 use crate::signals::{Signal, SignalEvent};
 use std::collections::VecDeque;
 
@@ -80,8 +81,10 @@ impl Divergence {
     /// Returns `IndicatorError::InvalidParameter` if `lookback` is `0`.
     pub fn new(lookback: usize) -> Result<Self, IndicatorError> {
         if lookback == 0 {
-            return Err(IndicatorError::InvalidParameter(
-                "Divergence lookback must be at least 1".to_string(),
+            return Err(IndicatorError::invalid_parameter(
+                "lookback",
+                lookback,
+                "must be at least 1",
             ));
         }
         Ok(Self {
@@ -157,6 +160,15 @@ impl Signal for Divergence {
                 // Bearish divergence: price prints a higher high, oscillator
                 // prints a lower high.
                 if new.price > prev.price && new.osc < prev.osc {
+                    instrument::debug!(
+                        signal = "divergence",
+                        bar = center_bar,
+                        price = new.price,
+                        osc = new.osc,
+                        prev_price = prev.price,
+                        prev_osc = prev.osc,
+                        "bearish divergence"
+                    );
                     Some(SignalEvent::Short)
                 } else {
                     None
@@ -178,6 +190,15 @@ impl Signal for Divergence {
                 // Bullish divergence: price prints a lower low, oscillator
                 // prints a higher low.
                 if new.price < prev.price && new.osc > prev.osc {
+                    instrument::debug!(
+                        signal = "divergence",
+                        bar = center_bar,
+                        price = new.price,
+                        osc = new.osc,
+                        prev_price = prev.price,
+                        prev_osc = prev.osc,
+                        "bullish divergence"
+                    );
                     Some(SignalEvent::Long)
                 } else {
                     None
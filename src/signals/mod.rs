@@ -15,6 +15,9 @@
 //!   (driven by [`crate::indicators::volatility::Donchian`] or any custom
 //!   upper/lower band).
 //!
+//! For raw crossover detection without the [`Signal`] wrapper, see the
+//! [`cross`] module ([`cross_above`], [`cross_below`], [`CrossoverTracker`]).
+//!
 //! Combinators ([`SignalExt::and`], [`SignalExt::or`], [`SignalExt::not`])
 //! let users compose signals without writing custom structs.
 //!
@@ -41,9 +44,13 @@
 //! }
 //! ```
 
+pub mod cross;
 pub mod divergence;
+pub use self::cross::{cross_above, cross_below, Cross, CrossoverTracker};
 pub use self::divergence::Divergence;
 
+use crate::instrument;
+
 /// A discrete trading event emitted by a [`Signal`].
 ///
 /// `Long` and `Short` indicate an entry direction. `Exit` flags an explicit
@@ -122,7 +129,17 @@ impl Signal for CrossUp {
     type Input = (f64, f64);
     fn next(&mut self, (a, b): (f64, f64)) -> Option<SignalEvent> {
         let event = match self.prev {
-            Some((pa, pb)) if pa <= pb && a > b => Some(SignalEvent::Long),
+            Some((pa, pb)) if pa <= pb && a > b => {
+                instrument::debug!(
+                    signal = "cross_up",
+                    a,
+                    b,
+                    prev_a = pa,
+                    prev_b = pb,
+                    "crossed above"
+                );
+                Some(SignalEvent::Long)
+            }
             Some(_) => Some(SignalEvent::Hold),
             None => None,
         };
@@ -151,7 +168,17 @@ impl Signal for CrossDown {
     type Input = (f64, f64);
     fn next(&mut self, (a, b): (f64, f64)) -> Option<SignalEvent> {
         let event = match self.prev {
-            Some((pa, pb)) if pa >= pb && a < b => Some(SignalEvent::Short),
+            Some((pa, pb)) if pa >= pb && a < b => {
+                instrument::debug!(
+                    signal = "cross_down",
+                    a,
+                    b,
+                    prev_a = pa,
+                    prev_b = pb,
+                    "crossed below"
+                );
+                Some(SignalEvent::Short)
+            }
             Some(_) => Some(SignalEvent::Hold),
             None => None,
         };
@@ -185,7 +212,16 @@ impl Signal for ThresholdAbove {
     type Input = f64;
     fn next(&mut self, value: f64) -> Option<SignalEvent> {
         let event = match self.prev {
-            Some(prev) if prev <= self.level && value > self.level => Some(SignalEvent::Long),
+            Some(prev) if prev <= self.level && value > self.level => {
+                instrument::debug!(
+                    signal = "threshold_above",
+                    value,
+                    prev,
+                    level = self.level,
+                    "crossed above level"
+                );
+                Some(SignalEvent::Long)
+            }
             Some(_) => Some(SignalEvent::Hold),
             None => None,
         };
@@ -215,7 +251,16 @@ impl Signal for ThresholdBelow {
     type Input = f64;
     fn next(&mut self, value: f64) -> Option<SignalEvent> {
         let event = match self.prev {
-            Some(prev) if prev >= self.level && value < self.level => Some(SignalEvent::Short),
+            Some(prev) if prev >= self.level && value < self.level => {
+                instrument::debug!(
+                    signal = "threshold_below",
+                    value,
+                    prev,
+                    level = self.level,
+                    "crossed below level"
+                );
+                Some(SignalEvent::Short)
+            }
             Some(_) => Some(SignalEvent::Hold),
             None => None,
         };
@@ -255,8 +300,26 @@ impl Signal for Breakout {
         self.inside = Some(now_inside);
         match was_inside {
             None => None,
-            Some(true) if value > upper => Some(SignalEvent::Long),
-            Some(true) if value < lower => Some(SignalEvent::Short),
+            Some(true) if value > upper => {
+                instrument::debug!(
+                    signal = "breakout",
+                    value,
+                    upper,
+                    lower,
+                    "broke above channel"
+                );
+                Some(SignalEvent::Long)
+            }
+            Some(true) if value < lower => {
+                instrument::debug!(
+                    signal = "breakout",
+                    value,
+                    upper,
+                    lower,
+                    "broke below channel"
+                );
+                Some(SignalEvent::Short)
+            }
             _ => Some(SignalEvent::Hold),
         }
     }
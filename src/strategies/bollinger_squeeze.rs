@@ -0,0 +1,173 @@
+use crate::backtest::{Action, Context, Quantity, Side, Strategy};
+use crate::indicators::any_indicator::IndicatorValue;
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::utils::{validate_period, RollingMin};
+use crate::indicators::{Candle, IndicatorError};
+use crate::signals::{Breakout, Signal, SignalEvent};
+
+/// Bollinger squeeze breakout.
+///
+/// Volatility contracts before it expands: when the band width
+/// `(upper - lower) / middle` is the narrowest of the last `lookback`
+/// bars the strategy arms, and the next close outside the bands enters in
+/// the breakout's direction (shorts only when built
+/// [`with_shorts`](Self::with_shorts)). The position is closed when the
+/// close crosses back over the middle band.
+///
+/// # Example
+/// ```
+/// use rsta::strategies::BollingerSqueeze;
+///
+/// let strategy = BollingerSqueeze::new(20, 2.0, 120).unwrap();
+/// assert!(BollingerSqueeze::new(20, 2.0, 0).is_err());
+/// # let _ = strategy;
+/// ```
+#[derive(Debug)]
+pub struct BollingerSqueeze {
+    period: usize,
+    k: f64,
+    quantity: Quantity,
+    shorts: bool,
+    narrowest: RollingMin,
+    armed: bool,
+    breakout: Breakout,
+}
+
+impl BollingerSqueeze {
+    /// Create a new squeeze strategy
+    ///
+    /// # Arguments
+    /// * `period` - Bollinger Bands period (must be at least 1)
+    /// * `k` - Band width in standard deviations (must be positive)
+    /// * `lookback` - Bars over which the band width must be the narrowest (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new strategy or an error
+    pub fn new(period: usize, k: f64, lookback: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        if k <= 0.0 || !k.is_finite() {
+            return Err(IndicatorError::invalid_parameter(
+                "k",
+                k,
+                "must be positive",
+            ));
+        }
+        if lookback == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "lookback",
+                lookback,
+                "must be at least 1",
+            ));
+        }
+        Ok(Self {
+            period,
+            k,
+            quantity: Quantity::AllCash,
+            shorts: false,
+            narrowest: RollingMin::new(lookback)?,
+            armed: false,
+            breakout: Breakout::new(),
+        })
+    }
+
+    /// Size entries with `quantity`.
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Also trade downside breakouts.
+    pub fn with_shorts(mut self) -> Self {
+        self.shorts = true;
+        self
+    }
+
+    /// `true` while a squeeze waits for its breakout.
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+}
+
+impl Strategy for BollingerSqueeze {
+    fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+        vec![(
+            "bands",
+            IndicatorSpec::new("bollinger")
+                .param("period", self.period as f64)
+                .param("k", self.k),
+        )]
+    }
+
+    fn on_candle(&mut self, candle: &Candle, ctx: &Context) -> Action {
+        let Some(IndicatorValue::Bands {
+            upper,
+            middle,
+            lower,
+        }) = ctx.indicators.get("bands")
+        else {
+            return Action::Hold;
+        };
+        let breakout = self.breakout.next((candle.close, upper, lower));
+
+        match ctx.portfolio.position.map(|p| p.side) {
+            Some(Side::Long) if candle.close < middle => return Action::Exit,
+            Some(Side::Short) if candle.close > middle => return Action::Exit,
+            _ => {}
+        }
+
+        let action = match breakout {
+            Some(SignalEvent::Long) if self.armed => {
+                super::enter(Side::Long, self.quantity, true, ctx)
+            }
+            Some(SignalEvent::Short) if self.armed && self.shorts => {
+                super::enter(Side::Short, self.quantity, true, ctx)
+            }
+            _ => Action::Hold,
+        };
+        if matches!(breakout, Some(SignalEvent::Long | SignalEvent::Short)) {
+            self.armed = false;
+        }
+
+        if middle != 0.0 {
+            let width = (upper - lower) / middle;
+            let narrowest = self.narrowest.push(width);
+            if self.narrowest.is_full() && narrowest.is_some_and(|n| width <= n) {
+                self.armed = true;
+            }
+        }
+        action
+    }
+
+    fn on_start(&mut self) {
+        self.narrowest.reset();
+        self.armed = false;
+        self.breakout.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, Backtester};
+    use crate::strategies::tests::candles;
+
+    #[test]
+    fn test_breakout_after_squeeze() {
+        // Choppy, then a tight range, then a rally that rolls over
+        let closes: Vec<f64> = (0..20)
+            .map(|i| if i % 2 == 0 { 95.0 } else { 105.0 })
+            .chain((0..10).map(|i| if i % 2 == 0 { 99.5 } else { 100.5 }))
+            .chain([106.0, 110.0, 114.0, 116.0, 108.0, 100.0, 96.0])
+            .collect();
+        let bt = Backtester::new(BacktestConfig::default());
+        // k = 1.5: a 5-bar window never holds a close 2 deviations out
+        let mut strategy = BollingerSqueeze::new(5, 1.5, 10).unwrap();
+        let result = bt.run(&candles(&closes), &mut strategy).unwrap();
+
+        assert_eq!(result.metrics.trade_count, 1);
+        let trade = result.portfolio.trades[0];
+        assert_eq!(trade.side, Side::Long);
+        assert!(trade.pnl > 0.0);
+        assert!(BollingerSqueeze::new(5, 0.0, 10).is_err());
+    }
+}
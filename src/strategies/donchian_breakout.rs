@@ -0,0 +1,150 @@
+use crate::backtest::{Action, Context, Quantity, Side, Strategy};
+use crate::indicators::any_indicator::IndicatorValue;
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::utils::validate_period;
+use crate::indicators::{Candle, IndicatorError};
+use crate::signals::{Breakout, Signal, SignalEvent};
+
+/// Donchian channel breakout, after the Turtle rules.
+///
+/// Enters long when the close breaks above the highest high of the
+/// previous `entry_period` bars and exits when it breaks below the lowest
+/// low of the previous `exit_period` bars. Built
+/// [`with_shorts`](Self::with_shorts) it trades the mirror rules too. The
+/// channels are taken from the bar before the signal, so the current
+/// bar's own range never contains the breakout.
+///
+/// # Example
+/// ```
+/// use rsta::strategies::DonchianBreakout;
+///
+/// // Turtle system 1
+/// let strategy = DonchianBreakout::new(20, 10).unwrap();
+/// assert!(DonchianBreakout::new(10, 20).is_err());
+/// # let _ = strategy;
+/// ```
+#[derive(Debug)]
+pub struct DonchianBreakout {
+    entry_period: usize,
+    exit_period: usize,
+    quantity: Quantity,
+    shorts: bool,
+    breakout: Breakout,
+}
+
+impl DonchianBreakout {
+    /// Create a new Donchian breakout strategy
+    ///
+    /// # Arguments
+    /// * `entry_period` - Channel period for entries (must be at least 1)
+    /// * `exit_period` - Channel period for exits (must be at least 1 and at most `entry_period`)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new strategy or an error
+    pub fn new(entry_period: usize, exit_period: usize) -> Result<Self, IndicatorError> {
+        validate_period(entry_period, 1)?;
+        if exit_period == 0 || exit_period > entry_period {
+            return Err(IndicatorError::invalid_parameter(
+                "exit_period",
+                exit_period,
+                format!("must be between 1 and entry_period ({entry_period})"),
+            ));
+        }
+        Ok(Self {
+            entry_period,
+            exit_period,
+            quantity: Quantity::AllCash,
+            shorts: false,
+            breakout: Breakout::new(),
+        })
+    }
+
+    /// Size entries with `quantity`.
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Also trade downside breakouts.
+    pub fn with_shorts(mut self) -> Self {
+        self.shorts = true;
+        self
+    }
+}
+
+/// `(upper, lower)` of a channel value.
+fn channel(value: Option<IndicatorValue>) -> Option<(f64, f64)> {
+    match value? {
+        IndicatorValue::Bands { upper, lower, .. } => Some((upper, lower)),
+        _ => None,
+    }
+}
+
+impl Strategy for DonchianBreakout {
+    fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+        vec![
+            (
+                "entry",
+                IndicatorSpec::new("donchian").param("period", self.entry_period as f64),
+            ),
+            (
+                "exit",
+                IndicatorSpec::new("donchian").param("period", self.exit_period as f64),
+            ),
+        ]
+    }
+
+    fn on_candle(&mut self, candle: &Candle, ctx: &Context) -> Action {
+        let (Some((upper, lower)), Some((exit_upper, exit_lower))) = (
+            channel(ctx.indicators.previous("entry")),
+            channel(ctx.indicators.previous("exit")),
+        ) else {
+            return Action::Hold;
+        };
+        let breakout = self.breakout.next((candle.close, upper, lower));
+
+        match ctx.portfolio.position.map(|p| p.side) {
+            Some(Side::Long) if candle.close < exit_lower => return Action::Exit,
+            Some(Side::Short) if candle.close > exit_upper => return Action::Exit,
+            _ => {}
+        }
+        match breakout {
+            Some(SignalEvent::Long) => super::enter(Side::Long, self.quantity, true, ctx),
+            Some(SignalEvent::Short) if self.shorts => {
+                super::enter(Side::Short, self.quantity, true, ctx)
+            }
+            _ => Action::Hold,
+        }
+    }
+
+    fn on_start(&mut self) {
+        self.breakout.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, Backtester};
+    use crate::strategies::tests::candles;
+
+    #[test]
+    fn test_rides_the_trend_until_the_exit_channel_breaks() {
+        // Range, breakout, trend, reversal
+        let closes: Vec<f64> = (0..10)
+            .map(|i| if i % 2 == 0 { 99.0 } else { 101.0 })
+            .chain((1..=10).map(|i| 101.0 + 2.0 * i as f64))
+            .chain((1..=6).map(|i| 121.0 - 4.0 * i as f64))
+            .collect();
+        let bt = Backtester::new(BacktestConfig::default());
+        let result = bt
+            .run(&candles(&closes), &mut DonchianBreakout::new(6, 3).unwrap())
+            .unwrap();
+
+        assert_eq!(result.metrics.trade_count, 1);
+        let trade = result.portfolio.trades[0];
+        assert_eq!(trade.side, Side::Long);
+        assert!(trade.pnl > 0.0);
+        assert!(result.portfolio.position.is_none());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::backtest::{Action, Context, Quantity, Side, Strategy};
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::trend::MaType;
+use crate::indicators::{Candle, IndicatorError};
+use crate::signals::{CrossDown, CrossUp, Signal, SignalEvent};
+
+/// Moving-average crossover trend follower.
+///
+/// Goes long when the fast moving average crosses above the slow one and
+/// leaves when it crosses back below — or reverses into a short when built
+/// [`with_shorts`](Self::with_shorts). Both averages are simple by
+/// default; [`with_ma`](Self::with_ma) picks another [`MaType`].
+///
+/// # Example
+/// ```
+/// use rsta::indicators::trend::MaType;
+/// use rsta::strategies::MaCrossover;
+///
+/// let strategy = MaCrossover::new(50, 200).unwrap().with_ma(MaType::Ema);
+/// assert!(MaCrossover::new(20, 10).is_err());
+/// # let _ = strategy;
+/// ```
+#[derive(Debug)]
+pub struct MaCrossover {
+    fast: usize,
+    slow: usize,
+    ma_type: MaType,
+    quantity: Quantity,
+    shorts: bool,
+    cross_up: CrossUp,
+    cross_down: CrossDown,
+}
+
+impl MaCrossover {
+    /// Create a new crossover strategy
+    ///
+    /// # Arguments
+    /// * `fast` - Period of the fast average (must be at least 1)
+    /// * `slow` - Period of the slow average (must be greater than `fast`)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new strategy or an error
+    pub fn new(fast: usize, slow: usize) -> Result<Self, IndicatorError> {
+        if fast == 0 {
+            return Err(IndicatorError::invalid_parameter(
+                "fast",
+                fast,
+                "must be at least 1",
+            ));
+        }
+        if slow <= fast {
+            return Err(IndicatorError::invalid_parameter(
+                "slow",
+                slow,
+                format!("must be greater than fast ({fast})"),
+            ));
+        }
+        Ok(Self {
+            fast,
+            slow,
+            ma_type: MaType::Sma,
+            quantity: Quantity::AllCash,
+            shorts: false,
+            cross_up: CrossUp::new(),
+            cross_down: CrossDown::new(),
+        })
+    }
+
+    /// Use `ma_type` for both averages.
+    pub fn with_ma(mut self, ma_type: MaType) -> Self {
+        self.ma_type = ma_type;
+        self
+    }
+
+    /// Size entries with `quantity`.
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Go short on bearish crossings instead of only exiting.
+    pub fn with_shorts(mut self) -> Self {
+        self.shorts = true;
+        self
+    }
+
+    fn spec(&self, period: usize) -> IndicatorSpec {
+        let name = match self.ma_type {
+            MaType::Sma => "sma",
+            MaType::Ema => "ema",
+            MaType::Wma => "wma",
+            MaType::Dema => "dema",
+        };
+        IndicatorSpec::new(name).param("period", period as f64)
+    }
+}
+
+impl Strategy for MaCrossover {
+    fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+        vec![
+            ("fast", self.spec(self.fast)),
+            ("slow", self.spec(self.slow)),
+        ]
+    }
+
+    fn on_candle(&mut self, _candle: &Candle, ctx: &Context) -> Action {
+        let (Some(fast), Some(slow)) = (ctx.indicators.value("fast"), ctx.indicators.value("slow"))
+        else {
+            return Action::Hold;
+        };
+        let up = self.cross_up.next((fast, slow));
+        let down = self.cross_down.next((fast, slow));
+        match (up, down) {
+            (Some(SignalEvent::Long), _) => super::enter(Side::Long, self.quantity, true, ctx),
+            (_, Some(SignalEvent::Short)) => {
+                super::enter(Side::Short, self.quantity, self.shorts, ctx)
+            }
+            _ => Action::Hold,
+        }
+    }
+
+    fn on_start(&mut self) {
+        self.cross_up.reset();
+        self.cross_down.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, Backtester};
+    use crate::strategies::tests::candles;
+
+    #[test]
+    fn test_enters_and_exits_on_crossings() {
+        // Down, up, down: one long trade in the middle
+        let closes: Vec<f64> = (0..10)
+            .map(|i| 50.0 - i as f64)
+            .chain((0..10).map(|i| 41.0 + 2.0 * i as f64))
+            .chain((0..10).map(|i| 59.0 - 3.0 * i as f64))
+            .collect();
+        let data = candles(&closes);
+        let bt = Backtester::new(BacktestConfig::default());
+
+        let result = bt.run(&data, &mut MaCrossover::new(2, 4).unwrap()).unwrap();
+        assert_eq!(result.metrics.trade_count, 1);
+        let trade = result.portfolio.trades[0];
+        assert_eq!(trade.side, Side::Long);
+        assert!(trade.pnl > 0.0);
+        assert!(result.portfolio.position.is_none());
+
+        let mut reversing = MaCrossover::new(2, 4).unwrap().with_shorts();
+        let result = bt.run(&data, &mut reversing).unwrap();
+        assert_eq!(result.portfolio.position.map(|p| p.side), Some(Side::Short));
+        assert!(MaCrossover::new(0, 4).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+//! # Reference strategies
+//!
+//! Complete [`Strategy`](crate::backtest::Strategy) implementations of
+//! classic systems, built from the crate's own pieces: indicators declared
+//! through [`Strategy::indicators`](crate::backtest::Strategy::indicators)
+//! and events from the [`signals`](crate::signals) layer. They are meant
+//! as starting points and worked examples more than as edges; read the
+//! source of one before writing your own.
+//!
+//! | Strategy | Enters | Exits |
+//! |---|---|---|
+//! | [`MaCrossover`] | fast MA crosses above the slow MA | fast MA crosses back below |
+//! | [`RsiMeanReversion`] | RSI climbs back above the oversold level | RSI reaches the exit level |
+//! | [`BollingerSqueeze`] | close leaves the bands after the narrowest bandwidth in a lookback | close crosses back over the middle band |
+//! | [`DonchianBreakout`] | close breaks the prior entry-channel high | close breaks the prior exit-channel low |
+//!
+//! All of them trade long only unless built `with_shorts()`, in which case
+//! the mirror rules open shorts, and size entries with
+//! [`Quantity::AllCash`](crate::backtest::Quantity::AllCash) unless given
+//! another `with_quantity`.
+//!
+//! ```
+//! use rsta::backtest::{BacktestConfig, Backtester};
+//! use rsta::indicators::Candle;
+//! use rsta::strategies::MaCrossover;
+//!
+//! let candles: Vec<Candle> = (0..200)
+//!     .map(|i| {
+//!         let close = 100.0 + (i as f64 / 15.0).sin() * 20.0;
+//!         Candle { timestamp: i, open: close, high: close + 1.0, low: close - 1.0, close, volume: 1.0 }
+//!     })
+//!     .collect();
+//!
+//! let mut strategy = MaCrossover::new(5, 20).unwrap();
+//! let result = Backtester::new(BacktestConfig::default()).run(&candles, &mut strategy).unwrap();
+//! assert!(result.metrics.trade_count > 0);
+//! ```
+
+pub mod bollinger_squeeze;
+pub mod donchian_breakout;
+pub mod ma_crossover;
+pub mod rsi_reversion;
+
+pub use self::bollinger_squeeze::BollingerSqueeze;
+pub use self::donchian_breakout::DonchianBreakout;
+pub use self::ma_crossover::MaCrossover;
+pub use self::rsi_reversion::RsiMeanReversion;
+
+use crate::backtest::{Action, Context, Quantity, Side};
+
+/// Action for a directional event, given what is held: enter `side`
+/// unless already there; with shorts disabled a short event only closes a
+/// long.
+fn enter(side: Side, quantity: Quantity, shorts: bool, ctx: &Context) -> Action {
+    let held = ctx.portfolio.position.map(|p| p.side);
+    match side {
+        _ if held == Some(side) => Action::Hold,
+        Side::Long => Action::EnterLong(quantity),
+        Side::Short if shorts => Action::EnterShort(quantity),
+        Side::Short if held.is_some() => Action::Exit,
+        Side::Short => Action::Hold,
+    }
+}
+
+/// Close the position if it is on `side`.
+fn exit(side: Side, ctx: &Context) -> Action {
+    match ctx.portfolio.position {
+        Some(p) if p.side == side => Action::Exit,
+        _ => Action::Hold,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::indicators::Candle;
+
+    /// Candles with `open = close` and a one-unit range around it.
+    pub(crate) fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                timestamp: i as u64,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+}
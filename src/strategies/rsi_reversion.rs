@@ -0,0 +1,217 @@
+use crate::backtest::{Action, Context, Quantity, Side, Strategy};
+use crate::indicators::factory::IndicatorSpec;
+use crate::indicators::utils::validate_period;
+use crate::indicators::{Candle, IndicatorError};
+use crate::signals::{Signal, SignalEvent, ThresholdAbove, ThresholdBelow};
+
+/// RSI mean reversion.
+///
+/// Buys when the RSI climbs back above the oversold level — the selling
+/// has exhausted itself — and sells once it reaches the exit level (50 by
+/// default). Built [`with_shorts`](Self::with_shorts), it also shorts when
+/// the RSI falls back below the overbought level and covers at the exit
+/// level.
+///
+/// # Example
+/// ```
+/// use rsta::strategies::RsiMeanReversion;
+///
+/// // Connors-style RSI(2) with 10 / 90 bands
+/// let strategy = RsiMeanReversion::new(2).unwrap().with_levels(10.0, 90.0).unwrap();
+/// assert_eq!(strategy.levels(), (10.0, 90.0));
+/// ```
+#[derive(Debug)]
+pub struct RsiMeanReversion {
+    period: usize,
+    oversold: f64,
+    overbought: f64,
+    exit_level: f64,
+    quantity: Quantity,
+    shorts: bool,
+    recover: ThresholdAbove,
+    fade: ThresholdBelow,
+    exit_long: ThresholdAbove,
+    exit_short: ThresholdBelow,
+}
+
+impl RsiMeanReversion {
+    /// Create a new RSI mean-reversion strategy with 30 / 70 levels
+    ///
+    /// # Arguments
+    /// * `period` - RSI period (must be at least 1)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - A new strategy or an error
+    pub fn new(period: usize) -> Result<Self, IndicatorError> {
+        validate_period(period, 1)?;
+        Ok(Self::build(
+            period,
+            30.0,
+            70.0,
+            50.0,
+            Quantity::AllCash,
+            false,
+        ))
+    }
+
+    /// Use other oversold / overbought levels
+    ///
+    /// # Arguments
+    /// * `oversold` - Level the RSI recovers above to buy
+    /// * `overbought` - Level the RSI falls below to short (the exit level must lie between the two)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The strategy with the new levels or an error
+    pub fn with_levels(self, oversold: f64, overbought: f64) -> Result<Self, IndicatorError> {
+        validate_levels(oversold, self.exit_level, overbought)?;
+        Ok(Self::build(
+            self.period,
+            oversold,
+            overbought,
+            self.exit_level,
+            self.quantity,
+            self.shorts,
+        ))
+    }
+
+    /// Exit at `level` instead of 50
+    ///
+    /// # Arguments
+    /// * `level` - RSI level closing positions (must lie between the oversold and overbought levels)
+    ///
+    /// # Returns
+    /// * `Result<Self, IndicatorError>` - The strategy with the new exit or an error
+    pub fn with_exit_level(self, level: f64) -> Result<Self, IndicatorError> {
+        validate_levels(self.oversold, level, self.overbought)?;
+        Ok(Self::build(
+            self.period,
+            self.oversold,
+            self.overbought,
+            level,
+            self.quantity,
+            self.shorts,
+        ))
+    }
+
+    /// Size entries with `quantity`.
+    pub fn with_quantity(mut self, quantity: Quantity) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Also short overbought reversals.
+    pub fn with_shorts(mut self) -> Self {
+        self.shorts = true;
+        self
+    }
+
+    /// `(oversold, overbought)` levels.
+    pub fn levels(&self) -> (f64, f64) {
+        (self.oversold, self.overbought)
+    }
+
+    fn build(
+        period: usize,
+        oversold: f64,
+        overbought: f64,
+        exit_level: f64,
+        quantity: Quantity,
+        shorts: bool,
+    ) -> Self {
+        Self {
+            period,
+            oversold,
+            overbought,
+            exit_level,
+            quantity,
+            shorts,
+            recover: ThresholdAbove::new(oversold),
+            fade: ThresholdBelow::new(overbought),
+            exit_long: ThresholdAbove::new(exit_level),
+            exit_short: ThresholdBelow::new(exit_level),
+        }
+    }
+}
+
+fn validate_levels(oversold: f64, exit: f64, overbought: f64) -> Result<(), IndicatorError> {
+    if !(0.0 < oversold && oversold < exit && exit < overbought && overbought < 100.0) {
+        return Err(IndicatorError::invalid_parameter(
+            "levels",
+            format!("{oversold} / {exit} / {overbought}"),
+            "oversold, exit and overbought must increase strictly within (0, 100)",
+        ));
+    }
+    Ok(())
+}
+
+impl Strategy for RsiMeanReversion {
+    fn indicators(&self) -> Vec<(&'static str, IndicatorSpec)> {
+        vec![(
+            "rsi",
+            IndicatorSpec::new("rsi").param("period", self.period as f64),
+        )]
+    }
+
+    fn on_candle(&mut self, _candle: &Candle, ctx: &Context) -> Action {
+        let Some(rsi) = ctx.indicators.value("rsi") else {
+            return Action::Hold;
+        };
+        let recover = self.recover.next(rsi);
+        let fade = self.fade.next(rsi);
+        let exit_long = self.exit_long.next(rsi);
+        let exit_short = self.exit_short.next(rsi);
+
+        if recover == Some(SignalEvent::Long) {
+            return super::enter(Side::Long, self.quantity, true, ctx);
+        }
+        if self.shorts && fade == Some(SignalEvent::Short) {
+            return super::enter(Side::Short, self.quantity, true, ctx);
+        }
+        if exit_long == Some(SignalEvent::Long) {
+            return super::exit(Side::Long, ctx);
+        }
+        if exit_short == Some(SignalEvent::Short) {
+            return super::exit(Side::Short, ctx);
+        }
+        Action::Hold
+    }
+
+    fn on_start(&mut self) {
+        self.recover.reset();
+        self.fade.reset();
+        self.exit_long.reset();
+        self.exit_short.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, Backtester};
+    use crate::strategies::tests::candles;
+
+    #[test]
+    fn test_buys_the_recovery_and_exits_at_midline() {
+        // Sell-off, rebound, rally
+        let closes = [
+            50.0, 49.0, 47.0, 44.0, 40.0, 35.0, 37.0, 40.0, 44.0, 49.0, 55.0, 56.0,
+        ];
+        let bt = Backtester::new(BacktestConfig::default());
+        let result = bt
+            .run(&candles(&closes), &mut RsiMeanReversion::new(3).unwrap())
+            .unwrap();
+        assert_eq!(result.metrics.trade_count, 1);
+        let trade = result.portfolio.trades[0];
+        // Signalled on the close at 37, filled at the next open
+        assert_eq!(trade.entry_price, 40.0);
+        assert!(trade.pnl > 0.0);
+
+        let strategy = RsiMeanReversion::new(3).unwrap();
+        assert!(strategy.with_exit_level(80.0).is_err());
+        assert!(RsiMeanReversion::new(3)
+            .unwrap()
+            .with_levels(60.0, 40.0)
+            .is_err());
+        assert!(RsiMeanReversion::new(0).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! # Async streams
+//!
+//! [`futures_core::Stream`] adapters that run indicators over an async
+//! market-data feed, so the library plugs straight into tokio (or any
+//! other executor) pipelines.
+//!
+//! [`IndicatorStreamExt`] is implemented for every stream:
+//!
+//! - [`with_indicator`](IndicatorStreamExt::with_indicator) wraps a
+//!   `Stream<Item = T>` and yields `Ok((item, output))` for every item
+//!   once the indicator has warmed up.
+//! - [`with_indicator_set`](IndicatorStreamExt::with_indicator_set) does
+//!   the same for an [`IndicatorSet`], yielding the map of latest outputs
+//!   for every item.
+//!
+//! Indicator errors are yielded as `Err` and the stream carries on with
+//! the next item, so a single bad candle does not end the feed.
+//!
+//! Available with the `async` feature.
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use futures::stream::{self, StreamExt};
+//! use rsta::indicators::trend::Sma;
+//! use rsta::stream::IndicatorStreamExt;
+//!
+//! let feed = stream::iter(vec![1.0, 2.0, 3.0, 4.0]);
+//! let out: Vec<_> = block_on(feed.with_indicator(Sma::new(3).unwrap()).collect());
+//! let out: Vec<(f64, f64)> = out.into_iter().map(Result::unwrap).collect();
+//! assert_eq!(out, vec![(3.0, 2.0), (4.0, 3.0)]);
+//! ```
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use pin_project_lite::pin_project;
+
+use crate::indicators::indicator_set::IndicatorSet;
+use crate::indicators::{Indicator, IndicatorError};
+
+pin_project! {
+    /// Stream returned by [`IndicatorStreamExt::with_indicator`].
+    #[must_use = "streams do nothing unless polled"]
+    pub struct WithIndicator<S, I, O> {
+        #[pin]
+        inner: S,
+        indicator: I,
+        _output: PhantomData<fn() -> O>,
+    }
+}
+
+impl<S, I, O> WithIndicator<S, I, O> {
+    /// The wrapped indicator.
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    /// Consume the adapter, returning the inner stream and the indicator.
+    pub fn into_inner(self) -> (S, I) {
+        (self.inner, self.indicator)
+    }
+}
+
+impl<S, I, O> Stream for WithIndicator<S, I, O>
+where
+    S: Stream,
+    S::Item: Clone,
+    I: Indicator<S::Item, O>,
+{
+    type Item = Result<(S::Item, O), IndicatorError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            let Some(item) = std::task::ready!(this.inner.as_mut().poll_next(cx)) else {
+                return Poll::Ready(None);
+            };
+            match this.indicator.next(item.clone()) {
+                Ok(Some(output)) => return Poll::Ready(Some(Ok((item, output)))),
+                // Still warming up: pull the next item.
+                Ok(None) => continue,
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Warm-up items are dropped, so only the upper bound carries over.
+        (0, self.inner.size_hint().1)
+    }
+}
+
+pin_project! {
+    /// Stream returned by [`IndicatorStreamExt::with_indicator_set`].
+    #[must_use = "streams do nothing unless polled"]
+    pub struct WithIndicatorSet<S, T, O> {
+        #[pin]
+        inner: S,
+        set: IndicatorSet<T, O>,
+    }
+}
+
+impl<S, T, O> WithIndicatorSet<S, T, O> {
+    /// The wrapped indicator set.
+    pub fn set(&self) -> &IndicatorSet<T, O> {
+        &self.set
+    }
+
+    /// Consume the adapter, returning the inner stream and the set.
+    pub fn into_inner(self) -> (S, IndicatorSet<T, O>) {
+        (self.inner, self.set)
+    }
+}
+
+impl<S, T, O> Stream for WithIndicatorSet<S, T, O>
+where
+    S: Stream<Item = T>,
+    T: Clone,
+{
+    type Item = Result<(T, BTreeMap<String, Option<O>>), IndicatorError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let Some(item) = std::task::ready!(this.inner.poll_next(cx)) else {
+            return Poll::Ready(None);
+        };
+        Poll::Ready(Some(this.set.next(item.clone()).map(|out| (item, out))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Indicator adapters for any [`Stream`].
+pub trait IndicatorStreamExt: Stream {
+    /// Run `indicator` over the stream, yielding each item with the
+    /// indicator's output. Items consumed during warm-up are not yielded.
+    fn with_indicator<I, O>(self, indicator: I) -> WithIndicator<Self, I, O>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        I: Indicator<Self::Item, O>,
+    {
+        WithIndicator {
+            inner: self,
+            indicator,
+            _output: PhantomData,
+        }
+    }
+
+    /// Run every indicator of `set` over the stream, yielding each item
+    /// with the latest outputs (`None` while an indicator warms up).
+    fn with_indicator_set<O>(
+        self,
+        set: IndicatorSet<Self::Item, O>,
+    ) -> WithIndicatorSet<Self, Self::Item, O>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        WithIndicatorSet { inner: self, set }
+    }
+}
+
+impl<S: Stream + ?Sized> IndicatorStreamExt for S {}
+
+#[cfg(all(test, feature = "volatility"))]
+mod tests {
+    use super::*;
+    use crate::indicators::nan_policy::{NanGuard, NanPolicy};
+    use crate::indicators::trend::{Ema, Sma};
+    use crate::indicators::volatility::Atr;
+    use crate::indicators::Candle;
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    fn candles(n: usize) -> Vec<Candle> {
+        (0..n)
+            .map(|i| {
+                let c = 100.0 + i as f64;
+                Candle {
+                    timestamp: i as u64,
+                    open: c,
+                    high: c + 1.0,
+                    low: c - 1.0,
+                    close: c,
+                    volume: 1.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_batch_calculation() {
+        let data = candles(20);
+        let expected = Atr::new(5).unwrap().calculate(&data).unwrap();
+        let out: Vec<(Candle, f64)> = block_on(
+            stream::iter(data.clone())
+                .with_indicator(Atr::new(5).unwrap())
+                .map(Result::unwrap)
+                .collect(),
+        );
+        assert_eq!(out.len(), expected.len());
+        for ((candle, atr), want) in out.iter().zip(&expected) {
+            assert_eq!(atr, want);
+            assert!(candle.timestamp >= 4);
+        }
+        assert_eq!(out[0].0.timestamp, data[4].timestamp);
+    }
+
+    #[test]
+    fn errors_do_not_end_the_stream() {
+        let feed = stream::iter(vec![1.0, f64::NAN, 2.0]);
+        let guarded = NanGuard::new(Sma::new(1).unwrap(), NanPolicy::Error);
+        let out: Vec<_> = block_on(feed.with_indicator(guarded).collect());
+        assert_eq!(out.len(), 3);
+        assert!(out[1].is_err());
+        assert_eq!(*out[2].as_ref().unwrap(), (2.0, 2.0));
+    }
+
+    #[test]
+    fn drives_an_indicator_set() {
+        let mut set = IndicatorSet::<f64, f64>::new();
+        set.add("sma", Box::new(Sma::new(2).unwrap()))
+            .add("ema", Box::new(Ema::new(2).unwrap()));
+        let adapter = stream::iter(vec![1.0, 2.0, 3.0]).with_indicator_set(set);
+        assert_eq!(adapter.size_hint(), (3, Some(3)));
+        let out: Vec<_> = block_on(adapter.map(Result::unwrap).collect());
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[0].1["sma"], None);
+        assert_eq!(out[2].0, 3.0);
+        assert_eq!(out[2].1["sma"], Some(2.5));
+    }
+
+    #[test]
+    fn into_inner_keeps_indicator_state() {
+        let mut adapter = stream::iter(vec![1.0, 2.0, 3.0]).with_indicator(Sma::new(2).unwrap());
+        let first = block_on(adapter.next()).unwrap().unwrap();
+        assert_eq!(first, (2.0, 1.5));
+        let (_, mut sma) = adapter.into_inner();
+        assert_eq!(
+            Indicator::<f64, f64>::next(&mut sma, 4.0).unwrap(),
+            Some(3.0)
+        );
+    }
+}
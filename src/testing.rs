@@ -0,0 +1,284 @@
+//! Seeded synthetic OHLCV data
+//!
+//! [`CandleGenerator`] produces candle series from a few classic price
+//! processes — a random walk, geometric Brownian motion, a noisy trend and
+//! a mean-reverting (Ornstein–Uhlenbeck) process. The same seed always
+//! yields the same series, so strategies and indicators can be
+//! property-tested over many series deterministically.
+//!
+//! ```
+//! use rsta::testing::{CandleGenerator, Process};
+//!
+//! let candles = CandleGenerator::new(42)
+//!     .with_start_price(50.0)
+//!     .generate(Process::gbm(0.0005, 0.02), 250);
+//! assert_eq!(candles.len(), 250);
+//! assert!(candles.iter().all(|c| c.low <= c.open.min(c.close) && c.high >= c.open.max(c.close)));
+//!
+//! // Same seed, same data.
+//! let again = CandleGenerator::new(42)
+//!     .with_start_price(50.0)
+//!     .generate(Process::gbm(0.0005, 0.02), 250);
+//! assert_eq!(candles, again);
+//! ```
+//!
+//! Enable the `test-utils` feature to use this module outside the crate,
+//! typically from `[dev-dependencies]`.
+
+use crate::indicators::Candle;
+use crate::rng::Rng;
+
+/// Floor applied to generated closes so additive processes stay positive.
+const MIN_PRICE: f64 = 0.01;
+
+/// Price process driving the closes of a generated series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Process {
+    /// Additive random walk: each close moves by a normal step with
+    /// standard deviation `step` (in price units).
+    RandomWalk {
+        /// Standard deviation of a step.
+        step: f64,
+    },
+    /// Geometric Brownian motion: log returns are normal with mean
+    /// `drift - volatility² / 2` and standard deviation `volatility` per bar.
+    Gbm {
+        /// Expected return per bar.
+        drift: f64,
+        /// Standard deviation of log returns per bar.
+        volatility: f64,
+    },
+    /// Linear trend of `slope` price units per bar plus normal noise.
+    Trending {
+        /// Price change per bar; negative for a downtrend.
+        slope: f64,
+        /// Standard deviation of the noise per bar.
+        noise: f64,
+    },
+    /// Ornstein–Uhlenbeck process pulled towards `mean` at rate `speed`
+    /// (the fraction of the gap closed per bar).
+    MeanReverting {
+        /// Level the price reverts to.
+        mean: f64,
+        /// Fraction of the distance to `mean` closed each bar, in `0..=1`.
+        speed: f64,
+        /// Standard deviation of the shocks per bar.
+        volatility: f64,
+    },
+}
+
+impl Process {
+    /// [`Process::RandomWalk`] with the given step size.
+    pub fn random_walk(step: f64) -> Self {
+        Self::RandomWalk { step }
+    }
+
+    /// [`Process::Gbm`] with the given per-bar drift and volatility.
+    pub fn gbm(drift: f64, volatility: f64) -> Self {
+        Self::Gbm { drift, volatility }
+    }
+
+    /// [`Process::Trending`] with the given slope and noise.
+    pub fn trending(slope: f64, noise: f64) -> Self {
+        Self::Trending { slope, noise }
+    }
+
+    /// [`Process::MeanReverting`] around `mean`.
+    pub fn mean_reverting(mean: f64, speed: f64, volatility: f64) -> Self {
+        Self::MeanReverting {
+            mean,
+            speed,
+            volatility,
+        }
+    }
+}
+
+/// Seeded generator of synthetic candles. See the [module docs](self).
+///
+/// Each candle opens at the previous close; its high and low extend past
+/// the body by a random wick, and volume varies randomly around the base
+/// volume. Consecutive [`generate`](Self::generate) calls continue the
+/// same series, timestamps and prices included.
+#[derive(Debug, Clone)]
+pub struct CandleGenerator {
+    rng: Rng,
+    price: f64,
+    timestamp: u64,
+    interval: u64,
+    volume: f64,
+    wick: f64,
+}
+
+impl CandleGenerator {
+    /// Create a generator from a seed
+    ///
+    /// Defaults: start price 100, timestamps from 0 in steps of 60, base
+    /// volume 1000, wicks of about 0.5% of the price.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            price: 100.0,
+            timestamp: 0,
+            interval: 60,
+            volume: 1_000.0,
+            wick: 0.005,
+        }
+    }
+
+    /// Set the price the series starts from.
+    pub fn with_start_price(mut self, price: f64) -> Self {
+        self.price = price.max(MIN_PRICE);
+        self
+    }
+
+    /// Set the timestamp of the first candle.
+    pub fn with_start_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Set the spacing between candle timestamps.
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the volume candles vary around.
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = volume.max(0.0);
+        self
+    }
+
+    /// Set the typical wick length as a fraction of the price (`0.0` for
+    /// candles whose high and low are the body).
+    pub fn with_wick(mut self, wick: f64) -> Self {
+        self.wick = wick.max(0.0);
+        self
+    }
+
+    /// Generate `n` candles following `process`.
+    pub fn generate(&mut self, process: Process, n: usize) -> Vec<Candle> {
+        (0..n).map(|_| self.next_candle(process)).collect()
+    }
+
+    /// Generate `n` closing prices following `process`, for indicators
+    /// that take plain prices.
+    pub fn closes(&mut self, process: Process, n: usize) -> Vec<f64> {
+        (0..n).map(|_| self.next_candle(process).close).collect()
+    }
+
+    fn next_close(&mut self, process: Process) -> f64 {
+        let z = self.rng.normal();
+        let close = match process {
+            Process::RandomWalk { step } => self.price + step * z,
+            Process::Gbm { drift, volatility } => {
+                self.price * (drift - volatility * volatility / 2.0 + volatility * z).exp()
+            }
+            Process::Trending { slope, noise } => self.price + slope + noise * z,
+            Process::MeanReverting {
+                mean,
+                speed,
+                volatility,
+            } => self.price + speed * (mean - self.price) + volatility * z,
+        };
+        close.max(MIN_PRICE)
+    }
+
+    fn next_candle(&mut self, process: Process) -> Candle {
+        let open = self.price;
+        let close = self.next_close(process);
+        let high = open.max(close) * (1.0 + self.wick * self.rng.normal().abs());
+        let low = open.min(close) * (1.0 - self.wick * self.rng.normal().abs()).max(0.0);
+        let volume = self.volume * (1.0 + 0.3 * self.rng.normal()).max(0.05);
+        let candle = Candle {
+            timestamp: self.timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        };
+        self.price = close;
+        self.timestamp += self.interval;
+        candle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROCESSES: [Process; 4] = [
+        Process::RandomWalk { step: 1.0 },
+        Process::Gbm {
+            drift: 0.0,
+            volatility: 0.02,
+        },
+        Process::Trending {
+            slope: 0.5,
+            noise: 0.5,
+        },
+        Process::MeanReverting {
+            mean: 100.0,
+            speed: 0.1,
+            volatility: 1.0,
+        },
+    ];
+
+    #[test]
+    fn test_deterministic_and_valid_ohlc() {
+        for process in PROCESSES {
+            let a = CandleGenerator::new(7).generate(process, 500);
+            assert_eq!(a, CandleGenerator::new(7).generate(process, 500));
+            assert_ne!(a, CandleGenerator::new(8).generate(process, 500));
+
+            for (i, c) in a.iter().enumerate() {
+                assert_eq!(c.timestamp, 60 * i as u64);
+                assert!(c.low > 0.0 && c.low <= c.open.min(c.close), "{c:?}");
+                assert!(c.high >= c.open.max(c.close), "{c:?}");
+                assert!(c.volume > 0.0);
+            }
+            for pair in a.windows(2) {
+                assert_eq!(pair[1].open, pair[0].close);
+            }
+        }
+    }
+
+    #[test]
+    fn test_processes_behave_as_named() {
+        let trend = CandleGenerator::new(1).closes(Process::trending(0.5, 0.5), 400);
+        assert!(trend[399] - trend[0] > 150.0);
+
+        let reverting = CandleGenerator::new(1)
+            .with_start_price(150.0)
+            .closes(Process::mean_reverting(100.0, 0.2, 0.5), 400);
+        let tail_mean = reverting[200..].iter().sum::<f64>() / 200.0;
+        assert!((tail_mean - 100.0).abs() < 2.0, "tail mean {tail_mean}");
+
+        let gbm = CandleGenerator::new(1).closes(Process::gbm(0.0, 0.5), 2_000);
+        assert!(gbm.iter().all(|&p| p >= MIN_PRICE));
+    }
+
+    #[test]
+    fn test_generate_continues_the_series() {
+        let process = Process::random_walk(1.0);
+        let whole = CandleGenerator::new(3)
+            .with_start_timestamp(1_000)
+            .with_interval(10)
+            .generate(process, 20);
+
+        let mut generator = CandleGenerator::new(3)
+            .with_start_timestamp(1_000)
+            .with_interval(10);
+        let mut parts = generator.generate(process, 12);
+        parts.extend(generator.generate(process, 8));
+        assert_eq!(parts, whole);
+        assert_eq!(whole[19].timestamp, 1_190);
+
+        let flat = CandleGenerator::new(3).with_wick(0.0).generate(process, 5);
+        assert!(flat
+            .iter()
+            .all(|c| c.high == c.open.max(c.close) && c.low == c.open.min(c.close)));
+    }
+}